@@ -274,6 +274,9 @@ pub struct SchemaResolver<'a> {
     pub reader_to_resolved_names: HashMap<usize, usize>,
     pub reader_fullnames: HashMap<usize, &'a FullName>,
     pub reader_schema: &'a Schema,
+    /// Whether reader fields that are missing from the writer, have no
+    /// default, and are nullable resolve to null.
+    pub null_defaults: bool,
 }
 
 impl<'a> SchemaResolver<'a> {
@@ -304,19 +307,22 @@ impl<'a> SchemaResolver<'a> {
                 for (r_index, rf) in r_fields.iter().enumerate() {
                     match w_lookup.get(&rf.name) {
                         None => {
-                            let default_field = match &rf.default {
-                                Some(v) => ResolvedDefaultValueField {
-                                    name: rf.name.clone(),
-                                    doc: rf.doc.clone(),
-                                    default: reader
-                                        .top_node_or_named()
-                                        .step(&rf.schema)
-                                        .lookup()
-                                        .json_to_value(v)?,
-                                    order: rf.order.clone(),
-                                    position: r_index,
+                            let r_node = reader.top_node_or_named().step(&rf.schema).lookup();
+                            let null_variant = match r_node.inner {
+                                SchemaPiece::Union(us) if self.null_defaults => us
+                                    .match_piece(&SchemaPiece::Null)
+                                    .map(|(idx, _)| (idx, us.schemas.len())),
+                                _ => None,
+                            };
+                            let default = match (&rf.default, null_variant) {
+                                (Some(v), _) => r_node.json_to_value(v)?,
+                                (None, Some((idx, n_variants))) => Value::Union {
+                                    index: idx,
+                                    inner: Box::new(Value::Null),
+                                    n_variants,
+                                    null_variant: Some(idx),
                                 },
-                                None => {
+                                (None, None) => {
                                     return Err(SchemaResolutionError::new(format!(
                                         "Reader field `{}` not found in writer, and has no default",
                                         rf.name
@@ -324,6 +330,13 @@ impl<'a> SchemaResolver<'a> {
                                     .into())
                                 }
                             };
+                            let default_field = ResolvedDefaultValueField {
+                                name: rf.name.clone(),
+                                doc: rf.doc.clone(),
+                                default,
+                                order: rf.order.clone(),
+                                position: r_index,
+                            };
                             defaults.push(default_field);
                         }
                         Some(w_index) => {
@@ -32,6 +32,24 @@ use crate::util::MapHelper;
 pub fn resolve_schemas(
     writer_schema: &Schema,
     reader_schema: &Schema,
+) -> Result<Schema, AvroError> {
+    resolve_schemas_inner(writer_schema, reader_schema, false)
+}
+
+/// Like [`resolve_schemas`], but reader fields that are missing from the
+/// writer and have no default value are resolved to null, rather than
+/// causing an error, if their type is a union that includes null.
+pub fn resolve_schemas_with_null_defaults(
+    writer_schema: &Schema,
+    reader_schema: &Schema,
+) -> Result<Schema, AvroError> {
+    resolve_schemas_inner(writer_schema, reader_schema, true)
+}
+
+fn resolve_schemas_inner(
+    writer_schema: &Schema,
+    reader_schema: &Schema,
+    null_defaults: bool,
 ) -> Result<Schema, AvroError> {
     let r_indices = reader_schema.indices.clone();
     let (reader_to_writer_names, writer_to_reader_names): (HashMap<_, _>, HashMap<_, _>) =
@@ -57,6 +75,7 @@ pub fn resolve_schemas(
         reader_to_resolved_names: Default::default(),
         reader_fullnames,
         reader_schema,
+        null_defaults,
     };
     let writer_node = writer_schema.top_node_or_named();
     let reader_node = reader_schema.top_node_or_named();
@@ -1228,7 +1247,11 @@ impl SchemaParser {
         if let Some("decimal") = logical_type {
             match Self::parse_decimal(complex) {
                 Ok((precision, scale)) => {
-                    let max = ((2_usize.pow((8 * size - 1) as u32) - 1) as f64).log10() as usize;
+                    // The largest number of base-10 digits that fit into a
+                    // signed `size`-byte integer, i.e., floor(log10(2^(8 * size - 1) - 1)).
+                    // This is computed in floating point, because the power
+                    // of two overflows any integer type for large sizes.
+                    let max = ((8 * size - 1) as f64 * 2_f64.log10()).floor() as usize;
                     if precision > max {
                         warn!("Decimal precision {} requires more than {} bytes of space, parsing as fixed", precision, size);
                     } else {
@@ -2277,6 +2300,35 @@ mod tests {
         };
         check_schema(schema, expected);
 
+        // Sixteen bytes is large enough for 38 digits of precision, which
+        // must not overflow while computing the maximum precision.
+        let schema = r#"{
+                "type": "fixed",
+                "name": "dec",
+                "size": 16,
+                "logicalType": "decimal",
+                "precision": 38,
+                "scale": 38
+            }"#;
+        let expected = SchemaPiece::Decimal {
+            precision: 38,
+            scale: 38,
+            fixed_size: Some(16),
+        };
+        check_schema(schema, expected);
+
+        // Eight bytes is not large enough for 19 digits of precision.
+        let schema = r#"{
+                "type": "fixed",
+                "name": "dec",
+                "size": 8,
+                "logicalType": "decimal",
+                "precision": 19,
+                "scale": 0
+            }"#;
+        let expected = SchemaPiece::Fixed { size: 8 };
+        check_schema(schema, expected);
+
         let schema = r#"{
                 "type": "bytes",
                 "logicalType": "decimal",
@@ -9,7 +9,7 @@ use std::str::FromStr;
 
 use chrono::{NaiveDate, NaiveDateTime};
 use lazy_static::lazy_static;
-use mz_avro::schema::resolve_schemas;
+use mz_avro::schema::{resolve_schemas, resolve_schemas_with_null_defaults};
 use mz_avro::{
     error::Error as AvroError,
     from_avro_datum, to_avro_datum,
@@ -235,6 +235,64 @@ fn test_no_default_value() -> Result<(), String> {
     }
 }
 
+#[test]
+fn test_null_defaults() {
+    let reader_schema = Schema::from_str(
+        r#"{
+            "type": "record",
+            "name": "Test",
+            "fields": [
+                {"name": "A", "type": "int"},
+                {"name": "H", "type": ["int", "null"]},
+                {"name": "I", "type": "int"}
+            ]
+        }"#,
+    )
+    .unwrap();
+    // Non-nullable fields without defaults still can't be resolved.
+    assert!(resolve_schemas_with_null_defaults(&LONG_RECORD_SCHEMA, &reader_schema).is_err());
+
+    let reader_schema = Schema::from_str(
+        r#"{
+            "type": "record",
+            "name": "Test",
+            "fields": [
+                {"name": "A", "type": "int"},
+                {"name": "H", "type": ["int", "null"]},
+                {"name": "I", "type": ["null", "int"], "default": null}
+            ]
+        }"#,
+    )
+    .unwrap();
+    assert!(resolve_schemas(&LONG_RECORD_SCHEMA, &reader_schema).is_err());
+    let resolved_schema =
+        resolve_schemas_with_null_defaults(&LONG_RECORD_SCHEMA, &reader_schema).unwrap();
+    let encoded = to_avro_datum(&LONG_RECORD_SCHEMA, LONG_RECORD_DATUM.clone()).unwrap();
+    let datum_read = from_avro_datum(&resolved_schema, &mut Cursor::new(encoded)).unwrap();
+    let datum_to_read = Value::Record(vec![
+        ("A".to_string(), Value::Int(1)),
+        (
+            "H".to_string(),
+            Value::Union {
+                index: 1,
+                inner: Box::new(Value::Null),
+                n_variants: 2,
+                null_variant: Some(1),
+            },
+        ),
+        (
+            "I".to_string(),
+            Value::Union {
+                index: 0,
+                inner: Box::new(Value::Null),
+                n_variants: 2,
+                null_variant: Some(0),
+            },
+        ),
+    ]);
+    assert_eq!(datum_to_read, datum_read);
+}
+
 #[test]
 fn test_union_default() {
     let reader_schema = Schema::from_str(
@@ -41,4 +41,19 @@ impl BuildInfo {
     pub fn human_version(&self) -> String {
         format!("v{} ({})", self.version, &self.sha[..9])
     }
+
+    /// Encodes the version number as an integer, like PostgreSQL's
+    /// `server_version_num`.
+    ///
+    /// The encoding is `major * 10000 + minor * 100 + patch`, so that versions
+    /// compare in the same order as their encodings. Pre-release and build
+    /// metadata are ignored, and components that cannot be parsed, as in
+    /// [`DUMMY_BUILD_INFO`], are treated as zero.
+    pub fn version_num(&self) -> i32 {
+        let version = self.version.split(|c| c == '-' || c == '+').next().unwrap();
+        let mut components = version.split('.').map(|c| c.parse::<i32>().unwrap_or(0));
+        let mut next = || components.next().unwrap_or(0);
+        let (major, minor, patch) = (next(), next(), next());
+        major * 10000 + minor * 100 + patch
+    }
 }
@@ -9,25 +9,45 @@
 
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
 
 use reqwest::{Method, Url};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde_json::json;
+use tokio::time;
 
 use crate::config::Auth;
 
+/// The largest response that a [`Client`] accepts from the schema registry, in
+/// bytes.
+///
+/// Larger responses, which no reasonable schema produces, are rejected rather
+/// than buffered in memory.
+pub const MAX_RESPONSE_BYTES: usize = 4 << 20;
+
 /// An API client for a Confluent-compatible schema registry.
 #[derive(Debug)]
 pub struct Client {
     inner: reqwest::Client,
     url: Url,
     auth: Option<Auth>,
+    max_retries: u32,
 }
 
 impl Client {
-    pub(crate) fn new(inner: reqwest::Client, url: Url, auth: Option<Auth>) -> Self {
-        Client { inner, url, auth }
+    pub(crate) fn new(
+        inner: reqwest::Client,
+        url: Url,
+        auth: Option<Auth>,
+        max_retries: u32,
+    ) -> Self {
+        Client {
+            inner,
+            url,
+            auth,
+            max_retries,
+        }
     }
 
     fn make_request(&self, method: Method, path: impl AsRef<str>) -> reqwest::RequestBuilder {
@@ -44,7 +64,7 @@ impl Client {
     /// Gets the schema with the associated ID.
     pub async fn get_schema_by_id(&self, id: i32) -> Result<Schema, GetByIdError> {
         let req = self.make_request(Method::GET, format!("/schemas/ids/{}", id));
-        let res: GetByIdResponse = send_request(req).await?;
+        let res: GetByIdResponse = self.send_request(req).await?;
         Ok(Schema {
             id,
             raw: res.schema,
@@ -57,7 +77,7 @@ impl Client {
             Method::GET,
             format!("/subjects/{}/versions/latest", subject),
         );
-        let res: GetBySubjectResponse = send_request(req).await?;
+        let res: GetBySubjectResponse = self.send_request(req).await?;
         Ok(Schema {
             id: res.id,
             raw: res.schema,
@@ -74,14 +94,14 @@ impl Client {
         let req = self
             .make_request(Method::POST, format!("/subjects/{}/versions", subject))
             .json(&json!({ "schema": schema }));
-        let res: PublishResponse = send_request(req).await?;
+        let res: PublishResponse = self.send_request(req).await?;
         Ok(res.id)
     }
 
     /// Lists the names of all subjects that the schema registry is aware of.
     pub async fn list_subjects(&self) -> Result<Vec<String>, ListError> {
         let req = self.make_request(Method::GET, "/subjects");
-        Ok(send_request(req).await?)
+        Ok(self.send_request(req).await?)
     }
 
     /// Deletes all schema versions associated with the specified subject.
@@ -92,21 +112,47 @@ impl Client {
     /// to be reused.
     pub async fn delete_subject(&self, subject: &str) -> Result<(), DeleteError> {
         let req = self.make_request(Method::DELETE, format!("/subjects/{}", subject));
-        let _res: Vec<i32> = send_request(req).await?;
+        let _res: Vec<i32> = self.send_request(req).await?;
         Ok(())
     }
+
+    /// Sends `req`, retrying it with exponential backoff if it fails with a
+    /// transport or server error, up to the configured number of retries.
+    async fn send_request<T>(&self, req: reqwest::RequestBuilder) -> Result<T, UnhandledError>
+    where
+        T: DeserializeOwned,
+    {
+        let mut backoff = Duration::from_millis(100);
+        let mut retries = 0;
+        loop {
+            // Request bodies are always held in memory, so they can be cloned.
+            let attempt = req.try_clone().expect("request body is not a stream");
+            match send_request_once(attempt).await {
+                Err(err) if err.is_retryable() && retries < self.max_retries => {
+                    retries += 1;
+                    time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(Duration::from_secs(10));
+                }
+                res => return res,
+            }
+        }
+    }
 }
 
-async fn send_request<T>(req: reqwest::RequestBuilder) -> Result<T, UnhandledError>
+async fn send_request_once<T>(req: reqwest::RequestBuilder) -> Result<T, UnhandledError>
 where
     T: DeserializeOwned,
 {
-    let res = req.send().await?;
+    let mut res = req.send().await?;
     let status = res.status();
+    let body = read_body(&mut res).await?;
     if status.is_success() {
-        Ok(res.json().await?)
+        serde_json::from_slice(&body).map_err(|e| UnhandledError::Api {
+            code: i32::from(status.as_u16()),
+            message: format!("unable to decode response: {}", e),
+        })
     } else {
-        match res.json::<ErrorResponse>().await {
+        match serde_json::from_slice::<ErrorResponse>(&body) {
             Ok(err_res) => Err(UnhandledError::Api {
                 code: err_res.error_code,
                 message: err_res.message,
@@ -119,6 +165,24 @@ where
     }
 }
 
+/// Reads the body of `res`, giving up as soon as it exceeds
+/// [`MAX_RESPONSE_BYTES`].
+async fn read_body(res: &mut reqwest::Response) -> Result<Vec<u8>, UnhandledError> {
+    if let Some(len) = res.content_length() {
+        if len > MAX_RESPONSE_BYTES as u64 {
+            return Err(UnhandledError::ResponseTooLarge);
+        }
+    }
+    let mut body = vec![];
+    while let Some(chunk) = res.chunk().await? {
+        if body.len() + chunk.len() > MAX_RESPONSE_BYTES {
+            return Err(UnhandledError::ResponseTooLarge);
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
 /// A schema stored by a schema registry.
 #[derive(Debug, Eq, PartialEq)]
 pub struct Schema {
@@ -142,12 +206,15 @@ pub enum GetByIdError {
     Transport(reqwest::Error),
     /// An internal server error occured.
     Server { code: i32, message: String },
+    /// The response was larger than [`MAX_RESPONSE_BYTES`].
+    ResponseTooLarge,
 }
 
 impl From<UnhandledError> for GetByIdError {
     fn from(err: UnhandledError) -> GetByIdError {
         match err {
             UnhandledError::Transport(err) => GetByIdError::Transport(err),
+            UnhandledError::ResponseTooLarge => GetByIdError::ResponseTooLarge,
             UnhandledError::Api { code, message } => match code {
                 40403 => GetByIdError::SchemaNotFound,
                 _ => GetByIdError::Server { code, message },
@@ -159,7 +226,9 @@ impl From<UnhandledError> for GetByIdError {
 impl Error for GetByIdError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            GetByIdError::SchemaNotFound | GetByIdError::Server { .. } => None,
+            GetByIdError::SchemaNotFound
+            | GetByIdError::Server { .. }
+            | GetByIdError::ResponseTooLarge => None,
             GetByIdError::Transport(err) => Some(err),
         }
     }
@@ -170,6 +239,11 @@ impl fmt::Display for GetByIdError {
         match self {
             GetByIdError::SchemaNotFound => write!(f, "schema not found"),
             GetByIdError::Transport(err) => write!(f, "transport: {}", err),
+            GetByIdError::ResponseTooLarge => write!(
+                f,
+                "response exceeds the limit of {} bytes",
+                MAX_RESPONSE_BYTES
+            ),
             GetByIdError::Server { code, message } => {
                 write!(f, "server error {}: {}", code, message)
             }
@@ -192,12 +266,15 @@ pub enum GetBySubjectError {
     Transport(reqwest::Error),
     /// An internal server error occured.
     Server { code: i32, message: String },
+    /// The response was larger than [`MAX_RESPONSE_BYTES`].
+    ResponseTooLarge,
 }
 
 impl From<UnhandledError> for GetBySubjectError {
     fn from(err: UnhandledError) -> GetBySubjectError {
         match err {
             UnhandledError::Transport(err) => GetBySubjectError::Transport(err),
+            UnhandledError::ResponseTooLarge => GetBySubjectError::ResponseTooLarge,
             UnhandledError::Api { code, message } => match code {
                 40401 => GetBySubjectError::SubjectNotFound,
                 _ => GetBySubjectError::Server { code, message },
@@ -209,7 +286,9 @@ impl From<UnhandledError> for GetBySubjectError {
 impl Error for GetBySubjectError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            GetBySubjectError::SubjectNotFound | GetBySubjectError::Server { .. } => None,
+            GetBySubjectError::SubjectNotFound
+            | GetBySubjectError::Server { .. }
+            | GetBySubjectError::ResponseTooLarge => None,
             GetBySubjectError::Transport(err) => Some(err),
         }
     }
@@ -220,6 +299,11 @@ impl fmt::Display for GetBySubjectError {
         match self {
             GetBySubjectError::SubjectNotFound => write!(f, "subject not found"),
             GetBySubjectError::Transport(err) => write!(f, "transport: {}", err),
+            GetBySubjectError::ResponseTooLarge => write!(
+                f,
+                "response exceeds the limit of {} bytes",
+                MAX_RESPONSE_BYTES
+            ),
             GetBySubjectError::Server { code, message } => {
                 write!(f, "server error {}: {}", code, message)
             }
@@ -245,12 +329,15 @@ pub enum PublishError {
     Transport(reqwest::Error),
     /// An internal server error occured.
     Server { code: i32, message: String },
+    /// The response was larger than [`MAX_RESPONSE_BYTES`].
+    ResponseTooLarge,
 }
 
 impl From<UnhandledError> for PublishError {
     fn from(err: UnhandledError) -> PublishError {
         match err {
             UnhandledError::Transport(err) => PublishError::Transport(err),
+            UnhandledError::ResponseTooLarge => PublishError::ResponseTooLarge,
             UnhandledError::Api { code, message } => match code {
                 409 => PublishError::IncompatibleSchema,
                 42201 => PublishError::InvalidSchema,
@@ -265,7 +352,8 @@ impl Error for PublishError {
         match self {
             PublishError::IncompatibleSchema
             | PublishError::InvalidSchema
-            | PublishError::Server { .. } => None,
+            | PublishError::Server { .. }
+            | PublishError::ResponseTooLarge => None,
             PublishError::Transport(err) => Some(err),
         }
     }
@@ -282,6 +370,11 @@ impl fmt::Display for PublishError {
             ),
             PublishError::InvalidSchema => write!(f, "input schema is an invalid avro schema"),
             PublishError::Transport(err) => write!(f, "transport: {}", err),
+            PublishError::ResponseTooLarge => write!(
+                f,
+                "response exceeds the limit of {} bytes",
+                MAX_RESPONSE_BYTES
+            ),
             PublishError::Server { code, message } => {
                 write!(f, "server error {}: {}", code, message)
             }
@@ -296,12 +389,15 @@ pub enum ListError {
     Transport(reqwest::Error),
     /// An internal server error occured.
     Server { code: i32, message: String },
+    /// The response was larger than [`MAX_RESPONSE_BYTES`].
+    ResponseTooLarge,
 }
 
 impl From<UnhandledError> for ListError {
     fn from(err: UnhandledError) -> ListError {
         match err {
             UnhandledError::Transport(err) => ListError::Transport(err),
+            UnhandledError::ResponseTooLarge => ListError::ResponseTooLarge,
             UnhandledError::Api { code, message } => ListError::Server { code, message },
         }
     }
@@ -310,7 +406,7 @@ impl From<UnhandledError> for ListError {
 impl Error for ListError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            ListError::Server { .. } => None,
+            ListError::Server { .. } | ListError::ResponseTooLarge => None,
             ListError::Transport(err) => Some(err),
         }
     }
@@ -320,6 +416,11 @@ impl fmt::Display for ListError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             ListError::Transport(err) => write!(f, "transport: {}", err),
+            ListError::ResponseTooLarge => write!(
+                f,
+                "response exceeds the limit of {} bytes",
+                MAX_RESPONSE_BYTES
+            ),
             ListError::Server { code, message } => write!(f, "server error {}: {}", code, message),
         }
     }
@@ -334,12 +435,15 @@ pub enum DeleteError {
     Transport(reqwest::Error),
     /// An internal server error occured.
     Server { code: i32, message: String },
+    /// The response was larger than [`MAX_RESPONSE_BYTES`].
+    ResponseTooLarge,
 }
 
 impl From<UnhandledError> for DeleteError {
     fn from(err: UnhandledError) -> DeleteError {
         match err {
             UnhandledError::Transport(err) => DeleteError::Transport(err),
+            UnhandledError::ResponseTooLarge => DeleteError::ResponseTooLarge,
             UnhandledError::Api { code, message } => match code {
                 40401 => DeleteError::SubjectNotFound,
                 _ => DeleteError::Server { code, message },
@@ -351,7 +455,9 @@ impl From<UnhandledError> for DeleteError {
 impl Error for DeleteError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         match self {
-            DeleteError::SubjectNotFound | DeleteError::Server { .. } => None,
+            DeleteError::SubjectNotFound
+            | DeleteError::Server { .. }
+            | DeleteError::ResponseTooLarge => None,
             DeleteError::Transport(err) => Some(err),
         }
     }
@@ -362,6 +468,11 @@ impl fmt::Display for DeleteError {
         match self {
             DeleteError::SubjectNotFound => write!(f, "subject not found"),
             DeleteError::Transport(err) => write!(f, "transport: {}", err),
+            DeleteError::ResponseTooLarge => write!(
+                f,
+                "response exceeds the limit of {} bytes",
+                MAX_RESPONSE_BYTES
+            ),
             DeleteError::Server { code, message } => {
                 write!(f, "server error {}: {}", code, message)
             }
@@ -379,6 +490,23 @@ struct ErrorResponse {
 enum UnhandledError {
     Transport(reqwest::Error),
     Api { code: i32, message: String },
+    ResponseTooLarge,
+}
+
+impl UnhandledError {
+    /// Reports whether the request that failed with this error might succeed
+    /// if it were sent again.
+    fn is_retryable(&self) -> bool {
+        match self {
+            UnhandledError::Transport(_) => true,
+            // Both HTTP status codes and the schema registry's own error codes
+            // put server errors in the 5xx range.
+            UnhandledError::Api { code, .. } => {
+                (500..600).contains(code) || (50000..60000).contains(code)
+            }
+            UnhandledError::ResponseTooLarge => false,
+        }
+    }
 }
 
 impl From<reqwest::Error> for UnhandledError {
@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::time::Duration;
+
 use reqwest::Url;
 use serde::{Deserialize, Serialize};
 
@@ -26,6 +28,9 @@ pub struct ClientConfig {
     root_certs: Vec<Certificate>,
     identity: Option<Identity>,
     auth: Option<Auth>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    max_retries: u32,
 }
 
 impl ClientConfig {
@@ -37,6 +42,9 @@ impl ClientConfig {
             root_certs: Vec::new(),
             identity: None,
             auth: None,
+            connect_timeout: None,
+            timeout: None,
+            max_retries: 0,
         }
     }
 
@@ -61,6 +69,34 @@ impl ClientConfig {
         self
     }
 
+    /// Sets how long to wait for a connection to the schema registry to be
+    /// established.
+    ///
+    /// There is no timeout by default.
+    pub fn connect_timeout(mut self, timeout: Duration) -> ClientConfig {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how long to wait for each request to complete, from when it is
+    /// sent until its response has been read in full.
+    ///
+    /// There is no timeout by default.
+    pub fn timeout(mut self, timeout: Duration) -> ClientConfig {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets how many times a request that fails because of a transport error
+    /// or a server error is retried, with exponential backoff, before the
+    /// error is reported.
+    ///
+    /// Requests are not retried by default.
+    pub fn max_retries(mut self, max_retries: u32) -> ClientConfig {
+        self.max_retries = max_retries;
+        self
+    }
+
     /// Builds the [`Client`].
     pub fn build(self) -> Client {
         let mut builder = reqwest::Client::builder();
@@ -73,11 +109,19 @@ impl ClientConfig {
             builder = builder.identity(ident.into());
         }
 
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+
         let inner = builder
             .redirect(reqwest::redirect::Policy::none())
             .build()
             .unwrap();
 
-        Client::new(inner, self.url, self.auth)
+        Client::new(inner, self.url, self.auth, self.max_retries)
     }
 }
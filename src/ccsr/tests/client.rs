@@ -8,6 +8,12 @@
 // by the Apache License, Version 2.0.
 
 use std::env;
+use std::future::Future;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use futures::future;
 
 use hyper::server::conn::AddrIncoming;
 use hyper::service;
@@ -16,7 +22,9 @@ use hyper::StatusCode;
 use hyper::{Body, Response};
 use lazy_static::lazy_static;
 
-use ccsr::{Client, DeleteError, GetByIdError, GetBySubjectError, PublishError};
+use ccsr::{
+    Client, DeleteError, GetByIdError, GetBySubjectError, PublishError, MAX_RESPONSE_BYTES,
+};
 
 lazy_static! {
     pub static ref SCHEMA_REGISTRY_URL: reqwest::Url = match env::var("SCHEMA_REGISTRY_URL") {
@@ -219,31 +227,177 @@ async fn test_server_errors() -> Result<(), anyhow::Error> {
     Ok(())
 }
 
-fn start_server(status_code: StatusCode, body: &'static str) -> Client {
-    let addr = {
-        let incoming = AddrIncoming::bind(&([127, 0, 0, 1], 0).into()).unwrap();
-        let addr = incoming.local_addr();
-        let server =
-            Server::builder(incoming).serve(service::make_service_fn(move |_conn| async move {
-                Ok::<_, hyper::Error>(service::service_fn(move |_req| async move {
+#[tokio::test]
+async fn test_stalled_server() -> Result<(), anyhow::Error> {
+    // A schema registry that accepts requests but never responds to them
+    // should cause each attempt to time out, rather than hang the client.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let url = start_mock_server({
+        let requests = Arc::clone(&requests);
+        move || {
+            requests.fetch_add(1, Ordering::SeqCst);
+            future::pending()
+        }
+    });
+    let client = ccsr::ClientConfig::new(url)
+        .connect_timeout(Duration::from_millis(100))
+        .timeout(Duration::from_millis(100))
+        .max_retries(2)
+        .build();
+    match client.get_schema_by_subject("foo").await {
+        Err(GetBySubjectError::Transport(err)) if err.is_timeout() => (),
+        res => panic!(
+            "expected timed out GetBySubjectError::Transport, got {:?}",
+            res
+        ),
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 3);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_retries() -> Result<(), anyhow::Error> {
+    // A schema registry that fails the first two requests it receives, as if
+    // it were temporarily overloaded.
+    let start_flaky_server = || {
+        let requests = Arc::new(AtomicUsize::new(0));
+        start_mock_server(move || {
+            let n = requests.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if n < 2 {
                     Response::builder()
-                        .status(status_code)
-                        .body(Body::from(body))
-                }))
-            }));
-        tokio::spawn(async {
-            match server.await {
-                Ok(()) => (),
-                Err(err) => eprintln!("server error: {}", err),
+                        .status(StatusCode::SERVICE_UNAVAILABLE)
+                        .body(Body::from("unavailable"))
+                        .unwrap()
+                } else {
+                    Response::new(Body::from(r#"{ "id": 1, "schema": "\"int\"" }"#))
+                }
             }
-        });
-        addr
+        })
     };
 
-    let url: reqwest::Url = format!("http://{}", addr).parse().unwrap();
+    let client = ccsr::ClientConfig::new(start_flaky_server())
+        .max_retries(2)
+        .build();
+    let res = client.get_schema_by_subject("foo").await?;
+    assert_eq!(res.id, 1);
+    assert_eq!(res.raw, "\"int\"");
+
+    // Without enough retries, the last failure is reported.
+    let client = ccsr::ClientConfig::new(start_flaky_server())
+        .max_retries(1)
+        .build();
+    match client.get_schema_by_subject("foo").await {
+        Err(GetBySubjectError::Server { code: 503, .. }) => (),
+        res => panic!("expected GetBySubjectError::Server, got {:?}", res),
+    }
+
+    // Errors that would recur are not retried.
+    let requests = Arc::new(AtomicUsize::new(0));
+    let url = start_mock_server({
+        let requests = Arc::clone(&requests);
+        move || {
+            requests.fetch_add(1, Ordering::SeqCst);
+            async {
+                Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Body::from(
+                        r#"{ "error_code": 40401, "message": "Subject not found." }"#,
+                    ))
+                    .unwrap()
+            }
+        }
+    });
+    let client = ccsr::ClientConfig::new(url).max_retries(2).build();
+    match client.get_schema_by_subject("foo").await {
+        Err(GetBySubjectError::SubjectNotFound) => (),
+        res => panic!("expected GetBySubjectError::SubjectNotFound, got {:?}", res),
+    }
+    assert_eq!(requests.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_oversized_response() -> Result<(), anyhow::Error> {
+    // A schema document that is larger than the limit is rejected, whether
+    // its size is announced up front...
+    let url = start_mock_server(|| async {
+        let schema = format!("\"{}\"", "a".repeat(MAX_RESPONSE_BYTES));
+        let body = serde_json::json!({ "id": 1, "schema": schema }).to_string();
+        Response::new(Body::from(body))
+    });
+    let client = ccsr::ClientConfig::new(url).max_retries(2).build();
+    match client.get_schema_by_subject("foo").await {
+        Err(GetBySubjectError::ResponseTooLarge) => (),
+        res => panic!(
+            "expected GetBySubjectError::ResponseTooLarge, got {:?}",
+            res
+        ),
+    }
+    match client.get_schema_by_id(1).await {
+        Err(GetByIdError::ResponseTooLarge) => (),
+        res => panic!("expected GetByIdError::ResponseTooLarge, got {:?}", res),
+    }
+
+    // ...or only discovered as it is streamed.
+    let url = start_mock_server(|| async {
+        let (mut tx, body) = Body::channel();
+        tokio::spawn(async move {
+            let chunk = vec![b' '; 1 << 20];
+            while tx.send_data(chunk.clone().into()).await.is_ok() {}
+        });
+        Response::new(body)
+    });
+    let client = ccsr::ClientConfig::new(url).build();
+    match client.get_schema_by_subject("foo").await {
+        Err(GetBySubjectError::ResponseTooLarge) => (),
+        res => panic!(
+            "expected GetBySubjectError::ResponseTooLarge, got {:?}",
+            res
+        ),
+    }
+
+    Ok(())
+}
+
+fn start_server(status_code: StatusCode, body: &'static str) -> Client {
+    let url = start_mock_server(move || async move {
+        Response::builder()
+            .status(status_code)
+            .body(Body::from(body))
+            .unwrap()
+    });
     ccsr::ClientConfig::new(url).build()
 }
 
+/// Starts a schema registry that responds to every request with the response
+/// that `handler` produces.
+fn start_mock_server<F, Fut>(handler: F) -> reqwest::Url
+where
+    F: Fn() -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Response<Body>> + Send + 'static,
+{
+    let incoming = AddrIncoming::bind(&([127, 0, 0, 1], 0).into()).unwrap();
+    let addr = incoming.local_addr();
+    let server = Server::builder(incoming).serve(service::make_service_fn(move |_conn| {
+        let handler = handler.clone();
+        async move {
+            Ok::<_, hyper::Error>(service::service_fn(move |_req| {
+                let res = handler();
+                async move { Ok::<_, hyper::Error>(res.await) }
+            }))
+        }
+    }));
+    tokio::spawn(async {
+        match server.await {
+            Ok(()) => (),
+            Err(err) => eprintln!("server error: {}", err),
+        }
+    });
+    format!("http://{}", addr).parse().unwrap()
+}
+
 fn assert_raw_schemas_eq(schema1: &str, schema2: &str) {
     let schema1: serde_json::Value = serde_json::from_str(schema1).unwrap();
     let schema2: serde_json::Value = serde_json::from_str(schema2).unwrap();
@@ -0,0 +1,101 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Benchmarks catalog content migrations against a large synthetic catalog.
+
+use std::path::Path;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use rusqlite::{params, Connection};
+use serde_json::json;
+use tempfile::TempDir;
+
+use build_info::DUMMY_BUILD_INFO;
+use coord::catalog::{Catalog, Config};
+
+/// The number of views in the synthetic catalog.
+const NUM_VIEWS: u64 = 10_000;
+
+/// The ID of the `materialize.public` schema in a freshly created catalog.
+const PUBLIC_SCHEMA_ID: i64 = 3;
+
+fn config(path: &Path) -> Config {
+    Config {
+        path,
+        experimental_mode: None,
+        enable_logging: false,
+        cache_directory: None,
+        build_info: &DUMMY_BUILD_INFO,
+    }
+}
+
+/// Creates a catalog containing `NUM_VIEWS` views in the format written by
+/// v0.6.0, i.e., before any content migrations have been applied, so that
+/// every migration has work to do.
+fn create_catalog() -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("catalog");
+    Catalog::open(&config(&path)).unwrap();
+
+    let mut conn = Connection::open(&path).unwrap();
+    let tx = conn.transaction().unwrap();
+    for i in 0..NUM_VIEWS {
+        let gid = serde_json::to_vec(&json!({ "User": i + 1 })).unwrap();
+        let create_sql = format!(
+            "CREATE VIEW \"materialize\".\"public\".\"v{i}\" AS \
+             SELECT abs({i} + 1) * 2 AS a, CAST('{i}' AS text) || 'x' AS b",
+            i = i,
+        );
+        let definition = serde_json::to_vec(&json!({
+            "V1": {
+                "create_sql": create_sql,
+                "eval_env": null,
+            }
+        }))
+        .unwrap();
+        tx.execute(
+            "INSERT INTO items (gid, schema_id, name, definition) VALUES (?, ?, ?, ?)",
+            params![gid, PUBLIC_SCHEMA_ID, format!("v{}", i), definition],
+        )
+        .unwrap();
+    }
+    tx.execute(
+        "UPDATE gid_alloc SET next_gid = ?",
+        params![(NUM_VIEWS + 1) as i64],
+    )
+    .unwrap();
+    tx.execute(
+        "UPDATE settings SET value = 0 WHERE name = 'catalog_content_version'",
+        params![],
+    )
+    .unwrap();
+    tx.commit().unwrap();
+
+    dir
+}
+
+fn bench_migrate(c: &mut Criterion) {
+    let dir = create_catalog();
+    let path = dir.path().join("catalog");
+
+    let mut group = c.benchmark_group("migrate");
+    group.sample_size(10);
+    group.bench_function("10k_views", |b| {
+        // A dry run migrates an in-memory copy of the catalog, so the catalog
+        // on disk remains unmigrated across iterations.
+        b.iter(|| {
+            let diff = Catalog::migrate_dry_run(&config(&path)).unwrap();
+            assert_eq!(diff.len(), NUM_VIEWS as usize);
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_migrate);
+criterion_main!(benches);
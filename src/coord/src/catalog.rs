@@ -8,9 +8,10 @@
 // by the Apache License, Version 2.0.
 
 use std::collections::{BTreeMap, HashMap, HashSet};
+use std::convert::TryFrom;
 use std::path::Path;
 use std::sync::{Arc, Mutex, MutexGuard};
-use std::time::SystemTime;
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::bail;
 use chrono::{DateTime, TimeZone, Utc};
@@ -21,9 +22,12 @@ use log::{info, trace};
 use ore::collections::CollectionExt;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 
 use build_info::DUMMY_BUILD_INFO;
-use dataflow_types::{SinkConnector, SinkConnectorBuilder, SourceConnector};
+use dataflow_types::{
+    Consistency, ExternalSourceConnector, SinkConnector, SinkConnectorBuilder, SourceConnector,
+};
 use expr::{ExprHumanizer, GlobalId, MirScalarExpr, OptimizedMirRelationExpr};
 use repr::{ColumnType, RelationDesc, ScalarType};
 use sql::ast::display::AstDisplay;
@@ -33,7 +37,7 @@ use sql::catalog::{
     CatalogItemType as SqlCatalogItemType,
 };
 use sql::names::{DatabaseSpecifier, FullName, PartialName, SchemaName};
-use sql::plan::{Params, Plan, PlanContext};
+use sql::plan::{IndexOption, Params, Plan, PlanContext};
 use transform::Optimizer;
 
 use crate::catalog::builtin::{
@@ -81,7 +85,7 @@ pub const FIRST_USER_OID: u32 = 20_000;
 /// The catalog also maintains special "ambient schemas": virtual schemas,
 /// implicitly present in all databases, that house various system views.
 /// The big examples of ambient schemas are `pg_catalog` and `mz_catalog`.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Catalog {
     by_name: BTreeMap<String, Database>,
     by_id: BTreeMap<GlobalId, CatalogEntry>,
@@ -110,7 +114,7 @@ impl ConnCatalog<'_> {
     }
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Database {
     pub name: String,
     pub id: i64,
@@ -119,7 +123,7 @@ pub struct Database {
     pub schemas: BTreeMap<String, Schema>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Schema {
     pub name: SchemaName,
     pub id: i64,
@@ -129,7 +133,7 @@ pub struct Schema {
     pub functions: BTreeMap<String, GlobalId>,
 }
 
-#[derive(Debug, Serialize)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Role {
     pub name: String,
     pub id: i64,
@@ -164,6 +168,9 @@ pub struct Table {
     pub desc: RelationDesc,
     #[serde(skip)]
     pub defaults: Vec<Expr<Raw>>,
+    pub keys: Vec<Vec<usize>>,
+    #[serde(skip)]
+    pub checks: Vec<(String, Expr<Raw>)>,
     pub conn_id: Option<u32>,
 }
 
@@ -199,6 +206,9 @@ pub struct View {
     pub optimized_expr: OptimizedMirRelationExpr,
     pub desc: RelationDesc,
     pub conn_id: Option<u32>,
+    /// The amount of history that indexes on the view retain by default,
+    /// overriding the global logical compaction window.
+    pub retain_history: Option<Duration>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -207,6 +217,10 @@ pub struct Index {
     pub plan_cx: PlanContext,
     pub on: GlobalId,
     pub keys: Vec<MirScalarExpr>,
+    pub memory_limit_bytes: Option<usize>,
+    /// The options with which the index was created, which are applied each
+    /// time its dataflow is installed.
+    pub options: Vec<IndexOption>,
     pub conn_id: Option<u32>,
 }
 
@@ -442,8 +456,153 @@ impl Catalog {
     /// Returns the catalog and a list of events that describe the initial state
     /// of the catalog.
     pub fn open(config: &Config) -> Result<(Catalog, Vec<Event>), Error> {
-        let (storage, experimental_mode, cluster_id) = storage::Connection::open(&config)?;
+        let (mut catalog, mut events) =
+            Self::open_bootstrap(config, storage::Connection::open(config)?)?;
 
+        catalog.migrate()?;
+
+        let items = catalog.storage().load_items()?;
+        for (id, name, def) in items {
+            // TODO(benesch): a better way of detecting when a view has depended
+            // upon a non-existent logging view. This is fine for now because
+            // the only goal is to produce a nicer error message; we'll bail out
+            // safely even if the error message we're sniffing out changes.
+            lazy_static! {
+                static ref LOGGING_ERROR: Regex =
+                    Regex::new("unknown catalog item 'mz_catalog.[^']*'").unwrap();
+            }
+            let item = match catalog.deserialize_item(def) {
+                Ok(item) => item,
+                Err(e) if LOGGING_ERROR.is_match(&e.to_string()) => {
+                    return Err(Error::new(ErrorKind::UnsatisfiableLoggingDependency {
+                        depender_name: name.to_string(),
+                    }));
+                }
+                Err(e) => {
+                    return Err(Error::new(ErrorKind::Corruption {
+                        detail: format!("failed to deserialize item {} ({}): {}", id, name, e),
+                    }))
+                }
+            };
+            let oid = catalog.allocate_oid()?;
+            events.push(catalog.insert_item(id, oid, name, item));
+        }
+        Ok((catalog, events))
+    }
+
+    /// Reports the changes that any pending content migrations would make to
+    /// the catalog that stores data at `path`, without applying them.
+    ///
+    /// The catalog is copied into memory before any schema or content
+    /// migrations run, so the file at `path` is never modified, and the
+    /// migrations behave exactly as they would during [`Catalog::open`].
+    /// Items whose definitions cannot be parsed are reported as failed entries
+    /// in the returned diff and are otherwise excluded from the run.
+    pub fn migrate_dry_run(config: &Config) -> Result<Vec<MigrationDiff>, Error> {
+        let (mut catalog, _) =
+            Self::open_bootstrap(config, storage::Connection::open_copy(config)?)?;
+
+        let mut diff = vec![];
+        let mut old_items = BTreeMap::new();
+        {
+            let mut storage = catalog.storage();
+            let items = storage.load_items()?;
+            let tx = storage.transaction()?;
+            for (id, name, def) in items {
+                let parsed = serde_json::from_slice::<SerializedCatalogItem>(&def)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|item| {
+                        sql::parse::parse(item.create_sql())?;
+                        Ok(item)
+                    });
+                match parsed {
+                    Ok(item) => {
+                        old_items.insert(id, item.create_sql().to_owned());
+                    }
+                    Err(e) => {
+                        tx.remove_item(id)?;
+                        diff.push(MigrationDiff {
+                            id,
+                            name,
+                            old_create_sql: String::from_utf8_lossy(&def).into_owned(),
+                            new_create_sql: Err(e.to_string()),
+                        });
+                    }
+                }
+            }
+            tx.commit()?;
+        }
+
+        catalog.migrate()?;
+
+        for (id, name, def) in catalog.storage().load_items()? {
+            let old_create_sql = match old_items.remove(&id) {
+                Some(old_create_sql) => old_create_sql,
+                None => continue,
+            };
+            let new_create_sql = match serde_json::from_slice::<SerializedCatalogItem>(&def) {
+                Ok(item) if item.create_sql() == old_create_sql => continue,
+                Ok(item) => Ok(item.create_sql().to_owned()),
+                Err(e) => Err(e.to_string()),
+            };
+            diff.push(MigrationDiff {
+                id,
+                name,
+                old_create_sql,
+                new_create_sql,
+            });
+        }
+        diff.sort_by_key(|d| d.id);
+        Ok(diff)
+    }
+
+    /// Runs any content migrations that have not yet been applied to the
+    /// catalog's storage.
+    fn migrate(&mut self) -> Result<(), Error> {
+        let mut catalog_content_version = self.storage().get_catalog_content_version()?;
+
+        while CONTENT_MIGRATIONS.len() > catalog_content_version {
+            // Migrations record the number of items they examine and rewrite
+            // on this span.
+            let span = tracing::info_span!(
+                "catalog_migration",
+                index = catalog_content_version,
+                items = tracing::field::Empty,
+                rewritten = tracing::field::Empty,
+                skipped = tracing::field::Empty,
+            );
+            let _guard = span.enter();
+            let start = Instant::now();
+            self.storage().set_running_migration(Some(
+                u32::try_from(catalog_content_version)
+                    .expect("fewer than u32::MAX catalog content migrations"),
+            ));
+            let res = CONTENT_MIGRATIONS[catalog_content_version](self);
+            self.storage().set_running_migration(None);
+            if let Err(e) = res {
+                return Err(Error::new(ErrorKind::FailedMigration {
+                    last_version: catalog_content_version,
+                    cause: format!("{:#}", e),
+                }));
+            }
+            tracing::info!(
+                "catalog migration {} completed in {:?}",
+                catalog_content_version,
+                start.elapsed()
+            );
+            catalog_content_version += 1;
+            self.storage()
+                .set_catalog_content_version(catalog_content_version)?;
+        }
+        Ok(())
+    }
+
+    /// Installs the databases, schemas, roles, and builtin items from the
+    /// freshly opened `storage`, but neither migrates nor installs user items.
+    fn open_bootstrap(
+        config: &Config,
+        (storage, experimental_mode, cluster_id): (storage::Connection, bool, Uuid),
+    ) -> Result<(Catalog, Vec<Event>), Error> {
         let mut catalog = Catalog {
             by_name: BTreeMap::new(),
             by_id: BTreeMap::new(),
@@ -574,6 +733,8 @@ impl Catalog {
                                     &log.variant.index_by(),
                                 ),
                                 plan_cx: PlanContext::default(),
+                                memory_limit_bytes: None,
+                                options: vec![],
                                 conn_id: None,
                             }),
                         ),
@@ -599,6 +760,8 @@ impl Catalog {
                             plan_cx: PlanContext::default(),
                             desc: table.desc.clone(),
                             defaults: vec![Expr::null(); table.desc.arity()],
+                            keys: vec![],
+                            checks: vec![],
                             conn_id: None,
                         }),
                     ));
@@ -620,6 +783,8 @@ impl Catalog {
                                     .collect(),
                                 create_sql: index_sql,
                                 plan_cx: PlanContext::default(),
+                                memory_limit_bytes: None,
+                                options: vec![],
                                 conn_id: None,
                             }),
                         ),
@@ -685,47 +850,6 @@ impl Catalog {
             }
         }
 
-        let mut catalog_content_version = catalog.storage().get_catalog_content_version()?;
-
-        while CONTENT_MIGRATIONS.len() > catalog_content_version {
-            if let Err(e) = CONTENT_MIGRATIONS[catalog_content_version](&mut catalog) {
-                return Err(Error::new(ErrorKind::FailedMigration {
-                    last_version: catalog_content_version,
-                    cause: e.to_string(),
-                }));
-            }
-            catalog_content_version += 1;
-            catalog
-                .storage()
-                .set_catalog_content_version(catalog_content_version)?;
-        }
-
-        let items = catalog.storage().load_items()?;
-        for (id, name, def) in items {
-            // TODO(benesch): a better way of detecting when a view has depended
-            // upon a non-existent logging view. This is fine for now because
-            // the only goal is to produce a nicer error message; we'll bail out
-            // safely even if the error message we're sniffing out changes.
-            lazy_static! {
-                static ref LOGGING_ERROR: Regex =
-                    Regex::new("unknown catalog item 'mz_catalog.[^']*'").unwrap();
-            }
-            let item = match catalog.deserialize_item(def) {
-                Ok(item) => item,
-                Err(e) if LOGGING_ERROR.is_match(&e.to_string()) => {
-                    return Err(Error::new(ErrorKind::UnsatisfiableLoggingDependency {
-                        depender_name: name.to_string(),
-                    }));
-                }
-                Err(e) => {
-                    return Err(Error::new(ErrorKind::Corruption {
-                        detail: format!("failed to deserialize item {} ({}): {}", id, name, e),
-                    }))
-                }
-            };
-            let oid = catalog.allocate_oid()?;
-            events.push(catalog.insert_item(id, oid, name, item));
-        }
         Ok((catalog, events))
     }
 
@@ -776,6 +900,12 @@ impl Catalog {
         self.storage.lock().expect("lock poisoned")
     }
 
+    /// Reports the index of the content migration that last rewrote each item,
+    /// for those items that have been rewritten by a migration.
+    pub fn item_migrations(&self) -> Result<Vec<(GlobalId, u32)>, Error> {
+        self.storage().load_item_migrations()
+    }
+
     pub fn allocate_id(&mut self) -> Result<GlobalId, Error> {
         self.storage().allocate_id()
     }
@@ -1385,6 +1515,34 @@ impl Catalog {
                     }
                     vec![Action::DropItem(id)]
                 }
+                Op::AlterSourceOptions {
+                    id,
+                    create_sql,
+                    config_options,
+                } => {
+                    let entry = self.by_id.get(&id).unwrap();
+                    let mut source = match entry.item() {
+                        CatalogItem::Source(source) => source.clone(),
+                        _ => unreachable!("AlterSourceOptions on non-source {}", entry.name),
+                    };
+                    source.create_sql = create_sql;
+                    match &mut source.connector {
+                        SourceConnector::External {
+                            connector: ExternalSourceConnector::Kafka(kc),
+                            ..
+                        } => kc.config_options = config_options,
+                        _ => unreachable!("AlterSourceOptions on non-Kafka source {}", entry.name),
+                    }
+                    let item = CatalogItem::Source(source);
+                    let serialized_item = self.serialize_item(&item);
+                    tx.update_item(id, &entry.name.item, &serialized_item)?;
+                    vec![Action::UpdateItem {
+                        id,
+                        from_name: None,
+                        to_name: entry.name.clone(),
+                        item,
+                    }]
+                }
                 Op::RenameItem { id, to_name } => {
                     let mut actions = Vec::new();
 
@@ -1646,41 +1804,47 @@ impl Catalog {
     }
 
     fn serialize_item(&self, item: &CatalogItem) -> Vec<u8> {
-        let item = match item {
-            CatalogItem::Table(table) => SerializedCatalogItem::V1 {
-                create_sql: table.create_sql.clone(),
-                eval_env: Some(table.plan_cx.clone().into()),
-            },
-            CatalogItem::Source(source) => SerializedCatalogItem::V1 {
-                create_sql: source.create_sql.clone(),
-                eval_env: Some(source.plan_cx.clone().into()),
-            },
-            CatalogItem::View(view) => SerializedCatalogItem::V1 {
-                create_sql: view.create_sql.clone(),
-                eval_env: Some(view.plan_cx.clone().into()),
-            },
-            CatalogItem::Index(index) => SerializedCatalogItem::V1 {
-                create_sql: index.create_sql.clone(),
-                eval_env: Some(index.plan_cx.clone().into()),
-            },
-            CatalogItem::Sink(sink) => SerializedCatalogItem::V1 {
-                create_sql: sink.create_sql.clone(),
-                eval_env: Some(sink.plan_cx.clone().into()),
-            },
-            CatalogItem::Type(typ) => SerializedCatalogItem::V1 {
-                create_sql: typ.create_sql.clone(),
-                eval_env: Some(typ.plan_cx.clone().into()),
-            },
+        let (create_sql, plan_cx) = match item {
+            CatalogItem::Table(table) => (&table.create_sql, &table.plan_cx),
+            CatalogItem::Source(source) => (&source.create_sql, &source.plan_cx),
+            CatalogItem::View(view) => (&view.create_sql, &view.plan_cx),
+            CatalogItem::Index(index) => (&index.create_sql, &index.plan_cx),
+            CatalogItem::Sink(sink) => (&sink.create_sql, &sink.plan_cx),
+            CatalogItem::Type(typ) => (&typ.create_sql, &typ.plan_cx),
             CatalogItem::Func(_) => unreachable!("cannot serialize functions yet"),
         };
+        let item = SerializedCatalogItem::V2 {
+            create_sql: create_sql.clone(),
+            eval_env: Some(plan_cx.clone().into()),
+            depends_on: item.uses(),
+        };
         serde_json::to_vec(&item).expect("catalog serialization cannot fail")
     }
 
     fn deserialize_item(&self, bytes: Vec<u8>) -> Result<CatalogItem, anyhow::Error> {
-        let SerializedCatalogItem::V1 {
-            create_sql,
-            eval_env,
-        } = serde_json::from_slice(&bytes)?;
+        let (create_sql, eval_env) = match serde_json::from_slice(&bytes)? {
+            SerializedCatalogItem::V1 {
+                create_sql,
+                eval_env,
+            } => (create_sql, eval_env),
+            SerializedCatalogItem::V2 {
+                create_sql,
+                eval_env,
+                depends_on,
+            } => {
+                // Catch references to dropped user items before planning,
+                // which would otherwise fail with a less helpful resolution
+                // error. Missing system items are left to the planner, so that
+                // references to disabled logging views are reported as such.
+                if let Some(id) = depends_on
+                    .iter()
+                    .find(|id| id.is_user() && !self.by_id.contains_key(id))
+                {
+                    bail!("catalog item depends on missing item {}", id);
+                }
+                (create_sql, eval_env)
+            }
+        };
         let pcx = match eval_env {
             // Old sources and sinks don't have plan contexts, but it's safe to
             // just give them a default, as they clearly don't depend on the
@@ -1704,6 +1868,8 @@ impl Catalog {
                 plan_cx: pcx,
                 desc: table.desc,
                 defaults: table.defaults,
+                keys: table.keys,
+                checks: table.checks,
                 conn_id: None,
             }),
             Plan::CreateSource { source, .. } => CatalogItem::Source(Source {
@@ -1722,13 +1888,16 @@ impl Catalog {
                     optimized_expr,
                     desc,
                     conn_id: None,
+                    retain_history: view.retain_history,
                 })
             }
-            Plan::CreateIndex { index, .. } => CatalogItem::Index(Index {
+            Plan::CreateIndex { index, options, .. } => CatalogItem::Index(Index {
                 create_sql: index.create_sql,
                 plan_cx: pcx,
                 on: index.on,
                 keys: index.keys,
+                memory_limit_bytes: index.memory_limit_bytes,
+                options,
                 conn_id: None,
             }),
             Plan::CreateSink {
@@ -1841,6 +2010,32 @@ impl Catalog {
         }
     }
 
+    /// Reports whether every source that `id` depends on timestamps its data
+    /// with a BYO consistency topic, so that the data is assigned the same
+    /// timestamps in every run of Materialize.
+    pub fn uses_only_byo_sources(&self, id: GlobalId) -> bool {
+        match self.get_by_id(&id).item() {
+            CatalogItem::Table(_) => false,
+            CatalogItem::Source(source) => matches!(
+                source.connector,
+                SourceConnector::External {
+                    consistency: Consistency::BringYourOwn(_),
+                    ..
+                }
+            ),
+            item @ CatalogItem::View(_) => item
+                .uses()
+                .into_iter()
+                .all(|id| self.uses_only_byo_sources(id)),
+            CatalogItem::Func(_)
+            | CatalogItem::Index(_)
+            | CatalogItem::Sink(_)
+            | CatalogItem::Type(_) => {
+                unreachable!("cannot depend on functions, indexes, sinks, or user-defined types");
+            }
+        }
+    }
+
     /// Serializes the catalog's in-memory state.
     ///
     /// There are no guarantees about the format of the serialized state, except
@@ -1898,6 +2093,13 @@ pub enum Op {
         id: GlobalId,
         to_name: String,
     },
+    /// Replaces the `CREATE SOURCE` statement and Kafka options of a source,
+    /// as changed by `ALTER SOURCE ... SET`.
+    AlterSourceOptions {
+        id: GlobalId,
+        create_sql: String,
+        config_options: BTreeMap<String, String>,
+    },
 }
 
 #[derive(Debug, Clone)]
@@ -1960,6 +2162,19 @@ pub enum Event {
     NoOp,
 }
 
+/// A change that a pending content migration would make to a catalog item, as
+/// reported by [`Catalog::migrate_dry_run`].
+#[derive(Debug, Clone)]
+pub struct MigrationDiff {
+    pub id: GlobalId,
+    pub name: FullName,
+    /// The item's definition before migrating.
+    pub old_create_sql: String,
+    /// The item's definition after migrating, or a description of why the
+    /// item could not be migrated.
+    pub new_create_sql: Result<String, String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 enum SerializedCatalogItem {
     V1 {
@@ -1967,6 +2182,43 @@ enum SerializedCatalogItem {
         // The name "eval_env" is historical.
         eval_env: Option<SerializedPlanContext>,
     },
+    V2 {
+        create_sql: String,
+        eval_env: Option<SerializedPlanContext>,
+        /// The items that this item depends upon, as resolved when the item
+        /// was planned.
+        depends_on: Vec<GlobalId>,
+    },
+}
+
+impl SerializedCatalogItem {
+    /// Returns the `CREATE` statement that defines the item.
+    fn create_sql(&self) -> &str {
+        match self {
+            SerializedCatalogItem::V1 { create_sql, .. }
+            | SerializedCatalogItem::V2 { create_sql, .. } => create_sql,
+        }
+    }
+
+    /// Returns a copy of the item, in the same format version, whose `CREATE`
+    /// statement has been replaced with `create_sql`.
+    fn with_create_sql(self, create_sql: String) -> SerializedCatalogItem {
+        match self {
+            SerializedCatalogItem::V1 { eval_env, .. } => SerializedCatalogItem::V1 {
+                create_sql,
+                eval_env,
+            },
+            SerializedCatalogItem::V2 {
+                eval_env,
+                depends_on,
+                ..
+            } => SerializedCatalogItem::V2 {
+                create_sql,
+                eval_env,
+                depends_on,
+            },
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -2334,9 +2586,15 @@ impl sql::catalog::CatalogItem for CatalogEntry {
         }
     }
 
-    fn table_details(&self) -> Option<&[Expr<Raw>]> {
-        if let CatalogItem::Table(Table { defaults, .. }) = self.item() {
-            Some(defaults)
+    fn table_details(&self) -> Option<(&[Expr<Raw>], &[Vec<usize>], &[(String, Expr<Raw>)])> {
+        if let CatalogItem::Table(Table {
+            defaults,
+            keys,
+            checks,
+            ..
+        }) = self.item()
+        {
+            Some((defaults, keys, checks))
         } else {
             None
         }
@@ -2353,13 +2611,109 @@ impl sql::catalog::CatalogItem for CatalogEntry {
 
 #[cfg(test)]
 mod tests {
+    use std::fs;
+
+    use rusqlite::params;
     use tempfile::NamedTempFile;
 
+    use build_info::DUMMY_BUILD_INFO;
+    use sql::catalog::CatalogItemType;
     use sql::names::{DatabaseSpecifier, FullName, PartialName};
 
-    use crate::catalog::{Catalog, MZ_CATALOG_SCHEMA, PG_CATALOG_SCHEMA};
+    use crate::catalog::builtin::{MZ_OBJECTS, MZ_RELATIONS};
+    use crate::catalog::{
+        Catalog, Config, SerializedCatalogItem, MZ_CATALOG_SCHEMA, PG_CATALOG_SCHEMA,
+    };
     use crate::session::Session;
 
+    /// A dry run must leave the catalog on disk untouched, even when the
+    /// catalog has schema migrations pending.
+    #[test]
+    fn test_migrate_dry_run_does_not_modify_catalog() -> Result<(), anyhow::Error> {
+        let catalog_file = NamedTempFile::new()?;
+        Catalog::open_debug(catalog_file.path())?;
+
+        // Roll the catalog back to the schema of v0.7.0, before the
+        // `last_migration` column was added to the items table.
+        let mut conn = rusqlite::Connection::open(catalog_file.path())?;
+        let tx = conn.transaction()?;
+        tx.execute_batch(
+            "CREATE TABLE items_old (
+                 gid        blob PRIMARY KEY,
+                 schema_id  integer REFERENCES schemas,
+                 name       text NOT NULL,
+                 definition blob NOT NULL,
+                 UNIQUE (schema_id, name)
+             );
+             INSERT INTO items_old SELECT gid, schema_id, name, definition FROM items;
+             DROP TABLE items;
+             ALTER TABLE items_old RENAME TO items;
+             PRAGMA user_version = 4;",
+        )?;
+        tx.commit()?;
+        drop(conn);
+
+        let before = fs::read(catalog_file.path())?;
+        Catalog::migrate_dry_run(&Config {
+            path: catalog_file.path(),
+            enable_logging: true,
+            experimental_mode: None,
+            cache_directory: None,
+            build_info: &DUMMY_BUILD_INFO,
+        })?;
+        assert_eq!(fs::read(catalog_file.path())?, before);
+
+        let conn = rusqlite::Connection::open(catalog_file.path())?;
+        let version: u32 = conn.query_row("PRAGMA user_version", params![], |row| row.get(0))?;
+        assert_eq!(version, 4);
+        Ok(())
+    }
+
+    /// Items written by older versions of materialized must remain readable.
+    #[test]
+    fn test_deserialize_v1_item() -> Result<(), anyhow::Error> {
+        let v1 = br#"{"V1":{"create_sql":"CREATE VIEW v AS SELECT 1","eval_env":null}}"#;
+        let item: SerializedCatalogItem = serde_json::from_slice(v1)?;
+        assert!(matches!(item, SerializedCatalogItem::V1 { .. }));
+        assert_eq!(item.create_sql(), "CREATE VIEW v AS SELECT 1");
+        Ok(())
+    }
+
+    /// `mz_objects` is a hand-maintained union of the catalog tables for each
+    /// kind of item, so it must be extended whenever a kind is added.
+    #[test]
+    fn test_mz_objects_lists_every_item_kind() {
+        let definition = format!("{}\n{}", MZ_RELATIONS.sql, MZ_OBJECTS.sql);
+        for typ in &[
+            CatalogItemType::Table,
+            CatalogItemType::Source,
+            CatalogItemType::Sink,
+            CatalogItemType::View,
+            CatalogItemType::Index,
+            CatalogItemType::Type,
+            CatalogItemType::Func,
+        ] {
+            // This match is exhaustive so that a new kind of item does not
+            // compile until it is added to the list above.
+            let listed = match typ {
+                CatalogItemType::Table
+                | CatalogItemType::Source
+                | CatalogItemType::Sink
+                | CatalogItemType::View
+                | CatalogItemType::Index
+                | CatalogItemType::Type => true,
+                // Functions are listed in `mz_functions` instead.
+                CatalogItemType::Func => false,
+            };
+            assert_eq!(
+                definition.contains(&format!("'{}'", typ)),
+                listed,
+                "mz_objects does not list items of type {} as expected",
+                typ,
+            );
+        }
+    }
+
     /// System sessions have an empty `search_path` so it's necessary to
     /// schema-qualify all referenced items.
     ///
@@ -512,6 +512,14 @@ pub const MZ_SOURCE_INFO: BuiltinLog = BuiltinLog {
     index_id: GlobalId::System(3027),
 };
 
+pub const MZ_SOURCE_ERRORS: BuiltinLog = BuiltinLog {
+    name: "mz_source_errors",
+    schema: MZ_INTERNAL_SCHEMA,
+    variant: LogVariant::Materialized(MaterializedLog::SourceErrors),
+    id: GlobalId::System(3028),
+    index_id: GlobalId::System(3029),
+};
+
 lazy_static! {
     pub static ref MZ_VIEW_KEYS: BuiltinTable = BuiltinTable {
         name: "mz_view_keys",
@@ -542,6 +550,7 @@ lazy_static! {
         desc: RelationDesc::empty()
             .with_column("sink_id", ScalarType::String.nullable(false))
             .with_column("topic", ScalarType::String.nullable(false))
+            .with_column("consistency_topic", ScalarType::String.nullable(true))
             .with_key(vec![0]),
         id: GlobalId::System(4005),
         index_id: GlobalId::System(4006),
@@ -585,7 +594,9 @@ lazy_static! {
             .with_column("name", ScalarType::String.nullable(false))
             .with_column("position", ScalarType::Int64.nullable(false))
             .with_column("nullable", ScalarType::Bool.nullable(false))
-            .with_column("type", ScalarType::String.nullable(false)),
+            .with_column("type", ScalarType::String.nullable(false))
+            .with_column("full_type", ScalarType::String.nullable(false))
+            .with_column("key", ScalarType::Bool.nullable(false)),
         id: GlobalId::System(4013),
         index_id: GlobalId::System(4014),
     };
@@ -596,7 +607,8 @@ lazy_static! {
             .with_column("id", ScalarType::String.nullable(false))
             .with_column("oid", ScalarType::Oid.nullable(false))
             .with_column("name", ScalarType::String.nullable(false))
-            .with_column("on_id", ScalarType::String.nullable(false)),
+            .with_column("on_id", ScalarType::String.nullable(false))
+            .with_column("memory_limit_bytes", ScalarType::Int64.nullable(true)),
         id: GlobalId::System(4015),
         index_id: GlobalId::System(4016),
     };
@@ -730,10 +742,67 @@ lazy_static! {
             .with_column("schema_id", ScalarType::Int64.nullable(false))
             .with_column("name", ScalarType::String.nullable(false))
             .with_column("arg_ids", ScalarType::Array(Box::new(ScalarType::String)).nullable(false))
-            .with_column("variadic_id", ScalarType::String.nullable(true)),
+            .with_column("variadic_id", ScalarType::String.nullable(true))
+            .with_column("arg_oids", ScalarType::Array(Box::new(ScalarType::Oid)).nullable(false))
+            .with_column("ret_id", ScalarType::String.nullable(false))
+            .with_column("ret_set", ScalarType::Bool.nullable(false)),
             id: GlobalId::System(4041),
             index_id: GlobalId::System(4042),
     };
+    pub static ref MZ_ITEM_MIGRATION_STAMPS: BuiltinTable = BuiltinTable {
+        name: "mz_item_migration_stamps",
+        schema: MZ_CATALOG_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_column("id", ScalarType::String.nullable(false))
+            .with_column("last_migration", ScalarType::Int32.nullable(false)),
+            id: GlobalId::System(4043),
+            index_id: GlobalId::System(4044),
+    };
+    pub static ref MZ_SOURCE_UPSTREAM_OFFSETS: BuiltinTable = BuiltinTable {
+        name: "mz_source_upstream_offsets",
+        schema: MZ_INTERNAL_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_column("source_id", ScalarType::String.nullable(false))
+            .with_column("partition_id", ScalarType::String.nullable(false))
+            .with_column("offset", ScalarType::Int64.nullable(true))
+            .with_column("bytes", ScalarType::Int64.nullable(true))
+            .with_key(vec![0, 1]),
+        id: GlobalId::System(4045),
+        index_id: GlobalId::System(4046),
+    };
+    pub static ref MZ_PEEK_DURATION_HISTOGRAM: BuiltinTable = BuiltinTable {
+        name: "mz_peek_durations",
+        schema: MZ_INTERNAL_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_column("index_id", ScalarType::String.nullable(true))
+            .with_column("duration_ns", ScalarType::Int64.nullable(false))
+            .with_column("count", ScalarType::Int64.nullable(false)),
+        id: GlobalId::System(4047),
+        index_id: GlobalId::System(4048),
+    };
+    pub static ref MZ_ACTIVE_PEEKS: BuiltinTable = BuiltinTable {
+        name: "mz_active_peeks",
+        schema: MZ_INTERNAL_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_column("query_id", ScalarType::Int64.nullable(false))
+            .with_column("conn_id", ScalarType::Int64.nullable(false))
+            .with_column("index_id", ScalarType::String.nullable(true))
+            .with_key(vec![0]),
+        id: GlobalId::System(4049),
+        index_id: GlobalId::System(4050),
+    };
+    pub static ref MZ_FRONTIERS: BuiltinTable = BuiltinTable {
+        name: "mz_frontiers",
+        schema: MZ_INTERNAL_SCHEMA,
+        desc: RelationDesc::empty()
+            .with_column("index_id", ScalarType::String.nullable(false))
+            .with_column("object_id", ScalarType::String.nullable(false))
+            .with_column("since", ScalarType::Int64.nullable(true))
+            .with_column("upper", ScalarType::Int64.nullable(true))
+            .with_key(vec![0]),
+        id: GlobalId::System(4051),
+        index_id: GlobalId::System(4052),
+    };
 }
 
 pub const MZ_RELATIONS: BuiltinView = BuiltinView {
@@ -747,6 +816,8 @@ UNION SELECT id, oid, schema_id, name, 'view' FROM mz_catalog.mz_views",
     needs_logs: false,
 };
 
+// Every kind of catalog item other than functions must be listed here, as
+// `test_mz_objects_lists_every_item_kind` checks.
 pub const MZ_OBJECTS: BuiltinView = BuiltinView {
     name: "mz_objects",
     schema: MZ_CATALOG_SCHEMA,
@@ -757,7 +828,9 @@ UNION
 UNION
     SELECT mz_indexes.id, mz_indexes.oid, schema_id, mz_indexes.name, 'index'
     FROM mz_catalog.mz_indexes
-    JOIN mz_catalog.mz_relations ON mz_indexes.on_id = mz_relations.id",
+    JOIN mz_catalog.mz_relations ON mz_indexes.on_id = mz_relations.id
+UNION
+    SELECT id, oid, schema_id, name, 'type' FROM mz_catalog.mz_types",
     id: GlobalId::System(5001),
     needs_logs: false,
 };
@@ -1021,7 +1094,8 @@ pub const PG_CLASS: BuiltinView = BuiltinView {
         WHEN mz_objects.type = 'view' THEN 'v'
     END relkind
 FROM mz_catalog.mz_objects
-JOIN mz_catalog.mz_schemas ON mz_schemas.id = mz_objects.schema_id",
+JOIN mz_catalog.mz_schemas ON mz_schemas.id = mz_objects.schema_id
+WHERE mz_objects.type <> 'type'",
     id: GlobalId::System(5016),
     needs_logs: false,
 };
@@ -1094,7 +1168,24 @@ pub const PG_TYPE: BuiltinView = BuiltinView {
     mz_schemas.oid AS typnamespace,
     typtype,
     0::pg_catalog.oid AS typrelid,
-    NULL::pg_catalog.oid AS typelem,
+    coalesce(
+        (
+            SELECT
+                t.oid
+            FROM
+                (
+                    SELECT type_id, element_id FROM mz_catalog.mz_array_types
+                    UNION ALL SELECT type_id, element_id FROM mz_catalog.mz_list_types
+                    UNION ALL SELECT type_id, value_id FROM mz_catalog.mz_map_types
+                )
+                    AS e
+                JOIN mz_catalog.mz_types AS t ON e.element_id = t.id
+            WHERE
+                e.type_id = mz_types.id
+        ),
+        0
+    )
+        AS typelem,
     coalesce(
         (
             SELECT
@@ -1130,9 +1221,15 @@ pub const PG_PROC: BuiltinView = BuiltinView {
     name: "pg_proc",
     schema: PG_CATALOG_SCHEMA,
     sql: "CREATE VIEW pg_proc AS SELECT
-    NULL::pg_catalog.oid AS oid,
-    NULL::pg_catalog.text AS proname
-    WHERE false",
+    mz_functions.oid,
+    mz_functions.name AS proname,
+    mz_schemas.oid AS pronamespace,
+    mz_functions.arg_oids AS proargtypes,
+    mz_types.oid AS prorettype,
+    mz_functions.ret_set AS proretset
+FROM mz_catalog.mz_functions
+JOIN mz_catalog.mz_schemas ON mz_functions.schema_id = mz_schemas.id
+JOIN mz_catalog.mz_types ON mz_functions.ret_id = mz_types.id",
     id: GlobalId::System(5022),
     needs_logs: false,
 };
@@ -1161,6 +1258,108 @@ pub const PG_ENUM: BuiltinView = BuiltinView {
     needs_logs: false,
 };
 
+pub const MZ_CATALOG_ITEM_MIGRATIONS: BuiltinView = BuiltinView {
+    name: "mz_catalog_item_migrations",
+    schema: MZ_CATALOG_SCHEMA,
+    sql: "CREATE VIEW mz_catalog_item_migrations AS SELECT
+    mz_objects.id,
+    mz_objects.name,
+    mz_item_migration_stamps.last_migration
+FROM mz_catalog.mz_objects
+LEFT JOIN mz_catalog.mz_item_migration_stamps ON mz_objects.id = mz_item_migration_stamps.id
+WHERE mz_objects.id LIKE 'u%'",
+    id: GlobalId::System(5025),
+    needs_logs: false,
+};
+
+// Kafka partitions report offsets, while files report their size in bytes.
+// Ingested offsets are one past the offset of the last message that was
+// ingested, just like the high watermarks of Kafka partitions.
+pub const MZ_SOURCE_LAG: BuiltinView = BuiltinView {
+    name: "mz_source_lag",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_source_lag AS SELECT
+    mz_source_info.source_name,
+    mz_source_info.source_id,
+    mz_source_info.dataflow_id,
+    mz_source_info.partition_id,
+    upstream.offset AS upstream_offset,
+    mz_source_info.offset AS ingested_offset,
+    upstream.bytes AS upstream_bytes,
+    mz_source_info.bytes AS ingested_bytes,
+    coalesce(
+        upstream.offset - mz_source_info.offset,
+        upstream.bytes - mz_source_info.bytes
+    ) AS lag
+FROM mz_catalog.mz_source_info
+JOIN mz_internal.mz_source_upstream_offsets upstream
+    ON mz_source_info.source_id = upstream.source_id
+    AND mz_source_info.partition_id = upstream.partition_id",
+    id: GlobalId::System(5026),
+    needs_logs: true,
+};
+
+// Operators of dataflows that do not maintain an index, like the logging
+// dataflow and the dataflows of sinks, have a NULL `global_id`. Dataflows that
+// maintain several indexes have one row per operator for each of them.
+pub const MZ_DATAFLOW_OPERATORS_WITH_OWNERS: BuiltinView = BuiltinView {
+    name: "mz_dataflow_operators",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_dataflow_operators AS SELECT
+    mz_dataflow_names.local_id AS dataflow_id,
+    mz_dataflow_names.name AS dataflow_name,
+    mz_dataflow_operators.id AS operator_id,
+    mz_dataflow_operators.name AS operator_name,
+    mz_dataflow_operators.worker,
+    mz_materializations.name AS global_id
+FROM mz_catalog.mz_dataflow_operators
+JOIN mz_catalog.mz_dataflow_operator_addresses
+    ON mz_dataflow_operators.id = mz_dataflow_operator_addresses.id
+    AND mz_dataflow_operators.worker = mz_dataflow_operator_addresses.worker
+    AND mz_dataflow_operator_addresses.slot = 0
+JOIN mz_catalog.mz_dataflow_names
+    ON mz_dataflow_names.local_id = mz_dataflow_operator_addresses.value
+    AND mz_dataflow_names.worker = mz_dataflow_operator_addresses.worker
+LEFT JOIN mz_catalog.mz_materializations
+    ON mz_materializations.dataflow_id = mz_dataflow_names.local_id
+    AND mz_materializations.worker = mz_dataflow_names.worker",
+    id: GlobalId::System(5027),
+    needs_logs: true,
+};
+
+// Differential dataflow reports only the number of updates in each
+// arrangement, so the size is estimated at 96 bytes per update: the inline
+// size of a key row and a value row, the offsets that delimit them, and the
+// update's time and diff. This overestimates arrangements whose updates share
+// keys or values, and it does not count the bytes of rows too large to be
+// stored inline, the spare capacity of the batches' allocations, or merges
+// that are still in progress.
+pub const MZ_DATAFLOW_ARRANGEMENT_SIZES: BuiltinView = BuiltinView {
+    name: "mz_dataflow_arrangement_sizes",
+    schema: MZ_INTERNAL_SCHEMA,
+    sql: "CREATE VIEW mz_dataflow_arrangement_sizes AS SELECT
+    mz_dataflow_operators.global_id,
+    mz_dataflow_operators.dataflow_id,
+    mz_dataflow_operators.dataflow_name,
+    mz_dataflow_operators.operator_id,
+    mz_dataflow_operators.operator_name,
+    pg_catalog.sum(mz_arrangement_sizes.records)::pg_catalog.int8 AS records,
+    pg_catalog.sum(mz_arrangement_sizes.batches)::pg_catalog.int8 AS batches,
+    pg_catalog.sum(mz_arrangement_sizes.records)::pg_catalog.int8 * 96 AS estimated_bytes
+FROM mz_catalog.mz_arrangement_sizes
+JOIN mz_internal.mz_dataflow_operators
+    ON mz_dataflow_operators.operator_id = mz_arrangement_sizes.operator
+    AND mz_dataflow_operators.worker = mz_arrangement_sizes.worker
+GROUP BY
+    mz_dataflow_operators.global_id,
+    mz_dataflow_operators.dataflow_id,
+    mz_dataflow_operators.dataflow_name,
+    mz_dataflow_operators.operator_id,
+    mz_dataflow_operators.operator_name",
+    id: GlobalId::System(5028),
+    needs_logs: true,
+};
+
 lazy_static! {
     pub static ref BUILTINS: BTreeMap<GlobalId, Builtin> = {
         let mut builtins = vec![
@@ -1218,6 +1417,7 @@ lazy_static! {
             Builtin::Log(&MZ_PEEK_ACTIVE),
             Builtin::Log(&MZ_PEEK_DURATIONS),
             Builtin::Log(&MZ_SOURCE_INFO),
+            Builtin::Log(&MZ_SOURCE_ERRORS),
             Builtin::Table(&MZ_VIEW_KEYS),
             Builtin::Table(&MZ_VIEW_FOREIGN_KEYS),
             Builtin::Table(&MZ_KAFKA_SINKS),
@@ -1239,6 +1439,11 @@ lazy_static! {
             Builtin::Table(&MZ_ROLES),
             Builtin::Table(&MZ_PSEUDO_TYPES),
             Builtin::Table(&MZ_FUNCTIONS),
+            Builtin::Table(&MZ_ITEM_MIGRATION_STAMPS),
+            Builtin::Table(&MZ_SOURCE_UPSTREAM_OFFSETS),
+            Builtin::Table(&MZ_PEEK_DURATION_HISTOGRAM),
+            Builtin::Table(&MZ_ACTIVE_PEEKS),
+            Builtin::Table(&MZ_FRONTIERS),
             Builtin::View(&MZ_RELATIONS),
             Builtin::View(&MZ_OBJECTS),
             Builtin::View(&MZ_CATALOG_NAMES),
@@ -1264,6 +1469,10 @@ lazy_static! {
             Builtin::View(&PG_PROC),
             Builtin::View(&PG_RANGE),
             Builtin::View(&PG_ENUM),
+            Builtin::View(&MZ_CATALOG_ITEM_MIGRATIONS),
+            Builtin::View(&MZ_SOURCE_LAG),
+            Builtin::View(&MZ_DATAFLOW_OPERATORS_WITH_OWNERS),
+            Builtin::View(&MZ_DATAFLOW_ARRANGEMENT_SIZES),
         ];
 
         // TODO(sploiselle): assign static global IDs to functions
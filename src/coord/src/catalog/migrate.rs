@@ -7,15 +7,20 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use anyhow::bail;
+use anyhow::{bail, Context};
+use rayon::prelude::*;
 
+use expr::GlobalId;
 use ore::collections::CollectionExt;
 use sql::ast::display::AstDisplay;
-use sql::ast::visit_mut::VisitMut;
+use sql::ast::visit_mut::{self, VisitMut};
 use sql::ast::{
-    CreateIndexStatement, CreateTableStatement, CreateTypeStatement, CreateViewStatement, DataType,
-    Function, Ident, Raw, Statement, TableFactor, UnresolvedObjectName,
+    CreateIndexStatement, CreateSinkStatement, CreateSourceStatement, CreateTableStatement,
+    CreateTypeStatement, CreateViewStatement, DataType, Function, Ident, Op, Raw, RawName,
+    Statement, TableFactor, UnresolvedObjectName,
 };
+use sql::names::FullName;
+use sql::plan::PlanContext;
 
 use crate::catalog::{Catalog, SerializedCatalogItem};
 use crate::catalog::{MZ_CATALOG_SCHEMA, MZ_INTERNAL_SCHEMA, PG_CATALOG_SCHEMA};
@@ -47,18 +52,8 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             }
         }
 
-        let mut storage = catalog.storage();
-        let items = storage.load_items()?;
-        let tx = storage.transaction()?;
-
-        for (id, name, def) in items {
-            let SerializedCatalogItem::V1 {
-                create_sql,
-                eval_env,
-            } = serde_json::from_slice(&def)?;
-
-            let mut stmt = sql::parse::parse(&create_sql)?.into_element();
-            match &mut stmt {
+        rewrite_items(catalog, |id, name, stmt| {
+            match stmt {
                 Statement::CreateTable(CreateTableStatement {
                     name: _,
                     columns,
@@ -111,22 +106,17 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
 
                 // At the time the migration was written, sinks and sources
                 // could not contain references to types.
-                Statement::CreateSource(_) | Statement::CreateSink(_) => continue,
+                Statement::CreateSource(_) | Statement::CreateSink(_) => return Ok(false),
 
-                _ => bail!("catalog item contained inappropriate statement: {}", stmt),
+                _ => bail!(
+                    "catalog item {} \"{}\" contained inappropriate statement: {}",
+                    id,
+                    name,
+                    stmt
+                ),
             }
-
-            let serialized_item = SerializedCatalogItem::V1 {
-                create_sql: stmt.to_ast_string_stable(),
-                eval_env,
-            };
-
-            let serialized_item =
-                serde_json::to_vec(&serialized_item).expect("catalog serialization cannot fail");
-            tx.update_item(id, &name.item, &serialized_item)?;
-        }
-        tx.commit()?;
-        Ok(())
+            Ok(true)
+        })
     },
     |catalog: &mut Catalog| {
         fn normalize_function_name(name: &mut UnresolvedObjectName) {
@@ -158,18 +148,8 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
             }
         }
 
-        let mut storage = catalog.storage();
-        let items = storage.load_items()?;
-        let tx = storage.transaction()?;
-
-        for (id, name, def) in items {
-            let SerializedCatalogItem::V1 {
-                create_sql,
-                eval_env,
-            } = serde_json::from_slice(&def)?;
-
-            let mut stmt = sql::parse::parse(&create_sql)?.into_element();
-            match &mut stmt {
+        rewrite_items(catalog, |id, name, stmt| {
+            match stmt {
                 Statement::CreateView(CreateViewStatement {
                     name: _,
                     columns: _,
@@ -199,25 +179,154 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
                 Statement::CreateTable(_)
                 | Statement::CreateSink(_)
                 | Statement::CreateSource(_)
-                | Statement::CreateType(_) => continue,
+                | Statement::CreateType(_) => return Ok(false),
 
-                _ => bail!("catalog item contained inappropriate statement: {}", stmt),
+                _ => bail!(
+                    "catalog item {} \"{}\" contained inappropriate statement: {}",
+                    id,
+                    name,
+                    stmt
+                ),
             }
+            Ok(true)
+        })
+    },
+    // Upgrades all items stored in the V1 format to the V2 format, which
+    // records the items that each item depends upon.
+    //
+    // Introduced in v0.7.1.
+    //
+    // Unlike the previous migrations, this migration must plan each item to
+    // discover its resolved dependencies. Items are planned against a scratch
+    // copy of the catalog, in `GlobalId` order, so that every item's
+    // dependencies have been installed by the time it is planned. Items that
+    // fail to plan are left in the V1 format, which remains readable; the
+    // failure will be reported when the catalog loads the item for real.
+    |catalog: &mut Catalog| {
+        let mut scratch = catalog.clone();
+        let mut storage = catalog.storage();
+        let items = storage.load_items()?;
+        let item_count = items.len();
+        let tx = storage.transaction()?;
+        let mut rewritten = 0;
 
-            let serialized_item = SerializedCatalogItem::V1 {
-                create_sql: stmt.to_ast_string_stable(),
-                eval_env,
+        for (id, name, def) in items {
+            let (create_sql, eval_env) = match deserialize_item(id, &name, &def)? {
+                SerializedCatalogItem::V1 {
+                    create_sql,
+                    eval_env,
+                } => (create_sql, eval_env),
+                SerializedCatalogItem::V2 { .. } => continue,
+            };
+            let pcx = match eval_env.clone() {
+                None => PlanContext::default(),
+                Some(eval_env) => eval_env.into(),
+            };
+            let item = match scratch.parse_item(create_sql.clone(), pcx) {
+                Ok(item) => item,
+                Err(_) => continue,
             };
 
+            let serialized_item = SerializedCatalogItem::V2 {
+                create_sql,
+                eval_env,
+                depends_on: item.uses(),
+            };
             let serialized_item =
                 serde_json::to_vec(&serialized_item).expect("catalog serialization cannot fail");
             tx.update_item(id, &name.item, &serialized_item)?;
+            rewritten += 1;
+
+            let oid = scratch.allocate_oid()?;
+            let _ = scratch.insert_item(id, oid, name, item);
+        }
+        record_item_counts(item_count, rewritten);
+        if rewritten > 0 {
+            tx.commit()?;
         }
-        tx.commit()?;
         Ok(())
     },
+    // Rewrites all operator references to have `pg_catalog` qualification,
+    // mirroring the earlier type and function migrations.
+    //
+    // Introduced in v0.7.1.
+    //
+    // All operators are built in to the `pg_catalog` schema, so the approach
+    // is simply to prepend `pg_catalog` to every unqualified operator.
+    |catalog: &mut Catalog| {
+        rewrite_items(catalog, |id, name, stmt| {
+            match stmt {
+                Statement::CreateView(CreateViewStatement {
+                    name: _,
+                    columns: _,
+                    query,
+                    temporary: _,
+                    materialized: _,
+                    if_exists: _,
+                    with_options: _,
+                }) => OpNormalizer.visit_query_mut(query),
+
+                Statement::CreateIndex(CreateIndexStatement {
+                    name: _,
+                    on_name: _,
+                    key_parts,
+                    with_options: _,
+                    if_not_exists: _,
+                }) => {
+                    if let Some(key_parts) = key_parts {
+                        for key_part in key_parts {
+                            OpNormalizer.visit_expr_mut(key_part);
+                        }
+                    }
+                }
+
+                // At the time the migration was written, tables, sinks,
+                // sources, and types could not contain references to operators.
+                Statement::CreateTable(_)
+                | Statement::CreateSink(_)
+                | Statement::CreateSource(_)
+                | Statement::CreateType(_) => return Ok(false),
+
+                _ => bail!(
+                    "catalog item {} \"{}\" contained inappropriate statement: {}",
+                    id,
+                    name,
+                    stmt
+                ),
+            }
+            Ok(true)
+        })
+    },
+    // Rewrites all references to the deprecated `mz_now` function to its
+    // canonical name, `mz_logical_timestamp`.
+    //
+    // Introduced in v0.7.1.
+    //
+    // New items store the canonical name, as the SQL normalizer rewrites
+    // deprecated function aliases, but items created while `mz_now` was not
+    // yet an alias store the deprecated name. The earlier function migration
+    // has qualified every such reference with `mz_catalog`.
+    |catalog: &mut Catalog| {
+        rewrite_items(catalog, |_id, _name, stmt| {
+            canonicalize_mz_now(stmt);
+            Ok(true)
+        })
+    },
     // Add new migrations here.
     //
+    // Migrations should only update items whose definitions actually change,
+    // and should skip committing their transaction entirely if no item
+    // changed, so that a clean restart does not churn the catalog. They should
+    // also call `record_item_counts` to report their progress. Migrations that
+    // only rewrite the AST of each item's `CREATE` statement should use
+    // `rewrite_items`, which takes care of all three.
+    //
+    // Note that the earlier migrations skip sources and sinks because, at the
+    // time they were written, sources and sinks could not contain references
+    // to types or functions. That is no longer true. Migrations that rewrite
+    // references should use `visit_create_source_mut` and
+    // `visit_create_sink_mut` to reach every reference in those statements.
+    //
     // Migrations should be preceded with a comment of the following form:
     //
     //     > Short summary of migration's purpose.
@@ -232,3 +341,381 @@ pub const CONTENT_MIGRATIONS: &[fn(&mut Catalog) -> Result<(), anyhow::Error>] =
     // release, after which they must never be removed, only patched by future
     // migrations.
 ];
+
+/// Rewrites the `CREATE` statement of every item in the catalog.
+///
+/// The `rewrite` function is called with each item's parsed `CREATE` statement
+/// and may modify it in place. It returns whether the item is subject to the
+/// migration at all; items for which it returns `false` are left untouched.
+///
+/// Deserializing, parsing, rewriting, and reserializing items is CPU bound and
+/// dominates the cost of a migration on large catalogs, so that work is
+/// spread across threads. The resulting updates are applied serially, in
+/// `GlobalId` order, in a single transaction, which is only committed if at
+/// least one item changed.
+fn rewrite_items<F>(catalog: &mut Catalog, rewrite: F) -> Result<(), anyhow::Error>
+where
+    F: Fn(GlobalId, &FullName, &mut Statement<Raw>) -> Result<bool, anyhow::Error> + Sync,
+{
+    let mut storage = catalog.storage();
+    let items = storage.load_items()?;
+    let item_count = items.len();
+
+    // `collect` preserves the order of `items`, which `load_items` guarantees
+    // is sorted by `GlobalId`.
+    let updates = items
+        .into_par_iter()
+        .map(|(id, name, def)| {
+            let item = deserialize_item(id, &name, &def)?;
+            let mut stmt = parse_item(id, &name, &item)?;
+            if !rewrite(id, &name, &mut stmt)? {
+                return Ok(None);
+            }
+            let create_sql = stmt.to_ast_string_stable();
+            if create_sql == item.create_sql() {
+                return Ok(None);
+            }
+            let serialized_item = item.with_create_sql(create_sql);
+            let serialized_item =
+                serde_json::to_vec(&serialized_item).expect("catalog serialization cannot fail");
+            Ok(Some((id, name, serialized_item)))
+        })
+        .filter_map(Result::transpose)
+        .collect::<Result<Vec<_>, anyhow::Error>>()?;
+    record_item_counts(item_count, updates.len());
+
+    if updates.is_empty() {
+        return Ok(());
+    }
+    let tx = storage.transaction()?;
+    for (id, name, serialized_item) in updates {
+        tx.update_item(id, &name.item, &serialized_item)?;
+    }
+    tx.commit()?;
+    Ok(())
+}
+
+/// Records the number of items that a migration examined and rewrote on the
+/// span that the migration runner creates for each migration.
+fn record_item_counts(items: usize, rewritten: usize) {
+    let span = tracing::Span::current();
+    span.record("items", &items);
+    span.record("rewritten", &rewritten);
+    span.record("skipped", &(items - rewritten));
+}
+
+/// Rewrites all references to the item named `from` in `stmt` to refer to the
+/// item named `to` instead.
+///
+/// References are matched exactly, so `from` and `to` should be qualified
+/// to the same degree as the references in `stmt`, e.g. by running after the
+/// migrations that qualify type and function names. Only the names of tables
+/// in `FROM` clauses, table functions, types, and functions are rewritten.
+fn rename_referenced_item(
+    stmt: &mut Statement<Raw>,
+    from: &UnresolvedObjectName,
+    to: &UnresolvedObjectName,
+) {
+    struct ItemRenamer<'a> {
+        from: &'a UnresolvedObjectName,
+        to: &'a UnresolvedObjectName,
+    }
+
+    impl<'a> ItemRenamer<'a> {
+        fn rename(&self, name: &mut UnresolvedObjectName) {
+            if name == self.from {
+                *name = self.to.clone();
+            }
+        }
+    }
+
+    impl<'a, 'ast> VisitMut<'ast, Raw> for ItemRenamer<'a> {
+        fn visit_table_factor_mut(&mut self, table_factor: &'ast mut TableFactor<Raw>) {
+            match table_factor {
+                TableFactor::Table {
+                    name: RawName::Name(name),
+                    ..
+                }
+                | TableFactor::Table {
+                    name: RawName::Id(_, name),
+                    ..
+                }
+                | TableFactor::Function { name, .. } => self.rename(name),
+                _ => (),
+            }
+            visit_mut::visit_table_factor_mut(self, table_factor);
+        }
+
+        fn visit_data_type_mut(&mut self, data_type: &'ast mut DataType) {
+            if let DataType::Other { name, .. } = data_type {
+                self.rename(name);
+            }
+            visit_mut::visit_data_type_mut(self, data_type);
+        }
+
+        fn visit_function_mut(&mut self, func: &'ast mut Function<Raw>) {
+            self.rename(&mut func.name);
+            visit_mut::visit_function_mut(self, func);
+        }
+    }
+
+    ItemRenamer { from, to }.visit_statement_mut(stmt);
+}
+
+/// Visits every part of a `CREATE SOURCE` statement that can contain
+/// references to other catalog items: its connector, `WITH` options, format,
+/// and envelope.
+///
+/// The statement is destructured exhaustively, so that adding a field to
+/// `CreateSourceStatement` forces a decision about whether migrations must
+/// visit it.
+#[allow(dead_code)] // Not yet used by any migration.
+fn visit_create_source_mut<'ast, V>(visitor: &mut V, stmt: &'ast mut CreateSourceStatement)
+where
+    V: VisitMut<'ast, Raw>,
+{
+    let CreateSourceStatement {
+        name: _,
+        col_names: _,
+        connector,
+        with_options,
+        format,
+        envelope,
+        if_not_exists: _,
+        materialized: _,
+    } = stmt;
+    visitor.visit_connector_mut(connector);
+    for option in with_options {
+        visitor.visit_sql_option_mut(option);
+    }
+    if let Some(format) = format {
+        visitor.visit_format_mut(format);
+    }
+    visitor.visit_envelope_mut(envelope);
+}
+
+/// Visits every part of a `CREATE SINK` statement that can contain references
+/// to other catalog items, other than the name of the item it exports: its
+/// connector, `WITH` options, format, envelope, and `AS OF` expression.
+///
+/// Like [`visit_create_source_mut`], the statement is destructured
+/// exhaustively.
+#[allow(dead_code)] // Not yet used by any migration.
+fn visit_create_sink_mut<'ast, V>(visitor: &mut V, stmt: &'ast mut CreateSinkStatement<Raw>)
+where
+    V: VisitMut<'ast, Raw>,
+{
+    let CreateSinkStatement {
+        name: _,
+        from: _,
+        connector,
+        with_options,
+        format,
+        envelope,
+        with_snapshot: _,
+        as_of,
+        if_not_exists: _,
+    } = stmt;
+    visitor.visit_connector_mut(connector);
+    for option in with_options {
+        visitor.visit_sql_option_mut(option);
+    }
+    if let Some(format) = format {
+        visitor.visit_format_mut(format);
+    }
+    if let Some(envelope) = envelope {
+        visitor.visit_envelope_mut(envelope);
+    }
+    if let Some(as_of) = as_of {
+        visitor.visit_expr_mut(as_of);
+    }
+}
+
+/// Rewrites all references to `mz_catalog.mz_now` in `stmt` to refer to
+/// `mz_catalog.mz_logical_timestamp` instead.
+fn canonicalize_mz_now(stmt: &mut Statement<Raw>) {
+    rename_referenced_item(
+        stmt,
+        &UnresolvedObjectName::qualified(&[MZ_CATALOG_SCHEMA, "mz_now"]),
+        &UnresolvedObjectName::qualified(&[MZ_CATALOG_SCHEMA, "mz_logical_timestamp"]),
+    );
+}
+
+/// Qualifies all unqualified operators with `pg_catalog`.
+struct OpNormalizer;
+
+impl<'ast> VisitMut<'ast, Raw> for OpNormalizer {
+    fn visit_op_mut(&mut self, op: &'ast mut Op) {
+        if op.namespace.is_empty() {
+            op.namespace = vec![Ident::new(PG_CATALOG_SCHEMA)];
+        }
+    }
+}
+
+/// Deserializes the stored definition of the item identified by `id` and
+/// `name`.
+fn deserialize_item(
+    id: GlobalId,
+    name: &FullName,
+    def: &[u8],
+) -> Result<SerializedCatalogItem, anyhow::Error> {
+    serde_json::from_slice(def)
+        .with_context(|| format!("catalog item {} \"{}\" has a corrupt definition", id, name))
+}
+
+/// Parses the `CREATE` statement of the item identified by `id` and `name`.
+fn parse_item(
+    id: GlobalId,
+    name: &FullName,
+    item: &SerializedCatalogItem,
+) -> Result<Statement<Raw>, anyhow::Error> {
+    Ok(sql::parse::parse(item.create_sql())
+        .with_context(|| format!("catalog item {} \"{}\" could not be parsed", id, name))?
+        .into_element())
+}
+
+#[cfg(test)]
+mod tests {
+    use sql::ast::SqlOption;
+
+    use super::*;
+
+    #[test]
+    fn test_rename_referenced_item() -> Result<(), anyhow::Error> {
+        let from = UnresolvedObjectName::qualified(&["s", "t"]);
+        let to = UnresolvedObjectName::qualified(&["s", "u"]);
+        let mut stmt = sql::parse::parse(
+            "CREATE VIEW v AS SELECT CAST(x AS s.t), s.t(1), t.x \
+             FROM s.t, t, other.t, s.t(2)",
+        )?
+        .into_element();
+        rename_referenced_item(&mut stmt, &from, &to);
+        assert_eq!(
+            stmt.to_ast_string(),
+            "CREATE VIEW v AS SELECT x::s.u, s.u(1), t.x FROM s.u, t, other.t, s.u(2)"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_canonicalize_mz_now() -> Result<(), anyhow::Error> {
+        let mut stmt = sql::parse::parse(
+            "CREATE VIEW \"materialize\".\"public\".\"v\" AS SELECT * \
+             FROM \"materialize\".\"public\".\"t\" \
+             WHERE \"mz_catalog\".\"mz_now\"() BETWEEN \"a\" AND \"b\"",
+        )?
+        .into_element();
+        canonicalize_mz_now(&mut stmt);
+        let create_sql = stmt.to_ast_string_stable();
+        assert_eq!(
+            create_sql,
+            "CREATE VIEW \"materialize\".\"public\".\"v\" AS SELECT * \
+             FROM \"materialize\".\"public\".\"t\" \
+             WHERE \"mz_catalog\".\"mz_logical_timestamp\"() BETWEEN \"a\" AND \"b\""
+        );
+
+        // Canonicalization must be idempotent, so that the migration does not
+        // rewrite already-migrated items.
+        let mut recanonicalized = sql::parse::parse(&create_sql)?.into_element();
+        canonicalize_mz_now(&mut recanonicalized);
+        assert_eq!(recanonicalized.to_ast_string_stable(), create_sql);
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit_create_source_mut() -> Result<(), anyhow::Error> {
+        struct OptionCollector(Vec<String>);
+
+        impl<'ast> VisitMut<'ast, Raw> for OptionCollector {
+            fn visit_sql_option_mut(&mut self, option: &'ast mut SqlOption) {
+                self.0.push(option.name().to_string());
+            }
+        }
+
+        let mut stmt = sql::parse::parse(
+            "CREATE SOURCE foo FROM KAFKA BROKER 'b' TOPIC 't' WITH (a = 1) \
+             FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY 'http://x' WITH (b = 2) \
+             ENVELOPE UPSERT FORMAT AVRO USING CONFLUENT SCHEMA REGISTRY 'http://y' WITH (c = 3)",
+        )?
+        .into_element();
+        let mut collector = OptionCollector(vec![]);
+        match &mut stmt {
+            Statement::CreateSource(stmt) => visit_create_source_mut(&mut collector, stmt),
+            _ => unreachable!(),
+        }
+        assert_eq!(collector.0, vec!["a", "b", "c"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_visit_create_sink_mut() -> Result<(), anyhow::Error> {
+        let mut stmt = sql::parse::parse(
+            "CREATE SINK foo FROM bar INTO KAFKA BROKER 'b' TOPIC 't' FORMAT BYTES AS OF 1 + 2",
+        )?
+        .into_element();
+        match &mut stmt {
+            Statement::CreateSink(stmt) => visit_create_sink_mut(&mut OpNormalizer, stmt),
+            _ => unreachable!(),
+        }
+        assert_eq!(
+            stmt.to_ast_string(),
+            "CREATE SINK foo FROM bar INTO KAFKA BROKER 'b' TOPIC 't' FORMAT BYTES \
+             WITH SNAPSHOT AS OF 1 OPERATOR(pg_catalog.+) 2"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_op_normalizer_roundtrip() -> Result<(), anyhow::Error> {
+        let sql = "CREATE VIEW v AS SELECT -a + b * c, a || b = c FROM t WHERE NOT a <> b";
+        let mut stmt = sql::parse::parse(sql)?.into_element();
+        let orig = stmt.clone();
+        match &mut stmt {
+            Statement::CreateView(CreateViewStatement { query, .. }) => {
+                OpNormalizer.visit_query_mut(query)
+            }
+            _ => unreachable!(),
+        }
+        let create_sql = stmt.to_ast_string_stable();
+        assert_eq!(
+            create_sql,
+            "CREATE VIEW \"v\" AS SELECT OPERATOR(\"pg_catalog\".-) \"a\" \
+             OPERATOR(\"pg_catalog\".+) \"b\" OPERATOR(\"pg_catalog\".*) \"c\", \
+             \"a\" OPERATOR(\"pg_catalog\".||) \"b\" OPERATOR(\"pg_catalog\".=) \"c\" \
+             FROM \"t\" WHERE NOT \"a\" OPERATOR(\"pg_catalog\".<>) \"b\""
+        );
+
+        // Reparsing the normalized statement must produce the same tree, so
+        // that qualification does not change operator precedence.
+        let reparsed = sql::parse::parse(&create_sql)?.into_element();
+        assert_eq!(reparsed, stmt);
+
+        // Stripping the qualification must recover the original statement.
+        struct OpDenormalizer;
+        impl<'ast> VisitMut<'ast, Raw> for OpDenormalizer {
+            fn visit_op_mut(&mut self, op: &'ast mut Op) {
+                op.namespace.clear();
+            }
+        }
+        let mut denormalized = reparsed;
+        match &mut denormalized {
+            Statement::CreateView(CreateViewStatement { query, .. }) => {
+                OpDenormalizer.visit_query_mut(query)
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(denormalized, orig);
+
+        // Normalization must be idempotent, so that the migration does not
+        // rewrite already-migrated items.
+        let mut renormalized = sql::parse::parse(&create_sql)?.into_element();
+        match &mut renormalized {
+            Statement::CreateView(CreateViewStatement { query, .. }) => {
+                OpNormalizer.visit_query_mut(query)
+            }
+            _ => unreachable!(),
+        }
+        assert_eq!(renormalized.to_ast_string_stable(), create_sql);
+        Ok(())
+    }
+}
@@ -8,7 +8,9 @@
 // by the Apache License, Version 2.0.
 
 use std::convert::TryFrom;
+use std::time::Duration;
 
+use rusqlite::backup::Backup;
 use rusqlite::params;
 use rusqlite::types::{FromSql, FromSqlError, ToSql, ToSqlOutput, Value, ValueRef};
 use rusqlite::OptionalExtension;
@@ -105,6 +107,10 @@ const MIGRATIONS: &[&str] = &[
     // Introduced in v0.7.0.
     "INSERT INTO schemas (database_id, name) VALUES
         (NULL, 'mz_internal');",
+    // Records the index of the content migration that last rewrote each item.
+    //
+    // Introduced in v0.7.1.
+    "ALTER TABLE items ADD COLUMN last_migration integer;",
     // Add new migrations here.
     //
     // Migrations should be preceded with a comment of the following form:
@@ -125,12 +131,34 @@ const MIGRATIONS: &[&str] = &[
 #[derive(Debug)]
 pub struct Connection {
     inner: rusqlite::Connection,
+    /// The index of the content migration that is currently running, if any.
+    migration: Option<u32>,
 }
 
 impl Connection {
     pub fn open(config: &Config) -> Result<(Connection, bool, Uuid), Error> {
-        let mut sqlite = rusqlite::Connection::open(&config.path)?;
+        let sqlite = rusqlite::Connection::open(&config.path)?;
+        Self::init(sqlite, config)
+    }
+
+    /// Like [`Connection::open`], but operates on an in-memory copy of the
+    /// catalog at `config.path`. The file is opened read-only, so neither
+    /// schema migrations nor any later writes through the returned connection
+    /// change it. Unlike `open`, fails if the catalog does not exist.
+    pub fn open_copy(config: &Config) -> Result<(Connection, bool, Uuid), Error> {
+        let file = rusqlite::Connection::open_with_flags(
+            &config.path,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+        let mut sqlite = rusqlite::Connection::open_in_memory()?;
+        Backup::new(&file, &mut sqlite)?.run_to_completion(-1, Duration::from_secs(0), None)?;
+        Self::init(sqlite, config)
+    }
 
+    fn init(
+        mut sqlite: rusqlite::Connection,
+        config: &Config,
+    ) -> Result<(Connection, bool, Uuid), Error> {
         // Validate application ID.
         let tx = sqlite.transaction()?;
         let app_id: i32 = tx.query_row("PRAGMA application_id", params![], |row| row.get(0))?;
@@ -166,7 +194,14 @@ impl Connection {
             Self::set_or_get_experimental_mode(&mut sqlite, config.experimental_mode)?;
         let cluster_id = Self::set_or_get_cluster_id(&mut sqlite)?;
 
-        Ok((Connection { inner: sqlite }, experimental_mode, cluster_id))
+        Ok((
+            Connection {
+                inner: sqlite,
+                migration: None,
+            },
+            experimental_mode,
+            cluster_id,
+        ))
     }
 
     /// Sets catalog's `experimental_mode` setting on initialization or gets
@@ -362,6 +397,28 @@ impl Connection {
             .collect()
     }
 
+    /// Loads the index of the content migration that last rewrote each item,
+    /// for those items that have been rewritten by a migration.
+    pub fn load_item_migrations(&self) -> Result<Vec<(GlobalId, u32)>, Error> {
+        self.inner
+            .prepare("SELECT gid, last_migration FROM items WHERE last_migration IS NOT NULL")?
+            .query_and_then(params![], |row| -> Result<_, Error> {
+                let id: SqlVal<GlobalId> = row.get(0)?;
+                let last_migration: u32 = row.get(1)?;
+                Ok((id.0, last_migration))
+            })?
+            .collect()
+    }
+
+    /// Marks the content migration with index `migration` as running, or
+    /// clears the mark if `migration` is `None`.
+    ///
+    /// While a migration is marked as running, every item updated through a
+    /// transaction is stamped with that migration's index.
+    pub fn set_running_migration(&mut self, migration: Option<u32>) {
+        self.migration = migration;
+    }
+
     pub fn allocate_id(&mut self) -> Result<GlobalId, Error> {
         let tx = self.inner.transaction()?;
         // SQLite doesn't support u64s, so we constrain ourselves to the more
@@ -380,12 +437,14 @@ impl Connection {
     pub fn transaction(&mut self) -> Result<Transaction, Error> {
         Ok(Transaction {
             inner: self.inner.transaction()?,
+            migration: self.migration,
         })
     }
 }
 
 pub struct Transaction<'a> {
     inner: rusqlite::Transaction<'a>,
+    migration: Option<u32>,
 }
 
 impl Transaction<'_> {
@@ -534,10 +593,18 @@ impl Transaction<'_> {
     }
 
     pub fn update_item(&self, id: GlobalId, item_name: &str, item: &[u8]) -> Result<(), Error> {
-        let n = self
-            .inner
-            .prepare_cached("UPDATE items SET name = ?, definition = ? WHERE gid = ?")?
-            .execute(params![item_name, item, SqlVal(id)])?;
+        let n = match self.migration {
+            None => self
+                .inner
+                .prepare_cached("UPDATE items SET name = ?, definition = ? WHERE gid = ?")?
+                .execute(params![item_name, item, SqlVal(id)])?,
+            Some(migration) => self
+                .inner
+                .prepare_cached(
+                    "UPDATE items SET name = ?, definition = ?, last_migration = ? WHERE gid = ?",
+                )?
+                .execute(params![item_name, item, migration, SqlVal(id)])?,
+        };
         assert!(n <= 1);
         if n == 1 {
             Ok(())
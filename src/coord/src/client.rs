@@ -12,11 +12,13 @@ use std::sync::Arc;
 
 use tokio::sync::{mpsc, oneshot, watch};
 
+use expr::GlobalId;
 use sql::ast::{Raw, Statement};
 use sql::plan::Params;
 
 use crate::command::{
-    Cancelled, Command, ExecuteResponse, NoSessionExecuteResponse, Response, StartupMessage,
+    Cancelled, Command, ExecuteResponse, NoSessionExecuteResponse, Notification, Response,
+    StartupMessage,
 };
 use crate::error::CoordError;
 use crate::session::{EndTransactionAction, Session};
@@ -47,12 +49,19 @@ impl Client {
         let (cancel_tx, cancel_rx) = watch::channel(Cancelled::NotCancelled);
         let cancel_tx = Arc::new(cancel_tx);
 
+        // The coordinator sends the notifications for the channels that the
+        // session listens on to `notify_tx`, for the connection to deliver
+        // when it is idle.
+        let (notify_tx, notify_rx) = mpsc::unbounded_channel();
+
         SessionClient {
             inner: self.clone(),
             session: Some(session),
             started_up: false,
             cancel_tx,
             cancel_rx,
+            notify_tx,
+            notify_rx,
         }
     }
 
@@ -111,6 +120,9 @@ pub struct SessionClient {
 
     pub cancel_tx: Arc<watch::Sender<Cancelled>>,
     pub cancel_rx: watch::Receiver<Cancelled>,
+
+    notify_tx: mpsc::UnboundedSender<Notification>,
+    pub notify_rx: mpsc::UnboundedReceiver<Notification>,
 }
 
 impl SessionClient {
@@ -126,6 +138,22 @@ impl SessionClient {
         }
     }
 
+    /// Notifies the coordinator that the statement currently executing in this
+    /// session was abandoned before it completed, because it exceeded the
+    /// session's `statement_timeout`, was canceled, or lost its client, so
+    /// that any dataflow work on its behalf, including the named sinks, is torn
+    /// down.
+    pub fn abandon_statement(&mut self, drop_sinks: Vec<GlobalId>) {
+        let conn_id = self.session().conn_id();
+        self.inner
+            .cmd_tx
+            .send(Command::AbandonStatement {
+                conn_id,
+                drop_sinks,
+            })
+            .expect("coordinator unexpectedly gone")
+    }
+
     pub fn reset_canceled(&mut self) {
         // Clear any cancellation message.
         // TODO(mjibson): This makes the use of .changed annoying since it will
@@ -147,10 +175,12 @@ impl SessionClient {
     pub async fn startup(&mut self) -> Result<Vec<StartupMessage>, CoordError> {
         assert!(!self.started_up);
         let cancel_tx = Arc::clone(&self.cancel_tx);
+        let notify_tx = self.notify_tx.clone();
         match self
             .send(|tx, session| Command::Startup {
                 session,
                 cancel_tx,
+                notify_tx,
                 tx,
             })
             .await
@@ -15,7 +15,8 @@ use derivative::Derivative;
 use tokio::sync::{mpsc, oneshot};
 
 use dataflow_types::PeekResponse;
-use repr::Row;
+use expr::GlobalId;
+use repr::{ColumnName, Row, ScalarType};
 use sql::ast::{FetchDirection, ObjectType, Raw, Statement};
 use sql::plan::ExecuteTimeout;
 use tokio::sync::watch;
@@ -29,6 +30,7 @@ pub enum Command {
     Startup {
         session: Session,
         cancel_tx: Arc<watch::Sender<Cancelled>>,
+        notify_tx: mpsc::UnboundedSender<Notification>,
         tx: oneshot::Sender<Response<Vec<StartupMessage>>>,
     },
 
@@ -64,6 +66,11 @@ pub enum Command {
         conn_id: u32,
     },
 
+    AbandonStatement {
+        conn_id: u32,
+        drop_sinks: Vec<GlobalId>,
+    },
+
     DumpCatalog {
         tx: oneshot::Sender<String>,
     },
@@ -141,6 +148,8 @@ pub enum ExecuteResponse {
     /// The requested source was created.
     CreatedSource {
         existed: bool,
+        /// The column types that were inferred for the source, if any.
+        inferred_column_types: Vec<(ColumnName, ScalarType)>,
     },
     /// The requested table was created.
     CreatedTable {
@@ -191,6 +200,10 @@ pub enum ExecuteResponse {
     },
     /// The specified number of rows were inserted into the requested table.
     Inserted(usize),
+    /// The session started listening on a notification channel.
+    Listened,
+    /// A notification was sent.
+    Notified,
     /// Rows will be delivered via the specified future.
     SendingRows(#[derivative(Debug = "ignore")] RowsFuture),
     /// The specified variable was set to a new value.
@@ -204,10 +217,22 @@ pub enum ExecuteResponse {
     Tailing {
         rx: mpsc::UnboundedReceiver<Vec<Row>>,
     },
+    /// The session stopped listening on one or all notification channels.
+    Unlistened,
     /// The specified number of rows were updated in the requested table.
     Updated(usize),
 }
 
+/// A notification for a session that is listening on its channel.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    /// The ID of the connection that sent the notification, or 0 if it was
+    /// sent by a sink.
+    pub conn_id: u32,
+    pub channel: String,
+    pub payload: String,
+}
+
 /// The state of a cancellation request.
 #[derive(Debug, Clone, Copy)]
 pub enum Cancelled {
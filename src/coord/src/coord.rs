@@ -17,15 +17,16 @@
 //! must accumulate to the same value as would an un-compacted trace.
 
 use std::cmp;
-use std::collections::{BTreeMap, HashMap};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::convert::{TryFrom, TryInto};
 use std::iter;
 use std::mem;
 use std::os::unix::ffi::OsStringExt;
 use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::thread::{self, JoinHandle};
-use std::time::{Duration, SystemTime};
+use std::time::{Duration, Instant, SystemTime};
 
 use anyhow::{anyhow, Context};
 use derivative::Derivative;
@@ -35,7 +36,7 @@ use futures::stream::{self, StreamExt};
 use timely::communication::WorkerGuards;
 use timely::progress::{Antichain, ChangeBatch, Timestamp as _};
 use tokio::runtime::{Handle, Runtime};
-use tokio::sync::{mpsc, watch};
+use tokio::sync::{mpsc, oneshot, watch};
 use tokio_stream::wrappers::UnboundedReceiverStream;
 use uuid::Uuid;
 
@@ -45,23 +46,26 @@ use dataflow_types::logging::LoggingConfig as DataflowLoggingConfig;
 use dataflow_types::SinkEnvelope;
 use dataflow_types::{
     AvroOcfSinkConnector, DataflowDesc, IndexDesc, KafkaSinkConnector, PeekResponse, SinkConnector,
-    SourceConnector, TailSinkConnector, TimestampSourceUpdate, Update,
+    SinkConnectorBuilder, SourceConnector, TailOutput, TailSinkConnector, TimestampSourceUpdate,
+    Update,
 };
 use expr::{
-    ExprHumanizer, GlobalId, Id, MirRelationExpr, MirScalarExpr, NullaryFunc,
+    ColumnOrder, ExprHumanizer, GlobalId, Id, MirRelationExpr, MirScalarExpr, NullaryFunc,
     OptimizedMirRelationExpr, RowSetFinishing, SourceInstanceId,
 };
 use ore::collections::CollectionExt;
 use ore::str::StrExt;
 use ore::thread::JoinHandleExt;
 use repr::adt::array::ArrayDimension;
-use repr::{ColumnName, Datum, RelationDesc, RelationType, Row, RowPacker, Timestamp};
+use repr::{
+    ColumnName, Datum, RelationDesc, RelationType, Row, RowArena, RowPacker, ScalarType, Timestamp,
+};
 use sql::ast::display::AstDisplay;
 use sql::ast::{
     CreateIndexStatement, CreateTableStatement, DropObjectsStatement, ExplainOptions, ExplainStage,
     FetchStatement, Ident, ObjectType, Raw, Statement,
 };
-use sql::catalog::{Catalog as _, CatalogError};
+use sql::catalog::{Catalog as _, CatalogError, CatalogItem as _};
 use sql::names::{DatabaseSpecifier, FullName, SchemaName};
 use sql::plan::StatementDesc;
 use sql::plan::{
@@ -72,16 +76,18 @@ use transform::Optimizer;
 use self::arrangement_state::{ArrangementFrontiers, Frontiers};
 use crate::cache::{CacheConfig, Cacher};
 use crate::catalog::builtin::{
-    BUILTINS, MZ_ARRAY_TYPES, MZ_AVRO_OCF_SINKS, MZ_BASE_TYPES, MZ_COLUMNS, MZ_DATABASES,
-    MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS, MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES,
-    MZ_PSEUDO_TYPES, MZ_ROLES, MZ_SCHEMAS, MZ_SINKS, MZ_SOURCES, MZ_TABLES, MZ_TYPES, MZ_VIEWS,
-    MZ_VIEW_FOREIGN_KEYS, MZ_VIEW_KEYS,
+    BUILTINS, MZ_ACTIVE_PEEKS, MZ_ARRAY_TYPES, MZ_AVRO_OCF_SINKS, MZ_BASE_TYPES, MZ_COLUMNS,
+    MZ_DATABASES, MZ_FRONTIERS, MZ_FUNCTIONS, MZ_INDEXES, MZ_INDEX_COLUMNS,
+    MZ_ITEM_MIGRATION_STAMPS, MZ_KAFKA_SINKS, MZ_LIST_TYPES, MZ_MAP_TYPES,
+    MZ_PEEK_DURATION_HISTOGRAM, MZ_PSEUDO_TYPES, MZ_ROLES, MZ_SCHEMAS, MZ_SINKS, MZ_SOURCES,
+    MZ_SOURCE_UPSTREAM_OFFSETS, MZ_TABLES, MZ_TYPES, MZ_VIEWS, MZ_VIEW_FOREIGN_KEYS, MZ_VIEW_KEYS,
 };
 use crate::catalog::{
     self, Catalog, CatalogItem, Func, Index, SinkConnectorState, Type, TypeInner,
 };
 use crate::command::{
-    Cancelled, Command, ExecuteResponse, NoSessionExecuteResponse, Response, StartupMessage,
+    Cancelled, Command, ExecuteResponse, NoSessionExecuteResponse, Notification, Response,
+    StartupMessage,
 };
 use crate::error::CoordError;
 use crate::session::{
@@ -99,8 +105,11 @@ pub enum Message {
     Command(Command),
     Worker(WorkerFeedbackWithMeta),
     AdvanceSourceTimestamp(AdvanceSourceTimestamp),
+    AdvanceSourceUpstream(AdvanceSourceUpstream),
     StatementReady(StatementReady),
     SinkConnectorReady(SinkConnectorReady),
+    PeekFinished(PeekFinished),
+    SinkNotifications(SinkNotifications),
     Shutdown,
 }
 
@@ -110,6 +119,74 @@ pub struct AdvanceSourceTimestamp {
     pub update: TimestampSourceUpdate,
 }
 
+/// The latest known position of a source's upstream, for each of its
+/// partitions.
+#[derive(Debug)]
+pub struct AdvanceSourceUpstream {
+    pub id: SourceInstanceId,
+    pub partitions: Vec<(String, UpstreamPosition)>,
+}
+
+/// The completion of a peek that was served by the dataflow layer.
+#[derive(Debug)]
+pub struct PeekFinished {
+    /// The ID that the peek was assigned in `mz_internal.mz_active_peeks`.
+    pub query_id: u64,
+    /// The index that the peek read from, or `None` if the peek required a
+    /// transient dataflow.
+    pub index_id: Option<GlobalId>,
+    /// The time from sequencing the peek to receiving all of its results.
+    pub duration: Duration,
+}
+
+/// The notifications that a NOTIFY sink sent for a batch of new rows.
+#[derive(Debug)]
+pub struct SinkNotifications {
+    pub channel: String,
+    pub payloads: Vec<String>,
+}
+
+/// A session that can receive notifications.
+#[derive(Debug)]
+struct Listener {
+    /// Delivers notifications to the session's connection.
+    tx: mpsc::UnboundedSender<Notification>,
+    /// The channels that the session is listening on.
+    channels: HashSet<String>,
+}
+
+/// A peek that is listed in `mz_internal.mz_active_peeks`.
+#[derive(Debug)]
+struct ActivePeek {
+    /// The ID of the connection that issued the peek.
+    conn_id: u32,
+    /// The index that the peek reads from, or `None` if the peek required a
+    /// transient dataflow.
+    index_id: Option<GlobalId>,
+    /// Set by whichever of the peek's completion and its cancelation by
+    /// `CANCEL QUERY` happens first, so that exactly one of them takes
+    /// effect.
+    settled: Arc<AtomicBool>,
+}
+
+impl ActivePeek {
+    fn pack_row(&self, query_id: u64) -> Row {
+        Row::pack_slice(&[
+            Datum::Int64(query_id as i64),
+            Datum::Int64(i64::from(self.conn_id)),
+            Datum::from(self.index_id.map(|id| id.to_string()).as_deref()),
+        ])
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum UpstreamPosition {
+    /// The offset of the next message that will be appended to the partition.
+    Offset(i64),
+    /// The size of the upstream object, in bytes.
+    Bytes(i64),
+}
+
 #[derive(Derivative)]
 #[derivative(Debug)]
 pub struct StatementReady {
@@ -183,6 +260,34 @@ pub struct Coordinator {
     /// Map from connection id to a tokio::sync::watch sender that can be used to
     /// signal to the receiver end that a cancel message has been sent.
     cancel: HashMap<u32, Arc<watch::Sender<Cancelled>>>,
+    /// The rows currently in `mz_source_upstream_offsets`, keyed by source and
+    /// partition.
+    source_upstream: HashMap<(GlobalId, String), Row>,
+    /// The counts in `mz_internal.mz_peek_durations`, keyed by index and the
+    /// upper bound of the duration bucket in nanoseconds. As in
+    /// `mz_catalog.mz_peek_durations`, counts are cumulative since startup,
+    /// and only buckets with a nonzero count have an entry.
+    peek_durations: HashMap<(Option<GlobalId>, i64), i64>,
+    /// The peeks in `mz_internal.mz_active_peeks`, keyed by query ID.
+    active_peeks: HashMap<u64, ActivePeek>,
+    /// The rows currently in `mz_internal.mz_frontiers`, keyed by index.
+    frontiers: HashMap<GlobalId, Row>,
+    /// The query ID to assign to the next peek.
+    next_query_id: u64,
+    /// For each table with declared keys, the number of committed rows with
+    /// each value of each key. Consulted by `INSERT ... ON CONFLICT`.
+    table_key_counts: HashMap<GlobalId, KeyCounts>,
+    /// The indexes that each active `TAIL` reads from, keyed by the ID of the
+    /// `TAIL`'s sink.
+    active_tails: HashMap<GlobalId, Vec<GlobalId>>,
+    /// Indexes on temporary items that were dropped from the catalog while an
+    /// active `TAIL` was reading from them. Their dataflows are torn down
+    /// once no active `TAIL` reads from them.
+    orphaned_indexes: HashSet<GlobalId>,
+    /// The sessions that can receive notifications, keyed by connection ID.
+    listeners: HashMap<u32, Listener>,
+    /// The channel over which NOTIFY sinks send their notifications.
+    notify_tx: mpsc::UnboundedSender<(String, Vec<String>)>,
 }
 
 impl Coordinator {
@@ -299,6 +404,7 @@ impl Coordinator {
                         sink.with_snapshot,
                         self.determine_frontier(sink.as_of, sink.from)?,
                         *id,
+                        self.notify_tx.clone(),
                     )
                     .await
                     .with_context(|| format!("recreating sink {}", name))?;
@@ -359,6 +465,20 @@ impl Coordinator {
             }
         }
 
+        // Announce which content migration last rewrote each item.
+        let item_migrations = self.catalog.item_migrations()?;
+        self.update_catalog_view(
+            MZ_ITEM_MIGRATION_STAMPS.id,
+            item_migrations.into_iter().map(|(id, last_migration)| {
+                let row = Row::pack_slice(&[
+                    Datum::String(&id.to_string()),
+                    Datum::Int32(last_migration as i32),
+                ]);
+                (row, 1)
+            }),
+        )
+        .await;
+
         Ok(())
     }
 
@@ -370,6 +490,7 @@ impl Coordinator {
         mut self,
         cmd_rx: mpsc::UnboundedReceiver<Command>,
         feedback_rx: mpsc::UnboundedReceiver<WorkerFeedbackWithMeta>,
+        notify_rx: mpsc::UnboundedReceiver<(String, Vec<String>)>,
     ) {
         let (internal_cmd_tx, internal_cmd_rx) = mpsc::unbounded_channel();
 
@@ -379,6 +500,16 @@ impl Coordinator {
 
         let feedback_stream = UnboundedReceiverStream::new(feedback_rx).map(Message::Worker);
 
+        // The senders of notifications live as long as the coordinator and its
+        // sinks, so stop receiving notifications at shutdown, when
+        // `notify_shutdown_tx` is dropped.
+        let (notify_shutdown_tx, notify_shutdown_rx) = oneshot::channel::<()>();
+        let notify_stream = UnboundedReceiverStream::new(notify_rx)
+            .map(|(channel, payloads)| {
+                Message::SinkNotifications(SinkNotifications { channel, payloads })
+            })
+            .take_until(notify_shutdown_rx);
+
         let (ts_tx, ts_rx) = std::sync::mpsc::channel();
         let mut timestamper =
             Timestamper::new(&self.timestamp_config, internal_cmd_tx.clone(), ts_rx);
@@ -395,6 +526,7 @@ impl Coordinator {
             // external commands (`cmd_stream`).
             UnboundedReceiverStream::new(internal_cmd_rx).boxed(),
             feedback_stream.boxed(),
+            notify_stream.boxed(),
             cmd_stream.boxed(),
         ]);
 
@@ -411,6 +543,13 @@ impl Coordinator {
                 Message::AdvanceSourceTimestamp(advance) => {
                     self.message_advance_source_timestamp(advance).await
                 }
+                Message::AdvanceSourceUpstream(advance) => {
+                    self.message_advance_source_upstream(advance).await
+                }
+                Message::PeekFinished(finished) => self.message_peek_finished(finished).await,
+                Message::SinkNotifications(notifications) => {
+                    self.message_sink_notifications(notifications)
+                }
                 Message::Shutdown => {
                     self.message_shutdown(&ts_tx).await;
                     break;
@@ -442,6 +581,7 @@ impl Coordinator {
         // Cleanly drain any pending messages from the worker before shutting
         // down.
         drop(internal_cmd_tx);
+        drop(notify_shutdown_tx);
         while messages.next().await.is_some() {}
     }
 
@@ -455,9 +595,12 @@ impl Coordinator {
     ) {
         match message {
             WorkerFeedback::FrontierUppers(updates) => {
+                let mut ids = vec![];
                 for (name, changes) in updates {
                     self.update_upper(&name, changes);
+                    ids.push(name);
                 }
+                self.report_frontiers(ids).await;
                 self.maintenance().await;
             }
             WorkerFeedback::DroppedSource(source_id) => {
@@ -546,6 +689,34 @@ impl Coordinator {
         }
     }
 
+    fn message_sink_notifications(
+        &self,
+        SinkNotifications { channel, payloads }: SinkNotifications,
+    ) {
+        for payload in payloads {
+            self.notify(0, &channel, payload);
+        }
+    }
+
+    /// Delivers a notification to every session that is listening on
+    /// `channel`.
+    ///
+    /// Delivery is best effort: sessions that are not listening at this
+    /// moment, including those that have not connected yet or whose connection
+    /// is lost, never receive the notification.
+    fn notify(&self, conn_id: u32, channel: &str, payload: String) {
+        for listener in self.listeners.values() {
+            if listener.channels.contains(channel) {
+                // The session may have disconnected without terminating yet.
+                let _ = listener.tx.send(Notification {
+                    conn_id,
+                    channel: channel.to_owned(),
+                    payload: payload.clone(),
+                });
+            }
+        }
+    }
+
     async fn message_shutdown(&mut self, ts_tx: &std::sync::mpsc::Sender<TimestampMessage>) {
         ts_tx.send(TimestampMessage::Shutdown).unwrap();
         self.broadcast(SequencedCommand::Shutdown);
@@ -558,6 +729,83 @@ impl Coordinator {
         self.broadcast(SequencedCommand::AdvanceSourceTimestamp { id, update });
     }
 
+    async fn message_advance_source_upstream(
+        &mut self,
+        AdvanceSourceUpstream { id, partitions }: AdvanceSourceUpstream,
+    ) {
+        let source_id = id.source_id;
+        if self.catalog.try_get_by_id(source_id).is_none() {
+            // The source was dropped while the update was in flight.
+            return;
+        }
+        let mut updates = vec![];
+        for (partition_id, position) in partitions {
+            let (offset, bytes) = match position {
+                UpstreamPosition::Offset(offset) => (Some(offset), None),
+                UpstreamPosition::Bytes(bytes) => (None, Some(bytes)),
+            };
+            let row = Row::pack_slice(&[
+                Datum::String(&source_id.to_string()),
+                Datum::String(&partition_id),
+                Datum::from(offset),
+                Datum::from(bytes),
+            ]);
+            match self
+                .source_upstream
+                .insert((source_id, partition_id), row.clone())
+            {
+                Some(old) if old == row => continue,
+                Some(old) => updates.push((old, -1)),
+                None => (),
+            }
+            updates.push((row, 1));
+        }
+        if !updates.is_empty() {
+            self.update_catalog_view(MZ_SOURCE_UPSTREAM_OFFSETS.id, updates)
+                .await;
+        }
+    }
+
+    async fn message_peek_finished(
+        &mut self,
+        PeekFinished {
+            query_id,
+            index_id,
+            duration,
+        }: PeekFinished,
+    ) {
+        self.retire_active_peeks(vec![query_id]).await;
+        if let Some(id) = index_id {
+            if self.catalog.try_get_by_id(id).is_none() {
+                // The index was dropped while the peek was in flight.
+                return;
+            }
+        }
+        // Like `mz_catalog.mz_peek_durations`, bucket durations by the next
+        // power of two nanoseconds.
+        let duration_ns =
+            i64::try_from(duration.as_nanos().next_power_of_two()).unwrap_or(i64::MAX);
+        let pack_row = |count: i64| {
+            Row::pack_slice(&[
+                Datum::from(index_id.map(|id| id.to_string()).as_deref()),
+                Datum::Int64(duration_ns),
+                Datum::Int64(count),
+            ])
+        };
+        let count = self
+            .peek_durations
+            .entry((index_id, duration_ns))
+            .or_insert(0);
+        let mut updates = vec![];
+        if *count > 0 {
+            updates.push((pack_row(*count), -1));
+        }
+        *count += 1;
+        updates.push((pack_row(*count), 1));
+        self.update_catalog_view(MZ_PEEK_DURATION_HISTOGRAM.id, updates)
+            .await;
+    }
+
     async fn message_command(
         &mut self,
         cmd: Command,
@@ -567,6 +815,7 @@ impl Coordinator {
             Command::Startup {
                 session,
                 cancel_tx,
+                notify_tx,
                 tx,
             } => {
                 if let Err(e) = self.catalog.create_temporary_schema(session.conn_id()) {
@@ -595,6 +844,13 @@ impl Coordinator {
                 }
 
                 self.cancel.insert(session.conn_id(), cancel_tx);
+                self.listeners.insert(
+                    session.conn_id(),
+                    Listener {
+                        tx: notify_tx,
+                        channels: HashSet::new(),
+                    },
+                );
 
                 ClientTransmitter::new(tx).send(Ok(messages), session)
             }
@@ -659,12 +915,15 @@ impl Coordinator {
                             | &TransactionStatus::InTransaction(_) => match stmt {
                                 // Statements that are safe in a transaction. We still need to verify that we
                                 // don't interleave reads and writes since we can't perform those serializably.
-                                Statement::Close(_)
+                                Statement::CancelQuery(_)
+                                | Statement::Close(_)
                                 | Statement::Commit(_)
                                 | Statement::Declare(_)
                                 | Statement::Discard(_)
                                 | Statement::Explain(_)
                                 | Statement::Fetch(_)
+                                | Statement::Listen(_)
+                                | Statement::Notify(_)
                                 | Statement::Rollback(_)
                                 | Statement::SetTransaction(_)
                                 | Statement::ShowColumns(_)
@@ -677,7 +936,8 @@ impl Coordinator {
                                 | Statement::ShowIndexes(_)
                                 | Statement::ShowObjects(_)
                                 | Statement::ShowVariable(_)
-                                | Statement::StartTransaction(_) => {
+                                | Statement::StartTransaction(_)
+                                | Statement::Unlisten(_) => {
                                     // Always safe.
                                 }
 
@@ -710,6 +970,7 @@ impl Coordinator {
                                 // Statements below must by run singly (in Started).
                                 Statement::AlterIndexOptions(_)
                                 | Statement::AlterObjectRename(_)
+                                | Statement::AlterSourceOptions(_)
                                 | Statement::CreateDatabase(_)
                                 | Statement::CreateIndex(_)
                                 | Statement::CreateRole(_)
@@ -784,8 +1045,15 @@ impl Coordinator {
                             finishing,
                             copy_to,
                         } => {
-                            self.sequence_peek(conn_id, source, when, finishing, copy_to)
-                                .await?
+                            self.sequence_peek(
+                                internal_cmd_tx,
+                                conn_id,
+                                source,
+                                when,
+                                finishing,
+                                copy_to,
+                            )
+                            .await?
                         }
 
                         Plan::SendRows(rows) => send_immediate_rows(rows),
@@ -827,6 +1095,13 @@ impl Coordinator {
                 self.handle_cancel(conn_id).await;
             }
 
+            Command::AbandonStatement {
+                conn_id,
+                drop_sinks,
+            } => {
+                self.handle_abandon_statement(conn_id, drop_sinks).await;
+            }
+
             Command::DumpCatalog { tx } => {
                 let _ = tx.send(self.catalog.dump());
             }
@@ -884,6 +1159,62 @@ impl Coordinator {
         }
     }
 
+    /// Updates `mz_internal.mz_frontiers` to report the current frontiers of
+    /// the specified indexes, or to stop reporting those that no longer exist.
+    /// Indexes that are not in the catalog, like those of one-shot dataflows,
+    /// are not reported.
+    ///
+    /// The updates are written at `closed_up_to` rather than at a new write
+    /// timestamp, as choosing one would force the tables to advance, which
+    /// would in turn advance the frontiers of their indexes and produce more
+    /// updates, forever. The updates instead become visible the next time the
+    /// tables advance.
+    async fn report_frontiers(&mut self, ids: Vec<GlobalId>) {
+        let mut updates = vec![];
+        for id in ids {
+            let row = match (self.indexes.get(&id), self.catalog.try_get_by_id(id)) {
+                (Some(frontiers), Some(entry)) => match entry.item() {
+                    CatalogItem::Index(index) => {
+                        let since = frontiers.since.elements().first();
+                        let upper = frontiers.upper.frontier().iter().next();
+                        Some(Row::pack_slice(&[
+                            Datum::String(&id.to_string()),
+                            Datum::String(&index.on.to_string()),
+                            Datum::from(since.map(|t| *t as i64)),
+                            Datum::from(upper.map(|t| *t as i64)),
+                        ]))
+                    }
+                    _ => None,
+                },
+                _ => None,
+            };
+            let old = match &row {
+                Some(row) => self.frontiers.insert(id, row.clone()),
+                None => self.frontiers.remove(&id),
+            };
+            if old == row {
+                continue;
+            }
+            updates.extend(old.map(|old| (old, -1)));
+            updates.extend(row.map(|row| (row, 1)));
+        }
+        if !updates.is_empty() {
+            let timestamp = self.closed_up_to;
+            let updates = updates
+                .into_iter()
+                .map(|(row, diff)| Update {
+                    row,
+                    diff,
+                    timestamp,
+                })
+                .collect();
+            self.broadcast(SequencedCommand::Insert {
+                id: MZ_FRONTIERS.id,
+                updates,
+            });
+        }
+    }
+
     /// Perform maintenance work associated with the coordinator.
     ///
     /// Primarily, this involves sequencing compaction commands, which should be
@@ -1006,7 +1337,11 @@ impl Coordinator {
     /// the named `conn_id`.
     async fn handle_cancel(&mut self, conn_id: u32) {
         // Tell dataflow to cancel any pending peeks.
-        self.broadcast(SequencedCommand::CancelPeek { conn_id });
+        self.broadcast(SequencedCommand::CancelPeek {
+            conn_id,
+            query_id: None,
+        });
+        self.retire_conn_active_peeks(conn_id).await;
 
         // Inform the session (if it asks) about the cancellation.
         if let Some(cancel) = self.cancel.get_mut(&conn_id) {
@@ -1014,6 +1349,82 @@ impl Coordinator {
         }
     }
 
+    /// Instruct the dataflow layer to tear down any pending peeks for the named
+    /// `conn_id`, whose current statement was abandoned before it completed,
+    /// along with the sinks of any `TAIL`s that the statement ended.
+    ///
+    /// Unlike [`Coordinator::handle_cancel`], the session is not informed, as
+    /// the connection has already reported the error to the client.
+    async fn handle_abandon_statement(&mut self, conn_id: u32, drop_sinks: Vec<GlobalId>) {
+        self.broadcast(SequencedCommand::CancelPeek {
+            conn_id,
+            query_id: None,
+        });
+        self.retire_conn_active_peeks(conn_id).await;
+        self.drop_sinks(drop_sinks).await;
+    }
+
+    /// Cancels the peek with the given ID in `mz_internal.mz_active_peeks`
+    /// on behalf of another session.
+    ///
+    /// Only the peek with that ID is canceled, which then reports the
+    /// cancelation to its own client. Unlike a cancel request, the peek's
+    /// session is not told to cancel its current statement, which may no
+    /// longer be the peek: a peek stays listed until the coordinator learns
+    /// that its results arrived, by which time its connection may have moved
+    /// on to another statement.
+    ///
+    /// Reports whether there was a peek to cancel, as the peek may have
+    /// completed since it was listed. A peek whose results all arrived is not
+    /// canceled, even if the coordinator has yet to learn that it finished.
+    async fn sequence_cancel_query(&mut self, query_id: u64) -> ExecuteResponse {
+        let canceled = match self.active_peeks.get(&query_id) {
+            Some(peek) if !peek.settled.swap(true, Ordering::SeqCst) => {
+                let conn_id = peek.conn_id;
+                self.broadcast(SequencedCommand::CancelPeek {
+                    conn_id,
+                    query_id: Some(query_id),
+                });
+                self.retire_active_peeks(vec![query_id]).await;
+                true
+            }
+            _ => false,
+        };
+        send_immediate_rows(vec![Row::pack_slice(&[Datum::from(canceled)])])
+    }
+
+    /// Removes the peeks with the given IDs from
+    /// `mz_internal.mz_active_peeks`, ignoring any that were already removed.
+    async fn retire_active_peeks(&mut self, query_ids: Vec<u64>) {
+        let updates: Vec<_> = query_ids
+            .into_iter()
+            .filter_map(|query_id| {
+                self.active_peeks
+                    .remove(&query_id)
+                    .map(|peek| (peek.pack_row(query_id), -1))
+            })
+            .collect();
+        if !updates.is_empty() {
+            self.update_catalog_view(MZ_ACTIVE_PEEKS.id, updates).await;
+        }
+    }
+
+    /// Removes the peeks of the named connection from
+    /// `mz_internal.mz_active_peeks`.
+    ///
+    /// Used when the connection's peeks are torn down, as the results of a
+    /// torn down peek may never be awaited and so may never report that the
+    /// peek finished.
+    async fn retire_conn_active_peeks(&mut self, conn_id: u32) {
+        let query_ids = self
+            .active_peeks
+            .iter()
+            .filter(|(_, peek)| peek.conn_id == conn_id)
+            .map(|(query_id, _)| *query_id)
+            .collect();
+        self.retire_active_peeks(query_ids).await;
+    }
+
     /// Handle termination of a client session.
     ///
     // This cleans up any state in the coordinator associated with the session.
@@ -1026,12 +1437,23 @@ impl Coordinator {
             .drop_temporary_schema(session.conn_id())
             .expect("unable to drop temporary schema");
         self.cancel.remove(&session.conn_id());
+        self.listeners.remove(&session.conn_id());
+        self.retire_conn_active_peeks(session.conn_id()).await;
     }
 
     // Removes all temporary items created by the specified connection, though
     // not the temporary schema itself.
     async fn drop_temp_items(&mut self, conn_id: u32) {
         let ops = self.catalog.drop_temp_item_ops(conn_id);
+        // The dataflows of indexes that an active TAIL reads from are kept
+        // until the TAIL ends, so that the TAIL runs until it is canceled.
+        for op in &ops {
+            if let catalog::Op::DropItem(id) = op {
+                if self.active_tails.values().any(|ids| ids.contains(id)) {
+                    self.orphaned_indexes.insert(*id);
+                }
+            }
+        }
         self.catalog_transact(ops)
             .await
             .expect("unable to drop temporary items for conn_id");
@@ -1156,10 +1578,17 @@ impl Coordinator {
 
     async fn report_column_updates(
         &mut self,
+        item: &CatalogItem,
         desc: &RelationDesc,
         global_id: GlobalId,
         diff: isize,
     ) {
+        // The keys of a table are those declared by its constraints, rather
+        // than any inferred from its relation type.
+        let keys = match item {
+            CatalogItem::Table(table) => &table.keys,
+            _ => &desc.typ().keys,
+        };
         for (i, (column_name, column_type)) in desc.iter().enumerate() {
             self.update_catalog_view(
                 MZ_COLUMNS.id,
@@ -1174,6 +1603,8 @@ impl Coordinator {
                         Datum::Int64(i as i64 + 1),
                         Datum::from(column_type.nullable),
                         Datum::String(pgrepr::Type::from(&column_type.scalar_type).name()),
+                        Datum::String(&full_type_name(&column_type.scalar_type)),
+                        Datum::from(keys.iter().any(|key| key.contains(&i))),
                     ]),
                     diff,
                 )),
@@ -1235,6 +1666,10 @@ impl Coordinator {
                     Datum::Int32(oid as i32),
                     Datum::String(name),
                     Datum::String(&index.on.to_string()),
+                    match index.memory_limit_bytes {
+                        Some(n) => Datum::Int64(i64::try_from(n).expect("invalid memory limit")),
+                        None => Datum::Null,
+                    },
                 ]),
                 diff,
             )),
@@ -1439,13 +1874,32 @@ impl Coordinator {
                     arg_ids.iter().map(|id| Datum::String(&id)),
                 )
                 .unwrap();
+            packer
+                .push_array(
+                    &[ArrayDimension {
+                        lower_bound: 1,
+                        length: func_impl_details.arg_oids.len(),
+                    }],
+                    func_impl_details
+                        .arg_oids
+                        .iter()
+                        .map(|oid| Datum::Int32(*oid as i32)),
+                )
+                .unwrap();
             let row = packer.finish();
-            let arg_ids = row.unpack_first();
+            let mut arrays = row.iter();
+            let arg_ids = arrays.next().unwrap();
+            let arg_oids = arrays.next().unwrap();
 
             let variadic_id = match func_impl_details.variadic_oid {
                 Some(oid) => Some(self.catalog.get_by_oid(&oid).id().to_string()),
                 None => None,
             };
+            let ret_id = self
+                .catalog
+                .get_by_oid(&func_impl_details.return_oid)
+                .id()
+                .to_string();
 
             self.update_catalog_view(
                 MZ_FUNCTIONS.id,
@@ -1457,6 +1911,9 @@ impl Coordinator {
                         Datum::String(name),
                         arg_ids,
                         Datum::from(variadic_id.as_deref()),
+                        arg_oids,
+                        Datum::String(&ret_id),
+                        Datum::from(func_impl_details.return_is_set),
                     ]),
                     diff,
                 )),
@@ -1509,9 +1966,17 @@ impl Coordinator {
                 source,
                 if_not_exists,
                 materialized,
+                inferred_column_types,
             } => tx.send(
-                self.sequence_create_source(pcx, name, source, if_not_exists, materialized)
-                    .await,
+                self.sequence_create_source(
+                    pcx,
+                    name,
+                    source,
+                    if_not_exists,
+                    materialized,
+                    inferred_column_types,
+                )
+                .await,
                 session,
             ),
 
@@ -1583,6 +2048,8 @@ impl Coordinator {
                 tx.send(self.sequence_drop_items(items, ty).await, session)
             }
 
+            Plan::ExplainDrop { items } => tx.send(self.sequence_explain_drop(items), session),
+
             Plan::EmptyQuery => tx.send(Ok(ExecuteResponse::EmptyQuery), session),
 
             Plan::ShowAllVariables => {
@@ -1621,8 +2088,15 @@ impl Coordinator {
                 finishing,
                 copy_to,
             } => tx.send(
-                self.sequence_peek(session.conn_id(), source, when, finishing, copy_to)
-                    .await,
+                self.sequence_peek(
+                    internal_cmd_tx,
+                    session.conn_id(),
+                    source,
+                    when,
+                    finishing,
+                    copy_to,
+                )
+                .await,
                 session,
             ),
 
@@ -1634,6 +2108,8 @@ impl Coordinator {
                 emit_progress,
                 object_columns,
                 desc,
+                output,
+                order_by,
             } => tx.send(
                 self.sequence_tail(
                     &mut session,
@@ -1644,6 +2120,8 @@ impl Coordinator {
                     emit_progress,
                     object_columns,
                     desc,
+                    output,
+                    order_by,
                 )
                 .await,
                 session,
@@ -1680,8 +2158,14 @@ impl Coordinator {
                 session,
             ),
 
-            Plan::Insert { id, values } => tx.send(
-                self.sequence_insert(&mut session, id, values).await,
+            Plan::Insert {
+                id,
+                values,
+                conflict_keys,
+                checks,
+            } => tx.send(
+                self.sequence_insert(&mut session, id, values, conflict_keys, checks)
+                    .await,
                 session,
             ),
 
@@ -1708,6 +2192,16 @@ impl Coordinator {
                 session,
             ),
 
+            Plan::AlterSourceSetOptions {
+                id,
+                create_sql,
+                config_options,
+            } => tx.send(
+                self.sequence_alter_source_set_options(id, create_sql, config_options)
+                    .await,
+                session,
+            ),
+
             Plan::DiscardTemp => {
                 self.drop_temp_items(session.conn_id()).await;
                 tx.send(Ok(ExecuteResponse::DiscardedTemp), session);
@@ -1718,6 +2212,7 @@ impl Coordinator {
                     self.drop_temp_items(session.conn_id()).await;
                     let drop_sinks = session.reset();
                     self.drop_sinks(drop_sinks).await;
+                    self.unlisten(session.conn_id(), None);
                     Ok(ExecuteResponse::DiscardedAll)
                 } else {
                     Err(CoordError::OperationProhibitsTransaction(
@@ -1748,6 +2243,10 @@ impl Coordinator {
                 session,
             ),
 
+            Plan::CancelQuery { query_id } => {
+                tx.send(Ok(self.sequence_cancel_query(query_id).await), session)
+            }
+
             Plan::Close { name } => {
                 if session.remove_portal(&name) {
                     tx.send(Ok(ExecuteResponse::ClosedCursor), session)
@@ -1755,6 +2254,36 @@ impl Coordinator {
                     tx.send(Err(CoordError::UnknownCursor(name)), session)
                 }
             }
+
+            Plan::Listen { channel } => {
+                if let Some(listener) = self.listeners.get_mut(&session.conn_id()) {
+                    listener.channels.insert(channel);
+                }
+                tx.send(Ok(ExecuteResponse::Listened), session);
+            }
+
+            Plan::Unlisten { channel } => {
+                self.unlisten(session.conn_id(), channel);
+                tx.send(Ok(ExecuteResponse::Unlistened), session);
+            }
+
+            Plan::Notify { channel, payload } => {
+                self.notify(session.conn_id(), &channel, payload);
+                tx.send(Ok(ExecuteResponse::Notified), session);
+            }
+        }
+    }
+
+    /// Stops the session `conn_id` from listening on `channel`, or on all
+    /// channels if `channel` is `None`.
+    fn unlisten(&mut self, conn_id: u32, channel: Option<String>) {
+        if let Some(listener) = self.listeners.get_mut(&conn_id) {
+            match channel {
+                Some(channel) => {
+                    listener.channels.remove(&channel);
+                }
+                None => listener.channels.clear(),
+            }
         }
     }
 
@@ -1825,6 +2354,8 @@ impl Coordinator {
             plan_cx: pcx,
             desc: table.desc,
             defaults: table.defaults,
+            keys: table.keys,
+            checks: table.checks,
             conn_id,
         };
         let index_id = self.catalog.allocate_id()?;
@@ -1873,6 +2404,7 @@ impl Coordinator {
         source: sql::plan::Source,
         if_not_exists: bool,
         materialized: bool,
+        inferred_column_types: Vec<(ColumnName, ScalarType)>,
     ) -> Result<ExecuteResponse, CoordError> {
         let source = catalog::Source {
             create_sql: source.create_sql,
@@ -1918,9 +2450,15 @@ impl Coordinator {
                 }
 
                 self.maybe_begin_caching(source_id, &source.connector).await;
-                Ok(ExecuteResponse::CreatedSource { existed: false })
+                Ok(ExecuteResponse::CreatedSource {
+                    existed: false,
+                    inferred_column_types,
+                })
             }
-            Err(_) if if_not_exists => Ok(ExecuteResponse::CreatedSource { existed: true }),
+            Err(_) if if_not_exists => Ok(ExecuteResponse::CreatedSource {
+                existed: true,
+                inferred_column_types: vec![],
+            }),
             Err(err) => Err(err),
         }
     }
@@ -1954,6 +2492,21 @@ impl Coordinator {
             }
         };
 
+        // Resuming a sink after a restart relies on its input being assigned
+        // the same timestamps again.
+        if let SinkConnectorBuilder::Kafka(builder) = &sink.connector_builder {
+            if builder.consistency_topic.is_some() && !self.catalog.uses_only_byo_sources(sink.from)
+            {
+                tx.send(
+                    Err(CoordError::Unstructured(anyhow!(
+                        "consistency_topic requires that all of the sink's inputs are sources with BYO consistency"
+                    ))),
+                    session,
+                );
+                return;
+            }
+        }
+
         let frontier = match self.determine_frontier(as_of, sink.from) {
             Ok(frontier) => frontier,
             Err(e) => {
@@ -1997,6 +2550,7 @@ impl Coordinator {
         // Now we're ready to create the sink connector. Arrange to notify the
         // main coordinator thread when the future completes.
         let connector_builder = sink.connector_builder;
+        let notify_tx = self.notify_tx.clone();
         tokio::spawn(async move {
             internal_cmd_tx
                 .send(Message::SinkConnectorReady(SinkConnectorReady {
@@ -2004,8 +2558,14 @@ impl Coordinator {
                     tx,
                     id,
                     oid,
-                    result: sink_connector::build(connector_builder, with_snapshot, frontier, id)
-                        .await,
+                    result: sink_connector::build(
+                        connector_builder,
+                        with_snapshot,
+                        frontier,
+                        id,
+                        notify_tx,
+                    )
+                    .await,
                 }))
                 .expect("sending to internal_cmd_tx cannot fail");
         });
@@ -2037,6 +2597,7 @@ impl Coordinator {
             optimized_expr,
             desc,
             conn_id: if view.temporary { Some(conn_id) } else { None },
+            retain_history: view.retain_history,
         };
         ops.push(catalog::Op::CreateItem {
             id: view_id,
@@ -2095,6 +2656,8 @@ impl Coordinator {
             plan_cx: pcx,
             keys: index.keys,
             on: index.on,
+            memory_limit_bytes: index.memory_limit_bytes,
+            options,
             conn_id: None,
         };
         let id = self.catalog.allocate_id()?;
@@ -2109,7 +2672,6 @@ impl Coordinator {
             Ok(()) => {
                 self.ship_dataflow(self.dataflow_builder().build_index_dataflow(id))
                     .await?;
-                self.set_index_options(id, options);
                 Ok(ExecuteResponse::CreatedIndex { existed: false })
             }
             Err(_) if if_not_exists => Ok(ExecuteResponse::CreatedIndex { existed: true }),
@@ -2201,6 +2763,29 @@ impl Coordinator {
         })
     }
 
+    fn sequence_explain_drop(
+        &mut self,
+        items: Vec<GlobalId>,
+    ) -> Result<ExecuteResponse, CoordError> {
+        // Use the same dependency walk as `sequence_drop_items`, so that the
+        // objects are reported in the order they would be dropped.
+        let ops = self.catalog.drop_items_ops(&items);
+        let rows = ops
+            .into_iter()
+            .filter_map(|op| match op {
+                catalog::Op::DropItem(id) => {
+                    let entry = self.catalog.get_by_id(&id);
+                    Some(Row::pack_slice(&[
+                        Datum::String(&entry.item_type().to_string()),
+                        Datum::String(&entry.name().to_string()),
+                    ]))
+                }
+                _ => None,
+            })
+            .collect();
+        Ok(send_immediate_rows(rows))
+    }
+
     async fn sequence_show_all_variables(
         &mut self,
         session: &Session,
@@ -2262,7 +2847,12 @@ impl Coordinator {
                 | TransactionStatus::InTransactionImplicit(ops) => {
                     if let TransactionOps::Writes(inserts) = ops {
                         let timestamp = self.get_write_ts();
-                        for WriteOp { id, rows } in inserts {
+                        for WriteOp {
+                            id,
+                            rows,
+                            conflict_keys,
+                        } in inserts
+                        {
                             // Re-verify this id exists.
                             if self.catalog.try_get_by_id(id).is_none() {
                                 return Err(CoordError::SqlCatalog(CatalogError::UnknownItem(
@@ -2270,6 +2860,20 @@ impl Coordinator {
                                 )));
                             }
 
+                            // Another transaction may have committed
+                            // conflicting rows since this write was planned.
+                            let rows = if conflict_keys.is_empty() {
+                                rows
+                            } else {
+                                skip_conflicting_rows(
+                                    rows,
+                                    &conflict_keys,
+                                    self.table_key_counts.get(&id),
+                                    iter::empty(),
+                                )
+                            };
+                            self.update_table_key_counts(id, &rows);
+
                             let updates = rows
                                 .into_iter()
                                 .map(|(row, diff)| Update {
@@ -2294,13 +2898,31 @@ impl Coordinator {
 
     async fn sequence_peek(
         &mut self,
+        internal_cmd_tx: &mpsc::UnboundedSender<Message>,
         conn_id: u32,
         source: MirRelationExpr,
         when: PeekWhen,
         finishing: RowSetFinishing,
         copy_to: Option<CopyFormat>,
     ) -> Result<ExecuteResponse, CoordError> {
+        let start = Instant::now();
+        let explicit_timestamp = matches!(when, PeekWhen::AtTimestamp(_));
         let timestamp = self.determine_timestamp(&source, when)?;
+        if explicit_timestamp {
+            // Rather than blocking until every input has caught up to an
+            // explicitly requested timestamp, which may never happen, reject
+            // timestamps that are not yet complete.
+            let (index_ids, _indexes_complete) =
+                self.catalog.nearest_indexes(&source.global_uses());
+            let upper = self.indexes.greatest_open_upper(index_ids.iter().copied());
+            if upper.less_equal(&timestamp) {
+                coord_bail!(
+                    "Timestamp ({}) is not yet complete for all inputs: {}",
+                    timestamp,
+                    self.describe_valid_timestamps(&index_ids)
+                );
+            }
+        }
 
         let source = self.prep_relation_expr(
             source,
@@ -2396,15 +3018,18 @@ impl Coordinator {
                 dataflow.set_as_of(Antichain::from_elem(timestamp));
                 self.dataflow_builder()
                     .import_view_into_dataflow(&view_id, &source, &mut dataflow);
-                dataflow.add_index_to_build(index_id, view_id, typ.clone(), key.clone());
+                dataflow.add_index_to_build(index_id, view_id, typ.clone(), key.clone(), None);
                 dataflow.add_index_export(index_id, view_id, typ, key);
                 self.ship_dataflow(dataflow).await?;
             }
 
+            let query_id = self.next_query_id;
+            self.next_query_id += 1;
             self.broadcast(SequencedCommand::Peek {
                 id: index_id,
                 key: literal_row,
                 conn_id,
+                query_id,
                 tx: rows_tx,
                 timestamp,
                 finishing: finishing.clone(),
@@ -2415,6 +3040,23 @@ impl Coordinator {
                 self.drop_indexes(vec![index_id]).await;
             }
 
+            // List the peek in `mz_internal.mz_active_peeks` until all of its
+            // results have arrived, and then report its duration to
+            // `mz_internal.mz_peek_durations`. A connection runs one peek at
+            // a time, so any peek still listed for it has been abandoned.
+            let internal_cmd_tx = internal_cmd_tx.clone();
+            let peeked_index_id = if fast_path { Some(index_id) } else { None };
+            self.retire_conn_active_peeks(conn_id).await;
+            let settled = Arc::new(AtomicBool::new(false));
+            let peek = ActivePeek {
+                conn_id,
+                index_id: peeked_index_id,
+                settled: Arc::clone(&settled),
+            };
+            self.update_catalog_view(MZ_ACTIVE_PEEKS.id, vec![(peek.pack_row(query_id), 1)])
+                .await;
+            self.active_peeks.insert(query_id, peek);
+
             let rows_rx = UnboundedReceiverStream::new(rows_rx)
                 .fold(PeekResponse::Rows(vec![]), |memo, resp| async {
                     match (memo, resp) {
@@ -2431,6 +3073,17 @@ impl Coordinator {
                     }
                 })
                 .map(move |mut resp| {
+                    // If `CANCEL QUERY` reported the peek as canceled before
+                    // its results arrived, honor the cancelation.
+                    if settled.swap(true, Ordering::SeqCst) {
+                        resp = PeekResponse::Canceled;
+                    }
+                    // The coordinator may have shut down in the meantime.
+                    let _ = internal_cmd_tx.send(Message::PeekFinished(PeekFinished {
+                        query_id,
+                        index_id: peeked_index_id,
+                        duration: start.elapsed(),
+                    }));
                     if let PeekResponse::Rows(rows) = &mut resp {
                         finishing.finish(rows)
                     }
@@ -2460,6 +3113,8 @@ impl Coordinator {
         emit_progress: bool,
         object_columns: usize,
         desc: RelationDesc,
+        output: TailOutput,
+        order_by: Vec<ColumnOrder>,
     ) -> Result<ExecuteResponse, CoordError> {
         // Determine the frontier of updates to tail *from*.
         // Updates greater or equal to this frontier will be produced.
@@ -2475,7 +3130,7 @@ impl Coordinator {
         session.add_drop_sink(sink_id);
         let (tx, rx) = mpsc::unbounded_channel();
 
-        self.ship_dataflow(self.dataflow_builder().build_sink_dataflow(
+        let dataflow = self.dataflow_builder().build_sink_dataflow(
             sink_name,
             sink_id,
             source_id,
@@ -2486,10 +3141,14 @@ impl Coordinator {
                 emit_progress,
                 object_columns,
                 value_desc: desc,
+                output,
+                order_by,
             }),
             SinkEnvelope::Tail { emit_progress },
-        ))
-        .await?;
+        );
+        self.active_tails
+            .insert(sink_id, dataflow.index_imports.keys().copied().collect());
+        self.ship_dataflow(dataflow).await?;
 
         let resp = ExecuteResponse::Tailing { rx };
 
@@ -2605,25 +3264,29 @@ impl Coordinator {
         if since.less_equal(&timestamp) {
             Ok(timestamp)
         } else {
-            let invalid = index_ids
-                .iter()
-                .filter(|id| {
-                    !self
-                        .indexes
-                        .since_of(id)
-                        .expect("id not found")
-                        .less_equal(&timestamp)
-                })
-                .map(|id| (id, self.indexes.since_of(id)))
-                .collect::<Vec<_>>();
             coord_bail!(
-                "Timestamp ({}) is not valid for all inputs: {:?}",
+                "Timestamp ({}) is not valid for all inputs: {}",
                 timestamp,
-                invalid
+                self.describe_valid_timestamps(&index_ids)
             );
         }
     }
 
+    /// Describes the timestamps at which the identified indexes can currently
+    /// be read: those in advance of their `since` frontiers, but not in
+    /// advance of their `upper` frontiers.
+    fn describe_valid_timestamps(&self, index_ids: &[GlobalId]) -> String {
+        let since = self.indexes.least_valid_since(index_ids.iter().copied());
+        let upper = self.indexes.greatest_open_upper(index_ids.iter().copied());
+        // As in `determine_timestamp`, this assumes that the frontiers have at
+        // most one element.
+        match (since.elements().get(0), upper.elements().get(0)) {
+            (None, _) => "no timestamps are valid".into(),
+            (Some(since), None) => format!("the earliest valid timestamp is {}", since),
+            (Some(since), Some(upper)) => format!("the valid range is [{}, {})", since, upper),
+        }
+    }
+
     /// Determine the frontier of updates to start *from*.
     /// Updates greater or equal to this frontier will be produced.
     fn determine_frontier(
@@ -2710,6 +3373,21 @@ impl Coordinator {
                 }
                 explanation.to_string()
             }
+            ExplainStage::Memory => {
+                let optimized_plan = self
+                    .prep_relation_expr(decorrelated_plan, ExprPrepStyle::Explain)?
+                    .into_inner();
+                let catalog = self.catalog.for_session(session);
+                let mut explanation = expr::explain::Explanation::new(&optimized_plan, &catalog);
+                if let Some(row_set_finishing) = row_set_finishing {
+                    explanation.explain_row_set_finishing(row_set_finishing);
+                }
+                if options.typed {
+                    explanation.explain_types();
+                }
+                explanation.explain_memory();
+                explanation.to_string()
+            }
         };
         let rows = vec![Row::pack_slice(&[Datum::from(&*explanation_string)])];
         Ok(send_immediate_rows(rows))
@@ -2723,7 +3401,11 @@ impl Coordinator {
         affected_rows: usize,
         kind: MutationKind,
     ) -> Result<ExecuteResponse, CoordError> {
-        session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp { id, rows }]))?;
+        session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
+            id,
+            rows,
+            conflict_keys: vec![],
+        }]))?;
         Ok(match kind {
             MutationKind::Delete => ExecuteResponse::Deleted(affected_rows),
             MutationKind::Insert => ExecuteResponse::Inserted(affected_rows),
@@ -2731,11 +3413,35 @@ impl Coordinator {
         })
     }
 
+    /// Records the committed write of `rows` to the table `id` in
+    /// `table_key_counts`, if the table has declared keys.
+    fn update_table_key_counts(&mut self, id: GlobalId, rows: &[(Row, isize)]) {
+        let keys = match self.catalog.get_by_id(&id).item() {
+            CatalogItem::Table(table) if !table.keys.is_empty() => &table.keys,
+            _ => return,
+        };
+        let key_counts = self.table_key_counts.entry(id).or_default();
+        for key in keys {
+            let counts = key_counts.entry(key.clone()).or_default();
+            for (row, diff) in rows {
+                if let Some(value) = key_value(row, key) {
+                    let count = counts.entry(value.clone()).or_default();
+                    *count += diff;
+                    if *count == 0 {
+                        counts.remove(&value);
+                    }
+                }
+            }
+        }
+    }
+
     async fn sequence_insert(
         &mut self,
         session: &mut Session,
         id: GlobalId,
         values: MirRelationExpr,
+        conflict_keys: Vec<Vec<usize>>,
+        checks: Vec<(String, MirScalarExpr)>,
     ) -> Result<ExecuteResponse, CoordError> {
         let prep_style = ExprPrepStyle::OneShot {
             logical_time: self.get_write_ts(),
@@ -2743,7 +3449,8 @@ impl Coordinator {
         match self.prep_relation_expr(values, prep_style)?.into_inner() {
             MirRelationExpr::Constant { rows, typ: _ } => {
                 let rows = rows?;
-                let desc = self.catalog.get_by_id(&id).desc()?;
+                let entry = self.catalog.get_by_id(&id);
+                let desc = entry.desc()?;
                 for (row, _) in &rows {
                     for (datum, (name, typ)) in row.unpack().iter().zip(desc.iter()) {
                         if datum == &Datum::Null && !typ.nullable {
@@ -2756,9 +3463,51 @@ impl Coordinator {
                         }
                     }
                 }
+                // Any row that violates a constraint rejects the whole
+                // statement. A constraint that evaluates to NULL is satisfied.
+                let arena = RowArena::new();
+                for (row, _) in &rows {
+                    let datums = row.unpack();
+                    for (name, check) in &checks {
+                        if check.eval(&datums, &arena)? == Datum::False {
+                            return Err(CoordError::CheckViolation {
+                                relation: entry.name().item.clone(),
+                                constraint: name.clone(),
+                            });
+                        }
+                    }
+                }
+                if conflict_keys.is_empty() {
+                    let affected_rows = rows.len();
+                    return self
+                        .sequence_send_diffs(session, id, rows, affected_rows, MutationKind::Insert)
+                        .await;
+                }
+                // Rows written earlier in this transaction are not yet
+                // reflected in `table_key_counts`, so must be checked too.
+                let pending: Vec<_> = match session.transaction() {
+                    TransactionStatus::Started(TransactionOps::Writes(ops))
+                    | TransactionStatus::InTransaction(TransactionOps::Writes(ops))
+                    | TransactionStatus::InTransactionImplicit(TransactionOps::Writes(ops)) => ops
+                        .iter()
+                        .filter(|op| op.id == id)
+                        .flat_map(|op| op.rows.iter().map(|(row, _diff)| row))
+                        .collect(),
+                    _ => vec![],
+                };
+                let rows = skip_conflicting_rows(
+                    rows,
+                    &conflict_keys,
+                    self.table_key_counts.get(&id),
+                    pending,
+                );
                 let affected_rows = rows.len();
-                self.sequence_send_diffs(session, id, rows, affected_rows, MutationKind::Insert)
-                    .await
+                session.add_transaction_ops(TransactionOps::Writes(vec![WriteOp {
+                    id,
+                    rows,
+                    conflict_keys,
+                }]))?;
+                Ok(ExecuteResponse::Inserted(affected_rows))
             }
             // If we couldn't optimize the INSERT statement to a constant, it
             // must depend on another relation. We're not yet sophisticated
@@ -2798,7 +3547,8 @@ impl Coordinator {
             .into_iter()
             .map(|o| match o {
                 IndexOptionName::LogicalCompactionWindow => IndexOption::LogicalCompactionWindow(
-                    self.logical_compaction_window_ms.map(Duration::from_millis),
+                    self.default_compaction_window_ms(id)
+                        .map(Duration::from_millis),
                 ),
             })
             .collect();
@@ -2806,6 +3556,24 @@ impl Coordinator {
         Ok(ExecuteResponse::AlteredObject(ObjectType::Index))
     }
 
+    async fn sequence_alter_source_set_options(
+        &mut self,
+        id: GlobalId,
+        create_sql: String,
+        config_options: BTreeMap<String, String>,
+    ) -> Result<ExecuteResponse, CoordError> {
+        let op = catalog::Op::AlterSourceOptions {
+            id,
+            create_sql,
+            config_options: config_options.clone(),
+        };
+        self.catalog_transact(vec![op]).await?;
+        // Sources instantiated from now on pick up the new options from the
+        // catalog, but the running ones must be told about them.
+        self.broadcast(SequencedCommand::UpdateSourceOptions { id, config_options });
+        Ok(ExecuteResponse::AlteredObject(ObjectType::Source))
+    }
+
     async fn catalog_transact(&mut self, ops: Vec<catalog::Op>) -> Result<(), CoordError> {
         let events = self.catalog.transact(ops)?;
         self.process_catalog_events(events).await
@@ -2844,7 +3612,7 @@ impl Coordinator {
                     item,
                 } => {
                     if let Ok(desc) = item.desc(&name) {
-                        self.report_column_updates(desc, *id, 1).await;
+                        self.report_column_updates(item, desc, *id, 1).await;
                     }
                     match item {
                         CatalogItem::Index(index) => {
@@ -2977,7 +3745,28 @@ impl Coordinator {
                             nullable.to_owned(),
                             -1,
                         )
-                        .await
+                        .await;
+                        let index_id = Some(entry.id());
+                        let mut retractions = vec![];
+                        self.peek_durations.retain(|(id, duration_ns), count| {
+                            if *id == index_id {
+                                retractions.push((
+                                    Row::pack_slice(&[
+                                        Datum::String(&entry.id().to_string()),
+                                        Datum::Int64(*duration_ns),
+                                        Datum::Int64(*count),
+                                    ]),
+                                    -1,
+                                ));
+                                false
+                            } else {
+                                true
+                            }
+                        });
+                        if !retractions.is_empty() {
+                            self.update_catalog_view(MZ_PEEK_DURATION_HISTOGRAM.id, retractions)
+                                .await;
+                        }
                     }
                     _ => unreachable!("DroppedIndex for non-index item"),
                 },
@@ -2985,6 +3774,7 @@ impl Coordinator {
                     match entry.item() {
                         CatalogItem::Table(_) => {
                             sources_to_drop.push(entry.id());
+                            self.table_key_counts.remove(&entry.id());
                             self.report_table_update(
                                 entry.id(),
                                 entry.oid(),
@@ -3004,6 +3794,23 @@ impl Coordinator {
                                 -1,
                             )
                             .await;
+                            let source_id = entry.id();
+                            let mut retractions = vec![];
+                            self.source_upstream.retain(|(id, _), row| {
+                                if *id == source_id {
+                                    retractions.push((row.clone(), -1));
+                                    false
+                                } else {
+                                    true
+                                }
+                            });
+                            if !retractions.is_empty() {
+                                self.update_catalog_view(
+                                    MZ_SOURCE_UPSTREAM_OFFSETS.id,
+                                    retractions,
+                                )
+                                .await;
+                            }
                         }
                         CatalogItem::View(_) => {
                             self.report_view_update(
@@ -3029,10 +3836,19 @@ impl Coordinator {
                             )
                             .await;
                             match connector {
-                                SinkConnector::Kafka(KafkaSinkConnector { topic, .. }) => {
+                                SinkConnector::Kafka(KafkaSinkConnector {
+                                    topic,
+                                    consistency,
+                                    ..
+                                }) => {
+                                    let consistency_topic = match consistency {
+                                        Some(consistency) => Datum::String(&consistency.topic),
+                                        None => Datum::Null,
+                                    };
                                     let row = Row::pack_slice(&[
                                         Datum::String(entry.id().to_string().as_str()),
                                         Datum::String(topic.as_str()),
+                                        consistency_topic,
                                     ]);
                                     self.update_catalog_view(
                                         MZ_KAFKA_SINKS.id,
@@ -3080,7 +3896,8 @@ impl Coordinator {
                         }
                     }
                     if let Ok(desc) = entry.desc() {
-                        self.report_column_updates(desc, entry.id(), -1).await;
+                        self.report_column_updates(entry.item(), desc, entry.id(), -1)
+                            .await;
                     }
                 }
                 catalog::Event::NoOp => (),
@@ -3102,27 +3919,76 @@ impl Coordinator {
 
     async fn drop_sinks(&mut self, dataflow_names: Vec<GlobalId>) {
         if !dataflow_names.is_empty() {
+            for id in &dataflow_names {
+                self.active_tails.remove(id);
+            }
             self.broadcast(SequencedCommand::DropSinks(dataflow_names));
+
+            // Tear down the orphaned indexes that were kept only for the
+            // TAILs that just ended.
+            let active_tails = &self.active_tails;
+            let released: Vec<_> = self
+                .orphaned_indexes
+                .iter()
+                .filter(|id| !active_tails.values().any(|ids| ids.contains(*id)))
+                .copied()
+                .collect();
+            for id in &released {
+                self.orphaned_indexes.remove(id);
+            }
+            self.drop_indexes(released).await;
         }
     }
 
     async fn drop_indexes(&mut self, indexes: Vec<GlobalId>) {
         let mut trace_keys = Vec::new();
         for id in indexes {
+            if self.orphaned_indexes.contains(&id) {
+                // Torn down by `drop_sinks` once the last TAIL reading from
+                // the index ends.
+                continue;
+            }
             if self.indexes.remove(&id).is_some() {
                 trace_keys.push(id);
             }
         }
         if !trace_keys.is_empty() {
+            self.report_frontiers(trace_keys.clone()).await;
             self.broadcast(SequencedCommand::DropIndexes(trace_keys))
         }
     }
 
+    /// Returns the logical compaction window of the index identified by `id`
+    /// in the absence of an explicit `logical_compaction_window` option.
+    ///
+    /// Indexes on views created with the `retain_history` option retain that
+    /// much history; all other indexes use the global compaction window.
+    fn default_compaction_window_ms(&self, id: GlobalId) -> Option<Timestamp> {
+        let on = match self.catalog.try_get_by_id(id).map(|entry| entry.item()) {
+            Some(CatalogItem::Index(index)) => index.on,
+            _ => return self.logical_compaction_window_ms,
+        };
+        match self.catalog.try_get_by_id(on).map(|entry| entry.item()) {
+            Some(CatalogItem::View(catalog::View {
+                retain_history: Some(retain_history),
+                ..
+            })) => Some(duration_to_timestamp_millis(*retain_history)),
+            _ => self.logical_compaction_window_ms,
+        }
+    }
+
     fn set_index_options(&mut self, id: GlobalId, options: Vec<IndexOption>) {
         let index = self.indexes.get_mut(&id).expect("index known to exist");
         for o in options {
             match o {
                 IndexOption::LogicalCompactionWindow(window) => {
+                    if window.is_none() {
+                        log::warn!(
+                            "logical compaction disabled for index {}: its memory usage \
+                             will grow without bound as it retains all history",
+                            id
+                        );
+                    }
                     let window = window.map(duration_to_timestamp_millis);
                     index.set_compaction_window_ms(window);
                 }
@@ -3232,18 +4098,37 @@ impl Coordinator {
         // For each produced arrangement, start tracking the arrangement with
         // a compaction frontier of at least `since`.
         for (global_id, _description, _typ) in dataflow.index_exports.iter() {
-            let mut frontiers =
-                Frontiers::new(self.num_workers(), self.logical_compaction_window_ms);
+            let mut frontiers = Frontiers::new(
+                self.num_workers(),
+                self.default_compaction_window_ms(*global_id),
+            );
             frontiers.advance_since(&since);
             self.indexes.insert(*global_id, frontiers);
+            // Apply the options the index was created with, which the catalog
+            // retains across restarts.
+            if let Some(CatalogItem::Index(index)) = self
+                .catalog
+                .try_get_by_id(*global_id)
+                .map(|entry| entry.item())
+            {
+                let options = index.options.clone();
+                self.set_index_options(*global_id, options);
+            }
         }
 
         for (id, sink) in &dataflow.sink_exports {
             match &sink.connector {
-                SinkConnector::Kafka(KafkaSinkConnector { topic, .. }) => {
+                SinkConnector::Kafka(KafkaSinkConnector {
+                    topic, consistency, ..
+                }) => {
+                    let consistency_topic = match consistency {
+                        Some(consistency) => Datum::String(&consistency.topic),
+                        None => Datum::Null,
+                    };
                     let row = Row::pack_slice(&[
                         Datum::String(&id.to_string()),
                         Datum::String(topic.as_str()),
+                        consistency_topic,
                     ]);
                     self.update_catalog_view(MZ_KAFKA_SINKS.id, iter::once((row, 1)))
                         .await;
@@ -3362,6 +4247,7 @@ pub async fn serve(
     runtime: Arc<Runtime>,
 ) -> Result<(JoinHandle<()>, Uuid), CoordError> {
     let (feedback_tx, feedback_rx) = mpsc::unbounded_channel();
+    let (notify_tx, notify_rx) = mpsc::unbounded_channel();
     let cache_tx = if let Some(cache_config) = &cache_config {
         let (cache_tx, cache_rx) = mpsc::unbounded_channel();
         let mut cacher = Cacher::new(cache_rx, cache_config.clone());
@@ -3414,6 +4300,16 @@ pub async fn serve(
         need_advance: true,
         transient_id_counter: 1,
         cancel: HashMap::new(),
+        source_upstream: HashMap::new(),
+        peek_durations: HashMap::new(),
+        active_peeks: HashMap::new(),
+        frontiers: HashMap::new(),
+        next_query_id: 1,
+        table_key_counts: HashMap::new(),
+        active_tails: HashMap::new(),
+        orphaned_indexes: HashSet::new(),
+        listeners: HashMap::new(),
+        notify_tx,
     };
     coord.broadcast(SequencedCommand::EnableFeedback(feedback_tx));
     if let Some(config) = &logging {
@@ -3431,7 +4327,9 @@ pub async fn serve(
     }
     match coord.bootstrap(initial_catalog_events).await {
         Ok(()) => {
-            let coord = thread::spawn(move || runtime.block_on(coord.serve(cmd_rx, feedback_rx)));
+            let coord = thread::spawn(move || {
+                runtime.block_on(coord.serve(cmd_rx, feedback_rx, notify_rx))
+            });
             Ok((coord, cluster_id))
         }
         Err(e) => {
@@ -3462,6 +4360,82 @@ fn send_immediate_rows(rows: Vec<Row>) -> ExecuteResponse {
     ExecuteResponse::SendingRows(Box::pin(async { PeekResponse::Rows(rows) }))
 }
 
+/// Returns the name of `typ` including its type modifiers, like
+/// `numeric(10,2)`, as opposed to the bare name reported by [`pgrepr::Type`].
+fn full_type_name(typ: &ScalarType) -> String {
+    match typ {
+        ScalarType::Decimal(precision, scale) => format!("numeric({},{})", precision, scale),
+        ScalarType::Array(elem_type) => format!("{}[]", full_type_name(elem_type)),
+        _ => pgrepr::Type::from(typ).name().to_owned(),
+    }
+}
+
+/// The number of rows with each value of each declared key of a table.
+type KeyCounts = HashMap<Vec<usize>, HashMap<Row, isize>>;
+
+/// Returns the value of `key` in `row`, or `None` if any column of the key is
+/// null, as null key values never conflict.
+fn key_value(row: &Row, key: &[usize]) -> Option<Row> {
+    let datums = row.unpack();
+    let value: Vec<_> = key.iter().map(|i| datums[*i]).collect();
+    if value.iter().any(|d| d.is_null()) {
+        None
+    } else {
+        Some(Row::pack_slice(&value))
+    }
+}
+
+/// Removes the rows in `rows` that conflict on any of `keys` with a row that
+/// already exists, as described by `existing`, with a row in `pending`, or with
+/// an earlier row in `rows`.
+fn skip_conflicting_rows<'a, I>(
+    rows: Vec<(Row, isize)>,
+    keys: &[Vec<usize>],
+    existing: Option<&KeyCounts>,
+    pending: I,
+) -> Vec<(Row, isize)>
+where
+    I: IntoIterator<Item = &'a Row>,
+{
+    let mut seen = vec![HashSet::new(); keys.len()];
+    for row in pending {
+        for (key, seen) in keys.iter().zip(&mut seen) {
+            if let Some(value) = key_value(row, key) {
+                seen.insert(value);
+            }
+        }
+    }
+    let mut out = vec![];
+    for (row, diff) in rows {
+        for _ in 0..diff {
+            let values: Vec<_> = keys.iter().map(|key| key_value(&row, key)).collect();
+            let conflicts = keys
+                .iter()
+                .zip(&values)
+                .zip(&seen)
+                .any(|((key, value), seen)| {
+                    let value = match value {
+                        None => return false,
+                        Some(value) => value,
+                    };
+                    let count = existing
+                        .and_then(|existing| existing.get(key))
+                        .and_then(|counts| counts.get(value));
+                    seen.contains(value) || count.map_or(false, |n| *n > 0)
+                });
+            if !conflicts {
+                for (value, seen) in values.into_iter().zip(&mut seen) {
+                    if let Some(value) = value {
+                        seen.insert(value);
+                    }
+                }
+                out.push((row.clone(), 1));
+            }
+        }
+    }
+    out
+}
+
 fn auto_generate_primary_idx(
     index_name: String,
     on_name: FullName,
@@ -3479,6 +4453,8 @@ fn auto_generate_primary_idx(
             .iter()
             .map(|k| MirScalarExpr::Column(*k))
             .collect(),
+        memory_limit_bytes: None,
+        options: vec![],
         conn_id,
     }
 }
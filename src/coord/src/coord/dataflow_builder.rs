@@ -153,7 +153,13 @@ impl<'a> DataflowBuilder<'a> {
         let on_type = on_entry.desc().unwrap().typ().clone();
         let mut dataflow = DataflowDesc::new(index_entry.name().to_string());
         self.import_into_dataflow(&index.on, &mut dataflow);
-        dataflow.add_index_to_build(id, index.on.clone(), on_type.clone(), index.keys.clone());
+        dataflow.add_index_to_build(
+            id,
+            index.on.clone(),
+            on_type.clone(),
+            index.keys.clone(),
+            index.memory_limit_bytes,
+        );
         dataflow.add_index_export(id, index.on, on_type, index.keys.clone());
         dataflow
     }
@@ -22,6 +22,11 @@ use crate::session::Var;
 pub enum CoordError {
     /// An error occurred in a catalog operation.
     Catalog(catalog::Error),
+    /// A row violated the named `CHECK` constraint of the named relation.
+    CheckViolation {
+        relation: String,
+        constraint: String,
+    },
     /// The specified session parameter is constrained to its current value.
     ConstrainedParameter(&'static (dyn Var + Send + Sync)),
     /// The cursor already exists.
@@ -30,6 +35,12 @@ pub enum CoordError {
     Eval(EvalError),
     /// The value for the specified parameter does not have the right type.
     InvalidParameterType(&'static (dyn Var + Send + Sync)),
+    /// The value for the specified parameter is not one of its permitted
+    /// values.
+    InvalidParameterValue {
+        parameter: &'static (dyn Var + Send + Sync),
+        value: String,
+    },
     /// The named operation cannot be run in a transaction.
     OperationProhibitsTransaction(String),
     /// The named operation requires an active transaction.
@@ -88,6 +99,15 @@ impl fmt::Display for CoordError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             CoordError::Catalog(e) => e.fmt(f),
+            CoordError::CheckViolation {
+                relation,
+                constraint,
+            } => write!(
+                f,
+                "new row for relation {} violates check constraint {}",
+                relation.quoted(),
+                constraint.quoted()
+            ),
             CoordError::ConstrainedParameter(p) => write!(
                 f,
                 "parameter {} can only be set to {}",
@@ -104,6 +124,12 @@ impl fmt::Display for CoordError {
                 p.name().quoted(),
                 p.type_name().quoted()
             ),
+            CoordError::InvalidParameterValue { parameter, value } => write!(
+                f,
+                "invalid value for parameter {}: {}",
+                parameter.name().quoted(),
+                value.quoted()
+            ),
             CoordError::OperationProhibitsTransaction(op) => {
                 write!(f, "{} cannot be run inside a transaction block", op)
             }
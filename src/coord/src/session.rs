@@ -372,6 +372,9 @@ pub struct WriteOp {
     pub id: GlobalId,
     /// The data rows.
     pub rows: Vec<(Row, isize)>,
+    /// The keys on which to skip rows that conflict with existing rows at
+    /// commit time, as requested by `ON CONFLICT DO NOTHING`.
+    pub conflict_keys: Vec<Vec<usize>>,
 }
 
 /// The action to take during end_transaction.
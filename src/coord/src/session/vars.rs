@@ -37,6 +37,12 @@ const DATE_STYLE: ServerVar<str> = ServerVar {
     description: "Sets the display format for date and time values (PostgreSQL).",
 };
 
+const DEFAULT_TRANSACTION_ISOLATION: ServerVar<str> = ServerVar {
+    name: unicase::Ascii::new("default_transaction_isolation"),
+    value: "serializable",
+    description: "Sets the isolation level of each new transaction (PostgreSQL).",
+};
+
 const EXTRA_FLOAT_DIGITS: ServerVar<i32> = ServerVar {
     name: unicase::Ascii::new("extra_float_digits"),
     value: &3,
@@ -78,6 +84,13 @@ const STANDARD_CONFORMING_STRINGS: ServerVar<bool> = ServerVar {
     description: "Causes '...' strings to treat backslashes literally (PostgreSQL).",
 };
 
+const STATEMENT_TIMEOUT: ServerVar<u32> = ServerVar {
+    name: unicase::Ascii::new("statement_timeout"),
+    value: &0,
+    description:
+        "Sets the maximum allowed duration of any statement, in milliseconds (PostgreSQL).",
+};
+
 const TIMEZONE: ServerVar<str> = ServerVar {
     // TimeZone has nonstandard capitalization for historical reasons.
     name: unicase::Ascii::new("TimeZone"),
@@ -118,12 +131,14 @@ pub struct Vars {
     client_encoding: ServerVar<str>,
     database: SessionVar<str>,
     date_style: ServerVar<str>,
+    default_transaction_isolation: ServerVar<str>,
     extra_float_digits: SessionVar<i32>,
     integer_datetimes: ServerVar<bool>,
     search_path: ServerVar<[&'static str]>,
     server_version: ServerVar<str>,
     sql_safe_updates: SessionVar<bool>,
     standard_conforming_strings: ServerVar<bool>,
+    statement_timeout: SessionVar<u32>,
     timezone: ServerVar<str>,
     transaction_isolation: ServerVar<str>,
 }
@@ -135,12 +150,14 @@ impl Default for Vars {
             client_encoding: CLIENT_ENCODING,
             database: SessionVar::new(&DATABASE),
             date_style: DATE_STYLE,
+            default_transaction_isolation: DEFAULT_TRANSACTION_ISOLATION,
             extra_float_digits: SessionVar::new(&EXTRA_FLOAT_DIGITS),
             integer_datetimes: INTEGER_DATETIMES,
             search_path: SEARCH_PATH,
             server_version: SERVER_VERSION,
             sql_safe_updates: SessionVar::new(&SQL_SAFE_UPDATES),
             standard_conforming_strings: STANDARD_CONFORMING_STRINGS,
+            statement_timeout: SessionVar::new(&STATEMENT_TIMEOUT),
             timezone: TIMEZONE,
             transaction_isolation: TRANSACTION_ISOLATION,
         }
@@ -156,12 +173,14 @@ impl Vars {
             &self.client_encoding,
             &self.database,
             &self.date_style,
+            &self.default_transaction_isolation,
             &self.extra_float_digits,
             &self.integer_datetimes,
             &self.search_path,
             &self.server_version,
             &self.sql_safe_updates,
             &self.standard_conforming_strings,
+            &self.statement_timeout,
             &self.timezone,
             &self.transaction_isolation,
         ]
@@ -202,6 +221,8 @@ impl Vars {
             Ok(&self.database)
         } else if name == DATE_STYLE.name {
             Ok(&self.date_style)
+        } else if name == DEFAULT_TRANSACTION_ISOLATION.name {
+            Ok(&self.default_transaction_isolation)
         } else if name == EXTRA_FLOAT_DIGITS.name {
             Ok(&self.extra_float_digits)
         } else if name == INTEGER_DATETIMES.name {
@@ -214,6 +235,8 @@ impl Vars {
             Ok(&self.sql_safe_updates)
         } else if name == STANDARD_CONFORMING_STRINGS.name {
             Ok(&self.standard_conforming_strings)
+        } else if name == STATEMENT_TIMEOUT.name {
+            Ok(&self.statement_timeout)
         } else if name == TIMEZONE.name {
             Ok(&self.timezone)
         } else if name == TRANSACTION_ISOLATION.name {
@@ -245,6 +268,8 @@ impl Vars {
                 }
             }
             Ok(())
+        } else if name == DEFAULT_TRANSACTION_ISOLATION.name {
+            set_isolation_level(&DEFAULT_TRANSACTION_ISOLATION, value)
         } else if name == EXTRA_FLOAT_DIGITS.name {
             self.extra_float_digits.set(value)
         } else if name == INTEGER_DATETIMES.name {
@@ -257,6 +282,8 @@ impl Vars {
             self.sql_safe_updates.set(value)
         } else if name == STANDARD_CONFORMING_STRINGS.name {
             Err(CoordError::ReadOnlyParameter(&STANDARD_CONFORMING_STRINGS))
+        } else if name == STATEMENT_TIMEOUT.name {
+            self.statement_timeout.set(value)
         } else if name == TIMEZONE.name {
             if unicase::Ascii::new(value) != TIMEZONE.value {
                 return Err(CoordError::ConstrainedParameter(&TIMEZONE));
@@ -264,7 +291,7 @@ impl Vars {
                 Ok(())
             }
         } else if name == TRANSACTION_ISOLATION.name {
-            Err(CoordError::ReadOnlyParameter(&TRANSACTION_ISOLATION))
+            set_isolation_level(&TRANSACTION_ISOLATION, value)
         } else {
             Err(CoordError::UnknownParameter(name.into()))
         }
@@ -290,6 +317,12 @@ impl Vars {
         self.database.value()
     }
 
+    /// Returns the value of the `default_transaction_isolation` configuration
+    /// parameter.
+    pub fn default_transaction_isolation(&self) -> &'static str {
+        self.default_transaction_isolation.value
+    }
+
     /// Returns the value of the `extra_float_digits` configuration parameter.
     pub fn extra_float_digits(&self) -> i32 {
         *self.extra_float_digits.value()
@@ -321,6 +354,12 @@ impl Vars {
         *self.standard_conforming_strings.value
     }
 
+    /// Returns the value of the `statement_timeout` configuration parameter,
+    /// in milliseconds. A value of zero means that statements never time out.
+    pub fn statement_timeout(&self) -> u32 {
+        *self.statement_timeout.value()
+    }
+
     /// Returns the value of the `timezone` configuration parameter.
     pub fn timezone(&self) -> &'static str {
         self.timezone.value
@@ -333,6 +372,21 @@ impl Vars {
     }
 }
 
+/// Accepts any of the SQL standard's isolation levels as the value of `var`,
+/// which nonetheless remains `serializable`. Materialize runs every
+/// transaction serializably, which the standard permits in place of any of
+/// the weaker levels, so clients that ask for one of those get at least what
+/// they asked for.
+fn set_isolation_level(var: &'static ServerVar<str>, value: &str) -> Result<(), CoordError> {
+    match value.trim().to_lowercase().as_str() {
+        "read uncommitted" | "read committed" | "repeatable read" | "serializable" => Ok(()),
+        _ => Err(CoordError::InvalidParameterValue {
+            parameter: var,
+            value: value.into(),
+        }),
+    }
+}
+
 /// A `Var` represents a configuration parameter of an arbitrary type.
 pub trait Var: fmt::Debug {
     /// Returns the name of the configuration parameter.
@@ -485,6 +539,18 @@ impl Value for i32 {
     }
 }
 
+impl Value for u32 {
+    const TYPE_NAME: &'static str = "unsigned integer";
+
+    fn parse(s: &str) -> Result<u32, ()> {
+        s.parse().map_err(|_| ())
+    }
+
+    fn format(&self) -> String {
+        self.to_string()
+    }
+}
+
 impl Value for str {
     const TYPE_NAME: &'static str = "string";
 
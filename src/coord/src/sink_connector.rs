@@ -14,30 +14,50 @@ use anyhow::{anyhow, Context};
 use rdkafka::admin::{AdminClient, AdminOptions, NewTopic, TopicReplication};
 use rdkafka::client::DefaultClientContext;
 use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::message::Message;
+use rdkafka::producer::{BaseProducer, Producer};
+use rdkafka::{Offset, TopicPartitionList};
+use rusoto_s3::{ListObjectsV2Request, S3};
+use tokio::sync::mpsc;
 
 use dataflow_types::{
     AvroOcfSinkConnector, AvroOcfSinkConnectorBuilder, KafkaSinkConnector,
-    KafkaSinkConnectorBuilder, KafkaSinkConsistencyConnector, SinkConnector, SinkConnectorBuilder,
+    KafkaSinkConnectorBuilder, KafkaSinkConsistencyConnector, NotifySinkConnector,
+    NotifySinkConnectorBuilder, S3SinkConnector, S3SinkConnectorBuilder, SinkConnector,
+    SinkConnectorBuilder,
 };
 use expr::GlobalId;
+use interchange::avro;
 use ore::collections::CollectionExt;
 use repr::Timestamp;
 use timely::progress::Antichain;
 
 use crate::error::CoordError;
 
+/// Builds the connector of the sink `id`.
+///
+/// NOTIFY sinks hand their notifications to the coordinator over `notify_tx`.
 pub async fn build(
     builder: SinkConnectorBuilder,
     with_snapshot: bool,
     frontier: Antichain<Timestamp>,
     id: GlobalId,
+    notify_tx: mpsc::UnboundedSender<(String, Vec<String>)>,
 ) -> Result<SinkConnector, CoordError> {
     match builder {
         SinkConnectorBuilder::Kafka(k) => build_kafka(k, with_snapshot, frontier, id).await,
         SinkConnectorBuilder::AvroOcf(a) => build_avro_ocf(a, with_snapshot, frontier, id),
+        SinkConnectorBuilder::S3(s) => build_s3(s, with_snapshot, frontier, id).await,
+        SinkConnectorBuilder::Notify(n) => Ok(build_notify(n, with_snapshot, frontier, notify_tx)),
     }
 }
 
+/// Creates the topic of a Kafka sink and publishes its schemas.
+///
+/// If `reuse_topic` is set, an existing topic of the same name is used as is.
+#[allow(clippy::too_many_arguments)]
 async fn register_kafka_topic(
     client: &AdminClient<DefaultClientContext>,
     topic: &str,
@@ -46,6 +66,7 @@ async fn register_kafka_topic(
     ccsr: &ccsr::Client,
     value_schema: &str,
     key_schema: Option<&str>,
+    reuse_topic: bool,
 ) -> Result<(Option<i32>, i32), CoordError> {
     let res = client
         .create_topics(
@@ -66,8 +87,11 @@ async fn register_kafka_topic(
             res.len()
         );
     }
-    res.into_element()
-        .map_err(|(_, e)| anyhow!("error creating topic {} for sink: {}", topic, e))?;
+    match res.into_element() {
+        Ok(_) => (),
+        Err((_, RDKafkaErrorCode::TopicAlreadyExists)) if reuse_topic => (),
+        Err((_, e)) => coord_bail!("error creating topic {} for sink: {}", topic, e),
+    }
 
     // Publish value schema for the topic.
     //
@@ -91,13 +115,83 @@ async fn register_kafka_topic(
     Ok((key_schema_id, value_schema_id))
 }
 
+/// Returns the timestamp of the last END record that was committed to the
+/// consistency topic `topic`, if any.
+///
+/// Any transaction that an earlier incarnation of the sink left open is
+/// aborted first, so that only timestamps that were completely written are
+/// considered.
+async fn last_committed_timestamp(
+    config: ClientConfig,
+    topic: String,
+    transactional_id: String,
+) -> Result<Option<Timestamp>, CoordError> {
+    let task = tokio::task::spawn_blocking(move || -> Result<_, anyhow::Error> {
+        let timeout = Duration::from_secs(5);
+
+        // Initializing a producer with the sink's transactional ID fences
+        // out the earlier incarnation's producer and aborts its pending
+        // transaction.
+        let producer: BaseProducer = config
+            .clone()
+            .set("transactional.id", &transactional_id)
+            .create()
+            .context("creating producer for consistency topic")?;
+        producer
+            .init_transactions(timeout)
+            .context("fencing earlier producers of consistency topic")?;
+        drop(producer);
+
+        let consumer: BaseConsumer = config
+            .clone()
+            .set("group.id", &format!("materialize-{}", transactional_id))
+            .set("isolation.level", "read_committed")
+            .set("enable.auto.commit", "false")
+            .set("enable.partition.eof", "true")
+            .create()
+            .context("creating consumer for consistency topic")?;
+        let mut partitions = TopicPartitionList::new();
+        partitions.add_partition_offset(&topic, 0, Offset::Beginning)?;
+        consumer.assign(&partitions)?;
+
+        let mut last = None;
+        loop {
+            match consumer.poll(timeout) {
+                Some(Ok(message)) => {
+                    let payload = match message.payload() {
+                        Some(payload) => payload,
+                        None => continue,
+                    };
+                    let (id, status, _) = avro::decode_debezium_transaction(payload)
+                        .with_context(|| format!("decoding consistency topic {}", topic))?;
+                    if status == "END" {
+                        last = Some(id.parse::<Timestamp>().with_context(|| {
+                            format!("invalid timestamp {} in consistency topic {}", id, topic)
+                        })?);
+                    }
+                }
+                Some(Err(KafkaError::PartitionEOF(_))) => return Ok(last),
+                Some(Err(e)) => return Err(e.into()),
+                None => anyhow::bail!("timed out reading consistency topic {}", topic),
+            }
+        }
+    });
+    Ok(task.await.expect("reading consistency topic panicked")?)
+}
+
 async fn build_kafka(
     builder: KafkaSinkConnectorBuilder,
     with_snapshot: bool,
     frontier: Antichain<Timestamp>,
     id: GlobalId,
 ) -> Result<SinkConnector, CoordError> {
-    let topic = format!("{}-{}-{}", builder.topic_prefix, id, builder.topic_suffix);
+    // Sinks that resume from a consistency topic write to the same topic in
+    // each incarnation.
+    let topic = match &builder.consistency_topic {
+        Some(_) => builder.topic_prefix.clone(),
+        None => format!("{}-{}-{}", builder.topic_prefix, id, builder.topic_suffix),
+    };
+    let reuse_topic = builder.consistency_topic.is_some();
 
     // Create Kafka topic with single partition.
     let mut config = ClientConfig::new();
@@ -118,12 +212,19 @@ async fn build_kafka(
         &ccsr,
         &builder.value_schema,
         builder.key_schema.as_deref(),
+        reuse_topic,
     )
     .await
     .context("error registering kafka topic for sink")?;
 
+    let mut frontier = frontier;
+    let mut strict = !with_snapshot;
+    let mut config_options = builder.config_options;
+
     let consistency = if let Some(consistency_value_schema) = builder.consistency_value_schema {
-        let consistency_topic = format!("{}-consistency", topic);
+        let consistency_topic = builder
+            .consistency_topic
+            .unwrap_or_else(|| format!("{}-consistency", topic));
         let (_, consistency_schema_id) = register_kafka_topic(
             &client,
             &consistency_topic,
@@ -132,10 +233,38 @@ async fn build_kafka(
             &ccsr,
             &consistency_value_schema,
             None,
+            reuse_topic,
         )
         .await
         .context("error registering kafka consistency topic for sink")?;
 
+        if reuse_topic {
+            // Each timestamp is written in a transaction that spans its data
+            // and its consistency records, so a timestamp whose END record
+            // was committed was completely written, and one that was not
+            // committed is invisible to readers that read committed
+            // messages. Resume after the last committed timestamp, if the
+            // sink's input can still produce the changes that follow it.
+            // Otherwise write a snapshot of the input, which duplicates the
+            // earlier output but loses nothing.
+            let transactional_id = format!("mz-sink-{}", consistency_topic);
+            let resume_ts = last_committed_timestamp(
+                config,
+                consistency_topic.clone(),
+                transactional_id.clone(),
+            )
+            .await?;
+            match resume_ts {
+                Some(ts) if frontier.less_equal(&ts) => {
+                    frontier = Antichain::from_elem(ts);
+                    strict = true;
+                }
+                Some(_) => strict = false,
+                None => (),
+            }
+            config_options.insert("transactional.id".into(), transactional_id);
+        }
+
         Some(KafkaSinkConsistencyConnector {
             topic: consistency_topic,
             schema_id: consistency_schema_id,
@@ -152,10 +281,13 @@ async fn build_kafka(
         key_desc_and_indices: builder.key_desc_and_indices,
         value_desc: builder.value_desc,
         consistency,
+        partition_count: builder.partition_count,
+        partition_by: builder.partition_by,
         fuel: builder.fuel,
         frontier,
-        strict: !with_snapshot,
-        config_options: builder.config_options,
+        strict,
+        compression_codec: builder.compression_codec,
+        config_options,
     }))
 }
 
@@ -202,3 +334,72 @@ fn build_avro_ocf(
         value_desc: builder.value_desc,
     }))
 }
+
+async fn build_s3(
+    builder: S3SinkConnectorBuilder,
+    with_snapshot: bool,
+    frontier: Antichain<Timestamp>,
+    id: GlobalId,
+) -> Result<SinkConnector, CoordError> {
+    // Files from an earlier incarnation of this sink must never be
+    // overwritten, so continue numbering after the last file in the bucket.
+    let client = aws_util::s3::client(builder.aws_info.clone()).await?;
+    let file_prefix = format!("{}{}-", builder.prefix, id);
+    let mut first_file_counter = 0;
+    let mut continuation_token = None;
+    loop {
+        let response = client
+            .list_objects_v2(ListObjectsV2Request {
+                bucket: builder.bucket.clone(),
+                prefix: Some(file_prefix.clone()),
+                continuation_token,
+                ..Default::default()
+            })
+            .await
+            .with_context(|| format!("error listing S3 bucket {} for sink", builder.bucket))?;
+        for key in response
+            .contents
+            .into_iter()
+            .flatten()
+            .filter_map(|o| o.key)
+        {
+            let counter = key
+                .strip_prefix(&file_prefix)
+                .and_then(|k| k.strip_suffix(".parquet"))
+                .and_then(|c| c.parse::<u64>().ok());
+            if let Some(counter) = counter {
+                first_file_counter = first_file_counter.max(counter + 1);
+            }
+        }
+        continuation_token = response.next_continuation_token;
+        if continuation_token.is_none() {
+            break;
+        }
+    }
+    Ok(SinkConnector::S3(S3SinkConnector {
+        bucket: builder.bucket,
+        prefix: builder.prefix,
+        aws_info: builder.aws_info,
+        value_desc: builder.value_desc,
+        first_file_counter,
+        max_file_bytes: builder.max_file_bytes,
+        max_file_age: builder.max_file_age,
+        frontier,
+        strict: !with_snapshot,
+    }))
+}
+
+fn build_notify(
+    builder: NotifySinkConnectorBuilder,
+    with_snapshot: bool,
+    frontier: Antichain<Timestamp>,
+    tx: mpsc::UnboundedSender<(String, Vec<String>)>,
+) -> SinkConnector {
+    SinkConnector::Notify(NotifySinkConnector {
+        channel: builder.channel,
+        value_desc: builder.value_desc,
+        frontier,
+        strict: !with_snapshot,
+        tx,
+    })
+}
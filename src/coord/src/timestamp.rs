@@ -288,8 +288,13 @@ struct RtKinesisConnector {
 /// Data consumer stub for Kinesis source with BYO consistency
 struct ByoKinesisConnector {}
 
-/// Data consumer stub for File source with RT consistency
-struct RtFileConnector {}
+/// Data consumer for File source with RT consistency
+struct RtFileConnector {
+    id: SourceInstanceId,
+    path: PathBuf,
+    /// The size of the file when it was last reported to the coordinator
+    last_size: Option<u64>,
+}
 
 /// Data consumer stub for S3 source with RT consistency
 struct RtS3Connector {}
@@ -762,6 +767,7 @@ impl Timestamper {
                 break;
             } else {
                 self.update_byo_timestamp();
+                self.update_rt_file_sizes();
             }
         }
     }
@@ -780,9 +786,11 @@ impl Timestamper {
                     let (sc, enc, env, cons) = if let SourceConnector::External {
                         connector,
                         encoding,
+                        key_encoding: _,
                         envelope,
                         consistency,
                         ts_frequency: _,
+                        dedupe: _,
                     } = sc
                     {
                         (connector, encoding, envelope, consistency)
@@ -830,6 +838,37 @@ impl Timestamper {
         false
     }
 
+    /// Reports the size of the files that back real-time file sources to the
+    /// coordinator, so that it can track how far behind those sources are
+    fn update_rt_file_sizes(&mut self) {
+        for consumer in self.rt_sources.values_mut() {
+            let c = match &mut consumer.connector {
+                RtTimestampConnector::File(c) | RtTimestampConnector::Ocf(c) => c,
+                _ => continue,
+            };
+            let size = match std::fs::metadata(&c.path) {
+                Ok(metadata) => metadata.len(),
+                // The file source itself reports errors opening the file.
+                Err(_) => continue,
+            };
+            if c.last_size == Some(size) {
+                continue;
+            }
+            c.last_size = Some(size);
+            self.tx
+                .send(coord::Message::AdvanceSourceUpstream(
+                    coord::AdvanceSourceUpstream {
+                        id: c.id,
+                        partitions: vec![(
+                            PartitionId::File.to_string(),
+                            coord::UpstreamPosition::Bytes(size as i64),
+                        )],
+                    },
+                ))
+                .expect("Failed to send update to coordinator. This should not happen");
+        }
+    }
+
     /// Implements the byo timestamping logic
     ///
     /// If the partition count remains the same:
@@ -1097,6 +1136,7 @@ impl Timestamper {
                 PathBuf::from(timestamp_topic),
                 tx,
                 None,
+                None,
                 tail,
                 compression,
                 ctor,
@@ -1189,28 +1229,42 @@ impl Timestamper {
                 .unwrap(),
         );
 
-        thread::spawn({
-            let connector = connector.clone();
-            move || rt_kafka_metadata_fetch_loop(connector, consumer, metadata_refresh_frequency)
-        });
+        // Sources that read a topic pattern discover their topics themselves,
+        // as a partition count cannot describe several topics.
+        if !kc.topic_pattern {
+            thread::spawn({
+                let connector = connector.clone();
+                move || {
+                    rt_kafka_metadata_fetch_loop(connector, consumer, metadata_refresh_frequency)
+                }
+            });
+        }
 
         Some(connector)
     }
 
     fn create_rt_ocf_connector(
         &self,
-        _id: SourceInstanceId,
-        _fc: FileSourceConnector,
+        id: SourceInstanceId,
+        fc: FileSourceConnector,
     ) -> Option<RtFileConnector> {
-        Some(RtFileConnector {})
+        Some(RtFileConnector {
+            id,
+            path: fc.path,
+            last_size: None,
+        })
     }
 
     fn create_rt_file_connector(
         &self,
-        _id: SourceInstanceId,
-        _fc: FileSourceConnector,
+        id: SourceInstanceId,
+        fc: FileSourceConnector,
     ) -> Option<RtFileConnector> {
-        Some(RtFileConnector {})
+        Some(RtFileConnector {
+            id,
+            path: fc.path,
+            last_size: None,
+        })
     }
 
     fn create_rt_s3_connector(
@@ -1240,6 +1294,7 @@ impl Timestamper {
                 PathBuf::from(timestamp_topic),
                 tx,
                 None,
+                None,
                 tail,
                 compression,
                 ctor,
@@ -1462,6 +1517,7 @@ fn rt_kafka_metadata_fetch_loop(c: RtKafkaConnector, consumer: BaseConsumer, wai
         // TODO(benesch): Kafka supports fetching these in bulk, but
         // rust-rdkafka does not. That would save us a lot of requests on
         // large topics.
+        let mut upstream = vec![];
         for pid in 0..current_partition_count {
             match consumer.fetch_watermarks(&c.topic, pid, Duration::from_secs(30)) {
                 Ok((_low, high)) => {
@@ -1471,6 +1527,7 @@ fn rt_kafka_metadata_fetch_loop(c: RtKafkaConnector, consumer: BaseConsumer, wai
                         &pid.to_string(),
                     ]);
                     max_offset.set(high);
+                    upstream.push((pid.to_string(), coord::UpstreamPosition::Offset(high)));
                 }
                 Err(e) => {
                     error!(
@@ -1481,6 +1538,18 @@ fn rt_kafka_metadata_fetch_loop(c: RtKafkaConnector, consumer: BaseConsumer, wai
             }
         }
 
+        if !upstream.is_empty() {
+            c.coordination_state
+                .coordinator_channel
+                .send(coord::Message::AdvanceSourceUpstream(
+                    coord::AdvanceSourceUpstream {
+                        id: c.id,
+                        partitions: upstream,
+                    },
+                ))
+                .expect("Failed to send update to coordinator. This should not happen");
+        }
+
         if current_partition_count > 0 {
             thread::sleep(wait);
         } else {
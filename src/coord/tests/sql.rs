@@ -65,6 +65,8 @@ fn datadriven() {
                                 Vec::<Option<String>>::new(),
                             ),
                             defaults: vec![Expr::null(); 0],
+                            keys: vec![],
+                            checks: vec![],
                             conn_id: None,
                         }),
                     );
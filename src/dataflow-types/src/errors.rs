@@ -9,27 +9,99 @@
 
 use std::fmt::Display;
 
-use expr::EvalError;
+use expr::{EvalError, GlobalId};
 
 use serde::{Deserialize, Serialize};
 
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum SourceError {
     FileIO(String),
+    /// A record could not be decoded according to the source's format.
+    Decode(DecodeError),
+}
+
+impl SourceError {
+    /// Returns the code that classifies this error.
+    pub fn code(&self) -> SourceErrorCode {
+        match self {
+            SourceError::FileIO(_) => SourceErrorCode::FileIo,
+            SourceError::Decode(e) => e.code,
+        }
+    }
 }
 
 impl Display for SourceError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             SourceError::FileIO(e) => write!(f, "File IO: {}", e),
+            SourceError::Decode(e) => write!(f, "Decode error: {}", e.text),
         }
     }
 }
 
+/// A record that could not be decoded according to its source's format.
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub struct DecodeError {
+    /// The class of the error.
+    pub code: SourceErrorCode,
+    /// A description of the error for humans.
+    pub text: String,
+}
+
+/// A machine-readable classification of a [`SourceError`].
+///
+/// Unlike the descriptions of the errors, which may be reworded at any time,
+/// the names that [`SourceErrorCode::as_str`] returns are stable, so that
+/// alerts can be keyed on them.
+#[derive(Ord, PartialOrd, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub enum SourceErrorCode {
+    /// The source's file could not be read.
+    FileIo,
+    /// A record was not valid JSON.
+    JsonParse,
+    /// A JSON record did not match the source's JSON Schema.
+    JsonSchemaMismatch,
+    /// An Avro record could not be decoded with the source's schema.
+    AvroSchemaMismatch,
+    /// A CSV record had the wrong number of fields.
+    CsvFieldCount,
+    /// A CSV record was not valid UTF-8, or a field did not parse as its
+    /// column's type.
+    CsvParse,
+    /// A message key could not be decoded with the source's key format.
+    KeyDecode,
+}
+
+impl SourceErrorCode {
+    /// Returns the stable name of the code.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceErrorCode::FileIo => "FILE_IO",
+            SourceErrorCode::JsonParse => "JSON_PARSE",
+            SourceErrorCode::JsonSchemaMismatch => "JSON_SCHEMA_MISMATCH",
+            SourceErrorCode::AvroSchemaMismatch => "AVRO_SCHEMA_MISMATCH",
+            SourceErrorCode::CsvFieldCount => "CSV_FIELD_COUNT",
+            SourceErrorCode::CsvParse => "CSV_PARSE",
+            SourceErrorCode::KeyDecode => "KEY_DECODE",
+        }
+    }
+}
+
+impl Display for SourceErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum DataflowError {
     EvalError(EvalError),
     SourceError(SourceError),
+    /// An index's arrangement grew beyond its memory limit.
+    IndexMemoryLimitExceeded {
+        index_id: GlobalId,
+        limit: usize,
+    },
 }
 
 impl Display for DataflowError {
@@ -37,6 +109,11 @@ impl Display for DataflowError {
         match self {
             DataflowError::EvalError(e) => write!(f, "Evaluation error: {}", e),
             DataflowError::SourceError(e) => write!(f, "Source error: {}", e),
+            DataflowError::IndexMemoryLimitExceeded { index_id, limit } => write!(
+                f,
+                "index {} exceeded its memory limit of {} bytes",
+                index_id, limit
+            ),
         }
     }
 }
@@ -53,6 +53,7 @@ pub enum MaterializedLog {
     FrontierCurrent,
     PeekCurrent,
     PeekDuration,
+    SourceErrors,
     SourceInfo,
 }
 
@@ -132,6 +133,7 @@ impl LogVariant {
             LogVariant::Materialized(MaterializedLog::DataflowCurrent) => RelationDesc::empty()
                 .with_column("name", ScalarType::String.nullable(false))
                 .with_column("worker", ScalarType::Int64.nullable(false))
+                .with_column("dataflow_id", ScalarType::Int64.nullable(false))
                 .with_key(vec![0, 1]),
 
             LogVariant::Materialized(MaterializedLog::SourceInfo) => RelationDesc::empty()
@@ -141,6 +143,15 @@ impl LogVariant {
                 .with_column("partition_id", ScalarType::String.nullable(false))
                 .with_column("offset", ScalarType::Int64.nullable(false))
                 .with_column("timestamp", ScalarType::Int64.nullable(false))
+                .with_column("bytes", ScalarType::Int64.nullable(false))
+                .with_key(vec![0, 1, 2, 3]),
+
+            LogVariant::Materialized(MaterializedLog::SourceErrors) => RelationDesc::empty()
+                .with_column("source_name", ScalarType::String.nullable(false))
+                .with_column("source_id", ScalarType::String.nullable(false))
+                .with_column("dataflow_id", ScalarType::Int64.nullable(false))
+                .with_column("error_code", ScalarType::String.nullable(false))
+                .with_column("count", ScalarType::Int64.nullable(false))
                 .with_key(vec![0, 1, 2, 3]),
 
             LogVariant::Materialized(MaterializedLog::DataflowDependency) => RelationDesc::empty()
@@ -201,6 +212,7 @@ impl LogVariant {
             LogVariant::Materialized(MaterializedLog::DataflowDependency) => vec![],
             LogVariant::Materialized(MaterializedLog::FrontierCurrent) => vec![],
             LogVariant::Materialized(MaterializedLog::PeekCurrent) => vec![],
+            LogVariant::Materialized(MaterializedLog::SourceErrors) => vec![],
             LogVariant::Materialized(MaterializedLog::SourceInfo) => vec![],
             LogVariant::Materialized(MaterializedLog::PeekDuration) => vec![],
         }
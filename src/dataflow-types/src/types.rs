@@ -29,8 +29,11 @@ use url::Url;
 use uuid::Uuid;
 
 use aws_util::aws;
-use expr::{GlobalId, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, PartitionId};
+use expr::{
+    ColumnOrder, GlobalId, MirRelationExpr, MirScalarExpr, OptimizedMirRelationExpr, PartitionId,
+};
 use interchange::avro::{self, DebeziumDeduplicationStrategy};
+use interchange::json_schema;
 use interchange::protobuf::{decode_descriptors, validate_descriptors};
 use kafka_util::KafkaAddrs;
 use repr::{ColumnName, ColumnType, RelationDesc, RelationType, Row, ScalarType, Timestamp};
@@ -71,6 +74,9 @@ pub struct BuildDesc {
     /// If building a view, the types of columns of the built view
     /// None if building an index
     pub typ: Option<RelationType>,
+    /// If building an index, the maximum number of bytes its arrangement
+    /// may occupy, if limited
+    pub memory_limit_bytes: Option<usize>,
 }
 
 /// A description of a dataflow to construct and results to surface.
@@ -153,6 +159,7 @@ impl DataflowDesc {
             id,
             relation_expr: expr,
             typ: Some(typ),
+            memory_limit_bytes: None,
         });
     }
 
@@ -162,6 +169,7 @@ impl DataflowDesc {
         on_id: GlobalId,
         on_type: RelationType,
         keys: Vec<MirScalarExpr>,
+        memory_limit_bytes: Option<usize>,
     ) {
         self.objects_to_build.push(BuildDesc {
             id,
@@ -172,6 +180,7 @@ impl DataflowDesc {
                 },
             ),
             typ: None,
+            memory_limit_bytes,
         });
     }
 
@@ -300,6 +309,8 @@ pub enum DataEncoding {
     Avro(AvroEncoding),
     AvroOcf(AvroOcfEncoding),
     Protobuf(ProtobufEncoding),
+    JsonSchema(JsonSchemaEncoding),
+    Json(JsonEncoding),
     Csv(CsvEncoding),
     Regex(RegexEncoding),
     Bytes,
@@ -372,6 +383,15 @@ impl DataEncoding {
                 let d = decode_descriptors(descriptors)?;
                 validate_descriptors(message_name, &d)?
             }
+            DataEncoding::JsonSchema(JsonSchemaEncoding { value_schema }) => {
+                json_schema::validate_value_schema(value_schema)
+                    .context("validating JSON Schema")?
+                    .into_iter()
+                    .fold(key_desc, |desc, (name, ty)| desc.with_column(name, ty))
+            }
+            DataEncoding::Json(_) => {
+                key_desc.with_column("data", ScalarType::Jsonb.nullable(false))
+            }
             DataEncoding::Regex(RegexEncoding { regex }) => regex
                 .capture_names()
                 .enumerate()
@@ -388,11 +408,17 @@ impl DataEncoding {
                     let ty = ScalarType::String.nullable(true);
                     desc.with_column(name, ty)
                 }),
-            DataEncoding::Csv(CsvEncoding { n_cols, .. }) => {
-                (1..=*n_cols).fold(key_desc, |desc, i| {
-                    desc.with_column(format!("column{}", i), ScalarType::String.nullable(false))
-                })
-            }
+            DataEncoding::Csv(CsvEncoding {
+                n_cols,
+                column_types,
+                ..
+            }) => (1..=*n_cols).fold(key_desc, |desc, i| {
+                let ty = match column_types {
+                    Some(column_types) => column_types[i - 1].clone(),
+                    None => ScalarType::String,
+                };
+                desc.with_column(format!("column{}", i), ty.nullable(false))
+            }),
             DataEncoding::Text => key_desc.with_column("text", ScalarType::String.nullable(false)),
         })
     }
@@ -403,6 +429,8 @@ impl DataEncoding {
             DataEncoding::AvroOcf { .. } => "AvroOcf",
             DataEncoding::Avro(_) => "Avro",
             DataEncoding::Protobuf(_) => "Protobuf",
+            DataEncoding::JsonSchema(_) => "JsonSchema",
+            DataEncoding::Json(_) => "Json",
             DataEncoding::Regex { .. } => "Regex",
             DataEncoding::Csv(_) => "Csv",
             DataEncoding::Text => "Text",
@@ -430,12 +458,36 @@ pub struct ProtobufEncoding {
     pub message_name: String,
 }
 
+/// Encoding in JSON format, described by a JSON Schema.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonSchemaEncoding {
+    pub value_schema: String,
+}
+
+/// Encoding in newline-delimited JSON format, with one document per line.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JsonEncoding {
+    pub on_decode_error: DecodeErrorPolicy,
+}
+
+/// What to do with a record that cannot be decoded.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum DecodeErrorPolicy {
+    /// Report the record on the source's error stream, which causes queries
+    /// of the source to fail.
+    Fail,
+    /// Log the record and drop it, then continue decoding.
+    Skip,
+}
+
 /// Encoding in CSV format, with `n_cols` columns per row, with an optional header.
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct CsvEncoding {
     pub header_row: bool,
     pub n_cols: usize,
     pub delimiter: u8,
+    /// The types of the columns, if they are not all text.
+    pub column_types: Option<Vec<ScalarType>>,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -513,13 +565,30 @@ pub enum SourceConnector {
     External {
         connector: ExternalSourceConnector,
         encoding: DataEncoding,
+        /// The encoding of the message keys to decode into the source's
+        /// trailing key columns, for Kafka sources created with
+        /// `include_key = true`.
+        key_encoding: Option<DataEncoding>,
         envelope: SourceEnvelope,
         consistency: Consistency,
         ts_frequency: Duration,
+        /// Suppresses rows whose key was already seen within a time window,
+        /// for sources created with `dedupe_key` and `dedupe_window`.
+        dedupe: Option<SourceDedupe>,
     },
     Local,
 }
 
+/// Deduplicates the rows of a source by key within a time window.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct SourceDedupe {
+    /// The indices of the columns that make up the key.
+    pub key_indices: Vec<usize>,
+    /// The length of the window, in milliseconds. A row is suppressed if a row
+    /// with the same key was emitted less than this long before it.
+    pub window_ms: u64,
+}
+
 pub fn cached_files(e: &ExternalSourceConnector) -> Vec<PathBuf> {
     match e {
         ExternalSourceConnector::Kafka(KafkaSourceConnector { cached_files, .. }) => {
@@ -544,13 +613,37 @@ impl ExternalSourceConnector {
     ///
     /// Presently, each source type exposes precisely one metadata column that
     /// corresponds to some source-specific record counter. For example, file
-    /// sources use a line number, while Kafka sources use a topic offset.
+    /// sources use a line number, while Kafka sources use a topic offset. Kafka
+    /// sources that read a topic pattern additionally expose a `topic` column
+    /// with the name of each record's topic, those created with
+    /// `include_headers` a `headers` column that maps each header key to its
+    /// last value, and those created with `include_timestamp_type` a
+    /// `timestamp_type` column with the type of each record's timestamp.
     ///
     /// The columns declared here must be kept in sync with the actual source
     /// implementations that produce these columns.
     pub fn metadata_columns(&self) -> Vec<(ColumnName, ColumnType)> {
         match self {
-            Self::Kafka(_) => vec![("mz_offset".into(), ScalarType::Int64.nullable(false))],
+            Self::Kafka(kc) => {
+                let mut columns = vec![("mz_offset".into(), ScalarType::Int64.nullable(false))];
+                if kc.topic_pattern {
+                    columns.push(("topic".into(), ScalarType::String.nullable(false)));
+                }
+                if kc.include_headers {
+                    columns.push((
+                        "headers".into(),
+                        ScalarType::Map {
+                            value_type: Box::new(ScalarType::Bytes),
+                            custom_oid: None,
+                        }
+                        .nullable(false),
+                    ));
+                }
+                if kc.include_timestamp_type {
+                    columns.push(("timestamp_type".into(), ScalarType::String.nullable(true)));
+                }
+                columns
+            }
             Self::File(_) => vec![("mz_line_no".into(), ScalarType::Int64.nullable(false))],
             Self::Kinesis(_) => vec![("mz_offset".into(), ScalarType::Int64.nullable(false))],
             Self::AvroOcf(_) => vec![("mz_obj_no".into(), ScalarType::Int64.nullable(false))],
@@ -647,18 +740,32 @@ pub struct KafkaSourceConnector {
     pub config_options: BTreeMap<String, String>,
     // Map from partition -> starting offset
     pub start_offsets: HashMap<i32, i64>,
+    // The time, in milliseconds since the Unix epoch, from which to start
+    // reading each partition, if any.
+    pub start_timestamp: Option<i64>,
     pub group_id_prefix: Option<String>,
     pub enable_caching: bool,
     pub cluster_id: Uuid,
     // This field gets set after the initial construction of this struct, so this is None if it has
     // not yet been set.
     pub cached_files: Option<Vec<PathBuf>>,
+    // Whether to expose each record's headers in a `headers` column.
+    pub include_headers: bool,
+    // Whether to expose the type of each record's timestamp, `CreateTime` or
+    // `LogAppendTime`, in a `timestamp_type` column.
+    pub include_timestamp_type: bool,
+    // Whether `topic` is a regular expression, in which case the source reads
+    // every topic whose entire name matches it and exposes each record's
+    // topic in a `topic` column.
+    pub topic_pattern: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KinesisSourceConnector {
     pub stream_name: String,
     pub aws_info: aws::ConnectInfo,
+    /// How often to refresh the list of the stream's shards.
+    pub shard_refresh: Duration,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -666,6 +773,9 @@ pub struct FileSourceConnector {
     pub path: PathBuf,
     pub tail: bool,
     pub compression: Compression,
+    /// The byte sequence that separates the file's records. Avro OCF sources
+    /// ignore this, as their records are framed by the OCF format.
+    pub line_delimiter: Vec<u8>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -673,6 +783,9 @@ pub struct S3SourceConnector {
     pub key_sources: Vec<S3KeySource>,
     pub pattern: Option<Glob>,
     pub aws_info: aws::ConnectInfo,
+    /// The compression of the source's objects, or `None` to detect the
+    /// compression of each object from its key and metadata.
+    pub compression: Option<Compression>,
 }
 
 /// A Source of Object Key names, the argument of the `OBJECTS FROM` clause
@@ -687,6 +800,8 @@ pub enum SinkConnector {
     Kafka(KafkaSinkConnector),
     Tail(TailSinkConnector),
     AvroOcf(AvroOcfSinkConnector),
+    S3(S3SinkConnector),
+    Notify(NotifySinkConnector),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -695,6 +810,17 @@ pub struct KafkaSinkConsistencyConnector {
     pub schema_id: i32,
 }
 
+/// The codec with which a Kafka sink compresses the message batches that it
+/// produces.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CompressionCodec {
+    None,
+    Gzip,
+    Snappy,
+    Lz4,
+    Zstd,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KafkaSinkConnector {
     pub addrs: KafkaAddrs,
@@ -704,11 +830,18 @@ pub struct KafkaSinkConnector {
     pub key_schema_id: Option<i32>,
     pub value_schema_id: i32,
     pub consistency: Option<KafkaSinkConsistencyConnector>,
+    pub partition_count: i32,
+    /// An expression over the rows of the sink's input, and its type, whose
+    /// hashed value selects the partition of each message in place of the
+    /// message key.
+    pub partition_by: Option<(MirScalarExpr, ScalarType)>,
     // Maximum number of records the sink will attempt to send each time it is
     // invoked
     pub fuel: usize,
     pub frontier: Antichain<Timestamp>,
     pub strict: bool,
+    /// The codec with which the sink's producer compresses message batches.
+    pub compression_codec: CompressionCodec,
     pub config_options: BTreeMap<String, String>,
 }
 
@@ -720,12 +853,66 @@ pub struct AvroOcfSinkConnector {
     pub strict: bool,
 }
 
+/// A sink that writes rolling Parquet files to an S3 bucket.
+///
+/// Each file is named `{prefix}{sink_id}-{counter}.parquet`, where the counter
+/// starts after the highest counter already present in the bucket and
+/// increases with each file. Files are only uploaded once complete, so an
+/// interrupted file never becomes visible. After a restart the sink writes its
+/// output again, starting from a new counter.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct S3SinkConnector {
+    pub bucket: String,
+    pub prefix: String,
+    pub aws_info: aws::ConnectInfo,
+    pub value_desc: RelationDesc,
+    /// The counter of the first file to write.
+    pub first_file_counter: u64,
+    /// The approximate size at which a file is completed.
+    pub max_file_bytes: u64,
+    /// The age of the oldest buffered row at which a file is completed.
+    pub max_file_age: Duration,
+    pub frontier: Antichain<Timestamp>,
+    pub strict: bool,
+}
+
+impl S3SinkConnector {
+    /// Returns the key of the object that holds file number `counter` of the
+    /// sink `id`.
+    pub fn object_key(&self, id: GlobalId, counter: u64) -> String {
+        format!("{}{}-{:010}.parquet", self.prefix, id, counter)
+    }
+}
+
+/// The length, in bytes, that the payload of a notification must be shorter
+/// than, as in PostgreSQL.
+pub const MAX_NOTIFY_PAYLOAD_BYTES: usize = 8000;
+
+/// A sink that sends a notification on a channel, like `NOTIFY`, for each row
+/// that appears in its input.
+///
+/// The payload of each notification is the value of the row's only column.
+/// Rows that disappear from the input do not produce notifications.
+#[derive(Clone, Debug, Serialize)]
+pub struct NotifySinkConnector {
+    pub channel: String,
+    pub value_desc: RelationDesc,
+    pub frontier: Antichain<Timestamp>,
+    pub strict: bool,
+    /// Receives the channel and payloads of the notifications for each batch
+    /// of new rows.
+    #[serde(skip)]
+    pub tx: mpsc::UnboundedSender<(String, Vec<String>)>,
+}
+
 impl SinkConnector {
     pub fn get_frontier(&self) -> Antichain<Timestamp> {
         match self {
             SinkConnector::AvroOcf(avro) => avro.frontier.clone(),
             SinkConnector::Kafka(kafka) => kafka.frontier.clone(),
             SinkConnector::Tail(tail) => tail.frontier.clone(),
+            SinkConnector::S3(s3) => s3.frontier.clone(),
+            SinkConnector::Notify(notify) => notify.frontier.clone(),
         }
     }
 
@@ -734,6 +921,8 @@ impl SinkConnector {
             SinkConnector::Kafka(k) => k.key_desc_and_indices.as_ref().map(|(desc, _indices)| desc),
             SinkConnector::Tail(_) => None,
             SinkConnector::AvroOcf(_) => None,
+            SinkConnector::S3(_) => None,
+            SinkConnector::Notify(_) => None,
         }
     }
 
@@ -745,6 +934,8 @@ impl SinkConnector {
                 .map(|(_desc, indices)| indices.as_slice()),
             SinkConnector::Tail(_) => None,
             SinkConnector::AvroOcf(_) => None,
+            SinkConnector::S3(_) => None,
+            SinkConnector::Notify(_) => None,
         }
     }
 
@@ -753,6 +944,8 @@ impl SinkConnector {
             SinkConnector::Kafka(k) => &k.value_desc,
             SinkConnector::Tail(t) => &t.value_desc,
             SinkConnector::AvroOcf(a) => &a.value_desc,
+            SinkConnector::S3(s) => &s.value_desc,
+            SinkConnector::Notify(n) => &n.value_desc,
         }
     }
 }
@@ -766,12 +959,30 @@ pub struct TailSinkConnector {
     pub emit_progress: bool,
     pub object_columns: usize,
     pub value_desc: RelationDesc,
+    pub output: TailOutput,
+    /// The order in which to emit the updates within each timestamp, in
+    /// terms of the columns of the tailed relation. Empty if the updates may
+    /// be emitted in any order.
+    pub order_by: Vec<ColumnOrder>,
+}
+
+/// The shape of the rows that a TAIL emits.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum TailOutput {
+    /// The timestamp, diff, and columns of each update are emitted as separate
+    /// columns.
+    Rows,
+    /// Each update is emitted as a single `text` column that contains a JSON
+    /// object with `mz_timestamp`, `mz_diff`, and one key per column.
+    Jsonl,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SinkConnectorBuilder {
     Kafka(KafkaSinkConnectorBuilder),
     AvroOcf(AvroOcfSinkConnectorBuilder),
+    S3(S3SinkConnectorBuilder),
+    Notify(NotifySinkConnectorBuilder),
 }
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
@@ -781,6 +992,22 @@ pub struct AvroOcfSinkConnectorBuilder {
     pub value_desc: RelationDesc,
 }
 
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct S3SinkConnectorBuilder {
+    pub bucket: String,
+    pub prefix: String,
+    pub aws_info: aws::ConnectInfo,
+    pub value_desc: RelationDesc,
+    pub max_file_bytes: u64,
+    pub max_file_age: Duration,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct NotifySinkConnectorBuilder {
+    pub channel: String,
+    pub value_desc: RelationDesc,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct KafkaSinkConnectorBuilder {
     pub broker_addrs: KafkaAddrs,
@@ -793,8 +1020,16 @@ pub struct KafkaSinkConnectorBuilder {
     pub topic_suffix: String,
     pub partition_count: i32,
     pub replication_factor: i32,
+    pub partition_by: Option<(MirScalarExpr, ScalarType)>,
     pub fuel: usize,
     pub consistency_value_schema: Option<String>,
+    /// The name of the consistency topic from which the sink resumes after a
+    /// restart, if the user named one. Such sinks write to the topic named
+    /// by `topic_prefix` verbatim, so that each incarnation of the sink
+    /// appends to the same topics.
+    pub consistency_topic: Option<String>,
+    /// The codec with which the sink's producer compresses message batches.
+    pub compression_codec: CompressionCodec,
     pub config_options: BTreeMap<String, String>,
     pub ccsr_config: ccsr::ClientConfig,
 }
@@ -10,10 +10,11 @@
 use futures::executor::block_on;
 use log::error;
 
+use dataflow_types::{DecodeError, SourceErrorCode};
 use interchange::avro::{DebeziumDeduplicationStrategy, Decoder, EnvelopeType};
-use repr::{Diff, Row, Timestamp};
+use repr::{Diff, Row, RowPacker, Timestamp};
 
-use super::{DecoderState, PushSession};
+use super::{with_key_columns, DecodeErrorLogger, DecoderState, PushSession};
 use crate::metrics::EVENTS_COUNTER;
 
 pub struct AvroDecoderState {
@@ -21,6 +22,8 @@ pub struct AvroDecoderState {
     events_success: i64,
     events_error: i64,
     reject_non_inserts: bool,
+    row_packer: RowPacker,
+    error_logger: Option<DecodeErrorLogger>,
 }
 
 impl AvroDecoderState {
@@ -48,8 +51,29 @@ impl AvroDecoderState {
             events_success: 0,
             events_error: 0,
             reject_non_inserts,
+            row_packer: RowPacker::new(),
+            error_logger: None,
         })
     }
+
+    /// Reports the records that fail to decode to `error_logger`, rather
+    /// than only to the log.
+    pub fn with_error_logger(mut self, error_logger: DecodeErrorLogger) -> Self {
+        self.error_logger = Some(error_logger);
+        self
+    }
+
+    fn log_decode_error(&mut self, err: anyhow::Error) {
+        self.events_error += 1;
+        let text = format!("avro deserialization error: {}", err);
+        match self.error_logger.as_mut() {
+            Some(error_logger) => error_logger.log(DecodeError {
+                code: SourceErrorCode::AvroSchemaMismatch,
+                text,
+            }),
+            None => error!("{}", text),
+        }
+    }
 }
 
 impl DecoderState for AvroDecoderState {
@@ -78,6 +102,9 @@ impl DecoderState for AvroDecoderState {
         bytes: &[u8],
         coord: Option<i64>,
         upstream_time_millis: Option<i64>,
+        _topic: Option<&str>,
+        _headers: Option<&[(String, Vec<u8>)]>,
+        _timestamp_type: Option<Option<&str>>,
         session: &mut PushSession<'a, (Row, Option<Row>, Timestamp)>,
         time: Timestamp,
     ) {
@@ -86,10 +113,7 @@ impl DecoderState for AvroDecoderState {
                 self.events_success += 1;
                 session.give((key, diff_pair.after, time));
             }
-            Err(err) => {
-                self.events_error += 1;
-                error!("avro deserialization error: {}", err)
-            }
+            Err(err) => self.log_decode_error(err),
         }
     }
 
@@ -99,6 +123,10 @@ impl DecoderState for AvroDecoderState {
         bytes: &[u8],
         coord: Option<i64>,
         upstream_time_millis: Option<i64>,
+        _topic: Option<&str>,
+        _headers: Option<&[(String, Vec<u8>)]>,
+        _timestamp_type: Option<Option<&str>>,
+        key_columns: Option<&Row>,
         session: &mut PushSession<'a, (Row, Timestamp, Diff)>,
         time: Timestamp,
     ) {
@@ -114,16 +142,16 @@ impl DecoderState for AvroDecoderState {
                     // element of the data, which will cause it to turn into a retraction
                     // in a future call to `explode`
                     // (currently in dataflow/render/mod.rs:299)
-                    session.give((diff_pair.before.unwrap(), time, 1));
+                    let before = diff_pair.before.unwrap();
+                    let before = with_key_columns(&mut self.row_packer, before, key_columns);
+                    session.give((before, time, 1));
                 }
                 if let Some(after) = diff_pair.after {
+                    let after = with_key_columns(&mut self.row_packer, after, key_columns);
                     session.give((after, time, 1));
                 }
             }
-            Err(err) => {
-                self.events_error += 1;
-                error!("avro deserialization error: {}", err)
-            }
+            Err(err) => self.log_decode_error(err),
         }
     }
 
@@ -7,25 +7,27 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::iter;
-
-use dataflow_types::LinearOperator;
-
-use log::error;
+use dataflow_types::{DecodeError, LinearOperator, SourceErrorCode};
 
 use timely::dataflow::operators::Operator;
 use timely::dataflow::{Scope, Stream};
 
-use repr::{Datum, Diff, Row, Timestamp};
+use interchange::csv::parse_field;
+use repr::{Datum, Diff, Row, ScalarType, Timestamp};
 
+use super::{push_headers, DecodeErrorLogger, KeyDecoder};
 use crate::{metrics::EVENTS_COUNTER, source::SourceOutput};
 
+#[allow(clippy::too_many_arguments)]
 pub fn csv<G>(
     stream: &Stream<G, SourceOutput<Vec<u8>, Vec<u8>>>,
     header_row: bool,
     n_cols: usize,
     delimiter: u8,
+    column_types: Option<Vec<ScalarType>>,
     operators: &mut Option<LinearOperator>,
+    mut key_decoder: Option<KeyDecoder>,
+    mut error_logger: DecodeErrorLogger,
 ) -> Stream<G, (Row, Timestamp, Diff)>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -62,12 +64,15 @@ where
                     // but the CsvReader *itself* searches for line breaks.
                     // This is mainly an aesthetic/performance-golfing
                     // issue as I doubt it will ever be a bottleneck.
-                    for SourceOutput { key: _, value: line, position: line_no , upstream_time_millis: _ } in &*lines {
+                    for SourceOutput { key, key_present, value: line, position: line_no , upstream_time_millis: _, topic, headers, timestamp_type } in &*lines {
                         // We only want to process utf8 strings, as this ensures that all fields
                         // will be utf8 as well, allowing some unsafe shenanigans.
                         if std::str::from_utf8(line.as_slice()).is_err() {
                             events_error += 1;
-                            error!("CSV error: input text is not utf8");
+                            error_logger.log(DecodeError {
+                                code: SourceErrorCode::CsvParse,
+                                text: "CSV error: input text is not utf8".into(),
+                            });
                         } else {
                             // Reset the reader to read a new series of records.
                             csv_reader.reset();
@@ -109,37 +114,66 @@ where
                                     csv_core::ReadRecordResult::Record => {
                                         if bounds_valid != n_cols {
                                             events_error += 1;
-                                            error!(
-                                                "CSV error: expected {} columns, got {}. Ignoring row.",
-                                                n_cols, bounds_valid,
-                                            );
-                                        } else {
-                                            events_success += 1;
-                                            session.give((
-                                                row_packer.pack(
-                                                    (0..n_cols)
-                                                        .map(|i| {
-                                                            // Unsafety rationalized as 1. the input text is determined to be
-                                                            // valid utf8, and 2. the delimiter is ascii, which should make each
-                                                            // delimited region also utf8.
-                                                            Datum::String(unsafe {
-                                                                if demanded[i] {
-                                                                    std::str::from_utf8_unchecked(
-                                                                        &buffer
-                                                                            [bounds[i]..bounds[i + 1]],
-                                                                    )
-                                                                } else {
-                                                                    ""
-                                                                }
-                                                            })
-                                                        })
-                                                        .chain(iter::once(
-                                                            line_no.map(Datum::Int64).into(),
-                                                        )),
+                                            error_logger.log(DecodeError {
+                                                code: SourceErrorCode::CsvFieldCount,
+                                                text: format!(
+                                                    "CSV error: expected {} columns, got {}. Ignoring row.",
+                                                    n_cols, bounds_valid,
                                                 ),
-                                                *cap.time(),
-                                                1,
-                                            ));
+                                            });
+                                        } else {
+                                            let mut parse_error = None;
+                                            for i in 0..n_cols {
+                                                // Unsafety rationalized as 1. the input text is determined to be
+                                                // valid utf8, and 2. the delimiter is ascii, which should make each
+                                                // delimited region also utf8.
+                                                let field = unsafe {
+                                                    std::str::from_utf8_unchecked(
+                                                        &buffer[bounds[i]..bounds[i + 1]],
+                                                    )
+                                                };
+                                                let datum = match &column_types {
+                                                    _ if !demanded[i] => Datum::String(""),
+                                                    Some(column_types) => {
+                                                        match parse_field(field, &column_types[i]) {
+                                                            Ok(datum) => datum,
+                                                            Err(e) => {
+                                                                parse_error = Some(e);
+                                                                break;
+                                                            }
+                                                        }
+                                                    }
+                                                    None => Datum::String(field),
+                                                };
+                                                row_packer.push(datum);
+                                            }
+                                            row_packer.push(line_no.map(Datum::Int64).into());
+                                            if let Some(topic) = topic {
+                                                row_packer.push(Datum::String(topic));
+                                            }
+                                            if let Some(headers) = headers {
+                                                push_headers(&mut row_packer, headers);
+                                            }
+                                            if let Some(timestamp_type) = timestamp_type {
+                                                row_packer.push(Datum::from(timestamp_type.as_deref()));
+                                            }
+                                            if let Some(key_decoder) = &mut key_decoder {
+                                                row_packer.extend_by_row(&key_decoder.decode(key, *key_present));
+                                            }
+                                            let row = row_packer.finish_and_reuse();
+                                            match parse_error {
+                                                Some(e) => {
+                                                    events_error += 1;
+                                                    error_logger.log(DecodeError {
+                                                        code: SourceErrorCode::CsvParse,
+                                                        text: format!("CSV error: {:#}. Ignoring row.", e),
+                                                    });
+                                                }
+                                                None => {
+                                                    events_success += 1;
+                                                    session.give((row, *cap.time(), 1));
+                                                }
+                                            }
                                             // Reset valid data to extract the next record, should one exist.
                                             buffer_valid = 0;
                                             bounds_valid = 0;
@@ -153,6 +187,9 @@ where
                         }
                     }
                 });
+                if let Some(key_decoder) = &mut key_decoder {
+                    key_decoder.log_error_count();
+                }
                 if events_success > 0 {
                     EVENTS_COUNTER.csv.success.inc_by(events_success);
                 }
@@ -163,3 +200,51 @@ where
         },
     )
 }
+
+/// Decodes `bytes` as a single CSV record of exactly `n_cols` string fields.
+pub(super) fn decode_record(bytes: &[u8], n_cols: usize, delimiter: u8) -> Result<Row, String> {
+    let text = std::str::from_utf8(bytes).map_err(|_| "CSV error: input text is not utf8")?;
+    let mut reader = csv_core::ReaderBuilder::new().delimiter(delimiter).build();
+    let mut buffer = vec![0u8; text.len()];
+    let mut bounds = vec![0usize; n_cols];
+    let mut input = text.as_bytes();
+    let mut buffer_valid = 0;
+    let mut bounds_valid = 0;
+    loop {
+        let (result, in_read, out_wrote, ends_wrote) = reader.read_record(
+            input,
+            &mut buffer[buffer_valid..],
+            &mut bounds[bounds_valid..],
+        );
+        input = &input[in_read..];
+        buffer_valid += out_wrote;
+        bounds_valid += ends_wrote;
+        match result {
+            csv_core::ReadRecordResult::InputEmpty => (),
+            csv_core::ReadRecordResult::Record | csv_core::ReadRecordResult::End => break,
+            csv_core::ReadRecordResult::OutputFull => {
+                let length = buffer.len();
+                buffer.extend(std::iter::repeat(0).take(length.max(1)));
+            }
+            csv_core::ReadRecordResult::OutputEndsFull => {
+                let length = bounds.len();
+                bounds.extend(std::iter::repeat(0).take(length.max(1)));
+            }
+        }
+    }
+    if bounds_valid != n_cols {
+        return Err(format!(
+            "CSV error: expected {} columns, got {}",
+            n_cols, bounds_valid
+        ));
+    }
+    let mut start = 0;
+    let fields = bounds[..n_cols].iter().map(|&end| {
+        // The input is valid utf8 and the delimiter is ascii, so each field
+        // is valid utf8 as well.
+        let field = std::str::from_utf8(&buffer[start..end]).expect("field is utf8");
+        start = end;
+        Datum::String(field)
+    });
+    Ok(Row::pack(fields))
+}
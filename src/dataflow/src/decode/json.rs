@@ -0,0 +1,132 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use log::warn;
+use timely::dataflow::{Scope, Stream};
+
+use dataflow_types::{DecodeError, DecodeErrorPolicy, JsonEncoding, SourceError, SourceErrorCode};
+use expr::SourceInstanceId;
+use repr::adt::jsonb::JsonbPacker;
+use repr::{Datum, Diff, Row, RowPacker, Timestamp};
+
+use crate::logging::materialized::{Logger, MaterializedEvent};
+use crate::operator::StreamExt;
+use crate::source::SourceOutput;
+
+/// The number of characters of a malformed document that are included in the
+/// error that reports it.
+const MAX_REPORTED_CHARS: usize = 64;
+
+/// Decodes a stream of newline-delimited JSON documents into `jsonb` values.
+///
+/// Documents that fail to parse are handled according to the encoding's
+/// [`DecodeErrorPolicy`]: with `Fail`, they are emitted on the returned error
+/// stream, which causes queries of the source to fail; with `Skip`, they are
+/// dropped, and decoding continues with the next document. Skipped documents
+/// are still recorded in the `mz_source_errors` log of the source instance
+/// `source_id`, if `logger` is present, so that they are counted alongside the
+/// errors that `Fail` reports.
+pub fn json<G>(
+    stream: &Stream<G, SourceOutput<Vec<u8>, Vec<u8>>>,
+    encoding: &JsonEncoding,
+    debug_name: &str,
+    mut logger: Option<Logger>,
+    source_name: String,
+    source_id: SourceInstanceId,
+) -> (Stream<G, (Row, Timestamp, Diff)>, Stream<G, SourceError>)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let on_decode_error = encoding.on_decode_error;
+    let debug_name = debug_name.to_string();
+    let pact = SourceOutput::<Vec<u8>, Vec<u8>>::position_value_contract();
+    stream.unary_fallible(pact, "JsonDecode", |_cap, _op_info| {
+        move |input, ok_output, err_output| {
+            input.for_each(|cap, data| {
+                let mut ok_session = ok_output.session(&cap);
+                let mut err_session = err_output.session(&cap);
+                for SourceOutput {
+                    value, position, ..
+                } in &*data
+                {
+                    if value.iter().all(|b| b.is_ascii_whitespace()) {
+                        continue;
+                    }
+                    match JsonbPacker::new(RowPacker::new()).pack_slice(value) {
+                        Ok(mut packer) => {
+                            if let Some(position) = position {
+                                packer.push(Datum::from(*position));
+                            }
+                            ok_session.give((packer.finish(), *cap.time(), 1))
+                        }
+                        Err(e) => {
+                            let msg = describe_malformed(value, *position, &e);
+                            match on_decode_error {
+                                DecodeErrorPolicy::Fail => {
+                                    err_session.give(SourceError::Decode(DecodeError {
+                                        code: SourceErrorCode::JsonParse,
+                                        text: msg,
+                                    }))
+                                }
+                                DecodeErrorPolicy::Skip => {
+                                    warn!("{}: skipping {}", debug_name, msg);
+                                    if let Some(logger) = logger.as_mut() {
+                                        logger.log(MaterializedEvent::SourceError {
+                                            source_name: source_name.clone(),
+                                            source_id,
+                                            code: SourceErrorCode::JsonParse,
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            });
+        }
+    })
+}
+
+/// Describes a document that failed to parse, including its position in the
+/// source, if known, the byte offset within the document at which parsing
+/// failed, and a prefix of its content.
+fn describe_malformed(value: &[u8], position: Option<i64>, err: &anyhow::Error) -> String {
+    let content = String::from_utf8_lossy(value);
+    let mut prefix: String = content.chars().take(MAX_REPORTED_CHARS).collect();
+    if prefix.len() < content.len() {
+        prefix.push_str("...");
+    }
+    let mut msg = "malformed JSON".to_string();
+    if let Some(position) = position {
+        msg += &format!(" on line {}", position);
+    }
+    if let Some(offset) = error_offset(value, err) {
+        msg += &format!(" at byte {}", offset);
+    }
+    format!("{}: {}: {:?}", msg, err, prefix)
+}
+
+/// Computes the zero-based byte offset within `value` at which `err`, if it
+/// is a JSON syntax error, occurred.
+///
+/// `serde_json` reports the location of an error as a one-based line and a
+/// column that counts the bytes of that line up to and including the byte at
+/// fault.
+fn error_offset(value: &[u8], err: &anyhow::Error) -> Option<usize> {
+    let err = err.downcast_ref::<serde_json::Error>()?;
+    if err.line() == 0 {
+        return None;
+    }
+    let line_start: usize = value
+        .split(|b| *b == b'\n')
+        .take(err.line() - 1)
+        .map(|line| line.len() + 1)
+        .sum();
+    Some((line_start + err.column().saturating_sub(1)).min(value.len()))
+}
@@ -0,0 +1,62 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use timely::dataflow::{Scope, Stream};
+
+use dataflow_types::{DecodeError, JsonSchemaEncoding, SourceError, SourceErrorCode};
+use interchange::json_schema::Decoder;
+use repr::{Diff, Row, Timestamp};
+
+use crate::operator::StreamExt;
+use crate::source::SourceOutput;
+
+/// Decodes a stream of JSON documents that conform to a JSON Schema.
+///
+/// Unlike most decoders, documents that fail to decode are not dropped, but
+/// emitted on the returned error stream.
+pub fn json_schema<G>(
+    stream: &Stream<G, SourceOutput<Vec<u8>, Vec<u8>>>,
+    encoding: &JsonSchemaEncoding,
+) -> (Stream<G, (Row, Timestamp, Diff)>, Stream<G, SourceError>)
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let mut decoder =
+        Decoder::new(&encoding.value_schema).expect("JSON Schema is validated during planning");
+    let pact = SourceOutput::<Vec<u8>, Vec<u8>>::position_value_contract();
+    stream.unary_fallible(pact, "JsonSchemaDecode", |_cap, _op_info| {
+        move |input, ok_output, err_output| {
+            input.for_each(|cap, data| {
+                let mut ok_session = ok_output.session(&cap);
+                let mut err_session = err_output.session(&cap);
+                for SourceOutput { value, .. } in &*data {
+                    if value.is_empty() {
+                        continue;
+                    }
+                    match decoder.decode(value) {
+                        Ok(row) => ok_session.give((row, *cap.time(), 1)),
+                        Err(e) => {
+                            // Documents that are valid JSON, but don't match
+                            // the schema, fail to decode for other reasons.
+                            let code = if e.downcast_ref::<serde_json::Error>().is_some() {
+                                SourceErrorCode::JsonParse
+                            } else {
+                                SourceErrorCode::JsonSchemaMismatch
+                            };
+                            err_session.give(SourceError::Decode(DecodeError {
+                                code,
+                                text: format!("{:#}", e),
+                            }))
+                        }
+                    }
+                }
+            });
+        }
+    })
+}
@@ -7,7 +7,14 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::{any::Any, cell::RefCell, collections::VecDeque, rc::Rc, time::Duration};
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::{BTreeMap, VecDeque},
+    iter,
+    rc::Rc,
+    time::Duration,
+};
 
 use anyhow::anyhow;
 use differential_dataflow::{capture::YieldingIter, hashable::Hashable};
@@ -27,22 +34,62 @@ use timely::{
 
 use ::mz_avro::{types::Value, Schema};
 use dataflow_types::LinearOperator;
-use dataflow_types::{DataEncoding, RegexEncoding, SourceEnvelope};
-use interchange::avro::{extract_row, ConfluentAvroResolver, DebeziumDecodeState, DiffPair};
+use dataflow_types::{DataEncoding, DecodeError, RegexEncoding, SourceEnvelope, SourceErrorCode};
+use expr::SourceInstanceId;
+use interchange::avro::{
+    extract_row, ConfluentAvroResolver, DebeziumDecodeState, DiffPair, EnvelopeType,
+};
 use log::error;
 use repr::Datum;
 use repr::{Diff, Row, RowPacker, Timestamp};
 
 use self::csv::csv;
 use self::regex::regex as regex_fn;
+use crate::logging::materialized::{Logger, MaterializedEvent};
 use crate::operator::StreamExt;
 use crate::source::{SourceData, SourceOutput};
 
 mod avro;
 mod csv;
+mod json;
+mod json_schema;
 mod protobuf;
 mod regex;
 
+pub use self::json::json;
+pub use self::json_schema::json_schema;
+
+/// Reports the records that a decoder drops because they cannot be decoded,
+/// counting them by code in `mz_internal.mz_source_errors`.
+#[derive(Clone)]
+pub struct DecodeErrorLogger {
+    logger: Option<Logger>,
+    source_name: String,
+    source_id: SourceInstanceId,
+}
+
+impl DecodeErrorLogger {
+    pub fn new(logger: Option<Logger>, source_name: String, source_id: SourceInstanceId) -> Self {
+        DecodeErrorLogger {
+            logger,
+            source_name,
+            source_id,
+        }
+    }
+
+    /// Logs `err`, whose record is being dropped.
+    fn log(&mut self, err: DecodeError) {
+        error!("{}", err.text);
+        if let Some(logger) = self.logger.as_mut() {
+            logger.log(MaterializedEvent::SourceError {
+                source_name: self.source_name.clone(),
+                source_id: self.source_id,
+                code: err.code,
+            });
+        }
+    }
+}
+
 pub fn decode_avro_values<G>(
     stream: &Stream<G, SourceOutput<Vec<u8>, Value>>,
     envelope: &SourceEnvelope,
@@ -74,6 +121,10 @@ where
                 position: index,
                 upstream_time_millis,
                 key: _,
+                key_present: _,
+                topic: _,
+                headers: _,
+                timestamp_type: _,
             },
             r,
             d,
@@ -123,21 +174,31 @@ pub type PushSession<'a, R> =
 pub trait DecoderState {
     fn decode_key(&mut self, bytes: &[u8]) -> Result<Row, String>;
     /// give a session a key-value pair
+    #[allow(clippy::too_many_arguments)]
     fn give_key_value<'a>(
         &mut self,
         key: Row,
         bytes: &[u8],
         aux_num: Option<i64>,
         upstream_time_millis: Option<i64>,
+        topic: Option<&str>,
+        headers: Option<&[(String, Vec<u8>)]>,
+        timestamp_type: Option<Option<&str>>,
         session: &mut PushSession<'a, (Row, Option<Row>, Timestamp)>,
         time: Timestamp,
     );
-    /// give a session a plain value
+    /// give a session a plain value, followed by the datums of `key_columns`,
+    /// if present
+    #[allow(clippy::too_many_arguments)]
     fn give_value<'a>(
         &mut self,
         bytes: &[u8],
         aux_num: Option<i64>,
         upstream_time_millis: Option<i64>,
+        topic: Option<&str>,
+        headers: Option<&[(String, Vec<u8>)]>,
+        timestamp_type: Option<Option<&str>>,
+        key_columns: Option<&Row>,
         session: &mut PushSession<'a, (Row, Timestamp, Diff)>,
         time: Timestamp,
     );
@@ -146,12 +207,55 @@ pub trait DecoderState {
     fn log_error_count(&mut self);
 }
 
-fn pack_with_line_no(datum: Datum, line_no: Option<i64>) -> Row {
+fn pack_with_line_no(
+    row_packer: &mut RowPacker,
+    datum: Datum,
+    line_no: Option<i64>,
+    topic: Option<&str>,
+    headers: Option<&[(String, Vec<u8>)]>,
+    timestamp_type: Option<Option<&str>>,
+    key_columns: Option<&Row>,
+) -> Row {
+    row_packer.push(datum);
     if let Some(line_no) = line_no {
-        Row::pack_slice(&[datum, Datum::from(line_no)])
-    } else {
-        Row::pack_slice(&[datum])
+        row_packer.push(Datum::from(line_no));
+    }
+    if let Some(topic) = topic {
+        row_packer.push(Datum::String(topic));
     }
+    if let Some(headers) = headers {
+        push_headers(row_packer, headers);
+    }
+    if let Some(timestamp_type) = timestamp_type {
+        row_packer.push(Datum::from(timestamp_type));
+    }
+    if let Some(key_columns) = key_columns {
+        row_packer.extend_by_row(key_columns);
+    }
+    row_packer.finish_and_reuse()
+}
+
+/// Appends the datums of `key_columns`, if present, to `row`.
+fn with_key_columns(row_packer: &mut RowPacker, row: Row, key_columns: Option<&Row>) -> Row {
+    match key_columns {
+        None => row,
+        Some(key_columns) => {
+            row_packer.extend_by_row(&row);
+            row_packer.extend_by_row(key_columns);
+            row_packer.finish_and_reuse()
+        }
+    }
+}
+
+/// Pushes a map datum with the given message headers.
+///
+/// Header keys may repeat, in which case the last value for the key wins.
+fn push_headers(row_packer: &mut RowPacker, headers: &[(String, Vec<u8>)]) {
+    let headers: BTreeMap<_, _> = headers
+        .iter()
+        .map(|(k, v)| (k.as_str(), Datum::Bytes(v)))
+        .collect();
+    row_packer.push_dict(headers);
 }
 
 fn bytes_to_datum(bytes: &[u8]) -> Datum {
@@ -191,12 +295,23 @@ where
         bytes: &[u8],
         line_no: Option<i64>,
         _upstream_time_millis: Option<i64>,
+        topic: Option<&str>,
+        headers: Option<&[(String, Vec<u8>)]>,
+        timestamp_type: Option<Option<&str>>,
         session: &mut PushSession<'a, (Row, Option<Row>, Timestamp)>,
         time: Timestamp,
     ) {
         session.give((
             key,
-            Some(pack_with_line_no((self.datum_func)(bytes), line_no)),
+            Some(pack_with_line_no(
+                &mut self.row_packer,
+                (self.datum_func)(bytes),
+                line_no,
+                topic,
+                headers,
+                timestamp_type,
+                None,
+            )),
             time,
         ));
     }
@@ -207,11 +322,23 @@ where
         bytes: &[u8],
         line_no: Option<i64>,
         _upstream_time_millis: Option<i64>,
+        topic: Option<&str>,
+        headers: Option<&[(String, Vec<u8>)]>,
+        timestamp_type: Option<Option<&str>>,
+        key_columns: Option<&Row>,
         session: &mut PushSession<'a, (Row, Timestamp, Diff)>,
         time: Timestamp,
     ) {
         session.give((
-            pack_with_line_no((self.datum_func)(bytes), line_no),
+            pack_with_line_no(
+                &mut self.row_packer,
+                (self.datum_func)(bytes),
+                line_no,
+                topic,
+                headers,
+                timestamp_type,
+                key_columns,
+            ),
             time,
             1,
         ));
@@ -220,6 +347,96 @@ where
     fn log_error_count(&mut self) {}
 }
 
+/// Decodes the keys of a Kafka source's messages into the trailing key
+/// columns of its rows, for sources created with `include_key = true`.
+pub struct KeyDecoder {
+    format: KeyFormat,
+    arity: usize,
+    row_packer: RowPacker,
+    error_logger: DecodeErrorLogger,
+}
+
+enum KeyFormat {
+    Bytes,
+    Text,
+    Csv { n_cols: usize, delimiter: u8 },
+    Avro(avro::AvroDecoderState),
+}
+
+impl KeyDecoder {
+    fn new(
+        encoding: DataEncoding,
+        debug_name: &str,
+        worker_index: usize,
+        error_logger: DecodeErrorLogger,
+    ) -> Self {
+        let arity = encoding
+            .desc(&SourceEnvelope::None)
+            .expect("key encoding validated in planning")
+            .arity();
+        let format = match encoding {
+            DataEncoding::Bytes => KeyFormat::Bytes,
+            DataEncoding::Text => KeyFormat::Text,
+            DataEncoding::Csv(enc) => KeyFormat::Csv {
+                n_cols: enc.n_cols,
+                delimiter: enc.delimiter,
+            },
+            DataEncoding::Avro(enc) => KeyFormat::Avro(
+                avro::AvroDecoderState::new(
+                    &enc.value_schema,
+                    enc.schema_registry_config,
+                    EnvelopeType::None,
+                    false,
+                    format!("{}-keys", debug_name),
+                    worker_index,
+                    None,
+                    None,
+                )
+                .expect("Failed to create Avro decoder"),
+            ),
+            _ => unreachable!("Internal error: unsupported key encoding"),
+        };
+        KeyDecoder {
+            format,
+            arity,
+            row_packer: RowPacker::new(),
+            error_logger,
+        }
+    }
+
+    /// Decodes `key` into a row with one datum per key column. Messages
+    /// without a key produce a row of nulls, as do keys that cannot be
+    /// decoded, which are reported with the `KEY_DECODE` error code. An empty
+    /// key that is present is decoded like any other.
+    fn decode(&mut self, key: &[u8], key_present: bool) -> Row {
+        if key_present {
+            let decoded = match &mut self.format {
+                KeyFormat::Bytes => Ok(self.row_packer.pack(&[bytes_to_datum(key)])),
+                KeyFormat::Text => Ok(self.row_packer.pack(&[text_to_datum(key)])),
+                KeyFormat::Csv { n_cols, delimiter } => {
+                    csv::decode_record(key, *n_cols, *delimiter)
+                }
+                KeyFormat::Avro(decoder) => decoder.decode_key(key),
+            };
+            match decoded {
+                Ok(row) => return row,
+                Err(e) => self.error_logger.log(DecodeError {
+                    code: SourceErrorCode::KeyDecode,
+                    text: format!("failed to decode message key: {}", e),
+                }),
+            }
+        }
+        self.row_packer
+            .pack(iter::repeat(Datum::Null).take(self.arity))
+    }
+
+    fn log_error_count(&mut self) {
+        if let KeyFormat::Avro(decoder) = &mut self.format {
+            decoder.log_error_count();
+        }
+    }
+}
+
 /// Inner method for decoding an upsert source
 /// Mostly, this inner method exists that way static dispatching
 /// can be used for different combinations of key-value decoders
@@ -257,6 +474,9 @@ where
                                         &data.value,
                                         data.position,
                                         data.upstream_time_millis,
+                                        data.topic.as_deref(),
+                                        data.headers.as_deref(),
+                                        data.timestamp_type.as_ref().map(|t| t.as_deref()),
                                         &mut session,
                                         *time,
                                     );
@@ -281,6 +501,7 @@ pub(crate) fn decode_upsert<G>(
     key_encoding: DataEncoding,
     debug_name: &str,
     worker_index: usize,
+    error_logger: DecodeErrorLogger,
 ) -> Stream<G, (Row, Option<Row>, Timestamp)>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -305,7 +526,8 @@ where
                 None,
                 None,
             )
-            .expect(avro_err),
+            .expect(avro_err)
+            .with_error_logger(error_logger),
             &op_name,
         ),
         (DataEncoding::Text, DataEncoding::Avro(val_enc)) => decode_upsert_inner(
@@ -321,7 +543,8 @@ where
                 None,
                 None,
             )
-            .expect(avro_err),
+            .expect(avro_err)
+            .with_error_logger(error_logger),
             &op_name,
         ),
         (DataEncoding::Avro(key_enc), DataEncoding::Avro(val_enc)) => decode_upsert_inner(
@@ -347,7 +570,8 @@ where
                 None,
                 None,
             )
-            .expect(avro_err),
+            .expect(avro_err)
+            .with_error_logger(error_logger),
             &op_name,
         ),
         (DataEncoding::Text, DataEncoding::Bytes) => decode_upsert_inner(
@@ -381,6 +605,7 @@ where
 fn decode_values_inner<G, V, C>(
     stream: &Stream<G, SourceOutput<Vec<u8>, Vec<u8>>>,
     mut value_decoder_state: V,
+    mut key_decoder: Option<KeyDecoder>,
     op_name: &str,
     contract: C,
 ) -> Stream<G, (Row, Timestamp, Diff)>
@@ -394,17 +619,26 @@ where
             input.for_each(|cap, data| {
                 let mut session = output.session(&cap);
                 for SourceOutput {
-                    key: _,
+                    key,
+                    key_present,
                     value: payload,
                     position: aux_num,
                     upstream_time_millis,
+                    topic,
+                    headers,
+                    timestamp_type,
                 } in data.iter()
                 {
                     if !payload.is_empty() {
+                        let key_columns = key_decoder.as_mut().map(|d| d.decode(key, *key_present));
                         value_decoder_state.give_value(
                             payload,
                             *aux_num,
                             *upstream_time_millis,
+                            topic.as_deref(),
+                            headers.as_deref(),
+                            timestamp_type.as_ref().map(|t| t.as_deref()),
+                            key_columns.as_ref(),
                             &mut session,
                             *cap.time(),
                         );
@@ -412,6 +646,9 @@ where
                 }
             });
             value_decoder_state.log_error_count();
+            if let Some(key_decoder) = &mut key_decoder {
+                key_decoder.log_error_count();
+            }
         }
     })
 }
@@ -421,7 +658,7 @@ fn decode_cdcv2<G: Scope<Timestamp = Timestamp>>(
     schema: &str,
     registry: Option<ccsr::ClientConfig>,
 ) -> (Stream<G, (Row, Timestamp, Diff)>, Option<Box<dyn Any>>) {
-    let mut resolver = ConfluentAvroResolver::new(schema, registry).unwrap(); // We will have already checked validity of the schema by now, so this can't fail.
+    let mut resolver = ConfluentAvroResolver::new(schema, registry, false).unwrap(); // We will have already checked validity of the schema by now, so this can't fail.
     let channel = Rc::new(RefCell::new(VecDeque::new()));
     let activator: Rc<RefCell<Option<SyncActivator>>> = Rc::new(RefCell::new(None));
     let mut vector = Vec::new();
@@ -481,6 +718,7 @@ fn decode_cdcv2<G: Scope<Timestamp = Timestamp>>(
 /// Returns the corresponding stream of Row/Timestamp/Diff tuples,
 /// and, optionally, a token that can be dropped to stop the decoding operator
 /// (if it isn't automatically stopped by the upstream operator stopping)
+#[allow(clippy::too_many_arguments)]
 pub fn decode_values<G>(
     stream: &Stream<G, SourceOutput<Vec<u8>, Vec<u8>>>,
     encoding: DataEncoding,
@@ -491,12 +729,18 @@ pub fn decode_values<G>(
     // `None`.
     operators: &mut Option<LinearOperator>,
     fast_forwarded: bool,
+    // The encoding of the message keys, if they are to be decoded into
+    // trailing key columns.
+    key_encoding: Option<DataEncoding>,
+    error_logger: DecodeErrorLogger,
 ) -> (Stream<G, (Row, Timestamp, Diff)>, Option<Box<dyn Any>>)
 where
     G: Scope<Timestamp = Timestamp>,
 {
     let op_name = format!("{}Decode", encoding.op_name());
     let worker_index = stream.scope().index();
+    let key_decoder = key_encoding
+        .map(|enc| KeyDecoder::new(enc, debug_name, worker_index, error_logger.clone()));
     let key_indices = encoding
         .desc(envelope)
         .ok()
@@ -506,7 +750,16 @@ where
             unreachable!("Internal error: Upsert is not supported yet on non-Kafka sources.")
         }
         (DataEncoding::Csv(enc), SourceEnvelope::None) => (
-            csv(stream, enc.header_row, enc.n_cols, enc.delimiter, operators),
+            csv(
+                stream,
+                enc.header_row,
+                enc.n_cols,
+                enc.delimiter,
+                enc.column_types,
+                operators,
+                key_decoder,
+                error_logger,
+            ),
             None,
         ),
         (DataEncoding::Avro(enc), SourceEnvelope::CdcV2) => {
@@ -536,7 +789,9 @@ where
                         Some(dedup_strat),
                         key_indices,
                     )
-                    .expect("Failed to create Avro decoder"),
+                    .expect("Failed to create Avro decoder")
+                    .with_error_logger(error_logger),
+                    key_decoder,
                     &op_name,
                     SourceOutput::<Vec<u8>, Vec<u8>>::key_contract(),
                 ),
@@ -556,7 +811,9 @@ where
                     None,
                     key_indices,
                 )
-                .expect("Failed to create Avro decoder"),
+                .expect("Failed to create Avro decoder")
+                .with_error_logger(error_logger),
+                key_decoder,
                 &op_name,
                 SourceOutput::<Vec<u8>, Vec<u8>>::position_value_contract(),
             ),
@@ -565,16 +822,20 @@ where
         (DataEncoding::AvroOcf { .. }, _) => {
             unreachable!("Internal error: Cannot decode Avro OCF separately from reading")
         }
+        (DataEncoding::JsonSchema(_), _) | (DataEncoding::Json(_), _) => {
+            unreachable!("Internal error: JSON values must be decoded with their errors")
+        }
         (_, SourceEnvelope::Debezium(_)) => unreachable!(
             "Internal error: A non-Avro Debezium-envelope source should not have been created."
         ),
         (DataEncoding::Regex(RegexEncoding { regex }), SourceEnvelope::None) => {
-            (regex_fn(stream, regex, debug_name), None)
+            (regex_fn(stream, regex, debug_name, key_decoder), None)
         }
         (DataEncoding::Protobuf(enc), SourceEnvelope::None) => (
             decode_values_inner(
                 stream,
                 protobuf::ProtobufDecoderState::new(&enc.descriptors, &enc.message_name),
+                key_decoder,
                 &op_name,
                 SourceOutput::<Vec<u8>, Vec<u8>>::position_value_contract(),
             ),
@@ -584,6 +845,7 @@ where
             decode_values_inner(
                 stream,
                 OffsetDecoderState::from(bytes_to_datum),
+                key_decoder,
                 &op_name,
                 SourceOutput::<Vec<u8>, Vec<u8>>::position_value_contract(),
             ),
@@ -593,6 +855,7 @@ where
             decode_values_inner(
                 stream,
                 OffsetDecoderState::from(text_to_datum),
+                key_decoder,
                 &op_name,
                 SourceOutput::<Vec<u8>, Vec<u8>>::position_value_contract(),
             ),
@@ -10,15 +10,16 @@
 use log::error;
 
 use interchange::protobuf::{self, Decoder};
-use repr::{Diff, Row, Timestamp};
+use repr::{Diff, Row, RowPacker, Timestamp};
 
-use super::{DecoderState, PushSession};
+use super::{with_key_columns, DecoderState, PushSession};
 use crate::metrics::EVENTS_COUNTER;
 
 pub struct ProtobufDecoderState {
     decoder: Decoder,
     events_success: i64,
     events_error: i64,
+    row_packer: RowPacker,
 }
 
 impl ProtobufDecoderState {
@@ -29,6 +30,7 @@ impl ProtobufDecoderState {
             decoder: Decoder::new(descriptors, message_name),
             events_success: 0,
             events_error: 0,
+            row_packer: RowPacker::new(),
         }
     }
 }
@@ -59,6 +61,9 @@ impl DecoderState for ProtobufDecoderState {
         bytes: &[u8],
         _: Option<i64>,
         _upstream_time_millis: Option<i64>,
+        _: Option<&str>,
+        _: Option<&[(String, Vec<u8>)]>,
+        _: Option<Option<&str>>,
         session: &mut PushSession<'a, (Row, Option<Row>, Timestamp)>,
         time: Timestamp,
     ) {
@@ -80,6 +85,10 @@ impl DecoderState for ProtobufDecoderState {
         bytes: &[u8],
         _: Option<i64>,
         _: Option<i64>,
+        _: Option<&str>,
+        _: Option<&[(String, Vec<u8>)]>,
+        _: Option<Option<&str>>,
+        key_columns: Option<&Row>,
         session: &mut PushSession<'a, (Row, Timestamp, Diff)>,
         time: Timestamp,
     ) {
@@ -87,6 +96,7 @@ impl DecoderState for ProtobufDecoderState {
             Ok(row) => {
                 if let Some(row) = row {
                     self.events_success += 1;
+                    let row = with_key_columns(&mut self.row_packer, row, key_columns);
                     session.give((row, time, 1));
                 } else {
                     self.events_error += 1;
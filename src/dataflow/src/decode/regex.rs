@@ -17,12 +17,14 @@ use timely::dataflow::{Scope, Stream};
 
 use repr::{Datum, Diff, Row, Timestamp};
 
+use super::{push_headers, KeyDecoder};
 use crate::source::SourceOutput;
 
 pub fn regex<G>(
     stream: &Stream<G, SourceOutput<Vec<u8>, Vec<u8>>>,
     regex: Regex,
     name: &str,
+    mut key_decoder: Option<KeyDecoder>,
 ) -> Stream<G, (Row, Timestamp, Diff)>
 where
     G: Scope<Timestamp = Timestamp>,
@@ -35,10 +37,14 @@ where
             input.for_each(|cap, lines| {
                 let mut session = output.session(&cap);
                 for SourceOutput {
-                    key: _,
+                    key,
+                    key_present,
                     value: line,
                     position: line_no,
                     upstream_time_millis: _,
+                    topic,
+                    headers,
+                    timestamp_type,
                 } in &*lines
                 {
                     let line = match str::from_utf8(&line) {
@@ -74,7 +80,20 @@ where
                         .map(|c| Datum::from(c.map(|c| c.as_str())))
                         .chain(iter::once(Datum::from(*line_no)));
 
-                    session.give((row_packer.pack(datums), *cap.time(), 1));
+                    row_packer.extend(datums);
+                    if let Some(topic) = topic {
+                        row_packer.push(Datum::String(topic));
+                    }
+                    if let Some(headers) = headers {
+                        push_headers(&mut row_packer, headers);
+                    }
+                    if let Some(timestamp_type) = timestamp_type {
+                        row_packer.push(Datum::from(timestamp_type.as_deref()));
+                    }
+                    if let Some(key_decoder) = &mut key_decoder {
+                        row_packer.extend_by_row(&key_decoder.decode(key, *key_present));
+                    }
+                    session.give((row_packer.finish_and_reuse(), *cap.time(), 1));
                 }
             });
         }
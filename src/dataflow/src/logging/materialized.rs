@@ -20,6 +20,7 @@ use timely::logging::WorkerIdentifier;
 
 use super::{LogVariant, MaterializedLog};
 use crate::arrangement::KeysValsHandle;
+use dataflow_types::SourceErrorCode;
 use expr::{GlobalId, SourceInstanceId};
 use repr::{Datum, Timestamp};
 
@@ -29,8 +30,9 @@ pub type Logger = timely::logging_core::Logger<MaterializedEvent, WorkerIdentifi
 /// A logged materialized event.
 #[derive(Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum MaterializedEvent {
-    /// Dataflow command, true for create and false for drop.
-    Dataflow(GlobalId, bool),
+    /// Dataflow command for an index and the index of the Timely dataflow that
+    /// maintains it, true for create and false for drop.
+    Dataflow(GlobalId, usize, bool),
     /// Dataflow depends on a named source of data.
     DataflowDependency {
         /// Globally unique identifier for the dataflow.
@@ -52,6 +54,18 @@ pub enum MaterializedEvent {
         offset: i64,
         /// Difference between the previous timestamp and current highest timestamp we've seen
         timestamp: i64,
+        /// Difference between the previous and current number of bytes read from the upstream
+        /// object, for sources that measure it
+        bytes: i64,
+    },
+    /// A source produced an error.
+    SourceError {
+        /// Name of the source
+        source_name: String,
+        /// Source identifier
+        source_id: SourceInstanceId,
+        /// The class of the error
+        code: SourceErrorCode,
     },
     /// Available frontier information for views.
     Frontier(GlobalId, Timestamp, i64),
@@ -105,6 +119,7 @@ pub fn construct<A: Allocate>(
         let (mut dependency_out, dependency) = demux.new_output();
         let (mut peek_out, peek) = demux.new_output();
         let (mut source_info_out, source_info) = demux.new_output();
+        let (mut source_error_out, source_error) = demux.new_output();
         let (mut frontier_out, frontier) = demux.new_output();
 
         let mut demux_buffer = Vec::new();
@@ -116,6 +131,7 @@ pub fn construct<A: Allocate>(
                 let mut dependency = dependency_out.activate();
                 let mut peek = peek_out.activate();
                 let mut source_info = source_info_out.activate();
+                let mut source_error = source_error_out.activate();
                 let mut frontier = frontier_out.activate();
 
                 input.for_each(|time, data| {
@@ -125,6 +141,7 @@ pub fn construct<A: Allocate>(
                     let mut dependency_session = dependency.session(&time);
                     let mut peek_session = peek.session(&time);
                     let mut source_info_session = source_info.session(&time);
+                    let mut source_error_session = source_error.session(&time);
                     let mut frontier_session = frontier.session(&time);
 
                     for (time, worker, datum) in demux_buffer.drain(..) {
@@ -134,8 +151,14 @@ pub fn construct<A: Allocate>(
                         let time_ms = time_ms as Timestamp;
 
                         match datum {
-                            MaterializedEvent::Dataflow(id, is_create) => {
-                                dataflow_session.give((id, worker, is_create, time_ns));
+                            MaterializedEvent::Dataflow(id, dataflow_index, is_create) => {
+                                dataflow_session.give((
+                                    id,
+                                    worker,
+                                    dataflow_index,
+                                    is_create,
+                                    time_ns,
+                                ));
 
                                 // For now we know that these always happen in
                                 // the correct order, but it may be necessary
@@ -185,11 +208,23 @@ pub fn construct<A: Allocate>(
                                 partition_id,
                                 offset,
                                 timestamp,
+                                bytes,
                             } => {
                                 source_info_session.give((
                                     (source_name, source_id, partition_id),
                                     time_ms,
-                                    DiffPair::new(offset, timestamp),
+                                    DiffPair::new(offset, DiffPair::new(timestamp, bytes)),
+                                ));
+                            }
+                            MaterializedEvent::SourceError {
+                                source_name,
+                                source_id,
+                                code,
+                            } => {
+                                source_error_session.give((
+                                    (source_name, source_id, code),
+                                    time_ms,
+                                    1isize,
                                 ));
                             }
                             MaterializedEvent::Frontier(name, logical, delta) => {
@@ -210,18 +245,20 @@ pub fn construct<A: Allocate>(
         });
 
         let dataflow_current = dataflow
-            .map(move |(name, worker, is_create, time_ns)| {
+            .map(move |(name, worker, dataflow_index, is_create, time_ns)| {
                 let time_ms = (time_ns / 1_000_000) as Timestamp;
                 let time_ms = ((time_ms / granularity_ms) + 1) * granularity_ms;
-                ((name, worker), time_ms, if is_create { 1 } else { -1 })
+                let diff = if is_create { 1 } else { -1 };
+                ((name, worker, dataflow_index), time_ms, diff)
             })
             .as_collection()
             .map({
                 let mut row_packer = repr::RowPacker::new();
-                move |(name, worker)| {
+                move |(name, worker, dataflow_index)| {
                     row_packer.pack(&[
                         Datum::String(&name.to_string()),
                         Datum::Int64(worker as i64),
+                        Datum::Int64(dataflow_index as i64),
                     ])
                 }
             });
@@ -270,7 +307,8 @@ pub fn construct<A: Allocate>(
             let mut row_packer = repr::RowPacker::new();
             move |((name, id, pid), pair)| {
                 let offset = pair.element1;
-                let timestamp = pair.element2;
+                let timestamp = pair.element2.element1;
+                let bytes = pair.element2.element2;
                 row_packer.pack(&[
                     Datum::String(&name),
                     Datum::String(&id.source_id.to_string()),
@@ -278,6 +316,20 @@ pub fn construct<A: Allocate>(
                     Datum::String(&pid),
                     Datum::Int64(offset),
                     Datum::Int64(timestamp),
+                    Datum::Int64(bytes),
+                ])
+            }
+        });
+
+        let source_error_current = source_error.as_collection().count().map({
+            let mut row_packer = repr::RowPacker::new();
+            move |((name, id, code), count)| {
+                row_packer.pack(&[
+                    Datum::String(&name),
+                    Datum::String(&id.source_id.to_string()),
+                    Datum::Int64(id.dataflow_id as i64),
+                    Datum::String(code.as_str()),
+                    Datum::Int64(count as i64),
                 ])
             }
         });
@@ -373,6 +425,10 @@ pub fn construct<A: Allocate>(
                 LogVariant::Materialized(MaterializedLog::SourceInfo),
                 source_info_current,
             ),
+            (
+                LogVariant::Materialized(MaterializedLog::SourceErrors),
+                source_error_current,
+            ),
         ];
 
         use differential_dataflow::operators::arrange::arrangement::ArrangeByKey;
@@ -7,17 +7,21 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::convert::TryFrom;
+
 use differential_dataflow::lattice::Lattice;
 use differential_dataflow::operators::arrange::arrangement::Arrange;
 use differential_dataflow::trace::implementations::ord::{OrdKeySpine, OrdValSpine};
+use differential_dataflow::{AsCollection, Collection, Hashable};
+use timely::dataflow::channels::pact::Exchange;
 use timely::dataflow::Scope;
 use timely::progress::{timestamp::Refines, Timestamp};
 
 use dataflow_types::*;
-use expr::MirRelationExpr;
-use repr::{Row, RowArena};
+use expr::{GlobalId, MirRelationExpr};
+use repr::{Diff, Row, RowArena};
 
-use crate::operator::CollectionExt;
+use crate::operator::{CollectionExt, StreamExt};
 use crate::render::context::{ArrangementFlavor, Context};
 
 impl<G, T> Context<G, MirRelationExpr, Row, T>
@@ -26,7 +30,17 @@ where
     G::Timestamp: Lattice + Refines<T>,
     T: Timestamp + Lattice,
 {
-    pub fn render_arrangeby(&mut self, relation_expr: &MirRelationExpr, id: Option<&str>) {
+    /// Renders the arrangements described by an `ArrangeBy`.
+    ///
+    /// If `memory_limit` names an index and a number of bytes, rows are only
+    /// admitted into the arrangement until they would occupy more than that
+    /// many bytes, at which point the index reports an error instead.
+    pub fn render_arrangeby(
+        &mut self,
+        relation_expr: &MirRelationExpr,
+        id: Option<&str>,
+        memory_limit: Option<(GlobalId, usize)>,
+    ) {
         if let MirRelationExpr::ArrangeBy { input, keys } = relation_expr {
             if keys.is_empty() {
                 let collection = self.collection(input).unwrap();
@@ -35,6 +49,14 @@ where
             for key_set in keys {
                 if self.arrangement(&input, &key_set).is_none() {
                     let (ok_built, err_built) = self.collection(input).unwrap();
+                    let (ok_built, err_built) = match memory_limit {
+                        Some((index_id, limit)) => {
+                            let (ok_limited, err_limited) =
+                                limit_memory(&ok_built, index_id, limit);
+                            (ok_limited, err_built.concat(&err_limited))
+                        }
+                        None => (ok_built, err_built),
+                    };
                     let keys2 = key_set.clone();
                     let name = if let Some(id) = id {
                         format!("Arrange: {}", id)
@@ -69,3 +91,49 @@ where
         }
     }
 }
+
+/// Passes the rows of `collection` through until they would occupy more than
+/// `limit` bytes, then reports that the index `index_id` exceeded its memory
+/// limit and discards all further updates.
+///
+/// Rows are first distributed among workers by hash, and each worker admits
+/// an equal share of `limit`.
+fn limit_memory<G>(
+    collection: &Collection<G, Row>,
+    index_id: GlobalId,
+    limit: usize,
+) -> (Collection<G, Row>, Collection<G, DataflowError>)
+where
+    G: Scope,
+{
+    let worker_limit = isize::try_from(limit / collection.scope().peers()).unwrap_or(isize::MAX);
+    let mut storage = Vec::new();
+    let (ok_stream, err_stream) = collection.inner.unary_fallible(
+        Exchange::new(|(row, _time, _diff): &(Row, G::Timestamp, Diff)| row.hashed()),
+        "MemoryLimit",
+        move |_, _| {
+            let mut bytes: isize = 0;
+            let mut exceeded = false;
+            move |input, ok_output, err_output| {
+                input.for_each(|time, data| {
+                    if exceeded {
+                        return;
+                    }
+                    let mut ok_session = ok_output.session(&time);
+                    data.swap(&mut storage);
+                    for (row, t, diff) in storage.drain(..) {
+                        bytes += isize::try_from(row.data().len()).unwrap_or(isize::MAX) * diff;
+                        if bytes > worker_limit {
+                            let err = DataflowError::IndexMemoryLimitExceeded { index_id, limit };
+                            err_output.session(&time).give((err, t, 1));
+                            exceeded = true;
+                            break;
+                        }
+                        ok_session.give((row, t, diff));
+                    }
+                })
+            }
+        },
+    );
+    (ok_stream.as_collection(), err_stream.as_collection())
+}
@@ -0,0 +1,111 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use differential_dataflow::hashable::Hashable;
+use differential_dataflow::{AsCollection, Collection};
+use timely::dataflow::channels::pact::Exchange;
+use timely::dataflow::operators::generic::Operator;
+use timely::dataflow::operators::Map;
+use timely::dataflow::Scope;
+
+use dataflow_types::SourceDedupe;
+use repr::{Row, RowPacker, Timestamp};
+
+/// Suppresses the rows of a source whose key was already seen within a time
+/// window.
+///
+/// A row whose key was last emitted at time `t` is suppressed if it arrives
+/// before `t + window_ms`, and emitted if it arrives at or after that time:
+/// the window includes the time of the emitted row but not the time at which
+/// it closes. Suppressed rows do not extend the window. Each key is forgotten
+/// once its window closes, so the state of the operator is bounded by the
+/// number of distinct keys emitted within one window.
+///
+/// Rows are deduplicated in the order of their times, and of the rows with
+/// the same key at the same time, the least is kept, so that the result does
+/// not depend on the order in which the rows arrive. The input must contain
+/// only insertions, as sources with `ENVELOPE NONE` do.
+pub fn dedupe_by_key<G>(collection: &Collection<G, Row>, dedupe: SourceDedupe) -> Collection<G, Row>
+where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let SourceDedupe {
+        key_indices,
+        window_ms,
+    } = dedupe;
+    let mut row_packer = RowPacker::new();
+    collection
+        .inner
+        .map(move |(row, time, diff)| {
+            let key = {
+                let datums = row.unpack();
+                row_packer.pack(key_indices.iter().map(|i| datums[*i]))
+            };
+            (key, row, time, diff)
+        })
+        .unary_frontier(
+            Exchange::new(|(key, _row, _time, _diff): &(Row, Row, Timestamp, isize)| key.hashed()),
+            "SourceDedupe",
+            move |_cap, _info| {
+                // The rows at times that are not yet complete.
+                let mut pending = HashMap::<_, Vec<(Row, Row, isize)>>::new();
+                // The keys whose windows are open, and the times at which
+                // their windows close, in the order in which they close.
+                let mut open_keys = HashSet::new();
+                let mut closings = VecDeque::<(Timestamp, Row)>::new();
+                let mut vector = Vec::new();
+
+                move |input, output| {
+                    input.for_each(|cap, data| {
+                        data.swap(&mut vector);
+                        for (key, row, time, diff) in vector.drain(..) {
+                            pending
+                                .entry(cap.delayed(&time))
+                                .or_insert_with(Vec::new)
+                                .push((key, row, diff));
+                        }
+                    });
+
+                    // Deduplicate complete times in order, so that the rows
+                    // that are emitted do not depend on the order in which
+                    // the times complete.
+                    let mut complete = pending
+                        .keys()
+                        .filter(|cap| !input.frontier.less_equal(cap.time()))
+                        .cloned()
+                        .collect::<Vec<_>>();
+                    complete.sort_by_key(|cap| *cap.time());
+                    for cap in complete {
+                        let mut rows = pending.remove(&cap).expect("pending time must exist");
+                        rows.sort();
+                        let time = *cap.time();
+                        while let Some((closing, _)) = closings.front() {
+                            if *closing > time {
+                                break;
+                            }
+                            let (_, key) = closings.pop_front().unwrap();
+                            open_keys.remove(&key);
+                        }
+                        let mut session = output.session(&cap);
+                        for (key, row, diff) in rows {
+                            if diff <= 0 || open_keys.contains(&key) {
+                                continue;
+                            }
+                            closings.push_back((time.saturating_add(window_ms), key.clone()));
+                            open_keys.insert(key);
+                            session.give((row, time, 1));
+                        }
+                    }
+                }
+            },
+        )
+        .as_collection()
+}
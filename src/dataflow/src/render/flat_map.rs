@@ -70,7 +70,10 @@ where
                             Ok(exprs) => exprs,
                             Err(e) => return vec![(Err(e.into()), 1)],
                         };
-                        let output_rows = func.eval(exprs, &temp_storage);
+                        let output_rows = match func.eval(exprs, &temp_storage) {
+                            Ok(output_rows) => output_rows,
+                            Err(e) => return vec![(Err(e.into()), 1)],
+                        };
                         // Blank out entries in `datum` here, for simplicity later on.
                         for index in 0..datums_len {
                             if replace[index] {
@@ -265,7 +265,7 @@ where
                     input: Box::new(next_input.clone()),
                     keys: vec![next_keys.to_vec()],
                 };
-                self.render_arrangeby(&arrange_by, Some("MissingArrangement"));
+                self.render_arrangeby(&arrange_by, Some("MissingArrangement"), None);
             } else {
                 panic!("Arrangement alarmingly absent!");
             }
@@ -133,10 +133,12 @@ use ore::iter::IteratorExt;
 use repr::adt::decimal::Significand;
 use repr::{Datum, RelationType, Row, RowArena, RowPacker, Timestamp};
 
-use crate::decode::{decode_avro_values, decode_values};
+use crate::decode::{decode_avro_values, decode_values, json, json_schema, DecodeErrorLogger};
 use crate::operator::{CollectionExt, StreamExt};
 use crate::render::context::{ArrangementFlavor, Context};
-use crate::server::{CacheMessage, LocalInput, TimestampDataUpdates, TimestampMetadataUpdates};
+use crate::server::{
+    CacheMessage, LocalInput, SourceConfigUpdates, TimestampDataUpdates, TimestampMetadataUpdates,
+};
 use crate::sink;
 use crate::source::{self, FileSourceInfo, KafkaSourceInfo, KinesisSourceInfo, S3SourceInfo};
 use crate::source::{SourceConfig, SourceToken};
@@ -147,6 +149,7 @@ use crate::{
 
 mod arrange_by;
 mod context;
+mod dedupe;
 pub(crate) mod filter;
 mod flat_map;
 mod join;
@@ -168,6 +171,9 @@ pub struct RenderState {
     /// Communication channel for enabling/disabling timestamping on new/dropped
     /// sources.
     pub ts_source_updates: TimestampMetadataUpdates,
+    /// Connector options changed by `ALTER SOURCE`, pending application by
+    /// each source instance.
+    pub source_config_updates: SourceConfigUpdates,
     /// Tokens that should be dropped when a dataflow is dropped to clean up
     /// associated state.
     pub dataflow_tokens: HashMap<GlobalId, Box<dyn Any>>,
@@ -272,9 +278,11 @@ where
             SourceConnector::External {
                 connector,
                 encoding,
+                key_encoding,
                 envelope,
                 consistency,
                 ts_frequency,
+                dedupe,
             } => {
                 // TODO(benesch): this match arm is hard to follow. Refactor.
 
@@ -294,8 +302,10 @@ where
 
                 let fast_forwarded = match &connector {
                     ExternalSourceConnector::Kafka(KafkaSourceConnector {
-                        start_offsets, ..
-                    }) => start_offsets.values().any(|&val| val > 0),
+                        start_offsets,
+                        start_timestamp,
+                        ..
+                    }) => start_timestamp.is_some() || start_offsets.values().any(|&val| val > 0),
                     _ => false,
                 };
 
@@ -317,14 +327,17 @@ where
                     None
                 };
 
+                let source_name = format!("{}-{}", connector.name(), uid);
+                let error_logger = materialized_logging.clone();
                 let source_config = SourceConfig {
-                    name: format!("{}-{}", connector.name(), uid),
+                    name: source_name.clone(),
                     id: uid,
                     scope,
                     // Distribute read responsibility among workers.
                     active: active_read_worker,
                     timestamp_histories: render_state.ts_histories.clone(),
                     timestamp_tx: render_state.ts_source_updates.clone(),
+                    config_updates: render_state.source_config_updates.clone(),
                     consistency,
                     timestamp_frequency: ts_frequency,
                     worker_id: scope.index(),
@@ -352,6 +365,7 @@ where
                                     self.as_of_frontier.clone(),
                                     &mut src.operators,
                                     src.desc.typ(),
+                                    DecodeErrorLogger::new(error_logger, source_name, uid),
                                 );
 
                             let arranged = arrange_from_upsert(
@@ -384,7 +398,7 @@ where
                                 connector,
                             );
                         err_collection = err_collection.concat(
-                            &err_source
+                            &source::log_errors(&err_source, error_logger, source_name, uid)
                                 .map(DataflowError::SourceError)
                                 .pass_through("AvroOCF-errors")
                                 .as_collection(),
@@ -435,22 +449,53 @@ where
                             ExternalSourceConnector::AvroOcf(_) => unreachable!(),
                         };
                         err_collection = err_collection.concat(
-                            &err_source
-                                .map(DataflowError::SourceError)
-                                .pass_through("source-errors")
-                                .as_collection(),
+                            &source::log_errors(
+                                &err_source,
+                                error_logger.clone(),
+                                source_name.clone(),
+                                uid,
+                            )
+                            .map(DataflowError::SourceError)
+                            .pass_through("source-errors")
+                            .as_collection(),
                         );
 
                         // TODO(brennan) -- this should just be a MirRelationExpr::FlatMap using regexp_extract, csv_extract,
                         // a hypothetical future avro_extract, protobuf_extract, etc.
-                        let (stream, extra_token) = decode_values(
-                            &ok_source,
-                            encoding,
-                            &self.debug_name,
-                            &envelope,
-                            &mut src.operators,
-                            fast_forwarded,
-                        );
+                        let decoded_with_errors = match &encoding {
+                            DataEncoding::JsonSchema(enc) => Some(json_schema(&ok_source, enc)),
+                            DataEncoding::Json(enc) => Some(json(
+                                &ok_source,
+                                enc,
+                                &self.debug_name,
+                                error_logger.clone(),
+                                source_name.clone(),
+                                uid,
+                            )),
+                            _ => None,
+                        };
+                        let (stream, extra_token) = if let Some((stream, decode_errors)) =
+                            decoded_with_errors
+                        {
+                            err_collection = err_collection.concat(
+                                &source::log_errors(&decode_errors, error_logger, source_name, uid)
+                                    .map(DataflowError::SourceError)
+                                    .pass_through("decode-errors")
+                                    .as_collection(),
+                            );
+                            (stream, None)
+                        } else {
+                            decode_values(
+                                &ok_source,
+                                encoding,
+                                &self.debug_name,
+                                &envelope,
+                                &mut src.operators,
+                                fast_forwarded,
+                                key_encoding,
+                                DecodeErrorLogger::new(error_logger, source_name, uid),
+                            )
+                        };
                         if let Some(tok) = extra_token {
                             self.additional_tokens
                                 .entry(src_id)
@@ -478,6 +523,12 @@ where
                         SourceEnvelope::Upsert(_) => unreachable!(),
                     };
 
+                    // Deduplication must see every column of the key, so it
+                    // precedes source filtering and projection.
+                    if let Some(dedupe) = dedupe {
+                        collection = dedupe::dedupe_by_key(&collection, dedupe);
+                    }
+
                     // Implement source filtering and projection.
                     // At the moment this is strictly optional, but we perform it anyhow
                     // to demonstrate the intended use.
@@ -600,14 +651,24 @@ where
     }
 
     fn build_object(&mut self, scope: &mut Child<'g, G, G::Timestamp>, object: &BuildDesc) {
-        self.ensure_rendered(object.relation_expr.as_ref(), scope, scope.index());
         if let Some(typ) = &object.typ {
+            self.ensure_rendered(object.relation_expr.as_ref(), scope, scope.index());
             self.clone_from_to(
                 &object.relation_expr.as_ref(),
                 &MirRelationExpr::global_get(object.id, typ.clone()),
             );
         } else {
-            self.render_arrangeby(&object.relation_expr.as_ref(), Some(&object.id.to_string()));
+            // Render only the input of the arrange_by, so that the arrangement
+            // itself is built here, named for the index and within its memory
+            // limit.
+            if let MirRelationExpr::ArrangeBy { input, .. } = object.relation_expr.as_ref() {
+                self.ensure_rendered(input, scope, scope.index());
+            }
+            self.render_arrangeby(
+                &object.relation_expr.as_ref(),
+                Some(&object.id.to_string()),
+                object.memory_limit_bytes.map(|limit| (object.id, limit)),
+            );
             // Under the premise that this is always an arrange_by aroung a global get,
             // this will leave behind the arrangements bound to the global get, so that
             // we will not tidy them up in the next pass.
@@ -776,6 +837,8 @@ where
             SinkConnector::Kafka(c) => c.consistency.is_some(),
             SinkConnector::Tail(_) => false,
             SinkConnector::AvroOcf(_) => false,
+            SinkConnector::S3(_) => false,
+            SinkConnector::Notify(_) => false,
         };
         let collection = if append_timestamp {
             collection
@@ -825,6 +888,12 @@ where
             SinkConnector::AvroOcf(c) => {
                 sink::avro_ocf(collection, sink_id, c, sink.value_desc.clone());
             }
+            SinkConnector::S3(c) => {
+                sink::s3(collection, sink_id, c);
+            }
+            SinkConnector::Notify(c) => {
+                sink::notify(collection, sink_id, c);
+            }
         };
 
         let tokens = Rc::new((
@@ -1168,7 +1237,7 @@ where
                     {
                         self.ensure_rendered(input, scope, worker_index);
                     }
-                    self.render_arrangeby(relation_expr, None);
+                    self.render_arrangeby(relation_expr, None, None);
                 }
             };
         }
@@ -21,7 +21,7 @@ use timely::progress::Antichain;
 use dataflow_types::{DataEncoding, DataflowError, LinearOperator};
 use repr::{RelationType, Row, Timestamp};
 
-use crate::decode::decode_upsert;
+use crate::decode::{decode_upsert, DecodeErrorLogger};
 use crate::source::{SourceData, SourceOutput};
 
 /// Entrypoint to the upsert-specific transformations involved
@@ -40,6 +40,7 @@ pub fn pre_arrange_from_upsert_transforms<G>(
     as_of_frontier: Antichain<Timestamp>,
     linear_operator: &mut Option<LinearOperator>,
     src_type: &RelationType,
+    error_logger: DecodeErrorLogger,
 ) -> (
     Stream<G, (Row, Option<Row>, Timestamp)>,
     Stream<G, DataflowError>,
@@ -96,6 +97,7 @@ where
         key_encoding,
         debug_name,
         worker_index,
+        error_logger,
     );
 
     apply_linear_operators(&decoded, linear_operator, src_type)
@@ -129,9 +131,13 @@ where
                     for (
                         SourceOutput {
                             key,
+                            key_present: _,
                             value: new_value,
                             position: new_position,
                             upstream_time_millis: new_upstream_time_millis,
+                            topic: new_topic,
+                            headers: new_headers,
+                            timestamp_type: new_timestamp_type,
                         },
                         time,
                     ) in vector.drain(..)
@@ -149,6 +155,9 @@ where
                                         value: new_value,
                                         position: new_position,
                                         upstream_time_millis: new_upstream_time_millis,
+                                        topic: new_topic,
+                                        headers: new_headers,
+                                        timestamp_type: new_timestamp_type,
                                     };
                                 }
                             } else {
@@ -156,6 +165,9 @@ where
                                     value: new_value,
                                     position: new_position,
                                     upstream_time_millis: new_upstream_time_millis,
+                                    topic: new_topic,
+                                    headers: new_headers,
+                                    timestamp_type: new_timestamp_type,
                                 };
                             }
                         }
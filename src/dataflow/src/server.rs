@@ -10,6 +10,7 @@
 //! An interactive dataflow server.
 
 use std::cell::RefCell;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::collections::VecDeque;
 use std::rc::Rc;
@@ -75,6 +76,9 @@ pub enum SequencedCommand {
         ///
         /// Used in responses and cancelation requests.
         conn_id: u32,
+        /// The ID that the coordinator assigned this peek in
+        /// `mz_internal.mz_active_peeks`.
+        query_id: u64,
         /// A communication link for sending a response.
         tx: mpsc::UnboundedSender<PeekResponse>,
         /// The logical timestamp at which the arrangement is queried.
@@ -88,6 +92,10 @@ pub enum SequencedCommand {
     CancelPeek {
         /// The identifier of the peek request to cancel.
         conn_id: u32,
+        /// If present, the peek is canceled only if it was assigned this
+        /// query ID, so that a cancelation aimed at a peek that has since
+        /// finished cannot cancel a later peek of the same connection.
+        query_id: Option<u64>,
     },
     /// Insert `updates` into the local input named `id`.
     Insert {
@@ -109,6 +117,13 @@ pub enum SequencedCommand {
         /// The associated update (RT or BYO)
         update: TimestampSourceUpdate,
     },
+    /// Apply new connector options to the running instances of a source.
+    UpdateSourceOptions {
+        /// The ID of the source.
+        id: GlobalId,
+        /// The complete set of connector options the source now has.
+        config_options: BTreeMap<String, String>,
+    },
     /// Advance all local inputs to the given timestamp.
     AdvanceAllLocalInputs {
         /// The timestamp to advance to.
@@ -202,6 +217,7 @@ pub fn serve(config: Config) -> Result<WorkerGuards<()>, String> {
                     ts_source_mapping: HashMap::new(),
                     ts_histories: Default::default(),
                     ts_source_updates: Default::default(),
+                    source_config_updates: Default::default(),
                     dataflow_tokens: HashMap::new(),
                     caching_tx: None,
                 },
@@ -210,6 +226,7 @@ pub fn serve(config: Config) -> Result<WorkerGuards<()>, String> {
                 pending_peeks: Vec::new(),
                 feedback_tx: None,
                 reported_frontiers: HashMap::new(),
+                index_dataflows: HashMap::new(),
                 metrics: Metrics::for_worker_id(worker_idx),
             }
             .run()
@@ -239,6 +256,10 @@ pub type TimestampDataUpdates = Rc<RefCell<HashMap<SourceInstanceId, TimestampDa
 /// StopTimestamping request once the operator for the source is dropped.
 pub type TimestampMetadataUpdates = Rc<RefCell<Vec<TimestampMetadataUpdate>>>;
 
+/// Map of source ID to the connector options the source should switch to the
+/// next time it is scheduled.
+pub type SourceConfigUpdates = Rc<RefCell<HashMap<SourceInstanceId, BTreeMap<String, String>>>>;
+
 /// Possible timestamping metadata information messages that get sent from workers to coordinator
 pub enum TimestampMetadataUpdate {
     /// Requests to start timestamping a source with given id
@@ -269,6 +290,8 @@ where
     feedback_tx: Option<mpsc::UnboundedSender<WorkerFeedbackWithMeta>>,
     /// Tracks the frontier information that has been sent over `feedback_tx`.
     reported_frontiers: HashMap<GlobalId, Antichain<Timestamp>>,
+    /// The index of the Timely dataflow that maintains each index.
+    index_dataflows: HashMap<GlobalId, usize>,
     /// Metrics bundle.
     metrics: Metrics,
 }
@@ -466,6 +489,10 @@ where
                     // A source was deleted
                     self.render_state.ts_histories.borrow_mut().remove(id);
                     self.render_state.ts_source_mapping.remove(id);
+                    self.render_state
+                        .source_config_updates
+                        .borrow_mut()
+                        .remove(id);
                     let tx = self.feedback_tx.as_mut().unwrap();
                     tx.send(WorkerFeedbackWithMeta {
                         worker_id: self.timely_worker.index(),
@@ -537,11 +564,15 @@ where
         match cmd {
             SequencedCommand::CreateDataflows(dataflows) => {
                 for dataflow in dataflows.into_iter() {
+                    // The dataflow is about to be built, so it will receive
+                    // the next index.
+                    let dataflow_index = self.timely_worker.next_dataflow_index();
                     for (idx_id, idx, _) in dataflow.index_exports.iter() {
                         self.reported_frontiers
                             .insert(*idx_id, Antichain::from_elem(0));
+                        self.index_dataflows.insert(*idx_id, dataflow_index);
                         if let Some(logger) = self.materialized_logger.as_mut() {
-                            logger.log(MaterializedEvent::Dataflow(*idx_id, true));
+                            logger.log(MaterializedEvent::Dataflow(*idx_id, dataflow_index, true));
                             logger.log(MaterializedEvent::Frontier(*idx_id, 0, 1));
                             for import_id in dataflow.get_imports(&idx.on_id) {
                                 logger.log(MaterializedEvent::DataflowDependency {
@@ -573,8 +604,11 @@ where
                         .reported_frontiers
                         .remove(&id)
                         .expect("Dropped index with no frontier");
+                    let dataflow_index = self.index_dataflows.remove(&id);
                     if let Some(logger) = self.materialized_logger.as_mut() {
-                        logger.log(MaterializedEvent::Dataflow(id, false));
+                        if let Some(dataflow_index) = dataflow_index {
+                            logger.log(MaterializedEvent::Dataflow(id, dataflow_index, false));
+                        }
                         for time in frontier.elements().iter() {
                             logger.log(MaterializedEvent::Frontier(id, *time, -1));
                         }
@@ -587,6 +621,7 @@ where
                 key,
                 timestamp,
                 conn_id,
+                query_id,
                 tx,
                 finishing,
                 map_filter_project,
@@ -612,6 +647,7 @@ where
                     id,
                     key,
                     conn_id,
+                    query_id,
                     tx,
                     timestamp,
                     finishing,
@@ -635,10 +671,10 @@ where
                 self.metrics.observe_pending_peeks(&self.pending_peeks);
             }
 
-            SequencedCommand::CancelPeek { conn_id } => {
+            SequencedCommand::CancelPeek { conn_id, query_id } => {
                 let logger = &mut self.materialized_logger;
                 self.pending_peeks.retain(|peek| {
-                    if peek.conn_id == conn_id {
+                    if peek.conn_id == conn_id && query_id.map_or(true, |id| id == peek.query_id) {
                         peek.tx
                             .send(PeekResponse::Canceled)
                             .expect("peek receiver should not drop first");
@@ -696,6 +732,20 @@ where
                 self.render_state.traces.del_all_traces();
                 self.shutdown_logging();
             }
+            SequencedCommand::UpdateSourceOptions { id, config_options } => {
+                let mut config_updates = self.render_state.source_config_updates.borrow_mut();
+                for (instance_id, source) in &self.render_state.ts_source_mapping {
+                    if instance_id.source_id != id {
+                        continue;
+                    }
+                    config_updates.insert(*instance_id, config_options.clone());
+                    if let Some(source) = source.upgrade() {
+                        if let Some(token) = &*source {
+                            token.activate();
+                        }
+                    }
+                }
+            }
             SequencedCommand::AdvanceSourceTimestamp { id, update } => {
                 let mut timestamps = self.render_state.ts_histories.borrow_mut();
                 if let Some(ts_entries) = timestamps.get_mut(&id) {
@@ -797,6 +847,8 @@ struct PendingPeek {
     key: Option<Row>,
     /// The ID of the connection that submitted the peek. For logging only.
     conn_id: u32,
+    /// The ID that the coordinator assigned the peek.
+    query_id: u64,
     /// A transmitter connected to the intended recipient of the peek.
     tx: mpsc::UnboundedSender<PeekResponse>,
     /// Time at which the collection should be materialized.
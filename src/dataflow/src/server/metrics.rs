@@ -107,6 +107,8 @@ struct CommandsProcessedMetrics {
     append_log: IntCounter,
     advance_source_timestamp_int: i32,
     advance_source_timestamp: IntCounter,
+    update_source_options_int: i32,
+    update_source_options: IntCounter,
     enable_feedback_int: i32,
     enable_feedback: IntCounter,
     enable_logging_int: i32,
@@ -148,6 +150,9 @@ impl CommandsProcessedMetrics {
             advance_source_timestamp_int: 0,
             advance_source_timestamp: COMMANDS_PROCESSED_RAW
                 .with_label_values(&[worker, "advance_source_timestamp"]),
+            update_source_options_int: 0,
+            update_source_options: COMMANDS_PROCESSED_RAW
+                .with_label_values(&[worker, "update_source_options"]),
             enable_feedback_int: 0,
             enable_feedback: COMMANDS_PROCESSED_RAW.with_label_values(&[worker, "enable_feedback"]),
             enable_logging_int: 0,
@@ -175,6 +180,7 @@ impl CommandsProcessedMetrics {
             SequencedCommand::AdvanceSourceTimestamp { .. } => {
                 self.advance_source_timestamp_int += 1
             }
+            SequencedCommand::UpdateSourceOptions { .. } => self.update_source_options_int += 1,
             SequencedCommand::EnableFeedback(..) => self.enable_feedback_int += 1,
             SequencedCommand::EnableCaching(..) => self.enable_caching_int += 1,
             SequencedCommand::EnableLogging(_) => self.enable_logging_int += 1,
@@ -225,6 +231,11 @@ impl CommandsProcessedMetrics {
                 .inc_by(self.advance_source_timestamp_int as i64);
             self.advance_source_timestamp_int = 0;
         }
+        if self.update_source_options_int > 0 {
+            self.update_source_options
+                .inc_by(self.update_source_options_int as i64);
+            self.update_source_options_int = 0;
+        }
         if self.enable_feedback_int > 0 {
             self.enable_feedback.inc_by(self.enable_feedback_int as i64);
             self.enable_feedback_int = 0;
@@ -31,10 +31,10 @@ use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
 use timely::dataflow::operators::generic::FrontieredInputHandle;
 use timely::dataflow::Scope;
 
-use dataflow_types::KafkaSinkConnector;
-use expr::GlobalId;
+use dataflow_types::{CompressionCodec, KafkaSinkConnector};
+use expr::{GlobalId, MirScalarExpr};
 use interchange::avro::{self, Encoder};
-use repr::{Diff, RelationDesc, Row, Timestamp};
+use repr::{Diff, RelationDesc, Row, RowArena, ScalarType, Timestamp};
 
 /// Per-Kafka sink metrics.
 #[derive(Clone)]
@@ -227,9 +227,49 @@ enum SendState {
 struct EncodedRow {
     key: Option<Vec<u8>>,
     value: Option<Vec<u8>>,
+    /// The partition to write to, if not chosen by the producer from the key.
+    partition: Option<i32>,
     count: usize,
 }
 
+/// Selects the partition of a message by evaluating `partition_by`, of type
+/// `ty`, over the sink input row described by the Debezium-formatted `value`:
+/// its `after` record, or its `before` record for deletions.
+///
+/// The partition is the CRC-32 checksum of the result's text representation,
+/// modulo the number of partitions, so that it does not depend on the
+/// internal representation of the result. Messages for which the expression
+/// evaluates to null, or fails to evaluate, are written to partition 0.
+fn select_partition(
+    partition_by: &MirScalarExpr,
+    ty: &ScalarType,
+    partition_count: i32,
+    value: &Row,
+) -> i32 {
+    let mut fields = value.iter();
+    let before = fields.next().expect("debezium value has a before field");
+    let after = fields.next().expect("debezium value has an after field");
+    let record = if after.is_null() { before } else { after };
+    if record.is_null() {
+        return 0;
+    }
+    let datums: Vec<_> = record.unwrap_list().iter().collect();
+    let arena = RowArena::new();
+    let result = match partition_by.eval(&datums, &arena) {
+        Ok(datum) => pgrepr::Value::from_datum(datum, ty),
+        Err(_) => None,
+    };
+    match result {
+        Some(result) => {
+            let mut buf = Vec::new();
+            result.encode_text(&mut buf);
+            // The planner ensures that the partition count is positive.
+            (crc32fast::hash(&buf) % partition_count as u32) as i32
+        }
+        None => 0,
+    }
+}
+
 // TODO@jldlaughlin: What guarantees does this sink support? #1728
 pub fn kafka<G>(
     collection: Collection<G, (Option<Row>, Option<Row>)>,
@@ -264,6 +304,19 @@ where
     // if it makes a big difference
     config.set("queue.buffering.max.ms", &format!("{}", 10));
 
+    // Compression applies only to the producer, so it is not one of the
+    // config options that the sink shares with its admin client.
+    config.set(
+        "compression.codec",
+        match connector.compression_codec {
+            CompressionCodec::None => "none",
+            CompressionCodec::Gzip => "gzip",
+            CompressionCodec::Snappy => "snappy",
+            CompressionCodec::Lz4 => "lz4",
+            CompressionCodec::Zstd => "zstd",
+        },
+    );
+
     for (k, v) in connector.config_options.iter() {
         config.set(k, v);
     }
@@ -343,6 +396,15 @@ where
                 };
                 let diff = diff as usize;
 
+                let partition = match (&connector.partition_by, &value) {
+                    (Some((partition_by, ty)), Some(value)) => Some(select_partition(
+                        partition_by,
+                        ty,
+                        connector.partition_count,
+                        value,
+                    )),
+                    _ => None,
+                };
                 let key = key.map(|key| {
                     s.encoder
                         .encode_key_unchecked(connector.key_schema_id.unwrap(), key)
@@ -356,6 +418,7 @@ where
                 rows.push(EncodedRow {
                     key,
                     value,
+                    partition,
                     count: diff,
                 });
                 s.metrics.rows_queued.inc();
@@ -441,6 +504,10 @@ where
                         } else {
                             record
                         };
+                        let record = match encoded_row.partition {
+                            Some(partition) => record.partition(partition),
+                            None => record,
+                        };
                         if let Err(retry) = s.send(record) {
                             return retry;
                         }
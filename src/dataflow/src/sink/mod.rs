@@ -9,8 +9,12 @@
 
 mod avro_ocf;
 mod kafka;
+mod notify;
+mod s3;
 mod tail;
 
 pub use avro_ocf::avro_ocf;
 pub use kafka::kafka;
+pub use notify::notify;
+pub use s3::s3;
 pub use tail::tail;
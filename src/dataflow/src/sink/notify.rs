@@ -0,0 +1,89 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::mem;
+
+use differential_dataflow::Collection;
+use log::error;
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::generic::Operator;
+use timely::dataflow::Scope;
+
+use dataflow_types::{NotifySinkConnector, MAX_NOTIFY_PAYLOAD_BYTES};
+use expr::GlobalId;
+use repr::{Datum, Row, Timestamp};
+
+/// Sends a notification on the connector's channel for each row that appears
+/// in `collection`, whose values are Debezium-style `before` and `after`
+/// records.
+///
+/// Notifications are handed to the coordinator, which delivers them to the
+/// sessions that are listening on the channel at that moment.
+pub fn notify<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>)>,
+    id: GlobalId,
+    connector: NotifySinkConnector,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let mut vector = vec![];
+    let mut payloads = vec![];
+
+    collection
+        .inner
+        .sink(Pipeline, &format!("notify-{}", id), move |input| {
+            input.for_each(|_, rows| {
+                rows.swap(&mut vector);
+                for ((k, v), time, diff) in vector.drain(..) {
+                    let should_emit = if connector.strict {
+                        connector.frontier.less_than(&time)
+                    } else {
+                        connector.frontier.less_equal(&time)
+                    };
+                    if !should_emit {
+                        // Skip stale data for already published timestamps
+                        continue;
+                    }
+                    assert!(k.is_none(), "NOTIFY sinks must not have keys");
+                    let v = v.expect("NOTIFY sinks must have values");
+                    assert!(diff >= 0, "can't sink negative multiplicities");
+                    let payload = match v.iter().nth(1) {
+                        Some(Datum::List(after)) => match after.iter().next() {
+                            Some(Datum::String(s)) => s.to_owned(),
+                            // A null payload is sent as an empty one, as
+                            // payloads are never null.
+                            _ => String::new(),
+                        },
+                        // Rows that were removed send nothing.
+                        _ => continue,
+                    };
+                    if payload.len() >= MAX_NOTIFY_PAYLOAD_BYTES {
+                        // `NOTIFY` would refuse such a payload, so listeners
+                        // must not receive it from a sink either.
+                        error!(
+                            "notify sink {} dropped a payload of {} bytes, as payloads must be shorter than {} bytes",
+                            id,
+                            payload.len(),
+                            MAX_NOTIFY_PAYLOAD_BYTES
+                        );
+                        continue;
+                    }
+                    for _ in 0..diff {
+                        payloads.push(payload.clone());
+                    }
+                }
+            });
+            if !payloads.is_empty() {
+                let notifications = (connector.channel.clone(), mem::take(&mut payloads));
+                if connector.tx.send(notifications).is_err() {
+                    error!("notify sink {} lost its connection to the coordinator", id);
+                }
+            }
+        })
+}
@@ -0,0 +1,190 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::{Duration, Instant};
+
+use differential_dataflow::Collection;
+use log::{debug, error, warn};
+use rusoto_s3::{PutObjectRequest, S3Client, S3};
+use timely::dataflow::channels::pact::Pipeline;
+use timely::dataflow::operators::generic::builder_rc::OperatorBuilder;
+use timely::dataflow::operators::generic::FrontieredInputHandle;
+use timely::dataflow::Scope;
+use tokio::sync::mpsc;
+use tokio::time;
+
+use aws_util::aws;
+use dataflow_types::S3SinkConnector;
+use expr::GlobalId;
+use interchange::parquet::Encoder;
+use repr::{Row, Timestamp};
+
+/// The maximum delay between attempts to upload a file.
+const MAX_UPLOAD_BACKOFF: Duration = Duration::from_secs(60);
+
+/// A complete Parquet file, waiting to be uploaded.
+struct File {
+    key: String,
+    data: Vec<u8>,
+}
+
+/// Writes the rows of `collection` to rolling Parquet files in S3.
+///
+/// Rows are buffered in memory until they exceed the connector's
+/// `max_file_bytes`, the oldest of them is older than its `max_file_age`, or
+/// the input is complete. The buffered rows are then encoded as a single file
+/// and uploaded with one `PutObject` request, so that a file becomes visible
+/// either in its entirety or not at all. Rows buffered when the sink is
+/// dropped or the process crashes are never uploaded; a restarted sink writes
+/// its output again, into files whose counters follow those already present.
+pub fn s3<G>(
+    collection: Collection<G, (Option<Row>, Option<Row>)>,
+    id: GlobalId,
+    connector: S3SinkConnector,
+) where
+    G: Scope<Timestamp = Timestamp>,
+{
+    let stream = &collection.inner;
+    let name = format!("s3-{}", id);
+    let mut builder = OperatorBuilder::new(name.clone(), stream.scope());
+    let activator = stream
+        .scope()
+        .activator_for(&builder.operator_info().address[..]);
+    let encoder =
+        Encoder::new(connector.value_desc.clone()).expect("parquet encoding validated in planning");
+
+    // Uploads happen off of the timely thread. Dropping the operator closes
+    // the channel, which stops the upload task once it has drained.
+    let (tx, rx) = mpsc::unbounded_channel();
+    tokio::spawn(upload_files_task(
+        connector.bucket.clone(),
+        connector.aws_info.clone(),
+        rx,
+    ));
+
+    let mut buffer: Vec<Row> = vec![];
+    let mut buffer_bytes = 0;
+    let mut oldest_buffered: Option<Instant> = None;
+    let mut counter = connector.first_file_counter;
+    let mut vector = vec![];
+
+    // We want exactly one worker to write all the files. This should already
+    // have been handled upstream (in render/mod.rs), so we can use `Pipeline`
+    // here.
+    let mut input = builder.new_input(stream, Pipeline);
+    builder.build_reschedule(|_capabilities| {
+        move |frontiers| {
+            let mut input = FrontieredInputHandle::new(&mut input, &frontiers[0]);
+            input.for_each(|_, rows| {
+                rows.swap(&mut vector);
+                for ((k, v), time, diff) in vector.drain(..) {
+                    let should_emit = if connector.strict {
+                        connector.frontier.less_than(&time)
+                    } else {
+                        connector.frontier.less_equal(&time)
+                    };
+                    if !should_emit {
+                        // Skip stale data for already published timestamps
+                        continue;
+                    }
+                    assert!(k.is_none(), "S3 sinks must not have keys");
+                    let v = v.expect("S3 sinks must have values");
+                    assert!(diff >= 0, "can't sink negative multiplicities");
+                    for _ in 0..diff {
+                        buffer_bytes += v.data().len();
+                        buffer.push(v.clone());
+                    }
+                    oldest_buffered.get_or_insert_with(Instant::now);
+                }
+            });
+
+            let done = input.frontier.frontier().is_empty();
+            let age = oldest_buffered.map(|t| t.elapsed()).unwrap_or_default();
+            if !buffer.is_empty()
+                && (buffer_bytes as u64 >= connector.max_file_bytes
+                    || age >= connector.max_file_age
+                    || done)
+            {
+                match encoder.encode(&buffer) {
+                    Ok(data) => {
+                        let key = connector.object_key(id, counter);
+                        counter += 1;
+                        if tx.send(File { key, data }).is_err() {
+                            error!("upload task for sink {} exited unexpectedly", name);
+                        }
+                    }
+                    Err(e) => error!("encoding parquet file for sink {} failed: {}", name, e),
+                }
+                buffer.clear();
+                buffer_bytes = 0;
+                oldest_buffered = None;
+            }
+
+            if oldest_buffered.is_some() {
+                // Complete the file once it is old enough, even if no more
+                // rows arrive in the meantime.
+                activator
+                    .activate_after(connector.max_file_age.checked_sub(age).unwrap_or_default());
+            }
+            false
+        }
+    });
+}
+
+async fn upload_files_task(
+    bucket: String,
+    aws_info: aws::ConnectInfo,
+    mut rx: mpsc::UnboundedReceiver<File>,
+) {
+    let client = match aws_util::s3::client(aws_info).await {
+        Ok(client) => client,
+        Err(e) => {
+            error!("unable to create s3 client for sink: {}", e);
+            return;
+        }
+    };
+    while let Some(file) = rx.recv().await {
+        upload_file(&client, &bucket, file).await;
+    }
+}
+
+/// Uploads `file`, retrying with backoff until the upload succeeds. Retries
+/// write the same key, so a request that succeeded without our knowing is
+/// harmlessly repeated.
+///
+/// Files are never abandoned, as that would silently drop rows from the
+/// sink's output. Later files wait behind the failing one, so that files
+/// still appear in the order of their counters.
+async fn upload_file(client: &S3Client, bucket: &str, file: File) {
+    let mut backoff = Duration::from_millis(100);
+    for attempt in 1.. {
+        let res = client
+            .put_object(PutObjectRequest {
+                bucket: bucket.to_string(),
+                key: file.key.clone(),
+                body: Some(file.data.clone().into()),
+                ..Default::default()
+            })
+            .await;
+        match res {
+            Ok(_) => {
+                debug!("uploaded s3://{}/{}", bucket, file.key);
+                return;
+            }
+            Err(e) => {
+                warn!(
+                    "unable to upload s3://{}/{} (attempt {}), retrying in {:?}: {}",
+                    bucket, file.key, attempt, backoff, e
+                );
+                time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_UPLOAD_BACKOFF);
+            }
+        }
+    }
+}
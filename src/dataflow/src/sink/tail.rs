@@ -7,6 +7,8 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::rc::Rc;
 
 use differential_dataflow::trace::cursor::Cursor;
@@ -16,7 +18,7 @@ use timely::dataflow::channels::pact::Pipeline;
 use timely::dataflow::operators::Operator;
 use timely::dataflow::{Scope, Stream};
 
-use dataflow_types::TailSinkConnector;
+use dataflow_types::{TailOutput, TailSinkConnector};
 use expr::GlobalId;
 use repr::adt::decimal::Significand;
 use repr::{Datum, Diff, Row, RowPacker, Timestamp};
@@ -67,7 +69,17 @@ pub fn tail<G>(
             // Sort results by time and convert to Vec<Row>. We use stable sort here even
             // though it is slower because it will produce deterministic results since the
             // cursor will always produce rows in the same order.
-            results.sort_by_key(|(time, _)| *time);
+            if connector.order_by.is_empty() {
+                results.sort_by_key(|(time, _)| *time);
+            } else {
+                // Every update at a time arrives in the same batch, so sorting
+                // the updates of these batches sorts each time completely.
+                results.sort_by(|(left_time, left), (right_time, right)| {
+                    left_time
+                        .cmp(right_time)
+                        .then_with(|| compare_updates(&connector, left, right))
+                });
+            }
             let mut results: Vec<Row> = results.into_iter().map(|(_, row)| row).collect();
 
             if connector.emit_progress {
@@ -86,6 +98,12 @@ pub fn tail<G>(
                 }
             }
 
+            if let TailOutput::Jsonl = connector.output {
+                for row in &mut results {
+                    *row = jsonl_row(row, &connector, &mut packer);
+                }
+            }
+
             // TODO(benesch): the lack of backpressure here can result in
             // unbounded memory usage.
             if connector.tx.send(results).is_err() {
@@ -96,6 +114,78 @@ pub fn tail<G>(
     })
 }
 
+/// Compares two updates at the same time by the tail's `ORDER BY` columns.
+/// Updates that agree on those columns are ordered with retractions before
+/// insertions, so that a changed row appears as its old value followed by its
+/// new one, and then by their remaining columns.
+fn compare_updates(connector: &TailSinkConnector, left: &Row, right: &Row) -> Ordering {
+    // Skip the timestamp, progressed, and diff columns that precede the
+    // columns of the tailed relation.
+    let diff_column = if connector.emit_progress { 2 } else { 1 };
+    let (left, right) = (left.unpack(), right.unpack());
+    let (left_columns, right_columns) = (&left[diff_column + 1..], &right[diff_column + 1..]);
+    expr::compare_columns(&connector.order_by, left_columns, right_columns, || {
+        left[diff_column]
+            .cmp(&right[diff_column])
+            .then_with(|| left_columns.cmp(right_columns))
+    })
+}
+
+/// Converts a row produced by the tail into a row with a single column that
+/// contains the update as a JSON object.
+///
+/// Progress messages contain only the `mz_timestamp` and `mz_progressed` keys.
+fn jsonl_row(row: &Row, connector: &TailSinkConnector, packer: &mut RowPacker) -> Row {
+    fn push_field(out: &mut String, key: &str, value: &serde_json::Value) {
+        out.push(if out.is_empty() { '{' } else { ',' });
+        out.push_str(&serde_json::to_string(key).expect("strings are valid JSON"));
+        out.push(':');
+        out.push_str(&value.to_string());
+    }
+
+    let mut datums = row.iter();
+    let mut out = String::new();
+    let timestamp = datums.next().expect("tail rows have a timestamp");
+    push_field(
+        &mut out,
+        "mz_timestamp",
+        &serde_json::Value::from(
+            u64::try_from(timestamp.unwrap_decimal().as_i128()).expect("timestamps fit in a u64"),
+        ),
+    );
+    let progressed = connector.emit_progress
+        && datums
+            .next()
+            .expect("tail rows have a progressed column")
+            .unwrap_bool();
+    if connector.emit_progress {
+        push_field(
+            &mut out,
+            "mz_progressed",
+            &serde_json::Value::Bool(progressed),
+        );
+    }
+    if !progressed {
+        let diff = datums.next().expect("tail rows have a diff");
+        push_field(
+            &mut out,
+            "mz_diff",
+            &serde_json::Value::from(diff.unwrap_int64()),
+        );
+        for ((name, typ), datum) in connector.value_desc.iter().zip(datums) {
+            let value = match pgrepr::Value::from_datum(datum, &typ.scalar_type) {
+                None => serde_json::Value::Null,
+                Some(value) => value.to_json(),
+            };
+            let name = name.map(|name| name.as_str()).unwrap_or("?column?");
+            push_field(&mut out, name, &value);
+        }
+    }
+    out.push('}');
+    packer.push(Datum::String(&out));
+    packer.finish_and_reuse()
+}
+
 fn batch_upper(
     batch: Option<&Rc<OrdValBatch<GlobalId, Row, u64, isize, usize>>>,
 ) -> Option<Timestamp> {
@@ -10,7 +10,9 @@
 use std::collections::HashMap;
 use std::io::{self, BufRead, Read};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicI64, Ordering};
 use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
 use std::thread;
 
 use anyhow::{Context, Error};
@@ -54,6 +56,8 @@ pub struct FileSourceInfo<Out> {
     /// Current File Offset. This corresponds to the offset of last processed message
     /// (initially 0 if no records have been processed)
     current_file_offset: FileOffset,
+    /// Number of bytes that the reader thread has read from the file
+    bytes_read: Arc<AtomicI64>,
     /// Timely worker logger for source events
     logger: Option<Logger>,
 }
@@ -86,6 +90,7 @@ impl SourceConstructor<Value> for FileSourceInfo<Value> {
         consistency_info: &mut ConsistencyInfo,
         encoding: DataEncoding,
     ) -> Result<FileSourceInfo<Value>, anyhow::Error> {
+        let bytes_read = Arc::new(AtomicI64::new(0));
         let receiver = match connector {
             ExternalSourceConnector::AvroOcf(oc) => {
                 let reader_schema = match &encoding {
@@ -105,15 +110,19 @@ impl SourceConstructor<Value> for FileSourceInfo<Value> {
                     FileReadStyle::ReadOnce
                 };
                 let (tx, rx) = std::sync::mpsc::sync_channel(10000_usize);
-                std::thread::spawn(move || {
-                    read_file_task(
-                        oc.path,
-                        tx,
-                        Some(consumer_activator),
-                        tail,
-                        oc.compression,
-                        ctor,
-                    );
+                std::thread::spawn({
+                    let bytes_read = bytes_read.clone();
+                    move || {
+                        read_file_task(
+                            oc.path,
+                            tx,
+                            Some(consumer_activator),
+                            Some(bytes_read),
+                            tail,
+                            oc.compression,
+                            ctor,
+                        );
+                    }
                 });
                 rx
             }
@@ -133,6 +142,7 @@ impl SourceConstructor<Value> for FileSourceInfo<Value> {
             receiver_stream: receiver,
             buffer: None,
             current_file_offset: FileOffset { offset: 0 },
+            bytes_read,
             logger,
         })
     }
@@ -151,25 +161,36 @@ impl SourceConstructor<Vec<u8>> for FileSourceInfo<Vec<u8>> {
         consistency_info: &mut ConsistencyInfo,
         _: DataEncoding,
     ) -> Result<FileSourceInfo<Vec<u8>>, anyhow::Error> {
+        let bytes_read = Arc::new(AtomicI64::new(0));
         let receiver = match connector {
             ExternalSourceConnector::File(fc) if active => {
                 log::debug!("creating FileSourceInfo worker_id={}", worker_id);
-                let ctor = |fi| Ok(std::io::BufReader::new(fi).split(b'\n'));
+                let delimiter = fc.line_delimiter.clone();
+                let ctor = move |fi| {
+                    Ok(DelimitedRecords {
+                        inner: std::io::BufReader::new(fi),
+                        delimiter,
+                    })
+                };
                 let (tx, rx) = std::sync::mpsc::sync_channel(10000);
                 let tail = if fc.tail {
                     FileReadStyle::TailFollowFd
                 } else {
                     FileReadStyle::ReadOnce
                 };
-                std::thread::spawn(move || {
-                    read_file_task(
-                        fc.path,
-                        tx,
-                        Some(consumer_activator),
-                        tail,
-                        fc.compression,
-                        ctor,
-                    );
+                std::thread::spawn({
+                    let bytes_read = bytes_read.clone();
+                    move || {
+                        read_file_task(
+                            fc.path,
+                            tx,
+                            Some(consumer_activator),
+                            Some(bytes_read),
+                            tail,
+                            fc.compression,
+                            ctor,
+                        );
+                    }
                 });
                 rx
             }
@@ -195,6 +216,7 @@ impl SourceConstructor<Vec<u8>> for FileSourceInfo<Vec<u8>> {
             receiver_stream: receiver,
             buffer: None,
             current_file_offset: FileOffset { offset: 0 },
+            bytes_read,
             logger,
         })
     }
@@ -295,6 +317,8 @@ impl<Out> SourceInfo<Out> for FileSourceInfo<Out> {
                         upstream_time_millis: None,
                         key: None,
                         payload: Some(record),
+                        headers: None,
+                        timestamp_type: None,
                     };
                     Ok(NextMessage::Ready(message))
                 }
@@ -311,6 +335,10 @@ impl<Out> SourceInfo<Out> for FileSourceInfo<Out> {
     fn buffer_message(&mut self, message: SourceMessage<Out>) {
         self.buffer = Some(message);
     }
+
+    fn upstream_bytes_read(&self) -> Option<i64> {
+        Some(self.bytes_read.load(Ordering::Relaxed))
+    }
 }
 
 /// Blocking logic to read from a file, intended for its own thread.
@@ -318,6 +346,7 @@ pub fn read_file_task<Ctor, I, Out, Err>(
     path: PathBuf,
     tx: std::sync::mpsc::SyncSender<Result<Out, anyhow::Error>>,
     activator: Option<SyncActivator>,
+    bytes_read: Option<Arc<AtomicI64>>,
     read_style: FileReadStyle,
     compression: Compression,
     iter_ctor: Ctor,
@@ -414,6 +443,13 @@ pub fn read_file_task<Ctor, I, Out, Err>(
         }
     };
 
+    // Count bytes before decompression, so that the count is comparable with
+    // the size of the file.
+    let file: Box<dyn AvroRead + Send> = match bytes_read {
+        Some(count) => Box::new(CountingReader { inner: file, count }),
+        None => file,
+    };
+
     let file: Box<dyn AvroRead + Send> = match compression {
         Compression::Gzip => Box::new(MultiGzDecoder::new(file)),
         Compression::None => Box::new(file),
@@ -482,6 +518,69 @@ impl<Ev> Read for ForeverTailedFile<Ev> {
     }
 }
 
+/// An iterator over the records of a reader that are separated by
+/// `delimiter`, which may be several bytes long.
+///
+/// Like [`BufRead::split`], this strips the delimiter from each record, and
+/// produces a final record that is not followed by the delimiter.
+struct DelimitedRecords<R> {
+    inner: R,
+    delimiter: Vec<u8>,
+}
+
+impl<R> Iterator for DelimitedRecords<R>
+where
+    R: BufRead,
+{
+    type Item = Result<Vec<u8>, io::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let last = *self
+            .delimiter
+            .last()
+            .expect("line delimiters are not empty");
+        let mut record = vec![];
+        loop {
+            // Reading up to the delimiter's last byte reassembles delimiters
+            // that span the reader's buffer boundaries.
+            match self.inner.read_until(last, &mut record) {
+                Ok(0) if record.is_empty() => return None,
+                Ok(0) => return Some(Ok(record)),
+                Ok(_) if record.ends_with(&self.delimiter) => {
+                    record.truncate(record.len() - self.delimiter.len());
+                    return Some(Ok(record));
+                }
+                // Either the delimiter's last byte appeared on its own, or
+                // the reader ran out of data; keep reading.
+                Ok(_) => (),
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+/// Wraps a reader, counting the bytes that are read through it.
+struct CountingReader<R> {
+    inner: R,
+    count: Arc<AtomicI64>,
+}
+
+impl<R: Skip> Skip for CountingReader<R> {
+    fn skip(&mut self, len: usize) -> Result<(), io::Error> {
+        self.inner.skip(len)?;
+        self.count.fetch_add(len as i64, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.count.fetch_add(n as i64, Ordering::Relaxed);
+        Ok(n)
+    }
+}
+
 /// Sends a sequence of records and activates a timely operator for each.
 fn send_records<I, Out, Err>(
     iter: I,
@@ -510,3 +609,52 @@ fn send_records<I, Out, Err>(
     }
     log::trace!("sent {} records to reader", records);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(input: &[u8], delimiter: &[u8], capacity: usize) -> Vec<Vec<u8>> {
+        DelimitedRecords {
+            inner: io::BufReader::with_capacity(capacity, input),
+            delimiter: delimiter.to_vec(),
+        }
+        .collect::<Result<_, _>>()
+        .unwrap()
+    }
+
+    #[test]
+    fn delimited_records() {
+        let records: Vec<Vec<u8>> = vec![b"a".to_vec(), b"".to_vec(), b"bc".to_vec()];
+        assert_eq!(split(b"a\0\0bc\0", b"\0", 1024), records);
+        assert_eq!(split(b"a\0\0bc", b"\0", 1024), records);
+        assert_eq!(split(b"", b"\0", 1024), Vec::<Vec<u8>>::new());
+
+        // The last byte of a delimiter on its own does not end a record.
+        assert_eq!(
+            split(b"a\nb\r\nc", b"\r\n", 1024),
+            vec![b"a\nb".to_vec(), b"c".to_vec()]
+        );
+        assert_eq!(
+            split(b"aaa", b"aa", 1024),
+            vec![b"".to_vec(), b"a".to_vec()]
+        );
+    }
+
+    #[test]
+    fn delimited_records_across_buffers() {
+        // With a four-byte buffer, the first delimiter is split between the
+        // first and second buffers, and the second record spans three
+        // buffers.
+        let input = b"abc\r\ndefghijk\r\nl";
+        let expected = vec![b"abc".to_vec(), b"defghijk".to_vec(), b"l".to_vec()];
+        assert_eq!(split(input, b"\r\n", 4), expected);
+        for capacity in 1..input.len() {
+            assert_eq!(split(input, b"\r\n", capacity), expected);
+        }
+        assert_eq!(
+            split(b"ab<=>cd<=>", b"<=>", 3),
+            vec![b"ab".to_vec(), b"cd".to_vec()]
+        );
+    }
+}
@@ -9,19 +9,22 @@
 
 use std::cmp;
 use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
-use std::convert::TryInto;
+use std::convert::{TryFrom, TryInto};
 use std::fs;
 use std::path::PathBuf;
-use std::sync::Arc;
-use std::time::Duration;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 use differential_dataflow::hashable::Hashable;
+use rand::Rng;
 use rdkafka::consumer::base_consumer::PartitionQueue;
 use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
-use rdkafka::error::KafkaError;
-use rdkafka::message::BorrowedMessage;
+use rdkafka::error::{KafkaError, KafkaResult};
+use rdkafka::message::{BorrowedMessage, Headers, Timestamp as KafkaTimestamp};
 use rdkafka::topic_partition_list::Offset;
 use rdkafka::{ClientConfig, ClientContext, Message, Statistics, TopicPartitionList};
+use regex::Regex;
 use timely::scheduling::activate::{Activator, SyncActivator};
 
 use dataflow_types::{
@@ -48,21 +51,36 @@ use crate::{
 
 /// Contains all information necessary to ingest data from Kafka
 pub struct KafkaSourceInfo {
-    /// Name of the topic on which this source is backed on
+    /// Name of the topic on which this source is backed on, or the pattern
+    /// that selects its topics
     topic_name: String,
+    /// Whether `topic_name` is a pattern, in which case the source reads
+    /// every topic that matches it
+    topic_pattern: bool,
+    /// The topics that match the pattern, with their partition counts, as
+    /// most recently discovered and not yet picked up by the source
+    discovered_topics: Arc<Mutex<Option<BTreeMap<String, i32>>>>,
     /// Name of the source (will have format kafka-source-id)
     source_name: String,
     /// Source instance ID
     id: SourceInstanceId,
     /// Kafka consumer for this source
     consumer: Arc<BaseConsumer<GlueConsumerContext>>,
+    /// Activates the source when its consumer has new messages
+    consumer_activator: SyncActivator,
+    /// The brokers to which the consumer connects
+    addrs: KafkaAddrs,
+    /// The prefix of the consumer's group ID, if any
+    group_id_prefix: Option<String>,
+    /// The ID of the Materialize cluster, used in the consumer's group ID
+    cluster_id: Uuid,
     /// List of consumers. A consumer should be assigned per partition to guarantee fairness
     partition_consumers: VecDeque<PartitionConsumer>,
     /// Metadata to keep track of whether a message is buffered at
     /// that partition
-    buffered_metadata: HashSet<i32>,
-    /// The number of known partitions.
-    known_partitions: i32,
+    buffered_metadata: HashSet<PartitionId>,
+    /// The number of known partitions of each topic.
+    known_partitions: BTreeMap<String, i32>,
     /// Worker ID
     worker_id: i32,
     /// Worker Count
@@ -71,6 +89,19 @@ pub struct KafkaSourceInfo {
     cached_files: Vec<PathBuf>,
     /// Timely worker logger for source events
     logger: Option<Logger>,
+    /// Delays polling the consumer again after errors
+    backoff: Backoff,
+    /// Whether to attach each message's headers to its output
+    include_headers: bool,
+    /// Whether to attach the type of each message's timestamp to its output
+    include_timestamp_type: bool,
+    /// The time, in milliseconds since the Unix epoch, from which to start
+    /// reading new partitions, if any
+    start_timestamp: Option<i64>,
+    /// Partitions that this worker is responsible for, but that are not yet
+    /// assigned to the consumer because their start offsets could not be
+    /// resolved
+    unresolved_partitions: UnresolvedPartitions,
 }
 
 impl SourceConstructor<Vec<u8>> for KafkaSourceInfo {
@@ -143,7 +174,8 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
     ) -> bool {
         let kafka_pid = match pid {
             PartitionId::Kafka(pid) => *pid,
-            // KafkaSourceInfo should only receive PartitionId::Kafka
+            // KafkaSourceInfo should only receive PartitionId::Kafka, as
+            // sources that read a topic pattern use real-time consistency
             _ => unreachable!(),
         };
 
@@ -162,7 +194,7 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
 
         // We separate these two cases, as consumer.position() is an expensive call that should
         // be avoided if possible. Case 1 and 2.a occur first, and we only test 2.b when necessary
-        if !self.has_partition(kafka_pid) // Case 1
+        if !self.has_partition(&self.topic_name, kafka_pid) // Case 1
         || last_offset >= offset
         // Case 2.a
         {
@@ -170,9 +202,13 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
         } else {
             let mut current_consumer_position: MzOffset = KafkaOffset {
                 offset: match self.consumer.position() {
+                    // Partitions with unresolved start offsets are not yet
+                    // assigned, so look the partition up by ID rather than
+                    // by position.
                     Ok(topic_list) => topic_list
                         .elements_for_topic(&self.topic_name)
-                        .get(kafka_pid as usize)
+                        .into_iter()
+                        .find(|el| el.partition() == kafka_pid)
                         .map(|el| match el.offset() {
                             Offset::Offset(o) => o - 1,
                             _ => -1,
@@ -185,7 +221,7 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
 
             // If a message has been buffered (but not timestamped), the consumer will already have
             // moved ahead.
-            if self.is_buffered(kafka_pid) {
+            if self.is_buffered(pid) {
                 current_consumer_position.offset -= 1;
             }
 
@@ -196,66 +232,55 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
     /// Returns the number of partitions expected *for this worker*. Partitions are assigned
     /// round-robin in worker id order offset by the hash of the source_id
     fn get_worker_partition_count(&self) -> i32 {
-        (0..self.known_partitions)
-            .filter(|pid| has_partition(self.id.source_id, self.worker_id, self.worker_count, *pid))
-            .count() as i32
+        self.known_partitions
+            .iter()
+            .map(|(topic, partition_count)| {
+                (0..*partition_count)
+                    .filter(|pid| self.has_partition(topic, *pid))
+                    .count() as i32
+            })
+            .sum()
     }
 
     /// Returns true if this worker is responsible for this partition
     fn has_partition(&self, partition_id: PartitionId) -> bool {
-        let pid = match partition_id {
-            PartitionId::Kafka(pid) => pid,
+        match partition_id {
+            PartitionId::Kafka(pid) => self.has_partition(&self.topic_name, pid),
+            PartitionId::KafkaTopic { topic, partition } => self.has_partition(&topic, partition),
             _ => unreachable!(),
-        };
-
-        self.has_partition(pid)
+        }
     }
 
     /// Ensures that a partition queue for `pid` exists.
     /// In Kafka, partitions are assigned contiguously. This function consequently
     /// creates partition queues for every p <= pid
     fn ensure_has_partition(&mut self, consistency_info: &mut ConsistencyInfo, pid: PartitionId) {
-        let pid = match pid {
-            PartitionId::Kafka(p) => p,
+        let (topic, pid) = match pid {
+            PartitionId::Kafka(p) => (self.topic_name.clone(), p),
+            PartitionId::KafkaTopic { topic, partition } => (topic, partition),
             _ => unreachable!(),
         };
-        for i in self.known_partitions..=pid {
-            if self.has_partition(i) {
-                self.create_partition_queue(i);
-                consistency_info.partition_metrics.insert(
-                    PartitionId::Kafka(i),
-                    PartitionMetrics::new(
-                        &self.topic_name,
-                        self.id,
-                        &i.to_string(),
-                        self.logger.clone(),
-                    ),
-                );
-            }
-            consistency_info.update_partition_metadata(PartitionId::Kafka(i));
-        }
-        self.known_partitions = cmp::max(self.known_partitions, pid + 1);
-
-        assert_eq!(
-            self.get_worker_partition_count(),
-            self.get_partition_consumers_count()
-        );
-        assert_eq!(
-            self.known_partitions as usize,
-            consistency_info.partition_metadata.len()
-        );
+        self.ensure_has_partitions(consistency_info, &topic, pid + 1);
     }
 
     /// Updates the Kafka source to reflect the new partition count.
     /// Kafka creates partitions with contiguous IDs, starting from 0.
     /// as PIDs are contiguous, we ensure that we have created partitions up to PID
     /// (partition_count-1) as partitions are 0-indexed.
+    ///
+    /// The partition count describes a single topic, so sources that read a
+    /// topic pattern instead pick up the topics that were last discovered to
+    /// match it.
     fn update_partition_count(
         &mut self,
         consistency_info: &mut ConsistencyInfo,
         partition_count: i32,
     ) {
-        self.ensure_has_partition(consistency_info, PartitionId::Kafka(partition_count - 1));
+        if self.topic_pattern {
+            self.update_topics(consistency_info);
+        } else {
+            self.ensure_has_partition(consistency_info, PartitionId::Kafka(partition_count - 1));
+        }
     }
 
     /// This function checks whether any messages have been buffered. If yes, returns the buffered
@@ -269,15 +294,32 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
         consistency_info: &mut ConsistencyInfo,
         activator: &Activator,
     ) -> Result<NextMessage<Vec<u8>>, anyhow::Error> {
+        if let Some(start_timestamp) = self.start_timestamp {
+            let resolved = self
+                .unresolved_partitions
+                .retry(&*self.consumer, start_timestamp);
+            for (topic, partition_id, offset) in resolved {
+                info!(
+                    "Resolved start_timestamp {} for {} [{}] (source {}) to {:?}",
+                    start_timestamp, topic, partition_id, self.id, offset
+                );
+                self.assign_partition_queue(&topic, partition_id, offset);
+            }
+        }
+
         // Poll the consumer once. Since we split the consumer's partitions out into separate queues and poll those individually,
         // we expect this poll to always return None - but it's necessary to drive logic that consumes from rdkafka's internal
         // event queue, such as statistics callbacks.
+        let mut errored = false;
         if let Some(result) = self.consumer.poll(Duration::from_secs(0)) {
             match result {
-                Err(e) => error!(
-                    "kafka error when polling consumer for source: {} topic: {} : {}",
-                    self.source_name, self.topic_name, e
-                ),
+                Err(e) => {
+                    errored = true;
+                    error!(
+                        "kafka error when polling consumer for source: {} topic: {} : {}",
+                        self.source_name, self.topic_name, e
+                    )
+                }
                 Ok(m) => error!(
                     "unexpected receipt of kafka message from non-partitioned queue for source: {} topic: {} partition: {} offset: {}",
                     self.source_name, self.topic_name, m.partition(), m.offset()
@@ -292,31 +334,28 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
             let mut partition_queue = self.partition_consumers.pop_front().unwrap();
             let message = match partition_queue.get_next_message() {
                 Err(e) => {
-                    let pid = partition_queue.pid();
                     let last_offset = consistency_info
                         .partition_metadata
-                        .get(&PartitionId::Kafka(pid))
+                        .get(&partition_queue.partition_id)
                         .unwrap()
                         .offset;
 
                     error!(
                         "kafka error consuming from source: {} topic: {}: partition: {} last processed offset: {} : {}",
                         self.source_name,
-                        self.topic_name,
-                        pid,
+                        partition_queue.topic,
+                        partition_queue.pid,
                         last_offset.offset,
                         e
                     );
+                    errored = true;
                     None
                 }
                 Ok(m) => m,
             };
 
             if let Some(message) = message {
-                let partition = match message.partition {
-                    PartitionId::Kafka(pid) => pid,
-                    _ => unreachable!(),
-                };
+                let partition = message.partition.clone();
                 // There are no more messages buffered on this pid
                 self.buffered_metadata.remove(&partition);
                 let offset = message.offset;
@@ -331,11 +370,11 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
 
                 // Given the explicit consumer to partition assignment, we should never receive a message
                 // for a partition for which we have no metadata
-                assert!(consistency_info.knows_of(PartitionId::Kafka(partition)));
+                assert!(consistency_info.knows_of(partition.clone()));
 
                 let mut last_offset = consistency_info
                     .partition_metadata
-                    .get(&PartitionId::Kafka(partition))
+                    .get(&partition)
                     .unwrap()
                     .offset;
 
@@ -345,14 +384,18 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
                              source {} (reading topic {}, partition {}) \
                              received Mz offset {} expected Mz offset {:?}",
                         self.source_name,
-                        self.topic_name,
-                        partition,
+                        partition_queue.topic,
+                        partition_queue.pid,
                         offset,
                         last_offset.offset + 1
                     );
                     // Seek to the *next* offset (aka last_offset + 1) that we have not yet processed
                     last_offset.offset += 1;
-                    self.fast_forward_consumer(partition, last_offset.into());
+                    self.fast_forward_consumer(
+                        &partition_queue.topic,
+                        partition_queue.pid,
+                        last_offset.into(),
+                    );
                     // We explicitly should not consume the message as we have already processed it
                     // However, we make sure to activate the source to make sure that we get a chance
                     // to read from this consumer again (even if no new data arrives)
@@ -370,20 +413,98 @@ impl SourceInfo<Vec<u8>> for KafkaSourceInfo {
             }
         }
         assert_eq!(
-            self.get_partition_consumers_count(),
+            self.get_owned_partitions_count(),
             self.get_worker_partition_count()
         );
 
+        if errored {
+            if let NextMessage::Pending = next_message {
+                let delay = self.backoff.next_delay();
+                debug!(
+                    "retrying kafka source: {} topic: {} after error (attempt {}, delay {:?})",
+                    self.source_name, self.topic_name, self.backoff.attempts, delay
+                );
+                return Ok(NextMessage::Backoff(delay));
+            }
+        } else {
+            self.backoff.reset();
+        }
+
+        // Make sure the source is scheduled again to retry any partitions
+        // whose start offsets are still unresolved, even if no data arrives.
+        if let NextMessage::Pending = next_message {
+            if let Some(delay) = self.unresolved_partitions.next_delay() {
+                return Ok(NextMessage::Backoff(delay));
+            }
+        }
+
         Ok(next_message)
     }
 
     fn buffer_message(&mut self, message: SourceMessage<Vec<u8>>) {
         // Guaranteed to exist as we just read from this consumer
         let mut consumer = self.partition_consumers.back_mut().unwrap();
-        assert_eq!(message.partition, PartitionId::Kafka(consumer.pid));
+        assert_eq!(message.partition, consumer.partition_id);
         consumer.buffer = Some(message);
         // Mark the partition has buffered
-        self.buffered_metadata.insert(consumer.pid);
+        self.buffered_metadata.insert(consumer.partition_id.clone());
+    }
+
+    fn reconfigure(&mut self, config_options: &BTreeMap<String, String>) {
+        info!(
+            "Reconfiguring Kafka consumer for {} (source {}) on worker {}",
+            self.topic_name, self.id, self.worker_id
+        );
+        let kafka_config = create_kafka_config(
+            &self.source_name,
+            &self.addrs,
+            self.group_id_prefix.clone(),
+            self.cluster_id,
+            config_options,
+        );
+        let consumer: Arc<BaseConsumer<GlueConsumerContext>> = match kafka_config
+            .create_with_context(GlueConsumerContext(self.consumer_activator.clone()))
+        {
+            Ok(consumer) => Arc::new(consumer),
+            Err(e) => {
+                error!(
+                    "Failed to reconfigure Kafka consumer for source {}: {}",
+                    self.source_name, e
+                );
+                return;
+            }
+        };
+
+        // Resume reading each partition from where the old consumer left off.
+        // The old consumer has no position for partitions it has not yet read
+        // from, in which case their assigned starting offset still applies.
+        let assignment = self.consumer.assignment().unwrap_or_default();
+        let positions = self.consumer.position().unwrap_or_default().to_topic_map();
+        let mut partition_list = TopicPartitionList::new();
+        for partition in assignment.elements() {
+            let key = (partition.topic().to_owned(), partition.partition());
+            let offset = match positions.get(&key) {
+                Some(Offset::Offset(o)) => Offset::Offset(*o),
+                _ => partition.offset(),
+            };
+            partition_list
+                .add_partition_offset(partition.topic(), partition.partition(), offset)
+                .expect("offset known to be valid");
+        }
+        if let Err(e) = consumer.assign(&partition_list) {
+            error!(
+                "Failed to reconfigure Kafka consumer for source {}: {}",
+                self.source_name, e
+            );
+            return;
+        }
+        for pc in &mut self.partition_consumers {
+            pc.partition_queue = consumer
+                .split_partition_queue(&pc.topic, pc.pid)
+                .expect("partition known to be valid");
+        }
+        self.consumer = consumer;
+        self.backoff = Backoff::new(RECONNECT_BACKOFF_BASE, backoff_max(config_options));
     }
 
     fn next_cached_file(&mut self) -> Option<Vec<(Vec<u8>, Vec<u8>, Timestamp, i64)>> {
@@ -461,6 +582,10 @@ impl KafkaSourceInfo {
             config_options,
             group_id_prefix,
             cluster_id,
+            include_headers,
+            include_timestamp_type,
+            start_timestamp,
+            topic_pattern,
             ..
         } = kc;
         let worker_id = worker_id.try_into().unwrap();
@@ -468,13 +593,24 @@ impl KafkaSourceInfo {
         let kafka_config = create_kafka_config(
             &source_name,
             &addrs,
-            group_id_prefix,
+            group_id_prefix.clone(),
             cluster_id,
             &config_options,
         );
         let consumer: BaseConsumer<GlueConsumerContext> = kafka_config
-            .create_with_context(GlueConsumerContext(consumer_activator))
+            .create_with_context(GlueConsumerContext(consumer_activator.clone()))
             .expect("Failed to create Kafka Consumer");
+        let discovered_topics = Arc::new(Mutex::new(None));
+        if topic_pattern {
+            spawn_topic_discovery(
+                &kafka_config,
+                compile_topic_pattern(&topic),
+                &discovered_topics,
+                consumer_activator.clone(),
+                metadata_refresh_interval(&config_options),
+                source_name.clone(),
+            );
+        }
         let cached_files = kc
             .cached_files
             .map(|files| {
@@ -520,26 +656,59 @@ impl KafkaSourceInfo {
         KafkaSourceInfo {
             buffered_metadata: HashSet::new(),
             topic_name: topic,
+            topic_pattern,
+            discovered_topics,
             source_name,
             id: source_id,
             partition_consumers: VecDeque::new(),
-            known_partitions: 0,
+            known_partitions: BTreeMap::new(),
             consumer: Arc::new(consumer),
+            consumer_activator,
+            addrs,
+            group_id_prefix,
+            cluster_id,
             worker_id,
             worker_count,
             cached_files,
             logger,
+            backoff: Backoff::new(RECONNECT_BACKOFF_BASE, backoff_max(&config_options)),
+            include_headers,
+            include_timestamp_type,
+            start_timestamp,
+            unresolved_partitions: UnresolvedPartitions::new(),
         }
     }
 
     /// Returns true if this worker is responsible for this partition
-    fn has_partition(&self, partition_id: i32) -> bool {
-        has_partition(
-            self.id.source_id,
-            self.worker_id,
-            self.worker_count,
-            partition_id,
-        )
+    fn has_partition(&self, topic: &str, partition_id: i32) -> bool {
+        if self.topic_pattern {
+            has_topic_partition(
+                self.id.source_id,
+                self.worker_id,
+                self.worker_count,
+                topic,
+                partition_id,
+            )
+        } else {
+            has_partition(
+                self.id.source_id,
+                self.worker_id,
+                self.worker_count,
+                partition_id,
+            )
+        }
+    }
+
+    /// Returns the ID with which messages from this partition are tracked
+    fn partition_id(&self, topic: &str, partition_id: i32) -> PartitionId {
+        if self.topic_pattern {
+            PartitionId::KafkaTopic {
+                topic: topic.to_owned(),
+                partition: partition_id,
+            }
+        } else {
+            PartitionId::Kafka(partition_id)
+        }
     }
 
     /// Returns a count of total number of consumers for this source
@@ -549,30 +718,170 @@ impl KafkaSourceInfo {
         self.partition_consumers.len().try_into().unwrap()
     }
 
+    /// Returns the number of partitions this worker is responsible for,
+    /// whether or not they have been assigned to the consumer yet
+    fn get_owned_partitions_count(&self) -> i32 {
+        self.get_partition_consumers_count() + self.unresolved_partitions.len()
+    }
+
     /// Returns true if a message has been buffered for this partition
-    fn is_buffered(&self, pid: i32) -> bool {
-        self.buffered_metadata.contains(&pid)
+    fn is_buffered(&self, pid: &PartitionId) -> bool {
+        self.buffered_metadata.contains(pid)
     }
 
-    /// Creates a new partition queue for `partition_id`.
-    fn create_partition_queue(&mut self, partition_id: i32) {
+    /// Ensures that partition queues exist for the first `partition_count`
+    /// partitions of `topic`, creating those this worker is responsible for.
+    fn ensure_has_partitions(
+        &mut self,
+        consistency_info: &mut ConsistencyInfo,
+        topic: &str,
+        partition_count: i32,
+    ) {
+        let known_partitions = self.known_partitions.get(topic).copied().unwrap_or(0);
+        for i in known_partitions..partition_count {
+            let pid = self.partition_id(topic, i);
+            if self.has_partition(topic, i) {
+                self.create_partition_queue(topic, i);
+                consistency_info.partition_metrics.insert(
+                    pid.clone(),
+                    PartitionMetrics::new(topic, self.id, &i.to_string(), self.logger.clone()),
+                );
+            }
+            consistency_info.update_partition_metadata(pid);
+        }
+        if partition_count > known_partitions {
+            self.known_partitions
+                .insert(topic.to_owned(), partition_count);
+        }
+
+        assert_eq!(
+            self.get_worker_partition_count(),
+            self.get_owned_partitions_count()
+        );
+        assert_eq!(
+            self.known_partitions.values().sum::<i32>() as usize,
+            consistency_info.partition_metadata.len()
+        );
+    }
+
+    /// Brings the topics that this source reads in line with those most
+    /// recently discovered to match its pattern: partition queues are created
+    /// for new topics and partitions, and topics that no longer match, which
+    /// usually means that they were deleted, stop being read.
+    fn update_topics(&mut self, consistency_info: &mut ConsistencyInfo) {
+        let discovered = match self.discovered_topics.lock().expect("lock poisoned").take() {
+            Some(discovered) => discovered,
+            None => return,
+        };
+        let TopicChanges { removed, grown } = diff_topics(&self.known_partitions, discovered);
+        for topic in removed {
+            self.remove_topic(consistency_info, &topic);
+        }
+        for (topic, partition_count) in grown {
+            self.ensure_has_partitions(consistency_info, &topic, partition_count);
+        }
+    }
+
+    /// Stops reading every partition of `topic`.
+    fn remove_topic(&mut self, consistency_info: &mut ConsistencyInfo, topic: &str) {
+        let partition_count = match self.known_partitions.remove(topic) {
+            Some(partition_count) => partition_count,
+            None => return,
+        };
+        info!(
+            "Deactivating Kafka queues for {} (source {}) on worker {}",
+            topic, self.id, self.worker_id
+        );
+
+        for i in 0..partition_count {
+            let pid = self.partition_id(topic, i);
+            self.buffered_metadata.remove(&pid);
+            consistency_info.partition_metadata.remove(&pid);
+            consistency_info.partition_metrics.remove(&pid);
+        }
+        self.partition_consumers.retain(|pc| pc.topic != topic);
+        self.unresolved_partitions.remove_topic(topic);
+
+        let tpl = self.consumer.assignment().unwrap();
+        let mut partition_list = TopicPartitionList::new();
+        for partition in tpl.elements() {
+            if partition.topic() != topic {
+                partition_list
+                    .add_partition_offset(
+                        partition.topic(),
+                        partition.partition(),
+                        partition.offset(),
+                    )
+                    .expect("offset known to be valid");
+            }
+        }
+        self.consumer
+            .assign(&partition_list)
+            .expect("assignment known to be valid");
+
+        // As in `create_partition_queue`, the remaining partition queues must
+        // be recreated after the assignment changes.
+        for pc in &mut self.partition_consumers {
+            pc.partition_queue = self
+                .consumer
+                .split_partition_queue(&pc.topic, pc.pid)
+                .expect("partition known to be valid");
+        }
+        assert_eq!(
+            self.consumer.assignment().unwrap().count(),
+            self.partition_consumers.len()
+        );
+    }
+
+    /// Creates a new partition queue for `partition_id` of `topic`.
+    ///
+    /// If the partition's start offset cannot be resolved from the source's
+    /// start timestamp, the partition is instead deferred, and its queue
+    /// created once a later attempt to resolve the offset succeeds.
+    fn create_partition_queue(&mut self, topic: &str, partition_id: i32) {
+        let start_offset = match self.start_timestamp {
+            None => Offset::Beginning,
+            Some(ts) => match resolve_start_offset(&*self.consumer, topic, partition_id, ts) {
+                Ok(offset) => {
+                    info!(
+                        "Resolved start_timestamp {} for {} [{}] (source {}) to {:?}",
+                        ts, topic, partition_id, self.id, offset
+                    );
+                    offset
+                }
+                Err(e) => {
+                    warn!(
+                        "Failed to resolve start_timestamp for source: {} topic: {} partition: {}, will retry: {}",
+                        self.source_name, topic, partition_id, e
+                    );
+                    self.unresolved_partitions.defer(topic, partition_id);
+                    return;
+                }
+            },
+        };
+        self.assign_partition_queue(topic, partition_id, start_offset);
+    }
+
+    /// Assigns `partition_id` of `topic` to the consumer, starting at
+    /// `start_offset`, and creates its partition queue.
+    fn assign_partition_queue(&mut self, topic: &str, partition_id: i32, start_offset: Offset) {
         info!(
             "Activating Kafka queue for {} [{}] (source {}) on worker {}",
-            self.topic_name, partition_id, self.id, self.worker_id
+            topic, partition_id, self.id, self.worker_id
         );
 
         // Collect old partition assignments
         let tpl = self.consumer.assignment().unwrap();
         // Create list from assignments
         let mut partition_list = TopicPartitionList::new();
-        for partition in tpl.elements_for_topic(&self.topic_name) {
+        for partition in tpl.elements() {
             partition_list
                 .add_partition_offset(partition.topic(), partition.partition(), partition.offset())
                 .expect("offset known to be valid");
         }
         // Add new partition
         partition_list
-            .add_partition_offset(&self.topic_name, partition_id, Offset::Beginning)
+            .add_partition_offset(topic, partition_id, start_offset)
             .expect("offset known to be valid");
         self.consumer
             .assign(&partition_list)
@@ -583,22 +892,24 @@ impl KafkaSourceInfo {
         for pc in &mut self.partition_consumers {
             pc.partition_queue = self
                 .consumer
-                .split_partition_queue(&self.topic_name, pc.pid)
+                .split_partition_queue(&pc.topic, pc.pid)
                 .expect("partition known to be valid");
         }
 
         let partition_queue = self
             .consumer
-            .split_partition_queue(&self.topic_name, partition_id)
+            .split_partition_queue(topic, partition_id)
             .expect("partition known to be valid");
-        self.partition_consumers
-            .push_front(PartitionConsumer::new(partition_id, partition_queue));
+        self.partition_consumers.push_front(PartitionConsumer::new(
+            self.partition_id(topic, partition_id),
+            topic.to_owned(),
+            partition_id,
+            partition_queue,
+            self.include_headers,
+            self.include_timestamp_type,
+        ));
         assert_eq!(
-            self.consumer
-                .assignment()
-                .unwrap()
-                .elements_for_topic(&self.topic_name)
-                .len(),
+            self.consumer.assignment().unwrap().count(),
             self.partition_consumers.len()
         );
     }
@@ -606,9 +917,9 @@ impl KafkaSourceInfo {
     /// Fast-forward consumer to specified Kafka Offset. Prints a warning if failed to do so
     /// Assumption: if offset does not exist (for instance, because of compaction), will seek
     /// to the next available offset
-    fn fast_forward_consumer(&self, pid: i32, next_offset: KafkaOffset) {
+    fn fast_forward_consumer(&self, topic: &str, pid: i32, next_offset: KafkaOffset) {
         let res = self.consumer.seek(
-            &self.topic_name,
+            topic,
             pid,
             Offset::Offset(next_offset.offset),
             Duration::from_secs(1),
@@ -616,12 +927,10 @@ impl KafkaSourceInfo {
         match res {
             Ok(_) => {
                 let res = self.consumer.position().unwrap_or_default().to_topic_map();
-                let position = res
-                    .get(&(self.topic_name.clone(), pid))
-                    .and_then(|p| match p {
-                        Offset::Offset(o) => Some(o),
-                        _ => None,
-                    });
+                let position = res.get(&(topic.to_owned(), pid)).and_then(|p| match p {
+                    Offset::Offset(o) => Some(o),
+                    _ => None,
+                });
                 if let Some(position) = position {
                     info!(
                         "Tried to fast-forward consumer on partition PID: {} to Kafka offset {}. Consumer is now at position {}",
@@ -642,6 +951,352 @@ impl KafkaSourceInfo {
     }
 }
 
+/// The maximum time to wait for the broker to resolve a start timestamp.
+///
+/// Lookups block the worker, so this is kept short; partitions whose lookups
+/// fail are retried with backoff by [`UnresolvedPartitions`].
+const OFFSETS_FOR_TIMES_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The initial delay before retrying a failed start timestamp lookup.
+const RESOLVE_BACKOFF_BASE: Duration = Duration::from_millis(100);
+
+/// The maximum delay between retries of a failed start timestamp lookup.
+const RESOLVE_BACKOFF_MAX: Duration = Duration::from_secs(10);
+
+/// A source of answers to Kafka's `offsetsForTimes` request.
+///
+/// This is implemented by every consumer, and exists so that start offset
+/// resolution can be exercised without a running broker.
+trait OffsetsForTimes {
+    /// Returns, for each partition in `timestamps`, the earliest offset whose
+    /// message timestamp is at or after the timestamp given as the partition's
+    /// offset, or [`Offset::End`] if there is no such message.
+    fn lookup_offsets_for_times(
+        &self,
+        timestamps: TopicPartitionList,
+    ) -> KafkaResult<TopicPartitionList>;
+}
+
+impl<C: ConsumerContext> OffsetsForTimes for BaseConsumer<C> {
+    fn lookup_offsets_for_times(
+        &self,
+        timestamps: TopicPartitionList,
+    ) -> KafkaResult<TopicPartitionList> {
+        self.offsets_for_times(timestamps, OFFSETS_FOR_TIMES_TIMEOUT)
+    }
+}
+
+/// Determines the offset from which to start reading `partition` of `topic`
+/// so that ingestion begins with the first message whose timestamp is at or
+/// after `start_timestamp`, in milliseconds since the Unix epoch.
+///
+/// Partitions without any such message start from their log end, so that only
+/// messages produced from now on are read.
+fn resolve_start_offset<B: OffsetsForTimes>(
+    broker: &B,
+    topic: &str,
+    partition: i32,
+    start_timestamp: i64,
+) -> KafkaResult<Offset> {
+    let mut timestamps = TopicPartitionList::new();
+    timestamps.add_partition_offset(topic, partition, Offset::Offset(start_timestamp))?;
+    let offsets = broker.lookup_offsets_for_times(timestamps)?;
+    let elem = match offsets.find_partition(topic, partition) {
+        Some(elem) => elem,
+        None => return Ok(Offset::End),
+    };
+    elem.error()?;
+    Ok(match elem.offset() {
+        Offset::Offset(offset) if offset >= 0 => Offset::Offset(offset),
+        _ => Offset::End,
+    })
+}
+
+/// Partitions whose start offsets could not be resolved, which are retried
+/// with backoff until their lookups succeed.
+struct UnresolvedPartitions {
+    partitions: Vec<(String, i32)>,
+    backoff: Backoff,
+    /// When the next retry is due.
+    next_attempt: Instant,
+}
+
+impl UnresolvedPartitions {
+    fn new() -> UnresolvedPartitions {
+        UnresolvedPartitions {
+            partitions: vec![],
+            backoff: Backoff::new(RESOLVE_BACKOFF_BASE, RESOLVE_BACKOFF_MAX),
+            next_attempt: Instant::now(),
+        }
+    }
+
+    /// Returns the number of unresolved partitions.
+    fn len(&self) -> i32 {
+        self.partitions.len().try_into().unwrap()
+    }
+
+    /// Defers `partition` of `topic` until a retry resolves its start offset.
+    fn defer(&mut self, topic: &str, partition: i32) {
+        if self.partitions.is_empty() {
+            self.next_attempt = Instant::now() + self.backoff.next_delay();
+        }
+        self.partitions.push((topic.to_owned(), partition));
+    }
+
+    /// Forgets the unresolved partitions of `topic`.
+    fn remove_topic(&mut self, topic: &str) {
+        self.partitions.retain(|(t, _)| t != topic);
+    }
+
+    /// Returns how long to wait before the next retry is due, if any
+    /// partitions are unresolved.
+    fn next_delay(&self) -> Option<Duration> {
+        if self.partitions.is_empty() {
+            None
+        } else {
+            Some(self.next_attempt.saturating_duration_since(Instant::now()))
+        }
+    }
+
+    /// Retries resolving the start offset of every unresolved partition, if a
+    /// retry is due, and returns the partitions that resolved along with their
+    /// start offsets.
+    fn retry<B: OffsetsForTimes>(
+        &mut self,
+        broker: &B,
+        start_timestamp: i64,
+    ) -> Vec<(String, i32, Offset)> {
+        if self.partitions.is_empty() || Instant::now() < self.next_attempt {
+            return vec![];
+        }
+        let mut resolved = vec![];
+        let mut unresolved = vec![];
+        for (topic, partition) in self.partitions.drain(..) {
+            match resolve_start_offset(broker, &topic, partition, start_timestamp) {
+                Ok(offset) => resolved.push((topic, partition, offset)),
+                Err(e) => {
+                    warn!(
+                        "Failed to resolve start_timestamp for topic: {} partition: {}, will retry: {}",
+                        topic, partition, e
+                    );
+                    unresolved.push((topic, partition));
+                }
+            }
+        }
+        self.partitions = unresolved;
+        if self.partitions.is_empty() {
+            self.backoff.reset();
+        } else {
+            self.next_attempt = Instant::now() + self.backoff.next_delay();
+        }
+        resolved
+    }
+}
+
+/// The maximum time to wait for the broker to list its topics.
+const METADATA_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often to look up the topics that match a source's pattern, unless
+/// overridden by the `topic_metadata_refresh_interval_ms` option. This matches
+/// the interval at which the consumer refreshes its metadata by default.
+const DEFAULT_METADATA_REFRESH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Returns how often `config_options` asks for metadata to be refreshed.
+fn metadata_refresh_interval(config_options: &BTreeMap<String, String>) -> Duration {
+    match config_options.get("topic.metadata.refresh.interval.ms") {
+        Some(ms) => Duration::from_millis(ms.parse().expect("validated during planning")),
+        None => DEFAULT_METADATA_REFRESH_INTERVAL,
+    }
+}
+
+/// A source of the topics that a broker knows of.
+///
+/// This is implemented by every consumer, and exists so that topic discovery
+/// can be exercised without a running broker.
+trait ListTopics {
+    /// Returns the name and partition count of each of the broker's topics.
+    fn list_topics(&self) -> KafkaResult<Vec<(String, i32)>>;
+}
+
+impl<C: ConsumerContext> ListTopics for BaseConsumer<C> {
+    fn list_topics(&self) -> KafkaResult<Vec<(String, i32)>> {
+        let metadata = self.fetch_metadata(None, METADATA_TIMEOUT)?;
+        Ok(metadata
+            .topics()
+            .iter()
+            // Topics that are being deleted, for example, report an error
+            // rather than their partitions.
+            .filter(|topic| topic.error().is_none())
+            .map(|topic| {
+                // There cannot be more than i32 partitions
+                let partitions = topic.partitions().len().try_into().unwrap();
+                (topic.name().to_owned(), partitions)
+            })
+            .collect())
+    }
+}
+
+/// Compiles the pattern of a source that reads several topics. To select a
+/// topic, the pattern must match its entire name.
+fn compile_topic_pattern(pattern: &str) -> Regex {
+    Regex::new(&format!("^(?:{})$", pattern)).expect("validated during planning")
+}
+
+/// Returns the partition count of each topic of `broker` whose name matches
+/// `pattern`.
+fn discover_topics<B: ListTopics>(
+    broker: &B,
+    pattern: &Regex,
+) -> KafkaResult<BTreeMap<String, i32>> {
+    Ok(broker
+        .list_topics()?
+        .into_iter()
+        .filter(|(topic, _)| pattern.is_match(topic))
+        .collect())
+}
+
+/// The changes that bring the topics a source reads in line with those that
+/// were discovered to match its pattern.
+#[derive(Debug, PartialEq, Eq)]
+struct TopicChanges {
+    /// The topics that no longer match, which must stop being read.
+    removed: Vec<String>,
+    /// The topics that are new or have new partitions, with their partition
+    /// counts.
+    grown: Vec<(String, i32)>,
+}
+
+/// Computes the [`TopicChanges`] from the partition counts of the topics that
+/// a source reads, `known`, to those of the topics that were `discovered`.
+///
+/// Partition counts never shrink, so a topic that is discovered with fewer
+/// partitions than are known keeps them.
+fn diff_topics(known: &BTreeMap<String, i32>, discovered: BTreeMap<String, i32>) -> TopicChanges {
+    let removed = known
+        .keys()
+        .filter(|topic| !discovered.contains_key(*topic))
+        .cloned()
+        .collect();
+    let grown = discovered
+        .into_iter()
+        .filter(|(topic, partition_count)| match known.get(topic) {
+            Some(known_count) => partition_count > known_count,
+            None => true,
+        })
+        .collect();
+    TopicChanges { removed, grown }
+}
+
+/// Starts a thread that looks up the topics that match `pattern` every
+/// `interval`. Whenever they change, the thread stores them in
+/// `discovered_topics` and activates the source, so that it starts reading
+/// new topics and stops reading deleted ones. Metadata requests can take a
+/// long time, which is why they are not issued from the worker itself.
+///
+/// The thread exits once the source, which owns `discovered_topics`, is
+/// dropped.
+fn spawn_topic_discovery(
+    kafka_config: &ClientConfig,
+    pattern: Regex,
+    discovered_topics: &Arc<Mutex<Option<BTreeMap<String, i32>>>>,
+    activator: SyncActivator,
+    interval: Duration,
+    source_name: String,
+) {
+    let consumer: BaseConsumer = kafka_config
+        .create()
+        .expect("Failed to create Kafka Consumer");
+    let discovered_topics = Arc::downgrade(discovered_topics);
+    thread::spawn(move || {
+        let mut current_topics = None;
+        while discovered_topics.strong_count() > 0 {
+            match discover_topics(&consumer, &pattern) {
+                Ok(topics) if current_topics.as_ref() != Some(&topics) => {
+                    let discovered_topics = match discovered_topics.upgrade() {
+                        Some(discovered_topics) => discovered_topics,
+                        None => break,
+                    };
+                    info!(
+                        "Discovered {} kafka topics matching the pattern of source {}",
+                        topics.len(),
+                        source_name
+                    );
+                    *discovered_topics.lock().expect("lock poisoned") = Some(topics.clone());
+                    if activator.activate().is_err() {
+                        break;
+                    }
+                    current_topics = Some(topics);
+                }
+                Ok(_) => (),
+                Err(e) => error!(
+                    "Unable to fetch kafka metadata for source {}: {}",
+                    source_name, e
+                ),
+            }
+            thread::sleep(interval);
+        }
+        debug!(
+            "Terminating topic discovery thread for source {}",
+            source_name
+        );
+    });
+}
+
+/// The delay before the first retry after a Kafka error.
+const RECONNECT_BACKOFF_BASE: Duration = Duration::from_millis(100);
+
+/// The maximum delay between retries after Kafka errors, unless overridden by
+/// the `reconnect_backoff_max_ms` option. This matches librdkafka's default for
+/// `reconnect.backoff.max.ms`, which governs its own reconnection attempts.
+const DEFAULT_RECONNECT_BACKOFF_MAX: Duration = Duration::from_secs(10);
+
+/// Returns the maximum delay between retries configured by `config_options`.
+fn backoff_max(config_options: &BTreeMap<String, String>) -> Duration {
+    match config_options.get("reconnect.backoff.max.ms") {
+        Some(ms) => Duration::from_millis(ms.parse().expect("validated during planning")),
+        None => DEFAULT_RECONNECT_BACKOFF_MAX,
+    }
+}
+
+/// Exponential backoff with full jitter.
+///
+/// Each delay is chosen uniformly at random between zero and an upper bound
+/// that starts at `base` and doubles with every consecutive attempt, up to
+/// `max`. Randomizing the entire delay spreads out the retries of the many
+/// consumers that may be waiting on the same recovering broker.
+struct Backoff {
+    base: Duration,
+    max: Duration,
+    /// The number of consecutive attempts since the last reset.
+    attempts: u32,
+}
+
+impl Backoff {
+    fn new(base: Duration, max: Duration) -> Backoff {
+        Backoff {
+            base,
+            max,
+            attempts: 0,
+        }
+    }
+
+    /// Returns the delay before the next attempt.
+    fn next_delay(&mut self) -> Duration {
+        let bound = self
+            .base
+            .checked_mul(1 << cmp::min(self.attempts, 31))
+            .map_or(self.max, |bound| cmp::min(bound, self.max));
+        self.attempts = self.attempts.saturating_add(1);
+        let bound_ms = u64::try_from(bound.as_millis()).unwrap_or(u64::MAX);
+        Duration::from_millis(rand::thread_rng().gen_range(0..=bound_ms))
+    }
+
+    /// Resets the backoff to the base delay.
+    fn reset(&mut self) {
+        self.attempts = 0;
+    }
+}
+
 /// Creates a Kafka config.
 fn create_kafka_config(
     name: &str,
@@ -728,6 +1383,8 @@ impl<'a> From<&BorrowedMessage<'a>> for SourceMessage<Vec<u8>> {
             offset: kafka_offset.into(),
             upstream_time_millis: msg.timestamp().to_millis(),
             key: msg.key().map(|k| k.to_vec()),
+            headers: None,
+            timestamp_type: None,
         }
     }
 }
@@ -736,21 +1393,40 @@ impl<'a> From<&BorrowedMessage<'a>> for SourceMessage<Vec<u8>> {
 /// To read from this partition consumer 1) first check whether the buffer is empty. If not,
 /// read from buffer. 2) If buffer is empty, poll consumer to get a new message
 struct PartitionConsumer {
+    /// the ID with which messages from this partition are tracked
+    partition_id: PartitionId,
+    /// the topic of the partition with which this consumer is associated
+    topic: String,
     /// the partition id with which this consumer is associated
     pid: i32,
     /// A buffer to store messages that cannot be timestamped yet
     buffer: Option<SourceMessage<Vec<u8>>>,
     /// The underlying Kafka partition queue
     partition_queue: PartitionQueue<GlueConsumerContext>,
+    /// Whether to copy the headers of each message
+    include_headers: bool,
+    /// Whether to record the type of each message's timestamp
+    include_timestamp_type: bool,
 }
 
 impl PartitionConsumer {
     /// Creates a new partition consumer from underlying Kafka consumer
-    fn new(pid: i32, partition_queue: PartitionQueue<GlueConsumerContext>) -> Self {
+    fn new(
+        partition_id: PartitionId,
+        topic: String,
+        pid: i32,
+        partition_queue: PartitionQueue<GlueConsumerContext>,
+        include_headers: bool,
+        include_timestamp_type: bool,
+    ) -> Self {
         PartitionConsumer {
+            partition_id,
+            topic,
             pid,
             buffer: None,
             partition_queue,
+            include_headers,
+            include_timestamp_type,
         }
     }
 
@@ -758,13 +1434,31 @@ impl PartitionConsumer {
     /// Either reads from the buffer or polls from the consumer
     fn get_next_message(&mut self) -> Result<Option<SourceMessage<Vec<u8>>>, KafkaError> {
         if let Some(message) = self.buffer.take() {
-            assert_eq!(message.partition, PartitionId::Kafka(self.pid));
+            assert_eq!(message.partition, self.partition_id);
             Ok(Some(message))
         } else {
             match self.partition_queue.poll(Duration::from_millis(0)) {
                 Some(Ok(msg)) => {
-                    let result = SourceMessage::from(&msg);
-                    assert_eq!(result.partition, PartitionId::Kafka(self.pid));
+                    assert_eq!((msg.topic(), msg.partition()), (&*self.topic, self.pid));
+                    let mut result = SourceMessage::from(&msg);
+                    result.partition = self.partition_id.clone();
+                    if self.include_headers {
+                        // A message without headers gets an empty list, so
+                        // that every record carries the headers column.
+                        result.headers = Some(
+                            msg.headers()
+                                .map(|headers| {
+                                    (0..headers.count())
+                                        .filter_map(|i| headers.get(i))
+                                        .map(|(k, v)| (k.to_owned(), v.to_vec()))
+                                        .collect()
+                                })
+                                .unwrap_or_default(),
+                        );
+                    }
+                    if self.include_timestamp_type {
+                        result.timestamp_type = Some(timestamp_type(&msg.timestamp()));
+                    }
                     Ok(Some(result))
                 }
                 Some(Err(err)) => Err(err),
@@ -772,11 +1466,6 @@ impl PartitionConsumer {
             }
         }
     }
-
-    /// Return the partition id for this PartitionConsumer
-    fn pid(&self) -> i32 {
-        self.pid
-    }
 }
 
 /// An implementation of [`ConsumerContext`] that unparks the wrapped thread
@@ -825,3 +1514,335 @@ fn has_partition(
     let hash = (source_id.hashed() >> 32) + partition_id as u64;
     (hash % worker_count as u64) == worker_id as u64
 }
+
+// Like `has_partition`, but for sources that read several topics. Partitions
+// with the same ID in different topics are additionally offset by a hash of
+// the topic, so that the partitions of small topics are not all read by the
+// same workers.
+fn has_topic_partition(
+    source_id: GlobalId,
+    worker_id: i32,
+    worker_count: i32,
+    topic: &str,
+    partition_id: i32,
+) -> bool {
+    assert!(worker_id >= 0);
+    assert!(worker_count > worker_id);
+    assert!(partition_id >= 0);
+
+    let hash = (source_id.hashed() >> 32) + (topic.hashed() >> 32) + partition_id as u64;
+    (hash % worker_count as u64) == worker_id as u64
+}
+
+/// Returns the name of the type of a message's timestamp, as reported in the
+/// `timestamp_type` column, or `None` if the message has no timestamp.
+fn timestamp_type(timestamp: &KafkaTimestamp) -> Option<&'static str> {
+    match timestamp {
+        KafkaTimestamp::CreateTime(_) => Some("CreateTime"),
+        KafkaTimestamp::LogAppendTime(_) => Some("LogAppendTime"),
+        KafkaTimestamp::NotAvailable => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use rdkafka::error::RDKafkaErrorCode;
+
+    use super::*;
+
+    #[test]
+    fn test_backoff() {
+        let mut backoff = Backoff::new(Duration::from_millis(100), Duration::from_secs(1));
+        for bound in &[100, 200, 400, 800, 1000, 1000] {
+            assert!(backoff.next_delay() <= Duration::from_millis(*bound));
+        }
+        assert_eq!(backoff.attempts, 6);
+        backoff.reset();
+        assert_eq!(backoff.attempts, 0);
+        assert!(backoff.next_delay() <= Duration::from_millis(100));
+
+        // The bound must not overflow after many attempts.
+        backoff.attempts = u32::MAX;
+        assert!(backoff.next_delay() <= Duration::from_secs(1));
+    }
+
+    /// A broker whose partitions hold messages with the given `(offset,
+    /// timestamp)` pairs, in offset order.
+    struct MockBroker(HashMap<i32, Vec<(i64, i64)>>);
+
+    impl OffsetsForTimes for MockBroker {
+        fn lookup_offsets_for_times(
+            &self,
+            timestamps: TopicPartitionList,
+        ) -> KafkaResult<TopicPartitionList> {
+            let mut offsets = TopicPartitionList::new();
+            for elem in timestamps.elements() {
+                let ts = match elem.offset() {
+                    Offset::Offset(ts) => ts,
+                    o => panic!("unexpected timestamp {:?}", o),
+                };
+                let offset = self.0[&elem.partition()]
+                    .iter()
+                    .find(|(_, msg_ts)| *msg_ts >= ts)
+                    .map_or(Offset::End, |(offset, _)| Offset::Offset(*offset));
+                offsets.add_partition_offset(elem.topic(), elem.partition(), offset)?;
+            }
+            Ok(offsets)
+        }
+    }
+
+    /// A broker whose lookups time out until `failures` more have failed.
+    struct FlakyBroker {
+        broker: MockBroker,
+        failures: Cell<u32>,
+    }
+
+    impl OffsetsForTimes for FlakyBroker {
+        fn lookup_offsets_for_times(
+            &self,
+            timestamps: TopicPartitionList,
+        ) -> KafkaResult<TopicPartitionList> {
+            match self.failures.get() {
+                0 => self.broker.lookup_offsets_for_times(timestamps),
+                n => {
+                    self.failures.set(n - 1);
+                    Err(KafkaError::OffsetFetch(RDKafkaErrorCode::RequestTimedOut))
+                }
+            }
+        }
+    }
+
+    /// A broker with the given topics, and their partition counts.
+    struct MockTopics(Vec<(String, i32)>);
+
+    impl ListTopics for MockTopics {
+        fn list_topics(&self) -> KafkaResult<Vec<(String, i32)>> {
+            Ok(self.0.clone())
+        }
+    }
+
+    #[test]
+    fn test_discover_topics() -> KafkaResult<()> {
+        fn topics(topics: &[(&str, i32)]) -> BTreeMap<String, i32> {
+            topics.iter().map(|(t, n)| (t.to_string(), *n)).collect()
+        }
+
+        let pattern = compile_topic_pattern(r"events\..*");
+        let mut broker = MockTopics(vec![
+            ("events.clicks".into(), 2),
+            ("other".into(), 1),
+            // Only matches of the entire name count.
+            ("old.events.views".into(), 1),
+            ("eventsXviews".into(), 1),
+        ]);
+        assert_eq!(
+            discover_topics(&broker, &pattern)?,
+            topics(&[("events.clicks", 2)])
+        );
+
+        // Topics created while the source is running are discovered, as are
+        // partitions added to known topics.
+        broker.0.push(("events.views".into(), 1));
+        broker.0[0].1 = 3;
+        assert_eq!(
+            discover_topics(&broker, &pattern)?,
+            topics(&[("events.clicks", 3), ("events.views", 1)])
+        );
+
+        // Deleted topics are no longer discovered.
+        broker.0.remove(0);
+        assert_eq!(
+            discover_topics(&broker, &pattern)?,
+            topics(&[("events.views", 1)])
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_diff_topics() -> KafkaResult<()> {
+        // Applies `changes` to `known` the way `update_topics` and
+        // `remove_topic` apply them to a source.
+        fn apply(known: &mut BTreeMap<String, i32>, changes: TopicChanges) {
+            for topic in changes.removed {
+                known.remove(&topic);
+            }
+            known.extend(changes.grown);
+        }
+
+        let pattern = compile_topic_pattern(r"events\..*");
+        let mut broker = MockTopics(vec![("events.clicks".into(), 2), ("other".into(), 1)]);
+        let mut known = BTreeMap::new();
+
+        let changes = diff_topics(&known, discover_topics(&broker, &pattern)?);
+        assert_eq!(
+            changes,
+            TopicChanges {
+                removed: vec![],
+                grown: vec![("events.clicks".into(), 2)],
+            }
+        );
+        apply(&mut known, changes);
+
+        // Rediscovering the same topics changes nothing.
+        let changes = diff_topics(&known, discover_topics(&broker, &pattern)?);
+        assert_eq!(
+            changes,
+            TopicChanges {
+                removed: vec![],
+                grown: vec![],
+            }
+        );
+
+        // The broker gains a matching topic, and a topic that does not
+        // match, while the source is running. Only the matching topic is
+        // picked up, and the known topic is left alone.
+        broker.0.push(("events.views".into(), 1));
+        broker.0.push(("other.views".into(), 1));
+        let changes = diff_topics(&known, discover_topics(&broker, &pattern)?);
+        assert_eq!(
+            changes,
+            TopicChanges {
+                removed: vec![],
+                grown: vec![("events.views".into(), 1)],
+            }
+        );
+        apply(&mut known, changes);
+
+        // New partitions of a known topic are picked up.
+        broker.0[0].1 = 3;
+        let changes = diff_topics(&known, discover_topics(&broker, &pattern)?);
+        assert_eq!(
+            changes,
+            TopicChanges {
+                removed: vec![],
+                grown: vec![("events.clicks".into(), 3)],
+            }
+        );
+        apply(&mut known, changes);
+
+        // A deleted topic stops being read, and the remaining topic keeps its
+        // partitions.
+        broker.0.remove(0);
+        let changes = diff_topics(&known, discover_topics(&broker, &pattern)?);
+        assert_eq!(
+            changes,
+            TopicChanges {
+                removed: vec!["events.clicks".into()],
+                grown: vec![],
+            }
+        );
+        apply(&mut known, changes);
+        assert_eq!(
+            known,
+            vec![("events.views".to_string(), 1)].into_iter().collect()
+        );
+
+        // A topic that is recreated after its deletion is read again.
+        broker.0.push(("events.clicks".into(), 1));
+        let changes = diff_topics(&known, discover_topics(&broker, &pattern)?);
+        assert_eq!(
+            changes,
+            TopicChanges {
+                removed: vec![],
+                grown: vec![("events.clicks".into(), 1)],
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_resolve_start_offset() -> KafkaResult<()> {
+        let broker = MockBroker(
+            vec![
+                // Empty.
+                (0, vec![]),
+                // Entirely before the start timestamp.
+                (1, vec![(0, 100), (1, 200)]),
+                // Entirely after the start timestamp.
+                (2, vec![(0, 1100), (1, 1200)]),
+                // Straddling the start timestamp, with a message exactly at it.
+                (3, vec![(0, 900), (1, 1000), (2, 1000), (3, 1100)]),
+                // Truncated by retention, with gaps from compaction.
+                (4, vec![(40, 500), (43, 999), (47, 1001)]),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let expected = vec![
+            Offset::End,
+            Offset::End,
+            Offset::Offset(0),
+            Offset::Offset(1),
+            Offset::Offset(47),
+        ];
+        for (partition, expected) in expected.into_iter().enumerate() {
+            let partition = i32::try_from(partition).unwrap();
+            assert_eq!(
+                resolve_start_offset(&broker, "topic", partition, 1000)?,
+                expected,
+                "partition {}",
+                partition
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unresolved_partitions() {
+        let broker = FlakyBroker {
+            broker: MockBroker(
+                vec![(0, vec![(0, 900), (1, 1100)]), (1, vec![])]
+                    .into_iter()
+                    .collect(),
+            ),
+            failures: Cell::new(3),
+        };
+        let mut unresolved = UnresolvedPartitions::new();
+        for partition in 0..2 {
+            assert!(resolve_start_offset(&broker, "topic", partition, 1000).is_err());
+            unresolved.defer("topic", partition);
+        }
+        unresolved.defer("other", 0);
+        unresolved.remove_topic("other");
+        assert_eq!(unresolved.len(), 2);
+        assert!(unresolved.next_delay().is_some());
+
+        // Retries are not attempted before they are due.
+        unresolved.next_attempt = Instant::now() + Duration::from_secs(60);
+        assert_eq!(unresolved.retry(&broker, 1000), vec![]);
+        assert_eq!(broker.failures.get(), 1);
+
+        // Partitions whose lookups fail again remain unresolved.
+        unresolved.next_attempt = Instant::now();
+        assert_eq!(
+            unresolved.retry(&broker, 1000),
+            vec![("topic".into(), 1, Offset::End)]
+        );
+        assert_eq!(unresolved.len(), 1);
+        assert!(unresolved.next_delay().is_some());
+
+        unresolved.next_attempt = Instant::now();
+        assert_eq!(
+            unresolved.retry(&broker, 1000),
+            vec![("topic".into(), 0, Offset::Offset(1))]
+        );
+        assert_eq!(unresolved.len(), 0);
+        assert_eq!(unresolved.next_delay(), None);
+    }
+
+    #[test]
+    fn test_timestamp_type() {
+        assert_eq!(
+            timestamp_type(&KafkaTimestamp::CreateTime(1)),
+            Some("CreateTime")
+        );
+        assert_eq!(
+            timestamp_type(&KafkaTimestamp::LogAppendTime(1)),
+            Some("LogAppendTime")
+        );
+        // Messages without a timestamp get a NULL timestamp type.
+        assert_eq!(timestamp_type(&KafkaTimestamp::NotAvailable), None);
+    }
+}
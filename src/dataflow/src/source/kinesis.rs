@@ -7,19 +7,21 @@
 // the Business Source License, use of this software will be governed
 // by the Apache License, Version 2.0.
 
-use std::collections::{HashSet, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::convert::TryInto;
 use std::time::Duration;
 use std::time::Instant;
 
 use anyhow::anyhow;
-use aws_util::kinesis::{get_shard_ids, get_shard_iterator};
+use async_trait::async_trait;
 use futures::executor::block_on;
 use lazy_static::lazy_static;
 use log::error;
 use prometheus::{register_int_gauge_vec, IntGauge, IntGaugeVec};
 use rusoto_core::RusotoError;
-use rusoto_kinesis::{GetRecordsError, GetRecordsInput, GetRecordsOutput, Kinesis, KinesisClient};
+use rusoto_kinesis::{
+    GetRecordsError, GetRecordsInput, GetRecordsOutput, Kinesis, KinesisClient, Shard,
+};
 use timely::scheduling::{Activator, SyncActivator};
 
 use dataflow_types::{
@@ -47,12 +49,137 @@ lazy_static! {
     .expect("Can construct an intgauge for millis_behind_latest");
 }
 
-/// To read all data from a Kinesis stream, we need to continually update
-/// our knowledge of the stream's shards by calling the ListShards API.
+/// The Kinesis API calls needed to discover a stream's shards.
+#[async_trait(?Send)]
+trait ShardClient {
+    /// Lists all of the stream's shards, including closed shards that are
+    /// still within the stream's retention period.
+    async fn list_shards(&self, stream_name: &str) -> Result<Vec<Shard>, anyhow::Error>;
+
+    /// Returns an iterator positioned at the oldest record in the shard.
+    async fn get_shard_iterator(
+        &self,
+        stream_name: &str,
+        shard_id: &str,
+    ) -> Result<Option<String>, anyhow::Error>;
+}
+
+#[async_trait(?Send)]
+impl ShardClient for KinesisClient {
+    async fn list_shards(&self, stream_name: &str) -> Result<Vec<Shard>, anyhow::Error> {
+        aws_util::kinesis::list_shards(self, stream_name).await
+    }
+
+    async fn get_shard_iterator(
+        &self,
+        stream_name: &str,
+        shard_id: &str,
+    ) -> Result<Option<String>, anyhow::Error> {
+        aws_util::kinesis::get_shard_iterator(self, stream_name, shard_id).await
+    }
+}
+
+/// Tracks which of a stream's shards can be read.
 ///
-/// We will call ListShards at most this often to stay under the API rate limit
-/// (100x/sec per stream) and to improve source performance overall.
-const KINESIS_SHARD_REFRESH_RATE: Duration = Duration::from_secs(60);
+/// Resharding closes shards and replaces them with new child shards: a split
+/// produces two children of one parent, while a merge produces one child of
+/// two parents. To preserve the order of records with the same partition key,
+/// a child shard is not read until all of its parents have been read to the
+/// end. Parents that no longer appear in the stream's shard list have aged out
+/// of its retention period, so there is nothing left to read from them.
+#[derive(Debug, Default)]
+struct ShardTracker {
+    /// Shards that are waiting for the listed parents to be read to the end.
+    blocked: HashMap<String, Vec<String>>,
+    /// Shards that are being read.
+    reading: HashSet<String>,
+    /// Shards that have been read to the end.
+    finished: HashSet<String>,
+}
+
+impl ShardTracker {
+    /// The number of shards that have been read from.
+    fn partition_count(&self) -> usize {
+        self.reading.len() + self.finished.len()
+    }
+
+    /// Refreshes the stream's shard list, returning the shards that can now be
+    /// read along with an iterator for each.
+    async fn refresh<C: ShardClient>(
+        &mut self,
+        client: &C,
+        stream_name: &str,
+    ) -> Result<Vec<(String, Option<String>)>, anyhow::Error> {
+        let shards = client.list_shards(stream_name).await?;
+        let listed: HashSet<&str> = shards.iter().map(|s| s.shard_id.as_str()).collect();
+        let mut readable = vec![];
+        for shard in &shards {
+            let shard_id = &shard.shard_id;
+            if self.reading.contains(shard_id)
+                || self.finished.contains(shard_id)
+                || self.blocked.contains_key(shard_id)
+            {
+                continue;
+            }
+            let parents: Vec<String> = shard
+                .parent_shard_id
+                .iter()
+                .chain(shard.adjacent_parent_shard_id.iter())
+                .filter(|p| {
+                    !self.finished.contains(*p)
+                        && (listed.contains(p.as_str()) || self.reading.contains(*p))
+                })
+                .cloned()
+                .collect();
+            if parents.is_empty() {
+                self.reading.insert(shard_id.clone());
+                readable.push(shard_id.clone());
+            } else {
+                self.blocked.insert(shard_id.clone(), parents);
+            }
+        }
+        self.with_iterators(client, stream_name, readable).await
+    }
+
+    /// Records that `shard_id` has been read to the end, returning the child
+    /// shards that can now be read along with an iterator for each.
+    async fn finish<C: ShardClient>(
+        &mut self,
+        client: &C,
+        stream_name: &str,
+        shard_id: &str,
+    ) -> Result<Vec<(String, Option<String>)>, anyhow::Error> {
+        self.reading.remove(shard_id);
+        self.finished.insert(shard_id.to_owned());
+        let mut readable = vec![];
+        for (child, parents) in self.blocked.iter_mut() {
+            parents.retain(|p| p != shard_id);
+            if parents.is_empty() {
+                readable.push(child.clone());
+            }
+        }
+        readable.sort();
+        for child in &readable {
+            self.blocked.remove(child);
+            self.reading.insert(child.clone());
+        }
+        self.with_iterators(client, stream_name, readable).await
+    }
+
+    async fn with_iterators<C: ShardClient>(
+        &self,
+        client: &C,
+        stream_name: &str,
+        shard_ids: Vec<String>,
+    ) -> Result<Vec<(String, Option<String>)>, anyhow::Error> {
+        let mut out = Vec::with_capacity(shard_ids.len());
+        for shard_id in shard_ids {
+            let iterator = client.get_shard_iterator(stream_name, &shard_id).await?;
+            out.push((shard_id, iterator));
+        }
+        Ok(out)
+    }
+}
 
 /// Contains all information necessary to ingest data from Kinesis
 pub struct KinesisSourceInfo {
@@ -68,11 +195,15 @@ pub struct KinesisSourceInfo {
     logger: Option<Logger>,
     /// The name of the stream
     stream_name: String,
-    /// The set of active shards
-    shard_set: HashSet<String>,
+    /// The state of each of the stream's shards
+    shard_tracker: ShardTracker,
     /// A queue representing the next shard to read from. This is necessary
     /// to ensure that all shards are read from uniformly
-    shard_queue: VecDeque<(String, Option<String>)>,
+    shard_queue: VecDeque<(String, String)>,
+    /// How often to call ListShards to discover new shards. We do this at most
+    /// every few seconds to stay under the API rate limit (100x/sec per
+    /// stream) and to improve source performance overall.
+    shard_refresh: Duration,
     /// The time at which we last refreshed metadata
     /// TODO(natacha): this should be moved to timestamper
     last_checked_shards: Instant,
@@ -100,49 +231,69 @@ impl SourceConstructor<Vec<u8>> for KinesisSourceInfo {
             _ => unreachable!(),
         };
 
-        let state = block_on(create_state(
-            kc,
-            &source_name,
-            source_id,
-            consistency_info,
-            logger.clone(),
-        ));
+        let shard_refresh = kc.shard_refresh;
+        let state = block_on(create_state(kc));
         match state {
-            Ok((kinesis_client, stream_name, shard_set, shard_queue)) => Ok(KinesisSourceInfo {
-                name: source_name,
-                id: source_id,
-                is_activated_reader: active,
-                kinesis_client,
-                logger,
-                shard_queue,
-                last_checked_shards: Instant::now(),
-                buffered_messages: VecDeque::new(),
-                shard_set,
-                stream_name,
-                processed_message_count: 0,
-            }),
+            Ok((kinesis_client, stream_name)) => {
+                let mut info = KinesisSourceInfo {
+                    name: source_name,
+                    id: source_id,
+                    is_activated_reader: active,
+                    kinesis_client,
+                    logger,
+                    shard_tracker: ShardTracker::default(),
+                    shard_queue: VecDeque::new(),
+                    shard_refresh,
+                    last_checked_shards: Instant::now(),
+                    buffered_messages: VecDeque::new(),
+                    stream_name,
+                    processed_message_count: 0,
+                };
+                block_on(info.update_shard_information(consistency_info))
+                    .map_err(|e| anyhow!("{}", e))?;
+                Ok(info)
+            }
             Err(e) => Err(anyhow!("{}", e)),
         }
     }
 }
 
 impl KinesisSourceInfo {
+    /// Starts reading from any shards that have appeared since the last
+    /// refresh and whose parents have been read to the end.
     async fn update_shard_information(
         &mut self,
         consistency_info: &mut ConsistencyInfo,
     ) -> Result<(), anyhow::Error> {
-        let new_shards: HashSet<String> = get_shard_ids(&self.kinesis_client, &self.stream_name)
-            .await?
-            .difference(&self.shard_set)
-            .map(|shard_id| shard_id.to_owned())
-            .collect();
-        for shard_id in new_shards {
-            self.shard_set.insert(shard_id.clone());
-            self.shard_queue.push_back((
-                shard_id.clone(),
-                get_shard_iterator(&self.kinesis_client, &self.stream_name, &shard_id).await?,
-            ));
-            let kinesis_id = PartitionId::Kinesis(shard_id);
+        let readable = self
+            .shard_tracker
+            .refresh(&self.kinesis_client, &self.stream_name)
+            .await?;
+        self.start_reading(consistency_info, readable).await
+    }
+
+    /// Records that a shard has been closed and read to the end, and starts
+    /// reading from any of its children that are now readable.
+    async fn finish_shard(
+        &mut self,
+        consistency_info: &mut ConsistencyInfo,
+        shard_id: &str,
+    ) -> Result<(), anyhow::Error> {
+        let readable = self
+            .shard_tracker
+            .finish(&self.kinesis_client, &self.stream_name, shard_id)
+            .await?;
+        self.start_reading(consistency_info, readable).await
+    }
+
+    async fn start_reading(
+        &mut self,
+        consistency_info: &mut ConsistencyInfo,
+        shards: Vec<(String, Option<String>)>,
+    ) -> Result<(), anyhow::Error> {
+        let mut shards: VecDeque<_> = shards.into();
+        while let Some((shard_id, iterator)) = shards.pop_front() {
+            let kinesis_id = PartitionId::Kinesis(shard_id.clone());
             consistency_info.update_partition_metadata(kinesis_id.clone());
             consistency_info.partition_metrics.insert(
                 kinesis_id.clone(),
@@ -153,6 +304,15 @@ impl KinesisSourceInfo {
                     self.logger.clone(),
                 ),
             );
+            match iterator {
+                Some(iterator) => self.shard_queue.push_back((shard_id, iterator)),
+                // A shard without an iterator has no records left to read.
+                None => shards.extend(
+                    self.shard_tracker
+                        .finish(&self.kinesis_client, &self.stream_name, &shard_id)
+                        .await?,
+                ),
+            }
         }
         Ok(())
     }
@@ -218,7 +378,7 @@ impl SourceInfo<Vec<u8>> for KinesisSourceInfo {
     fn get_worker_partition_count(&self) -> i32 {
         if self.is_activated_reader {
             // Kinesis does not support more than i32 partitions
-            self.shard_set.len().try_into().unwrap()
+            self.shard_tracker.partition_count().try_into().unwrap()
         } else {
             0
         }
@@ -245,10 +405,10 @@ impl SourceInfo<Vec<u8>> for KinesisSourceInfo {
         consistency_info: &mut ConsistencyInfo,
         activator: &Activator,
     ) -> Result<NextMessage<Vec<u8>>, anyhow::Error> {
-        assert_eq!(self.shard_queue.len(), self.shard_set.len());
+        assert_eq!(self.shard_queue.len(), self.shard_tracker.reading.len());
 
         //TODO move to timestamper
-        if self.last_checked_shards.elapsed() >= KINESIS_SHARD_REFRESH_RATE {
+        if self.last_checked_shards.elapsed() >= self.shard_refresh {
             if let Err(e) = block_on(self.update_shard_information(consistency_info)) {
                 error!("{:#?}", e);
                 return Err(anyhow::Error::msg(e.to_string()));
@@ -260,73 +420,82 @@ impl SourceInfo<Vec<u8>> for KinesisSourceInfo {
             Ok(NextMessage::Ready(message))
         } else {
             // Rotate through all of a stream's shards, start with a new shard on each activation.
-            if let Some((shard_id, mut shard_iterator)) = self.shard_queue.pop_front() {
-                if let Some(iterator) = &shard_iterator {
-                    let get_records_output = match block_on(self.get_records(&iterator)) {
-                        Ok(output) => {
-                            shard_iterator = output.next_shard_iterator.clone();
-                            if let Some(millis) = output.millis_behind_latest {
-                                let shard_metrics: IntGauge = MILLIS_BEHIND_LATEST
-                                    .with_label_values(&[&self.stream_name, &shard_id]);
-                                shard_metrics.set(millis);
-                            }
-                            output
-                        }
-                        Err(RusotoError::HttpDispatch(e)) => {
-                            // todo@jldlaughlin: Parse this to determine fatal/retriable?
-                            error!("{}", e);
-                            self.shard_queue.push_back((shard_id, shard_iterator));
-                            activator.activate();
-                            // Do not send error message as this would cause source to terminate
-                            return Ok(NextMessage::Pending);
-                        }
-                        Err(RusotoError::Service(GetRecordsError::ExpiredIterator(e))) => {
-                            // todo@jldlaughlin: Will need track source offsets to grab a new iterator.
-                            error!("{}", e);
-                            return Err(anyhow::Error::msg(e));
-                        }
-                        Err(RusotoError::Service(
-                            GetRecordsError::ProvisionedThroughputExceeded(_),
-                        )) => {
-                            self.shard_queue.push_back((shard_id, shard_iterator));
-                            activator.activate();
-                            // Do not send error message as this would cause source to terminate
-                            return Ok(NextMessage::Pending);
-                        }
-                        Err(e) => {
-                            // Fatal service errors:
-                            //  - InvalidArgument
-                            //  - KMSAccessDenied, KMSDisabled, KMSInvalidState, KMSNotFound,
-                            //    KMSOptInRequired, KMSThrottling
-                            //  - ResourceNotFound
-                            //
-                            // Other fatal Rusoto errors:
-                            // - Credentials
-                            // - Validation
-                            // - ParseError
-                            // - Unknown (raw HTTP provided)
-                            // - Blocking
-                            error!("{}", e);
-                            return Err(anyhow!("{}", e));
+            if let Some((shard_id, shard_iterator)) = self.shard_queue.pop_front() {
+                let get_records_output = match block_on(self.get_records(&shard_iterator)) {
+                    Ok(output) => {
+                        if let Some(millis) = output.millis_behind_latest {
+                            let shard_metrics: IntGauge = MILLIS_BEHIND_LATEST
+                                .with_label_values(&[&self.stream_name, &shard_id]);
+                            shard_metrics.set(millis);
                         }
-                    };
+                        output
+                    }
+                    Err(RusotoError::HttpDispatch(e)) => {
+                        // todo@jldlaughlin: Parse this to determine fatal/retriable?
+                        error!("{}", e);
+                        self.shard_queue.push_back((shard_id, shard_iterator));
+                        activator.activate();
+                        // Do not send error message as this would cause source to terminate
+                        return Ok(NextMessage::Pending);
+                    }
+                    Err(RusotoError::Service(GetRecordsError::ExpiredIterator(e))) => {
+                        // todo@jldlaughlin: Will need track source offsets to grab a new iterator.
+                        error!("{}", e);
+                        return Err(anyhow::Error::msg(e));
+                    }
+                    Err(RusotoError::Service(GetRecordsError::ProvisionedThroughputExceeded(
+                        _,
+                    ))) => {
+                        self.shard_queue.push_back((shard_id, shard_iterator));
+                        activator.activate();
+                        // Do not send error message as this would cause source to terminate
+                        return Ok(NextMessage::Pending);
+                    }
+                    Err(e) => {
+                        // Fatal service errors:
+                        //  - InvalidArgument
+                        //  - KMSAccessDenied, KMSDisabled, KMSInvalidState, KMSNotFound,
+                        //    KMSOptInRequired, KMSThrottling
+                        //  - ResourceNotFound
+                        //
+                        // Other fatal Rusoto errors:
+                        // - Credentials
+                        // - Validation
+                        // - ParseError
+                        // - Unknown (raw HTTP provided)
+                        // - Blocking
+                        error!("{}", e);
+                        return Err(anyhow!("{}", e));
+                    }
+                };
 
-                    for record in get_records_output.records {
-                        let data = record.data.as_ref().to_vec();
-                        self.processed_message_count += 1;
-                        let source_message = SourceMessage {
-                            partition: PartitionId::Kinesis(shard_id.clone()),
-                            offset: MzOffset {
-                                //TODO: should MzOffset be modified to be a string?
-                                offset: self.processed_message_count,
-                            },
-                            upstream_time_millis: None,
-                            key: None,
-                            payload: Some(data),
-                        };
-                        self.buffered_messages.push_back(source_message);
+                for record in get_records_output.records {
+                    let data = record.data.as_ref().to_vec();
+                    self.processed_message_count += 1;
+                    let source_message = SourceMessage {
+                        partition: PartitionId::Kinesis(shard_id.clone()),
+                        offset: MzOffset {
+                            //TODO: should MzOffset be modified to be a string?
+                            offset: self.processed_message_count,
+                        },
+                        upstream_time_millis: None,
+                        key: None,
+                        payload: Some(data),
+                        headers: None,
+                        timestamp_type: None,
+                    };
+                    self.buffered_messages.push_back(source_message);
+                }
+                match get_records_output.next_shard_iterator {
+                    Some(iterator) => self.shard_queue.push_back((shard_id, iterator)),
+                    // The shard was closed by resharding and has been read to
+                    // the end, so its children can be read.
+                    None => {
+                        if let Err(e) = block_on(self.finish_shard(consistency_info, &shard_id)) {
+                            error!("{:#?}", e);
+                            return Err(anyhow::Error::msg(e.to_string()));
+                        }
                     }
-                    self.shard_queue.push_back((shard_id, shard_iterator));
                 }
             }
             Ok(match self.buffered_messages.pop_front() {
@@ -341,44 +510,115 @@ impl SourceInfo<Vec<u8>> for KinesisSourceInfo {
     }
 }
 
-/// Creates the necessary data-structures for shard management
+/// Creates the Kinesis client for the source
 // todo: Better error handling here! Not all errors mean we're done/can't progress.
-async fn create_state(
-    c: KinesisSourceConnector,
-    name: &str,
-    id: SourceInstanceId,
-    consistency_info: &mut ConsistencyInfo,
-    logger: Option<Logger>,
-) -> Result<
-    (
-        KinesisClient,
-        String,
-        HashSet<String>,
-        VecDeque<(String, Option<String>)>,
-    ),
-    anyhow::Error,
-> {
+async fn create_state(c: KinesisSourceConnector) -> Result<(KinesisClient, String), anyhow::Error> {
     let kinesis_client = aws_util::kinesis::client(c.aws_info).await?;
+    Ok((kinesis_client, c.stream_name))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+
+    use super::*;
+
+    /// A stream whose shard list can be changed between refreshes.
+    #[derive(Default)]
+    struct MockClient {
+        shards: RefCell<Vec<Shard>>,
+    }
+
+    impl MockClient {
+        fn add_shard(&self, shard_id: &str, parents: &[&str]) {
+            self.shards.borrow_mut().push(Shard {
+                shard_id: shard_id.into(),
+                parent_shard_id: parents.get(0).map(|p| p.to_string()),
+                adjacent_parent_shard_id: parents.get(1).map(|p| p.to_string()),
+                ..Default::default()
+            });
+        }
+
+        fn expire_shard(&self, shard_id: &str) {
+            self.shards.borrow_mut().retain(|s| s.shard_id != shard_id);
+        }
+    }
+
+    #[async_trait(?Send)]
+    impl ShardClient for MockClient {
+        async fn list_shards(&self, _stream_name: &str) -> Result<Vec<Shard>, anyhow::Error> {
+            Ok(self.shards.borrow().clone())
+        }
+
+        async fn get_shard_iterator(
+            &self,
+            _stream_name: &str,
+            shard_id: &str,
+        ) -> Result<Option<String>, anyhow::Error> {
+            Ok(Some(format!("iterator-{}", shard_id)))
+        }
+    }
 
-    let shard_set: HashSet<String> = get_shard_ids(&kinesis_client, &c.stream_name).await?;
-    let mut shard_queue: VecDeque<(String, Option<String>)> = VecDeque::new();
-    for shard_id in &shard_set {
-        let kinesis_id = PartitionId::Kinesis(shard_id.clone());
-        shard_queue.push_back((
-            shard_id.clone(),
-            get_shard_iterator(&kinesis_client, &c.stream_name, shard_id).await?,
-        ));
-        consistency_info.update_partition_metadata(kinesis_id.clone());
-        consistency_info.partition_metrics.insert(
-            kinesis_id.clone(),
-            PartitionMetrics::new(name, id, &kinesis_id.to_string(), logger.clone()),
+    fn shard_ids(shards: Vec<(String, Option<String>)>) -> Vec<String> {
+        let mut ids: Vec<_> = shards.into_iter().map(|(id, _)| id).collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_shard_split() -> Result<(), anyhow::Error> {
+        let client = MockClient::default();
+        let mut tracker = ShardTracker::default();
+        client.add_shard("parent", &[]);
+        client.add_shard("other", &[]);
+        let readable = block_on(tracker.refresh(&client, "stream"))?;
+        assert_eq!(shard_ids(readable), vec!["other", "parent"]);
+
+        // The children of a split are not read until their parent is drained.
+        client.add_shard("child1", &["parent"]);
+        client.add_shard("child2", &["parent"]);
+        assert!(block_on(tracker.refresh(&client, "stream"))?.is_empty());
+        assert!(block_on(tracker.refresh(&client, "stream"))?.is_empty());
+
+        let readable = block_on(tracker.finish(&client, "stream", "parent"))?;
+        assert_eq!(
+            readable,
+            vec![
+                ("child1".into(), Some("iterator-child1".into())),
+                ("child2".into(), Some("iterator-child2".into())),
+            ]
         );
+        assert_eq!(tracker.reading.len(), 3);
+        assert_eq!(tracker.partition_count(), 4);
+
+        // Nothing is read twice.
+        assert!(block_on(tracker.refresh(&client, "stream"))?.is_empty());
+        Ok(())
     }
 
-    Ok((
-        kinesis_client,
-        c.stream_name.clone(),
-        shard_set,
-        shard_queue,
-    ))
+    #[test]
+    fn test_shard_merge() -> Result<(), anyhow::Error> {
+        let client = MockClient::default();
+        let mut tracker = ShardTracker::default();
+        client.add_shard("a", &[]);
+        client.add_shard("b", &[]);
+        client.add_shard("c", &[]);
+        client.add_shard("ab", &["a", "b"]);
+        let readable = block_on(tracker.refresh(&client, "stream"))?;
+        assert_eq!(shard_ids(readable), vec!["a", "b", "c"]);
+
+        // A merged shard waits for both of its parents.
+        assert!(block_on(tracker.finish(&client, "stream", "a"))?.is_empty());
+        let readable = block_on(tracker.finish(&client, "stream", "b"))?;
+        assert_eq!(shard_ids(readable), vec!["ab"]);
+
+        // Parents that have aged out of the stream's retention period do not
+        // block their children.
+        client.add_shard("cd", &["c", "d"]);
+        assert!(block_on(tracker.refresh(&client, "stream"))?.is_empty());
+        client.expire_shard("c");
+        let readable = block_on(tracker.finish(&client, "stream", "c"))?;
+        assert_eq!(shard_ids(readable), vec!["cd"]);
+        Ok(())
+    }
 }
@@ -11,8 +11,9 @@
 
 use mz_avro::types::Value;
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::convert::TryInto;
 use std::fmt::{self, Debug};
 use std::rc::Rc;
@@ -42,7 +43,8 @@ use super::source::util::source;
 use crate::logging::materialized::{Logger, MaterializedEvent};
 use crate::operator::StreamExt;
 use crate::server::{
-    TimestampDataUpdate, TimestampDataUpdates, TimestampMetadataUpdate, TimestampMetadataUpdates,
+    SourceConfigUpdates, TimestampDataUpdate, TimestampDataUpdates, TimestampMetadataUpdate,
+    TimestampMetadataUpdates,
 };
 use crate::CacheMessage;
 
@@ -79,6 +81,8 @@ pub struct SourceConfig<'a, G> {
     pub timestamp_histories: TimestampDataUpdates,
     /// Control-timestamping updates: information about when to start/stop timestamping a source
     pub timestamp_tx: TimestampMetadataUpdates,
+    /// Connector options changed by `ALTER SOURCE`, pending application
+    pub config_updates: SourceConfigUpdates,
     /// A source can use Real-Time consistency timestamping or BYO consistency information.
     pub consistency: Consistency,
     /// Source Type
@@ -104,12 +108,22 @@ where
 {
     /// The record's key (or some empty/default value for sources without the concept of key)
     pub key: K,
+    /// Whether the record arrived with a key, which distinguishes an absent key
+    /// from an empty one
+    pub key_present: bool,
     /// The record's value
     pub value: V,
     /// The position in the source, if such a concept exists (e.g., Kafka offset, file line number)
     pub position: Option<i64>,
     /// The time the record was created in the upstream systsem, as milliseconds since the epoch
     pub upstream_time_millis: Option<i64>,
+    /// The record's topic, if the source reads several topics
+    pub topic: Option<String>,
+    /// The record's headers, if the source was asked to include them
+    pub headers: Option<Vec<(String, Vec<u8>)>>,
+    /// The type of the record's timestamp, if the source was asked to include
+    /// it, or `Some(None)` if the record has no timestamp
+    pub timestamp_type: Option<Option<Cow<'static, str>>>,
 }
 
 /// The data that we send from sources to the decode process
@@ -126,6 +140,21 @@ pub(crate) struct SourceData {
     ///
     /// Currently only applies to Kafka
     pub(crate) upstream_time_millis: Option<i64>,
+
+    /// The topic from which the message was read, if the source reads several
+    ///
+    /// Currently only applies to Kafka
+    pub(crate) topic: Option<String>,
+
+    /// The headers attached to the message, if requested
+    ///
+    /// Currently only applies to Kafka
+    pub(crate) headers: Option<Vec<(String, Vec<u8>)>>,
+
+    /// The type of the message's timestamp, if requested
+    ///
+    /// Currently only applies to Kafka
+    pub(crate) timestamp_type: Option<Option<Cow<'static, str>>>,
 }
 
 impl<K, V> SourceOutput<K, V>
@@ -134,17 +163,26 @@ where
     V: Data,
 {
     /// Build a new SourceOutput
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         key: K,
+        key_present: bool,
         value: V,
         position: Option<i64>,
         upstream_time_millis: Option<i64>,
+        topic: Option<String>,
+        headers: Option<Vec<(String, Vec<u8>)>>,
+        timestamp_type: Option<Option<Cow<'static, str>>>,
     ) -> SourceOutput<K, V> {
         SourceOutput {
             key,
+            key_present,
             value,
             position,
             upstream_time_millis,
+            topic,
+            headers,
+            timestamp_type,
         }
     }
 }
@@ -351,6 +389,20 @@ pub(crate) trait SourceInfo<Out> {
     /// Buffer a message that cannot get timestamped
     fn buffer_message(&mut self, message: SourceMessage<Out>);
 
+    /// Applies the connector options changed by `ALTER SOURCE`, as the
+    /// complete set of options the source now has.
+    fn reconfigure(&mut self, _config_options: &BTreeMap<String, String>) {
+        // Default implementation is to ignore the new options, which take
+        // effect whenever the source is next instantiated.
+    }
+
+    /// Returns the number of bytes that have been read from the upstream
+    /// object, for sources that can measure it
+    fn upstream_bytes_read(&self) -> Option<i64> {
+        // Default implementation is to not measure anything
+        None
+    }
+
     // caching
 
     /// Cache a message
@@ -378,6 +430,9 @@ pub(crate) trait SourceInfo<Out> {
 pub(crate) enum NextMessage<Out> {
     Ready(SourceMessage<Out>),
     Pending,
+    /// The source encountered a transient error, and should not be polled
+    /// again until the specified delay has elapsed.
+    Backoff(Duration),
     Finished,
 }
 
@@ -396,6 +451,11 @@ pub struct SourceMessage<Out> {
     pub key: Option<Vec<u8>>,
     /// Optional payload
     pub payload: Option<Out>,
+    /// Optional headers, in the order they appear in the message
+    pub headers: Option<Vec<(String, Vec<u8>)>>,
+    /// The type of the message's timestamp, if requested, or `Some(None)` if
+    /// the message has no timestamp
+    pub timestamp_type: Option<Option<&'static str>>,
 }
 
 impl<Out> fmt::Debug for SourceMessage<Out> {
@@ -406,6 +466,8 @@ impl<Out> fmt::Debug for SourceMessage<Out> {
             .field("upstream_time_millis", &self.upstream_time_millis)
             .field("key[present]", &self.key.is_some())
             .field("payload[present]", &self.payload.is_some())
+            .field("headers[present]", &self.headers.is_some())
+            .field("timestamp_type", &self.timestamp_type)
             .finish()
     }
 }
@@ -755,11 +817,13 @@ pub struct PartitionMetrics {
     partition_id: String,
     last_offset: i64,
     last_timestamp: i64,
+    last_bytes: i64,
 }
 
 impl PartitionMetrics {
-    /// Record the latest offset ingested high-water mark
-    pub fn record_offset(&mut self, offset: i64, timestamp: i64) {
+    /// Record the latest offset ingested high-water mark, and the number of
+    /// bytes read from the upstream object
+    pub fn record_offset(&mut self, offset: i64, timestamp: i64, bytes: i64) {
         if let Some(logger) = self.logger.as_mut() {
             logger.log(MaterializedEvent::SourceInfo {
                 source_name: self.source_name.clone(),
@@ -767,10 +831,12 @@ impl PartitionMetrics {
                 partition_id: self.partition_id.clone(),
                 offset: offset - self.last_offset,
                 timestamp: timestamp - self.last_timestamp,
+                bytes: bytes - self.last_bytes,
             });
         }
         self.last_offset = offset;
         self.last_timestamp = timestamp;
+        self.last_bytes = bytes;
     }
 
     /// Initialises partition metrics for a given (source_id, partition_id)
@@ -835,6 +901,7 @@ impl PartitionMetrics {
             partition_id: partition_id.to_string(),
             last_offset: 0,
             last_timestamp: 0,
+            last_bytes: 0,
         }
     }
 }
@@ -849,6 +916,7 @@ impl Drop for PartitionMetrics {
                 partition_id: self.partition_id.clone(),
                 offset: -self.last_offset,
                 timestamp: -self.last_timestamp,
+                bytes: -self.last_bytes,
             });
         }
     }
@@ -859,6 +927,31 @@ enum MessageProcessing {
     Active,
     Yielded,
     YieldedWithDelay,
+    YieldedWithBackoff(Duration),
+}
+
+/// Records the code of each error in `errors`, which belong to the source
+/// instance `source_id`, in the `mz_source_errors` log.
+pub(crate) fn log_errors<G>(
+    errors: &timely::dataflow::Stream<G, SourceError>,
+    mut logger: Option<Logger>,
+    source_name: String,
+    source_id: SourceInstanceId,
+) -> timely::dataflow::Stream<G, SourceError>
+where
+    G: Scope,
+{
+    use timely::dataflow::operators::Inspect;
+
+    errors.inspect(move |e| {
+        if let Some(logger) = logger.as_mut() {
+            logger.log(MaterializedEvent::SourceError {
+                source_name: source_name.clone(),
+                source_id,
+                code: e.code(),
+            });
+        }
+    })
 }
 
 /// Creates a source dataflow operator. The type of ExternalSourceConnector determines the
@@ -884,6 +977,7 @@ where
         scope,
         timestamp_histories,
         timestamp_tx,
+        config_updates,
         worker_id,
         worker_count,
         consistency,
@@ -949,6 +1043,10 @@ where
                 return SourceStatus::Done;
             }
 
+            if let Some(config_options) = config_updates.borrow_mut().remove(&id) {
+                source_info.reconfigure(&config_options);
+            }
+
             // Downgrade capability (if possible)
             consistency_info.downgrade_capability(&id, cap, source_info, &timestamp_histories);
 
@@ -964,11 +1062,18 @@ where
                     let ts = consistency_info.find_matching_rt_timestamp();
                     let ts_cap = cap.delayed(&ts);
                     for m in msgs {
+                        // The cache does not record whether a message had a
+                        // key, so empty cached keys are treated as absent.
+                        let key_present = !m.0.is_empty();
                         output.session(&ts_cap).give(Ok(SourceOutput::new(
                             m.0,
+                            key_present,
                             m.1,
                             Some(m.3),
                             None, // upstream timestamps are normalized before they are cached
+                            None,
+                            None,
+                            None,
                         )));
                     }
 
@@ -1039,8 +1144,9 @@ where
                                     ts,
                                     msg_predecessor,
                                 );
-                                // Note: empty and null payload/keys are currently
+                                // Note: empty and null payloads are currently
                                 // treated as the same thing.
+                                let key_present = message.key.is_some();
                                 let key = message.key.unwrap_or_default();
                                 let out = message.payload.unwrap_or_default();
                                 // Entry for partition_metadata is guaranteed to exist as messages
@@ -1054,12 +1160,20 @@ where
                                 bytes_read += key.len() as i64;
                                 bytes_read += out.len().unwrap_or(0) as i64;
                                 let ts_cap = cap.delayed(&ts);
+                                let topic = match &partition {
+                                    PartitionId::KafkaTopic { topic, .. } => Some(topic.clone()),
+                                    _ => None,
+                                };
 
                                 output.session(&ts_cap).give(Ok(SourceOutput::new(
                                     key,
+                                    key_present,
                                     out,
                                     Some(offset.offset),
                                     message.upstream_time_millis,
+                                    topic,
+                                    message.headers,
+                                    message.timestamp_type.map(|t| t.map(Cow::Borrowed)),
                                 )));
 
                                 // Update ingestion metrics
@@ -1087,6 +1201,10 @@ where
                         // There were no new messages, check again after a delay
                         (SourceStatus::Alive, MessageProcessing::YieldedWithDelay)
                     }
+                    Ok(NextMessage::Backoff(delay)) => (
+                        SourceStatus::Alive,
+                        MessageProcessing::YieldedWithBackoff(delay),
+                    ),
                     Ok(NextMessage::Finished) => {
                         if let Consistency::RealTime = consistency_info.source_type {
                             (SourceStatus::Done, MessageProcessing::Stopped)
@@ -1104,12 +1222,13 @@ where
             }
 
             BYTES_READ_COUNTER.inc_by(bytes_read);
+            let upstream_bytes_read = source_info.upstream_bytes_read().unwrap_or(0);
             for (partition, (offset, ts)) in metric_updates {
                 let partition_metrics = consistency_info
                     .partition_metrics
                     .get_mut(&partition)
                     .unwrap();
-                partition_metrics.record_offset(offset.offset, ts as i64);
+                partition_metrics.record_offset(offset.offset, ts as i64, upstream_bytes_read);
             }
 
             // Downgrade capability (if possible) before exiting
@@ -1122,6 +1241,7 @@ where
                 MessageProcessing::YieldedWithDelay => activator.activate_after(
                     Duration::from_millis(consistency_info.downgrade_capability_frequency),
                 ),
+                MessageProcessing::YieldedWithBackoff(delay) => activator.activate_after(delay),
                 _ => (),
             }
 
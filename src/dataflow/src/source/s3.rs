@@ -9,21 +9,24 @@
 
 //! Functionality for creating S3 sources
 
-use std::convert::{From, TryInto};
+use std::convert::From;
 use std::default::Default;
 use std::ops::AddAssign;
 use std::sync::mpsc::{Receiver, SyncSender, TryRecvError};
 
 use anyhow::{anyhow, Error};
+use async_compression::tokio::bufread::GzipDecoder;
 use globset::GlobMatcher;
 use rusoto_s3::{GetObjectRequest, ListObjectsV2Request, S3Client, S3};
 use timely::scheduling::{Activator, SyncActivator};
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, BufReader};
 use tokio::sync::mpsc as tokio_mpsc;
 use tokio::time::{self, Duration};
 
 use aws_util::aws;
-use dataflow_types::{Consistency, DataEncoding, ExternalSourceConnector, MzOffset, S3KeySource};
+use dataflow_types::{
+    Compression, Consistency, DataEncoding, ExternalSourceConnector, MzOffset, S3KeySource,
+};
 use expr::{PartitionId, SourceInstanceId};
 
 use crate::logging::materialized::Logger;
@@ -104,6 +107,7 @@ impl SourceConstructor<Vec<u8>> for S3SourceInfo {
                 dataflow_tx,
                 aws_info.clone(),
                 consumer_activator,
+                s3_conn.compression,
             ));
             for key_source in s3_conn.key_sources {
                 match key_source {
@@ -155,6 +159,7 @@ async fn download_objects_task(
     tx: SyncSender<anyhow::Result<InternalMessage>>,
     aws_info: aws::ConnectInfo,
     activator: SyncActivator,
+    compression: Option<Compression>,
 ) {
     let client = match aws_util::s3::client(aws_info).await {
         Ok(client) => client,
@@ -170,7 +175,15 @@ async fn download_objects_task(
     while let Some(record) = rx.recv().await {
         match record {
             Ok(record) => {
-                download_object(&tx, &activator, &client, record.bucket, record.key).await
+                download_object(
+                    &tx,
+                    &activator,
+                    &client,
+                    record.bucket,
+                    record.key,
+                    &compression,
+                )
+                .await
             }
             Err(e) => tx
                 .send(Err(e))
@@ -265,11 +278,12 @@ async fn download_object(
     client: &S3Client,
     bucket: String,
     key: String,
+    compression: &Option<Compression>,
 ) {
     let obj = match client
         .get_object(GetObjectRequest {
             bucket: bucket.clone(),
-            key,
+            key: key.clone(),
             ..Default::default()
         })
         .await
@@ -282,37 +296,78 @@ async fn download_object(
         }
     };
 
-    if let Some(body) = obj.body {
-        let mut reader = body.into_async_read();
-        // unwrap is safe because content length is not allowed to be negative
-        let mut buf = Vec::with_capacity(obj.content_length.unwrap_or(1024).try_into().unwrap());
-        match reader.read_to_end(&mut buf).await {
-            Ok(_) => {
-                let activate = !buf.is_empty();
-                let mut lines = 0;
-                for line in buf.split(|b| *b == b'\n').map(|s| s.to_vec()) {
-                    if let Err(e) = tx.send(Ok(InternalMessage {
-                        bucket: bucket.clone(),
-                        record: line,
-                    })) {
-                        log::debug!("unable to send read line on stream: {}", e);
-                        break;
-                    } else {
-                        lines += 1;
-                    }
+    let body = match obj.body {
+        Some(body) => body,
+        None => {
+            log::warn!("get object response had no body");
+            return;
+        }
+    };
+
+    // Decompress the object as it streams in, so that large objects need not
+    // fit in memory.
+    let compression = match compression {
+        Some(compression) => compression.clone(),
+        None => detect_compression(&key, obj.content_encoding.as_deref()),
+    };
+    let reader = BufReader::new(body.into_async_read());
+    let reader: Box<dyn AsyncBufRead + Send + Unpin> = match compression {
+        Compression::Gzip => {
+            let mut decoder = GzipDecoder::new(reader);
+            decoder.multiple_members(true);
+            Box::new(BufReader::new(decoder))
+        }
+        Compression::None => Box::new(reader),
+    };
+
+    let mut lines = reader.split(b'\n');
+    let mut sent = 0;
+    loop {
+        match lines.next_segment().await {
+            Ok(Some(line)) => {
+                if let Err(e) = tx.send(Ok(InternalMessage {
+                    bucket: bucket.clone(),
+                    record: line,
+                })) {
+                    log::debug!("unable to send read line on stream: {}", e);
+                    break;
                 }
-                log::trace!("sent {} lines to reader", lines);
-                if activate {
+                sent += 1;
+                // Wake the reader periodically, so that it drains the channel
+                // before it fills up.
+                if sent % 1_000 == 0 {
                     activator.activate().expect("s3 reader activation failed");
                 }
             }
+            Ok(None) => break,
             Err(e) => {
                 tx.send(Err(anyhow!("Unable to read object: {}", e)))
                     .unwrap_or_else(|e| log::debug!("unable to send error on stream: {}", e));
+                break;
             }
         }
+    }
+    log::trace!("sent {} lines to reader", sent);
+    if sent > 0 {
+        activator.activate().expect("s3 reader activation failed");
+    }
+}
+
+/// Determines whether an object is compressed from the suffix of its key and
+/// its `Content-Encoding` metadata.
+fn detect_compression(key: &str, content_encoding: Option<&str>) -> Compression {
+    let gzip_encoded = content_encoding
+        .map(|encoding| {
+            encoding.split(',').any(|encoding| {
+                let encoding = encoding.trim();
+                encoding.eq_ignore_ascii_case("gzip") || encoding.eq_ignore_ascii_case("x-gzip")
+            })
+        })
+        .unwrap_or(false);
+    if gzip_encoded || key.ends_with(".gz") {
+        Compression::Gzip
     } else {
-        log::warn!("get object response had no body");
+        Compression::None
     }
 }
 
@@ -363,6 +418,8 @@ impl SourceInfo<Vec<u8>> for S3SourceInfo {
                     upstream_time_millis: None,
                     key: None,
                     payload: Some(record),
+                    headers: None,
+                    timestamp_type: None,
                 }))
             }
             Ok(Err(e)) => {
@@ -483,4 +540,20 @@ mod test {
         assert_eq!(&find_prefix(r"class/\[ab]/**"), "class/[ab]/");
         assert_eq!(&find_prefix(r"alt/\{a,b}/**"), "alt/{a,b}/");
     }
+
+    #[test]
+    fn compression_detection() {
+        assert_eq!(detect_compression("a.csv", None), Compression::None);
+        assert_eq!(detect_compression("a.csv.gz", None), Compression::Gzip);
+        assert_eq!(detect_compression("a.csv", Some("gzip")), Compression::Gzip);
+        assert_eq!(
+            detect_compression("a.csv", Some("aws-chunked, GZIP")),
+            Compression::Gzip
+        );
+        assert_eq!(
+            detect_compression("a.csv", Some("identity")),
+            Compression::None
+        );
+        assert_eq!(detect_compression("a.gzip", None), Compression::None);
+    }
 }
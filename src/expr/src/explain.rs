@@ -20,16 +20,24 @@
 //!   * Collections of columns are written as ranges where possible,
 //!     e.g. "#2..#5".
 //!
+//! Memory estimates for the arrangements that a plan maintains are derived
+//! from the widths of the arranged columns' types and from an upper bound on
+//! the number of arranged rows. The number of rows is known only for
+//! collections that derive from constants or whose type has an empty key, so
+//! most estimates over sources and tables are unknown.
+//!
 //! It's important to avoid trailing whitespace everywhere, as plans may be
 //! printed in contexts where trailing whitespace is unacceptable, like
 //! sqllogictest files.
 
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::fmt;
 use std::iter;
+use std::mem::size_of;
 
 use ore::str::StrExt;
-use repr::RelationType;
+use repr::{ColumnType, Diff, RelationType, Row, Timestamp};
 
 use crate::{ExprHumanizer, Id, JoinImplementation, LocalId, MirRelationExpr, RowSetFinishing};
 
@@ -57,12 +65,25 @@ pub struct Explanation<'a> {
     chain: usize,
 }
 
+/// An estimate of the memory used by the arrangements of an expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoryEstimate {
+    /// The arrangements hold at most `rows` rows each, in at most `bytes`
+    /// bytes in total.
+    Known { rows: usize, bytes: usize },
+    /// The number of arranged rows or their width is not known.
+    Unknown,
+}
+
 #[derive(Debug)]
 pub struct ExplanationNode<'a> {
     /// The expression being explained.
     pub expr: &'a MirRelationExpr,
     /// The type of the expression, if desired.
     pub typ: Option<RelationType>,
+    /// The estimated size of the arrangements the expression maintains, if
+    /// desired and if the expression maintains any.
+    pub memory: Option<MemoryEstimate>,
     /// The ID of the linear chain to which this node belongs.
     pub chain: usize,
 }
@@ -156,6 +177,7 @@ impl<'a> Explanation<'a> {
             explanation.nodes.push(ExplanationNode {
                 expr,
                 typ: None,
+                memory: None,
                 chain: explanation.chain,
             });
             explanation
@@ -205,6 +227,84 @@ impl<'a> Explanation<'a> {
         }
     }
 
+    /// Attach estimates of the memory used by arrangements into the
+    /// explanation.
+    ///
+    /// Only expressions that maintain arrangements are annotated: `ArrangeBy`,
+    /// which includes the arranged inputs of joins, `Reduce`, `TopK`, and
+    /// `Threshold`.
+    pub fn explain_memory(&mut self) {
+        use MirRelationExpr::*;
+
+        let mut cardinalities = HashMap::new();
+        if let Some(root) = self.nodes.last() {
+            cardinality(root.expr, &mut HashMap::new(), &mut cardinalities);
+        }
+        let rows_of = |expr: &MirRelationExpr| cardinalities[&(expr as *const MirRelationExpr)];
+
+        for node in &mut self.nodes {
+            // Each arrangement is described by the widths of its keys and
+            // values.
+            let (rows, arrangements) = match node.expr {
+                ArrangeBy { input, keys } => {
+                    let typ = input.typ();
+                    let arrangements = keys
+                        .iter()
+                        .map(|key| {
+                            let key_types = key.iter().map(|e| e.typ(&typ)).collect::<Vec<_>>();
+                            (row_width(&key_types), row_width(&typ.column_types))
+                        })
+                        .collect::<Vec<_>>();
+                    (rows_of(input), arrangements)
+                }
+                Reduce { group_key, .. } => {
+                    let typ = node.expr.typ();
+                    let (key_types, value_types) = typ.column_types.split_at(group_key.len());
+                    (
+                        rows_of(node.expr),
+                        vec![(row_width(key_types), row_width(value_types))],
+                    )
+                }
+                TopK {
+                    input, group_key, ..
+                } => {
+                    let typ = input.typ();
+                    let key_types = group_key
+                        .iter()
+                        .map(|c| typ.column_types[*c].clone())
+                        .collect::<Vec<_>>();
+                    (
+                        rows_of(input),
+                        vec![(row_width(&key_types), row_width(&typ.column_types))],
+                    )
+                }
+                Threshold { input } => {
+                    let typ = input.typ();
+                    (
+                        rows_of(input),
+                        vec![(row_width(&typ.column_types), Some(0))],
+                    )
+                }
+                _ => continue,
+            };
+            let bytes = arrangements
+                .into_iter()
+                .map(|(key_width, value_width)| {
+                    let record_width = 2 * size_of::<Row>()
+                        + key_width?
+                        + value_width?
+                        + size_of::<Timestamp>()
+                        + size_of::<Diff>();
+                    rows?.checked_mul(record_width)
+                })
+                .try_fold(0_usize, |total, bytes| total.checked_add(bytes?));
+            node.memory = Some(match (rows, bytes) {
+                (Some(rows), Some(bytes)) => MemoryEstimate::Known { rows, bytes },
+                _ => MemoryEstimate::Unknown,
+            });
+        }
+    }
+
     /// Attach a `RowSetFinishing` to the explanation.
     pub fn explain_row_set_finishing(&mut self, finishing: RowSetFinishing) {
         self.finishing = Some(finishing);
@@ -305,6 +405,7 @@ impl<'a> Explanation<'a> {
             Reduce {
                 group_key,
                 aggregates,
+                hash_aggregate,
                 ..
             } => {
                 if aggregates.is_empty() {
@@ -323,6 +424,9 @@ impl<'a> Explanation<'a> {
                         writeln!(f, "| | agg {}", agg)?;
                     }
                 }
+                if *hash_aggregate {
+                    writeln!(f, "| | strategy = hash")?;
+                }
             }
             TopK {
                 group_key,
@@ -366,6 +470,14 @@ impl<'a> Explanation<'a> {
             )?,
         }
 
+        match node.memory {
+            Some(MemoryEstimate::Known { rows, bytes }) => {
+                writeln!(f, "| | memory = {} bytes ({} rows)", bytes, rows)?
+            }
+            Some(MemoryEstimate::Unknown) => writeln!(f, "| | memory = unknown")?,
+            None => (),
+        }
+
         if let Some(RelationType { column_types, keys }) = &node.typ {
             let column_types: Vec<_> = column_types
                 .iter()
@@ -446,6 +558,94 @@ impl<'a> Explanation<'a> {
     }
 }
 
+/// Computes an upper bound on the number of rows in `expr` and in each of its
+/// subexpressions, recording the bounds in `out` by address.
+///
+/// `locals` records the bounds for the values of enclosing lets.
+fn cardinality(
+    expr: &MirRelationExpr,
+    locals: &mut HashMap<LocalId, Option<usize>>,
+    out: &mut HashMap<*const MirRelationExpr, Option<usize>>,
+) -> Option<usize> {
+    use MirRelationExpr::*;
+
+    let rows = match expr {
+        Constant { rows: Ok(rows), .. } => Some(
+            rows.iter()
+                .map(|(_row, diff)| usize::try_from(*diff).unwrap_or(0))
+                .sum(),
+        ),
+        Constant { rows: Err(_), .. } => Some(0),
+        Get {
+            id: Id::Local(id), ..
+        } => locals.get(id).copied().flatten(),
+        Get {
+            id: Id::Global(_), ..
+        } => None,
+        Let { id, value, body } => {
+            let value_rows = cardinality(value, locals, out);
+            locals.insert(*id, value_rows);
+            cardinality(body, locals, out)
+        }
+        // These expressions never produce more rows than their input.
+        Project { input, .. }
+        | Map { input, .. }
+        | Filter { input, .. }
+        | Reduce { input, .. }
+        | Negate { input }
+        | Threshold { input }
+        | ArrangeBy { input, .. } => cardinality(input, locals, out),
+        FlatMap { input, .. } => {
+            cardinality(input, locals, out);
+            None
+        }
+        TopK {
+            input,
+            group_key,
+            limit,
+            ..
+        } => {
+            let input_rows = cardinality(input, locals, out);
+            match limit {
+                Some(limit) if group_key.is_empty() => {
+                    Some(input_rows.map_or(*limit, |rows| rows.min(*limit)))
+                }
+                _ => input_rows,
+            }
+        }
+        Join { inputs, .. } => inputs
+            .iter()
+            .map(|input| cardinality(input, locals, out))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .try_fold(1_usize, |product, rows| product.checked_mul(rows?)),
+        Union { base, inputs } => iter::once(&**base)
+            .chain(inputs)
+            .map(|input| cardinality(input, locals, out))
+            .collect::<Vec<_>>()
+            .into_iter()
+            .try_fold(0_usize, |sum, rows| sum.checked_add(rows?)),
+    };
+
+    // A collection with an empty key has at most one row.
+    let rows = if expr.typ().keys.iter().any(|key| key.is_empty()) {
+        Some(rows.map_or(1, |rows| rows.min(1)))
+    } else {
+        rows
+    };
+    out.insert(expr as *const MirRelationExpr, rows);
+    rows
+}
+
+/// Returns the number of bytes required by a row of the specified column
+/// types, or `None` if the width of any of the columns varies.
+fn row_width(column_types: &[ColumnType]) -> Option<usize> {
+    column_types
+        .iter()
+        .map(|typ| repr::fixed_datum_size(&typ.scalar_type))
+        .sum()
+}
+
 /// Creates a type whose [`fmt::Display`] implementation outputs each item in
 /// `iter` separated by `separator`.
 pub fn separated<'a, I>(separator: &'a str, iter: I) -> impl fmt::Display + 'a
@@ -123,12 +123,14 @@ impl fmt::Display for SourceInstanceId {
 
 /// Unique identifier for each part of a whole source.
 ///     Kafka -> partition
+///     Kafka (topic pattern) -> topic and partition
 ///     Kinesis -> shard
 ///     File -> only one
 ///     S3 -> bucket
 #[derive(Clone, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum PartitionId {
     Kafka(i32),
+    KafkaTopic { topic: String, partition: i32 },
     Kinesis(String),
     File,
     S3 { bucket: String },
@@ -138,6 +140,7 @@ impl fmt::Display for PartitionId {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
             PartitionId::Kafka(id) => write!(f, "{}", id.to_string()),
+            PartitionId::KafkaTopic { topic, partition } => write!(f, "{}-{}", topic, partition),
             PartitionId::S3 { bucket } => write!(f, "{}", bucket),
             _ => write!(f, "0"),
         }
@@ -148,6 +151,7 @@ impl PartitionId {
     pub fn kafka_id(&self) -> Option<i32> {
         match self {
             PartitionId::Kafka(id) => Some(*id),
+            PartitionId::KafkaTopic { partition, .. } => Some(*partition),
             _ => None,
         }
     }
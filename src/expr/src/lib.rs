@@ -26,13 +26,16 @@ pub mod explain;
 
 pub use id::{GlobalId, Id, LocalId, PartitionId, SourceInstanceId};
 pub use linear::MapFilterProject;
-pub use relation::func::{AggregateFunc, TableFunc};
+pub use relation::func::{
+    decimal_sum_from_parts, decimal_sum_parts, AggregateFunc, LagLeadType, TableFunc,
+};
 pub use relation::func::{AnalyzedRegex, CaptureGroupDesc};
 pub use relation::join_input_mapper::JoinInputMapper;
 pub use relation::{
     compare_columns, AggregateExpr, ColumnOrder, IdGen, JoinImplementation, MirRelationExpr,
     RowSetFinishing,
 };
+pub use scalar::func::jsonpath::{JsonPath, JsonPathError};
 pub use scalar::func::{BinaryFunc, NullaryFunc, UnaryFunc, VariadicFunc};
 pub use scalar::{like_pattern, EvalError, MirScalarExpr};
 
@@ -9,6 +9,7 @@
 
 #![allow(missing_docs)]
 
+use std::cmp::Ordering;
 use std::fmt;
 use std::fs;
 use std::iter;
@@ -20,14 +21,21 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 
 use ore::cast::CastFrom;
+use repr::adt::array::ArrayDimension;
 use repr::adt::decimal::{Significand, MAX_DECIMAL_PRECISION};
+use repr::adt::interval::Interval;
 use repr::adt::regex::Regex as ReprRegex;
 use repr::{
-    CachedRecordIter, ColumnType, Datum, Diff, RelationType, Row, RowArena, RowPacker, ScalarType,
+    CachedRecordIter, ColumnName, ColumnType, Datum, Diff, RelationType, Row, RowArena, RowPacker,
+    ScalarType,
 };
 
 use crate::id::GlobalId;
+use crate::relation::{compare_columns, ColumnOrder};
 use crate::scalar::func::jsonb_stringify;
+use crate::scalar::func::jsonpath::JsonPath;
+use crate::scalar::func::{add_timestamp_interval, add_timestamptz_interval};
+use crate::scalar::EvalError;
 
 // TODO(jamii) be careful about overflow in sum/avg
 // see https://timely.zulipchat.com/#narrow/stream/186635-engineering/topic/additional.20work/near/163507435
@@ -328,19 +336,52 @@ where
     }
 }
 
-fn sum_decimal<'a, I>(datums: I) -> Datum<'a>
+fn sum_decimal<'a, I>(datums: I) -> Result<Datum<'a>, EvalError>
 where
     I: IntoIterator<Item = Datum<'a>>,
 {
     let mut datums = datums.into_iter().filter(|d| !d.is_null()).peekable();
     if datums.peek().is_none() {
-        Datum::Null
+        Ok(Datum::Null)
     } else {
-        let sum: Significand = datums.map(|d| d.unwrap_decimal()).sum();
-        Datum::from(sum)
+        let (mut high, mut low) = (0, 0);
+        for d in datums {
+            let (h, l) = decimal_sum_parts(d.unwrap_decimal());
+            high += h;
+            low += l;
+        }
+        Ok(Datum::from(decimal_sum_from_parts(high, low)?))
     }
 }
 
+/// The low 64 bits of a 128-bit significand.
+const DECIMAL_SUM_LOW_MASK: i128 = (1 << 64) - 1;
+
+/// Splits the significand `d` of a summand of a numeric sum into high and low
+/// parts.
+///
+/// The sum of the significands of a group can exceed 128 bits even when the
+/// sum itself does not, if some of them are later cancelled out by negative
+/// values or retractions. Summing the two parts separately instead cannot
+/// overflow for fewer than 2^63 summands, and the parts can be added and
+/// subtracted in any order. Only their recombination by
+/// [`decimal_sum_from_parts`] is checked for overflow.
+pub fn decimal_sum_parts(d: Significand) -> (i128, i128) {
+    let d = d.as_i128();
+    (d >> 64, d & DECIMAL_SUM_LOW_MASK)
+}
+
+/// Recombines the summed `high` and `low` parts of
+/// [`decimal_sum_parts`] into the significand of the sum, or returns
+/// [`EvalError::NumericFieldOverflow`] if it does not fit in 128 bits.
+pub fn decimal_sum_from_parts(high: i128, low: i128) -> Result<Significand, EvalError> {
+    high.checked_add(low >> 64)
+        .and_then(|high| high.checked_mul(1 << 64))
+        .and_then(|high| high.checked_add(low & DECIMAL_SUM_LOW_MASK))
+        .map(Significand::new)
+        .ok_or(EvalError::NumericFieldOverflow)
+}
+
 fn count<'a, I>(datums: I) -> Datum<'a>
 where
     I: IntoIterator<Item = Datum<'a>>,
@@ -385,6 +426,246 @@ where
     Datum::List(datum.unwrap_list())
 }
 
+/// Sorts the non-null lists in `datums` by their fields as specified by
+/// `order_by`, and returns the fields of each.
+///
+/// The sort is stable, so lists that compare equal retain the order in which
+/// they were presented.
+fn sort_aggregate_lists<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Vec<Vec<Datum<'a>>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut rows: Vec<Vec<Datum>> = datums
+        .into_iter()
+        .filter(|d| !d.is_null())
+        .map(|d| d.unwrap_list().iter().collect())
+        .collect();
+    rows.sort_by(|left, right| compare_columns(order_by, left, right, || Ordering::Equal));
+    rows
+}
+
+/// Like [`sort_aggregate_lists`], but returns only the first field of each
+/// list.
+fn order_aggregate_datums<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Vec<Datum<'a>>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    sort_aggregate_lists(datums, order_by)
+        .into_iter()
+        .map(|row| row[0])
+        .collect()
+}
+
+fn array_agg<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let elems = order_aggregate_datums(datums, order_by);
+    if elems.is_empty() {
+        return Datum::Null;
+    }
+    temp_storage.make_datum(|packer| {
+        packer
+            .push_array(
+                &[ArrayDimension {
+                    lower_bound: 1,
+                    length: elems.len(),
+                }],
+                elems,
+            )
+            .expect("array_agg produces a valid one-dimensional array")
+    })
+}
+
+fn string_agg<'a, I>(datums: I, temp_storage: &'a RowArena, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut s: Option<String> = None;
+    for value in order_aggregate_datums(datums, order_by) {
+        let mut fields = value.unwrap_list().iter();
+        let (value, sep) = (fields.next().unwrap(), fields.next().unwrap());
+        if value.is_null() {
+            continue;
+        }
+        match &mut s {
+            None => s = Some(value.unwrap_str().to_owned()),
+            Some(s) => {
+                if !sep.is_null() {
+                    s.push_str(sep.unwrap_str());
+                }
+                s.push_str(value.unwrap_str());
+            }
+        }
+    }
+    match s {
+        None => Datum::Null,
+        Some(s) => Datum::String(temp_storage.push_string(s)),
+    }
+}
+
+/// Sorts the lists of a fraction and a value in `datums` as specified by
+/// `order_by`, and returns the fraction and the sorted non-null values.
+///
+/// Returns `None` if there are no non-null values, or if the fraction is null
+/// or not between 0 and 1. The planner ensures that the fraction is the same
+/// in every list.
+fn percentile_values<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Option<(f64, Vec<Datum<'a>>)>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let mut rows = sort_aggregate_lists(datums, order_by)
+        .into_iter()
+        .filter(|row| !row[1].is_null())
+        .peekable();
+    let fraction = match rows.peek()?[0] {
+        Datum::Null => return None,
+        fraction => fraction.unwrap_float64(),
+    };
+    if !(0.0..=1.0).contains(&fraction) {
+        return None;
+    }
+    Some((fraction, rows.map(|row| row[1]).collect()))
+}
+
+fn percentile_cont<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (fraction, values) = match percentile_values(datums, order_by) {
+        Some(percentile) => percentile,
+        None => return Datum::Null,
+    };
+    // Interpolate linearly between the two values that surround the
+    // requested position, as PostgreSQL does.
+    let position = fraction * (values.len() - 1) as f64;
+    let (lower, upper) = (position.floor(), position.ceil());
+    let lower_value = values[lower as usize].unwrap_float64();
+    if lower == upper {
+        return Datum::from(lower_value);
+    }
+    let upper_value = values[upper as usize].unwrap_float64();
+    Datum::from(lower_value + (upper_value - lower_value) * (position - lower))
+}
+
+fn percentile_disc<'a, I>(datums: I, order_by: &[ColumnOrder]) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let (fraction, values) = match percentile_values(datums, order_by) {
+        Some(percentile) => percentile,
+        None => return Datum::Null,
+    };
+    // Choose the first value whose position in the ordering is greater than
+    // or equal to the fraction.
+    let position = (fraction * values.len() as f64).ceil() as usize;
+    values[position.max(1) - 1]
+}
+
+/// Evaluates the `lag` and `lead` functions in `funcs` over the rows of a
+/// window partition, ordered as specified by `order_by`.
+///
+/// Each list in `datums` begins with a record of the columns of a row,
+/// followed by a record of the value, offset, and default of each function,
+/// and then by the values by which the rows are ordered. The result is a list
+/// of records, one per row, of the row's columns followed by the result of
+/// each function for that row.
+fn lag_lead<'a, I>(
+    datums: I,
+    temp_storage: &'a RowArena,
+    funcs: &[LagLeadType],
+    order_by: &[ColumnOrder],
+) -> Datum<'a>
+where
+    I: IntoIterator<Item = Datum<'a>>,
+{
+    let rows = sort_aggregate_lists(datums, order_by);
+    if rows.is_empty() {
+        return Datum::Null;
+    }
+    // The value, offset, and default of the `i`th function for `row`.
+    fn args<'b>(row: &[Datum<'b>], i: usize) -> Vec<Datum<'b>> {
+        row[1 + i].unwrap_list().iter().collect()
+    }
+    temp_storage.make_datum(|packer| {
+        packer.push_list_with(|packer| {
+            for (position, row) in rows.iter().enumerate() {
+                packer.push_list_with(|packer| {
+                    packer.push(row[0]);
+                    for (i, func) in funcs.iter().enumerate() {
+                        let current = args(row, i);
+                        // As in PostgreSQL, a null offset produces null, and a
+                        // negative offset looks in the opposite direction.
+                        let result = match current[1] {
+                            Datum::Null => Datum::Null,
+                            offset => {
+                                let offset = i64::from(offset.unwrap_int32());
+                                let target = match func {
+                                    LagLeadType::Lag => position as i64 - offset,
+                                    LagLeadType::Lead => position as i64 + offset,
+                                };
+                                if target >= 0 && (target as usize) < rows.len() {
+                                    args(&rows[target as usize], i)[0]
+                                } else {
+                                    current[2]
+                                }
+                            }
+                        };
+                        packer.push(result);
+                    }
+                });
+            }
+        })
+    })
+}
+
+/// Returns the type of the list produced by [`lag_lead`] for the records of
+/// type `input_type`.
+fn lag_lead_output_type(funcs: &[LagLeadType], input_type: &ScalarType) -> ScalarType {
+    let fields = match input_type {
+        ScalarType::Record { fields, .. } => fields,
+        _ => unreachable!("lag and lead input must be a record"),
+    };
+    let mut output_fields = vec![(ColumnName::from("row"), fields[0].1.clone())];
+    for (i, func) in funcs.iter().enumerate() {
+        let value_type = match &fields[1 + i].1.scalar_type {
+            ScalarType::Record { fields, .. } => fields[0].1.scalar_type.clone(),
+            _ => unreachable!("lag and lead arguments must be a record"),
+        };
+        output_fields.push((
+            ColumnName::from(format!("{}{}", func, i + 1)),
+            value_type.nullable(true),
+        ));
+    }
+    ScalarType::List {
+        element_type: Box::new(ScalarType::Record {
+            fields: output_fields,
+            custom_oid: None,
+            custom_name: None,
+        }),
+        custom_oid: None,
+    }
+}
+
+/// The direction in which a [`AggregateFunc::LagLead`] function looks for the
+/// row whose value it produces.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub enum LagLeadType {
+    /// Looks at preceding rows.
+    Lag,
+    /// Looks at following rows.
+    Lead,
+}
+
+impl fmt::Display for LagLeadType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LagLeadType::Lag => f.write_str("lag"),
+            LagLeadType::Lead => f.write_str("lead"),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum AggregateFunc {
     MaxInt32,
@@ -421,6 +702,38 @@ pub enum AggregateFunc {
     /// layer, this function filters out `Datum::Null`, for consistency with
     /// the other aggregate functions.
     JsonbAgg,
+    /// Accumulates the first elements of `Datum::List`s into an array, ordered
+    /// by the lists' remaining elements as specified by `order_by`.
+    ArrayAgg {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Concatenates the first elements of `Datum::List`s, which are lists of a
+    /// string value and its delimiter, ordered by the lists' remaining elements
+    /// as specified by `order_by`.
+    StringAgg {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Computes the continuous percentile of the second elements of
+    /// `Datum::List`s, which are `Float64`s, ordered as specified by
+    /// `order_by`. The first element of each list is the fraction of the
+    /// percentile.
+    PercentileCont {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `PercentileCont`, but chooses the first of the ordered values at or
+    /// after the fraction, rather than interpolating between them, and thus
+    /// accepts values of any type.
+    PercentileDisc {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Computes the `lag` and `lead` window functions in `funcs` over a window
+    /// partition, ordered as specified by `order_by`. Produces a list of
+    /// records of each row in the partition and the results of the functions
+    /// for that row, which the planner unnests to recover the rows.
+    LagLead {
+        funcs: Vec<LagLeadType>,
+        order_by: Vec<ColumnOrder>,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -429,11 +742,11 @@ pub enum AggregateFunc {
 }
 
 impl AggregateFunc {
-    pub fn eval<'a, I>(&self, datums: I, temp_storage: &'a RowArena) -> Datum<'a>
+    pub fn eval<'a, I>(&self, datums: I, temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError>
     where
         I: IntoIterator<Item = Datum<'a>>,
     {
-        match self {
+        Ok(match self {
             AggregateFunc::MaxInt32 => max_int32(datums),
             AggregateFunc::MaxInt64 => max_int64(datums),
             AggregateFunc::MaxFloat32 => max_float32(datums),
@@ -458,13 +771,20 @@ impl AggregateFunc {
             AggregateFunc::SumInt64 => sum_int64(datums),
             AggregateFunc::SumFloat32 => sum_float32(datums),
             AggregateFunc::SumFloat64 => sum_float64(datums),
-            AggregateFunc::SumDecimal => sum_decimal(datums),
+            AggregateFunc::SumDecimal => sum_decimal(datums)?,
             AggregateFunc::Count => count(datums),
             AggregateFunc::Any => any(datums),
             AggregateFunc::All => all(datums),
             AggregateFunc::JsonbAgg => jsonb_agg(datums, temp_storage),
+            AggregateFunc::ArrayAgg { order_by } => array_agg(datums, temp_storage, order_by),
+            AggregateFunc::StringAgg { order_by } => string_agg(datums, temp_storage, order_by),
+            AggregateFunc::PercentileCont { order_by } => percentile_cont(datums, order_by),
+            AggregateFunc::PercentileDisc { order_by } => percentile_disc(datums, order_by),
+            AggregateFunc::LagLead { funcs, order_by } => {
+                lag_lead(datums, temp_storage, funcs, order_by)
+            }
             AggregateFunc::Dummy => Datum::Dummy,
-        }
+        })
     }
 
     /// Returns the output of the aggregation function when applied on an empty
@@ -501,6 +821,21 @@ impl AggregateFunc {
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg => ScalarType::Jsonb,
+            AggregateFunc::ArrayAgg { .. } => match input_type.scalar_type {
+                ScalarType::Record { fields, .. } => {
+                    ScalarType::Array(Box::new(fields[0].1.scalar_type.clone()))
+                }
+                _ => unreachable!("array_agg input must be a record"),
+            },
+            AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::PercentileCont { .. } => ScalarType::Float64,
+            AggregateFunc::PercentileDisc { .. } => match input_type.scalar_type {
+                ScalarType::Record { fields, .. } => fields[1].1.scalar_type.clone(),
+                _ => unreachable!("percentile_disc input must be a record"),
+            },
+            AggregateFunc::LagLead { funcs, .. } => {
+                lag_lead_output_type(funcs, &input_type.scalar_type)
+            }
             AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => ScalarType::Decimal(MAX_DECIMAL_PRECISION, 0),
             _ => input_type.scalar_type,
@@ -509,6 +844,13 @@ impl AggregateFunc {
         // null in the presence of null inputs.
         let nullable = match self {
             AggregateFunc::Count => false,
+            // Every input may be filtered out or, for string_agg and the
+            // percentiles, have a null value.
+            AggregateFunc::ArrayAgg { .. }
+            | AggregateFunc::StringAgg { .. }
+            | AggregateFunc::PercentileCont { .. }
+            | AggregateFunc::PercentileDisc { .. }
+            | AggregateFunc::LagLead { .. } => true,
             _ => input_type.nullable,
         };
         scalar_type.nullable(nullable)
@@ -531,6 +873,14 @@ fn jsonb_each<'a>(a: Datum<'a>, temp_storage: &'a RowArena, stringify: bool) ->
     }
 }
 
+fn jsonb_path_query<'a>(a: Datum<'a>, path: &JsonPath) -> Vec<(Row, Diff)> {
+    let mut row_packer = RowPacker::new();
+    path.query(a)
+        .into_iter()
+        .map(move |item| (row_packer.pack(&[item]), 1))
+        .collect()
+}
+
 fn jsonb_object_keys<'a>(a: Datum<'a>) -> Vec<(Row, Diff)> {
     let mut row_packer = RowPacker::new();
     match a {
@@ -591,6 +941,43 @@ fn generate_series_int64(start: Datum, stop: Datum) -> Vec<(Row, Diff)> {
         .collect()
 }
 
+/// Generates the timestamps from `start` through `stop`, inclusive, in
+/// increments of `step`, where `add` adds an interval to a timestamp.
+///
+/// Like PostgreSQL, each timestamp is computed by adding `step` to the previous
+/// one, so steps that include months saturate at the end of shorter months
+/// from then on. A negative `step` produces a descending series.
+fn generate_series_timestamp<'a>(
+    start: Datum<'a>,
+    stop: Datum<'a>,
+    step: Datum<'a>,
+    add: fn(Datum<'a>, Datum<'a>) -> Datum<'a>,
+) -> Result<Vec<(Row, Diff)>, EvalError> {
+    let ascending = match step.unwrap_interval().cmp(&Interval::default()) {
+        Ordering::Equal => {
+            return Err(EvalError::InvalidParameterValue(
+                "step size cannot equal zero".into(),
+            ))
+        }
+        Ordering::Greater => true,
+        Ordering::Less => false,
+    };
+    let mut row_packer = RowPacker::new();
+    let mut rows = vec![];
+    let mut current = start;
+    while (ascending && current <= stop) || (!ascending && current >= stop) {
+        rows.push((row_packer.pack(&[current]), 1));
+        let next = add(current, step);
+        // A step like `1 month -29 days` is positive, but can move backwards
+        // from the end of a month. Stop rather than generating rows forever.
+        if (ascending && next <= current) || (!ascending && next >= current) {
+            break;
+        }
+        current = next;
+    }
+    Ok(rows)
+}
+
 fn unnest_array(a: Datum) -> Vec<(Row, Diff)> {
     let mut row_packer = RowPacker::new();
 
@@ -610,6 +997,23 @@ fn unnest_list(a: Datum) -> Vec<(Row, Diff)> {
         .collect()
 }
 
+/// Appends to each of `rows` its 1-based position among `rows`, counting a row
+/// with a multiplicity of `n` as `n` consecutive rows.
+fn with_ordinality(rows: Vec<(Row, Diff)>) -> Vec<(Row, Diff)> {
+    let mut row_packer = RowPacker::new();
+    let mut ordinality = 0;
+    let mut out = vec![];
+    for (row, diff) in rows {
+        for _ in 0..diff {
+            ordinality += 1;
+            row_packer.extend_by_row(&row);
+            row_packer.push(Datum::Int64(ordinality));
+            out.push((row_packer.finish_and_reuse(), 1));
+        }
+    }
+    out
+}
+
 impl fmt::Display for AggregateFunc {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -642,11 +1046,52 @@ impl fmt::Display for AggregateFunc {
             AggregateFunc::Any => f.write_str("any"),
             AggregateFunc::All => f.write_str("all"),
             AggregateFunc::JsonbAgg => f.write_str("jsonb_agg"),
+            AggregateFunc::ArrayAgg { order_by } => {
+                f.write_str("array_agg")?;
+                fmt_order_by(f, order_by)
+            }
+            AggregateFunc::StringAgg { order_by } => {
+                f.write_str("string_agg")?;
+                fmt_order_by(f, order_by)
+            }
+            AggregateFunc::PercentileCont { order_by } => {
+                f.write_str("percentile_cont")?;
+                fmt_order_by(f, order_by)
+            }
+            AggregateFunc::PercentileDisc { order_by } => {
+                f.write_str("percentile_disc")?;
+                fmt_order_by(f, order_by)
+            }
+            AggregateFunc::LagLead { funcs, order_by } => {
+                f.write_str("lag_lead[funcs=(")?;
+                for (i, func) in funcs.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{}", func)?;
+                }
+                f.write_str(")]")?;
+                fmt_order_by(f, order_by)
+            }
             AggregateFunc::Dummy => f.write_str("dummy"),
         }
     }
 }
 
+fn fmt_order_by(f: &mut fmt::Formatter, order_by: &[ColumnOrder]) -> fmt::Result {
+    if order_by.is_empty() {
+        return Ok(());
+    }
+    f.write_str("[order_by=(")?;
+    for (i, order) in order_by.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{}", order)?;
+    }
+    f.write_str(")]")
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub struct CaptureGroupDesc {
     pub index: u32,
@@ -733,11 +1178,13 @@ pub enum TableFunc {
     JsonbArrayElements {
         stringify: bool,
     },
+    JsonbPathQuery(JsonPath),
     RegexpExtract(AnalyzedRegex),
     CsvExtract(usize),
     GenerateSeriesInt32,
     GenerateSeriesInt64,
-    // TODO(justin): should also possibly have GenerateSeriesTimestamp{,Tz}.
+    GenerateSeriesTimestamp,
+    GenerateSeriesTimestampTz,
     Repeat,
     ReadCachedData {
         source: GlobalId,
@@ -749,6 +1196,9 @@ pub enum TableFunc {
     UnnestList {
         el_typ: ScalarType,
     },
+    /// The wrapped function, with a trailing `bigint` column that numbers its
+    /// output rows, as with `WITH ORDINALITY`.
+    WithOrdinality(Box<TableFunc>),
 }
 
 impl TableFunc {
@@ -756,22 +1206,32 @@ impl TableFunc {
         &'a self,
         datums: Vec<Datum<'a>>,
         temp_storage: &'a RowArena,
-    ) -> Vec<(Row, Diff)> {
+    ) -> Result<Vec<(Row, Diff)>, EvalError> {
         if self.empty_on_null_input() {
             if datums.iter().any(|d| d.is_null()) {
-                return vec![];
+                return Ok(vec![]);
             }
         }
-        match self {
+        Ok(match self {
             TableFunc::JsonbEach { stringify } => jsonb_each(datums[0], temp_storage, *stringify),
             TableFunc::JsonbObjectKeys => jsonb_object_keys(datums[0]),
             TableFunc::JsonbArrayElements { stringify } => {
                 jsonb_array_elements(datums[0], temp_storage, *stringify)
             }
+            TableFunc::JsonbPathQuery(path) => jsonb_path_query(datums[0], path),
             TableFunc::RegexpExtract(a) => regexp_extract(datums[0], a).into_iter().collect(),
             TableFunc::CsvExtract(n_cols) => csv_extract(datums[0], *n_cols).into_iter().collect(),
             TableFunc::GenerateSeriesInt32 => generate_series_int32(datums[0], datums[1]),
             TableFunc::GenerateSeriesInt64 => generate_series_int64(datums[0], datums[1]),
+            TableFunc::GenerateSeriesTimestamp => {
+                generate_series_timestamp(datums[0], datums[1], datums[2], add_timestamp_interval)?
+            }
+            TableFunc::GenerateSeriesTimestampTz => generate_series_timestamp(
+                datums[0],
+                datums[1],
+                datums[2],
+                add_timestamptz_interval,
+            )?,
             TableFunc::Repeat => repeat(datums[0]),
             TableFunc::ReadCachedData {
                 source,
@@ -798,7 +1258,8 @@ impl TableFunc {
             }
             TableFunc::UnnestArray { .. } => unnest_array(datums[0]),
             TableFunc::UnnestList { .. } => unnest_list(datums[0]),
-        }
+            TableFunc::WithOrdinality(func) => with_ordinality(func.eval(datums, temp_storage)?),
+        })
     }
 
     pub fn output_type(&self) -> RelationType {
@@ -818,6 +1279,7 @@ impl TableFunc {
             TableFunc::JsonbArrayElements { stringify: false } => {
                 vec![ScalarType::Jsonb.nullable(false)]
             }
+            TableFunc::JsonbPathQuery(_) => vec![ScalarType::Jsonb.nullable(false)],
             TableFunc::RegexpExtract(a) => a
                 .capture_groups_iter()
                 .map(|cg| ScalarType::String.nullable(cg.nullable))
@@ -827,6 +1289,8 @@ impl TableFunc {
                 .collect(),
             TableFunc::GenerateSeriesInt32 => vec![ScalarType::Int32.nullable(false)],
             TableFunc::GenerateSeriesInt64 => vec![ScalarType::Int64.nullable(false)],
+            TableFunc::GenerateSeriesTimestamp => vec![ScalarType::Timestamp.nullable(false)],
+            TableFunc::GenerateSeriesTimestampTz => vec![ScalarType::TimestampTz.nullable(false)],
             TableFunc::Repeat => vec![],
             TableFunc::ReadCachedData { .. } => vec![
                 ScalarType::String.nullable(true),
@@ -836,6 +1300,11 @@ impl TableFunc {
             ],
             TableFunc::UnnestArray { el_typ } => vec![el_typ.clone().nullable(true)],
             TableFunc::UnnestList { el_typ } => vec![el_typ.clone().nullable(true)],
+            TableFunc::WithOrdinality(func) => {
+                let mut typ = func.output_type().column_types;
+                typ.push(ScalarType::Int64.nullable(false));
+                typ
+            }
         })
     }
 
@@ -844,14 +1313,18 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => 2,
             TableFunc::JsonbObjectKeys => 1,
             TableFunc::JsonbArrayElements { .. } => 1,
+            TableFunc::JsonbPathQuery(_) => 1,
             TableFunc::RegexpExtract(a) => a.capture_groups_len(),
             TableFunc::CsvExtract(n_cols) => *n_cols,
             TableFunc::GenerateSeriesInt32 => 1,
             TableFunc::GenerateSeriesInt64 => 1,
+            TableFunc::GenerateSeriesTimestamp => 1,
+            TableFunc::GenerateSeriesTimestampTz => 1,
             TableFunc::Repeat => 0,
             TableFunc::ReadCachedData { .. } => 4,
             TableFunc::UnnestArray { .. } => 1,
             TableFunc::UnnestList { .. } => 1,
+            TableFunc::WithOrdinality(func) => func.output_arity() + 1,
         }
     }
 
@@ -865,14 +1338,18 @@ impl TableFunc {
             TableFunc::JsonbEach { .. }
             | TableFunc::JsonbObjectKeys
             | TableFunc::JsonbArrayElements { .. }
+            | TableFunc::JsonbPathQuery(_)
             | TableFunc::GenerateSeriesInt32
             | TableFunc::GenerateSeriesInt64
+            | TableFunc::GenerateSeriesTimestamp
+            | TableFunc::GenerateSeriesTimestampTz
             | TableFunc::RegexpExtract(_)
             | TableFunc::CsvExtract(_)
             | TableFunc::Repeat
             | TableFunc::ReadCachedData { .. }
             | TableFunc::UnnestArray { .. }
             | TableFunc::UnnestList { .. } => true,
+            TableFunc::WithOrdinality(func) => func.empty_on_null_input(),
         }
     }
 
@@ -884,14 +1361,18 @@ impl TableFunc {
             TableFunc::JsonbEach { .. } => true,
             TableFunc::JsonbObjectKeys => true,
             TableFunc::JsonbArrayElements { .. } => true,
+            TableFunc::JsonbPathQuery(_) => true,
             TableFunc::RegexpExtract(_) => true,
             TableFunc::CsvExtract(_) => true,
             TableFunc::GenerateSeriesInt32 => true,
             TableFunc::GenerateSeriesInt64 => true,
+            TableFunc::GenerateSeriesTimestamp => true,
+            TableFunc::GenerateSeriesTimestampTz => true,
             TableFunc::Repeat => false,
             TableFunc::ReadCachedData { .. } => true,
             TableFunc::UnnestArray { .. } => true,
             TableFunc::UnnestList { .. } => true,
+            TableFunc::WithOrdinality(func) => func.preserves_monotonicity(),
         }
     }
 }
@@ -902,16 +1383,22 @@ impl fmt::Display for TableFunc {
             TableFunc::JsonbEach { .. } => f.write_str("jsonb_each"),
             TableFunc::JsonbObjectKeys => f.write_str("jsonb_object_keys"),
             TableFunc::JsonbArrayElements { .. } => f.write_str("jsonb_array_elements"),
+            TableFunc::JsonbPathQuery(path) => {
+                write!(f, "jsonb_path_query(_, {:?})", path.as_str())
+            }
             TableFunc::RegexpExtract(a) => write!(f, "regexp_extract({:?}, _)", a.0),
             TableFunc::CsvExtract(n_cols) => write!(f, "csv_extract({}, _)", n_cols),
             TableFunc::GenerateSeriesInt32 => f.write_str("generate_series"),
             TableFunc::GenerateSeriesInt64 => f.write_str("generate_series"),
+            TableFunc::GenerateSeriesTimestamp => f.write_str("generate_series"),
+            TableFunc::GenerateSeriesTimestampTz => f.write_str("generate_series"),
             TableFunc::Repeat => f.write_str("repeat"),
             TableFunc::ReadCachedData { source, .. } => {
                 write!(f, "internal_read_cached_data({})", source)
             }
             TableFunc::UnnestArray { .. } => f.write_str("unnest_array"),
             TableFunc::UnnestList { .. } => f.write_str("unnest_list"),
+            TableFunc::WithOrdinality(func) => write!(f, "{}_with_ordinality", func),
         }
     }
 }
@@ -157,6 +157,9 @@ pub enum MirRelationExpr {
         monotonic: bool,
         /// User hint: expected number of values per group key. Used to optimize physical rendering.
         expected_group_size: Option<usize>,
+        /// User hint: accumulate accumulable aggregates in a hash map of the
+        /// distinct groups, rather than in sorted arrangements of the input.
+        hash_aggregate: bool,
     },
     /// Groups and orders within each group, limiting output.
     ///
@@ -609,6 +612,7 @@ impl MirRelationExpr {
             aggregates,
             monotonic: false,
             expected_group_size,
+            hash_aggregate: false,
         }
     }
 
@@ -1150,6 +1154,10 @@ pub struct ColumnOrder {
     pub column: usize,
     /// Whether to sort in descending order.
     pub desc: bool,
+    /// Whether to sort nulls after all other values, rather than before them.
+    /// Like PostgreSQL, nulls sort last in ascending orders and first in
+    /// descending orders unless requested otherwise.
+    pub nulls_last: bool,
 }
 
 impl fmt::Display for ColumnOrder {
@@ -1159,7 +1167,20 @@ impl fmt::Display for ColumnOrder {
             "#{} {}",
             self.column.to_string(),
             if self.desc { "desc" } else { "asc" }
-        )
+        )?;
+        // Only mention the placement of nulls when it is not the default.
+        if self.nulls_last == self.desc {
+            write!(
+                f,
+                " {}",
+                if self.nulls_last {
+                    "nulls_last"
+                } else {
+                    "nulls_first"
+                }
+            )?;
+        }
+        Ok(())
     }
 }
 
@@ -1306,10 +1327,14 @@ where
 {
     for order in order {
         let (lval, rval) = (&left[order.column], &right[order.column]);
-        let cmp = if order.desc {
-            rval.cmp(&lval)
-        } else {
-            lval.cmp(&rval)
+        let cmp = match (lval, rval) {
+            (Datum::Null, Datum::Null) => Ordering::Equal,
+            (Datum::Null, _) if order.nulls_last => Ordering::Greater,
+            (Datum::Null, _) => Ordering::Less,
+            (_, Datum::Null) if order.nulls_last => Ordering::Less,
+            (_, Datum::Null) => Ordering::Greater,
+            _ if order.desc => rval.cmp(&lval),
+            _ => lval.cmp(&rval),
         };
         if cmp != Ordering::Equal {
             return cmp;
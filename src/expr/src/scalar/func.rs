@@ -9,10 +9,14 @@
 
 use std::borrow::Cow;
 use std::cmp::{self, Ordering};
+use std::collections::BTreeMap;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::iter;
+use std::mem;
 use std::str;
+use std::thread;
+use std::time;
 
 use chrono::{
     DateTime, Datelike, Duration, NaiveDate, NaiveDateTime, NaiveTime, Offset, TimeZone, Timelike,
@@ -27,6 +31,8 @@ use regex::RegexBuilder;
 use serde::{Deserialize, Serialize};
 use sha1::Sha1;
 use sha2::{Sha224, Sha256, Sha384, Sha512};
+use unicode_normalization::UnicodeNormalization;
+use uuid::Uuid;
 
 use ore::collections::CollectionExt;
 use ore::fmt::FormatBuffer;
@@ -41,10 +47,12 @@ use repr::adt::jsonb::JsonbRef;
 use repr::adt::regex::Regex;
 use repr::{strconv, ColumnName, ColumnType, Datum, RowArena, RowPacker, ScalarType};
 
-use crate::scalar::func::format::DateTimeFormat;
+use crate::scalar::func::format::{DateTimeFormat, NumericFormat};
+use crate::scalar::func::jsonpath::JsonPath;
 use crate::{like_pattern, EvalError, MirScalarExpr};
 
 mod format;
+pub mod jsonpath;
 
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum NullaryFunc {
@@ -379,16 +387,38 @@ fn cast_string_to_float64<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
         .err_into()
 }
 
-fn cast_string_to_decimal<'a>(a: Datum<'a>, scale: u8) -> Result<Datum<'a>, EvalError> {
-    strconv::parse_decimal(a.unwrap_str())
-        .map(|d| {
-            Datum::from(match d.scale().cmp(&scale) {
-                Ordering::Less => d.significand() * 10_i128.pow(u32::from(scale - d.scale())),
-                Ordering::Equal => d.significand(),
-                Ordering::Greater => d.significand() / 10_i128.pow(u32::from(d.scale() - scale)),
-            })
-        })
-        .err_into()
+fn cast_string_to_decimal<'a>(
+    a: Datum<'a>,
+    precision: u8,
+    scale: u8,
+) -> Result<Datum<'a>, EvalError> {
+    let d = strconv::parse_decimal(a.unwrap_str())?;
+    // Like PostgreSQL, round excess fractional digits half away from zero, then
+    // reject values whose integer part has more digits than the precision
+    // allows.
+    let significand = match d.scale().cmp(&scale) {
+        Ordering::Less => 10_i128
+            .checked_pow(u32::from(scale - d.scale()))
+            .and_then(|factor| d.significand().checked_mul(factor)),
+        Ordering::Equal => Some(d.significand()),
+        Ordering::Greater => {
+            let factor = 10_i128.pow(u32::from(d.scale() - scale));
+            Some(d.round(i64::from(scale)).significand() / factor)
+        }
+    };
+    // A precision of zero means that the precision is unconstrained.
+    let precision = if precision == 0 {
+        MAX_DECIMAL_PRECISION
+    } else {
+        precision
+    };
+    let bound = 10_i128.pow(u32::from(precision));
+    match significand {
+        Some(significand) if -bound < significand && significand < bound => {
+            Ok(Datum::from(significand))
+        }
+        _ => Err(EvalError::NumericFieldOverflow),
+    }
 }
 
 fn cast_string_to_date<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
@@ -502,16 +532,13 @@ fn cast_time_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a
     Datum::String(temp_storage.push_string(buf))
 }
 
-fn cast_time_to_interval<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+fn cast_time_to_interval<'a>(a: Datum<'a>) -> Datum<'a> {
     let t = a.unwrap_time();
-    match Interval::new(
+    Datum::Interval(Interval::new(
         0,
-        t.num_seconds_from_midnight() as i64,
-        t.nanosecond() as i64,
-    ) {
-        Ok(i) => Ok(Datum::Interval(i)),
-        Err(_) => Err(EvalError::IntervalOutOfRange),
-    }
+        0,
+        i64::from(t.num_seconds_from_midnight()) * 1_000_000 + i64::from(t.nanosecond()) / 1_000,
+    ))
 }
 
 fn cast_timestamp_to_date<'a>(a: Datum<'a>) -> Datum<'a> {
@@ -549,24 +576,15 @@ fn cast_interval_to_string<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datu
 }
 
 fn cast_interval_to_time<'a>(a: Datum<'a>) -> Datum<'a> {
-    let mut i = a.unwrap_interval();
-
-    // Negative durations have their HH::MM::SS.NS values subtracted from 1 day.
-    if i.duration < 0 {
-        i = Interval::new(0, 86400, 0)
-            .unwrap()
-            .checked_add(
-                &Interval::new(0, i.dur_as_secs() % (24 * 60 * 60), i.nanoseconds() as i64)
-                    .unwrap(),
-            )
-            .unwrap();
-    }
-
-    Datum::Time(NaiveTime::from_hms_nano(
-        i.hours() as u32,
-        i.minutes() as u32,
-        i.seconds() as u32,
-        i.nanoseconds() as u32,
+    // Like PostgreSQL, ignore the interval's months and days, and subtract
+    // the HH:MM:SS.US values of negative intervals from 1 day.
+    let micros = a
+        .unwrap_interval()
+        .micros
+        .rem_euclid(24 * 60 * 60 * 1_000_000);
+    Datum::Time(NaiveTime::from_num_seconds_from_midnight(
+        (micros / 1_000_000) as u32,
+        (micros % 1_000_000 * 1_000) as u32,
     ))
 }
 
@@ -683,7 +701,7 @@ fn add_float64<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_float64() + b.unwrap_float64())
 }
 
-fn add_timestamp_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+pub(crate) fn add_timestamp_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     let dt = a.unwrap_timestamp();
     Datum::Timestamp(match b {
         Datum::Interval(i) => {
@@ -694,7 +712,7 @@ fn add_timestamp_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     })
 }
 
-fn add_timestamptz_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
+pub(crate) fn add_timestamptz_interval<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     let dt = a.unwrap_timestamptz().naive_utc();
 
     let new_ndt = match b {
@@ -828,6 +846,48 @@ where
     }
 }
 
+// Like PostgreSQL, negative integers are counted in their two's complement
+// representation.
+fn bit_count_int32<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_int32().count_ones() as i32)
+}
+
+fn bit_count_int64<'a>(a: Datum<'a>) -> Datum<'a> {
+    Datum::from(a.unwrap_int64().count_ones() as i32)
+}
+
+fn bit_count_bytes<'a>(a: Datum<'a>) -> Datum<'a> {
+    let count: i64 = a
+        .unwrap_bytes()
+        .iter()
+        .map(|b| i64::from(b.count_ones()))
+        .sum();
+    Datum::from(count)
+}
+
+/// Returns the index of the byte of `bytes` that contains bit `n`, and the
+/// position of that bit within the byte.
+///
+/// As in PostgreSQL, bits are numbered from the least significant bit of the
+/// first byte.
+fn bit_position(bytes: &[u8], n: i64) -> Result<(usize, u32), EvalError> {
+    let len = bytes.len() as i64 * 8;
+    if n < 0 || n >= len {
+        return Err(EvalError::InvalidParameterValue(format!(
+            "index {} out of valid range, 0..{}",
+            n,
+            len - 1
+        )));
+    }
+    Ok(((n / 8) as usize, (n % 8) as u32))
+}
+
+fn get_bit_bytes<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let bytes = a.unwrap_bytes();
+    let (byte, bit) = bit_position(bytes, b.unwrap_int64())?;
+    Ok(Datum::from(i32::from((bytes[byte] >> bit) & 1)))
+}
+
 fn byte_length<'a, B>(bytes: B) -> Result<Datum<'a>, EvalError>
 where
     B: AsRef<[u8]>,
@@ -956,16 +1016,27 @@ fn sub_decimal<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_decimal() - b.unwrap_decimal())
 }
 
-fn sub_timestamp<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
-    Datum::from(a.unwrap_timestamp() - b.unwrap_timestamp())
+/// Converts the difference between two timestamps into an interval of days
+/// and microseconds, as PostgreSQL does.
+fn justified_interval_from_duration<'a>(d: chrono::Duration) -> Result<Datum<'a>, EvalError> {
+    d.num_microseconds()
+        .and_then(|micros| Interval::new(0, 0, micros).justify_hours())
+        .ok_or(EvalError::IntervalOutOfRange)
+        .map(Datum::from)
+}
+
+fn sub_timestamp<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    justified_interval_from_duration(a.unwrap_timestamp() - b.unwrap_timestamp())
 }
 
-fn sub_timestamptz<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
-    Datum::from(a.unwrap_timestamptz() - b.unwrap_timestamptz())
+fn sub_timestamptz<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    justified_interval_from_duration(a.unwrap_timestamptz() - b.unwrap_timestamptz())
 }
 
 fn sub_date<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
-    Datum::from(a.unwrap_date() - b.unwrap_date())
+    // The difference between any two dates fits in an `i32` number of days.
+    let days = (a.unwrap_date() - b.unwrap_date()).num_days();
+    Datum::from(Interval::new(0, days as i32, 0))
 }
 
 fn sub_time<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
@@ -1156,6 +1227,27 @@ pub fn neg_interval<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(-a.unwrap_interval())
 }
 
+fn justify_days<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_interval()
+        .justify_days()
+        .ok_or(EvalError::IntervalOutOfRange)
+        .map(Datum::from)
+}
+
+fn justify_hours<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_interval()
+        .justify_hours()
+        .ok_or(EvalError::IntervalOutOfRange)
+        .map(Datum::from)
+}
+
+fn justify_interval<'a>(a: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    a.unwrap_interval()
+        .justify_interval()
+        .ok_or(EvalError::IntervalOutOfRange)
+        .map(Datum::from)
+}
+
 fn sqrt_float32<'a>(a: Datum<'a>) -> Result<Datum, EvalError> {
     let x = a.unwrap_float32();
     if x < 0.0 {
@@ -1217,6 +1309,29 @@ fn to_char_timestamptz<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowAren
     Datum::String(temp_storage.push_string(fmt.render(a.unwrap_timestamptz())))
 }
 
+fn to_char_decimal<'a>(
+    significand: i128,
+    scale: u8,
+    format: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let fmt = NumericFormat::compile(format.unwrap_str())?;
+    Ok(Datum::String(
+        temp_storage.push_string(fmt.render_decimal(significand, scale)),
+    ))
+}
+
+fn to_char_float64<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let fmt = NumericFormat::compile(b.unwrap_str())?;
+    Ok(Datum::String(
+        temp_storage.push_string(fmt.render_f64(a.unwrap_float64())),
+    ))
+}
+
 fn jsonb_get_int64<'a>(
     a: Datum<'a>,
     b: Datum<'a>,
@@ -1384,6 +1499,15 @@ fn jsonb_contains_jsonb<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     contains(a, b, true).into()
 }
 
+fn jsonb_path_query_first<'a>(a: Datum<'a>, path: &JsonPath) -> Datum<'a> {
+    path.query(a).into_iter().next().unwrap_or(Datum::Null)
+}
+
+fn jsonb_path_query_first_dynamic<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let path = JsonPath::parse(b.unwrap_str())?;
+    Ok(jsonb_path_query_first(a, &path))
+}
+
 fn jsonb_concat<'a>(a: Datum<'a>, b: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     match (a, b) {
         (Datum::Map(dict_a), Datum::Map(dict_b)) => {
@@ -2040,6 +2164,160 @@ fn jsonb_pretty<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     Datum::String(temp_storage.push_string(buf))
 }
 
+/// What `jsonb_set_path` does with the new value at the end of the path.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum JsonbPathOp {
+    /// Replace an existing value, as `jsonb_set` with `create_if_missing`
+    /// false.
+    Replace,
+    /// Replace an existing value or create a missing one, as `jsonb_set`.
+    Create,
+    /// Insert before an existing array element, as `jsonb_insert`.
+    InsertBefore,
+    /// Insert after an existing array element, as `jsonb_insert` with
+    /// `insert_after` true.
+    InsertAfter,
+}
+
+fn jsonb_set<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    let op = if datums[3].unwrap_bool() {
+        JsonbPathOp::Create
+    } else {
+        JsonbPathOp::Replace
+    };
+    jsonb_set_path(datums[0], datums[1], datums[2], op, temp_storage)
+}
+
+fn jsonb_insert<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let op = if datums[3].unwrap_bool() {
+        JsonbPathOp::InsertAfter
+    } else {
+        JsonbPathOp::InsertBefore
+    };
+    jsonb_set_path(datums[0], datums[1], datums[2], op, temp_storage)
+}
+
+/// Sets the value at `path` in `target` to `new_value`, following
+/// PostgreSQL's `setPath`. Objects and arrays that the path does not lead
+/// into, or that it leads past, are left unchanged.
+fn jsonb_set_path<'a>(
+    target: Datum<'a>,
+    path: Datum<'a>,
+    new_value: Datum<'a>,
+    op: JsonbPathOp,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let path = path.unwrap_array();
+    if path.dims().len() > 1 {
+        return Err(EvalError::InvalidParameterValue(
+            "wrong number of array subscripts".into(),
+        ));
+    }
+    let path = path.elements().iter().collect::<Vec<_>>();
+    let is_empty = match target {
+        Datum::Map(dict) => dict.iter().next().is_none(),
+        Datum::List(list) => list.iter().next().is_none(),
+        _ => {
+            return Err(EvalError::InvalidParameterValue(
+                "cannot set path in scalar".into(),
+            ))
+        }
+    };
+    if path.is_empty() || (is_empty && op == JsonbPathOp::Replace) {
+        return Ok(target);
+    }
+    set_path(target, &path, 0, new_value, op, temp_storage)
+}
+
+fn set_path<'a>(
+    target: Datum<'a>,
+    path: &[Datum<'a>],
+    level: usize,
+    new_value: Datum<'a>,
+    op: JsonbPathOp,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let key = match path[level] {
+        Datum::Null => {
+            return Err(EvalError::InvalidParameterValue(format!(
+                "path element at position {} is null",
+                level + 1
+            )))
+        }
+        key => key.unwrap_str(),
+    };
+    let last = level == path.len() - 1;
+    let create = last && op != JsonbPathOp::Replace;
+    match target {
+        Datum::Map(dict) => {
+            let mut pairs = dict.iter().collect::<Vec<_>>();
+            match pairs.binary_search_by(|(k, _v)| (*k).cmp(key)) {
+                Ok(i) if last => {
+                    if let JsonbPathOp::InsertBefore | JsonbPathOp::InsertAfter = op {
+                        return Err(EvalError::InvalidParameterValue(
+                            "cannot replace existing key".into(),
+                        ));
+                    }
+                    pairs[i].1 = new_value;
+                }
+                Ok(i) => {
+                    pairs[i].1 = set_path(pairs[i].1, path, level + 1, new_value, op, temp_storage)?
+                }
+                Err(i) if create => pairs.insert(i, (key, new_value)),
+                Err(_) => return Ok(target),
+            }
+            Ok(temp_storage.make_datum(|packer| packer.push_dict(pairs)))
+        }
+        Datum::List(list) => {
+            let mut elems = list.iter().collect::<Vec<_>>();
+            let len = elems.len() as i64;
+            let i = match key.trim_start().parse::<i32>() {
+                Ok(i) => i64::from(i),
+                Err(_) => {
+                    return Err(EvalError::InvalidParameterValue(format!(
+                        "path element at position {} is not an integer: \"{}\"",
+                        level + 1,
+                        key
+                    )))
+                }
+            };
+            // Negative indices count backwards from the end of the array.
+            let i = if i < 0 { len + i } else { i };
+            if i < 0 || elems.is_empty() {
+                // Indices before the start of the array create new values at
+                // its start.
+                if !create {
+                    return Ok(target);
+                }
+                elems.insert(0, new_value);
+            } else if i >= len {
+                // Indices past the end of the array create new values at its
+                // end.
+                if !create {
+                    return Ok(target);
+                }
+                elems.push(new_value);
+            } else {
+                let i = i as usize;
+                match op {
+                    _ if !last => {
+                        elems[i] = set_path(elems[i], path, level + 1, new_value, op, temp_storage)?
+                    }
+                    JsonbPathOp::Replace | JsonbPathOp::Create => elems[i] = new_value,
+                    JsonbPathOp::InsertBefore => elems.insert(i, new_value),
+                    JsonbPathOp::InsertAfter => elems.insert(i + 1, new_value),
+                }
+            }
+            Ok(temp_storage.make_datum(|packer| packer.push_list(elems)))
+        }
+        // Scalars along the path are left unchanged.
+        _ => Ok(target),
+    }
+}
+
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum BinaryFunc {
     And,
@@ -2097,6 +2375,10 @@ pub enum BinaryFunc {
     IsRegexpMatch { case_insensitive: bool },
     ToCharTimestamp,
     ToCharTimestampTz,
+    ToCharInt32,
+    ToCharInt64,
+    ToCharFloat64,
+    ToCharDecimal(u8),
     DatePartInterval,
     DatePartTimestamp,
     DatePartTimestampTz,
@@ -2118,6 +2400,7 @@ pub enum BinaryFunc {
     JsonbContainsJsonb,
     JsonbDeleteInt64,
     JsonbDeleteString,
+    JsonbPathQueryFirst,
     MapContainsKey,
     MapGetValue,
     MapGetValues,
@@ -2129,6 +2412,8 @@ pub enum BinaryFunc {
     TrimLeading,
     TrimTrailing,
     EncodedBytesCharLength,
+    GetBitBytes,
+    Levenshtein,
     ListIndex,
     ListLengthMax { max_dim: usize },
     ArrayContains,
@@ -2141,7 +2426,11 @@ pub enum BinaryFunc {
     ElementListConcat,
     DigestString,
     DigestBytes,
+    Encode,
+    Decode,
     MzRenderTypemod,
+    MzUuidV5,
+    WidthBucketArray,
 }
 
 impl BinaryFunc {
@@ -2181,8 +2470,8 @@ impl BinaryFunc {
             BinaryFunc::SubInt64 => eager!(sub_int64),
             BinaryFunc::SubFloat32 => Ok(eager!(sub_float32)),
             BinaryFunc::SubFloat64 => Ok(eager!(sub_float64)),
-            BinaryFunc::SubTimestamp => Ok(eager!(sub_timestamp)),
-            BinaryFunc::SubTimestampTz => Ok(eager!(sub_timestamptz)),
+            BinaryFunc::SubTimestamp => eager!(sub_timestamp),
+            BinaryFunc::SubTimestampTz => eager!(sub_timestamptz),
             BinaryFunc::SubTimestampInterval => Ok(eager!(sub_timestamp_interval)),
             BinaryFunc::SubTimestampTzInterval => Ok(eager!(sub_timestamptz_interval)),
             BinaryFunc::SubInterval => eager!(sub_interval),
@@ -2222,6 +2511,16 @@ impl BinaryFunc {
             }
             BinaryFunc::ToCharTimestamp => Ok(eager!(to_char_timestamp, temp_storage)),
             BinaryFunc::ToCharTimestampTz => Ok(eager!(to_char_timestamptz, temp_storage)),
+            BinaryFunc::ToCharInt32 => eager!(|a: Datum, b| {
+                to_char_decimal(i128::from(a.unwrap_int32()), 0, b, temp_storage)
+            }),
+            BinaryFunc::ToCharInt64 => eager!(|a: Datum, b| {
+                to_char_decimal(i128::from(a.unwrap_int64()), 0, b, temp_storage)
+            }),
+            BinaryFunc::ToCharFloat64 => eager!(to_char_float64, temp_storage),
+            BinaryFunc::ToCharDecimal(scale) => eager!(|a: Datum, b| {
+                to_char_decimal(a.unwrap_decimal().as_i128(), *scale, b, temp_storage)
+            }),
             BinaryFunc::DatePartInterval => {
                 eager!(|a, b: Datum| date_part_interval(a, b.unwrap_interval()))
             }
@@ -2264,6 +2563,7 @@ impl BinaryFunc {
             BinaryFunc::JsonbContainsJsonb => Ok(eager!(jsonb_contains_jsonb)),
             BinaryFunc::JsonbDeleteInt64 => Ok(eager!(jsonb_delete_int64, temp_storage)),
             BinaryFunc::JsonbDeleteString => Ok(eager!(jsonb_delete_string, temp_storage)),
+            BinaryFunc::JsonbPathQueryFirst => eager!(jsonb_path_query_first_dynamic),
             BinaryFunc::MapContainsKey => Ok(eager!(map_contains_key)),
             BinaryFunc::MapGetValue => Ok(eager!(map_get_value)),
             BinaryFunc::MapGetValues => Ok(eager!(map_get_values, temp_storage)),
@@ -2276,6 +2576,8 @@ impl BinaryFunc {
             BinaryFunc::TrimLeading => Ok(eager!(trim_leading)),
             BinaryFunc::TrimTrailing => Ok(eager!(trim_trailing)),
             BinaryFunc::EncodedBytesCharLength => eager!(encoded_bytes_char_length),
+            BinaryFunc::GetBitBytes => eager!(get_bit_bytes),
+            BinaryFunc::Levenshtein => eager!(levenshtein),
             BinaryFunc::ListIndex => Ok(eager!(list_index)),
             BinaryFunc::ListLengthMax { max_dim } => eager!(list_length_max, *max_dim),
             BinaryFunc::ArrayLength => Ok(eager!(array_length)),
@@ -2283,12 +2585,16 @@ impl BinaryFunc {
             BinaryFunc::ArrayIndex => Ok(eager!(array_index)),
             BinaryFunc::ArrayLower => Ok(eager!(array_lower)),
             BinaryFunc::ArrayUpper => Ok(eager!(array_upper)),
+            BinaryFunc::WidthBucketArray => eager!(width_bucket_array),
             BinaryFunc::ListListConcat => Ok(eager!(list_list_concat, temp_storage)),
             BinaryFunc::ListElementConcat => Ok(eager!(list_element_concat, temp_storage)),
             BinaryFunc::ElementListConcat => Ok(eager!(element_list_concat, temp_storage)),
             BinaryFunc::DigestString => eager!(digest_string, temp_storage),
             BinaryFunc::DigestBytes => eager!(digest_bytes, temp_storage),
+            BinaryFunc::Encode => eager!(encode, temp_storage),
+            BinaryFunc::Decode => eager!(decode, temp_storage),
             BinaryFunc::MzRenderTypemod => Ok(eager!(mz_render_typemod, temp_storage)),
+            BinaryFunc::MzUuidV5 => Ok(eager!(mz_uuid_v5)),
         }
     }
 
@@ -2318,13 +2624,17 @@ impl BinaryFunc {
                 ScalarType::Bool.nullable(true)
             }
 
-            ToCharTimestamp | ToCharTimestampTz | ConvertFrom | Trim | TrimLeading
-            | TrimTrailing => ScalarType::String.nullable(in_nullable),
+            ToCharTimestamp | ToCharTimestampTz | ToCharInt32 | ToCharInt64 | ToCharFloat64
+            | ToCharDecimal(_) | ConvertFrom | Trim | TrimLeading | TrimTrailing => {
+                ScalarType::String.nullable(in_nullable)
+            }
 
             AddInt32 | SubInt32 | MulInt32 | DivInt32 | ModInt32 | EncodedBytesCharLength => {
                 ScalarType::Int32.nullable(in_nullable || is_div_mod)
             }
 
+            GetBitBytes | Levenshtein => ScalarType::Int32.nullable(in_nullable),
+
             AddInt64 | SubInt64 | MulInt64 | DivInt64 | ModInt64 => {
                 ScalarType::Int64.nullable(in_nullable || is_div_mod)
             }
@@ -2414,6 +2724,10 @@ impl BinaryFunc {
 
             MzRenderTypemod | TextConcat => ScalarType::String.nullable(in_nullable),
 
+            MzUuidV5 => ScalarType::Uuid.nullable(in_nullable),
+
+            WidthBucketArray => ScalarType::Int32.nullable(in_nullable),
+
             JsonbGetInt64 { stringify: true } | JsonbGetString { stringify: true } => {
                 ScalarType::String.nullable(true)
             }
@@ -2422,7 +2736,8 @@ impl BinaryFunc {
             | JsonbGetString { stringify: false }
             | JsonbConcat
             | JsonbDeleteInt64
-            | JsonbDeleteString => ScalarType::Jsonb.nullable(true),
+            | JsonbDeleteString
+            | JsonbPathQueryFirst => ScalarType::Jsonb.nullable(true),
 
             JsonbContainsString | JsonbContainsJsonb | MapContainsKey | MapContainsAllKeys
             | MapContainsAnyKeys | MapContainsMap => ScalarType::Bool.nullable(in_nullable),
@@ -2456,6 +2771,8 @@ impl BinaryFunc {
             ListListConcat | ListElementConcat => input1_type.scalar_type.nullable(true),
             ElementListConcat => input2_type.scalar_type.nullable(true),
             DigestString | DigestBytes => ScalarType::Bytes.nullable(true),
+            Encode => ScalarType::String.nullable(in_nullable),
+            Decode => ScalarType::Bytes.nullable(in_nullable),
         }
     }
 
@@ -2529,6 +2846,10 @@ impl BinaryFunc {
                 | ModFloat32
                 | ModFloat64
                 | ModDecimal
+                | GetBitBytes
+                | Levenshtein
+                | MzUuidV5
+                | WidthBucketArray
         )
     }
 
@@ -2612,6 +2933,10 @@ impl BinaryFunc {
             IsLikePatternMatch { .. }
             | ToCharTimestamp
             | ToCharTimestampTz
+            | ToCharInt32
+            | ToCharInt64
+            | ToCharFloat64
+            | ToCharDecimal(_)
             | DatePartInterval
             | DatePartTimestamp
             | DatePartTimestampTz
@@ -2631,10 +2956,17 @@ impl BinaryFunc {
             | TrimLeading
             | TrimTrailing
             | EncodedBytesCharLength
+            | GetBitBytes
+            | Levenshtein
             | ListLengthMax { .. }
             | DigestString
             | DigestBytes
-            | MzRenderTypemod => false,
+            | Encode
+            | Decode
+            | JsonbPathQueryFirst
+            | MzRenderTypemod
+            | MzUuidV5
+            | WidthBucketArray => false,
         }
     }
 }
@@ -2706,6 +3038,10 @@ impl fmt::Display for BinaryFunc {
             } => f.write_str("~*"),
             BinaryFunc::ToCharTimestamp => f.write_str("tocharts"),
             BinaryFunc::ToCharTimestampTz => f.write_str("tochartstz"),
+            BinaryFunc::ToCharInt32 => f.write_str("tocharint32"),
+            BinaryFunc::ToCharInt64 => f.write_str("tocharint64"),
+            BinaryFunc::ToCharFloat64 => f.write_str("tocharf64"),
+            BinaryFunc::ToCharDecimal(_) => f.write_str("tochardec"),
             BinaryFunc::DatePartInterval => f.write_str("date_partiv"),
             BinaryFunc::DatePartTimestamp => f.write_str("date_partts"),
             BinaryFunc::DatePartTimestampTz => f.write_str("date_parttstz"),
@@ -2727,6 +3063,7 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::JsonbContainsJsonb | BinaryFunc::MapContainsMap => f.write_str("@>"),
             BinaryFunc::JsonbDeleteInt64 => f.write_str("-"),
             BinaryFunc::JsonbDeleteString => f.write_str("-"),
+            BinaryFunc::JsonbPathQueryFirst => f.write_str("jsonb_path_query_first"),
             BinaryFunc::MapGetValue | BinaryFunc::MapGetValues => f.write_str("->"),
             BinaryFunc::MapContainsAllKeys => f.write_str("?&"),
             BinaryFunc::MapContainsAnyKeys => f.write_str("?|"),
@@ -2736,6 +3073,8 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::TrimLeading => f.write_str("ltrim"),
             BinaryFunc::TrimTrailing => f.write_str("rtrim"),
             BinaryFunc::EncodedBytesCharLength => f.write_str("length"),
+            BinaryFunc::GetBitBytes => f.write_str("get_bit"),
+            BinaryFunc::Levenshtein => f.write_str("levenshtein"),
             BinaryFunc::ListIndex => f.write_str("list_index"),
             BinaryFunc::ListLengthMax { .. } => f.write_str("list_length_max"),
             BinaryFunc::ArrayContains => f.write_str("array_contains"),
@@ -2747,7 +3086,11 @@ impl fmt::Display for BinaryFunc {
             BinaryFunc::ListElementConcat => f.write_str("||"),
             BinaryFunc::ElementListConcat => f.write_str("||"),
             BinaryFunc::DigestString | BinaryFunc::DigestBytes => f.write_str("digest"),
+            BinaryFunc::Encode => f.write_str("encode"),
+            BinaryFunc::Decode => f.write_str("decode"),
             BinaryFunc::MzRenderTypemod => f.write_str("mz_render_typemod"),
+            BinaryFunc::MzUuidV5 => f.write_str("mz_uuid_v5"),
+            BinaryFunc::WidthBucketArray => f.write_str("width_bucket"),
         }
     }
 }
@@ -2829,7 +3172,10 @@ pub enum UnaryFunc {
     CastStringToTimestamp,
     CastStringToTimestampTz,
     CastStringToInterval,
-    CastStringToDecimal(u8),
+    CastStringToDecimal {
+        precision: u8,
+        scale: u8,
+    },
     CastStringToUuid,
     CastDateToTimestamp,
     CastDateToTimestampTz,
@@ -2879,6 +3225,9 @@ pub enum UnaryFunc {
     FloorFloat64,
     FloorDecimal(u8),
     Ascii,
+    BitCountInt32,
+    BitCountInt64,
+    BitCountBytes,
     BitLengthBytes,
     BitLengthString,
     ByteLengthBytes,
@@ -2886,6 +3235,8 @@ pub enum UnaryFunc {
     CharLength,
     IsRegexpMatch(Regex),
     RegexpMatch(Regex),
+    RegexpSplitToArray(Regex),
+    JsonbPathQueryFirst(JsonPath),
     DatePartInterval(DateTimeUnits),
     DatePartTimestamp(DateTimeUnits),
     DatePartTimestampTz(DateTimeUnits),
@@ -2895,6 +3246,9 @@ pub enum UnaryFunc {
     TimezoneTimestampTz(Timezone),
     TimezoneTime(Timezone),
     ToTimestamp,
+    JustifyDays,
+    JustifyHours,
+    JustifyInterval,
     JsonbArrayLength,
     JsonbTypeof,
     JsonbStripNulls,
@@ -2907,8 +3261,13 @@ pub enum UnaryFunc {
     TrimTrailingWhitespace,
     RecordGet(usize),
     ListLength,
+    MapLength,
+    MapKeys,
+    MapValues,
     Upper,
     Lower,
+    Unaccent,
+    Sleep,
 }
 
 impl UnaryFunc {
@@ -2972,7 +3331,9 @@ impl UnaryFunc {
             UnaryFunc::CastStringToInt64 => cast_string_to_int64(a),
             UnaryFunc::CastStringToFloat32 => cast_string_to_float32(a),
             UnaryFunc::CastStringToFloat64 => cast_string_to_float64(a),
-            UnaryFunc::CastStringToDecimal(scale) => cast_string_to_decimal(a, *scale),
+            UnaryFunc::CastStringToDecimal { precision, scale } => {
+                cast_string_to_decimal(a, *precision, *scale)
+            }
             UnaryFunc::CastStringToDate => cast_string_to_date(a),
             UnaryFunc::CastStringToList {
                 cast_expr,
@@ -2993,7 +3354,7 @@ impl UnaryFunc {
             UnaryFunc::CastDecimalToString(scale) => {
                 Ok(cast_decimal_to_string(a, *scale, temp_storage))
             }
-            UnaryFunc::CastTimeToInterval => cast_time_to_interval(a),
+            UnaryFunc::CastTimeToInterval => Ok(cast_time_to_interval(a)),
             UnaryFunc::CastTimeToString => Ok(cast_time_to_string(a, temp_storage)),
             UnaryFunc::CastTimestampToDate => Ok(cast_timestamp_to_date(a)),
             UnaryFunc::CastTimestampToTimestampTz => Ok(cast_timestamp_to_timestamptz(a)),
@@ -3030,6 +3391,9 @@ impl UnaryFunc {
             UnaryFunc::SqrtFloat64 => sqrt_float64(a),
             UnaryFunc::SqrtDec(scale) => sqrt_dec(a, *scale),
             UnaryFunc::Ascii => Ok(ascii(a)),
+            UnaryFunc::BitCountInt32 => Ok(bit_count_int32(a)),
+            UnaryFunc::BitCountInt64 => Ok(bit_count_int64(a)),
+            UnaryFunc::BitCountBytes => Ok(bit_count_bytes(a)),
             UnaryFunc::BitLengthString => bit_length(a.unwrap_str()),
             UnaryFunc::BitLengthBytes => bit_length(a.unwrap_bytes()),
             UnaryFunc::ByteLengthString => byte_length(a.unwrap_str()),
@@ -3037,6 +3401,10 @@ impl UnaryFunc {
             UnaryFunc::CharLength => char_length(a),
             UnaryFunc::IsRegexpMatch(regex) => Ok(is_regexp_match_static(a, &regex)),
             UnaryFunc::RegexpMatch(regex) => regexp_match_static(a, temp_storage, &regex),
+            UnaryFunc::RegexpSplitToArray(regex) => {
+                regexp_split_to_array_static(a, temp_storage, &regex)
+            }
+            UnaryFunc::JsonbPathQueryFirst(path) => Ok(jsonb_path_query_first(a, path)),
             UnaryFunc::DatePartInterval(units) => {
                 date_part_interval_inner(*units, a.unwrap_interval())
             }
@@ -3056,6 +3424,9 @@ impl UnaryFunc {
             }
             UnaryFunc::TimezoneTime(tz) => Ok(timezone_time(*tz, a.unwrap_time())),
             UnaryFunc::ToTimestamp => Ok(to_timestamp(a)),
+            UnaryFunc::JustifyDays => justify_days(a),
+            UnaryFunc::JustifyHours => justify_hours(a),
+            UnaryFunc::JustifyInterval => justify_interval(a),
             UnaryFunc::JsonbArrayLength => Ok(jsonb_array_length(a)),
             UnaryFunc::JsonbTypeof => Ok(jsonb_typeof(a)),
             UnaryFunc::JsonbStripNulls => Ok(jsonb_strip_nulls(a, temp_storage)),
@@ -3068,8 +3439,13 @@ impl UnaryFunc {
             UnaryFunc::TrimTrailingWhitespace => Ok(trim_trailing_whitespace(a)),
             UnaryFunc::RecordGet(i) => Ok(record_get(a, *i)),
             UnaryFunc::ListLength => Ok(list_length(a)),
+            UnaryFunc::MapLength => Ok(map_length(a)),
+            UnaryFunc::MapKeys => map_keys(a, temp_storage),
+            UnaryFunc::MapValues => map_values(a, temp_storage),
             UnaryFunc::Upper => Ok(upper(a, temp_storage)),
             UnaryFunc::Lower => Ok(lower(a, temp_storage)),
+            UnaryFunc::Unaccent => Ok(unaccent(a, temp_storage)),
+            UnaryFunc::Sleep => Ok(sleep(a)),
         }
     }
 
@@ -3079,8 +3455,12 @@ impl UnaryFunc {
         match self {
             IsNull | CastInt32ToBool | CastInt64ToBool => ScalarType::Bool.nullable(false),
 
-            Ascii | CharLength | BitLengthBytes | BitLengthString | ByteLengthBytes
-            | ByteLengthString => ScalarType::Int32.nullable(in_nullable),
+            Ascii | CharLength | BitCountInt32 | BitCountInt64 | BitLengthBytes
+            | BitLengthString | ByteLengthBytes | ByteLengthString => {
+                ScalarType::Int32.nullable(in_nullable)
+            }
+
+            BitCountBytes => ScalarType::Int64.nullable(in_nullable),
 
             IsRegexpMatch(_) => ScalarType::Bool.nullable(in_nullable),
 
@@ -3090,7 +3470,7 @@ impl UnaryFunc {
             CastStringToInt64 => ScalarType::Int64.nullable(true),
             CastStringToFloat32 => ScalarType::Float32.nullable(true),
             CastStringToFloat64 => ScalarType::Float64.nullable(true),
-            CastStringToDecimal(scale) => {
+            CastStringToDecimal { scale, .. } => {
                 ScalarType::Decimal(MAX_DECIMAL_PRECISION, *scale).nullable(true)
             }
             CastStringToDate => ScalarType::Date.nullable(true),
@@ -3123,7 +3503,8 @@ impl UnaryFunc {
             | TrimLeadingWhitespace
             | TrimTrailingWhitespace
             | Upper
-            | Lower => ScalarType::String.nullable(in_nullable),
+            | Lower
+            | Unaccent => ScalarType::String.nullable(in_nullable),
 
             CastFloat64ToFloat32
             | CastInt32ToFloat32
@@ -3204,9 +3585,14 @@ impl UnaryFunc {
 
             ToTimestamp => ScalarType::TimestampTz.nullable(true),
 
+            JustifyDays | JustifyHours | JustifyInterval => {
+                ScalarType::Interval.nullable(in_nullable)
+            }
+
             JsonbArrayLength => ScalarType::Int64.nullable(true),
             JsonbTypeof => ScalarType::String.nullable(in_nullable),
             JsonbStripNulls => ScalarType::Jsonb.nullable(true),
+            JsonbPathQueryFirst(_) => ScalarType::Jsonb.nullable(true),
             JsonbPretty => ScalarType::String.nullable(in_nullable),
 
             RecordGet(i) => match input_type.scalar_type {
@@ -3216,7 +3602,19 @@ impl UnaryFunc {
 
             ListLength => ScalarType::Int64.nullable(true),
 
+            MapLength => ScalarType::Int64.nullable(in_nullable),
+            MapKeys => ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable),
+            MapValues => ScalarType::Array(Box::new(
+                input_type.scalar_type.unwrap_map_value_type().clone(),
+            ))
+            .nullable(in_nullable),
+
+            Sleep => ScalarType::TimestampTz.nullable(true),
+
             RegexpMatch(_) => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            RegexpSplitToArray(_) => {
+                ScalarType::Array(Box::new(ScalarType::String)).nullable(in_nullable)
+            }
         }
     }
 
@@ -3225,6 +3623,13 @@ impl UnaryFunc {
         !matches!(self, UnaryFunc::IsNull | UnaryFunc::CastJsonbOrNullToJsonb)
     }
 
+    /// Whether the function is non-deterministic or has side effects, and so,
+    /// like the nullary functions, must only be evaluated when the query that
+    /// contains it runs, never folded into a constant ahead of time.
+    pub fn is_nondeterministic(&self) -> bool {
+        matches!(self, UnaryFunc::Sleep)
+    }
+
     /// True iff for x != y, we are assured f(x) != f(y).
     ///
     /// This is most often the case for methods that promote to types that
@@ -3308,7 +3713,7 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::CastStringToInt64 => f.write_str("strtoi64"),
             UnaryFunc::CastStringToFloat32 => f.write_str("strtof32"),
             UnaryFunc::CastStringToFloat64 => f.write_str("strtof64"),
-            UnaryFunc::CastStringToDecimal(_) => f.write_str("strtodec"),
+            UnaryFunc::CastStringToDecimal { .. } => f.write_str("strtodec"),
             UnaryFunc::CastStringToDate => f.write_str("strtodate"),
             UnaryFunc::CastStringToList { .. } => f.write_str("strtolist"),
             UnaryFunc::CastStringToMap { .. } => f.write_str("strtomap"),
@@ -3354,12 +3759,21 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::SqrtDec(_) => f.write_str("sqrtdec"),
             UnaryFunc::Ascii => f.write_str("ascii"),
             UnaryFunc::CharLength => f.write_str("char_length"),
+            UnaryFunc::BitCountInt32 | UnaryFunc::BitCountInt64 | UnaryFunc::BitCountBytes => {
+                f.write_str("bit_count")
+            }
             UnaryFunc::BitLengthBytes => f.write_str("bit_length"),
             UnaryFunc::BitLengthString => f.write_str("bit_length"),
             UnaryFunc::ByteLengthBytes => f.write_str("byte_length"),
             UnaryFunc::ByteLengthString => f.write_str("byte_length"),
             UnaryFunc::IsRegexpMatch(regex) => write!(f, "{} ~", regex.as_str().quoted()),
             UnaryFunc::RegexpMatch(regex) => write!(f, "regexp_match[{}]", regex.as_str()),
+            UnaryFunc::RegexpSplitToArray(regex) => {
+                write!(f, "regexp_split_to_array[{}]", regex.as_str())
+            }
+            UnaryFunc::JsonbPathQueryFirst(path) => {
+                write!(f, "jsonb_path_query_first[{}]", path)
+            }
             UnaryFunc::DatePartInterval(units) => write!(f, "date_part_{}_iv", units),
             UnaryFunc::DatePartTimestamp(units) => write!(f, "date_part_{}_ts", units),
             UnaryFunc::DatePartTimestampTz(units) => write!(f, "date_part_{}_tstz", units),
@@ -3369,6 +3783,9 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::TimezoneTimestampTz(tz) => write!(f, "timezone_{}_tstz", tz),
             UnaryFunc::TimezoneTime(tz) => write!(f, "timezone_{}_t", tz),
             UnaryFunc::ToTimestamp => f.write_str("tots"),
+            UnaryFunc::JustifyDays => f.write_str("justify_days"),
+            UnaryFunc::JustifyHours => f.write_str("justify_hours"),
+            UnaryFunc::JustifyInterval => f.write_str("justify_interval"),
             UnaryFunc::JsonbArrayLength => f.write_str("jsonb_array_length"),
             UnaryFunc::JsonbTypeof => f.write_str("jsonb_typeof"),
             UnaryFunc::JsonbStripNulls => f.write_str("jsonb_strip_nulls"),
@@ -3381,8 +3798,13 @@ impl fmt::Display for UnaryFunc {
             UnaryFunc::TrimTrailingWhitespace => f.write_str("rtrim"),
             UnaryFunc::RecordGet(_) => f.write_str("record_get"),
             UnaryFunc::ListLength => f.write_str("list_length"),
+            UnaryFunc::MapLength => f.write_str("map_length"),
+            UnaryFunc::MapKeys => f.write_str("map_keys"),
+            UnaryFunc::MapValues => f.write_str("map_values"),
             UnaryFunc::Upper => f.write_str("upper"),
             UnaryFunc::Lower => f.write_str("lower"),
+            UnaryFunc::Unaccent => f.write_str("unaccent"),
+            UnaryFunc::Sleep => f.write_str("mz_sleep"),
         }
     }
 }
@@ -3487,32 +3909,90 @@ fn split_part<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
     let string = datums[0].unwrap_str();
     let delimiter = datums[1].unwrap_str();
 
-    // Provided index value begins at 1, not 0.
-    let index = match usize::try_from(datums[2].unwrap_int64() - 1) {
-        Ok(index) => index,
-        Err(_) => {
-            return Err(EvalError::InvalidParameterValue(
-                "field position must be greater than zero".to_owned(),
-            ))
-        }
-    };
+    // Provided index value begins at 1, not 0. Negative index values count
+    // from the last part, which is -1.
+    let index = datums[2].unwrap_int64();
+    if index == 0 {
+        return Err(EvalError::InvalidParameterValue(
+            "field position must not be zero".to_owned(),
+        ));
+    }
 
     // If the provided delimiter is the empty string,
     // PostgreSQL does not break the string into individual
     // characters. Instead, it generates the following parts: [string].
     if delimiter.is_empty() {
-        if index == 0 {
+        if index == 1 || index == -1 {
             return Ok(datums[0]);
         } else {
             return Ok(Datum::String(""));
         }
     }
 
+    // Negative indexes must count back from the parts produced by splitting
+    // from the left, as splitting from the right can produce different parts
+    // when the delimiter overlaps with itself.
+    let part = if index > 0 {
+        usize::try_from(index - 1)
+            .ok()
+            .and_then(|i| string.split(delimiter).nth(i))
+    } else {
+        let parts: Vec<_> = string.split(delimiter).collect();
+        usize::try_from(-(index + 1))
+            .ok()
+            .and_then(|i| parts.len().checked_sub(i + 1))
+            .map(|i| parts[i])
+    };
+
     // If provided index is greater than the number of split parts,
     // return an empty string.
-    Ok(Datum::String(
-        string.split(delimiter).nth(index).unwrap_or(""),
-    ))
+    Ok(Datum::String(part.unwrap_or("")))
+}
+
+fn string_to_array<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if datums[0].is_null() {
+        return Ok(Datum::Null);
+    }
+    let string = datums[0].unwrap_str();
+    let null_str = match datums.get(2) {
+        None | Some(Datum::Null) => None,
+        Some(d) => Some(d.unwrap_str()),
+    };
+
+    // As in PostgreSQL, the empty string splits into an empty array, a null
+    // delimiter splits the string into its characters, and an empty
+    // delimiter does not split the string at all.
+    let parts: Vec<&str> = if string.is_empty() {
+        vec![]
+    } else {
+        match datums[1] {
+            Datum::Null => string
+                .char_indices()
+                .map(|(i, c)| &string[i..i + c.len_utf8()])
+                .collect(),
+            d if d.unwrap_str().is_empty() => vec![string],
+            d => string.split(d.unwrap_str()).collect(),
+        }
+    };
+
+    // Per PostgreSQL, empty arrays have zero dimensions.
+    let dims = if parts.is_empty() {
+        vec![]
+    } else {
+        vec![ArrayDimension {
+            lower_bound: 1,
+            length: parts.len(),
+        }]
+    };
+    let elems = parts.into_iter().map(|part| match null_str {
+        Some(null_str) if part == null_str => Datum::Null,
+        _ => Datum::String(part),
+    });
+    let datum = temp_storage.try_make_datum(|packer| packer.push_array(&dims, elems))?;
+    Ok(datum)
 }
 
 fn is_like_pattern_match_dynamic<'a>(
@@ -3597,6 +4077,61 @@ fn regexp_match_static<'a>(
     Ok(temp_storage.push_unary_row(packer.finish()))
 }
 
+fn regexp_split_to_array_dynamic<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let haystack = datums[0];
+    let needle = datums[1].unwrap_str();
+    let flags = match datums.get(2) {
+        Some(d) => d.unwrap_str(),
+        None => "",
+    };
+    let needle = build_regex(needle, flags)?;
+    regexp_split_to_array_static(haystack, temp_storage, &needle)
+}
+
+fn regexp_split_to_array_static<'a>(
+    haystack: Datum<'a>,
+    temp_storage: &'a RowArena,
+    needle: &regex::Regex,
+) -> Result<Datum<'a>, EvalError> {
+    let haystack = haystack.unwrap_str();
+    let mut segments = vec![];
+    let mut segment_start = 0;
+    // As in PostgreSQL, every match splits the string, except for "degenerate"
+    // matches: zero-length matches at the start or end of the string, or
+    // immediately after the previous match.
+    let mut prev_match_end = 0;
+    let mut search_start = 0;
+    while let Some(mtch) = needle.find_at(haystack, search_start) {
+        if mtch.start() < haystack.len() && mtch.end() > prev_match_end {
+            segments.push(&haystack[segment_start..mtch.start()]);
+            segment_start = mtch.end();
+        }
+        prev_match_end = mtch.end();
+        search_start = mtch.end();
+        if mtch.start() == mtch.end() {
+            // Step over the next character, so that the same zero-length
+            // match is not found again.
+            match haystack[search_start..].chars().next() {
+                Some(c) => search_start += c.len_utf8(),
+                None => break,
+            }
+        }
+    }
+    segments.push(&haystack[segment_start..]);
+    let mut packer = RowPacker::new();
+    packer.push_array(
+        &[ArrayDimension {
+            lower_bound: 1,
+            length: segments.len(),
+        }],
+        segments.into_iter().map(Datum::String),
+    )?;
+    Ok(temp_storage.push_unary_row(packer.finish()))
+}
+
 pub fn build_regex(needle: &str, flags: &str) -> Result<regex::Regex, EvalError> {
     let mut regex = RegexBuilder::new(needle);
     for f in flags.chars() {
@@ -3633,6 +4168,24 @@ pub fn hmac_bytes<'a>(
     hmac_inner(to_digest, key, typ, temp_storage)
 }
 
+fn set_bit_bytes<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let mut bytes = datums[0].unwrap_bytes().to_vec();
+    let (byte, bit) = bit_position(&bytes, datums[1].unwrap_int64())?;
+    match datums[2].unwrap_int32() {
+        0 => bytes[byte] &= !(1 << bit),
+        1 => bytes[byte] |= 1 << bit,
+        _ => {
+            return Err(EvalError::InvalidParameterValue(
+                "new bit must be 0 or 1".into(),
+            ))
+        }
+    }
+    Ok(Datum::Bytes(temp_storage.push_bytes(bytes)))
+}
+
 pub fn hmac_inner<'a>(
     to_digest: &[u8],
     key: &[u8],
@@ -3787,26 +4340,102 @@ fn array_to_string<'a>(
         Some(d) => Some(d.unwrap_str()),
     };
 
+    // As in PostgreSQL, null elements are omitted entirely, delimiter and
+    // all, unless a string to print in their place is given.
     let mut out = String::new();
+    let mut printed = false;
     for elem in array.elements().iter() {
-        if elem.is_null() {
-            if let Some(null_str) = null_str {
-                out.push_str(null_str);
-                out.push_str(delimiter);
-            }
-        } else {
-            stringify_datum(&mut out, elem, elem_type);
+        if elem.is_null() && null_str.is_none() {
+            continue;
+        }
+        if printed {
             out.push_str(delimiter);
         }
+        match null_str {
+            Some(null_str) if elem.is_null() => out.push_str(null_str),
+            _ => stringify_datum(&mut out, elem, elem_type),
+        }
+        printed = true;
     }
-    out.truncate(out.len() - delimiter.len()); // lop off last delimiter
     Ok(Datum::String(temp_storage.push_string(out)))
 }
 
+/// Returns the subscripts of the elements of `array` that are not distinct
+/// from `elem`, in ascending order.
+fn array_element_positions<'a>(
+    array: Datum<'a>,
+    elem: Datum<'a>,
+) -> Result<impl Iterator<Item = i32> + 'a, EvalError> {
+    let array = array.unwrap_array();
+    let mut dims = array.dims().into_iter();
+    let lower_bound = match (dims.next(), dims.next()) {
+        (None, _) => 1,
+        (Some(dim), None) => dim.lower_bound,
+        (Some(_), Some(_)) => {
+            return Err(EvalError::InvalidParameterValue(
+                "searching for elements in multidimensional arrays is not supported".into(),
+            ))
+        }
+    };
+    let lower_bound = i32::try_from(lower_bound).map_err(|_| EvalError::Int32OutOfRange)?;
+    Ok(array
+        .elements()
+        .iter()
+        .zip(lower_bound..)
+        .filter(move |(e, _)| *e == elem)
+        .map(|(_, i)| i))
+}
+
+fn array_position<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    if datums[0].is_null() {
+        return Ok(Datum::Null);
+    }
+    let start = match datums.get(2) {
+        None => i32::MIN,
+        Some(Datum::Null) => {
+            return Err(EvalError::InvalidParameterValue(
+                "initial position must not be null".into(),
+            ))
+        }
+        Some(d) => d.unwrap_int32(),
+    };
+    Ok(array_element_positions(datums[0], datums[1])?
+        .find(|i| *i >= start)
+        .map(Datum::Int32)
+        .unwrap_or(Datum::Null))
+}
+
+fn array_positions<'a>(
+    datums: &[Datum<'a>],
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    if datums[0].is_null() {
+        return Ok(Datum::Null);
+    }
+    let positions: Vec<_> = array_element_positions(datums[0], datums[1])?
+        .map(Datum::Int32)
+        .collect();
+    array_create_scalar(&positions, temp_storage)
+}
+
 fn list_create<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Datum<'a> {
     temp_storage.make_datum(|packer| packer.push_list(datums))
 }
 
+/// Builds a map from alternating keys and values. As when parsing a map from
+/// text, a key that appears more than once takes its last value.
+fn map_build<'a>(datums: &[Datum<'a>], temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    // Maps are packed with their keys in sorted order.
+    let mut map = BTreeMap::new();
+    for (k, v) in datums.iter().tuples() {
+        if k.is_null() {
+            return Err(EvalError::NullMapKey);
+        }
+        map.insert(k.unwrap_str(), *v);
+    }
+    Ok(temp_storage.make_datum(|packer| packer.push_dict(map)))
+}
+
 fn cast_collection_to_string<'a>(
     a: Datum,
     ty: &ScalarType,
@@ -3959,6 +4588,24 @@ fn list_length(a: Datum) -> Datum {
     Datum::Int64(a.unwrap_list().iter().count() as i64)
 }
 
+fn map_length(a: Datum) -> Datum {
+    Datum::Int64(a.unwrap_map().iter().count() as i64)
+}
+
+fn map_keys<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    let keys: Vec<_> = a
+        .unwrap_map()
+        .iter()
+        .map(|(k, _v)| Datum::String(k))
+        .collect();
+    array_create_scalar(&keys, temp_storage)
+}
+
+fn map_values<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Result<Datum<'a>, EvalError> {
+    let values: Vec<_> = a.unwrap_map().iter().map(|(_k, v)| v).collect();
+    array_create_scalar(&values, temp_storage)
+}
+
 fn upper<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     Datum::String(temp_storage.push_string(a.unwrap_str().to_owned().to_uppercase()))
 }
@@ -3967,6 +4614,71 @@ fn lower<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
     Datum::String(temp_storage.push_string(a.unwrap_str().to_owned().to_lowercase()))
 }
 
+/// The longest that a single call to `mz_sleep` will sleep for, in seconds.
+///
+/// The cap applies to each call, not to the query that makes it: a query that
+/// calls `mz_sleep` once per row of its input sleeps for up to this long per
+/// row. There is no per-query state in which to track a total.
+const MAX_SLEEP_SECONDS: f64 = 60.0;
+
+/// Blocks the calling thread for `a` seconds, capped at `MAX_SLEEP_SECONDS`,
+/// and returns null. Non-positive and NaN durations do not sleep at all.
+///
+/// There is no `void` type for the function to return, as PostgreSQL's
+/// `pg_sleep` does, so it returns a null `timestamptz` instead: a sleep is
+/// measured in wall-clock time, and the type leaves room to report when the
+/// sleep ended without changing the function's signature.
+fn sleep(a: Datum) -> Datum {
+    let seconds = a.unwrap_float64();
+    if seconds > 0.0 {
+        thread::sleep(time::Duration::from_secs_f64(
+            seconds.min(MAX_SLEEP_SECONDS),
+        ));
+    }
+    Datum::Null
+}
+
+/// Removes diacritics from letters, e.g. by mapping `é` to `e`.
+///
+/// Characters are decomposed into their canonical base character and
+/// combining marks, and the marks from the combining diacritical mark blocks
+/// are dropped. Other combining marks, like Indic vowel signs or the
+/// variation selectors and keycaps that are part of many emoji, are
+/// preserved. Letters that have no decomposition but conventionally drop
+/// their diacritic, like `ø`, are mapped explicitly.
+fn unaccent<'a>(a: Datum<'a>, temp_storage: &'a RowArena) -> Datum<'a> {
+    let s = a.unwrap_str();
+    if s.is_ascii() {
+        return a;
+    }
+    let mut out = String::with_capacity(s.len());
+    for c in s.nfd() {
+        match c {
+            '\u{0300}'..='\u{036F}'
+            | '\u{1AB0}'..='\u{1AFF}'
+            | '\u{1DC0}'..='\u{1DFF}'
+            | '\u{FE20}'..='\u{FE2F}' => (),
+            'Æ' => out.push_str("AE"),
+            'æ' => out.push_str("ae"),
+            'Đ' => out.push('D'),
+            'đ' => out.push('d'),
+            'Ħ' => out.push('H'),
+            'ħ' => out.push('h'),
+            'ı' => out.push('i'),
+            'Ł' => out.push('L'),
+            'ł' => out.push('l'),
+            'Ø' => out.push('O'),
+            'ø' => out.push('o'),
+            'Œ' => out.push_str("OE"),
+            'œ' => out.push_str("oe"),
+            'ß' => out.push_str("ss"),
+            _ => out.push(c),
+        }
+    }
+    // Recompose whatever decomposition remains, e.g. of Hangul syllables.
+    Datum::String(temp_storage.push_string(out.nfc().collect()))
+}
+
 fn make_timestamp<'a>(datums: &[Datum<'a>]) -> Datum<'a> {
     let year: i32 = match datums[0].unwrap_int64().try_into() {
         Ok(year) => year,
@@ -4002,6 +4714,230 @@ fn make_timestamp<'a>(datums: &[Datum<'a>]) -> Datum<'a> {
     Datum::Timestamp(timestamp)
 }
 
+/// Bins `source` into the bucket of width `stride` that contains it, where the
+/// buckets are aligned to `origin`, as in PostgreSQL's `date_bin`.
+///
+/// Buckets include their start but not their end, so a `source` that lies
+/// before `origin` is rounded down, away from `origin`.
+fn date_bin(
+    stride: Interval,
+    source: NaiveDateTime,
+    origin: NaiveDateTime,
+) -> Result<NaiveDateTime, EvalError> {
+    if stride.months != 0 {
+        return Err(EvalError::InvalidParameterValue(
+            "timestamps cannot be binned into intervals containing months or years".to_owned(),
+        ));
+    }
+    let stride = stride.dur_as_micros() * 1_000;
+    if stride <= 0 {
+        return Err(EvalError::InvalidParameterValue(
+            "stride must be greater than zero".to_owned(),
+        ));
+    }
+    let nanos = |ts: NaiveDateTime| {
+        i128::from(ts.timestamp()) * 1_000_000_000 + i128::from(ts.timestamp_subsec_nanos())
+    };
+    let diff = nanos(source) - nanos(origin);
+    let binned = nanos(origin) + diff - diff.rem_euclid(stride);
+    i64::try_from(binned.div_euclid(1_000_000_000))
+        .ok()
+        .and_then(|secs| {
+            // The remainder of a Euclidean division by 10^9 always fits.
+            let nsecs = binned.rem_euclid(1_000_000_000) as u32;
+            NaiveDateTime::from_timestamp_opt(secs, nsecs)
+        })
+        .ok_or(EvalError::TimestampOutOfRange)
+}
+
+fn date_bin_timestamp<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let stride = datums[0].unwrap_interval();
+    let source = datums[1].unwrap_timestamp();
+    let origin = datums[2].unwrap_timestamp();
+    Ok(Datum::Timestamp(date_bin(stride, source, origin)?))
+}
+
+fn date_bin_timestamptz<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let stride = datums[0].unwrap_interval();
+    let source = datums[1].unwrap_timestamptz().naive_utc();
+    let origin = datums[2].unwrap_timestamptz().naive_utc();
+    Ok(Datum::TimestampTz(DateTime::<Utc>::from_utc(
+        date_bin(stride, source, origin)?,
+        Utc,
+    )))
+}
+
+fn width_bucket_float64<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let operand = datums[0].unwrap_float64();
+    let low = datums[1].unwrap_float64();
+    let high = datums[2].unwrap_float64();
+    let count = datums[3].unwrap_int32();
+    check_width_bucket_count(count)?;
+    if operand.is_nan() || low.is_nan() || high.is_nan() {
+        return Err(EvalError::InvalidParameterValue(
+            "operand, lower bound, and upper bound cannot be NaN".to_owned(),
+        ));
+    }
+    if low.is_infinite() || high.is_infinite() {
+        return Err(EvalError::InvalidParameterValue(
+            "lower and upper bounds must be finite".to_owned(),
+        ));
+    }
+    // The distance of the operand from the lower bound, as a fraction of the
+    // distance between the bounds. Like PostgreSQL, halve everything if the
+    // distance between the bounds overflows.
+    let fraction = || {
+        if (high - low).is_infinite() {
+            (operand / 2.0 - low / 2.0) / (high / 2.0 - low / 2.0)
+        } else {
+            (operand - low) / (high - low)
+        }
+    };
+    let bucket = if low < high {
+        if operand < low {
+            0
+        } else if operand >= high {
+            overflow_width_bucket(count)?
+        } else {
+            interior_width_bucket(fraction(), count)
+        }
+    } else if low > high {
+        if operand > low {
+            0
+        } else if operand <= high {
+            overflow_width_bucket(count)?
+        } else {
+            interior_width_bucket(fraction(), count)
+        }
+    } else {
+        return Err(EvalError::WidthBucketEqualBounds);
+    };
+    Ok(Datum::Int32(bucket))
+}
+
+/// Like [`width_bucket_float64`], but for decimals, which the planner ensures
+/// all have the same scale.
+fn width_bucket_decimal<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    let operand = datums[0].unwrap_decimal().as_i128();
+    let low = datums[1].unwrap_decimal().as_i128();
+    let high = datums[2].unwrap_decimal().as_i128();
+    let count = datums[3].unwrap_int32();
+    check_width_bucket_count(count)?;
+    // The differences below are never negative, but may not fit in an `i128`.
+    let bucket = if low < high {
+        if operand < low {
+            0
+        } else if operand >= high {
+            overflow_width_bucket(count)?
+        } else {
+            exact_interior_width_bucket(
+                operand.wrapping_sub(low) as u128,
+                high.wrapping_sub(low) as u128,
+                count,
+            )
+        }
+    } else if low > high {
+        if operand > low {
+            0
+        } else if operand <= high {
+            overflow_width_bucket(count)?
+        } else {
+            exact_interior_width_bucket(
+                low.wrapping_sub(operand) as u128,
+                low.wrapping_sub(high) as u128,
+                count,
+            )
+        }
+    } else {
+        return Err(EvalError::WidthBucketEqualBounds);
+    };
+    Ok(Datum::Int32(bucket))
+}
+
+fn check_width_bucket_count(count: i32) -> Result<(), EvalError> {
+    if count <= 0 {
+        return Err(EvalError::InvalidParameterValue(
+            "count must be greater than zero".to_owned(),
+        ));
+    }
+    Ok(())
+}
+
+/// Returns the bucket for operands past the upper bound.
+fn overflow_width_bucket(count: i32) -> Result<i32, EvalError> {
+    count.checked_add(1).ok_or(EvalError::Int32OutOfRange)
+}
+
+/// Returns the bucket for an operand that lies `fraction` of the way from the
+/// lower bound to the upper bound.
+fn interior_width_bucket(fraction: f64, count: i32) -> i32 {
+    // The product can round up to `count` for operands just below the upper
+    // bound, which belong to the last bucket.
+    cmp::min((fraction * f64::from(count)) as i32, count - 1) + 1
+}
+
+/// Returns the bucket for an operand that lies `offset` from the lower bound,
+/// where the bounds are `width` apart, i.e., `offset * count / width + 1`,
+/// computed exactly.
+fn exact_interior_width_bucket(offset: u128, width: u128, count: i32) -> i32 {
+    debug_assert!(offset < width && count > 0);
+    let count = count as u32;
+    // Multiply `offset` by `count` one bit at a time, keeping the running
+    // product as a quotient and remainder of `width`. As the remainder and
+    // `offset` are both less than `width`, neither doubling the remainder nor
+    // adding `offset` to it ever exceeds `width` more than once.
+    let mut quot = 0_u32;
+    let mut rem = 0_u128;
+    for bit in (0..32).rev() {
+        quot <<= 1;
+        if rem >= width - rem {
+            rem -= width - rem;
+            quot += 1;
+        } else {
+            rem *= 2;
+        }
+        if (count >> bit) & 1 == 1 {
+            if rem >= width - offset {
+                rem -= width - offset;
+                quot += 1;
+            } else {
+                rem += offset;
+            }
+        }
+    }
+    // The quotient is less than `count`, so this cannot overflow.
+    quot as i32 + 1
+}
+
+fn width_bucket_array<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let thresholds = b.unwrap_array();
+    if thresholds.dims().len() > 1 {
+        return Err(EvalError::InvalidParameterValue(
+            "thresholds must be one-dimensional array".to_owned(),
+        ));
+    }
+    let thresholds: Vec<_> = thresholds.elements().iter().collect();
+    if thresholds.contains(&Datum::Null) {
+        return Err(EvalError::InvalidParameterValue(
+            "thresholds array must not contain NULLs".to_owned(),
+        ));
+    }
+    // Count the thresholds at or below the operand. Like PostgreSQL, this
+    // assumes that the thresholds are sorted, without checking.
+    let (mut lo, mut hi) = (0, thresholds.len());
+    while lo < hi {
+        let mid = lo + (hi - lo) / 2;
+        if a < thresholds[mid] {
+            hi = mid;
+        } else {
+            lo = mid + 1;
+        }
+    }
+    i32::try_from(lo)
+        .map(Datum::Int32)
+        .map_err(|_| EvalError::Int32OutOfRange)
+}
+
 fn trim_whitespace<'a>(a: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_str().trim_matches(' '))
 }
@@ -4035,6 +4971,76 @@ fn trim_trailing<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     Datum::from(a.unwrap_str().trim_end_matches(|c| trim_chars.contains(c)))
 }
 
+/// The length, in characters, of the longest string whose distance
+/// `levenshtein` and `levenshtein_less_equal` will compute. This is the same
+/// limit as in PostgreSQL's `fuzzystrmatch` extension.
+const MAX_LEVENSHTEIN_STRLEN: usize = 255;
+
+fn levenshtein<'a>(a: Datum<'a>, b: Datum<'a>) -> Result<Datum<'a>, EvalError> {
+    let distance = levenshtein_distance(a.unwrap_str(), b.unwrap_str(), None)?;
+    Ok(Datum::from(distance))
+}
+
+fn levenshtein_less_equal<'a>(datums: &[Datum<'a>]) -> Result<Datum<'a>, EvalError> {
+    // Like PostgreSQL, a negative bound computes the exact distance.
+    let max = Some(datums[2].unwrap_int32()).filter(|max| *max >= 0);
+    let distance = levenshtein_distance(datums[0].unwrap_str(), datums[1].unwrap_str(), max)?;
+    Ok(Datum::from(distance))
+}
+
+/// Computes the number of single-character insertions, deletions, and
+/// substitutions that turn `a` into `b`, counting characters by code point.
+///
+/// If `max` is given, the computation stops as soon as the distance is known
+/// to exceed `max`, and `max + 1` is returned instead.
+fn levenshtein_distance(a: &str, b: &str, max: Option<i32>) -> Result<i32, EvalError> {
+    let cap = |distance: i32| match max {
+        Some(max) if distance > max => max + 1,
+        _ => distance,
+    };
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    // Like PostgreSQL, distances from the empty string are not subject to the
+    // length limit.
+    if a.is_empty() || b.is_empty() {
+        return Ok(cap((a.len() + b.len()) as i32));
+    }
+    if a.len() > MAX_LEVENSHTEIN_STRLEN || b.len() > MAX_LEVENSHTEIN_STRLEN {
+        return Err(EvalError::InvalidParameterValue(format!(
+            "levenshtein argument exceeds maximum length of {} characters",
+            MAX_LEVENSHTEIN_STRLEN
+        )));
+    }
+    // The length limit keeps every distance well within an `i32`.
+    let (a_len, b_len) = (a.len() as i32, b.len() as i32);
+    if let Some(max) = max {
+        if (a_len - b_len).abs() > max {
+            return Ok(max + 1);
+        }
+    }
+
+    // `prev[j]` is the distance between the first `i` characters of `a` and
+    // the first `j` characters of `b`, and `cur` the same for `i + 1`.
+    let mut prev: Vec<i32> = (0..=b_len).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.iter().enumerate() {
+        cur[0] = i as i32 + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + if ca == cb { 0 } else { 1 };
+            cur[j + 1] = substitution.min(prev[j + 1] + 1).min(cur[j] + 1);
+        }
+        // No row has a smaller minimum than the row before it, so once every
+        // entry exceeds `max`, so does the distance.
+        if let Some(max) = max {
+            if cur.iter().all(|d| *d > max) {
+                return Ok(max + 1);
+            }
+        }
+        mem::swap(&mut prev, &mut cur);
+    }
+    Ok(cap(prev[b.len()]))
+}
+
 fn list_index<'a>(a: Datum<'a>, b: Datum<'a>) -> Datum<'a> {
     let i = b.unwrap_int64();
     if i < 1 {
@@ -4203,6 +5209,153 @@ fn digest_inner<'a>(
     Ok(Datum::Bytes(temp_storage.push_bytes(bytes)))
 }
 
+/// The line length at which PostgreSQL wraps `base64`-encoded output.
+const BASE64_LINE_LENGTH: usize = 76;
+
+fn encode<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let bytes = a.unwrap_bytes();
+    let encoded = match b.unwrap_str().to_lowercase().as_str() {
+        "base64" => {
+            let encoded = base64::encode(bytes);
+            let mut out = String::with_capacity(encoded.len() + encoded.len() / BASE64_LINE_LENGTH);
+            for (i, line) in encoded.as_bytes().chunks(BASE64_LINE_LENGTH).enumerate() {
+                if i > 0 {
+                    out.push('\n');
+                }
+                out.push_str(str::from_utf8(line).expect("base64 is ASCII"));
+            }
+            out
+        }
+        // RFC 4648 §5, without padding.
+        "base64url" => base64::encode_config(bytes, base64::URL_SAFE_NO_PAD),
+        "hex" => {
+            let mut out = String::with_capacity(bytes.len() * 2);
+            for b in bytes {
+                write!(out, "{:02x}", b);
+            }
+            out
+        }
+        "escape" => {
+            let mut out = String::with_capacity(bytes.len());
+            for &b in bytes {
+                match b {
+                    b'\\' => out.push_str("\\\\"),
+                    0 | 0x80..=0xff => write!(out, "\\{:03o}", b),
+                    _ => out.push(char::from(b)),
+                }
+            }
+            out
+        }
+        _ => return Err(EvalError::InvalidBinaryEncoding(b.unwrap_str().to_owned())),
+    };
+    Ok(Datum::String(temp_storage.push_string(encoded)))
+}
+
+fn decode<'a>(
+    a: Datum<'a>,
+    b: Datum<'a>,
+    temp_storage: &'a RowArena,
+) -> Result<Datum<'a>, EvalError> {
+    let s = a.unwrap_str();
+    let bytes = match b.unwrap_str().to_lowercase().as_str() {
+        "base64" => decode_base64(s, base64::STANDARD, "base64")?,
+        "base64url" => decode_base64(s, base64::URL_SAFE_NO_PAD, "base64url")?,
+        "hex" => decode_hex(s)?,
+        "escape" => decode_escape(s)?,
+        _ => return Err(EvalError::InvalidBinaryEncoding(b.unwrap_str().to_owned())),
+    };
+    Ok(Datum::Bytes(temp_storage.push_bytes(bytes)))
+}
+
+/// Decodes `s` using the base64 alphabet of `config`, accepting both padded
+/// and unpadded input.
+fn decode_base64(s: &str, config: base64::Config, name: &str) -> Result<Vec<u8>, EvalError> {
+    // Like PostgreSQL, ignore whitespace, which permits decoding the wrapped
+    // output of `encode`.
+    let s: Vec<u8> = s.bytes().filter(|b| !b.is_ascii_whitespace()).collect();
+    let unpadded_len = s.len() - s.iter().rev().take_while(|b| **b == b'=').count();
+    let padding = s.len() - unpadded_len;
+    if padding > 2 || (padding > 0 && s.len() % 4 != 0) {
+        return Err(EvalError::InvalidParameterValue(format!(
+            "invalid {} end sequence",
+            name
+        )));
+    }
+    base64::decode_config(&s[..unpadded_len], config).map_err(|e| {
+        EvalError::InvalidParameterValue(match e {
+            base64::DecodeError::InvalidByte(_, b) => format!(
+                "invalid symbol \"{}\" found while decoding {} sequence",
+                char::from(b).escape_default(),
+                name
+            ),
+            base64::DecodeError::InvalidLength | base64::DecodeError::InvalidLastSymbol(..) => {
+                format!("invalid {} end sequence", name)
+            }
+        })
+    })
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, EvalError> {
+    fn digit(c: char) -> Result<u8, EvalError> {
+        match c.to_digit(16) {
+            Some(d) => Ok(d as u8),
+            None => Err(EvalError::InvalidParameterValue(format!(
+                "invalid hexadecimal digit: \"{}\"",
+                c
+            ))),
+        }
+    }
+
+    let mut out = Vec::with_capacity(s.len() / 2);
+    let mut chars = s.chars().filter(|c| !c.is_ascii_whitespace());
+    while let Some(hi) = chars.next() {
+        let hi = digit(hi)?;
+        let lo = match chars.next() {
+            Some(lo) => digit(lo)?,
+            None => {
+                return Err(EvalError::InvalidParameterValue(
+                    "invalid hexadecimal data: odd number of digits".into(),
+                ))
+            }
+        };
+        out.push(hi << 4 | lo);
+    }
+    Ok(out)
+}
+
+fn decode_escape(s: &str) -> Result<Vec<u8>, EvalError> {
+    let s = s.as_bytes();
+    let mut out = Vec::with_capacity(s.len());
+    let mut i = 0;
+    while i < s.len() {
+        if s[i] != b'\\' {
+            out.push(s[i]);
+            i += 1;
+            continue;
+        }
+        match s.get(i + 1..i + 4) {
+            Some([d1 @ b'0'..=b'3', d2 @ b'0'..=b'7', d3 @ b'0'..=b'7']) => {
+                out.push((d1 - b'0') << 6 | (d2 - b'0') << 3 | (d3 - b'0'));
+                i += 4;
+            }
+            _ if s.get(i + 1) == Some(&b'\\') => {
+                out.push(b'\\');
+                i += 2;
+            }
+            _ => {
+                return Err(EvalError::InvalidParameterValue(
+                    "invalid input syntax for type bytea".into(),
+                ))
+            }
+        }
+    }
+    Ok(out)
+}
+
 fn mz_render_typemod<'a>(
     oid: Datum<'a>,
     typmod: Datum<'a>,
@@ -4230,16 +5383,34 @@ fn mz_render_typemod<'a>(
     Datum::String(inner)
 }
 
+/// Computes the name-based UUID for `name` within `namespace`, as described by
+/// RFC 4122 for version 5 UUIDs.
+fn mz_uuid_v5<'a>(namespace: Datum<'a>, name: Datum<'a>) -> Datum<'a> {
+    let mut hasher = Sha1::new();
+    hasher.update(namespace.unwrap_uuid().as_bytes());
+    hasher.update(name.unwrap_str().as_bytes());
+    let mut bytes = [0; 16];
+    bytes.copy_from_slice(&hasher.finalize()[..16]);
+    // Set the version to 5 and the variant to RFC 4122.
+    bytes[6] = (bytes[6] & 0x0f) | 0x50;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    Datum::Uuid(Uuid::from_bytes(bytes))
+}
+
 #[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum VariadicFunc {
     Coalesce,
     Concat,
+    DateBinTimestamp,
+    DateBinTimestampTz,
     MakeTimestamp,
     PadLeading,
     Substr,
     Replace,
     JsonbBuildArray,
     JsonbBuildObject,
+    JsonbSet,
+    JsonbInsert,
     ArrayCreate {
         // We need to know the element type to type empty arrays.
         elem_type: ScalarType,
@@ -4247,18 +5418,30 @@ pub enum VariadicFunc {
     ArrayToString {
         elem_type: ScalarType,
     },
+    StringToArray,
+    ArrayPosition,
+    ArrayPositions,
     ListCreate {
         // We need to know the element type to type empty lists.
         elem_type: ScalarType,
     },
+    MapBuild {
+        // We need to know the value type to type empty maps.
+        value_type: ScalarType,
+    },
     RecordCreate {
         field_names: Vec<ColumnName>,
     },
     ListSlice,
     SplitPart,
     RegexpMatch,
+    RegexpSplitToArray,
     HmacString,
     HmacBytes,
+    SetBitBytes,
+    LevenshteinLessEqual,
+    WidthBucketFloat64,
+    WidthBucketDecimal,
 }
 
 impl VariadicFunc {
@@ -4283,12 +5466,16 @@ impl VariadicFunc {
         match self {
             VariadicFunc::Coalesce => coalesce(datums, temp_storage, exprs),
             VariadicFunc::Concat => Ok(eager!(text_concat_variadic, temp_storage)),
+            VariadicFunc::DateBinTimestamp => eager!(date_bin_timestamp),
+            VariadicFunc::DateBinTimestampTz => eager!(date_bin_timestamptz),
             VariadicFunc::MakeTimestamp => Ok(eager!(make_timestamp)),
             VariadicFunc::PadLeading => eager!(pad_leading, temp_storage),
             VariadicFunc::Substr => Ok(eager!(substr)),
             VariadicFunc::Replace => Ok(eager!(replace, temp_storage)),
             VariadicFunc::JsonbBuildArray => Ok(eager!(jsonb_build_array, temp_storage)),
             VariadicFunc::JsonbBuildObject => Ok(eager!(jsonb_build_object, temp_storage)),
+            VariadicFunc::JsonbSet => eager!(jsonb_set, temp_storage),
+            VariadicFunc::JsonbInsert => eager!(jsonb_insert, temp_storage),
             VariadicFunc::ArrayCreate {
                 elem_type: ScalarType::Array(_),
             } => eager!(array_create_multidim, temp_storage),
@@ -4296,14 +5483,25 @@ impl VariadicFunc {
             VariadicFunc::ArrayToString { elem_type } => {
                 eager!(array_to_string, elem_type, temp_storage)
             }
+            VariadicFunc::StringToArray => eager!(string_to_array, temp_storage),
+            VariadicFunc::ArrayPosition => eager!(array_position),
+            VariadicFunc::ArrayPositions => eager!(array_positions, temp_storage),
             VariadicFunc::ListCreate { .. } | VariadicFunc::RecordCreate { .. } => {
                 Ok(eager!(list_create, temp_storage))
             }
+            VariadicFunc::MapBuild { .. } => eager!(map_build, temp_storage),
             VariadicFunc::ListSlice => Ok(eager!(list_slice, temp_storage)),
             VariadicFunc::SplitPart => eager!(split_part),
             VariadicFunc::RegexpMatch => eager!(regexp_match_dynamic, temp_storage),
+            VariadicFunc::RegexpSplitToArray => {
+                eager!(regexp_split_to_array_dynamic, temp_storage)
+            }
             VariadicFunc::HmacString => eager!(hmac_string, temp_storage),
             VariadicFunc::HmacBytes => eager!(hmac_bytes, temp_storage),
+            VariadicFunc::SetBitBytes => eager!(set_bit_bytes, temp_storage),
+            VariadicFunc::LevenshteinLessEqual => eager!(levenshtein_less_equal),
+            VariadicFunc::WidthBucketFloat64 => eager!(width_bucket_float64),
+            VariadicFunc::WidthBucketDecimal => eager!(width_bucket_decimal),
         }
     }
 
@@ -4322,11 +5520,15 @@ impl VariadicFunc {
                 input_types.into_first().nullable(true)
             }
             Concat => ScalarType::String.nullable(true),
+            DateBinTimestamp => ScalarType::Timestamp.nullable(true),
+            DateBinTimestampTz => ScalarType::TimestampTz.nullable(true),
             MakeTimestamp => ScalarType::Timestamp.nullable(true),
             PadLeading => ScalarType::String.nullable(true),
             Substr => ScalarType::String.nullable(true),
             Replace => ScalarType::String.nullable(true),
-            JsonbBuildArray | JsonbBuildObject => ScalarType::Jsonb.nullable(true),
+            JsonbBuildArray | JsonbBuildObject | JsonbSet | JsonbInsert => {
+                ScalarType::Jsonb.nullable(true)
+            }
             ArrayCreate { elem_type } => {
                 debug_assert!(
                     input_types.iter().all(|t| t.scalar_type == *elem_type),
@@ -4338,6 +5540,9 @@ impl VariadicFunc {
                 }
             }
             ArrayToString { .. } => ScalarType::String.nullable(true),
+            StringToArray => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            ArrayPosition => ScalarType::Int32.nullable(true),
+            ArrayPositions => ScalarType::Array(Box::new(ScalarType::Int32)).nullable(true),
             ListCreate { elem_type } => {
                 debug_assert!(
                     input_types.iter().all(|t| t.scalar_type == *elem_type),
@@ -4349,6 +5554,20 @@ impl VariadicFunc {
                 }
                 .nullable(false)
             }
+            MapBuild { value_type } => {
+                debug_assert!(
+                    input_types
+                        .chunks(2)
+                        .all(|kv| kv[0].scalar_type == ScalarType::String
+                            && kv[1].scalar_type == *value_type),
+                    "Args to MapBuild should be keys of type text and values of type value_type"
+                );
+                ScalarType::Map {
+                    value_type: Box::new(value_type.clone()),
+                    custom_oid: None,
+                }
+                .nullable(false)
+            }
             ListSlice { .. } => input_types[0].scalar_type.clone().nullable(true),
             RecordCreate { field_names } => ScalarType::Record {
                 fields: field_names
@@ -4362,7 +5581,11 @@ impl VariadicFunc {
             .nullable(true),
             SplitPart => ScalarType::String.nullable(true),
             RegexpMatch => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
-            HmacString | HmacBytes => ScalarType::Bytes.nullable(true),
+            RegexpSplitToArray => ScalarType::Array(Box::new(ScalarType::String)).nullable(true),
+            HmacString | HmacBytes | SetBitBytes => ScalarType::Bytes.nullable(true),
+            LevenshteinLessEqual | WidthBucketFloat64 | WidthBucketDecimal => {
+                ScalarType::Int32.nullable(true)
+            }
         }
     }
 
@@ -4373,9 +5596,13 @@ impl VariadicFunc {
             | VariadicFunc::JsonbBuildArray
             | VariadicFunc::JsonbBuildObject
             | VariadicFunc::ListCreate { .. }
+            | VariadicFunc::MapBuild { .. }
             | VariadicFunc::RecordCreate { .. }
             | VariadicFunc::ArrayCreate { .. }
-            | VariadicFunc::ArrayToString { .. })
+            | VariadicFunc::ArrayToString { .. }
+            | VariadicFunc::StringToArray
+            | VariadicFunc::ArrayPosition
+            | VariadicFunc::ArrayPositions)
     }
 }
 
@@ -4384,20 +5611,35 @@ impl fmt::Display for VariadicFunc {
         match self {
             VariadicFunc::Coalesce => f.write_str("coalesce"),
             VariadicFunc::Concat => f.write_str("concat"),
+            VariadicFunc::DateBinTimestamp | VariadicFunc::DateBinTimestampTz => {
+                f.write_str("date_bin")
+            }
             VariadicFunc::MakeTimestamp => f.write_str("makets"),
             VariadicFunc::PadLeading => f.write_str("lpad"),
             VariadicFunc::Substr => f.write_str("substr"),
             VariadicFunc::Replace => f.write_str("replace"),
             VariadicFunc::JsonbBuildArray => f.write_str("jsonb_build_array"),
             VariadicFunc::JsonbBuildObject => f.write_str("jsonb_build_object"),
+            VariadicFunc::JsonbSet => f.write_str("jsonb_set"),
+            VariadicFunc::JsonbInsert => f.write_str("jsonb_insert"),
             VariadicFunc::ArrayCreate { .. } => f.write_str("array_create"),
             VariadicFunc::ArrayToString { .. } => f.write_str("array_to_string"),
+            VariadicFunc::StringToArray => f.write_str("string_to_array"),
+            VariadicFunc::ArrayPosition => f.write_str("array_position"),
+            VariadicFunc::ArrayPositions => f.write_str("array_positions"),
             VariadicFunc::ListCreate { .. } => f.write_str("list_create"),
+            VariadicFunc::MapBuild { .. } => f.write_str("map_build"),
             VariadicFunc::RecordCreate { .. } => f.write_str("record_create"),
             VariadicFunc::ListSlice => f.write_str("list_slice"),
             VariadicFunc::SplitPart => f.write_str("split_string"),
             VariadicFunc::RegexpMatch => f.write_str("regexp_match"),
+            VariadicFunc::RegexpSplitToArray => f.write_str("regexp_split_to_array"),
             VariadicFunc::HmacString | VariadicFunc::HmacBytes => f.write_str("hmac"),
+            VariadicFunc::SetBitBytes => f.write_str("set_bit"),
+            VariadicFunc::LevenshteinLessEqual => f.write_str("levenshtein_less_equal"),
+            VariadicFunc::WidthBucketFloat64 | VariadicFunc::WidthBucketDecimal => {
+                f.write_str("width_bucket")
+            }
         }
     }
 }
@@ -4442,6 +5684,122 @@ mod test {
         );
     }
 
+    #[test]
+    fn exact_interior_width_bucket_matches_naive() {
+        for width in 1..40_u128 {
+            for offset in 0..width {
+                for count in 1..20 {
+                    let naive = offset * u128::from(count as u32) / width + 1;
+                    assert_eq!(
+                        exact_interior_width_bucket(offset, width, count),
+                        naive as i32,
+                        "offset {} width {} count {}",
+                        offset,
+                        width,
+                        count
+                    );
+                }
+            }
+        }
+
+        // Products that overflow 128 bits.
+        let width = u128::MAX;
+        assert_eq!(
+            exact_interior_width_bucket(width - 1, width, i32::MAX),
+            i32::MAX
+        );
+        assert_eq!(
+            exact_interior_width_bucket(width / 2, width, i32::MAX),
+            1 << 30
+        );
+        assert_eq!(exact_interior_width_bucket(width / 2 + 1, width, 2), 2);
+    }
+
+    #[test]
+    fn date_bin_boundaries() {
+        let ts = |h, m, s, nanos| NaiveDate::from_ymd(2020, 2, 11).and_hms_nano(h, m, s, nanos);
+        let minutes = |m| Interval::new(0, 0, m * 60 * 1_000_000);
+        let origin = NaiveDate::from_ymd(2001, 1, 1).and_hms(0, 0, 0);
+
+        // Buckets include their start, but not their end.
+        let bin = |source| date_bin(minutes(15), source, origin).unwrap();
+        assert_eq!(bin(ts(15, 44, 17, 0)), ts(15, 30, 0, 0));
+        assert_eq!(bin(ts(15, 45, 0, 0)), ts(15, 45, 0, 0));
+        assert_eq!(bin(ts(15, 44, 59, 999_999_999)), ts(15, 30, 0, 0));
+
+        // Sources before the origin are rounded down too.
+        let origin = ts(16, 0, 0, 0);
+        let bin = |source| date_bin(minutes(15), source, origin).unwrap();
+        assert_eq!(bin(ts(15, 44, 17, 0)), ts(15, 30, 0, 0));
+        assert_eq!(bin(ts(15, 45, 0, 0)), ts(15, 45, 0, 0));
+        assert_eq!(bin(ts(16, 0, 0, 0)), ts(16, 0, 0, 0));
+
+        // Strides may be shorter than a second.
+        let stride = Interval::new(0, 0, 100_000);
+        assert_eq!(
+            date_bin(stride, ts(15, 44, 17, 123_456_000), origin).unwrap(),
+            ts(15, 44, 17, 100_000_000)
+        );
+
+        assert!(date_bin(Interval::new(1, 0, 0), origin, origin).is_err());
+        assert!(date_bin(minutes(0), origin, origin).is_err());
+        assert!(date_bin(minutes(-15), origin, origin).is_err());
+    }
+
+    #[test]
+    fn cast_string_to_decimal_precision_and_scale() {
+        // Expected values are from PostgreSQL.
+        let cases: &[(&str, u8, u8, Option<i128>)] = &[
+            ("0.2", 38, 0, Some(0)),
+            ("0.5", 1, 0, Some(1)),
+            ("-0.5", 1, 0, Some(-1)),
+            ("1.005", 5, 2, Some(101)),
+            ("-1.005", 5, 2, Some(-101)),
+            ("999.994", 5, 2, Some(99999)),
+            ("999.995", 5, 2, None),
+            ("-999.995", 5, 2, None),
+            ("1000", 5, 2, None),
+            ("0.994", 2, 2, Some(99)),
+            ("1", 2, 2, None),
+            ("1.2345e2", 5, 1, Some(1235)),
+            ("5e-3", 3, 2, Some(1)),
+            ("4e-3", 3, 2, Some(0)),
+            ("1e2", 3, 0, Some(100)),
+            ("1e3", 3, 0, None),
+            ("1", 38, 37, Some(10_i128.pow(37))),
+            ("10", 38, 37, None),
+            (
+                "99999999999999999999999999999999999999",
+                38,
+                0,
+                Some(10_i128.pow(38) - 1),
+            ),
+            // A precision of zero is unconstrained.
+            ("12345.6", 0, 0, Some(12346)),
+        ];
+        for (input, precision, scale, expected) in cases {
+            let result = cast_string_to_decimal(Datum::String(*input), *precision, *scale);
+            match expected {
+                Some(significand) => assert_eq!(
+                    result,
+                    Ok(Datum::from(*significand)),
+                    "{}::numeric({}, {})",
+                    input,
+                    precision,
+                    scale
+                ),
+                None => assert_eq!(
+                    result,
+                    Err(EvalError::NumericFieldOverflow),
+                    "{}::numeric({}, {})",
+                    input,
+                    precision,
+                    scale
+                ),
+            }
+        }
+    }
+
     fn ym(year: i32, month: u32) -> NaiveDateTime {
         NaiveDate::from_ymd(year, month, 1).and_hms(9, 9, 9)
     }
@@ -13,12 +13,14 @@
 
 use std::convert::TryFrom;
 use std::fmt;
+use std::iter;
 
 use aho_corasick::AhoCorasickBuilder;
 use enum_iterator::IntoEnumIterator;
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 
 use crate::scalar::func::TimestampLike;
+use crate::EvalError;
 
 /// The raw tokens that can appear in a format string. Many of these tokens
 /// overlap, in which case the longest matching token should be selected.
@@ -882,3 +884,317 @@ impl DateTimeFormat {
         out
     }
 }
+
+/// A node in a compiled numeric format string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NumericFormatNode {
+    /// A digit position, specified by `9`, or by `0` if leading zeros are to
+    /// be printed from this position onwards.
+    Digit { zero: bool },
+    /// The decimal point, specified by `.` or `D`.
+    DecimalPoint,
+    /// The group separator, specified by `,` or `G`.
+    GroupSeparator,
+    /// The sign of the number, specified by `S`. If it precedes every digit
+    /// position, it is anchored to the number.
+    Sign { anchored: bool },
+    /// The minus sign for negative numbers, specified by `MI`.
+    Minus,
+    /// A character to be emitted as is.
+    Literal(char),
+}
+
+/// The numeric format patterns that PostgreSQL supports but we do not. They
+/// are rejected rather than emitted literally.
+const UNSUPPORTED_NUMERIC_PATTERNS: &[&str] = &[
+    "EEEE", "eeee", "PL", "pl", "PR", "pr", "RN", "rn", "SG", "sg", "TH", "th", "L", "l", "V", "v",
+];
+
+/// A compiled numeric format string.
+#[derive(Debug)]
+pub struct NumericFormat {
+    nodes: Vec<NumericFormatNode>,
+    /// Whether `FM` appeared anywhere in the format string.
+    fill_mode: bool,
+    /// The number of digit positions before the decimal point.
+    pre: usize,
+    /// The number of digit positions after the decimal point.
+    post: usize,
+    /// The integer digit position of the first `0`, if any.
+    zero_start: Option<usize>,
+    /// The fractional digit position of the last `0`, if any.
+    zero_end: Option<usize>,
+    /// Whether the format string specifies where the sign goes.
+    explicit_sign: bool,
+}
+
+impl NumericFormat {
+    /// Compiles a new `NumericFormat` from the input string `s`.
+    ///
+    /// Only a subset of PostgreSQL's numeric format patterns is supported;
+    /// the others produce an error.
+    pub fn compile(s: &str) -> Result<NumericFormat, EvalError> {
+        let mut nodes = vec![];
+        let mut fill_mode = false;
+        let mut sign = None;
+        let mut seen_decimal_point = false;
+        let mut seen_digit = false;
+        let (mut pre, mut post) = (0, 0);
+        let (mut zero_start, mut zero_end) = (None, None);
+
+        let mut rest = s;
+        while let Some(c) = rest.chars().next() {
+            if c == '"' {
+                // Quoted text is taken literally, with backslash escaping the
+                // next character.
+                let mut chars = rest[1..].char_indices();
+                let mut end = rest.len();
+                while let Some((i, c)) = chars.next() {
+                    match c {
+                        '"' => {
+                            end = i + 2;
+                            break;
+                        }
+                        '\\' => {
+                            if let Some((_, c)) = chars.next() {
+                                nodes.push(NumericFormatNode::Literal(c));
+                            }
+                        }
+                        _ => nodes.push(NumericFormatNode::Literal(c)),
+                    }
+                }
+                rest = &rest[end..];
+                continue;
+            }
+            if let Some(pattern) = UNSUPPORTED_NUMERIC_PATTERNS
+                .iter()
+                .find(|p| rest.starts_with(**p))
+            {
+                return Err(EvalError::UnsupportedToCharPattern(pattern.to_string()));
+            }
+            let (node, len) = match c {
+                '9' | '0' => {
+                    let zero = c == '0';
+                    if seen_decimal_point {
+                        if zero {
+                            zero_end = Some(post);
+                        }
+                        post += 1;
+                    } else {
+                        if zero && zero_start.is_none() {
+                            zero_start = Some(pre);
+                        }
+                        pre += 1;
+                    }
+                    seen_digit = true;
+                    (Some(NumericFormatNode::Digit { zero }), 1)
+                }
+                '.' | 'D' | 'd' => {
+                    if seen_decimal_point {
+                        return Err(EvalError::InvalidParameterValue(
+                            "multiple decimal points".into(),
+                        ));
+                    }
+                    seen_decimal_point = true;
+                    (Some(NumericFormatNode::DecimalPoint), 1)
+                }
+                ',' | 'G' | 'g' => (Some(NumericFormatNode::GroupSeparator), 1),
+                'F' | 'f' if rest[1..].starts_with(if c == 'F' { 'M' } else { 'm' }) => {
+                    fill_mode = true;
+                    (None, 2)
+                }
+                'M' | 'm' if rest[1..].starts_with(if c == 'M' { 'I' } else { 'i' }) => {
+                    (Some(NumericFormatNode::Minus), 2)
+                }
+                'S' | 's' => (
+                    Some(NumericFormatNode::Sign {
+                        anchored: !seen_digit,
+                    }),
+                    1,
+                ),
+                _ => (Some(NumericFormatNode::Literal(c)), c.len_utf8()),
+            };
+            let name = match node {
+                Some(NumericFormatNode::Sign { .. }) => Some("S"),
+                Some(NumericFormatNode::Minus) => Some("MI"),
+                _ => None,
+            };
+            if let Some(name) = name {
+                match sign.replace(name) {
+                    Some(prev) if prev == name => {
+                        return Err(EvalError::InvalidParameterValue(format!(
+                            "cannot use \"{}\" twice",
+                            name
+                        )))
+                    }
+                    Some(_) => {
+                        return Err(EvalError::InvalidParameterValue(
+                            "cannot use \"S\" and \"MI\" together".into(),
+                        ))
+                    }
+                    None => (),
+                }
+            }
+            nodes.extend(node);
+            rest = &rest[len..];
+        }
+
+        Ok(NumericFormat {
+            nodes,
+            fill_mode,
+            pre,
+            post,
+            zero_start,
+            zero_end,
+            explicit_sign: sign.is_some(),
+        })
+    }
+
+    /// Renders the number `significand * 10^-scale` using the format string.
+    pub fn render_decimal(&self, significand: i128, scale: u8) -> String {
+        let negative = significand < 0;
+        let abs = significand.abs();
+        let scale = usize::from(scale);
+        // Round half away from zero to the number of fractional digit
+        // positions.
+        let mut digits = if self.post < scale {
+            let factor = 10_i128.pow((scale - self.post) as u32);
+            let mut rounded = abs / factor;
+            // Compare the remainder against its complement rather than
+            // doubling it, which could overflow when `factor` is 10^38.
+            let remainder = abs % factor;
+            if remainder >= factor - remainder {
+                rounded += 1;
+            }
+            rounded.to_string()
+        } else {
+            let mut digits = abs.to_string();
+            digits.extend(iter::repeat('0').take(self.post - scale));
+            digits
+        };
+        if digits.len() <= self.post {
+            digits.insert_str(0, &"0".repeat(self.post + 1 - digits.len()));
+        }
+        let negative = negative && digits.bytes().any(|b| b != b'0');
+        let (int, frac) = digits.split_at(digits.len() - self.post);
+        self.render(negative, Some((int, frac)))
+    }
+
+    /// Renders the floating-point number `f` using the format string.
+    pub fn render_f64(&self, f: f64) -> String {
+        if !f.is_finite() {
+            return self.render(f.is_sign_negative() && !f.is_nan(), None);
+        }
+        let digits = format!("{:.*}", self.post, f.abs());
+        let negative = f < 0.0 && digits.bytes().any(|b| b != b'0' && b != b'.');
+        let (int, frac) = match digits.find('.') {
+            Some(i) => (&digits[..i], &digits[i + 1..]),
+            None => (&digits[..], ""),
+        };
+        self.render(negative, Some((int, frac)))
+    }
+
+    /// Renders a number with the specified sign and digits, or, if `digits`
+    /// is `None` or its integer part does not fit into the digit positions
+    /// before the decimal point, a `#` in place of every digit.
+    fn render(&self, negative: bool, digits: Option<(&str, &str)>) -> String {
+        let digits = digits.map(|(int, frac)| match int {
+            // A zero integer part needs no digit positions.
+            "0" if self.pre == 0 => ("", frac),
+            _ => (int, frac),
+        });
+        let (int, frac) = match digits {
+            Some((int, frac)) if int.len() <= self.pre => (int.to_owned(), frac.to_owned()),
+            _ => ("#".repeat(self.pre), "#".repeat(self.post)),
+        };
+        let (int, frac) = (int.as_bytes(), frac.as_bytes());
+        let leading = self.pre - int.len();
+        // Like PostgreSQL, omit the zero integer part of numbers between -1
+        // and 1, unless a `0` requests it.
+        let omit_zero = self.zero_start.is_none() && int == b"0" && self.post > 0;
+        // In fill mode, trailing zeros in the fractional part are omitted,
+        // except where a `0` requests them.
+        let last_frac_digit = if self.fill_mode {
+            let last_nonzero = frac.iter().rposition(|b| *b != b'0');
+            last_nonzero.max(self.zero_end)
+        } else {
+            Some(self.post)
+        };
+
+        let mut out = String::new();
+        // Without an explicit sign, a minus sign, or a space for positive
+        // numbers, is placed right before the first printed digit.
+        let mut anchored_sign = if self
+            .nodes
+            .contains(&NumericFormatNode::Sign { anchored: true })
+        {
+            Some(if negative { '-' } else { '+' })
+        } else if self.explicit_sign || (!negative && self.fill_mode) {
+            None
+        } else if negative {
+            Some('-')
+        } else {
+            Some(' ')
+        };
+        let (mut int_pos, mut frac_pos) = (0, 0);
+        let mut started = false;
+        let mut in_frac = false;
+        for node in &self.nodes {
+            match node {
+                NumericFormatNode::Digit { zero } if in_frac => {
+                    if *zero || last_frac_digit.map_or(false, |last| frac_pos <= last) {
+                        out.push(char::from(frac[frac_pos]));
+                    }
+                    frac_pos += 1;
+                }
+                NumericFormatNode::Digit { .. } => {
+                    let pos = int_pos;
+                    int_pos += 1;
+                    let digit = if pos < leading {
+                        if self.zero_start.map_or(false, |start| start <= pos) {
+                            Some('0')
+                        } else {
+                            None
+                        }
+                    } else if omit_zero && pos == leading {
+                        if self.fill_mode && last_frac_digit.is_none() {
+                            // Never print a bare decimal point.
+                            Some('0')
+                        } else {
+                            None
+                        }
+                    } else {
+                        Some(char::from(int[pos - leading]))
+                    };
+                    match digit {
+                        Some(digit) => {
+                            out.extend(anchored_sign.take());
+                            out.push(digit);
+                            started = true;
+                        }
+                        None if self.fill_mode => (),
+                        None => out.push(' '),
+                    }
+                }
+                NumericFormatNode::DecimalPoint => {
+                    out.extend(anchored_sign.take());
+                    out.push('.');
+                    started = true;
+                    in_frac = true;
+                }
+                NumericFormatNode::GroupSeparator if started => out.push(','),
+                NumericFormatNode::GroupSeparator if self.fill_mode => (),
+                NumericFormatNode::GroupSeparator => out.push(' '),
+                NumericFormatNode::Sign { anchored: true } => (),
+                NumericFormatNode::Sign { anchored: false } => {
+                    out.push(if negative { '-' } else { '+' })
+                }
+                NumericFormatNode::Minus if negative => out.push('-'),
+                NumericFormatNode::Minus if self.fill_mode => (),
+                NumericFormatNode::Minus => out.push(' '),
+                NumericFormatNode::Literal(c) => out.push(*c),
+            }
+        }
+        out
+    }
+}
@@ -0,0 +1,662 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! SQL/JSON path expressions.
+//!
+//! Only a subset of the path language is supported: the `lax` mode accessors
+//! `.key`, `."key"`, `.*`, `[*]`, and `[n]` (including `[n to m]` ranges and
+//! lists of subscripts), and filter expressions like `? (@.x > 1)` that combine
+//! comparisons with `&&`, `||`, and `!`. Other constructs, like `strict` mode,
+//! variables, item methods, and arithmetic, are rejected with
+//! [`JsonPathError::Unsupported`] rather than evaluated incorrectly.
+//!
+//! https://www.postgresql.org/docs/current/functions-json.html#FUNCTIONS-SQLJSON-PATH
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+
+use repr::Datum;
+
+/// An error that can occur when parsing a JSON path.
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub enum JsonPathError {
+    /// The path is not a valid SQL/JSON path.
+    Syntax(String),
+    /// The path is valid, but uses a construct that is not yet supported.
+    Unsupported(String),
+}
+
+impl fmt::Display for JsonPathError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            JsonPathError::Syntax(e) => write!(f, "syntax error in jsonpath: {}", e),
+            JsonPathError::Unsupported(construct) => {
+                write!(f, "jsonpath {} not yet supported", construct)
+            }
+        }
+    }
+}
+
+impl std::error::Error for JsonPathError {}
+
+/// A parsed SQL/JSON path, evaluated in `lax` mode.
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub struct JsonPath {
+    source: String,
+    accessors: Vec<Accessor>,
+}
+
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+enum Accessor {
+    /// `.key` or `."key"`.
+    Member(String),
+    /// `.*`.
+    MemberWildcard,
+    /// `[*]`.
+    ElementWildcard,
+    /// `[a, b to c, ...]`, as inclusive ranges of subscripts.
+    Elements(Vec<(i64, i64)>),
+    /// `? (predicate)`.
+    Filter(Predicate),
+}
+
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+enum Predicate {
+    Comparison(Operand, ComparisonOp, Operand),
+    And(Box<Predicate>, Box<Predicate>),
+    Or(Box<Predicate>, Box<Predicate>),
+    Not(Box<Predicate>),
+}
+
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+enum Operand {
+    /// A path that starts at the current item (`@`) or the root (`$`).
+    Path {
+        current: bool,
+        accessors: Vec<Accessor>,
+    },
+    Literal(Literal),
+}
+
+#[derive(Ord, PartialOrd, Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+enum Literal {
+    Null,
+    Bool(bool),
+    Number(OrderedFloat<f64>),
+    String(String),
+}
+
+#[derive(Ord, PartialOrd, Clone, Copy, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+enum ComparisonOp {
+    Eq,
+    NotEq,
+    Lt,
+    LtEq,
+    Gt,
+    GtEq,
+}
+
+impl JsonPath {
+    /// Parses a SQL/JSON path.
+    pub fn parse(s: &str) -> Result<JsonPath, JsonPathError> {
+        let mut parser = Parser { s, pos: 0 };
+        match parser.peek_ident() {
+            Some("strict") => return Err(JsonPathError::Unsupported("strict mode".into())),
+            Some("lax") => parser.pos += "lax".len(),
+            _ => (),
+        }
+        parser.expect_root("$")?;
+        let accessors = parser.parse_accessors()?;
+        parser.expect_end()?;
+        Ok(JsonPath {
+            source: s.into(),
+            accessors,
+        })
+    }
+
+    /// Returns the source text of the path.
+    pub fn as_str(&self) -> &str {
+        &self.source
+    }
+
+    /// Returns the sequence of items that the path selects from `target`,
+    /// which must be a `jsonb` datum.
+    pub fn query<'a>(&self, target: Datum<'a>) -> Vec<Datum<'a>> {
+        apply_accessors(&self.accessors, target, vec![target])
+    }
+}
+
+impl fmt::Display for JsonPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+struct Parser<'a> {
+    s: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn rest(&self) -> &'a str {
+        &self.s[self.pos..]
+    }
+
+    fn skip_whitespace(&mut self) {
+        let rest = self.rest();
+        self.pos += rest.len() - rest.trim_start().len();
+    }
+
+    fn peek(&mut self) -> Option<char> {
+        self.skip_whitespace();
+        self.rest().chars().next()
+    }
+
+    /// Consumes `token` if it is next in the input.
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        if self.rest().starts_with(token) {
+            self.pos += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn expect(&mut self, token: &str) -> Result<(), JsonPathError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(&format!("\"{}\"", token)))
+        }
+    }
+
+    /// Consumes the start of a path, `$` or `@`, rejecting variables like
+    /// `$name`.
+    fn expect_root(&mut self, token: &str) -> Result<(), JsonPathError> {
+        self.expect(token)?;
+        match self.rest().chars().next() {
+            Some(c) if is_ident_char(c) => Err(JsonPathError::Unsupported("variables".into())),
+            _ => Ok(()),
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), JsonPathError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        if rest.is_empty() {
+            Ok(())
+        } else if ["==", "!=", "<>", "<", ">", "&&", "||", "!"]
+            .iter()
+            .any(|op| rest.starts_with(op))
+        {
+            Err(JsonPathError::Unsupported(
+                "predicate check expressions".into(),
+            ))
+        } else if rest.starts_with(|c: char| "+-*/%".contains(c)) {
+            Err(JsonPathError::Unsupported("arithmetic expressions".into()))
+        } else {
+            Err(self.unexpected("end of path"))
+        }
+    }
+
+    fn unexpected(&self, expected: &str) -> JsonPathError {
+        match self.rest().chars().next() {
+            None => JsonPathError::Syntax(format!("expected {}, found end of path", expected)),
+            Some(c) => JsonPathError::Syntax(format!(
+                "expected {}, found \"{}\" at position {}",
+                expected,
+                c,
+                self.pos + 1
+            )),
+        }
+    }
+
+    /// Returns the identifier that is next in the input without consuming it.
+    fn peek_ident(&mut self) -> Option<&'a str> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let len = rest.find(|c| !is_ident_char(c)).unwrap_or(rest.len());
+        if len == 0 {
+            None
+        } else {
+            Some(&rest[..len])
+        }
+    }
+
+    fn parse_accessors(&mut self) -> Result<Vec<Accessor>, JsonPathError> {
+        let mut accessors = vec![];
+        loop {
+            if self.eat(".") {
+                if self.eat("**") {
+                    return Err(JsonPathError::Unsupported(
+                        "recursive wildcard member accessors".into(),
+                    ));
+                } else if self.eat("*") {
+                    accessors.push(Accessor::MemberWildcard);
+                } else if self.peek() == Some('"') {
+                    accessors.push(Accessor::Member(self.parse_string()?));
+                } else if let Some(key) = self.peek_ident() {
+                    self.pos += key.len();
+                    if self.peek() == Some('(') {
+                        return Err(JsonPathError::Unsupported(format!(
+                            "item method .{}()",
+                            key
+                        )));
+                    }
+                    accessors.push(Accessor::Member(key.into()));
+                } else {
+                    return Err(self.unexpected("member name"));
+                }
+            } else if self.eat("[") {
+                if self.eat("*") {
+                    self.expect("]")?;
+                    accessors.push(Accessor::ElementWildcard);
+                } else {
+                    let mut ranges = vec![];
+                    loop {
+                        let from = self.parse_subscript()?;
+                        let to = if self.peek_ident() == Some("to") {
+                            self.pos += "to".len();
+                            self.parse_subscript()?
+                        } else {
+                            from
+                        };
+                        ranges.push((from, to));
+                        if !self.eat(",") {
+                            break;
+                        }
+                    }
+                    self.expect("]")?;
+                    accessors.push(Accessor::Elements(ranges));
+                }
+            } else if self.eat("?") {
+                self.expect("(")?;
+                let predicate = self.parse_disjunction()?;
+                self.expect(")")?;
+                accessors.push(Accessor::Filter(predicate));
+            } else {
+                return Ok(accessors);
+            }
+        }
+    }
+
+    fn parse_subscript(&mut self) -> Result<i64, JsonPathError> {
+        if self.peek_ident() == Some("last") {
+            return Err(JsonPathError::Unsupported("last array subscripts".into()));
+        }
+        let n = self.parse_number()?;
+        if n.fract() != 0.0 || n.abs() > i64::MAX as f64 {
+            return Err(JsonPathError::Syntax(format!(
+                "array subscript {} is not an integer",
+                n
+            )));
+        }
+        Ok(n as i64)
+    }
+
+    fn parse_disjunction(&mut self) -> Result<Predicate, JsonPathError> {
+        let mut predicate = self.parse_conjunction()?;
+        while self.eat("||") {
+            let right = self.parse_conjunction()?;
+            predicate = Predicate::Or(Box::new(predicate), Box::new(right));
+        }
+        Ok(predicate)
+    }
+
+    fn parse_conjunction(&mut self) -> Result<Predicate, JsonPathError> {
+        let mut predicate = self.parse_negation()?;
+        while self.eat("&&") {
+            let right = self.parse_negation()?;
+            predicate = Predicate::And(Box::new(predicate), Box::new(right));
+        }
+        Ok(predicate)
+    }
+
+    fn parse_negation(&mut self) -> Result<Predicate, JsonPathError> {
+        if self.eat("!") {
+            Ok(Predicate::Not(Box::new(self.parse_negation()?)))
+        } else if self.eat("(") {
+            let predicate = self.parse_disjunction()?;
+            self.expect(")")?;
+            Ok(predicate)
+        } else {
+            self.parse_comparison()
+        }
+    }
+
+    fn parse_comparison(&mut self) -> Result<Predicate, JsonPathError> {
+        if self.peek_ident() == Some("exists") {
+            return Err(JsonPathError::Unsupported("exists predicates".into()));
+        }
+        let left = self.parse_operand()?;
+        let op = if self.eat("==") {
+            ComparisonOp::Eq
+        } else if self.eat("!=") || self.eat("<>") {
+            ComparisonOp::NotEq
+        } else if self.eat("<=") {
+            ComparisonOp::LtEq
+        } else if self.eat(">=") {
+            ComparisonOp::GtEq
+        } else if self.eat("<") {
+            ComparisonOp::Lt
+        } else if self.eat(">") {
+            ComparisonOp::Gt
+        } else {
+            return match self.peek_ident() {
+                Some(keyword @ "like_regex") | Some(keyword @ "starts") => Err(
+                    JsonPathError::Unsupported(format!("{} predicates", keyword)),
+                ),
+                Some("is") => Err(JsonPathError::Unsupported("is unknown predicates".into())),
+                _ => Err(self.unexpected("comparison operator")),
+            };
+        };
+        let right = self.parse_operand()?;
+        Ok(Predicate::Comparison(left, op, right))
+    }
+
+    fn parse_operand(&mut self) -> Result<Operand, JsonPathError> {
+        let operand = match self.peek() {
+            Some(c @ '@') | Some(c @ '$') => {
+                self.expect_root(&c.to_string())?;
+                Operand::Path {
+                    current: c == '@',
+                    accessors: self.parse_accessors()?,
+                }
+            }
+            Some('"') => Operand::Literal(Literal::String(self.parse_string()?)),
+            Some(c) if c == '-' || c == '.' || c.is_ascii_digit() => {
+                Operand::Literal(Literal::Number(self.parse_number()?.into()))
+            }
+            _ => {
+                let ident = self.peek_ident().unwrap_or_default();
+                let literal = match ident {
+                    "null" => Literal::Null,
+                    "true" => Literal::Bool(true),
+                    "false" => Literal::Bool(false),
+                    _ => return Err(self.unexpected("operand")),
+                };
+                self.pos += ident.len();
+                Operand::Literal(literal)
+            }
+        };
+        match self.peek() {
+            Some(c) if "+-*/%".contains(c) => {
+                Err(JsonPathError::Unsupported("arithmetic expressions".into()))
+            }
+            _ => Ok(operand),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<f64, JsonPathError> {
+        self.skip_whitespace();
+        let rest = self.rest();
+        let mut len = 0;
+        let mut prev = None;
+        for c in rest.chars() {
+            let valid = c.is_ascii_digit()
+                || c == '.'
+                || c == 'e'
+                || c == 'E'
+                || (c == '-' && (len == 0 || prev == Some('e') || prev == Some('E')))
+                || (c == '+' && (prev == Some('e') || prev == Some('E')));
+            if !valid {
+                break;
+            }
+            len += c.len_utf8();
+            prev = Some(c);
+        }
+        match rest[..len].parse() {
+            Ok(n) => {
+                self.pos += len;
+                Ok(n)
+            }
+            Err(_) => Err(self.unexpected("number")),
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<String, JsonPathError> {
+        self.expect("\"")?;
+        let mut out = String::new();
+        let mut chars = self.rest().char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    self.pos += i + 1;
+                    return Ok(out);
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('"') => out.push('"'),
+                    Some('\\') => out.push('\\'),
+                    Some('/') => out.push('/'),
+                    Some('b') => out.push('\u{8}'),
+                    Some('f') => out.push('\u{c}'),
+                    Some('n') => out.push('\n'),
+                    Some('r') => out.push('\r'),
+                    Some('t') => out.push('\t'),
+                    Some('u') => {
+                        let hex: String = chars.by_ref().take(4).map(|(_, c)| c).collect();
+                        match u32::from_str_radix(&hex, 16).ok().and_then(char::from_u32) {
+                            Some(c) if hex.len() == 4 => out.push(c),
+                            _ => {
+                                return Err(JsonPathError::Syntax(format!(
+                                    "invalid unicode escape \"\\u{}\"",
+                                    hex
+                                )))
+                            }
+                        }
+                    }
+                    Some(c) => {
+                        return Err(JsonPathError::Syntax(format!(
+                            "invalid escape sequence \"\\{}\"",
+                            c
+                        )))
+                    }
+                    None => break,
+                },
+                c => out.push(c),
+            }
+        }
+        Err(JsonPathError::Syntax("unterminated string literal".into()))
+    }
+}
+
+fn is_ident_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// In lax mode, arrays are unwrapped before member accessors, filters, and
+/// comparisons are applied to them.
+fn unwrap_array(item: Datum) -> Vec<Datum> {
+    match item {
+        Datum::List(list) => list.iter().collect(),
+        _ => vec![item],
+    }
+}
+
+fn apply_accessors<'a>(
+    accessors: &[Accessor],
+    root: Datum<'a>,
+    mut items: Vec<Datum<'a>>,
+) -> Vec<Datum<'a>> {
+    for accessor in accessors {
+        let mut next = vec![];
+        for item in items {
+            accessor.apply(root, item, &mut next);
+        }
+        items = next;
+    }
+    items
+}
+
+impl Accessor {
+    fn apply<'a>(&self, root: Datum<'a>, item: Datum<'a>, out: &mut Vec<Datum<'a>>) {
+        match self {
+            Accessor::Member(key) => {
+                for item in unwrap_array(item) {
+                    if let Datum::Map(dict) = item {
+                        out.extend(
+                            dict.iter()
+                                .filter(|(k, _)| *k == key.as_str())
+                                .map(|(_, v)| v),
+                        );
+                    }
+                }
+            }
+            Accessor::MemberWildcard => {
+                for item in unwrap_array(item) {
+                    if let Datum::Map(dict) = item {
+                        out.extend(dict.iter().map(|(_, v)| v));
+                    }
+                }
+            }
+            // In lax mode, subscripting a non-array treats it as an array
+            // that contains only that item.
+            Accessor::ElementWildcard => out.extend(unwrap_array(item)),
+            Accessor::Elements(ranges) => {
+                let elems = unwrap_array(item);
+                if elems.is_empty() {
+                    return;
+                }
+                // Clamp each range to the array's bounds up front, so that
+                // huge subscripts like `$[0 to 1e18]` don't iterate over
+                // indices that can never match.
+                let last = (elems.len() - 1) as i64;
+                for (from, to) in ranges {
+                    for i in (*from).max(0)..=(*to).min(last) {
+                        out.push(elems[i as usize]);
+                    }
+                }
+            }
+            Accessor::Filter(predicate) => {
+                for item in unwrap_array(item) {
+                    if predicate.eval(root, item) == Some(true) {
+                        out.push(item);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A scalar item that can take part in a comparison.
+#[derive(Clone, Copy)]
+enum Item<'a> {
+    Null,
+    Bool(bool),
+    Number(f64),
+    String(&'a str),
+    /// An array or object, which cannot be compared.
+    Container,
+}
+
+impl<'a> From<Datum<'a>> for Item<'a> {
+    fn from(datum: Datum<'a>) -> Item<'a> {
+        match datum {
+            Datum::JsonNull => Item::Null,
+            Datum::True => Item::Bool(true),
+            Datum::False => Item::Bool(false),
+            Datum::Float64(n) => Item::Number(n.into_inner()),
+            Datum::String(s) => Item::String(s),
+            _ => Item::Container,
+        }
+    }
+}
+
+impl Predicate {
+    /// Evaluates the predicate against the current item, returning `None` if
+    /// the result is unknown.
+    fn eval<'a>(&'a self, root: Datum<'a>, current: Datum<'a>) -> Option<bool> {
+        match self {
+            // Comparisons are existential: they are true if any pair of items
+            // from the two sides satisfy the comparison, and unknown if no pair
+            // does and some pair could not be compared.
+            Predicate::Comparison(left, op, right) => {
+                let left = left.eval(root, current);
+                let right = right.eval(root, current);
+                let mut unknown = false;
+                for l in &left {
+                    for r in &right {
+                        match compare(*l, *op, *r) {
+                            Some(true) => return Some(true),
+                            Some(false) => (),
+                            None => unknown = true,
+                        }
+                    }
+                }
+                if unknown {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            Predicate::And(left, right) => {
+                match (left.eval(root, current), right.eval(root, current)) {
+                    (Some(false), _) | (_, Some(false)) => Some(false),
+                    (Some(true), Some(true)) => Some(true),
+                    _ => None,
+                }
+            }
+            Predicate::Or(left, right) => {
+                match (left.eval(root, current), right.eval(root, current)) {
+                    (Some(true), _) | (_, Some(true)) => Some(true),
+                    (Some(false), Some(false)) => Some(false),
+                    _ => None,
+                }
+            }
+            Predicate::Not(predicate) => predicate.eval(root, current).map(|b| !b),
+        }
+    }
+}
+
+impl Operand {
+    fn eval<'a>(&'a self, root: Datum<'a>, current: Datum<'a>) -> Vec<Item<'a>> {
+        match self {
+            Operand::Path {
+                current: is_current,
+                accessors,
+            } => {
+                let start = if *is_current { current } else { root };
+                apply_accessors(accessors, root, vec![start])
+                    .into_iter()
+                    .flat_map(unwrap_array)
+                    .map(Item::from)
+                    .collect()
+            }
+            Operand::Literal(Literal::Null) => vec![Item::Null],
+            Operand::Literal(Literal::Bool(b)) => vec![Item::Bool(*b)],
+            Operand::Literal(Literal::Number(n)) => vec![Item::Number(n.into_inner())],
+            Operand::Literal(Literal::String(s)) => vec![Item::String(s)],
+        }
+    }
+}
+
+fn compare(left: Item, op: ComparisonOp, right: Item) -> Option<bool> {
+    let ordering = match (left, right) {
+        (Item::Null, Item::Null) => Ordering::Equal,
+        // Nulls are not equal to, and not ordered with respect to, any other
+        // item.
+        (Item::Null, _) | (_, Item::Null) => return Some(op == ComparisonOp::NotEq),
+        (Item::Bool(l), Item::Bool(r)) => l.cmp(&r),
+        (Item::Number(l), Item::Number(r)) => l.partial_cmp(&r)?,
+        (Item::String(l), Item::String(r)) => l.cmp(r),
+        _ => return None,
+    };
+    Some(match op {
+        ComparisonOp::Eq => ordering == Ordering::Equal,
+        ComparisonOp::NotEq => ordering != Ordering::Equal,
+        ComparisonOp::Lt => ordering == Ordering::Less,
+        ComparisonOp::LtEq => ordering != Ordering::Greater,
+        ComparisonOp::Gt => ordering == Ordering::Greater,
+        ComparisonOp::GtEq => ordering != Ordering::Less,
+    })
+}
@@ -20,6 +20,7 @@ use repr::adt::regex::Regex;
 use repr::strconv::ParseError;
 use repr::{ColumnType, Datum, RelationType, Row, RowArena, ScalarType};
 
+use self::func::jsonpath::{JsonPath, JsonPathError};
 use self::func::{BinaryFunc, NullaryFunc, UnaryFunc, VariadicFunc};
 use crate::explain;
 use crate::scalar::func::parse_timezone;
@@ -296,7 +297,7 @@ impl MirScalarExpr {
             | MirScalarExpr::Literal(_, _)
             | MirScalarExpr::CallNullary(_) => (),
             MirScalarExpr::CallUnary { func, expr } => {
-                if expr.is_literal() {
+                if expr.is_literal() && !func.is_nondeterministic() {
                     *e = eval(e);
                 } else if *func == UnaryFunc::IsNull {
                     // (<expr1> <op> <expr2>) IS NULL can often be simplified to
@@ -445,6 +446,18 @@ impl MirScalarExpr {
                             MirScalarExpr::literal(Err(err), e.typ(&relation_type).scalar_type)
                         }
                     }
+                } else if *func == BinaryFunc::JsonbPathQueryFirst && expr2.is_literal() {
+                    let path = expr2.as_literal_str().unwrap();
+                    *e = match JsonPath::parse(path) {
+                        Ok(path) => MirScalarExpr::CallUnary {
+                            func: UnaryFunc::JsonbPathQueryFirst(path),
+                            expr: Box::new(expr1.take()),
+                        },
+                        Err(err) => MirScalarExpr::literal(
+                            Err(EvalError::InvalidJsonPath(err)),
+                            e.typ(&relation_type).scalar_type,
+                        ),
+                    }
                 } else if *func == BinaryFunc::And {
                     // If we are here, not both inputs are literals.
                     if expr1.is_literal_false() || expr2.is_literal_true() {
@@ -508,17 +521,23 @@ impl MirScalarExpr {
                 } else if let Some(err) = exprs.iter().find_map(|e| e.as_literal_err()) {
                     *e =
                         MirScalarExpr::literal(Err(err.clone()), e.typ(&relation_type).scalar_type);
-                } else if *func == VariadicFunc::RegexpMatch {
+                } else if *func == VariadicFunc::RegexpMatch
+                    || *func == VariadicFunc::RegexpSplitToArray
+                {
                     if exprs[1].is_literal() && exprs.get(2).map_or(true, |e| e.is_literal()) {
                         let needle = exprs[1].as_literal_str().unwrap();
                         let flags = match exprs.len() {
                             3 => exprs[2].as_literal_str().unwrap(),
                             _ => "",
                         };
+                        let unary_func = match func {
+                            VariadicFunc::RegexpMatch => UnaryFunc::RegexpMatch,
+                            _ => UnaryFunc::RegexpSplitToArray,
+                        };
                         *e = match func::build_regex(needle, flags) {
                             Ok(regex) => mem::take(exprs)
                                 .into_first()
-                                .call_unary(UnaryFunc::RegexpMatch(Regex(regex))),
+                                .call_unary(unary_func(Regex(regex))),
                             Err(err) => {
                                 MirScalarExpr::literal(Err(err), e.typ(&relation_type).scalar_type)
                             }
@@ -651,6 +670,20 @@ impl MirScalarExpr {
         }
     }
 
+    /// True iff the expression contains a nullary function, like
+    /// `NullaryFunc::MzLogicalTimestamp`, or a non-deterministic unary
+    /// function, like `UnaryFunc::Sleep`, which must not be evaluated ahead of
+    /// the query that contains them.
+    pub fn contains_nondeterministic(&self) -> bool {
+        let mut contains = false;
+        self.visit(&mut |e| match e {
+            MirScalarExpr::CallNullary(_) => contains = true,
+            MirScalarExpr::CallUnary { func, .. } if func.is_nondeterministic() => contains = true,
+            _ => (),
+        });
+        contains
+    }
+
     /// True iff the expression contains `NullaryFunc::MzLogicalTimestamp`.
     pub fn contains_temporal(&self) -> bool {
         let mut contains = false;
@@ -712,6 +745,7 @@ pub enum EvalError {
     InvalidArray(InvalidArrayError),
     InvalidEncodingName(String),
     InvalidHashAlgorithm(String),
+    InvalidBinaryEncoding(String),
     InvalidByteSequence {
         byte_sequence: String,
         encoding_name: String,
@@ -722,7 +756,11 @@ pub enum EvalError {
     NegSqrt,
     UnknownUnits(String),
     UnsupportedDateTimeUnits(DateTimeUnits),
+    UnsupportedToCharPattern(String),
     UnterminatedLikeEscapeSequence,
+    InvalidJsonPath(JsonPathError),
+    WidthBucketEqualBounds,
+    NullMapKey,
     Parse(ParseError),
     Internal(String),
 }
@@ -751,6 +789,9 @@ impl fmt::Display for EvalError {
             EvalError::InvalidArray(e) => e.fmt(f),
             EvalError::InvalidEncodingName(name) => write!(f, "invalid encoding name '{}'", name),
             EvalError::InvalidHashAlgorithm(alg) => write!(f, "invalid hash algorithm '{}'", alg),
+            EvalError::InvalidBinaryEncoding(name) => {
+                write!(f, "unrecognized encoding: \"{}\"", name)
+            }
             EvalError::InvalidByteSequence {
                 byte_sequence,
                 encoding_name,
@@ -767,9 +808,17 @@ impl fmt::Display for EvalError {
             EvalError::UnsupportedDateTimeUnits(units) => {
                 write!(f, "unsupported timestamp units '{}'", units)
             }
+            EvalError::UnsupportedToCharPattern(pattern) => {
+                write!(f, "unsupported to_char pattern \"{}\"", pattern)
+            }
             EvalError::UnterminatedLikeEscapeSequence => {
                 f.write_str("unterminated escape sequence in LIKE")
             }
+            EvalError::InvalidJsonPath(e) => e.fmt(f),
+            EvalError::WidthBucketEqualBounds => {
+                f.write_str("lower bound cannot equal upper bound")
+            }
+            EvalError::NullMapKey => f.write_str("map keys cannot be NULL"),
             EvalError::Parse(e) => e.fmt(f),
             EvalError::Internal(s) => write!(f, "internal error: {}", s),
         }
@@ -790,6 +839,12 @@ impl From<InvalidArrayError> for EvalError {
     }
 }
 
+impl From<JsonPathError> for EvalError {
+    fn from(e: JsonPathError) -> EvalError {
+        EvalError::InvalidJsonPath(e)
+    }
+}
+
 impl From<regex::Error> for EvalError {
     fn from(e: regex::Error) -> EvalError {
         EvalError::InvalidRegex(e.to_string())
@@ -9,9 +9,8 @@
 
 use criterion::{black_box, Criterion, Throughput};
 use protobuf::Message;
-use serde_protobuf::descriptor::Descriptors;
 
-use interchange::protobuf::Decoder;
+use interchange::protobuf::{Decoder, DescriptorSet};
 
 use gen::benchmark::{Connector, Record, Value};
 
@@ -65,7 +64,7 @@ pub fn bench_protobuf(c: &mut Criterion) {
         .expect("record failed to serialize to bytes");
     let len = buf.len() as u64;
     let mut decoder = Decoder::new(
-        Descriptors::from_proto(&gen::file_descriptor_set()),
+        DescriptorSet::from_proto(&gen::file_descriptor_set()),
         ".bench.Record",
     );
 
@@ -31,8 +31,8 @@ use sha2::Sha256;
 use uuid::Uuid;
 
 use mz_avro::schema::{
-    resolve_schemas, RecordField, Schema, SchemaFingerprint, SchemaNode, SchemaPiece,
-    SchemaPieceOrNamed,
+    resolve_schemas, resolve_schemas_with_null_defaults, RecordField, Schema, SchemaFingerprint,
+    SchemaNode, SchemaPiece, SchemaPieceOrNamed,
 };
 use mz_avro::{
     define_unexpected,
@@ -2062,10 +2062,19 @@ pub struct ConfluentAvroResolver {
 }
 
 impl ConfluentAvroResolver {
-    pub fn new(reader_schema: &str, config: Option<ccsr::ClientConfig>) -> anyhow::Result<Self> {
+    /// Creates a resolver that decodes with `reader_schema`, resolving the
+    /// schemas of writers found in the schema registry described by `config`.
+    ///
+    /// If `null_defaults` is set, nullable reader fields that are missing
+    /// from a writer's schema decode as null, even if they have no default.
+    pub fn new(
+        reader_schema: &str,
+        config: Option<ccsr::ClientConfig>,
+        null_defaults: bool,
+    ) -> anyhow::Result<Self> {
         let reader_schema = parse_schema(reader_schema)?;
-        let writer_schemas =
-            config.map(|sr| SchemaCache::new(sr, reader_schema.fingerprint::<Sha256>()));
+        let writer_schemas = config
+            .map(|sr| SchemaCache::new(sr, reader_schema.fingerprint::<Sha256>(), null_defaults));
         Ok(Self {
             reader_schema,
             writer_schemas,
@@ -2172,7 +2181,15 @@ impl Decoder {
         );
         let debezium_dedup =
             debezium_dedup.map(|strat| DebeziumDeduplicationState::new(strat, key_indices));
-        let csr_avro = ConfluentAvroResolver::new(reader_schema, schema_registry)?;
+        // Debezium sources must survive additive schema migrations upstream,
+        // which drop columns from the `before` and `after` records of old
+        // messages. Writers' extra columns are always ignored by schema
+        // resolution.
+        let csr_avro = ConfluentAvroResolver::new(
+            reader_schema,
+            schema_registry,
+            envelope == EnvelopeType::Debezium,
+        )?;
 
         Ok(Decoder {
             csr_avro,
@@ -2323,6 +2340,38 @@ pub fn encode_debezium_transaction_unchecked(
     buf
 }
 
+/// Decodes a record written by [`encode_debezium_transaction_unchecked`],
+/// returning its transaction ID, status, and event count.
+pub fn decode_debezium_transaction(
+    buf: &[u8],
+) -> Result<(String, String, Option<i64>), anyhow::Error> {
+    if buf.len() < 5 || buf[0] != 0 {
+        bail!("consistency record is missing its Confluent header");
+    }
+    let value = mz_avro::from_avro_datum(&DEBEZIUM_TRANSACTION_SCHEMA, &mut &buf[5..])?;
+    let fields = match value {
+        Value::Record(fields) => fields,
+        _ => bail!("consistency record is not an Avro record"),
+    };
+    let (mut id, mut status, mut event_count) = (None, None, None);
+    for (name, value) in fields {
+        match (name.as_str(), value) {
+            ("id", Value::String(s)) => id = Some(s),
+            ("status", Value::String(s)) => status = Some(s),
+            ("event_count", Value::Union { inner, .. }) => {
+                if let Value::Long(n) = *inner {
+                    event_count = Some(n);
+                }
+            }
+            (name, _) => bail!("unexpected field {} in consistency record", name),
+        }
+    }
+    match (id, status) {
+        (Some(id), Some(status)) => Ok((id, status, event_count)),
+        _ => bail!("consistency record is missing its id or status"),
+    }
+}
+
 fn encode_avro_header(buf: &mut Vec<u8>, schema_id: i32) {
     // The first byte is a magic byte (0) that indicates the Confluent
     // serialization format version, and the next four bytes are a
@@ -2545,7 +2594,7 @@ impl<'a> mz_avro::types::ToAvro for TypedDatum<'a> {
                     let iv = datum.unwrap_interval();
                     let mut buf = Vec::with_capacity(24);
                     buf.extend(&iv.months.to_le_bytes());
-                    buf.extend(&iv.duration.to_le_bytes());
+                    buf.extend(&(iv.dur_as_micros() * 1_000).to_le_bytes());
                     debug_assert_eq!(buf.len(), 20);
                     buf
                 }),
@@ -2589,17 +2638,20 @@ struct SchemaCache {
     ccsr_client: ccsr::Client,
 
     reader_fingerprint: SchemaFingerprint,
+    null_defaults: bool,
 }
 
 impl SchemaCache {
     fn new(
         schema_registry: ccsr::ClientConfig,
         reader_fingerprint: SchemaFingerprint,
+        null_defaults: bool,
     ) -> SchemaCache {
         SchemaCache {
             cache: HashMap::new(),
             ccsr_client: schema_registry.build(),
             reader_fingerprint,
+            null_defaults,
         }
     }
 
@@ -2624,13 +2676,18 @@ impl SchemaCache {
                 // which  we don't want to repeat for every record. So, parse and resolve it, and cache the
                 // result (whether schema or error).
                 let rf = &self.reader_fingerprint.bytes;
+                let null_defaults = self.null_defaults;
                 let result = Schema::from_str(&response.raw).and_then(|schema| {
                     if &schema.fingerprint::<Sha256>().bytes == rf {
                         Ok(schema)
                     } else {
                         // the writer schema differs from the reader schema,
                         // so we need to perform schema resolution.
-                        let resolved = resolve_schemas(&schema, reader_schema)?;
+                        let resolved = if null_defaults {
+                            resolve_schemas_with_null_defaults(&schema, reader_schema)?
+                        } else {
+                            resolve_schemas(&schema, reader_schema)?
+                        };
                         Ok(resolved)
                     }
                 });
@@ -3052,9 +3109,10 @@ mod tests {
     use serde::Deserialize;
     use std::fs::File;
 
+    use futures::executor::block_on;
     use mz_avro::types::{DecimalValue, Value};
     use repr::adt::decimal::Significand;
-    use repr::{Datum, RelationDesc};
+    use repr::{Datum, RelationDesc, Row};
 
     use super::*;
 
@@ -3159,4 +3217,117 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    /// Test that decimals of every scale decode correctly from both `bytes`
+    /// and `fixed` encodings.
+    fn test_decimal_decoding() -> anyhow::Result<()> {
+        // Unscaled values and their encodings, as produced by the Java
+        // reference implementation, which writes the minimal big-endian two's
+        // complement representation of the unscaled value.
+        let cases: Vec<(u8, u8, i128, Vec<u8>)> = vec![
+            (10, 0, 0, vec![0x00]),
+            (10, 0, 1234567890, vec![0x49, 0x96, 0x02, 0xd2]),
+            (10, 0, -1234567890, vec![0xb6, 0x69, 0xfd, 0x2e]),
+            (20, 10, 0, vec![0x00]),
+            (
+                20,
+                10,
+                123456789012345,
+                vec![0x70, 0x48, 0x86, 0x0d, 0xdf, 0x79],
+            ),
+            (
+                20,
+                10,
+                -123456789012345,
+                vec![0x8f, 0xb7, 0x79, 0xf2, 0x20, 0x87],
+            ),
+            (38, 38, 0, vec![0x00]),
+            (38, 38, -1, vec![0xff]),
+            (
+                38,
+                38,
+                12345678901234567890123456789012345678,
+                vec![
+                    0x09, 0x49, 0xb0, 0xf6, 0xf0, 0x02, 0x33, 0x13, 0xc4, 0x49, 0x90, 0x50, 0xde,
+                    0x38, 0xf3, 0x4e,
+                ],
+            ),
+            (
+                38,
+                38,
+                -12345678901234567890123456789012345678,
+                vec![
+                    0xf6, 0xb6, 0x4f, 0x09, 0x0f, 0xfd, 0xcc, 0xec, 0x3b, 0xb6, 0x6f, 0xaf, 0x21,
+                    0xc7, 0x0c, 0xb2,
+                ],
+            ),
+        ];
+        for (precision, scale, unscaled, encoded) in cases {
+            let schema = format!(
+                r#"{{
+                    "type": "record",
+                    "name": "row",
+                    "fields": [
+                        {{"name": "b", "type": {{"type": "bytes", "logicalType": "decimal", "precision": {p}, "scale": {s}}}}},
+                        {{"name": "f16", "type": {{"type": "fixed", "name": "f16", "size": 16, "logicalType": "decimal", "precision": {p}, "scale": {s}}}}},
+                        {{"name": "f20", "type": {{"type": "fixed", "name": "f20", "size": 20, "logicalType": "decimal", "precision": {p}, "scale": {s}}}}}
+                    ]
+                }}"#,
+                p = precision,
+                s = scale
+            );
+            let typ = ScalarType::Decimal(precision, scale).nullable(false);
+            assert_eq!(
+                validate_value_schema(&schema, EnvelopeType::None)?,
+                vec![
+                    ("b".into(), typ.clone()),
+                    ("f16".into(), typ.clone()),
+                    ("f20".into(), typ),
+                ]
+            );
+
+            // The Confluent magic byte and schema ID, followed by the
+            // zig-zag encoded length of the `bytes` field and its contents,
+            // followed by the `fixed` fields, which are sign-extended to fill
+            // their size.
+            let mut buf = vec![0, 0, 0, 0, 0, (encoded.len() * 2) as u8];
+            buf.extend(&encoded);
+            let sign = if encoded[0] & 0x80 != 0 { 0xff } else { 0x00 };
+            for size in &[16, 20] {
+                buf.extend(std::iter::repeat(sign).take(size - encoded.len()));
+                buf.extend(&encoded);
+            }
+
+            let mut decoder = Decoder::new(
+                &schema,
+                None,
+                EnvelopeType::None,
+                "test".into(),
+                0,
+                None,
+                None,
+            )?;
+            let row = block_on(decoder.decode(&buf, None, None))?.after;
+            let datum = Datum::Decimal(Significand::new(unscaled));
+            assert_eq!(row, Some(Row::pack_slice(&[datum, datum, datum])));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_debezium_transaction_roundtrip() -> anyhow::Result<()> {
+        let begin = encode_debezium_transaction_unchecked(7, "1612", "BEGIN", None);
+        assert_eq!(
+            decode_debezium_transaction(&begin)?,
+            ("1612".into(), "BEGIN".into(), None)
+        );
+        let end = encode_debezium_transaction_unchecked(7, "1612", "END", Some(3));
+        assert_eq!(
+            decode_debezium_transaction(&end)?,
+            ("1612".into(), "END".into(), Some(3))
+        );
+        assert!(decode_debezium_transaction(&end[..4]).is_err());
+        Ok(())
+    }
 }
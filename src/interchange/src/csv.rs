@@ -0,0 +1,231 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Typed CSV columns.
+//!
+//! CSV sources decode every column as `text` unless they are given explicit
+//! column types, which can be inferred from a sample of the source's rows.
+
+use anyhow::{anyhow, bail, Result};
+
+use repr::adt::decimal::{Significand, MAX_DECIMAL_PRECISION};
+use repr::{strconv, Datum, ScalarType};
+
+/// The types that can be inferred for a CSV column, from narrowest to widest.
+const CANDIDATES: &[ScalarType] = &[
+    ScalarType::Int64,
+    ScalarType::Decimal(MAX_DECIMAL_PRECISION, 0),
+    ScalarType::Timestamp,
+    ScalarType::Bool,
+    ScalarType::String,
+];
+
+fn fits(field: &str, candidate: &ScalarType) -> bool {
+    match candidate {
+        ScalarType::Int64 => strconv::parse_int64(field).is_ok(),
+        ScalarType::Decimal(..) => strconv::parse_decimal(field).is_ok(),
+        ScalarType::Timestamp => strconv::parse_timestamp(field).is_ok(),
+        ScalarType::Bool => strconv::parse_bool(field).is_ok(),
+        _ => true,
+    }
+}
+
+/// Infers the type of each of the `n_cols` columns of a CSV source from a
+/// sample of its rows.
+///
+/// Each column is assigned the first of `bigint`, `numeric`, `timestamp`,
+/// `boolean`, and `text` that can represent the column's value in every
+/// sampled row. Inferred `numeric` columns use the largest scale that appears
+/// in the sample. Rows that do not have exactly `n_cols` fields are ignored,
+/// as the CSV decoder ignores them too. Without any sampled rows, every column
+/// is `text`.
+pub fn infer_column_types<'a, I>(rows: I, n_cols: usize) -> Vec<ScalarType>
+where
+    I: IntoIterator<Item = Vec<&'a str>>,
+{
+    let rows: Vec<_> = rows.into_iter().filter(|row| row.len() == n_cols).collect();
+    if rows.is_empty() {
+        return vec![ScalarType::String; n_cols];
+    }
+    (0..n_cols)
+        .map(|i| {
+            let candidate = CANDIDATES
+                .iter()
+                .find(|candidate| rows.iter().all(|row| fits(row[i], candidate)))
+                .expect("text fits every field");
+            match candidate {
+                ScalarType::Decimal(precision, _) => {
+                    let scale = rows
+                        .iter()
+                        .map(|row| {
+                            strconv::parse_decimal(row[i])
+                                .expect("candidate known to fit")
+                                .scale()
+                        })
+                        .max()
+                        .unwrap_or(0);
+                    ScalarType::Decimal(*precision, scale)
+                }
+                _ => candidate.clone(),
+            }
+        })
+        .collect()
+}
+
+/// Parses a CSV field as a datum of type `ty`, which must be one of the types
+/// returned by [`infer_column_types`].
+///
+/// Values with more fractional digits than a `numeric` column's scale are
+/// rounded, as in a cast.
+pub fn parse_field<'a>(field: &'a str, ty: &ScalarType) -> Result<Datum<'a>> {
+    Ok(match ty {
+        ScalarType::Int64 => Datum::Int64(strconv::parse_int64(field)?),
+        ScalarType::Decimal(_, scale) => {
+            let decimal = strconv::parse_decimal(field)?;
+            let significand = if decimal.scale() <= *scale {
+                10_i128
+                    .checked_pow(u32::from(*scale - decimal.scale()))
+                    .and_then(|factor| decimal.significand().checked_mul(factor))
+                    .ok_or_else(|| anyhow!("numeric field overflow: {}", field))?
+            } else {
+                let places = i64::from(*scale);
+                let factor = 10_i128.pow(u32::from(decimal.scale() - *scale));
+                decimal.round(places).significand() / factor
+            };
+            Datum::Decimal(Significand::new(significand))
+        }
+        ScalarType::Timestamp => Datum::Timestamp(strconv::parse_timestamp(field)?),
+        ScalarType::Bool => Datum::from(strconv::parse_bool(field)?),
+        ScalarType::String => Datum::String(field),
+        _ => bail!("unsupported CSV column type: {:?}", ty),
+    })
+}
+
+/// Encodes column types for the `column_types` option of a CSV source.
+pub fn encode_column_types(types: &[ScalarType]) -> String {
+    let names: Vec<_> = types
+        .iter()
+        .map(|ty| match ty {
+            ScalarType::Int64 => "bigint".into(),
+            ScalarType::Decimal(precision, scale) => format!("numeric({},{})", precision, scale),
+            ScalarType::Timestamp => "timestamp".into(),
+            ScalarType::Bool => "boolean".into(),
+            ScalarType::String => "text".into(),
+            _ => unreachable!("{:?} is not a CSV column type", ty),
+        })
+        .collect();
+    serde_json::to_string(&names).expect("string arrays are valid JSON")
+}
+
+/// Decodes the `column_types` option of a CSV source, as produced by
+/// [`encode_column_types`].
+pub fn decode_column_types(s: &str) -> Result<Vec<ScalarType>> {
+    let names: Vec<String> = serde_json::from_str(s)
+        .map_err(|e| anyhow!("column_types must be a JSON array of type names: {}", e))?;
+    names
+        .iter()
+        .map(|name| {
+            Ok(match name.as_str() {
+                "bigint" => ScalarType::Int64,
+                "timestamp" => ScalarType::Timestamp,
+                "boolean" => ScalarType::Bool,
+                "text" => ScalarType::String,
+                _ => match name
+                    .strip_prefix("numeric(")
+                    .and_then(|s| s.strip_suffix(')'))
+                    .and_then(|s| {
+                        let mut parts = s.splitn(2, ',');
+                        Some((
+                            parts.next()?.parse::<u8>().ok()?,
+                            parts.next()?.parse::<u8>().ok()?,
+                        ))
+                    }) {
+                    Some((precision, scale))
+                        if precision <= MAX_DECIMAL_PRECISION && scale <= precision =>
+                    {
+                        ScalarType::Decimal(precision, scale)
+                    }
+                    _ => bail!("unsupported CSV column type: {}", name),
+                },
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::*;
+
+    #[test]
+    fn test_infer_column_types() {
+        let rows = vec![
+            vec!["1", "1", "2020-01-01 00:00:00", "true", "a", "1"],
+            vec!["2", "2.25", "2020-01-02", "f", "1", "1.5"],
+            vec!["short row"],
+            vec!["-3", "3.5", "2020-01-03 12:00:00", "yes", "1.5", "no"],
+        ];
+        assert_eq!(
+            infer_column_types(rows, 6),
+            vec![
+                ScalarType::Int64,
+                ScalarType::Decimal(38, 2),
+                ScalarType::Timestamp,
+                ScalarType::Bool,
+                ScalarType::String,
+                ScalarType::String,
+            ]
+        );
+
+        // Without any rows, nothing can be narrowed.
+        assert_eq!(infer_column_types(vec![], 1), vec![ScalarType::String]);
+    }
+
+    #[test]
+    fn test_parse_field() -> Result<()> {
+        assert_eq!(parse_field("42", &ScalarType::Int64)?, Datum::Int64(42));
+        assert_eq!(
+            parse_field("1.5", &ScalarType::Decimal(38, 2))?,
+            Datum::Decimal(Significand::new(150))
+        );
+        assert_eq!(
+            parse_field("1.125", &ScalarType::Decimal(38, 2))?,
+            Datum::Decimal(Significand::new(113))
+        );
+        assert_eq!(
+            parse_field("2020-01-02", &ScalarType::Timestamp)?,
+            Datum::Timestamp(NaiveDate::from_ymd(2020, 1, 2).and_hms(0, 0, 0))
+        );
+        assert_eq!(parse_field("t", &ScalarType::Bool)?, Datum::True);
+        assert_eq!(parse_field("a", &ScalarType::String)?, Datum::String("a"));
+        assert!(parse_field("a", &ScalarType::Int64).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_column_types_roundtrip() -> Result<()> {
+        let types = vec![
+            ScalarType::Int64,
+            ScalarType::Decimal(38, 2),
+            ScalarType::Timestamp,
+            ScalarType::Bool,
+            ScalarType::String,
+        ];
+        let encoded = encode_column_types(&types);
+        assert_eq!(
+            encoded,
+            r#"["bigint","numeric(38,2)","timestamp","boolean","text"]"#
+        );
+        assert_eq!(decode_column_types(&encoded)?, types);
+        assert!(decode_column_types(r#"["numeric(39,2)"]"#).is_err());
+        assert!(decode_column_types("bigint").is_err());
+        Ok(())
+    }
+}
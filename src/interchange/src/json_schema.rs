@@ -0,0 +1,525 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! JSON Schema source connector.
+//!
+//! Translates a [JSON Schema] that describes a JSON object into the columns
+//! of a relation, and decodes JSON documents that conform to that schema into
+//! rows.
+//!
+//! [JSON Schema]: https://json-schema.org
+
+use anyhow::{anyhow, bail, Context, Result};
+use ordered_float::OrderedFloat;
+use serde_json::{Map, Number, Value};
+
+use repr::adt::decimal::{Decimal, Significand, MAX_DECIMAL_PRECISION};
+use repr::adt::jsonb::Jsonb;
+use repr::{ColumnName, ColumnType, Datum, Row, RowPacker, ScalarType};
+
+/// A parsed JSON Schema.
+#[derive(Debug, Clone)]
+struct Node {
+    kind: Kind,
+    /// Whether the schema admits `null` in addition to values of `kind`.
+    nullable: bool,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    Bool,
+    Int64,
+    Float64,
+    /// A number that the schema requires to be a multiple of `10^-scale`.
+    Decimal(u8),
+    String,
+    /// A value whose structure the schema does not pin down.
+    Jsonb,
+    List(Box<Node>),
+    Record(Vec<Property>),
+}
+
+#[derive(Debug, Clone)]
+struct Property {
+    name: String,
+    node: Node,
+    required: bool,
+}
+
+impl Node {
+    fn jsonb() -> Node {
+        Node {
+            kind: Kind::Jsonb,
+            nullable: true,
+        }
+    }
+
+    fn scalar_type(&self) -> ScalarType {
+        match &self.kind {
+            Kind::Bool => ScalarType::Bool,
+            Kind::Int64 => ScalarType::Int64,
+            Kind::Float64 => ScalarType::Float64,
+            Kind::Decimal(scale) => ScalarType::Decimal(MAX_DECIMAL_PRECISION, *scale),
+            Kind::String => ScalarType::String,
+            Kind::Jsonb => ScalarType::Jsonb,
+            Kind::List(element) => ScalarType::List {
+                element_type: Box::new(element.scalar_type()),
+                custom_oid: None,
+            },
+            Kind::Record(properties) => ScalarType::Record {
+                fields: properties
+                    .iter()
+                    .map(|p| (ColumnName::from(p.name.as_str()), p.column_type()))
+                    .collect(),
+                custom_oid: None,
+                custom_name: None,
+            },
+        }
+    }
+}
+
+impl Kind {
+    fn name(&self) -> &'static str {
+        match self {
+            Kind::Bool => "boolean",
+            Kind::Int64 => "integer",
+            Kind::Float64 | Kind::Decimal(_) => "number",
+            Kind::String => "string",
+            Kind::Jsonb => "any value",
+            Kind::List(_) => "array",
+            Kind::Record(_) => "object",
+        }
+    }
+}
+
+impl Property {
+    /// Optional properties may be absent, so they are always nullable.
+    fn column_type(&self) -> ColumnType {
+        self.node
+            .scalar_type()
+            .nullable(self.node.nullable || !self.required)
+    }
+}
+
+fn parse_schema(schema: &str) -> Result<Vec<Property>> {
+    let schema: Value = serde_json::from_str(schema).context("parsing JSON Schema")?;
+    match parse_node(&schema)?.kind {
+        Kind::Record(properties) => Ok(properties),
+        _ => bail!("JSON Schema must describe an object with properties at the top level"),
+    }
+}
+
+fn parse_node(schema: &Value) -> Result<Node> {
+    let schema = match schema {
+        // The `true` schema admits any value.
+        Value::Bool(true) => return Ok(Node::jsonb()),
+        Value::Object(schema) => schema,
+        _ => bail!("unsupported JSON Schema: {}", schema),
+    };
+    let mut types = match schema.get("type") {
+        None => vec![],
+        Some(Value::String(ty)) => vec![ty.as_str()],
+        Some(Value::Array(tys)) => tys
+            .iter()
+            .map(|ty| {
+                ty.as_str()
+                    .ok_or_else(|| anyhow!("invalid type in JSON Schema: {}", ty))
+            })
+            .collect::<Result<_>>()?,
+        Some(ty) => bail!("invalid type in JSON Schema: {}", ty),
+    };
+    let nullable = types.is_empty() || types.contains(&"null");
+    types.retain(|ty| *ty != "null");
+    let kind = match types.as_slice() {
+        ["boolean"] => Kind::Bool,
+        ["integer"] => Kind::Int64,
+        ["number"] => match schema.get("multipleOf").map(decimal_scale).transpose()? {
+            Some(Some(scale)) => Kind::Decimal(scale),
+            _ => Kind::Float64,
+        },
+        ["string"] => Kind::String,
+        ["array"] => match schema.get("items") {
+            Some(items @ Value::Object(_)) => Kind::List(Box::new(parse_node(items)?)),
+            // Tuple validation and unconstrained items can both mix types
+            // within the array.
+            _ => Kind::List(Box::new(Node::jsonb())),
+        },
+        ["object"] => match schema.get("properties") {
+            Some(Value::Object(properties)) => Kind::Record(parse_properties(schema, properties)?),
+            None => Kind::Jsonb,
+            Some(properties) => bail!("invalid properties in JSON Schema: {}", properties),
+        },
+        // Either no type or several types are permitted, so the value could
+        // be anything.
+        _ => Kind::Jsonb,
+    };
+    Ok(Node { kind, nullable })
+}
+
+fn parse_properties(
+    schema: &Map<String, Value>,
+    properties: &Map<String, Value>,
+) -> Result<Vec<Property>> {
+    let required = match schema.get("required") {
+        None => vec![],
+        Some(Value::Array(required)) => required
+            .iter()
+            .map(|name| {
+                name.as_str()
+                    .ok_or_else(|| anyhow!("invalid required property in JSON Schema: {}", name))
+            })
+            .collect::<Result<Vec<_>>>()?,
+        Some(required) => bail!("invalid required properties in JSON Schema: {}", required),
+    };
+    properties
+        .iter()
+        .map(|(name, schema)| {
+            Ok(Property {
+                name: name.clone(),
+                node: parse_node(schema)
+                    .with_context(|| format!("parsing JSON Schema for property \"{}\"", name))?,
+                required: required.contains(&name.as_str()),
+            })
+        })
+        .collect()
+}
+
+/// Strips trailing zeros from the fractional part of a decimal.
+fn normalize_decimal(decimal: &Decimal) -> (i128, u8) {
+    let mut significand = decimal.significand();
+    let mut scale = decimal.scale();
+    while scale > 0 && significand % 10 == 0 {
+        significand /= 10;
+        scale -= 1;
+    }
+    (significand, scale)
+}
+
+/// Returns `s` if `multiple_of` is exactly `10^-s`.
+///
+/// Returns an error if `s` exceeds the maximum scale of a numeric.
+fn decimal_scale(multiple_of: &Value) -> Result<Option<u8>> {
+    let n = match multiple_of {
+        Value::Number(n) => n.to_string(),
+        _ => return Ok(None),
+    };
+    let scale = match power_of_ten_exponent(&n) {
+        Some(exponent) if exponent <= 0 => -exponent,
+        _ => return Ok(None),
+    };
+    if scale > i32::from(MAX_DECIMAL_PRECISION) {
+        bail!(
+            "multipleOf {} requires a scale of {}, but the maximum scale of numeric is {}",
+            n,
+            scale,
+            MAX_DECIMAL_PRECISION
+        );
+    }
+    Ok(Some(scale as u8))
+}
+
+/// Returns `e` if the number written as `s`, in JSON's number syntax, is
+/// exactly `10^e`.
+fn power_of_ten_exponent(s: &str) -> Option<i32> {
+    let (mantissa, exponent) = match s.find(|c| c == 'e' || c == 'E') {
+        Some(i) => (&s[..i], s[i + 1..].parse::<i32>().ok()?),
+        None => (s, 0),
+    };
+    let (int, frac) = match mantissa.find('.') {
+        Some(i) => (&mantissa[..i], &mantissa[i + 1..]),
+        None => (mantissa, ""),
+    };
+    let digits = format!("{}{}", int, frac);
+    let digits = digits.trim_start_matches('0');
+    if digits.trim_end_matches('0') != "1" {
+        return None;
+    }
+    // The digits are a one followed by zeros, which the fractional digits
+    // shift to the right.
+    Some(exponent + digits.len() as i32 - 1 - frac.len() as i32)
+}
+
+fn decimal_from_number(n: &Number, scale: u8) -> Result<Significand> {
+    let decimal: Decimal = n.to_string().parse()?;
+    let (significand, s) = normalize_decimal(&decimal);
+    if s > scale {
+        bail!("{} is not a multiple of 1e-{}", n, scale);
+    }
+    10_i128
+        .checked_pow(u32::from(scale - s))
+        .and_then(|factor| significand.checked_mul(factor))
+        .map(Significand::new)
+        .ok_or_else(|| anyhow!("{} is out of range for numeric", n))
+}
+
+/// Converts a JSON Schema into the columns of a relation.
+///
+/// The schema must describe an object. Each of its properties becomes a
+/// column, in lexicographic order of the property names. Properties that are
+/// not required, or whose type permits `null`, become nullable columns.
+pub fn validate_value_schema(schema: &str) -> Result<Vec<(ColumnName, ColumnType)>> {
+    Ok(parse_schema(schema)?
+        .iter()
+        .map(|p| (ColumnName::from(p.name.as_str()), p.column_type()))
+        .collect())
+}
+
+/// Decodes JSON documents that conform to a JSON Schema into rows.
+#[derive(Debug)]
+pub struct Decoder {
+    properties: Vec<Property>,
+    packer: RowPacker,
+}
+
+impl Decoder {
+    /// Builds a decoder for documents described by the JSON Schema `schema`.
+    pub fn new(schema: &str) -> Result<Decoder> {
+        Ok(Decoder {
+            properties: parse_schema(schema)?,
+            packer: RowPacker::new(),
+        })
+    }
+
+    /// Decodes a JSON document, which may be prefixed with the header of the
+    /// Confluent wire format.
+    ///
+    /// Documents that do not conform to the schema, e.g. because they are
+    /// missing a required property, produce an error.
+    pub fn decode(&mut self, bytes: &[u8]) -> Result<Row> {
+        let bytes = match bytes {
+            [0, _, _, _, _, rest @ ..] => rest,
+            _ => bytes,
+        };
+        let value: Value = serde_json::from_slice(bytes).context("parsing JSON")?;
+        let object = match &value {
+            Value::Object(object) => object,
+            _ => bail!("expected object, found {}", value),
+        };
+        match pack_properties(&self.properties, object, &mut self.packer) {
+            Ok(()) => Ok(self.packer.finish_and_reuse()),
+            Err(e) => {
+                self.packer.clear();
+                Err(e)
+            }
+        }
+    }
+}
+
+fn pack_properties(
+    properties: &[Property],
+    object: &Map<String, Value>,
+    packer: &mut RowPacker,
+) -> Result<()> {
+    for property in properties {
+        match object.get(&property.name) {
+            Some(value) => pack_value(&property.node, value, packer)
+                .with_context(|| format!("invalid value for property \"{}\"", property.name))?,
+            None if property.required => {
+                bail!("missing required property \"{}\"", property.name)
+            }
+            None => packer.push(Datum::Null),
+        }
+    }
+    Ok(())
+}
+
+fn pack_value(node: &Node, value: &Value, packer: &mut RowPacker) -> Result<()> {
+    match (&node.kind, value) {
+        (_, Value::Null) if node.nullable => packer.push(Datum::Null),
+        (Kind::Jsonb, _) => packer.push(
+            Jsonb::from_serde_json(value.clone())?
+                .into_row()
+                .unpack_first(),
+        ),
+        (Kind::Bool, Value::Bool(b)) => packer.push(Datum::from(*b)),
+        (Kind::Int64, Value::Number(n)) => match (n.as_i64(), n.as_f64()) {
+            (Some(i), _) => packer.push(Datum::Int64(i)),
+            // JSON Schema considers numbers with a zero fractional part, like
+            // 1.0, to be integers.
+            (None, Some(f)) if f.fract() == 0.0 && f >= -(2f64.powi(63)) && f < 2f64.powi(63) => {
+                packer.push(Datum::Int64(f as i64))
+            }
+            _ => bail!("expected integer, found {}", n),
+        },
+        (Kind::Float64, Value::Number(n)) => match n.as_f64() {
+            Some(f) => packer.push(Datum::Float64(OrderedFloat(f))),
+            None => bail!("{} is out of range for double precision", n),
+        },
+        (Kind::Decimal(scale), Value::Number(n)) => {
+            packer.push(Datum::Decimal(decimal_from_number(n, *scale)?))
+        }
+        (Kind::String, Value::String(s)) => packer.push(Datum::String(s)),
+        (Kind::List(element), Value::Array(elements)) => packer.push_list_with(|packer| {
+            for element_value in elements {
+                pack_value(element, element_value, packer)?;
+            }
+            Ok::<_, anyhow::Error>(())
+        })?,
+        (Kind::Record(properties), Value::Object(object)) => {
+            packer.push_list_with(|packer| pack_properties(properties, object, packer))?
+        }
+        (kind, _) => bail!("expected {}, found {}", kind.name(), value),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHEMA: &str = r#"{
+        "type": "object",
+        "properties": {
+            "id": {"type": "integer"},
+            "name": {"type": "string"},
+            "price": {"type": "number", "multipleOf": 0.01},
+            "ratio": {"type": ["number", "null"]},
+            "tags": {"type": "array", "items": {"type": "string"}},
+            "owner": {
+                "type": "object",
+                "properties": {"active": {"type": "boolean"}},
+                "required": ["active"]
+            },
+            "extra": {}
+        },
+        "required": ["id", "price", "ratio"]
+    }"#;
+
+    #[test]
+    fn test_validate_value_schema() -> anyhow::Result<()> {
+        let columns = validate_value_schema(SCHEMA)?;
+        let expected: Vec<(ColumnName, ColumnType)> = vec![
+            ("extra".into(), ScalarType::Jsonb.nullable(true)),
+            ("id".into(), ScalarType::Int64.nullable(false)),
+            ("name".into(), ScalarType::String.nullable(true)),
+            (
+                "owner".into(),
+                ScalarType::Record {
+                    fields: vec![("active".into(), ScalarType::Bool.nullable(false))],
+                    custom_oid: None,
+                    custom_name: None,
+                }
+                .nullable(true),
+            ),
+            ("price".into(), ScalarType::Decimal(38, 2).nullable(false)),
+            ("ratio".into(), ScalarType::Float64.nullable(true)),
+            (
+                "tags".into(),
+                ScalarType::List {
+                    element_type: Box::new(ScalarType::String),
+                    custom_oid: None,
+                }
+                .nullable(true),
+            ),
+        ];
+        assert_eq!(columns, expected);
+
+        assert!(validate_value_schema(r#"{"type": "string"}"#).is_err());
+
+        // Only exact powers of ten determine a scale, which must fit a numeric.
+        for (multiple_of, expected) in &[
+            ("1", ScalarType::Decimal(38, 0)),
+            ("1e-38", ScalarType::Decimal(38, 38)),
+            ("0.0010", ScalarType::Decimal(38, 3)),
+            ("100e-4", ScalarType::Decimal(38, 2)),
+            ("10", ScalarType::Float64),
+            ("0.02", ScalarType::Float64),
+        ] {
+            let schema = format!(
+                r#"{{"type": "object", "properties": {{"a": {{"type": "number", "multipleOf": {}}}}}}}"#,
+                multiple_of
+            );
+            let columns = validate_value_schema(&schema)?;
+            assert_eq!(columns[0].1.scalar_type, *expected, "{}", multiple_of);
+        }
+        let schema =
+            r#"{"type": "object", "properties": {"a": {"type": "number", "multipleOf": 1e-39}}}"#;
+        let err = validate_value_schema(schema).unwrap_err();
+        assert_eq!(
+            format!("{:#}", err),
+            "parsing JSON Schema for property \"a\": multipleOf 1e-39 requires a scale of 39, \
+             but the maximum scale of numeric is 38"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode() -> anyhow::Result<()> {
+        let mut decoder = Decoder::new(SCHEMA)?;
+
+        let row = decoder.decode(
+            br#"{"id": 1, "price": 2.5, "ratio": null, "tags": ["a"], "owner": {"active": true}}"#,
+        )?;
+        let datums = row.unpack();
+        assert_eq!(datums[0], Datum::Null);
+        assert_eq!(datums[1], Datum::Int64(1));
+        assert_eq!(datums[2], Datum::Null);
+        assert_eq!(
+            datums[3].unwrap_list().iter().collect::<Vec<_>>(),
+            vec![Datum::True]
+        );
+        assert_eq!(datums[4], Datum::Decimal(Significand::new(250)));
+        assert_eq!(datums[5], Datum::Null);
+        assert_eq!(
+            datums[6].unwrap_list().iter().collect::<Vec<_>>(),
+            vec![Datum::String("a")]
+        );
+
+        // The Confluent wire format header is skipped.
+        let row = decoder.decode(b"\0\0\0\0\x01{\"id\": 2, \"price\": 1, \"ratio\": 0.5}")?;
+        assert_eq!(row.unpack()[1], Datum::Int64(2));
+
+        // Integers may be written with a zero fractional part.
+        let row = decoder.decode(br#"{"id": 3.0, "price": 1, "ratio": 0.5}"#)?;
+        assert_eq!(row.unpack()[1], Datum::Int64(3));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_decode_errors() -> anyhow::Result<()> {
+        let mut decoder = Decoder::new(SCHEMA)?;
+        for (input, expected) in &[
+            (
+                r#"{"price": 1, "ratio": 1}"#,
+                r#"missing required property "id""#,
+            ),
+            (
+                r#"{"id": null, "price": 1, "ratio": 1}"#,
+                r#"invalid value for property "id": expected integer, found null"#,
+            ),
+            (
+                r#"{"id": "1", "price": 1, "ratio": 1}"#,
+                r#"invalid value for property "id": expected integer, found "1""#,
+            ),
+            (
+                r#"{"id": 1.5, "price": 1, "ratio": 1}"#,
+                r#"invalid value for property "id": expected integer, found 1.5"#,
+            ),
+            (
+                r#"{"id": 1, "price": 1.001, "ratio": 1}"#,
+                r#"invalid value for property "price": 1.001 is not a multiple of 1e-2"#,
+            ),
+            (
+                r#"{"id": 1, "price": 1, "ratio": 1, "owner": {}}"#,
+                r#"invalid value for property "owner": missing required property "active""#,
+            ),
+            (r#"[1]"#, "expected object, found [1]"),
+        ] {
+            let err = decoder.decode(input.as_bytes()).unwrap_err();
+            assert_eq!(format!("{:#}", err), *expected);
+        }
+
+        // The decoder is still usable after an error.
+        let row = decoder.decode(br#"{"id": 3, "price": 1, "ratio": 1}"#)?;
+        assert_eq!(row.unpack()[1], Datum::Int64(3));
+
+        Ok(())
+    }
+}
@@ -12,5 +12,8 @@
 #![deny(missing_debug_implementations)]
 
 pub mod avro;
+pub mod csv;
 pub mod envelopes;
+pub mod json_schema;
+pub mod parquet;
 pub mod protobuf;
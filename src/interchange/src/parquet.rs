@@ -0,0 +1,408 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Parquet encoding of rows.
+//!
+//! Each column of a relation becomes a field of the Parquet schema, which is
+//! `OPTIONAL` if the column is nullable and `REQUIRED` otherwise. Records
+//! become groups, so that the before and after records of the Debezium
+//! envelope keep their structure. Types without a Parquet counterpart, like
+//! `interval` and `uuid`, are written as their text representation. Lists,
+//! arrays, and maps are not yet supported.
+
+use std::sync::Arc;
+
+use anyhow::{bail, Result};
+use chrono::{NaiveDate, Timelike};
+use parquet::basic::{Compression, ConvertedType, Repetition, Type as PhysicalType};
+use parquet::column::writer::ColumnWriter;
+use parquet::data_type::ByteArray;
+use parquet::file::properties::WriterProperties;
+use parquet::file::writer::{
+    FileWriter, InMemoryWriteableCursor, RowGroupWriter, SerializedFileWriter,
+};
+use parquet::schema::types::{Type, TypePtr};
+
+use repr::adt::jsonb::JsonbRef;
+use repr::{ColumnName, ColumnType, Datum, RelationDesc, Row, ScalarType};
+
+use crate::avro::column_names_and_types;
+
+/// Encodes rows of a fixed relation as Parquet files.
+#[derive(Debug)]
+pub struct Encoder {
+    schema: TypePtr,
+    leaves: Vec<Leaf>,
+    props: Arc<WriterProperties>,
+}
+
+/// A primitive column of the Parquet schema.
+#[derive(Debug)]
+struct Leaf {
+    /// The index of the field at each level of nesting, from the top-level
+    /// column down to this leaf.
+    path: Vec<usize>,
+    /// Whether the field at each level of `path` is optional.
+    optional: Vec<bool>,
+    scalar_type: ScalarType,
+}
+
+impl Encoder {
+    /// Creates an encoder for rows described by `desc`.
+    ///
+    /// Returns an error if any column has a type that cannot be written to
+    /// Parquet.
+    pub fn new(desc: RelationDesc) -> Result<Self> {
+        let mut fields = vec![];
+        let mut leaves = vec![];
+        for (i, (name, typ)) in column_names_and_types(desc).into_iter().enumerate() {
+            fields.push(Arc::new(build_type(
+                &name,
+                &typ,
+                &mut vec![i],
+                &mut vec![],
+                &mut leaves,
+            )?));
+        }
+        let schema = Type::group_type_builder("row")
+            .with_fields(&mut fields)
+            .build()?;
+        let props = WriterProperties::builder()
+            .set_compression(Compression::SNAPPY)
+            .build();
+        Ok(Encoder {
+            schema: Arc::new(schema),
+            leaves,
+            props: Arc::new(props),
+        })
+    }
+
+    /// Returns the Parquet schema of the encoded files.
+    pub fn schema(&self) -> &Type {
+        &self.schema
+    }
+
+    /// Encodes `rows` as a complete Parquet file with a single row group.
+    pub fn encode(&self, rows: &[Row]) -> Result<Vec<u8>> {
+        let cursor = InMemoryWriteableCursor::default();
+        let mut writer =
+            SerializedFileWriter::new(cursor.clone(), self.schema.clone(), self.props.clone())?;
+        let mut row_group = writer.next_row_group()?;
+        let mut leaves = self.leaves.iter();
+        while let Some(mut column) = row_group.next_column()? {
+            let leaf = leaves.next().expect("one column writer per leaf");
+            write_column(&mut column, leaf, rows)?;
+            row_group.close_column(column)?;
+        }
+        writer.close_row_group(row_group)?;
+        writer.close()?;
+        Ok(cursor.data())
+    }
+}
+
+/// Builds the Parquet type of the field `name`, recording a [`Leaf`] for each
+/// primitive column in `leaves`.
+fn build_type(
+    name: &ColumnName,
+    typ: &ColumnType,
+    path: &mut Vec<usize>,
+    optional: &mut Vec<bool>,
+    leaves: &mut Vec<Leaf>,
+) -> Result<Type> {
+    let repetition = if typ.nullable {
+        Repetition::OPTIONAL
+    } else {
+        Repetition::REQUIRED
+    };
+    optional.push(typ.nullable);
+    let primitive = |physical_type, converted_type| {
+        Type::primitive_type_builder(name.as_str(), physical_type)
+            .with_repetition(repetition)
+            .with_converted_type(converted_type)
+    };
+    let ty = match &typ.scalar_type {
+        ScalarType::Bool => primitive(PhysicalType::BOOLEAN, ConvertedType::NONE).build()?,
+        ScalarType::Int32 | ScalarType::Oid => {
+            primitive(PhysicalType::INT32, ConvertedType::NONE).build()?
+        }
+        ScalarType::Int64 => primitive(PhysicalType::INT64, ConvertedType::NONE).build()?,
+        ScalarType::Float32 => primitive(PhysicalType::FLOAT, ConvertedType::NONE).build()?,
+        ScalarType::Float64 => primitive(PhysicalType::DOUBLE, ConvertedType::NONE).build()?,
+        ScalarType::Decimal(precision, scale) => {
+            primitive(PhysicalType::BYTE_ARRAY, ConvertedType::DECIMAL)
+                .with_precision(i32::from(*precision))
+                .with_scale(i32::from(*scale))
+                .build()?
+        }
+        ScalarType::Date => primitive(PhysicalType::INT32, ConvertedType::DATE).build()?,
+        ScalarType::Time => primitive(PhysicalType::INT64, ConvertedType::TIME_MICROS).build()?,
+        ScalarType::Timestamp | ScalarType::TimestampTz => {
+            primitive(PhysicalType::INT64, ConvertedType::TIMESTAMP_MICROS).build()?
+        }
+        ScalarType::Interval | ScalarType::String | ScalarType::Uuid => {
+            primitive(PhysicalType::BYTE_ARRAY, ConvertedType::UTF8).build()?
+        }
+        ScalarType::Bytes => primitive(PhysicalType::BYTE_ARRAY, ConvertedType::NONE).build()?,
+        ScalarType::Jsonb => primitive(PhysicalType::BYTE_ARRAY, ConvertedType::JSON).build()?,
+        ScalarType::Record { fields, .. } => {
+            let mut children = vec![];
+            for (i, (name, typ)) in fields.iter().enumerate() {
+                path.push(i);
+                children.push(Arc::new(build_type(name, typ, path, optional, leaves)?));
+                path.pop();
+            }
+            optional.pop();
+            return Ok(Type::group_type_builder(name.as_str())
+                .with_repetition(repetition)
+                .with_fields(&mut children)
+                .build()?);
+        }
+        ScalarType::Array(_) => bail!("column {} has unsupported type array", name),
+        ScalarType::List { .. } => bail!("column {} has unsupported type list", name),
+        ScalarType::Map { .. } => bail!("column {} has unsupported type map", name),
+    };
+    leaves.push(Leaf {
+        path: path.clone(),
+        optional: optional.clone(),
+        scalar_type: typ.scalar_type.clone(),
+    });
+    optional.pop();
+    Ok(ty)
+}
+
+impl Leaf {
+    /// Extracts the value of this leaf from `row`, along with its Parquet
+    /// definition level: the number of optional fields along the path that
+    /// are present. The value is `None` if it or any enclosing record is null.
+    fn extract<'a>(&self, row: &'a Row) -> Result<(i16, Option<Datum<'a>>)> {
+        let mut def_level = 0;
+        let mut datums = row.iter();
+        for (depth, (&index, &optional)) in self.path.iter().zip(&self.optional).enumerate() {
+            let datum = datums.nth(index).expect("row matches relation description");
+            if datum.is_null() {
+                if !optional {
+                    bail!("null value in non-nullable field at {:?}", self.path);
+                }
+                return Ok((def_level, None));
+            }
+            if optional {
+                def_level += 1;
+            }
+            if depth + 1 == self.path.len() {
+                return Ok((def_level, Some(datum)));
+            }
+            datums = datum.unwrap_list().iter();
+        }
+        unreachable!("leaf paths are never empty")
+    }
+}
+
+fn write_column(column: &mut ColumnWriter, leaf: &Leaf, rows: &[Row]) -> Result<()> {
+    let mut def_levels = Vec::with_capacity(rows.len());
+    let mut datums = Vec::with_capacity(rows.len());
+    for row in rows {
+        let (def_level, datum) = leaf.extract(row)?;
+        def_levels.push(def_level);
+        datums.extend(datum);
+    }
+    let def_levels = Some(&def_levels[..]);
+    match column {
+        ColumnWriter::BoolColumnWriter(w) => {
+            let values: Vec<_> = datums.iter().map(|d| d.unwrap_bool()).collect();
+            w.write_batch(&values, def_levels, None)?;
+        }
+        ColumnWriter::Int32ColumnWriter(w) => {
+            let values: Vec<_> = datums
+                .iter()
+                .map(|d| match leaf.scalar_type {
+                    ScalarType::Date => {
+                        (d.unwrap_date() - NaiveDate::from_ymd(1970, 1, 1)).num_days() as i32
+                    }
+                    _ => d.unwrap_int32(),
+                })
+                .collect();
+            w.write_batch(&values, def_levels, None)?;
+        }
+        ColumnWriter::Int64ColumnWriter(w) => {
+            let values: Vec<_> = datums
+                .iter()
+                .map(|d| match leaf.scalar_type {
+                    ScalarType::Time => {
+                        let time = d.unwrap_time();
+                        i64::from(time.num_seconds_from_midnight()) * 1_000_000
+                            + i64::from(time.nanosecond()) / 1_000
+                    }
+                    ScalarType::Timestamp => timestamp_micros(d.unwrap_timestamp()),
+                    ScalarType::TimestampTz => timestamp_micros(d.unwrap_timestamptz().naive_utc()),
+                    _ => d.unwrap_int64(),
+                })
+                .collect();
+            w.write_batch(&values, def_levels, None)?;
+        }
+        ColumnWriter::FloatColumnWriter(w) => {
+            let values: Vec<_> = datums.iter().map(|d| d.unwrap_float32()).collect();
+            w.write_batch(&values, def_levels, None)?;
+        }
+        ColumnWriter::DoubleColumnWriter(w) => {
+            let values: Vec<_> = datums.iter().map(|d| d.unwrap_float64()).collect();
+            w.write_batch(&values, def_levels, None)?;
+        }
+        ColumnWriter::ByteArrayColumnWriter(w) => {
+            let values: Vec<ByteArray> = datums
+                .iter()
+                .map(|d| match leaf.scalar_type {
+                    ScalarType::Decimal(..) => {
+                        d.unwrap_decimal().as_i128().to_be_bytes().to_vec().into()
+                    }
+                    ScalarType::Bytes => d.unwrap_bytes().to_vec().into(),
+                    ScalarType::String => d.unwrap_str().into(),
+                    ScalarType::Jsonb => JsonbRef::from_datum(*d).to_string().into_bytes().into(),
+                    ScalarType::Interval => d.unwrap_interval().to_string().into_bytes().into(),
+                    ScalarType::Uuid => d.unwrap_uuid().to_string().into_bytes().into(),
+                    _ => unreachable!("{:?} is not a byte array type", leaf.scalar_type),
+                })
+                .collect();
+            w.write_batch(&values, def_levels, None)?;
+        }
+        ColumnWriter::Int96ColumnWriter(_) | ColumnWriter::FixedLenByteArrayColumnWriter(_) => {
+            unreachable!("schemas do not contain int96 or fixed length byte array columns")
+        }
+    }
+    Ok(())
+}
+
+fn timestamp_micros(ts: chrono::NaiveDateTime) -> i64 {
+    ts.timestamp() * 1_000_000 + i64::from(ts.timestamp_subsec_micros())
+}
+
+#[cfg(test)]
+mod tests {
+    use parquet::file::reader::{FileReader, SerializedFileReader};
+    use parquet::record::{Field, RowAccessor};
+    use parquet::util::cursor::SliceableCursor;
+
+    use repr::{RelationType, RowArena};
+
+    use super::*;
+
+    fn desc() -> RelationDesc {
+        let record = ScalarType::Record {
+            fields: vec![
+                ("x".into(), ScalarType::Int64.nullable(false)),
+                ("y".into(), ScalarType::String.nullable(true)),
+            ],
+            custom_oid: None,
+            custom_name: None,
+        };
+        RelationDesc::new(
+            RelationType::new(vec![
+                ScalarType::Int32.nullable(false),
+                ScalarType::String.nullable(true),
+                record.nullable(true),
+            ]),
+            vec![Some("a"), Some("b"), Some("c")],
+        )
+    }
+
+    #[test]
+    fn test_schema_nullability() -> Result<()> {
+        let encoder = Encoder::new(desc())?;
+        let fields = encoder.schema().get_fields();
+        assert_eq!(fields.len(), 3);
+        assert_eq!(
+            fields[0].get_basic_info().repetition(),
+            Repetition::REQUIRED
+        );
+        assert_eq!(fields[0].get_physical_type(), PhysicalType::INT32);
+        assert_eq!(
+            fields[1].get_basic_info().repetition(),
+            Repetition::OPTIONAL
+        );
+        assert_eq!(
+            fields[1].get_basic_info().converted_type(),
+            ConvertedType::UTF8
+        );
+        assert!(fields[2].is_group());
+        assert_eq!(
+            fields[2].get_basic_info().repetition(),
+            Repetition::OPTIONAL
+        );
+        let children = fields[2].get_fields();
+        assert_eq!(
+            children[0].get_basic_info().repetition(),
+            Repetition::REQUIRED
+        );
+        assert_eq!(
+            children[1].get_basic_info().repetition(),
+            Repetition::OPTIONAL
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_unsupported_types() {
+        let desc = RelationDesc::empty().with_column(
+            "l",
+            ScalarType::Map {
+                value_type: Box::new(ScalarType::Int32),
+                custom_oid: None,
+            }
+            .nullable(true),
+        );
+        assert!(Encoder::new(desc).is_err());
+    }
+
+    #[test]
+    fn test_encode_roundtrip() -> Result<()> {
+        let arena = RowArena::new();
+        let rows = vec![
+            Row::pack_slice(&[
+                Datum::Int32(1),
+                Datum::String("one"),
+                arena.make_datum(|packer| {
+                    packer.push_list(&[Datum::Int64(10), Datum::String("ten")])
+                }),
+            ]),
+            Row::pack_slice(&[Datum::Int32(2), Datum::Null, Datum::Null]),
+            Row::pack_slice(&[
+                Datum::Int32(3),
+                Datum::String("three"),
+                arena.make_datum(|packer| packer.push_list(&[Datum::Int64(30), Datum::Null])),
+            ]),
+        ];
+        let encoder = Encoder::new(desc())?;
+        let bytes = encoder.encode(&rows)?;
+        let reader = SerializedFileReader::new(SliceableCursor::new(bytes))?;
+        assert_eq!(reader.metadata().file_metadata().num_rows(), 3);
+        let decoded: Vec<_> = reader.get_row_iter(None)?.collect();
+
+        assert_eq!(decoded[0].get_int(0)?, 1);
+        assert_eq!(decoded[0].get_string(1)?, "one");
+        let c = decoded[0].get_group(2)?;
+        assert_eq!(c.get_long(0)?, 10);
+        assert_eq!(c.get_string(1)?, "ten");
+
+        assert_eq!(decoded[1].get_int(0)?, 2);
+        let fields: Vec<_> = decoded[1].get_column_iter().map(|(_, f)| f).collect();
+        assert_eq!(fields[1], &Field::Null);
+        assert_eq!(fields[2], &Field::Null);
+
+        let c = decoded[2].get_group(2)?;
+        assert_eq!(c.get_long(0)?, 30);
+        let fields: Vec<_> = c.get_column_iter().map(|(_, f)| f).collect();
+        assert_eq!(fields[1], &Field::Null);
+
+        // Nulls in non-nullable columns are refused rather than silently
+        // producing a corrupt file.
+        assert!(encoder
+            .encode(&[Row::pack_slice(&[Datum::Null, Datum::Null, Datum::Null])])
+            .is_err());
+        Ok(())
+    }
+}
@@ -9,9 +9,12 @@
 
 //! Protobuf source connector
 
+use std::collections::HashMap;
+
 use anyhow::{anyhow, bail, Context, Result};
 use num_traits::ToPrimitive;
 use ordered_float::OrderedFloat;
+use protobuf::descriptor::{DescriptorProto, FileDescriptorSet};
 use serde::de::Deserialize;
 use serde_protobuf::de::Deserializer;
 use serde_protobuf::descriptor::{
@@ -105,13 +108,117 @@ fn validate_proto_field_resolved(field: &FieldDescriptor, descriptors: &Descript
     Ok(())
 }
 
-pub fn decode_descriptors(descriptors: &[u8]) -> Result<Descriptors> {
+/// A `oneof` declared by a message.
+#[derive(Debug)]
+struct Oneof {
+    name: String,
+    /// The names of the member fields, in declaration order.
+    fields: Vec<String>,
+}
+
+impl Oneof {
+    fn contains(&self, field: &FieldDescriptor) -> bool {
+        self.fields.iter().any(|name| name == field.name())
+    }
+}
+
+/// A set of protobuf message descriptors.
+///
+/// [`Descriptors`] does not retain which fields of a message belong to a
+/// `oneof`, so that information is extracted separately from the file
+/// descriptor set.
+#[derive(Debug)]
+pub struct DescriptorSet {
+    descriptors: Descriptors,
+    /// The `oneof`s declared by each message, keyed by fully qualified message
+    /// name.
+    oneofs: HashMap<String, Vec<Oneof>>,
+}
+
+impl DescriptorSet {
+    pub fn from_proto(proto: &FileDescriptorSet) -> DescriptorSet {
+        fn add_oneofs(
+            oneofs: &mut HashMap<String, Vec<Oneof>>,
+            scope: &str,
+            message: &DescriptorProto,
+        ) {
+            let name = format!("{}.{}", scope, message.get_name());
+            let mut message_oneofs: Vec<_> = message
+                .get_oneof_decl()
+                .iter()
+                .map(|oneof| Oneof {
+                    name: oneof.get_name().to_string(),
+                    fields: vec![],
+                })
+                .collect();
+            for field in message.get_field() {
+                if field.has_oneof_index() {
+                    let index = field.get_oneof_index() as usize;
+                    if let Some(oneof) = message_oneofs.get_mut(index) {
+                        oneof.fields.push(field.get_name().to_string());
+                    }
+                }
+            }
+            for nested in message.get_nested_type() {
+                add_oneofs(oneofs, &name, nested);
+            }
+            if !message_oneofs.is_empty() {
+                oneofs.insert(name, message_oneofs);
+            }
+        }
+
+        let mut oneofs = HashMap::new();
+        for file in proto.get_file() {
+            let scope = match file.get_package() {
+                "" => String::new(),
+                package => format!(".{}", package),
+            };
+            for message in file.get_message_type() {
+                add_oneofs(&mut oneofs, &scope, message);
+            }
+        }
+        DescriptorSet {
+            descriptors: Descriptors::from_proto(proto),
+            oneofs,
+        }
+    }
+
+    fn oneofs(&self, message_name: &str) -> &[Oneof] {
+        self.oneofs
+            .get(message_name)
+            .map(|oneofs| oneofs.as_slice())
+            .unwrap_or(&[])
+    }
+}
+
+impl From<Descriptors> for DescriptorSet {
+    fn from(descriptors: Descriptors) -> DescriptorSet {
+        DescriptorSet {
+            descriptors,
+            oneofs: HashMap::new(),
+        }
+    }
+}
+
+pub fn decode_descriptors(descriptors: &[u8]) -> Result<DescriptorSet> {
     let proto = protobuf::parse_from_bytes(descriptors)
         .context("parsing encoded protobuf descriptors failed")?;
-    Ok(Descriptors::from_proto(&proto))
+    Ok(DescriptorSet::from_proto(&proto))
 }
 
-pub fn validate_descriptors(message_name: &str, descriptors: &Descriptors) -> Result<RelationDesc> {
+/// Determines the columns of the relation that a protobuf message decodes
+/// into.
+///
+/// Each field of the message becomes a column, in declaration order. The
+/// members of a `oneof` are always nullable, as at most one of them is set.
+/// Each `oneof` additionally produces a nullable `text` column, named after
+/// the `oneof` and placed after the field columns, that names the member that
+/// is set.
+pub fn validate_descriptors(
+    message_name: &str,
+    descriptor_set: &DescriptorSet,
+) -> Result<RelationDesc> {
+    let descriptors = &descriptor_set.descriptors;
     let proto_name = proto_message_name(message_name);
     let message = descriptors.message_by_name(&proto_name).ok_or_else(|| {
         anyhow!(
@@ -124,20 +231,27 @@ pub fn validate_descriptors(message_name: &str, descriptors: &Descriptors) -> Re
                 .join(", ")
         )
     })?;
-    let column_types = message
+    let oneofs = descriptor_set.oneofs(&proto_name);
+    let mut column_types = message
         .fields()
         .iter()
         .map(|f| {
             Ok(ColumnType {
                 /// All the fields have to be optional, so mark a field as
                 /// nullable if it doesn't have any defaults
-                nullable: f.default_value().is_none(),
+                nullable: f.default_value().is_none()
+                    || oneofs.iter().any(|oneof| oneof.contains(f)),
                 scalar_type: validate_proto_field(&f, descriptors)?,
             })
         })
         .collect::<Result<Vec<_>>>()?;
+    column_types.extend(oneofs.iter().map(|_| ScalarType::String.nullable(true)));
 
-    let column_names = message.fields().iter().map(|f| Some(f.name().to_string()));
+    let column_names = message
+        .fields()
+        .iter()
+        .map(|f| Some(f.name().to_string()))
+        .chain(oneofs.iter().map(|oneof| Some(oneof.name.clone())));
     Ok(RelationDesc::new(
         RelationType::new(column_types),
         column_names,
@@ -147,7 +261,7 @@ pub fn validate_descriptors(message_name: &str, descriptors: &Descriptors) -> Re
 /// Manages required metadata to read protobuf
 #[derive(Debug)]
 pub struct Decoder {
-    descriptors: Descriptors,
+    descriptors: DescriptorSet,
     message_name: String,
     packer: RowPacker,
 }
@@ -157,7 +271,7 @@ impl Decoder {
     ///
     /// The message `message_name` must exist in the descriptor set and be
     /// valid.
-    pub fn new(descriptors: Descriptors, message_name: &str) -> Decoder {
+    pub fn new(descriptors: DescriptorSet, message_name: &str) -> Decoder {
         // TODO: verify that name exists
         Decoder {
             descriptors,
@@ -168,22 +282,29 @@ impl Decoder {
 
     pub fn decode(&mut self, bytes: &[u8]) -> Result<Option<Row>> {
         let input_stream = protobuf::CodedInputStream::from_bytes(bytes);
-        let mut deserializer =
-            Deserializer::for_named_message(&self.descriptors, &self.message_name, input_stream)
-                .map_err(|e| anyhow!("Creating an input stream to parse protobuf: {}", e))?;
+        let mut deserializer = Deserializer::for_named_message(
+            &self.descriptors.descriptors,
+            &self.message_name,
+            input_stream,
+        )
+        .map_err(|e| anyhow!("Creating an input stream to parse protobuf: {}", e))?;
         let deserialized_message =
             SerdeValue::deserialize(&mut deserializer).context("Deserializing into rust object")?;
 
         let msg_name = &self.message_name;
         extract_row_into(
             deserialized_message,
-            &self.descriptors,
-            self.descriptors.message_by_name(&msg_name).ok_or_else(|| {
-                anyhow!(
-                    "Message should be included in the descriptor set {:?}",
-                    msg_name
-                )
-            })?,
+            &self.descriptors.descriptors,
+            self.descriptors
+                .descriptors
+                .message_by_name(&msg_name)
+                .ok_or_else(|| {
+                    anyhow!(
+                        "Message should be included in the descriptor set {:?}",
+                        msg_name
+                    )
+                })?,
+            self.descriptors.oneofs(&msg_name),
             &mut self.packer,
         )?;
         Ok(Some(self.packer.finish_and_reuse()))
@@ -194,17 +315,44 @@ fn extract_row_into(
     deserialized_message: SerdeValue,
     descriptors: &Descriptors,
     message_descriptors: &MessageDescriptor,
+    oneofs: &[Oneof],
     packer: &mut RowPacker,
 ) -> Result<()> {
     let deserialized_message = match deserialized_message {
         SerdeValue::Map(deserialized_message) => deserialized_message,
         _ => bail!("Deserialization failed with an unsupported top level object type"),
     };
+    let get = |name: &str| match deserialized_message.get(&SerdeValue::String(name.to_string())) {
+        None | Some(SerdeValue::Option(None)) => None,
+        Some(value) => Some(value),
+    };
+
+    // Determine which member of each oneof is set. If a message sets several
+    // members, the last one on the wire should win, but the deserialized
+    // message does not preserve wire order, so we settle for the last member
+    // in declaration order.
+    let set_members: Vec<_> = oneofs
+        .iter()
+        .map(|oneof| {
+            oneof
+                .fields
+                .iter()
+                .rev()
+                .find(|name| get(name.as_str()).is_some())
+        })
+        .collect();
 
     // TODO: This is actually unpacking a row, it should always return json
     for f in message_descriptors.fields().iter() {
-        let key = SerdeValue::String(f.name().to_string());
-        let value = deserialized_message.get(&key);
+        let oneof = oneofs.iter().position(|oneof| oneof.contains(f));
+        if let Some(i) = oneof {
+            if set_members[i].map(|name| name.as_str()) != Some(f.name()) {
+                packer.push(Datum::Null);
+                continue;
+            }
+        }
+
+        let value = get(f.name());
 
         if let Some(value) = value {
             json_from_serde_value(&value, packer, f, descriptors)?;
@@ -213,6 +361,13 @@ fn extract_row_into(
         }
     }
 
+    for member in set_members {
+        packer.push(match member {
+            Some(name) => Datum::String(name),
+            None => Datum::Null,
+        });
+    }
+
     Ok(())
 }
 
@@ -457,8 +612,10 @@ mod tests {
     use repr::adt::decimal::Significand;
     use repr::{Datum, DatumList, RelationDesc, ScalarType};
 
+    use super::DescriptorSet;
     use gen::fuzz::{
-        Color, TestNestedRecord, TestRecord, TestRepeatedNestedRecord, TestRepeatedRecord,
+        Color, TestNestedRecord, TestOneof, TestOneof_Inner, TestRecord, TestRepeatedNestedRecord,
+        TestRepeatedRecord,
     };
 
     mod gen {
@@ -543,6 +700,7 @@ mod tests {
             None,
         ));
         descriptors.add_message(m1);
+        let mut descriptors = DescriptorSet::from(descriptors);
 
         let mut relation = super::validate_descriptors(".test.message1", &descriptors)
             .expect("Failed to parse descriptor");
@@ -550,9 +708,10 @@ mod tests {
         sanity_check_relation(
             &relation,
             descriptors
+                .descriptors
                 .message_by_name(".test.message1")
                 .expect("message should be in the descriptor set"),
-            &descriptors,
+            &descriptors.descriptors,
         )?;
 
         let mut m2 = MessageDescriptor::new(".test.message2");
@@ -571,7 +730,7 @@ mod tests {
             InternalFieldType::String,
             None,
         ));
-        descriptors.add_message(m2);
+        descriptors.descriptors.add_message(m2);
 
         relation = super::validate_descriptors(".test.message2", &descriptors)
             .expect("Failed to parse descriptor");
@@ -579,25 +738,27 @@ mod tests {
         sanity_check_relation(
             &relation,
             descriptors
+                .descriptors
                 .message_by_name(".test.message2")
                 .expect("message should be in the descriptor set"),
-            &descriptors,
+            &descriptors.descriptors,
         )?;
 
         Ok(())
     }
 
     fn get_decoder(message_name: &str) -> super::Decoder {
-        let descriptors = Descriptors::from_proto(&gen::file_descriptor_set());
+        let descriptors = DescriptorSet::from_proto(&gen::file_descriptor_set());
         let relation = super::validate_descriptors(message_name, &descriptors)
             .expect("Failed to parse descriptor");
 
         sanity_check_relation(
             &relation,
             descriptors
+                .descriptors
                 .message_by_name(message_name)
                 .expect("message should be in the descriptor set"),
-            &descriptors,
+            &descriptors.descriptors,
         )
         .expect("Sanity checking descriptors failed");
         super::Decoder::new(descriptors, message_name)
@@ -853,4 +1014,93 @@ mod tests {
             assert_eq!(d, Datum::List(DatumList::empty()));
         }
     }
+
+    #[test]
+    fn test_oneof() {
+        let mut decoder = get_decoder(".TestOneof");
+        let relation = super::validate_descriptors(".TestOneof", &decoder.descriptors)
+            .expect("Failed to parse descriptor");
+        let columns = relation
+            .iter()
+            .map(|(name, typ)| (name.unwrap().as_str(), typ.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            columns,
+            vec![
+                ("id", ScalarType::Int32.nullable(true)),
+                ("text", ScalarType::String.nullable(true)),
+                ("inner", ScalarType::Jsonb.nullable(true)),
+                ("nested", ScalarType::Jsonb.nullable(true)),
+                ("payload", ScalarType::String.nullable(true)),
+            ]
+        );
+
+        let mut decode = |record: &TestOneof| {
+            let bytes = record
+                .write_to_bytes()
+                .expect("test failed to serialize to bytes");
+            decoder
+                .decode(&bytes)
+                .expect("deserialize protobuf into a row")
+                .unwrap()
+        };
+
+        let mut record = TestOneof::new();
+        record.set_id(1);
+        let row = decode(&record);
+        assert_eq!(
+            row.iter().collect::<Vec<_>>(),
+            vec![
+                Datum::Int32(1),
+                Datum::Null,
+                Datum::Null,
+                Datum::Null,
+                Datum::Null
+            ]
+        );
+
+        // Members set to their default value are still set.
+        record.set_text("".into());
+        let row = decode(&record);
+        assert_eq!(
+            row.iter().collect::<Vec<_>>(),
+            vec![
+                Datum::Int32(1),
+                Datum::String(""),
+                Datum::Null,
+                Datum::Null,
+                Datum::String("text")
+            ]
+        );
+
+        let mut inner = TestOneof_Inner::new();
+        inner.set_s("hi".into());
+        record.set_inner(inner);
+        let row = decode(&record);
+        let datums = row.iter().collect::<Vec<_>>();
+        assert_eq!(datums[1], Datum::Null);
+        match datums[2] {
+            Datum::Map(d) => assert_eq!(
+                d.iter().collect::<Vec<_>>(),
+                vec![("s", Datum::String("hi"))]
+            ),
+            d => panic!(
+                "Expected the inner member to be a dict of datums, got {:?}",
+                d
+            ),
+        }
+        assert_eq!(datums[3], Datum::Null);
+        assert_eq!(datums[4], Datum::String("inner"));
+
+        let mut test_record = TestRecord::new();
+        test_record.set_int_field(1);
+        let mut nested = TestNestedRecord::new();
+        nested.set_test_record(test_record);
+        record.set_nested(nested);
+        let row = decode(&record);
+        let datums = row.iter().collect::<Vec<_>>();
+        assert_eq!(datums[2], Datum::Null);
+        assert!(matches!(datums[3], Datum::Map(_)));
+        assert_eq!(datums[4], Datum::String("nested"));
+    }
 }
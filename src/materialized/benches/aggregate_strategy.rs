@@ -0,0 +1,59 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+//! Benchmarks count-distinct-heavy aggregations under the sort-based and
+//! hash-based aggregate strategies.
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+
+#[path = "../tests/util.rs"]
+pub mod util;
+
+/// The number of rows in the input table.
+const NUM_ROWS: i64 = 1_000_000;
+
+/// The numbers of distinct groups to aggregate the input into.
+const NUM_GROUPS: &[i64] = &[100, 10_000, 100_000];
+
+fn bench_count_distinct(c: &mut Criterion) {
+    let server = util::start_server(util::Config::default()).unwrap();
+    let mut client = server.connect(postgres::NoTls).unwrap();
+    client
+        .batch_execute(&format!(
+            "CREATE TABLE t (a bigint, b bigint);
+             INSERT INTO t SELECT x, x % 1000 FROM generate_series(1, {}) x",
+            NUM_ROWS,
+        ))
+        .unwrap();
+
+    let mut group = c.benchmark_group("count_distinct");
+    group.sample_size(10);
+    for groups in NUM_GROUPS {
+        for strategy in &["sort", "hash"] {
+            // Each query is a one-off peek of an unindexed table, so every
+            // iteration renders, runs, and tears down a fresh dataflow.
+            let query = format!(
+                "SELECT a % {groups}, count(DISTINCT b), count(*), sum(b) \
+                 FROM t GROUP BY a % {groups} OPTION (aggregate_strategy = '{strategy}')",
+                groups = groups,
+                strategy = strategy,
+            );
+            group.bench_with_input(BenchmarkId::new(*strategy, groups), &query, |b, query| {
+                b.iter(|| {
+                    let rows = client.query(query.as_str(), &[]).unwrap();
+                    assert_eq!(rows.len() as i64, *groups);
+                })
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_count_distinct);
+criterion_main!(benches);
@@ -26,7 +26,7 @@ use std::io;
 use std::net::SocketAddr;
 use std::panic;
 use std::panic::PanicInfo;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
 use std::str::FromStr;
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -77,6 +77,11 @@ struct Args {
     /// [DANGEROUS] Enable experimental features.
     #[structopt(long)]
     experimental: bool,
+    /// Print the changes that pending catalog migrations would make and exit.
+    ///
+    /// The items in the catalog are left unmodified.
+    #[structopt(long)]
+    migrate_dry_run: bool,
 
     // === Timely worker configuration. ===
     /// Number of dataflow worker threads.
@@ -325,6 +330,10 @@ fn run(args: Args) -> Result<(), anyhow::Error> {
     fs::create_dir_all(&data_directory)
         .with_context(|| format!("creating data directory: {}", data_directory.display()))?;
 
+    if args.migrate_dry_run {
+        return migrate_dry_run(&data_directory, logging.is_some());
+    }
+
     // Configure source caching.
     let cache = if args.experimental {
         let cache_directory = data_directory.join("cache");
@@ -576,6 +585,32 @@ lazy_static! {
     static ref PANIC_MUTEX: Mutex<()> = Mutex::new(());
 }
 
+fn migrate_dry_run(data_directory: &Path, enable_logging: bool) -> Result<(), anyhow::Error> {
+    let path = data_directory.join("catalog");
+    if !path.exists() {
+        bail!("no catalog found at {}", path.display());
+    }
+    let diff = coord::catalog::Catalog::migrate_dry_run(&coord::catalog::Config {
+        path: &path,
+        experimental_mode: None,
+        enable_logging,
+        cache_directory: None,
+        build_info: &materialized::BUILD_INFO,
+    })?;
+    if diff.is_empty() {
+        println!("no catalog items would be changed by migration");
+    }
+    for item in diff {
+        println!("item {} ({}):", item.id, item.name);
+        println!("  - {}", item.old_create_sql);
+        match item.new_create_sql {
+            Ok(new_create_sql) => println!("  + {}", new_create_sql),
+            Err(e) => println!("  ! failed to migrate: {}", e),
+        }
+    }
+    Ok(())
+}
+
 fn handle_panic(panic_info: &PanicInfo) {
     let _guard = PANIC_MUTEX.lock();
 
@@ -26,6 +26,7 @@ use postgres::SimpleQueryMessage;
 use postgres_array::{Array, Dimension};
 use tokio::runtime::Runtime;
 use tokio::sync::mpsc;
+use uuid::Uuid;
 
 use ore::collections::CollectionExt;
 use pgrepr::{Numeric, Record};
@@ -357,6 +358,12 @@ fn test_copy() -> Result<(), Box<dyn Error>> {
             .copy_out("COPY (VALUES (NULL, 2), (E'\t', 4)) TO STDOUT")?
             .read_to_string(&mut buf)?;
         assert_eq!(buf, "\\N\t2\n\\t\t4\n");
+
+        let mut buf = String::new();
+        client
+            .copy_out("COPY (VALUES (NULL, 2), (E'\t', 4), ('', 6)) TO STDOUT (FORMAT CSV)")?
+            .read_to_string(&mut buf)?;
+        assert_eq!(buf, ",2\n\t,4\n\"\",6\n");
     }
 
     Ok(())
@@ -424,6 +431,113 @@ fn test_record_types() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_uuid() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client = server.connect(postgres::NoTls)?;
+
+    // The client requests results and sends parameters in the binary format.
+    let uuid = Uuid::parse_str("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11")?;
+    let row = client.query_one("SELECT 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'::uuid", &[])?;
+    assert_eq!(row.get::<_, Uuid>(0), uuid);
+
+    let row = client.query_one("SELECT $1::uuid, $1::uuid::text", &[&uuid])?;
+    assert_eq!(row.get::<_, Uuid>(0), uuid);
+    assert_eq!(
+        row.get::<_, String>(1),
+        "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"
+    );
+
+    let row = client.query_one("SELECT $1::uuid IS NULL", &[&None::<Uuid>])?;
+    assert!(row.get::<_, bool>(0));
+
+    Ok(())
+}
+
+#[test]
+fn test_listen_notify() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+
+    Runtime::new()?.block_on(async {
+        let (listener, mut conn) = server.pg_config_async().connect(postgres::NoTls).await?;
+        let (notification_tx, mut notification_rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Some(msg) = future::poll_fn(|cx| conn.poll_message(cx)).await {
+                match msg {
+                    Ok(tokio_postgres::AsyncMessage::Notification(n)) => {
+                        notification_tx.send(n).unwrap()
+                    }
+                    Ok(_) => (),
+                    Err(e) => panic!(e),
+                }
+            }
+        });
+        async fn next_payload(
+            rx: &mut mpsc::UnboundedReceiver<tokio_postgres::Notification>,
+        ) -> String {
+            let n = tokio::time::timeout(Duration::from_secs(30), rx.recv())
+                .await
+                .expect("timed out waiting for notification")
+                .expect("listener connection closed");
+            assert_eq!(n.channel(), "events");
+            n.payload().to_string()
+        }
+
+        let (notifier, _handle) = server.connect_async(postgres::NoTls).await?;
+
+        // Notifications from other sessions are delivered to an idle listener.
+        listener.batch_execute("LISTEN events").await?;
+        notifier.batch_execute("NOTIFY other, 'ignored'").await?;
+        notifier.batch_execute("NOTIFY events, 'hello'").await?;
+        assert_eq!(next_payload(&mut notification_rx).await, "hello");
+
+        // A NOTIFY sink sends a notification for each row that is added to
+        // its relation.
+        notifier
+            .batch_execute("CREATE TABLE t (payload text)")
+            .await?;
+        notifier
+            .batch_execute("CREATE SINK s FROM t INTO NOTIFY events")
+            .await?;
+        notifier
+            .batch_execute("INSERT INTO t VALUES ('a'), ('b')")
+            .await?;
+        let mut payloads = vec![
+            next_payload(&mut notification_rx).await,
+            next_payload(&mut notification_rx).await,
+        ];
+        payloads.sort();
+        assert_eq!(payloads, vec!["a", "b"]);
+        notifier.batch_execute("DELETE FROM t").await?;
+        notifier.batch_execute("INSERT INTO t VALUES ('c')").await?;
+        assert_eq!(next_payload(&mut notification_rx).await, "c");
+
+        // Rows whose payloads are too long for `NOTIFY` send nothing.
+        notifier
+            .batch_execute(&format!("INSERT INTO t VALUES ('{}')", "x".repeat(8000)))
+            .await?;
+        notifier.batch_execute("INSERT INTO t VALUES ('d')").await?;
+        assert_eq!(next_payload(&mut notification_rx).await, "d");
+        notifier.batch_execute("DROP SINK s").await?;
+
+        // Notifications sent while a session is not listening are never
+        // delivered to it.
+        listener.batch_execute("UNLISTEN events").await?;
+        notifier.batch_execute("NOTIFY events, 'unheard'").await?;
+        listener.batch_execute("LISTEN events").await?;
+        notifier.batch_execute("NOTIFY events, 'heard'").await?;
+        assert_eq!(next_payload(&mut notification_rx).await, "heard");
+
+        Ok::<_, Box<dyn Error>>(())
+    })?;
+
+    Ok(())
+}
+
 #[test]
 fn test_pgtest() -> Result<(), Box<dyn Error>> {
     ore::test::init_logging();
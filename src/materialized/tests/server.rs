@@ -11,6 +11,8 @@
 
 use std::collections::HashMap;
 use std::error::Error;
+use std::thread;
+use std::time::{Duration, Instant};
 
 use reqwest::{blocking::Client, StatusCode, Url};
 use tempfile::NamedTempFile;
@@ -89,14 +91,16 @@ fn test_persistence() -> Result<(), Box<dyn Error>> {
                 "s3000", "s3001", "s3002", "s3003", "s3004", "s3005", "s3006", "s3007", "s3008",
                 "s3009", "s3010", "s3011", "s3012", "s3013", "s3014", "s3015", "s3016", "s3017",
                 "s3018", "s3019", "s3020", "s3021", "s3022", "s3023", "s3024", "s3025", "s3026",
-                "s3027", "s4001", "s4002", "s4003", "s4004", "s4005", "s4006", "s4007", "s4008",
-                "s4009", "s4010", "s4011", "s4012", "s4013", "s4014", "s4015", "s4016", "s4017",
-                "s4018", "s4019", "s4020", "s4021", "s4022", "s4023", "s4024", "s4025", "s4026",
-                "s4027", "s4028", "s4029", "s4030", "s4031", "s4032", "s4033", "s4034", "s4035",
-                "s4036", "s4037", "s4038", "s4039", "s4040", "s4041", "s4042", "s5000", "s5001",
-                "s5002", "s5003", "s5004", "s5005", "s5006", "s5007", "s5008", "s5009", "s5010",
-                "s5011", "s5012", "s5013", "s5014", "s5015", "s5016", "s5017", "s5018", "s5019",
-                "s5020", "s5021", "s5022", "s5023", "s5024", "u1", "u2", "u3", "u4", "u5", "u6"
+                "s3027", "s3028", "s3029", "s4001", "s4002", "s4003", "s4004", "s4005", "s4006",
+                "s4007", "s4008", "s4009", "s4010", "s4011", "s4012", "s4013", "s4014", "s4015",
+                "s4016", "s4017", "s4018", "s4019", "s4020", "s4021", "s4022", "s4023", "s4024",
+                "s4025", "s4026", "s4027", "s4028", "s4029", "s4030", "s4031", "s4032", "s4033",
+                "s4034", "s4035", "s4036", "s4037", "s4038", "s4039", "s4040", "s4041", "s4042",
+                "s4043", "s4044", "s4045", "s4046", "s4047", "s4048", "s4049", "s4050", "s4051",
+                "s4052", "s5000", "s5001", "s5002", "s5003", "s5004", "s5005", "s5006", "s5007",
+                "s5008", "s5009", "s5010", "s5011", "s5012", "s5013", "s5014", "s5015", "s5016",
+                "s5017", "s5018", "s5019", "s5020", "s5021", "s5022", "s5023", "s5024", "s5025",
+                "s5026", "s5027", "s5028", "u1", "u2", "u3", "u4", "u5", "u6"
             ]
         );
     }
@@ -116,6 +120,69 @@ fn test_persistence() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+// Ensures that the logical compaction window requested for an index when it is
+// created is reapplied after a restart.
+#[test]
+fn test_index_compaction_window_persistence() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let data_dir = tempfile::tempdir()?;
+    let config = util::Config::default().data_directory(data_dir.path());
+
+    {
+        let server = util::start_server(config.clone())?;
+        let mut client = server.connect(postgres::NoTls)?;
+        client.batch_execute("CREATE TABLE t (data text)")?;
+        client.batch_execute("CREATE VIEW compacted AS SELECT data FROM t")?;
+        client.batch_execute(
+            "CREATE INDEX compacted_idx ON compacted (data) \
+             WITH (logical_compaction_window_ms = 1)",
+        )?;
+        client.batch_execute("CREATE VIEW retained AS SELECT data FROM t")?;
+        client.batch_execute(
+            "CREATE INDEX retained_idx ON retained (data) \
+             WITH (logical_compaction_window_ms = 'off')",
+        )?;
+    }
+
+    {
+        let server = util::start_server(config)?;
+        let mut client = server.connect(postgres::NoTls)?;
+
+        // The index whose compaction window is short compacts away the
+        // earliest timestamps...
+        let deadline = Instant::now() + Duration::from_secs(30);
+        loop {
+            match client.query("SELECT data FROM compacted AS OF 0", &[]) {
+                Err(e)
+                    if e.as_db_error().map_or(false, |e| {
+                        e.message()
+                            .starts_with("Timestamp (0) is not valid for all inputs")
+                    }) =>
+                {
+                    break
+                }
+                _ if Instant::now() < deadline => thread::sleep(Duration::from_millis(100)),
+                _ => panic!("timestamp 0 was never compacted"),
+            }
+        }
+
+        // ...while the index with compaction disabled retains them.
+        let deadline = Instant::now() + Duration::from_secs(30);
+        loop {
+            match client.query("SELECT data FROM retained AS OF 0", &[]) {
+                Err(_) if Instant::now() < deadline => thread::sleep(Duration::from_millis(100)),
+                res => {
+                    res?;
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
 // Ensures that once a node is started with `--experimental`, it requires
 // `--experimental` on reboot.
 #[test]
@@ -14,12 +14,13 @@
 //! in testdrive, e.g., because they depend on the current time.
 
 use std::error::Error;
-use std::io::Write;
+use std::io::{Read, Write};
 use std::net::TcpListener;
 use std::thread;
 use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Utc};
+use postgres::error::SqlState;
 use tempfile::NamedTempFile;
 
 use util::{MzTimestamp, PostgresErrorExt};
@@ -101,6 +102,49 @@ fn test_current_timestamp_and_now() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_mz_sleep() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client = server.connect(postgres::NoTls)?;
+
+    // Sleeps are not folded into constants during planning, which would make
+    // the coordinator sleep, but are evaluated when the query runs.
+    let plan: String = client
+        .query_one("EXPLAIN PLAN FOR SELECT mz_internal.mz_sleep(0.5)", &[])?
+        .get(0);
+    assert!(plan.contains("mz_sleep("), "{}", plan);
+
+    // A constant sleep returns null after the given duration.
+    let before = Instant::now();
+    let row = client.query_one("SELECT mz_internal.mz_sleep(0.5)", &[])?;
+    assert_eq!(row.get::<_, Option<DateTime<Utc>>>(0), None);
+    assert!(before.elapsed() >= Duration::from_millis(500));
+
+    // Sleeps evaluated in a dataflow block it for the sum of their durations.
+    client.batch_execute("CREATE TABLE t (d float8)")?;
+    client.batch_execute("INSERT INTO t VALUES (0.25), (0.25), (NULL), (-1)")?;
+    let before = Instant::now();
+    let rows = client.query("SELECT mz_internal.mz_sleep(d) FROM t", &[])?;
+    assert_eq!(rows.len(), 4);
+    assert!(before.elapsed() >= Duration::from_millis(500));
+
+    // Views must not stall the dataflows that maintain them.
+    for sql in &[
+        "CREATE VIEW v AS SELECT mz_internal.mz_sleep(1)",
+        "CREATE MATERIALIZED VIEW v AS SELECT mz_internal.mz_sleep(d) FROM t",
+    ] {
+        let err = client.batch_execute(sql).unwrap_err();
+        assert_eq!(
+            err.unwrap_db_error().message(),
+            "mz_sleep cannot be used in static queries"
+        );
+    }
+
+    Ok(())
+}
+
 #[test]
 fn test_tail_basic() -> Result<(), Box<dyn Error>> {
     ore::test::init_logging();
@@ -235,6 +279,105 @@ fn test_tail_progress() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+/// Test that `ORDER BY` sorts the updates within each timestamp, placing each
+/// retraction before the insertions that share its `ORDER BY` columns, and
+/// that the progress message still follows them.
+#[test]
+fn test_tail_order_by() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let config = util::Config::default().workers(2);
+    let server = util::start_server(config)?;
+    let mut client_writes = server.connect(postgres::NoTls)?;
+    let mut client_reads = server.connect(postgres::NoTls)?;
+
+    client_writes.batch_execute("CREATE TABLE t (a int, b text)")?;
+    client_reads.batch_execute(
+        "BEGIN;
+         DECLARE c CURSOR FOR TAIL t WITH (PROGRESS, SNAPSHOT) ORDER BY a DESC;",
+    )?;
+
+    let fetch = |client: &mut postgres::Client| -> Result<_, Box<dyn Error>> {
+        let rows = client.query("FETCH ALL c", &[])?;
+        let (progress_row, data_rows) = rows.split_last().expect("at least one row");
+        assert_eq!(progress_row.get::<_, bool>("progressed"), true);
+        let progress_ts: MzTimestamp = progress_row.get("timestamp");
+        let mut updates = vec![];
+        for row in data_rows {
+            assert_eq!(row.get::<_, bool>("progressed"), false);
+            let ts: MzTimestamp = row.get("timestamp");
+            assert!(ts < progress_ts);
+            updates.push((
+                row.get::<_, i64>("diff"),
+                row.get::<_, i32>("a"),
+                row.get::<_, String>("b"),
+            ));
+        }
+        Ok(updates)
+    };
+
+    client_writes.batch_execute("INSERT INTO t VALUES (2, 'b'), (3, 'c'), (1, 'a')")?;
+    assert_eq!(
+        fetch(&mut client_reads)?,
+        vec![
+            (1, 3, "c".to_owned()),
+            (1, 2, "b".to_owned()),
+            (1, 1, "a".to_owned())
+        ]
+    );
+
+    client_writes.batch_execute("UPDATE t SET b = b || '!' WHERE a >= 2")?;
+    assert_eq!(
+        fetch(&mut client_reads)?,
+        vec![
+            (-1, 3, "c".to_owned()),
+            (1, 3, "c!".to_owned()),
+            (-1, 2, "b".to_owned()),
+            (1, 2, "b!".to_owned()),
+        ]
+    );
+
+    Ok(())
+}
+
+/// Test that a TAIL of a temporary view keeps running after the view is
+/// dropped by `DISCARD TEMP`, until the TAIL itself is closed.
+#[test]
+fn test_tail_temporary_view() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client_writes = server.connect(postgres::NoTls)?;
+    let mut client_reads = server.connect(postgres::NoTls)?;
+
+    client_writes.batch_execute("CREATE TABLE t (a int)")?;
+    client_reads.batch_execute(
+        "CREATE TEMPORARY MATERIALIZED VIEW v AS SELECT a + 1 AS b FROM t;
+         BEGIN;
+         DECLARE c CURSOR FOR TAIL v;
+         DISCARD TEMP;",
+    )?;
+
+    for i in 1..=3 {
+        client_writes.execute("INSERT INTO t VALUES ($1)", &[&i])?;
+        let rows = client_reads.query("FETCH ALL c", &[])?;
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].get::<_, i64>("diff"), 1);
+        assert_eq!(rows[0].get::<_, i32>("b"), i + 1);
+    }
+
+    client_reads.batch_execute("CLOSE c; COMMIT")?;
+    let row = client_reads.query_one("SELECT count(*) FROM mz_views WHERE name = 'v'", &[])?;
+    assert_eq!(row.get::<_, i64>(0), 0);
+
+    // The table remains usable once the TAIL's dataflows are torn down.
+    client_writes.batch_execute("INSERT INTO t VALUES (4)")?;
+    let row = client_writes.query_one("SELECT count(*) FROM t", &[])?;
+    assert_eq!(row.get::<_, i64>(0), 4);
+
+    Ok(())
+}
+
 #[test]
 fn test_tail_fetch_timeout() -> Result<(), Box<dyn Error>> {
     ore::test::init_logging();
@@ -471,6 +614,162 @@ fn test_tail_shutdown() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
+#[test]
+fn test_statement_timeout() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client = server.connect(postgres::NoTls)?;
+
+    client.batch_execute("CREATE TABLE t (i INT8)")?;
+    client.batch_execute("INSERT INTO t VALUES (1)")?;
+    client.batch_execute("SET statement_timeout = 500")?;
+
+    let assert_timed_out = |err: postgres::Error, before: Instant| {
+        let duration = before.elapsed();
+        let err = err.unwrap_db_error();
+        assert_eq!(*err.code(), SqlState::QUERY_CANCELED);
+        assert_eq!(
+            err.message(),
+            "canceling statement due to statement timeout"
+        );
+        assert!(duration >= Duration::from_millis(500));
+        assert!(duration < Duration::from_secs(10));
+    };
+
+    // A TAIL never ends on its own, whether or not it is read via COPY.
+    let before = Instant::now();
+    let err = client.simple_query("TAIL t").unwrap_err();
+    assert_timed_out(err, before);
+
+    let mut reader = client.copy_out("COPY (TAIL t) TO STDOUT")?;
+    let err = reader.read_to_end(&mut vec![]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("canceling statement due to statement timeout"));
+    drop(reader);
+
+    // Statements that finish in time are unaffected.
+    let rows = client.query("SELECT * FROM t", &[])?;
+    assert_eq!(rows.len(), 1);
+
+    // A peek that sleeps for ten times the timeout is canceled.
+    let before = Instant::now();
+    let err = client
+        .query("SELECT mz_internal.mz_sleep(5) FROM t", &[])
+        .unwrap_err();
+    assert_timed_out(err, before);
+
+    // The connection remains usable. The sleep blocks the worker, so wait for
+    // it without a timeout.
+    client.batch_execute("SET statement_timeout = 0")?;
+    let rows = client.query("SELECT * FROM t", &[])?;
+    assert_eq!(rows.len(), 1);
+
+    Ok(())
+}
+
+// Tests that CANCEL QUERY cancels a peek listed in mz_internal.mz_active_peeks
+// from another session, and that the peek's dataflow is torn down.
+#[test]
+fn test_cancel_query() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client_slow = server.connect(postgres::NoTls)?;
+    let mut client_cancel = server.connect(postgres::NoTls)?;
+
+    client_slow.batch_execute("CREATE TABLE t (i INT8)")?;
+    client_slow.batch_execute("INSERT INTO t VALUES (1)")?;
+
+    // A peek that sleeps for far longer than it takes to cancel it.
+    let slow_thread =
+        thread::spawn(move || client_slow.query("SELECT mz_internal.mz_sleep(10) FROM t", &[]));
+
+    // The sleep blocks the only worker, which would have to serve any read of
+    // mz_internal.mz_active_peeks, so instead try to cancel every query ID
+    // that could have been assigned so far. CANCEL QUERY only cancels peeks
+    // whose results are still outstanding, which only the slow peek's are.
+    let deadline = Instant::now() + Duration::from_secs(30);
+    let query_id = 'find: loop {
+        for query_id in 0..100 {
+            let canceled: bool = client_cancel
+                .query_one(&*format!("CANCEL QUERY {}", query_id), &[])?
+                .get(0);
+            if canceled {
+                break 'find query_id;
+            }
+        }
+        assert!(Instant::now() < deadline, "slow peek never became active");
+        thread::sleep(Duration::from_millis(10));
+    };
+    let err = slow_thread.join().unwrap().unwrap_db_error();
+    assert_eq!(*err.code(), SqlState::QUERY_CANCELED);
+    assert_eq!(err.message(), "canceling statement due to user request");
+
+    // The peek is no longer listed, so there is nothing left to cancel.
+    let rows = client_cancel.query(
+        "SELECT query_id FROM mz_internal.mz_active_peeks WHERE index_id IS NULL",
+        &[],
+    )?;
+    assert!(rows.is_empty());
+    let canceled: bool = client_cancel
+        .query_one(&*format!("CANCEL QUERY {}", query_id), &[])?
+        .get(0);
+    assert!(!canceled);
+
+    // Without the peek to hold on to it, the dataflow shuts down.
+    let dataflow_operators = |client: &mut postgres::Client| {
+        client
+            .query(
+                "SELECT id FROM mz_catalog.mz_dataflow_operators \
+                 WHERE name LIKE 'Dataflow: temp-view-%'",
+                &[],
+            )
+            .map(|rows| rows.len())
+    };
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while dataflow_operators(&mut client_cancel)? > 0 {
+        assert!(
+            Instant::now() < deadline,
+            "slow peek's dataflow was never torn down"
+        );
+        thread::sleep(Duration::from_millis(100));
+    }
+
+    Ok(())
+}
+
+// Tests that canceling a COPY midway through reports the cancellation and
+// leaves the connection usable.
+#[test]
+fn test_copy_cancel() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client = server.connect(postgres::NoTls)?;
+
+    client.batch_execute("CREATE TABLE t (i INT8)")?;
+    client.batch_execute("INSERT INTO t VALUES (1)")?;
+
+    // A TAIL never ends on its own, so wait for its first row to be sure the
+    // copy is underway before canceling it.
+    let cancel_token = client.cancel_token();
+    let mut reader = client.copy_out("COPY (TAIL t) TO STDOUT WITH (FORMAT csv)")?;
+    reader.read_exact(&mut [0; 1])?;
+    cancel_token.cancel_query(postgres::NoTls)?;
+    let err = reader.read_to_end(&mut vec![]).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("canceling statement due to user request"));
+    drop(reader);
+
+    let rows = client.query("SELECT * FROM t", &[])?;
+    assert_eq!(rows.len(), 1);
+
+    Ok(())
+}
+
 // Tests that temporary views created by one connection cannot be viewed
 // by another connection.
 #[test]
@@ -502,3 +801,95 @@ fn test_temporary_views() -> Result<(), Box<dyn Error>> {
 
     Ok(())
 }
+
+// Tests that SELECT ... AS OF reads the requested timestamp, and rejects
+// timestamps that its inputs cannot serve.
+#[test]
+fn test_select_as_of() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client = server.connect(postgres::NoTls)?;
+
+    client.batch_execute("CREATE TABLE t (data text)")?;
+    client.batch_execute("INSERT INTO t VALUES ('a')")?;
+    let ts: String = client
+        .query_one("SELECT mz_logical_timestamp()::text FROM t", &[])?
+        .get(0);
+    client.batch_execute("INSERT INTO t VALUES ('b')")?;
+
+    // The coordinator may not have learned that the timestamp that was just
+    // read is complete yet.
+    let deadline = Instant::now() + Duration::from_secs(30);
+    let rows = loop {
+        match client.query(&*format!("SELECT data FROM t AS OF {}", ts), &[]) {
+            Err(_) if Instant::now() < deadline => thread::sleep(Duration::from_millis(100)),
+            res => break res?,
+        }
+    };
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, String>(0), "a");
+    let rows = client.query(
+        &*format!("SELECT data FROM t FOR SYSTEM_TIME AS OF {}", ts),
+        &[],
+    )?;
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, String>(0), "a");
+    let count: i64 = client.query_one("SELECT count(*) FROM t", &[])?.get(0);
+    assert_eq!(count, 2);
+
+    // Timestamps that are not yet complete are rejected rather than waited
+    // for.
+    let err = client
+        .query("SELECT data FROM t AS OF 18446744073709551615", &[])
+        .unwrap_db_error();
+    assert!(err.message().starts_with(
+        "Timestamp (18446744073709551615) is not yet complete for all inputs: \
+         the valid range is [0, "
+    ));
+
+    // Once the index is compacted, earlier timestamps are rejected too.
+    client.batch_execute("ALTER INDEX t_primary_idx SET (logical_compaction_window = '1ms')")?;
+    let deadline = Instant::now() + Duration::from_secs(30);
+    let err = loop {
+        match client.query("SELECT data FROM t AS OF 0", &[]) {
+            Ok(_) if Instant::now() < deadline => thread::sleep(Duration::from_millis(100)),
+            Ok(_) => panic!("timestamp 0 was never compacted"),
+            Err(e) => break e.unwrap_db_error(),
+        }
+    };
+    assert!(err
+        .message()
+        .starts_with("Timestamp (0) is not valid for all inputs: the valid range is ["));
+
+    Ok(())
+}
+
+#[test]
+fn test_mz_version() -> Result<(), Box<dyn Error>> {
+    ore::test::init_logging();
+
+    let server = util::start_server(util::Config::default())?;
+    let mut client = server.connect(postgres::NoTls)?;
+    let row = client.query_one("SELECT mz_version(), mz_version_num()", &[])?;
+    let version: String = row.get(0);
+    let version_num: i32 = row.get(1);
+
+    // The human-readable version includes the abbreviated Git SHA.
+    let version_prefix = format!("v{} (", env!("CARGO_PKG_VERSION"));
+    let sha = version
+        .strip_prefix(&version_prefix)
+        .and_then(|s| s.strip_suffix(')'))
+        .unwrap_or_else(|| panic!("unexpected mz_version: {}", version));
+    assert_eq!(sha.len(), 9);
+    assert!(sha.chars().all(|c| c.is_ascii_hexdigit()));
+    assert_eq!(sha, &materialized::BUILD_INFO.sha[..9]);
+
+    // The numeric version encodes the semver components of the crate version.
+    let major: i32 = env!("CARGO_PKG_VERSION_MAJOR").parse()?;
+    let minor: i32 = env!("CARGO_PKG_VERSION_MINOR").parse()?;
+    let patch: i32 = env!("CARGO_PKG_VERSION_PATCH").parse()?;
+    assert_eq!(version_num, major * 10000 + minor * 100 + patch);
+
+    Ok(())
+}
@@ -12,18 +12,28 @@
 //! Reserved OIDs through Materialized.
 pub const TYPE_LIST_OID: u32 = 16_384;
 pub const TYPE_MAP_OID: u32 = 16_385;
+pub const FUNC_BIT_COUNT_I32_OID: u32 = 16_441;
+pub const FUNC_BIT_COUNT_I64_OID: u32 = 16_442;
 pub const FUNC_CEIL_F32_OID: u32 = 16_386;
 pub const FUNC_CONCAT_AGG_OID: u32 = 16_387;
 pub const FUNC_CSV_EXTRACT_OID: u32 = 16_388;
 pub const FUNC_CURRENT_TIMESTAMP_OID: u32 = 16_389;
 pub const FUNC_FLOOR_F32_OID: u32 = 16_390;
+pub const FUNC_GROUPING_OID: u32 = 16_446;
 pub const FUNC_INTERNAL_READ_CACHED_DATA_OID: u32 = 16_391;
+pub const FUNC_JSONB_INSERT_OID: u32 = 16_451;
+pub const FUNC_JSONB_SET_OID: u32 = 16_450;
+pub const FUNC_LEVENSHTEIN_OID: u32 = 16_443;
+pub const FUNC_LEVENSHTEIN_LESS_EQUAL_OID: u32 = 16_444;
 pub const FUNC_LIST_APPEND_OID: u32 = 16_392;
 pub const FUNC_LIST_CAT_OID: u32 = 16_393;
 pub const FUNC_LIST_LENGTH_MAX_OID: u32 = 16_394;
 pub const FUNC_LIST_LENGTH_OID: u32 = 16_395;
 pub const FUNC_LIST_NDIMS_OID: u32 = 16_396;
 pub const FUNC_LIST_PREPEND_OID: u32 = 16_397;
+pub const FUNC_MAP_KEYS_OID: u32 = 16_448;
+pub const FUNC_MAP_LENGTH_OID: u32 = 16_447;
+pub const FUNC_MAP_VALUES_OID: u32 = 16_449;
 pub const FUNC_MAX_BOOL_OID: u32 = 16_398;
 pub const FUNC_MIN_BOOL_OID: u32 = 16_399;
 pub const FUNC_MZ_ALL_OID: u32 = 16_400;
@@ -36,12 +46,18 @@ pub const FUNC_MZ_CLASSIFY_OBJECT_ID_OID: u32 = 16_406;
 pub const FUNC_MZ_CLUSTER_ID_OID: u32 = 16_407;
 pub const FUNC_MZ_IS_MATERIALIZED_OID: u32 = 16_408;
 pub const FUNC_MZ_LOGICAL_TIMESTAMP_OID: u32 = 16_409;
+pub const FUNC_MZ_NOW_OID: u32 = 16_440;
 pub const FUNC_MZ_RENDER_TYPEMOD_OID: u32 = 16_410;
+pub const FUNC_MZ_SLEEP_OID: u32 = 16_445;
+pub const FUNC_MZ_UUID_V5_OID: u32 = 16_437;
 pub const FUNC_MZ_VERSION_OID: u32 = 16_411;
+pub const FUNC_MZ_VERSION_NUM_OID: u32 = 16_439;
+pub const FUNC_PERCENTILE_DISC_NUMERIC_OID: u32 = 16_438;
 pub const FUNC_REGEXP_EXTRACT_OID: u32 = 16_412;
 pub const FUNC_REPEAT_OID: u32 = 16_413;
 pub const FUNC_ROUND_F32_OID: u32 = 16_434;
 pub const FUNC_SQRT_F32_OID: u32 = 16_435;
+pub const FUNC_UNACCENT_OID: u32 = 16_436;
 pub const FUNC_UNNEST_LIST_OID: u32 = 16_416;
 pub const OP_CONCAT_ELEMENY_LIST_OID: u32 = 16_417;
 pub const OP_CONCAT_LIST_ELEMENT_OID: u32 = 16_418;
@@ -287,6 +287,57 @@ impl Value {
         }
     }
 
+    /// Converts this value to JSON.
+    ///
+    /// Booleans, integers, finite floats, and strings are converted to the
+    /// corresponding JSON values, and `jsonb` values are embedded as is.
+    /// Arrays, lists, and records are converted to JSON arrays of their
+    /// elements, and maps to JSON objects. All other values, including
+    /// `numeric` values, which JSON numbers cannot represent exactly, are
+    /// converted to strings in the [text encoding format](Format::Text).
+    pub fn to_json(&self) -> serde_json::Value {
+        fn elems_to_json(elems: &[Option<Value>]) -> serde_json::Value {
+            serde_json::Value::Array(
+                elems
+                    .iter()
+                    .map(|elem| match elem {
+                        None => serde_json::Value::Null,
+                        Some(elem) => elem.to_json(),
+                    })
+                    .collect(),
+            )
+        }
+
+        match self {
+            Value::Array { elements, .. } => elems_to_json(elements),
+            Value::Bool(b) => serde_json::Value::Bool(*b),
+            Value::Int4(i) => serde_json::Value::from(*i),
+            Value::Int8(i) => serde_json::Value::from(*i),
+            Value::Float4(f) if f.is_finite() => serde_json::Value::from(f64::from(*f)),
+            Value::Float8(f) if f.is_finite() => serde_json::Value::from(*f),
+            Value::Jsonb(js) => js.0.as_ref().to_serde_json(),
+            Value::List(elems) | Value::Record(elems) => elems_to_json(elems),
+            Value::Map(elems) => serde_json::Value::Object(
+                elems
+                    .iter()
+                    .map(|(key, value)| {
+                        let value = match value {
+                            None => serde_json::Value::Null,
+                            Some(value) => value.to_json(),
+                        };
+                        (key.clone(), value)
+                    })
+                    .collect(),
+            ),
+            Value::Text(s) => serde_json::Value::String(s.clone()),
+            _ => {
+                let mut buf = String::new();
+                self.encode_text(&mut buf);
+                serde_json::Value::String(buf)
+            }
+        }
+    }
+
     /// Serializes this value to `buf` using the [binary encoding
     /// format](Format::Binary).
     pub fn encode_binary(&self, ty: &Type, buf: &mut BytesMut) -> Result<(), io::Error> {
@@ -42,14 +42,8 @@ impl ToSql for Interval {
         //
         // Postgres implementation: https://github.com/postgres/postgres/blob/517bf2d91/src/backend/utils/adt/timestamp.c#L1008
         // Diesel implementation: https://github.com/diesel-rs/diesel/blob/a8b52bd05/diesel/src/pg/types/date_and_time/mod.rs#L39
-        //
-        // Our intervals are guaranteed to fit within SQL's min/max intervals,
-        // so this is compression is guaranteed to be lossless. For details, see
-        // `repr::scalar::datetime::compute_interval`.
-        let days = std::cmp::min(self.0.days() as i128, i32::MAX as i128);
-        let ns = self.0.duration - days * 24 * 60 * 60 * 1_000_000_000;
-        out.put_i64((ns / 1000) as i64);
-        out.put_i32(days as i32);
+        out.put_i64(self.0.micros);
+        out.put_i32(self.0.days);
         out.put_i32(self.0.months);
         Ok(IsNull::No)
     }
@@ -66,9 +60,7 @@ impl<'a> FromSql<'a> for Interval {
         let micros = raw.read_i64::<NetworkEndian>()?;
         let days = raw.read_i32::<NetworkEndian>()?;
         let months = raw.read_i32::<NetworkEndian>()?;
-        Ok(Interval(
-            ReprInterval::new(months, days as i64 * 24 * 60 * 60, micros * 1000).unwrap(),
-        ))
+        Ok(Interval(ReprInterval::new(months, days, micros)))
     }
 
     fn accepts(ty: &Type) -> bool {
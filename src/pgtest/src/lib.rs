@@ -235,6 +235,13 @@ impl PgTest {
                     ),
                     Message::ParameterStatus(_) => continue,
                     Message::NoData => ("NoData", "".to_string()),
+                    Message::NotificationResponse(body) => (
+                        "NotificationResponse",
+                        serde_json::to_string(&NotificationResponse {
+                            channel: body.channel().unwrap().to_string(),
+                            payload: body.message().unwrap().to_string(),
+                        })?,
+                    ),
                     _ => ("UNKNOWN", format!("'{}'", ch)),
                 };
                 if self.verbose {
@@ -315,6 +322,12 @@ pub struct CommandComplete {
     pub tag: String,
 }
 
+#[derive(Serialize)]
+pub struct NotificationResponse {
+    pub channel: String,
+    pub payload: String,
+}
+
 #[derive(Serialize)]
 pub struct ErrorResponse {
     pub fields: Vec<ErrorField>,
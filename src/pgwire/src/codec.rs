@@ -221,6 +221,7 @@ impl Encoder<BackendMessage> for Codec {
             BackendMessage::CopyOutResponse { .. } => b'H',
             BackendMessage::CopyData(_) => b'd',
             BackendMessage::CopyDone => b'c',
+            BackendMessage::NotificationResponse { .. } => b'A',
         };
         dst.put_u8(byte);
 
@@ -307,6 +308,15 @@ impl Encoder<BackendMessage> for Codec {
                 dst.put_u32(conn_id);
                 dst.put_u32(secret_key);
             }
+            BackendMessage::NotificationResponse {
+                conn_id,
+                channel,
+                payload,
+            } => {
+                dst.put_u32(conn_id);
+                dst.put_string(&channel);
+                dst.put_string(&payload);
+            }
             BackendMessage::ParameterDescription(params) => {
                 dst.put_length_i16(params.len())?;
                 for param in params {
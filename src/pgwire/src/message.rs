@@ -228,6 +228,11 @@ pub enum BackendMessage {
     },
     CopyData(Vec<u8>),
     CopyDone,
+    NotificationResponse {
+        conn_id: u32,
+        channel: String,
+        payload: String,
+    },
 }
 
 /// A local representation of [`CoordTransactionStatus`]
@@ -307,10 +312,12 @@ impl ErrorResponse {
         // inappropriately.
         let code = match e {
             CoordError::Catalog(_) => SqlState::INTERNAL_ERROR,
+            CoordError::CheckViolation { .. } => SqlState::CHECK_VIOLATION,
             CoordError::ConstrainedParameter(_) => SqlState::INVALID_PARAMETER_VALUE,
             CoordError::DuplicateCursor(_) => SqlState::DUPLICATE_CURSOR,
             CoordError::Eval(_) => SqlState::INTERNAL_ERROR,
             CoordError::InvalidParameterType(_) => SqlState::INVALID_PARAMETER_VALUE,
+            CoordError::InvalidParameterValue { .. } => SqlState::INVALID_PARAMETER_VALUE,
             CoordError::OperationProhibitsTransaction(_) => SqlState::ACTIVE_SQL_TRANSACTION,
             CoordError::OperationRequiresTransaction(_) => SqlState::NO_ACTIVE_SQL_TRANSACTION,
             CoordError::ReadOnlyTransaction => SqlState::READ_ONLY_SQL_TRANSACTION,
@@ -480,6 +487,54 @@ pub fn encode_copy_row_text(
     Ok(())
 }
 
+/// Encodes a row in the `csv` format of `COPY TO`.
+///
+/// As in PostgreSQL, nulls are written as unquoted empty fields, while fields
+/// that are empty or contain the delimiter, a quote, or a line break are
+/// quoted, so that empty strings remain distinguishable from nulls.
+pub fn encode_copy_row_csv(
+    row: Row,
+    typ: &RelationType,
+    out: &mut Vec<u8>,
+) -> Result<(), io::Error> {
+    let delim = b',';
+    let quote = b'"';
+    let mut buf = BytesMut::new();
+    for (idx, field) in pgrepr::values_from_row(row, typ).into_iter().enumerate() {
+        if idx > 0 {
+            out.push(delim);
+        }
+        match field {
+            None => (),
+            Some(field) => {
+                buf.clear();
+                field.encode_text(&mut buf);
+                // A lone `\.` marks the end of data in psql, so PostgreSQL
+                // quotes it too.
+                let needs_quotes = buf.is_empty()
+                    || &buf[..] == b"\\."
+                    || buf
+                        .iter()
+                        .any(|b| *b == delim || *b == quote || *b == b'\n' || *b == b'\r');
+                if needs_quotes {
+                    out.push(quote);
+                    for b in &buf {
+                        if *b == quote {
+                            out.push(quote);
+                        }
+                        out.push(*b);
+                    }
+                    out.push(quote);
+                } else {
+                    out.extend(&buf);
+                }
+            }
+        }
+    }
+    out.push(b'\n');
+    Ok(())
+}
+
 pub fn encode_row_description(
     desc: &RelationDesc,
     formats: &[pgrepr::Format],
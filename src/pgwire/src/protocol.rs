@@ -30,7 +30,7 @@ use tokio_stream::wrappers::UnboundedReceiverStream;
 use coord::session::{
     EndTransactionAction, Portal, PortalState, RowBatchStream, TransactionStatus,
 };
-use coord::{ExecuteResponse, StartupMessage};
+use coord::{ExecuteResponse, Notification, StartupMessage};
 use dataflow_types::PeekResponse;
 use ore::cast::CastFrom;
 use ore::netio::AsyncReady;
@@ -136,7 +136,19 @@ where
     }
 
     async fn advance_ready(&mut self) -> Result<State, io::Error> {
-        let message = self.conn.recv().await?;
+        // While waiting for the next message, deliver notifications for the
+        // channels that the session listens on, but only outside of
+        // transaction blocks, as PostgreSQL does.
+        let message = loop {
+            let idle = self.idle();
+            tokio::select! {
+                message = self.conn.recv() => break message?,
+                Some(notification) = self.coord_client.notify_rx.recv(), if idle => {
+                    self.send_notification(notification).await?;
+                    self.conn.flush().await?;
+                }
+            }
+        };
         let timer = Instant::now();
         let name = match &message {
             Some(message) => message.name(),
@@ -668,6 +680,7 @@ where
                 }
                 PortalState::InProgress(rows) => {
                     let rows = rows.take().expect("InProgress rows must be populated");
+                    let statement_deadline = self.statement_deadline();
                     self.send_rows(
                         row_desc.expect("portal missing row desc on resumption"),
                         portal_name,
@@ -676,6 +689,7 @@ where
                         get_response,
                         fetch_portal_name,
                         timeout,
+                        statement_deadline,
                     )
                     .await
                 }
@@ -854,6 +868,13 @@ where
     }
 
     async fn ready(&mut self) -> Result<State, io::Error> {
+        // Deliver the notifications that arrived while the session was busy,
+        // including those that the session sent to itself.
+        if self.idle() {
+            while let Some(Some(notification)) = self.coord_client.notify_rx.recv().now_or_never() {
+                self.send_notification(notification).await?;
+            }
+        }
         let txn_state = self.coord_client.session().transaction().into();
         self.conn
             .send(BackendMessage::ReadyForQuery(txn_state))
@@ -861,6 +882,25 @@ where
         self.flush().await
     }
 
+    /// Reports whether the session is outside of any transaction, and so can
+    /// receive notifications.
+    fn idle(&mut self) -> bool {
+        matches!(
+            self.coord_client.session().transaction(),
+            TransactionStatus::Default
+        )
+    }
+
+    async fn send_notification(&mut self, notification: Notification) -> Result<(), io::Error> {
+        self.conn
+            .send(BackendMessage::NotificationResponse {
+                conn_id: notification.conn_id,
+                channel: notification.channel,
+                payload: notification.payload,
+            })
+            .await
+    }
+
     #[allow(clippy::too_many_arguments)]
     async fn send_execute_response(
         &mut self,
@@ -883,6 +923,8 @@ where
             }};
         }
 
+        let statement_deadline = self.statement_deadline();
+
         macro_rules! created {
             ($existed:expr, $code:expr, $type:expr) => {{
                 if $existed {
@@ -916,7 +958,23 @@ where
             ExecuteResponse::CreatedIndex { existed } => {
                 created!(existed, SqlState::DUPLICATE_OBJECT, "index")
             }
-            ExecuteResponse::CreatedSource { existed } => {
+            ExecuteResponse::CreatedSource {
+                existed,
+                inferred_column_types,
+            } => {
+                if !inferred_column_types.is_empty() {
+                    let columns = inferred_column_types
+                        .iter()
+                        .map(|(name, ty)| format!("{} {}", name, pgrepr::Type::from(ty).name()))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let msg = ErrorResponse::notice(
+                        SqlState::SUCCESSFUL_COMPLETION,
+                        format!("inferred column types: {}", columns),
+                    )
+                    .into_message();
+                    self.conn.send(msg).await?;
+                }
                 created!(existed, SqlState::DUPLICATE_OBJECT, "source")
             }
             ExecuteResponse::CreatedSink { existed } => {
@@ -970,22 +1028,25 @@ where
                 // have OIDs.
                 command_complete!("INSERT 0 {}", n)
             }
+            ExecuteResponse::Listened => command_complete!("LISTEN"),
+            ExecuteResponse::Notified => command_complete!("NOTIFY"),
             ExecuteResponse::SendingRows(rx) => {
                 let row_desc =
                     row_desc.expect("missing row description for ExecuteResponse::SendingRows");
-                match rx.await {
-                    PeekResponse::Canceled => {
+                match await_peek(rx, statement_deadline).await {
+                    Err(_) => return self.statement_timeout_error().await,
+                    Ok(PeekResponse::Canceled) => {
                         self.error(ErrorResponse::error(
                             SqlState::QUERY_CANCELED,
                             "canceling statement due to user request",
                         ))
                         .await
                     }
-                    PeekResponse::Error(text) => {
+                    Ok(PeekResponse::Error(text)) => {
                         self.error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text))
                             .await
                     }
-                    PeekResponse::Rows(rows) => {
+                    Ok(PeekResponse::Rows(rows)) => {
                         self.send_rows(
                             row_desc,
                             portal_name,
@@ -994,6 +1055,7 @@ where
                             get_response,
                             fetch_portal_name,
                             timeout,
+                            statement_deadline,
                         )
                         .await
                     }
@@ -1045,6 +1107,7 @@ where
                     get_response,
                     fetch_portal_name,
                     timeout,
+                    statement_deadline,
                 )
                 .await
             }
@@ -1053,23 +1116,26 @@ where
                     row_desc.expect("missing row description for ExecuteResponse::CopyTo");
                 let rows: RowBatchStream = match *resp {
                     ExecuteResponse::Tailing { rx } => Box::new(UnboundedReceiverStream::new(rx)),
-                    ExecuteResponse::SendingRows(rx) => match rx.await {
-                        // TODO(mjibson): This logic is duplicated from SendingRows. Dedup?
-                        PeekResponse::Canceled => {
-                            return self
-                                .error(ErrorResponse::error(
-                                    SqlState::QUERY_CANCELED,
-                                    "canceling statement due to user request",
-                                ))
-                                .await;
+                    ExecuteResponse::SendingRows(rx) => {
+                        match await_peek(rx, statement_deadline).await {
+                            // TODO(mjibson): This logic is duplicated from SendingRows. Dedup?
+                            Err(_) => return self.statement_timeout_error().await,
+                            Ok(PeekResponse::Canceled) => {
+                                return self
+                                    .error(ErrorResponse::error(
+                                        SqlState::QUERY_CANCELED,
+                                        "canceling statement due to user request",
+                                    ))
+                                    .await;
+                            }
+                            Ok(PeekResponse::Error(text)) => {
+                                return self
+                                    .error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text))
+                                    .await;
+                            }
+                            Ok(PeekResponse::Rows(rows)) => Box::new(stream::iter(vec![rows])),
                         }
-                        PeekResponse::Error(text) => {
-                            return self
-                                .error(ErrorResponse::error(SqlState::INTERNAL_ERROR, text))
-                                .await;
-                        }
-                        PeekResponse::Rows(rows) => Box::new(stream::iter(vec![rows])),
-                    },
+                    }
                     _ => {
                         return self
                             .error(ErrorResponse::error(
@@ -1079,8 +1145,10 @@ where
                             .await;
                     }
                 };
-                self.copy_rows(format, row_desc, rows).await
+                self.copy_rows(format, row_desc, rows, statement_deadline)
+                    .await
             }
+            ExecuteResponse::Unlistened => command_complete!("UNLISTEN"),
             ExecuteResponse::Updated(n) => command_complete!("UPDATE {}", n),
             ExecuteResponse::AlteredObject(o) => command_complete!("ALTER {}", o),
             ExecuteResponse::AlteredIndexLogicalCompaction => command_complete!("ALTER INDEX"),
@@ -1097,6 +1165,7 @@ where
         get_response: GetResponse,
         fetch_portal_name: Option<String>,
         timeout: ExecuteTimeout,
+        statement_deadline: Option<Instant>,
     ) -> Result<State, io::Error> {
         // If this portal is being executed from a FETCH then we need to use the result
         // format type of the outer portal.
@@ -1124,18 +1193,20 @@ where
         // implements timeout deadlines if they were requested.
         async fn fetch_batch(
             deadline: Option<Instant>,
+            statement_deadline: Option<Instant>,
             rows: &mut RowBatchStream,
             canceled: impl Future<Output = ()>,
         ) -> FetchResult {
             tokio::select! {
                 _ = time::sleep_until(deadline.unwrap_or_else(time::Instant::now)), if deadline.is_some() => FetchResult::Rows(None),
+                _ = time::sleep_until(statement_deadline.unwrap_or_else(time::Instant::now)), if statement_deadline.is_some() => FetchResult::TimedOut,
                 _ = canceled => FetchResult::Cancelled,
                 batch = rows.next() => FetchResult::Rows(batch),
             }
         };
 
         let canceled = self.coord_client.canceled();
-        let mut batch = fetch_batch(deadline, &mut rows, canceled).await;
+        let mut batch = fetch_batch(deadline, statement_deadline, &mut rows, canceled).await;
         if let Some([row, ..]) = batch.as_rows() {
             let datums = row.unpack();
             let col_types = &row_desc.typ().column_types;
@@ -1214,7 +1285,7 @@ where
                     }
                     self.conn.flush().await?;
                     let canceled = self.coord_client.canceled();
-                    batch = fetch_batch(deadline, &mut rows, canceled).await;
+                    batch = fetch_batch(deadline, statement_deadline, &mut rows, canceled).await;
                 }
                 FetchResult::Cancelled => {
                     return self
@@ -1224,6 +1295,7 @@ where
                         ))
                         .await;
                 }
+                FetchResult::TimedOut => return self.statement_timeout_error().await,
             }
         }
 
@@ -1255,21 +1327,15 @@ where
         format: CopyFormat,
         row_desc: RelationDesc,
         mut stream: RowBatchStream,
+        statement_deadline: Option<Instant>,
     ) -> Result<State, io::Error> {
         let (encode_fn, encode_format): (
             fn(Row, &RelationType, &mut Vec<u8>) -> Result<(), std::io::Error>,
             pgrepr::Format,
         ) = match format {
             CopyFormat::Text => (message::encode_copy_row_text, pgrepr::Format::Text),
+            CopyFormat::Csv => (message::encode_copy_row_csv, pgrepr::Format::Text),
             CopyFormat::Binary => (message::encode_copy_row_binary, pgrepr::Format::Binary),
-            _ => {
-                return self
-                    .error(ErrorResponse::error(
-                        SqlState::FEATURE_NOT_SUPPORTED,
-                        format!("COPY TO format {:?} not supported", format),
-                    ))
-                    .await
-            }
         };
 
         let typ = row_desc.typ();
@@ -1315,6 +1381,7 @@ where
                     // See: https://github.com/tokio-rs/mio/pull/1110
                     let ready = self.conn.ready(Interest::READABLE).await?;
                     if ready.is_read_closed() {
+                        self.abandon_statement();
                         return self
                             .error(ErrorResponse::fatal(
                                 SqlState::CONNECTION_FAILURE,
@@ -1324,6 +1391,9 @@ where
                     }
                 },
                 _ = self.coord_client.canceled() => {
+                    // The coordinator has canceled any pending peek, but the
+                    // sink of a TAIL being copied must be dropped explicitly.
+                    self.abandon_statement();
                     return self
                         .error(ErrorResponse::error(
                             SqlState::QUERY_CANCELED,
@@ -1331,6 +1401,9 @@ where
                         ))
                     .await;
                 },
+                _ = time::sleep_until(statement_deadline.unwrap_or_else(Instant::now)), if statement_deadline.is_some() => {
+                    return self.statement_timeout_error().await;
+                },
                 batch = stream.next() => match batch {
                     None => break,
                     Some(rows) => {
@@ -1399,6 +1472,44 @@ where
         }
     }
 
+    /// Returns the instant at which a statement that begins executing now will
+    /// exceed the session's `statement_timeout`, if the session has one.
+    fn statement_deadline(&mut self) -> Option<Instant> {
+        match self.coord_client.session().vars().statement_timeout() {
+            0 => None,
+            ms => Some(Instant::now() + Duration::from_millis(ms.into())),
+        }
+    }
+
+    /// Reports that the current statement exceeded the session's
+    /// `statement_timeout`, after asking the coordinator to tear down any
+    /// dataflow work the statement left behind.
+    async fn statement_timeout_error(&mut self) -> Result<State, io::Error> {
+        self.abandon_statement();
+        self.error(ErrorResponse::error(
+            SqlState::QUERY_CANCELED,
+            "canceling statement due to statement timeout",
+        ))
+        .await
+    }
+
+    /// Asks the coordinator to tear down any dataflow work that the current
+    /// statement left behind, because it will end with an error before it
+    /// completes.
+    fn abandon_statement(&mut self) {
+        // The error ends a transaction that was not explicitly started,
+        // and with it any TAIL that the statement started. Explicit
+        // transactions instead keep their TAILs until they are rolled back.
+        let session = self.coord_client.session();
+        let drop_sinks = match session.transaction() {
+            TransactionStatus::Started(_) | TransactionStatus::InTransactionImplicit(_) => {
+                session.clear_transaction().0
+            }
+            _ => vec![],
+        };
+        self.coord_client.abandon_statement(drop_sinks);
+    }
+
     async fn aborted_txn_error(&mut self) -> Result<State, io::Error> {
         self.conn
             .send(BackendMessage::ErrorResponse(ErrorResponse::error(
@@ -1417,6 +1528,17 @@ where
     }
 }
 
+/// Waits for the response to a peek, giving up if `deadline` passes first.
+async fn await_peek(
+    rx: impl Future<Output = PeekResponse>,
+    deadline: Option<Instant>,
+) -> Result<PeekResponse, time::error::Elapsed> {
+    match deadline {
+        Some(deadline) => time::timeout_at(deadline, rx).await,
+        None => Ok(rx.await),
+    }
+}
+
 fn pad_formats(formats: Vec<pgrepr::Format>, n: usize) -> Result<Vec<pgrepr::Format>, String> {
     match (formats.len(), n) {
         (0, e) => Ok(vec![pgrepr::Format::Text; e]),
@@ -1508,6 +1630,7 @@ fn is_txn_exit_stmt(stmt: Option<&Statement<Raw>>) -> bool {
 enum FetchResult {
     Rows(Option<Vec<Row>>),
     Cancelled,
+    TimedOut,
 }
 
 impl FetchResult {
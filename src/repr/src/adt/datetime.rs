@@ -197,13 +197,13 @@ impl DateTimeField {
         }
     }
 
-    /// Returns the number of nanoseconds in a single unit of `field`.
+    /// Returns the number of microseconds in a single unit of `field`.
     ///
     /// # Panics
     ///
     /// Panics if called on a non-duration field.
-    pub fn nanos_multiplier(self) -> i64 {
-        self.seconds_multiplier() * 1_000_000_000
+    pub fn micros_multiplier(self) -> i64 {
+        self.seconds_multiplier() * 1_000_000
     }
 }
 
@@ -384,6 +384,30 @@ impl Default for ParsedDateTime {
     }
 }
 
+/// Adds `fraction` billionths of a unit of `d` to `seconds` and `nanos`.
+fn add_fraction_of(
+    d: DateTimeField,
+    fraction: i64,
+    seconds: &mut i64,
+    nanos: &mut i64,
+) -> Result<(), String> {
+    let f_ns = fraction
+        .checked_mul(d.seconds_multiplier())
+        .ok_or_else(|| format!("Intermediate overflow in {} fraction", d))?;
+
+    // seconds += fraction * seconds_multiplier(d) / 1_000_000_000
+    *seconds = seconds.checked_add(f_ns / 1_000_000_000).ok_or_else(|| {
+        format!(
+            "Overflows maximum seconds; \
+             cannot exceed {} seconds",
+            std::i64::MAX
+        )
+    })?;
+
+    *nanos += f_ns % 1_000_000_000;
+    Ok(())
+}
+
 impl ParsedDateTime {
     /// Compute an Interval from an ParsedDateTime.
     ///
@@ -392,14 +416,15 @@ impl ParsedDateTime {
     pub fn compute_interval(&self) -> Result<Interval, String> {
         use DateTimeField::*;
         let mut months = 0i64;
+        let mut days = 0i64;
         let mut seconds = 0i64;
         let mut nanos = 0i64;
 
         // Add all DateTimeFields, from Year to Seconds.
-        self.add_field(Year, &mut months, &mut seconds, &mut nanos)?;
+        self.add_field(Year, &mut months, &mut days, &mut seconds, &mut nanos)?;
 
         for field in Year.into_iter().take_while(|f| *f <= Second) {
-            self.add_field(field, &mut months, &mut seconds, &mut nanos)?;
+            self.add_field(field, &mut months, &mut days, &mut seconds, &mut nanos)?;
         }
 
         let months: i32 = match months.try_into() {
@@ -412,14 +437,38 @@ impl ParsedDateTime {
             }
         };
 
-        match Interval::new(months, seconds, nanos) {
-            Ok(i) => Ok(i),
-            Err(e) => Err(e.to_string()),
-        }
+        let days: i32 = match days.try_into() {
+            Ok(d) => d,
+            Err(_) => {
+                return Err(format!(
+                    "exceeds min/max days (+/-2147483647); have {}",
+                    days
+                ))
+            }
+        };
+
+        // Round nanoseconds to the nearest microsecond, half away from zero.
+        let nanos = i128::from(seconds) * 1_000_000_000 + i128::from(nanos);
+        let micros = (nanos + nanos.signum() * 500) / 1_000;
+        let micros: i64 = match micros.try_into() {
+            Ok(m) => m,
+            Err(_) => {
+                return Err(format!(
+                    "exceeds min/max interval duration \
+                     (+/-{} microseconds); have {} microseconds",
+                    std::i64::MAX,
+                    micros
+                ))
+            }
+        };
+
+        Ok(Interval::new(months, days, micros))
     }
     /// Adds the appropriate values from self's ParsedDateTime to `months`,
-    /// `seconds`, and `nanos`. These fields are then appropriate to construct
-    /// std::time::Duration, once accounting for their sign.
+    /// `days`, `seconds`, and `nanos`.
+    ///
+    /// Fractional months are carried into days, and fractional days into
+    /// seconds, as in PostgreSQL.
     ///
     /// # Errors
     /// - If any component overflows a parameter (i.e. i64).
@@ -427,6 +476,7 @@ impl ParsedDateTime {
         &self,
         d: DateTimeField,
         months: &mut i64,
+        days: &mut i64,
         seconds: &mut i64,
         nanos: &mut i64,
     ) -> Result<(), String> {
@@ -476,24 +526,40 @@ impl ParsedDateTime {
                     )
                 })?;
 
-                let m_f_ns = m_f
-                    .checked_mul(30 * Day.seconds_multiplier())
+                // A fractional month is 30 days.
+                let m_f_d = m_f
+                    .checked_mul(30)
                     .ok_or_else(|| "Intermediate overflow in MONTH fraction".to_owned())?;
 
-                // seconds += m_f * 30 * seconds_multiplier(Day) / 1_000_000_000
-                *seconds = seconds.checked_add(m_f_ns / 1_000_000_000).ok_or_else(|| {
+                // days += m_f * 30 / 1_000_000_000
+                *days = days.checked_add(m_f_d / 1_000_000_000).ok_or_else(|| {
                     format!(
-                        "Overflows maximum seconds; \
-                         cannot exceed {} seconds",
+                        "Overflows maximum days; \
+                         cannot exceed {} days",
                         std::i64::MAX
                     )
                 })?;
 
-                *nanos += m_f_ns % 1_000_000_000;
-                Ok(())
+                add_fraction_of(Day, m_f_d % 1_000_000_000, seconds, nanos)
+            }
+            Day => {
+                let (t, t_f) = match self.units_of(Day) {
+                    Some(t) => (t.unit, t.fraction),
+                    None => return Ok(()),
+                };
+
+                *days = days.checked_add(t).ok_or_else(|| {
+                    format!(
+                        "Overflows maximum days; \
+                         cannot exceed {} days",
+                        std::i64::MAX
+                    )
+                })?;
+
+                add_fraction_of(Day, t_f, seconds, nanos)
             }
-            dhms => {
-                let (t, t_f) = match self.units_of(dhms) {
+            hms => {
+                let (t, t_f) = match self.units_of(hms) {
                     Some(t) => (t.unit, t.fraction),
                     None => return Ok(()),
                 };
@@ -509,21 +575,7 @@ impl ParsedDateTime {
                         )
                     })?;
 
-                let t_f_ns = t_f
-                    .checked_mul(dhms.seconds_multiplier())
-                    .ok_or_else(|| format!("Intermediate overflow in {} fraction", dhms))?;
-
-                // seconds += t_f * seconds_multiplier(dhms) / 1_000_000_000
-                *seconds = seconds.checked_add(t_f_ns / 1_000_000_000).ok_or_else(|| {
-                    format!(
-                        "Overflows maximum seconds; \
-                         cannot exceed {} seconds",
-                        std::i64::MAX
-                    )
-                })?;
-
-                *nanos += t_f_ns % 1_000_000_000;
-                Ok(())
+                add_fraction_of(hms, t_f, seconds, nanos)
             }
         }
     }
@@ -3318,20 +3370,21 @@ fn test_parseddatetime_add_field() {
         ..Default::default()
     };
 
-    run_test_parseddatetime_add_field(pdt_unit.clone(), Year, (12, 0, 0));
-    run_test_parseddatetime_add_field(pdt_unit.clone(), Month, (2, 0, 0));
-    run_test_parseddatetime_add_field(pdt_unit.clone(), Day, (0, 2 * 60 * 60 * 24, 0));
-    run_test_parseddatetime_add_field(pdt_unit.clone(), Hour, (0, 3 * 60 * 60, 0));
-    run_test_parseddatetime_add_field(pdt_unit.clone(), Minute, (0, 4 * 60, 0));
-    run_test_parseddatetime_add_field(pdt_unit, Second, (0, 5, 0));
-    run_test_parseddatetime_add_field(pdt_frac.clone(), Year, (18, 0, 0));
+    run_test_parseddatetime_add_field(pdt_unit.clone(), Year, (12, 0, 0, 0));
+    run_test_parseddatetime_add_field(pdt_unit.clone(), Month, (2, 0, 0, 0));
+    run_test_parseddatetime_add_field(pdt_unit.clone(), Day, (0, 2, 0, 0));
+    run_test_parseddatetime_add_field(pdt_unit.clone(), Hour, (0, 0, 3 * 60 * 60, 0));
+    run_test_parseddatetime_add_field(pdt_unit.clone(), Minute, (0, 0, 4 * 60, 0));
+    run_test_parseddatetime_add_field(pdt_unit, Second, (0, 0, 5, 0));
+    run_test_parseddatetime_add_field(pdt_frac.clone(), Year, (18, 0, 0, 0));
     run_test_parseddatetime_add_field(
         pdt_frac.clone(),
         Month,
         (
             2,
             // 16 days 15:59:59.99856
-            16 * 60 * 60 * 24 + 15 * 60 * 60 + 59 * 60 + 59,
+            16,
+            15 * 60 * 60 + 59 * 60 + 59,
             998_560_000,
         ),
     );
@@ -3341,7 +3394,8 @@ fn test_parseddatetime_add_field() {
         (
             0,
             // 2 days 13:19:59.999952
-            2 * 60 * 60 * 24 + 13 * 60 * 60 + 19 * 60 + 59,
+            2,
+            13 * 60 * 60 + 19 * 60 + 59,
             999_952_000,
         ),
     );
@@ -3349,6 +3403,7 @@ fn test_parseddatetime_add_field() {
         pdt_frac.clone(),
         Hour,
         (
+            0,
             0,
             // 03:33:19.999998
             3 * 60 * 60 + 33 * 60 + 19,
@@ -3359,6 +3414,7 @@ fn test_parseddatetime_add_field() {
         pdt_frac.clone(),
         Minute,
         (
+            0,
             0,
             // 00:04:33.333333
             4 * 60 + 33,
@@ -3369,20 +3425,22 @@ fn test_parseddatetime_add_field() {
         pdt_frac,
         Second,
         (
+            0,
             0,
             // 00:00:05.555556
             5,
             555_555_555,
         ),
     );
-    run_test_parseddatetime_add_field(pdt_frac_neg.clone(), Year, (-18, 0, 0));
+    run_test_parseddatetime_add_field(pdt_frac_neg.clone(), Year, (-18, 0, 0, 0));
     run_test_parseddatetime_add_field(
         pdt_frac_neg.clone(),
         Month,
         (
             -2,
             // -16 days -15:59:59.99856
-            -(16 * 60 * 60 * 24 + 15 * 60 * 60 + 59 * 60 + 59),
+            -16,
+            -(15 * 60 * 60 + 59 * 60 + 59),
             -998_560_000,
         ),
     );
@@ -3391,8 +3449,9 @@ fn test_parseddatetime_add_field() {
         Day,
         (
             0,
-            // -2 days 13:19:59.999952
-            -(2 * 60 * 60 * 24 + 13 * 60 * 60 + 19 * 60 + 59),
+            // -2 days -13:19:59.999952
+            -2,
+            -(13 * 60 * 60 + 19 * 60 + 59),
             -999_952_000,
         ),
     );
@@ -3400,6 +3459,7 @@ fn test_parseddatetime_add_field() {
         pdt_frac_neg.clone(),
         Hour,
         (
+            0,
             0,
             // -03:33:19.999998
             -(3 * 60 * 60 + 33 * 60 + 19),
@@ -3410,6 +3470,7 @@ fn test_parseddatetime_add_field() {
         pdt_frac_neg.clone(),
         Minute,
         (
+            0,
             0,
             // -00:04:33.333333
             -(4 * 60 + 33),
@@ -3420,6 +3481,7 @@ fn test_parseddatetime_add_field() {
         pdt_frac_neg,
         Second,
         (
+            0,
             0,
             // -00:00:05.555556
             -5,
@@ -3430,14 +3492,14 @@ fn test_parseddatetime_add_field() {
     fn run_test_parseddatetime_add_field(
         pdt: ParsedDateTime,
         f: DateTimeField,
-        expected: (i64, i64, i64),
+        expected: (i64, i64, i64, i64),
     ) {
-        let mut res = (0, 0, 0);
+        let mut res = (0, 0, 0, 0);
 
-        pdt.add_field(f, &mut res.0, &mut res.1, &mut res.2)
+        pdt.add_field(f, &mut res.0, &mut res.1, &mut res.2, &mut res.3)
             .unwrap();
 
-        if res.0 != expected.0 || res.1 != expected.1 || res.2 != expected.2 {
+        if res != expected {
             panic!(
                 "test_parseddatetime_add_field failed \n actual: {:?} \n expected: {:?}",
                 res, expected
@@ -3489,8 +3551,8 @@ fn test_parseddatetime_compute_interval() {
             second: Some(DateTimeFieldValue::new(-4, -500_000_000)),
             ..Default::default()
         },
-        // 21:56:55.5
-        Interval::new(0, 21 * 60 * 60 + 56 * 60 + 55, 500_000_000).unwrap(),
+        // 1 day -02:03:04.5
+        Interval::new(0, 1, -(2 * 60 * 60 + 3 * 60 + 4) * 1_000_000 - 500_000),
     );
     run_test_parseddatetime_compute_interval(
         ParsedDateTime {
@@ -3500,8 +3562,8 @@ fn test_parseddatetime_compute_interval() {
             second: Some(DateTimeFieldValue::new(4, 500_000_000)),
             ..Default::default()
         },
-        // -21:56:55.5
-        Interval::new(0, -(21 * 60 * 60 + 56 * 60 + 55), -500_000_000).unwrap(),
+        // -1 day +02:03:04.5
+        Interval::new(0, -1, (2 * 60 * 60 + 3 * 60 + 4) * 1_000_000 + 500_000),
     );
     run_test_parseddatetime_compute_interval(
         ParsedDateTime {
@@ -3509,8 +3571,8 @@ fn test_parseddatetime_compute_interval() {
             second: Some(DateTimeFieldValue::new(0, -270_000_000)),
             ..Default::default()
         },
-        // 23:59:59.73
-        Interval::new(0, 23 * 60 * 60 + 59 * 60 + 59, 730_000_000).unwrap(),
+        // 1 day -00:00:00.27
+        Interval::new(0, 1, -270_000),
     );
     run_test_parseddatetime_compute_interval(
         ParsedDateTime {
@@ -3518,8 +3580,8 @@ fn test_parseddatetime_compute_interval() {
             second: Some(DateTimeFieldValue::new(0, 270_000_000)),
             ..Default::default()
         },
-        // -23:59:59.73
-        Interval::new(0, -(23 * 60 * 60 + 59 * 60 + 59), -730_000_000).unwrap(),
+        // -1 day +00:00:00.27
+        Interval::new(0, -1, 270_000),
     );
     run_test_parseddatetime_compute_interval(
         ParsedDateTime {
@@ -3531,13 +3593,8 @@ fn test_parseddatetime_compute_interval() {
             second: Some(DateTimeFieldValue::new(6, 555_555_555)),
             ..Default::default()
         },
-        // -1 year -4 months +13 days +07:07:53.220828
-        Interval::new(
-            -16,
-            13 * 60 * 60 * 24 + 7 * 60 * 60 + 7 * 60 + 53,
-            220_828_255,
-        )
-        .unwrap(),
+        // -1 year -4 months +13 days 07:07:53.220828
+        Interval::new(-16, 13, (7 * 60 * 60 + 7 * 60 + 53) * 1_000_000 + 220_828),
     );
     run_test_parseddatetime_compute_interval(
         ParsedDateTime {
@@ -3549,19 +3606,16 @@ fn test_parseddatetime_compute_interval() {
             second: Some(DateTimeFieldValue::new(6, 555_555_555)),
             ..Default::default()
         },
-        // -1 year -4 months +13 days +07:07:53.220828255
-        Interval::new(
-            -16,
-            13 * 60 * 60 * 24 + 7 * 60 * 60 + 7 * 60 + 53,
-            220_828_255,
-        )
-        .unwrap(),
+        // -1 year -4 months +13 days 07:07:53.220828
+        Interval::new(-16, 13, (7 * 60 * 60 + 7 * 60 + 53) * 1_000_000 + 220_828),
     );
 
     fn run_test_parseddatetime_compute_interval(pdt: ParsedDateTime, expected: Interval) {
         let actual = pdt.compute_interval().unwrap();
 
-        if actual != expected {
+        if (actual.months, actual.days, actual.micros)
+            != (expected.months, expected.days, expected.micros)
+        {
             panic!(
                 "test_interval_compute_interval failed\n input {:?}\nactual {:?}\nexpected {:?}",
                 pdt, actual, expected
@@ -91,7 +91,16 @@ impl Significand {
 
     /// Parses a `Significand` from a buffer storing the two's complement
     /// representation of the significand in big-endian byte order.
-    pub fn from_twos_complement_be(input: &[u8]) -> Result<Significand, anyhow::Error> {
+    ///
+    /// The buffer may be wider than 16 bytes, as long as the extra high-order
+    /// bytes only extend the sign of the significand.
+    pub fn from_twos_complement_be(mut input: &[u8]) -> Result<Significand, anyhow::Error> {
+        while input.len() > 16
+            && ((input[0] == 0x00 && input[1] & 0x80 == 0)
+                || (input[0] == 0xff && input[1] & 0x80 != 0))
+        {
+            input = &input[1..];
+        }
         if input.len() > 16 {
             bail!("decimal exceeds maximum precision")
         }
@@ -576,6 +585,30 @@ mod tests {
         assert_eq!(d("55.5555").round(-3), d("0.0000"));
     }
 
+    #[test]
+    fn test_from_twos_complement_be() -> Result<(), anyhow::Error> {
+        let from = Significand::from_twos_complement_be;
+        assert_eq!(from(&[])?, Significand::new(0));
+        assert_eq!(from(&[0x00])?, Significand::new(0));
+        assert_eq!(from(&[0x7f])?, Significand::new(127));
+        assert_eq!(from(&[0xba])?, Significand::new(-70));
+        assert_eq!(from(&[0x00, 0xba])?, Significand::new(186));
+        assert_eq!(from(&[0xff; 16])?, Significand::new(-1));
+        assert_eq!(from(&[0xff; 20])?, Significand::new(-1));
+
+        // Sign extension beyond 16 bytes is permitted, but significant
+        // digits are not.
+        let mut max = vec![0x00; 4];
+        max.extend(&i128::MAX.to_be_bytes());
+        assert_eq!(from(&max)?, Significand::new(i128::MAX));
+        let mut min = vec![0xff; 4];
+        min.extend(&i128::MIN.to_be_bytes());
+        assert_eq!(from(&min)?, Significand::new(i128::MIN));
+        assert!(from(&[0x01; 17]).is_err());
+        assert!(from(&[0x00, 0x80, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]).is_err());
+        Ok(())
+    }
+
     #[test]
     fn test_parse_decimal() {
         assert_eq!(d("123.45"), Significand::new(12345).with_scale(2));
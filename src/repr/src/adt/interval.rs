@@ -9,32 +9,53 @@
 
 //! A time interval abstract data type.
 
+use std::cmp::Ordering;
+use std::convert::TryFrom;
 use std::fmt::{self, Write};
+use std::hash::{Hash, Hasher};
 
 use anyhow::bail;
 use serde::{Deserialize, Serialize};
 
 use crate::adt::datetime::DateTimeField;
 
+/// The number of days in a month, for the purpose of comparing and justifying
+/// intervals.
+const DAYS_PER_MONTH: i64 = 30;
+
+/// The number of microseconds in a day.
+const MICROS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000;
+
 /// An interval of time meant to express SQL intervals.
 ///
 /// Obtained by parsing an `INTERVAL '<value>' <unit> [TO <precision>]`.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Hash, Deserialize)]
+///
+/// Like in PostgreSQL, the months, days, and microseconds of an interval are
+/// independent, as the length of a month in days, or of a day in hours, depends
+/// on the instant to which the interval is applied. Only comparisons convert
+/// between the units, treating months as 30 days and days as 24 hours, so e.g.
+/// `1 day` and `24 hours` are equal. [`Interval::cmp_structural`] compares the
+/// units themselves.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 pub struct Interval {
     /// A possibly negative number of months for field types like `YEAR`
     pub months: i32,
-    /// A timespan represented in nanoseconds.
+    /// A possibly negative number of days.
+    ///
+    /// Irrespective of values, `days` will not be carried over into `months`.
+    pub days: i32,
+    /// A timespan represented in microseconds.
     ///
-    /// Irrespective of values, `duration` will not be carried over into
-    /// `months`.
-    pub duration: i128,
+    /// Irrespective of values, `micros` will not be carried over into `days`.
+    pub micros: i64,
 }
 
 impl Default for Interval {
     fn default() -> Self {
         Self {
             months: 0,
-            duration: 0,
+            days: 0,
+            micros: 0,
         }
     }
 }
@@ -44,101 +65,123 @@ impl std::ops::Neg for Interval {
     fn neg(self) -> Self {
         Self {
             months: -self.months,
-            duration: -self.duration,
+            days: -self.days,
+            micros: -self.micros,
         }
     }
 }
 
-impl Interval {
-    /// Constructs a new `Interval` with the specified units of time.
-    ///
-    /// `nanos` in excess of `999_999_999` are carried over into seconds.
-    pub fn new(months: i32, seconds: i64, nanos: i64) -> Result<Interval, anyhow::Error> {
-        let i = Interval {
-            months,
-            duration: i128::from(seconds) * 1_000_000_000 + i128::from(nanos),
-        };
-        // Don't let our duration exceed Postgres' min/max for those same fields,
-        // equivalent to:
-        // ```
-        // SELECT INTERVAL '2147483647 days 2147483647 hours 59 minutes 59.999999 seconds';
-        // SELECT INTERVAL '-2147483647 days -2147483647 hours -59 minutes -59.999999 seconds';
-        // ```
-        if i.duration > 193_273_528_233_599_999_999_000
-            || i.duration < -193_273_528_233_599_999_999_000
-        {
-            bail!(
-                "exceeds min/max interval duration +/-(2147483647 days 2147483647 hours \
-                59 minutes 59.999999 seconds)"
-            )
-        } else {
-            Ok(i)
-        }
+impl PartialEq for Interval {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_comparable_micros() == other.as_comparable_micros()
     }
+}
 
-    pub fn checked_add(&self, other: &Self) -> Option<Self> {
-        let months = match self.months.checked_add(other.months) {
-            Some(m) => m,
-            None => return None,
-        };
-        let seconds = match self.dur_as_secs().checked_add(other.dur_as_secs()) {
-            Some(s) => s,
-            None => return None,
-        };
+impl Eq for Interval {}
+
+impl PartialOrd for Interval {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Interval {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_comparable_micros()
+            .cmp(&other.as_comparable_micros())
+    }
+}
 
-        match Self::new(
+impl Hash for Interval {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_comparable_micros().hash(state)
+    }
+}
+
+impl Interval {
+    /// Constructs a new `Interval` with the specified units of time.
+    pub fn new(months: i32, days: i32, micros: i64) -> Interval {
+        Interval {
             months,
-            seconds,
-            i64::from(self.nanoseconds() + other.nanoseconds()),
-        ) {
-            Ok(i) => Some(i),
-            Err(_) => None,
+            days,
+            micros,
         }
     }
 
-    pub fn checked_mul(&self, other: f64) -> Option<Self> {
-        let months = self.months as f64 * other;
-        if months.is_nan() || months < i32::MIN as f64 || months > i32::MAX as f64 {
-            return None;
-        }
+    /// Compares the months, days, and microseconds of two intervals, in that
+    /// order, rather than their lengths. Unlike [`Ord::cmp`], this reports
+    /// `1 day` and `24 hours` as different.
+    pub fn cmp_structural(&self, other: &Self) -> Ordering {
+        (self.months, self.days, self.micros).cmp(&(other.months, other.days, other.micros))
+    }
 
-        let seconds =
-            self.dur_as_secs() as f64 * other + months.fract() * 60.0 * 60.0 * 24.0 * 30.0;
-        if seconds.is_nan() || seconds < i64::MIN as f64 || seconds > i64::MAX as f64 {
-            return None;
-        }
+    /// Computes the length of the interval in microseconds, treating months as
+    /// 30 days and days as 24 hours, as PostgreSQL does to compare intervals.
+    fn as_comparable_micros(&self) -> i128 {
+        (i128::from(self.months) * i128::from(DAYS_PER_MONTH) + i128::from(self.days))
+            * i128::from(MICROS_PER_DAY)
+            + i128::from(self.micros)
+    }
 
-        let nanos = self.nanoseconds() as f64 * other + seconds.fract() * 1e9;
-        if nanos.is_nan() || nanos < i64::MIN as f64 || nanos > i64::MAX as f64 {
-            return None;
-        }
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Some(Self::new(
+            self.months.checked_add(other.months)?,
+            self.days.checked_add(other.days)?,
+            self.micros.checked_add(other.micros)?,
+        ))
+    }
 
-        Self::new(months as i32, seconds as i64, nanos as i64).ok()
+    pub fn checked_mul(&self, other: f64) -> Option<Self> {
+        self.checked_scale(|x| x * other)
     }
 
     pub fn checked_div(&self, other: f64) -> Option<Self> {
-        let months = self.months as f64 / other;
-        if months.is_nan() || months < i32::MIN as f64 || months > i32::MAX as f64 {
+        self.checked_scale(|x| x / other)
+    }
+
+    /// Applies `scale` to each of the interval's units, carrying fractional
+    /// months into days and fractional days into microseconds, as PostgreSQL's
+    /// `interval_mul` and `interval_div` do.
+    fn checked_scale<F>(&self, scale: F) -> Option<Self>
+    where
+        F: Fn(f64) -> f64,
+    {
+        const SECS_PER_DAY: f64 = 60.0 * 60.0 * 24.0;
+        let fits_i32 = |x: f64| x >= f64::from(i32::MIN) && x <= f64::from(i32::MAX);
+
+        let months = scale(f64::from(self.months));
+        let days = scale(f64::from(self.days));
+        if !fits_i32(months) || !fits_i32(days) {
             return None;
         }
 
-        let seconds =
-            self.dur_as_secs() as f64 / other + months.fract() * 60.0 * 60.0 * 24.0 * 30.0;
-        if seconds.is_nan() || seconds < i64::MIN as f64 || seconds > i64::MAX as f64 {
+        // Fractional months become days, and what remains of fractional days
+        // becomes seconds, rounded to the nearest microsecond.
+        let month_remainder_days = months.fract() * DAYS_PER_MONTH as f64;
+        let mut sec_remainder = (days.fract() + month_remainder_days.fract()) * SECS_PER_DAY;
+        sec_remainder = (sec_remainder * 1e6).round() / 1e6;
+        let mut days = days.trunc() + month_remainder_days.trunc();
+        if sec_remainder.abs() >= SECS_PER_DAY {
+            let extra_days = (sec_remainder / SECS_PER_DAY).trunc();
+            days += extra_days;
+            sec_remainder -= extra_days * SECS_PER_DAY;
+        }
+        if !fits_i32(days) {
             return None;
         }
 
-        let nanos = self.nanoseconds() as f64 / other + seconds.fract() * 1e9;
-        if nanos.is_nan() || nanos < i64::MIN as f64 || nanos > i64::MAX as f64 {
+        let micros = (scale(self.micros as f64) + sec_remainder * 1e6).round();
+        if !(micros >= i64::MIN as f64 && micros <= i64::MAX as f64) {
             return None;
         }
 
-        Self::new(months as i32, seconds as i64, nanos as i64).ok()
+        Some(Self::new(months as i32, days as i32, micros as i64))
     }
 
-    /// Returns the total number of whole seconds in the `Interval`'s duration.
-    pub fn dur_as_secs(&self) -> i64 {
-        (self.duration / 1_000_000_000) as i64
+    /// Computes the length of the interval's days and microseconds in
+    /// microseconds, treating days as 24 hours.
+    pub fn dur_as_micros(&self) -> i128 {
+        i128::from(self.days) * i128::from(MICROS_PER_DAY) + i128::from(self.micros)
     }
 
     /// Computes the millennium part of the interval.
@@ -193,20 +236,22 @@ impl Interval {
 
     /// Computes the day part of the interval.
     ///
-    /// The day part is the number of whole days in the interval. For example,
-    /// this function returns `5.0` for the interval `5 days 4 hours 3 minutes
-    /// 2.1 seconds`.
+    /// The day part is the number of days in the interval, which is not
+    /// affected by its hours. For example, this function returns `5.0` for the
+    /// interval `5 days 4 hours 3 minutes 2.1 seconds`, and `0.0` for the
+    /// interval `36 hours`.
     pub fn days(&self) -> f64 {
-        (self.dur_as_secs() / (60 * 60 * 24)) as f64
+        f64::from(self.days)
     }
 
     /// Computes the hour part of the interval.
     ///
-    /// The hour part is the number of whole hours in the interval, modulo 24.
-    /// For example, this function returns `4.0` for the interval `5 days 4
-    /// hours 3 minutes 2.1 seconds`.
+    /// The hour part is the number of whole hours in the interval's time, which
+    /// is not carried over into days. For example, this function returns `4.0`
+    /// for the interval `5 days 4 hours 3 minutes 2.1 seconds`, and `36.0` for
+    /// the interval `36 hours`.
     pub fn hours(&self) -> f64 {
-        ((self.dur_as_secs() / (60 * 60)) % 24) as f64
+        (self.micros / (60 * 60 * 1_000_000)) as f64
     }
 
     /// Computes the minute part of the interval.
@@ -215,7 +260,7 @@ impl Interval {
     /// 60. For example, this function returns `3.0` for the interval `5 days 4
     /// hours 3 minutes 2.1 seconds`.
     pub fn minutes(&self) -> f64 {
-        ((self.dur_as_secs() / 60) % 60) as f64
+        ((self.micros / (60 * 1_000_000)) % 60) as f64
     }
 
     /// Computes the second part of the interval.
@@ -223,7 +268,7 @@ impl Interval {
     /// The second part is the number of fractional seconds in the interval,
     /// modulo 60.0.
     pub fn seconds(&self) -> f64 {
-        (self.duration % 60_000_000_000) as f64 / 1e9
+        (self.micros % 60_000_000) as f64 / 1e6
     }
 
     /// Computes the second part of the interval displayed in milliseconds.
@@ -231,7 +276,7 @@ impl Interval {
     /// The second part is the number of fractional seconds in the interval,
     /// modulo 60.0.
     pub fn milliseconds(&self) -> f64 {
-        (self.duration % 60_000_000_000) as f64 / 1e6
+        (self.micros % 60_000_000) as f64 / 1e3
     }
 
     /// Computes the second part of the interval displayed in microseconds.
@@ -239,20 +284,91 @@ impl Interval {
     /// The second part is the number of fractional seconds in the interval,
     /// modulo 60.0.
     pub fn microseconds(&self) -> f64 {
-        (self.duration % 60_000_000_000) as f64 / 1e3
-    }
-
-    /// Computes the nanosecond part of the interval.
-    pub fn nanoseconds(&self) -> i32 {
-        (self.duration % 1_000_000_000) as i32
+        (self.micros % 60_000_000) as f64
     }
 
     /// Computes the total number of seconds in the interval.
+    ///
+    /// Years count as 365.25 days, the remaining months as 30 days, and days as
+    /// 24 hours.
     pub fn as_seconds(&self) -> f64 {
         self.years() * 60.0 * 60.0 * 24.0 * 365.25
             + self.months() * 60.0 * 60.0 * 24.0 * 30.0
-            + (self.dur_as_secs() as f64)
-            + f64::from(self.nanoseconds()) / 1e9
+            + self.days() * 60.0 * 60.0 * 24.0
+            + self.micros as f64 / 1e6
+    }
+
+    /// Converts whole multiples of 24 hours into days, as in PostgreSQL's
+    /// `justify_hours`.
+    ///
+    /// Returns `None` if the number of days overflows.
+    pub fn justify_hours(&self) -> Option<Self> {
+        let mut days = i64::from(self.days) + self.micros / MICROS_PER_DAY;
+        let mut micros = self.micros % MICROS_PER_DAY;
+        if days > 0 && micros < 0 {
+            micros += MICROS_PER_DAY;
+            days -= 1;
+        } else if days < 0 && micros > 0 {
+            micros -= MICROS_PER_DAY;
+            days += 1;
+        }
+        Some(Self::new(self.months, i32::try_from(days).ok()?, micros))
+    }
+
+    /// Converts whole multiples of 30 days into months, as in PostgreSQL's
+    /// `justify_days`.
+    ///
+    /// Returns `None` if the number of months overflows.
+    pub fn justify_days(&self) -> Option<Self> {
+        let mut months = i64::from(self.months) + i64::from(self.days) / DAYS_PER_MONTH;
+        let mut days = i64::from(self.days) % DAYS_PER_MONTH;
+        if months > 0 && days < 0 {
+            days += DAYS_PER_MONTH;
+            months -= 1;
+        } else if months < 0 && days > 0 {
+            days -= DAYS_PER_MONTH;
+            months += 1;
+        }
+        Some(Self::new(
+            i32::try_from(months).ok()?,
+            i32::try_from(days).ok()?,
+            self.micros,
+        ))
+    }
+
+    /// Converts whole multiples of 24 hours into days and whole multiples of 30
+    /// days into months, with every unit given the same sign, as in
+    /// PostgreSQL's `justify_interval`.
+    ///
+    /// Returns `None` if the number of days or months overflows.
+    pub fn justify_interval(&self) -> Option<Self> {
+        let mut months = i64::from(self.months);
+        let mut days = i64::from(self.days) + self.micros / MICROS_PER_DAY;
+        let mut micros = self.micros % MICROS_PER_DAY;
+        months += days / DAYS_PER_MONTH;
+        days %= DAYS_PER_MONTH;
+
+        if months > 0 && (days < 0 || (days == 0 && micros < 0)) {
+            days += DAYS_PER_MONTH;
+            months -= 1;
+        } else if months < 0 && (days > 0 || (days == 0 && micros > 0)) {
+            days -= DAYS_PER_MONTH;
+            months += 1;
+        }
+
+        if days > 0 && micros < 0 {
+            micros += MICROS_PER_DAY;
+            days -= 1;
+        } else if days < 0 && micros > 0 {
+            micros -= MICROS_PER_DAY;
+            days += 1;
+        }
+
+        Some(Self::new(
+            i32::try_from(months).ok()?,
+            i32::try_from(days).ok()?,
+            micros,
+        ))
     }
 
     /// Truncate the "head" of the interval, removing all time units greater than `f`.
@@ -263,24 +379,23 @@ impl Interval {
             DateTimeField::Day => self.months = 0,
             hms => {
                 self.months = 0;
-                self.duration %= hms.next_largest().nanos_multiplier() as i128
+                self.days = 0;
+                self.micros %= hms.next_largest().micros_multiplier()
             }
         }
     }
 
-    /// Converts this `Interval`'s duration into `chrono::Duration`.
+    /// Converts this `Interval`'s days and microseconds into
+    /// `chrono::Duration`, treating days as 24 hours.
     pub fn duration_as_chrono(&self) -> chrono::Duration {
         use chrono::Duration;
-        // This can be converted into a single call with
-        // https://github.com/chronotope/chrono/pull/426
-        Duration::seconds(self.dur_as_secs() as i64)
-            + Duration::nanoseconds(self.nanoseconds() as i64)
+        Duration::days(i64::from(self.days)) + Duration::microseconds(self.micros)
     }
 
     /// Truncate the "tail" of the interval, removing all time units less than `f`.
     /// # Arguments
     /// - `f`: Round the interval down to the specified time unit.
-    /// - `fsec_max_precision`: If `Some(x)`, keep only `x` places of nanosecond precision.
+    /// - `fsec_max_precision`: If `Some(x)`, keep only `x` places of microsecond precision.
     ///    Must be `(0,6)`.
     ///
     /// # Errors
@@ -294,12 +409,17 @@ impl Interval {
         match f {
             Year => {
                 self.months -= self.months % 12;
-                self.duration = 0;
+                self.days = 0;
+                self.micros = 0;
             }
             Month => {
-                self.duration = 0;
+                self.days = 0;
+                self.micros = 0;
             }
-            // Round nanoseconds.
+            Day => {
+                self.micros = 0;
+            }
+            // Round microseconds.
             Second => {
                 let default_precision = 6;
                 let precision = match fsec_max_precision {
@@ -314,16 +434,16 @@ impl Interval {
                     )
                 }
 
-                // Check if value should round up to nearest fractional place.
-                let remainder = self.duration % 10_i128.pow(9 - precision as u32);
-                if remainder / 10_i128.pow(8 - precision as u32) > 4 {
-                    self.duration += 10_i128.pow(9 - precision as u32);
+                // Round half away from zero to the nearest fractional place.
+                let unit = 10_i64.pow(6 - precision as u32);
+                let remainder = self.micros % unit;
+                self.micros -= remainder;
+                if remainder.abs() * 2 >= unit {
+                    self.micros += remainder.signum() * unit;
                 }
-
-                self.duration -= remainder;
             }
-            dhm => {
-                self.duration -= self.duration % dhm.nanos_multiplier() as i128;
+            hm => {
+                self.micros -= self.micros % hm.micros_multiplier();
             }
         }
         Ok(())
@@ -335,23 +455,23 @@ impl Interval {
 /// Example outputs:
 ///
 /// * 1 year 2 months 5 days 03:04:00
-/// * -1 year +5 days +18:59:29.3
+/// * -1 year +5 days -18:59:29.3
+/// * 36:00:00
 /// * 00:00:00
 impl fmt::Display for Interval {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        let mut months = self.months;
-        let neg_mos = months < 0;
-        months = months.abs();
+        let neg_mos = self.months < 0;
+        let months = i64::from(self.months).abs();
         let years = months / 12;
-        months %= 12;
-        let mut secs = self.dur_as_secs().abs();
-        let mut nanos = self.nanoseconds().abs();
-        let days = secs / (24 * 60 * 60);
-        secs %= 24 * 60 * 60;
-        let hours = secs / (60 * 60);
-        secs %= 60 * 60;
-        let minutes = secs / 60;
-        secs %= 60;
+        let months = months % 12;
+        let days = i64::from(self.days).abs();
+        let mut micros = i128::from(self.micros).abs();
+        let hours = micros / (60 * 60 * 1_000_000);
+        micros %= 60 * 60 * 1_000_000;
+        let minutes = micros / (60 * 1_000_000);
+        micros %= 60 * 1_000_000;
+        let secs = micros / 1_000_000;
+        let mut fsecs = micros % 1_000_000;
 
         if years > 0 {
             if neg_mos {
@@ -380,7 +500,7 @@ impl fmt::Display for Interval {
             if years > 0 || months > 0 {
                 f.write_char(' ')?;
             }
-            if self.duration < 0 {
+            if self.days < 0 {
                 f.write_char('-')?;
             } else if neg_mos {
                 f.write_char('+')?;
@@ -391,25 +511,27 @@ impl fmt::Display for Interval {
             }
         }
 
-        let non_zero_hmsn = hours > 0 || minutes > 0 || secs > 0 || nanos > 0;
+        // Like PostgreSQL, positive units that follow a negative one are
+        // explicitly marked as positive.
+        let neg_before = if days > 0 { self.days < 0 } else { neg_mos };
 
-        if (years == 0 && months == 0 && days == 0) || non_zero_hmsn {
+        if (years == 0 && months == 0 && days == 0) || self.micros != 0 {
             if years > 0 || months > 0 || days > 0 {
                 f.write_char(' ')?;
             }
-            if self.duration < 0 && non_zero_hmsn {
+            if self.micros < 0 {
                 f.write_char('-')?;
-            } else if neg_mos {
+            } else if neg_before && self.micros > 0 {
                 f.write_char('+')?;
             }
             write!(f, "{:02}:{:02}:{:02}", hours, minutes, secs)?;
-            if nanos > 0 {
-                let mut width = 9;
-                while nanos % 10 == 0 {
+            if fsecs > 0 {
+                let mut width = 6;
+                while fsecs % 10 == 0 {
                     width -= 1;
-                    nanos /= 10;
+                    fsecs /= 10;
                 }
-                write!(f, ".{:0width$}", nanos, width = width)?;
+                write!(f, ".{:0width$}", fsecs, width = width)?;
             }
         }
 
@@ -438,126 +560,157 @@ mod test {
         assert_eq!(mon(25), "2 years 1 month");
         assert_eq!(mon(26), "2 years 2 months");
 
-        fn dur(d: i64) -> String {
-            Interval::new(0, d, 0).unwrap().to_string()
+        fn dur(d: i32, s: i64) -> String {
+            Interval::new(0, d, s * 1_000_000).to_string()
         }
-        assert_eq!(&dur(86_400 * 2), "2 days");
-        assert_eq!(&dur(86_400 * 2 + 3_600 * 3), "2 days 03:00:00");
-        assert_eq!(
-            &dur(86_400 * 2 + 3_600 * 3 + 60 * 45 + 6),
-            "2 days 03:45:06"
-        );
-        assert_eq!(&dur(86_400 * 2 + 3_600 * 3 + 60 * 45), "2 days 03:45:00");
-        assert_eq!(&dur(86_400 * 2 + 6), "2 days 00:00:06");
-        assert_eq!(&dur(86_400 * 2 + 60 * 45 + 6), "2 days 00:45:06");
-        assert_eq!(&dur(86_400 * 2 + 3_600 * 3 + 6), "2 days 03:00:06");
-        assert_eq!(&dur(3_600 * 3 + 60 * 45 + 6), "03:45:06");
-        assert_eq!(&dur(3_600 * 3 + 6), "03:00:06");
-        assert_eq!(&dur(3_600 * 3), "03:00:00");
-        assert_eq!(&dur(60 * 45 + 6), "00:45:06");
-        assert_eq!(&dur(60 * 45), "00:45:00");
-        assert_eq!(&dur(6), "00:00:06");
-
-        assert_eq!(&dur(-(86_400 * 2 + 6)), "-2 days -00:00:06");
-        assert_eq!(&dur(-(86_400 * 2 + 60 * 45 + 6)), "-2 days -00:45:06");
-        assert_eq!(&dur(-(86_400 * 2 + 3_600 * 3 + 6)), "-2 days -03:00:06");
-        assert_eq!(&dur(-(3_600 * 3 + 60 * 45 + 6)), "-03:45:06");
-        assert_eq!(&dur(-(3_600 * 3 + 6)), "-03:00:06");
-        assert_eq!(&dur(-(3_600 * 3)), "-03:00:00");
-        assert_eq!(&dur(-(60 * 45 + 6)), "-00:45:06");
-        assert_eq!(&dur(-(60 * 45)), "-00:45:00");
-        assert_eq!(&dur(-6), "-00:00:06");
-
-        fn mon_dur(mon: i32, d: i64) -> String {
-            Interval::new(mon, d, 0).unwrap().to_string()
+        assert_eq!(&dur(2, 0), "2 days");
+        assert_eq!(&dur(2, 3_600 * 3), "2 days 03:00:00");
+        assert_eq!(&dur(2, 3_600 * 3 + 60 * 45 + 6), "2 days 03:45:06");
+        assert_eq!(&dur(2, 3_600 * 3 + 60 * 45), "2 days 03:45:00");
+        assert_eq!(&dur(2, 6), "2 days 00:00:06");
+        assert_eq!(&dur(2, 60 * 45 + 6), "2 days 00:45:06");
+        assert_eq!(&dur(2, 3_600 * 3 + 6), "2 days 03:00:06");
+        assert_eq!(&dur(0, 3_600 * 3 + 60 * 45 + 6), "03:45:06");
+        assert_eq!(&dur(0, 3_600 * 3 + 6), "03:00:06");
+        assert_eq!(&dur(0, 3_600 * 3), "03:00:00");
+        assert_eq!(&dur(0, 60 * 45 + 6), "00:45:06");
+        assert_eq!(&dur(0, 60 * 45), "00:45:00");
+        assert_eq!(&dur(0, 6), "00:00:06");
+        assert_eq!(&dur(0, 0), "00:00:00");
+
+        // Hours are not carried over into days.
+        assert_eq!(&dur(0, 3_600 * 36), "36:00:00");
+        assert_eq!(&dur(1, 3_600 * 36), "1 day 36:00:00");
+
+        assert_eq!(&dur(-2, -6), "-2 days -00:00:06");
+        assert_eq!(&dur(-2, -(60 * 45 + 6)), "-2 days -00:45:06");
+        assert_eq!(&dur(-2, -(3_600 * 3 + 6)), "-2 days -03:00:06");
+        assert_eq!(&dur(0, -(3_600 * 3 + 60 * 45 + 6)), "-03:45:06");
+        assert_eq!(&dur(0, -(3_600 * 3 + 6)), "-03:00:06");
+        assert_eq!(&dur(0, -(3_600 * 3)), "-03:00:00");
+        assert_eq!(&dur(0, -(60 * 45 + 6)), "-00:45:06");
+        assert_eq!(&dur(0, -(60 * 45)), "-00:45:00");
+        assert_eq!(&dur(0, -6), "-00:00:06");
+
+        // Mixed signs.
+        assert_eq!(&dur(-1, 6), "-1 day +00:00:06");
+        assert_eq!(&dur(1, -6), "1 day -00:00:06");
+
+        fn mon_dur(mon: i32, d: i32, s: i64) -> String {
+            Interval::new(mon, d, s * 1_000_000).to_string()
         }
-        assert_eq!(&mon_dur(1, 86_400 * 2 + 6), "1 month 2 days 00:00:06");
-        assert_eq!(
-            &mon_dur(1, 86_400 * 2 + 60 * 45 + 6),
-            "1 month 2 days 00:45:06"
-        );
-        assert_eq!(
-            &mon_dur(1, 86_400 * 2 + 3_600 * 3 + 6),
-            "1 month 2 days 03:00:06"
-        );
+        assert_eq!(&mon_dur(1, 2, 6), "1 month 2 days 00:00:06");
+        assert_eq!(&mon_dur(1, 2, 60 * 45 + 6), "1 month 2 days 00:45:06");
+        assert_eq!(&mon_dur(1, 2, 3_600 * 3 + 6), "1 month 2 days 03:00:06");
         assert_eq!(
-            &mon_dur(26, 3_600 * 3 + 60 * 45 + 6),
+            &mon_dur(26, 0, 3_600 * 3 + 60 * 45 + 6),
             "2 years 2 months 03:45:06"
         );
-        assert_eq!(&mon_dur(26, 3_600 * 3 + 6), "2 years 2 months 03:00:06");
-        assert_eq!(&mon_dur(26, 3_600 * 3), "2 years 2 months 03:00:00");
-        assert_eq!(&mon_dur(26, 60 * 45 + 6), "2 years 2 months 00:45:06");
-        assert_eq!(&mon_dur(26, 60 * 45), "2 years 2 months 00:45:00");
-        assert_eq!(&mon_dur(26, 6), "2 years 2 months 00:00:06");
+        assert_eq!(&mon_dur(26, 0, 3_600 * 3 + 6), "2 years 2 months 03:00:06");
+        assert_eq!(&mon_dur(26, 0, 3_600 * 3), "2 years 2 months 03:00:00");
+        assert_eq!(&mon_dur(26, 0, 60 * 45 + 6), "2 years 2 months 00:45:06");
+        assert_eq!(&mon_dur(26, 0, 60 * 45), "2 years 2 months 00:45:00");
+        assert_eq!(&mon_dur(26, 0, 6), "2 years 2 months 00:00:06");
 
+        assert_eq!(&mon_dur(26, -2, -6), "2 years 2 months -2 days -00:00:06");
         assert_eq!(
-            &mon_dur(26, -(86_400 * 2 + 6)),
-            "2 years 2 months -2 days -00:00:06"
-        );
-        assert_eq!(
-            &mon_dur(26, -(86_400 * 2 + 60 * 45 + 6)),
+            &mon_dur(26, -2, -(60 * 45 + 6)),
             "2 years 2 months -2 days -00:45:06"
         );
         assert_eq!(
-            &mon_dur(26, -(86_400 * 2 + 3_600 * 3 + 6)),
+            &mon_dur(26, -2, -(3_600 * 3 + 6)),
             "2 years 2 months -2 days -03:00:06"
         );
         assert_eq!(
-            &mon_dur(26, -(3_600 * 3 + 60 * 45 + 6)),
+            &mon_dur(26, 0, -(3_600 * 3 + 60 * 45 + 6)),
             "2 years 2 months -03:45:06"
         );
-        assert_eq!(&mon_dur(26, -(3_600 * 3 + 6)), "2 years 2 months -03:00:06");
-        assert_eq!(&mon_dur(26, -(3_600 * 3)), "2 years 2 months -03:00:00");
-        assert_eq!(&mon_dur(26, -(60 * 45 + 6)), "2 years 2 months -00:45:06");
-        assert_eq!(&mon_dur(26, -(60 * 45)), "2 years 2 months -00:45:00");
-        assert_eq!(&mon_dur(26, -6), "2 years 2 months -00:00:06");
+        assert_eq!(&mon_dur(26, 0, -6), "2 years 2 months -00:00:06");
 
-        assert_eq!(&mon_dur(-1, 86_400 * 2 + 6), "-1 month +2 days +00:00:06");
-        assert_eq!(
-            &mon_dur(-1, 86_400 * 2 + 60 * 45 + 6),
-            "-1 month +2 days +00:45:06"
-        );
+        assert_eq!(&mon_dur(-1, 2, 6), "-1 month +2 days 00:00:06");
+        assert_eq!(&mon_dur(-1, 2, 3_600 * 3 + 6), "-1 month +2 days 03:00:06");
         assert_eq!(
-            &mon_dur(-1, 86_400 * 2 + 3_600 * 3 + 6),
-            "-1 month +2 days +03:00:06"
-        );
-        assert_eq!(
-            &mon_dur(-26, 3_600 * 3 + 60 * 45 + 6),
+            &mon_dur(-26, 0, 3_600 * 3 + 60 * 45 + 6),
             "-2 years -2 months +03:45:06"
         );
-        assert_eq!(&mon_dur(-26, 3_600 * 3 + 6), "-2 years -2 months +03:00:06");
-        assert_eq!(&mon_dur(-26, 3_600 * 3), "-2 years -2 months +03:00:00");
-        assert_eq!(&mon_dur(-26, 60 * 45 + 6), "-2 years -2 months +00:45:06");
-        assert_eq!(&mon_dur(-26, 60 * 45), "-2 years -2 months +00:45:00");
-        assert_eq!(&mon_dur(-26, 6), "-2 years -2 months +00:00:06");
-
-        assert_eq!(
-            &mon_dur(-26, -(86_400 * 2 + 6)),
-            "-2 years -2 months -2 days -00:00:06"
-        );
-        assert_eq!(
-            &mon_dur(-26, -(86_400 * 2 + 60 * 45 + 6)),
-            "-2 years -2 months -2 days -00:45:06"
-        );
+        assert_eq!(&mon_dur(-26, 0, 6), "-2 years -2 months +00:00:06");
         assert_eq!(
-            &mon_dur(-26, -(86_400 * 2 + 3_600 * 3 + 6)),
+            &mon_dur(-26, -2, -(3_600 * 3 + 6)),
             "-2 years -2 months -2 days -03:00:06"
         );
         assert_eq!(
-            &mon_dur(-26, -(3_600 * 3 + 60 * 45 + 6)),
-            "-2 years -2 months -03:45:06"
-        );
-        assert_eq!(
-            &mon_dur(-26, -(3_600 * 3 + 6)),
-            "-2 years -2 months -03:00:06"
+            &mon_dur(-26, 0, -(60 * 45 + 6)),
+            "-2 years -2 months -00:45:06"
         );
-        assert_eq!(&mon_dur(-26, -(3_600 * 3)), "-2 years -2 months -03:00:00");
+        assert_eq!(&mon_dur(-26, -2, 6), "-2 years -2 months -2 days +00:00:06");
+
+        assert_eq!(Interval::new(0, 0, 1_500_000).to_string(), "00:00:01.5");
+        assert_eq!(Interval::new(0, 0, -1).to_string(), "-00:00:00.000001");
+    }
+
+    #[test]
+    fn test_interval_cmp() {
+        let day = Interval::new(0, 1, 0);
+        let hours = Interval::new(0, 0, 24 * 60 * 60 * 1_000_000);
+        assert_eq!(day, hours);
+        assert_eq!(Interval::new(1, 0, 0), Interval::new(0, 30, 0));
+        assert!(Interval::new(0, 1, 1) > hours);
+        assert!(Interval::new(-1, 0, 0) < Interval::new(0, -29, 0));
+        assert!(Interval::new(0, -1, 25 * 60 * 60 * 1_000_000) > Interval::default());
+
+        // Equal intervals hash alike, whatever their units.
+        let hash = |i: &Interval| {
+            let mut hasher = std::collections::hash_map::DefaultHasher::new();
+            i.hash(&mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(&day), hash(&hours));
+
+        assert_eq!(day.cmp_structural(&hours), Ordering::Greater);
+        assert_eq!(day.cmp_structural(&day), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_interval_justify() {
+        const HOUR: i64 = 60 * 60 * 1_000_000;
+
+        let i = Interval::new(0, 0, 25 * HOUR).justify_hours().unwrap();
+        assert_eq!((i.months, i.days, i.micros), (0, 1, HOUR));
+        let i = Interval::new(0, 1, -HOUR).justify_hours().unwrap();
+        assert_eq!((i.months, i.days, i.micros), (0, 0, 23 * HOUR));
+
+        let i = Interval::new(0, 35, HOUR).justify_days().unwrap();
+        assert_eq!((i.months, i.days, i.micros), (1, 5, HOUR));
+        let i = Interval::new(1, -1, 0).justify_days().unwrap();
+        assert_eq!((i.months, i.days, i.micros), (0, 29, 0));
+
+        let i = Interval::new(1, 0, -HOUR).justify_interval().unwrap();
+        assert_eq!((i.months, i.days, i.micros), (0, 29, 23 * HOUR));
+        let i = Interval::new(0, 29, 25 * HOUR).justify_interval().unwrap();
+        assert_eq!((i.months, i.days, i.micros), (1, 0, HOUR));
+
+        assert!(Interval::new(i32::MAX, 30, 0).justify_days().is_none());
+        assert!(Interval::new(0, i32::MAX, 24 * HOUR)
+            .justify_hours()
+            .is_none());
+    }
+
+    #[test]
+    fn test_interval_scale() {
+        // Fractional months are carried into days, and fractional days into
+        // microseconds.
+        let i = Interval::new(1, 0, 0).checked_mul(1.5).unwrap();
+        assert_eq!((i.months, i.days, i.micros), (1, 15, 0));
+        let i = Interval::new(0, 1, 0).checked_div(4.0).unwrap();
         assert_eq!(
-            &mon_dur(-26, -(60 * 45 + 6)),
-            "-2 years -2 months -00:45:06"
+            (i.months, i.days, i.micros),
+            (0, 0, 6 * 60 * 60 * 1_000_000)
         );
-        assert_eq!(&mon_dur(-26, -(60 * 45)), "-2 years -2 months -00:45:00");
-        assert_eq!(&mon_dur(-26, -6), "-2 years -2 months -00:00:06");
+        let i = Interval::new(0, 0, 3).checked_div(2.0).unwrap();
+        assert_eq!((i.months, i.days, i.micros), (0, 0, 2));
+
+        assert!(Interval::new(i32::MAX, 0, 0).checked_mul(2.0).is_none());
+        assert!(Interval::new(1, 0, 0).checked_mul(f64::NAN).is_none());
     }
 
     #[test]
@@ -565,53 +718,49 @@ mod test {
         use DateTimeField::*;
 
         let mut test_cases = [
-            (Year, None, (321, 654_321, 321_000_000), (26 * 12, 0, 0)),
-            (Month, None, (321, 654_321, 321_000_000), (321, 0, 0)),
+            (Year, None, (321, 7, 123_456_789), (26 * 12, 0, 0)),
+            (Month, None, (321, 7, 123_456_789), (321, 0, 0)),
+            (Day, None, (321, 7, 123_456_789), (321, 7, 0)),
+            (Hour, None, (321, 7, 7_323_456_789), (321, 7, 7_200_000_000)),
             (
-                Day,
-                None,
-                (321, 654_321, 321_000_000),
-                (321, 7 * 60 * 60 * 24, 0), // months: 321, duration: 604800s, is_positive_dur: true
-            ),
-            (
-                Hour,
+                Minute,
                 None,
-                (321, 654_321, 321_000_000),
-                (321, 181 * 60 * 60, 0),
+                (321, 7, 7_323_456_789),
+                (321, 7, 7_320_000_000),
             ),
             (
-                Minute,
+                Second,
                 None,
-                (321, 654_321, 321_000_000),
-                (321, 10905 * 60, 0),
+                (321, 7, 7_323_456_789),
+                (321, 7, 7_323_456_789),
             ),
             (
                 Second,
-                None,
-                (321, 654_321, 321_000_000),
-                (321, 654_321, 321_000_000),
+                Some(1),
+                (321, 7, 7_323_456_789),
+                (321, 7, 7_323_500_000),
             ),
             (
                 Second,
                 Some(1),
-                (321, 654_321, 321_000_000),
-                (321, 654_321, 300_000_000),
+                (321, 7, -7_323_456_789),
+                (321, 7, -7_323_500_000),
             ),
             (
                 Second,
                 Some(0),
-                (321, 654_321, 321_000_000),
-                (321, 654_321, 0),
+                (321, 7, 7_323_456_789),
+                (321, 7, 7_323_000_000),
             ),
         ];
 
         for test in test_cases.iter_mut() {
-            let mut i = Interval::new((test.2).0, (test.2).1, (test.2).2).unwrap();
-            let j = Interval::new((test.3).0, (test.3).1, (test.3).2).unwrap();
+            let mut i = Interval::new((test.2).0, (test.2).1, (test.2).2);
+            let j = Interval::new((test.3).0, (test.3).1, (test.3).2);
 
             i.truncate_low_fields(test.0, test.1).unwrap();
 
-            if i != j {
+            if (i.months, i.days, i.micros) != (j.months, j.days, j.micros) {
                 panic!(
                 "test_interval_value_truncate_low_fields failed on {} \n actual: {:?} \n expected: {:?}",
                 test.0, i, j
@@ -625,21 +774,21 @@ mod test {
         use DateTimeField::*;
 
         let mut test_cases = [
-            (Year, (321, 654_321), (321, 654_321)),
-            (Month, (321, 654_321), (9, 654_321)),
-            (Day, (321, 654_321), (0, 654_321)),
-            (Hour, (321, 654_321), (0, 654_321 % (60 * 60 * 24))),
-            (Minute, (321, 654_321), (0, 654_321 % (60 * 60))),
-            (Second, (321, 654_321), (0, 654_321 % 60)),
+            (Year, (321, 7, 654_321), (321, 7, 654_321)),
+            (Month, (321, 7, 654_321), (9, 7, 654_321)),
+            (Day, (321, 7, 654_321), (0, 7, 654_321)),
+            (Hour, (321, 7, 654_321), (0, 0, 654_321 % (60 * 60 * 24))),
+            (Minute, (321, 7, 654_321), (0, 0, 654_321 % (60 * 60))),
+            (Second, (321, 7, 654_321), (0, 0, 654_321 % 60)),
         ];
 
         for test in test_cases.iter_mut() {
-            let mut i = Interval::new((test.1).0, (test.1).1, 123).unwrap();
-            let j = Interval::new((test.2).0, (test.2).1, 123).unwrap();
+            let mut i = Interval::new((test.1).0, (test.1).1, (test.1).2 * 1_000_000 + 123);
+            let j = Interval::new((test.2).0, (test.2).1, (test.2).2 * 1_000_000 + 123);
 
             i.truncate_high_fields(test.0);
 
-            if i != j {
+            if (i.months, i.days, i.micros) != (j.months, j.days, j.micros) {
                 panic!(
                     "test_interval_value_truncate_high_fields failed on {} \n actual: {:?} \n expected: {:?}",
                     test.0, i, j
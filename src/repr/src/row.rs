@@ -25,7 +25,7 @@ use crate::adt::array::{
 };
 use crate::adt::decimal::Significand;
 use crate::adt::interval::Interval;
-use crate::Datum;
+use crate::{Datum, ScalarType};
 use fmt::Debug;
 
 /// A packed representation for `Datum`s.
@@ -322,8 +322,13 @@ unsafe fn read_datum<'a>(data: &'a [u8], offset: &mut usize) -> Datum<'a> {
         }
         Tag::Interval => {
             let months = read_copy::<i32>(data, offset);
-            let duration = read_copy::<i128>(data, offset);
-            Datum::Interval(Interval { months, duration })
+            let days = read_copy::<i32>(data, offset);
+            let micros = read_copy::<i64>(data, offset);
+            Datum::Interval(Interval {
+                months,
+                days,
+                micros,
+            })
         }
         Tag::Decimal => {
             let s = read_copy::<Significand>(data, offset);
@@ -448,7 +453,8 @@ fn push_datum(data: &mut Vec<u8>, datum: Datum) {
         Datum::Interval(i) => {
             data.push(Tag::Interval as u8);
             push_copy!(data, i.months, i32);
-            push_copy!(data, i.duration, i128);
+            push_copy!(data, i.days, i32);
+            push_copy!(data, i.micros, i64);
         }
         Datum::Decimal(s) => {
             data.push(Tag::Decimal as u8);
@@ -515,7 +521,7 @@ pub fn datum_size(datum: &Datum) -> usize {
         Datum::Time(_) => 1 + size_of::<NaiveTime>(),
         Datum::Timestamp(_) => 1 + size_of::<NaiveDateTime>(),
         Datum::TimestampTz(_) => 1 + size_of::<DateTime<Utc>>(),
-        Datum::Interval(_) => 1 + size_of::<i32>() + size_of::<i128>(),
+        Datum::Interval(_) => 1 + size_of::<i32>() + size_of::<i32>() + size_of::<i64>(),
         Datum::Decimal(_) => 1 + size_of::<Significand>(),
         Datum::Bytes(bytes) => {
             // We use a variable length representation of slice length.
@@ -548,6 +554,41 @@ pub fn datum_size(datum: &Datum) -> usize {
     }
 }
 
+/// Number of bytes required by every non-null datum of type `typ`, or `None`
+/// if the size of such datums depends on their value.
+///
+/// Null datums never require more bytes than this, so for nullable columns the
+/// result is an upper bound.
+pub fn fixed_datum_size(typ: &ScalarType) -> Option<usize> {
+    Some(match typ {
+        ScalarType::Bool => 1,
+        ScalarType::Int32 | ScalarType::Oid => 1 + size_of::<i32>(),
+        ScalarType::Int64 => 1 + size_of::<i64>(),
+        ScalarType::Float32 => 1 + size_of::<u32>(),
+        ScalarType::Float64 => 1 + size_of::<u64>(),
+        ScalarType::Date => 1 + size_of::<NaiveDate>(),
+        ScalarType::Time => 1 + size_of::<NaiveTime>(),
+        ScalarType::Timestamp => 1 + size_of::<NaiveDateTime>(),
+        ScalarType::TimestampTz => 1 + size_of::<DateTime<Utc>>(),
+        ScalarType::Interval => 1 + size_of::<i32>() + size_of::<i32>() + size_of::<i64>(),
+        ScalarType::Decimal(..) => 1 + size_of::<Significand>(),
+        ScalarType::Uuid => 1 + size_of::<Uuid>(),
+        ScalarType::Record { fields, .. } => {
+            let mut size = 1 + size_of::<usize>();
+            for (_name, typ) in fields {
+                size += fixed_datum_size(&typ.scalar_type)?;
+            }
+            size
+        }
+        ScalarType::Bytes
+        | ScalarType::String
+        | ScalarType::Jsonb
+        | ScalarType::Array(_)
+        | ScalarType::List { .. }
+        | ScalarType::Map { .. } => return None,
+    })
+}
+
 // --------------------------------------------------------------------------------
 // public api
 
@@ -1277,7 +1318,8 @@ mod tests {
                 months: 312,
                 ..Default::default()
             }),
-            Datum::Interval(Interval::new(0, 0, 1_012_312).unwrap()),
+            Datum::Interval(Interval::new(0, 0, 1_012_312)),
+            Datum::Interval(Interval::new(-1, 3, -1_012_312)),
             Datum::Bytes(&[]),
             Datum::Bytes(&[0, 2, 1, 255]),
             Datum::String(""),
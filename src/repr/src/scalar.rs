@@ -486,14 +486,13 @@ impl From<Significand> for Datum<'static> {
 
 impl From<chrono::Duration> for Datum<'static> {
     fn from(duration: chrono::Duration) -> Datum<'static> {
-        Datum::Interval(
-            Interval::new(
-                0,
-                duration.num_seconds(),
-                duration.num_nanoseconds().unwrap_or(0) % 1_000_000_000,
-            )
-            .unwrap(),
-        )
+        let micros = match duration.num_microseconds() {
+            Some(micros) => micros,
+            // Saturate durations that cannot be represented in microseconds.
+            None if duration < chrono::Duration::zero() => i64::MIN,
+            None => i64::MAX,
+        };
+        Datum::Interval(Interval::new(0, 0, micros))
     }
 }
 
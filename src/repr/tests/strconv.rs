@@ -313,39 +313,29 @@ fn test_parse_interval_monthlike() {
 fn test_parse_interval_durationlike() {
     use DateTimeField::*;
 
-    run_test_parse_interval_durationlike("10", Interval::new(0, 10, 0).unwrap());
+    run_test_parse_interval_durationlike("10", Interval::new(0, 0, 10_000_000));
 
-    run_test_parse_interval_durationlike_from_sql(
-        "10",
-        Day,
-        Interval::new(0, 10 * 24 * 60 * 60, 0).unwrap(),
-    );
+    run_test_parse_interval_durationlike_from_sql("10", Day, Interval::new(0, 10, 0));
 
     run_test_parse_interval_durationlike_from_sql(
         "10",
         Hour,
-        Interval::new(0, 10 * 60 * 60, 0).unwrap(),
+        Interval::new(0, 0, 10 * 60 * 60 * 1_000_000),
     );
 
     run_test_parse_interval_durationlike_from_sql(
         "10",
         Minute,
-        Interval::new(0, 10 * 60, 0).unwrap(),
+        Interval::new(0, 0, 10 * 60 * 1_000_000),
     );
 
-    run_test_parse_interval_durationlike_from_sql("10", Second, Interval::new(0, 10, 0).unwrap());
+    run_test_parse_interval_durationlike_from_sql("10", Second, Interval::new(0, 0, 10_000_000));
 
-    run_test_parse_interval_durationlike("0.01", Interval::new(0, 0, 10_000_000).unwrap());
+    run_test_parse_interval_durationlike("0.01", Interval::new(0, 0, 10_000));
 
-    run_test_parse_interval_durationlike(
-        "1 2:3:4.5",
-        Interval::new(0, 93_784, 500_000_000).unwrap(),
-    );
+    run_test_parse_interval_durationlike("1 2:3:4.5", Interval::new(0, 1, 7_384_500_000));
 
-    run_test_parse_interval_durationlike(
-        "-1 2:3:4.5",
-        Interval::new(0, -79_015, -500_000_000).unwrap(),
-    );
+    run_test_parse_interval_durationlike("-1 2:3:4.5", Interval::new(0, -1, 7_384_500_000));
 
     fn run_test_parse_interval_durationlike(s: &str, expected: Interval) {
         let actual = strconv::parse_interval(s).unwrap();
@@ -365,37 +355,19 @@ fn test_parse_interval_durationlike() {
 fn test_parse_interval_full() {
     use DateTimeField::*;
 
-    run_test_parse_interval_full(
-        "6-7 1 2:3:4.5",
-        Interval::new(79, 93_784, 500_000_000).unwrap(),
-    );
+    run_test_parse_interval_full("6-7 1 2:3:4.5", Interval::new(79, 1, 7_384_500_000));
 
-    run_test_parse_interval_full(
-        "-6-7 1 2:3:4.5",
-        Interval::new(-79, 93_784, 500_000_000).unwrap(),
-    );
+    run_test_parse_interval_full("-6-7 1 2:3:4.5", Interval::new(-79, 1, 7_384_500_000));
 
-    run_test_parse_interval_full(
-        "6-7 -1 -2:3:4.5",
-        Interval::new(79, -93_784, -500_000_000).unwrap(),
-    );
+    run_test_parse_interval_full("6-7 -1 -2:3:4.5", Interval::new(79, -1, -7_384_500_000));
 
-    run_test_parse_interval_full(
-        "-6-7 -1 -2:3:4.5",
-        Interval::new(-79, -93_784, -500_000_000).unwrap(),
-    );
+    run_test_parse_interval_full("-6-7 -1 -2:3:4.5", Interval::new(-79, -1, -7_384_500_000));
 
-    run_test_parse_interval_full(
-        "-6-7 1 -2:3:4.5",
-        Interval::new(-79, 79_015, 500_000_000).unwrap(),
-    );
+    run_test_parse_interval_full("-6-7 1 -2:3:4.5", Interval::new(-79, 1, -7_384_500_000));
 
-    run_test_parse_interval_full(
-        "-6-7 -1 2:3:4.5",
-        Interval::new(-79, -79_015, -500_000_000).unwrap(),
-    );
+    run_test_parse_interval_full("-6-7 -1 2:3:4.5", Interval::new(-79, -1, 7_384_500_000));
 
-    run_test_parse_interval_full_from_sql("-6-7 1", Minute, Interval::new(-79, 60, 0).unwrap());
+    run_test_parse_interval_full_from_sql("-6-7 1", Minute, Interval::new(-79, 0, 60_000_000));
 
     fn run_test_parse_interval_full(s: &str, expected: Interval) {
         let actual = strconv::parse_interval(s).unwrap();
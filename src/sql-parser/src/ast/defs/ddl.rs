@@ -125,7 +125,17 @@ pub enum Format {
         delimiter: char,
     },
     Json,
+    /// JSON whose structure is described by a JSON Schema stored in a
+    /// Confluent Schema Registry.
+    JsonSchema {
+        url: String,
+        /// The JSON Schema of the values, if already known.
+        seed: Option<String>,
+        with_options: Vec<SqlOption>,
+    },
     Text,
+    /// Apache Parquet files. Only valid for sinks.
+    Parquet,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -208,7 +218,27 @@ impl AstDisplay for Format {
                 }
             }
             Self::Json => f.write_str("JSON"),
+            Self::JsonSchema {
+                url,
+                seed,
+                with_options,
+            } => {
+                f.write_str("JSON USING SCHEMA REGISTRY '");
+                f.write_node(&display::escape_single_quote_string(url));
+                f.write_str("'");
+                if let Some(seed) = seed {
+                    f.write_str(" SEED SCHEMA '");
+                    f.write_node(&display::escape_single_quote_string(seed));
+                    f.write_str("'");
+                }
+                if !with_options.is_empty() {
+                    f.write_str(" WITH (");
+                    f.write_node(&display::comma_separated(with_options));
+                    f.write_str(")");
+                }
+            }
             Self::Text => f.write_str("TEXT"),
+            Self::Parquet => f.write_str("PARQUET"),
         }
     }
 }
@@ -245,6 +275,9 @@ pub enum Connector {
     Kafka {
         broker: String,
         topic: String,
+        /// Whether `topic` is a regular expression that selects the topics to
+        /// read: `TOPIC PATTERN 'events\..*'`
+        topic_pattern: bool,
         key: Option<Vec<Ident>>,
     },
     Kinesis {
@@ -260,6 +293,16 @@ pub enum Connector {
         /// The argument to the MATCHING clause: `MATCHING 'a/**/*.json'`
         pattern: Option<String>,
     },
+    /// A bucket to write objects into, as a sink: `S3 BUCKET 'b' PREFIX 'p/'`
+    S3Bucket {
+        bucket: String,
+        /// The argument to the PREFIX clause, prepended to each object key
+        prefix: Option<String>,
+    },
+    /// A notification channel, as a sink: `NOTIFY channel`
+    Notify {
+        channel: Ident,
+    },
 }
 
 impl AstDisplay for Connector {
@@ -274,11 +317,20 @@ impl AstDisplay for Connector {
                     f.write_node(compression);
                 }
             }
-            Connector::Kafka { broker, topic, key } => {
+            Connector::Kafka {
+                broker,
+                topic,
+                topic_pattern,
+                key,
+            } => {
                 f.write_str("KAFKA BROKER '");
                 f.write_node(&display::escape_single_quote_string(broker));
                 f.write_str("'");
-                f.write_str(" TOPIC '");
+                f.write_str(" TOPIC ");
+                if *topic_pattern {
+                    f.write_str("PATTERN ");
+                }
+                f.write_str("'");
                 f.write_node(&display::escape_single_quote_string(topic));
                 f.write_str("'");
                 if let Some(key) = key.as_ref() {
@@ -309,6 +361,20 @@ impl AstDisplay for Connector {
                     f.write_str("'");
                 }
             }
+            Connector::S3Bucket { bucket, prefix } => {
+                f.write_str("S3 BUCKET '");
+                f.write_node(&display::escape_single_quote_string(bucket));
+                f.write_str("'");
+                if let Some(prefix) = prefix {
+                    f.write_str(" PREFIX '");
+                    f.write_node(&display::escape_single_quote_string(prefix));
+                    f.write_str("'");
+                }
+            }
+            Connector::Notify { channel } => {
+                f.write_str("NOTIFY ");
+                f.write_node(channel);
+            }
         }
     }
 }
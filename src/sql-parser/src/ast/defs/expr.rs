@@ -80,7 +80,7 @@ pub enum Expr<T: AstInfo> {
     },
     /// Unary or binary operator
     Op {
-        op: String,
+        op: Op,
         expr1: Box<Expr<T>>,
         expr2: Option<Box<Expr<T>>>,
     },
@@ -160,6 +160,8 @@ pub enum Expr<T: AstInfo> {
     Array(Vec<Expr<T>>),
     /// `LIST[<expr>*]`
     List(Vec<Expr<T>>),
+    /// `MAP[<expr> => <expr>*]`
+    Map(Vec<MapEntry<T>>),
     /// `<expr>[<expr>]`
     SubscriptIndex {
         expr: Box<Expr<T>>,
@@ -259,11 +261,11 @@ impl<T: AstInfo> AstDisplay for Expr<T> {
                 if let Some(expr2) = expr2 {
                     f.write_node(&expr1);
                     f.write_str(" ");
-                    f.write_str(op);
+                    f.write_node(op);
                     f.write_str(" ");
                     f.write_node(&expr2);
                 } else {
-                    f.write_str(op);
+                    f.write_node(op);
                     f.write_str(" ");
                     f.write_node(&expr1);
                 }
@@ -417,6 +419,11 @@ impl<T: AstInfo> AstDisplay for Expr<T> {
                 }
                 f.write_str("]");
             }
+            Expr::Map(entries) => {
+                f.write_str("MAP[");
+                f.write_node(&display::comma_separated(entries));
+                f.write_str("]");
+            }
             Expr::SubscriptIndex { expr, subscript } => {
                 f.write_node(&expr);
                 f.write_str("[");
@@ -472,7 +479,7 @@ impl<T: AstInfo> Expr<T> {
 
     pub fn binop(self, op: &str, right: Expr<T>) -> Expr<T> {
         Expr::Op {
-            op: op.to_string(),
+            op: Op::bare(op),
             expr1: Box::new(self),
             expr2: Some(Box::new(right)),
         }
@@ -517,10 +524,11 @@ impl<T: AstInfo> Expr<T> {
     pub fn call(name: Vec<&str>, args: Vec<Expr<T>>) -> Expr<T> {
         Expr::Function(Function {
             name: UnresolvedObjectName(name.into_iter().map(Into::into).collect()),
-            args: FunctionArgs::Args(args),
+            args: FunctionArgs::args(args),
             filter: None,
             over: None,
             distinct: false,
+            within_group: false,
         })
     }
 
@@ -537,6 +545,46 @@ impl<T: AstInfo> Expr<T> {
     }
 }
 
+/// An operator, optionally qualified by a schema, as in `+` or
+/// `OPERATOR(pg_catalog.+)`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Op {
+    /// The schema that qualifies the operator, if any.
+    pub namespace: Vec<Ident>,
+    /// The operator itself.
+    pub op: String,
+}
+
+impl Op {
+    /// Constructs a new unqualified operator.
+    pub fn bare<S>(op: S) -> Op
+    where
+        S: Into<String>,
+    {
+        Op {
+            namespace: vec![],
+            op: op.into(),
+        }
+    }
+}
+
+impl AstDisplay for Op {
+    fn fmt(&self, f: &mut AstFormatter) {
+        if self.namespace.is_empty() {
+            f.write_str(&self.op);
+        } else {
+            f.write_str("OPERATOR(");
+            for name in &self.namespace {
+                f.write_node(name);
+                f.write_str(".");
+            }
+            f.write_str(&self.op);
+            f.write_str(")");
+        }
+    }
+}
+impl_display!(Op);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct SubscriptPosition<T: AstInfo> {
     pub start: Option<Expr<T>>,
@@ -556,6 +604,22 @@ impl<T: AstInfo> AstDisplay for SubscriptPosition<T> {
 }
 impl_display_t!(SubscriptPosition);
 
+/// A `<key> => <value>` entry of a `MAP[...]` expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MapEntry<T: AstInfo> {
+    pub key: Expr<T>,
+    pub value: Expr<T>,
+}
+
+impl<T: AstInfo> AstDisplay for MapEntry<T> {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_node(&self.key);
+        f.write_str(" => ");
+        f.write_node(&self.value);
+    }
+}
+impl_display_t!(MapEntry);
+
 /// A window specification (i.e. `OVER (PARTITION BY .. ORDER BY .. etc.)`)
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct WindowSpec<T: AstInfo> {
@@ -671,6 +735,9 @@ pub struct Function<T: AstInfo> {
     pub over: Option<WindowSpec<T>>,
     // aggregate functions may specify eg `COUNT(DISTINCT x)`
     pub distinct: bool,
+    // ordered-set aggregate functions specify their `ORDER BY` clause in a
+    // `WITHIN GROUP` clause, as in `percentile_cont(0.5) WITHIN GROUP (ORDER BY x)`
+    pub within_group: bool,
 }
 
 impl<T: AstInfo> AstDisplay for Function<T> {
@@ -680,7 +747,14 @@ impl<T: AstInfo> AstDisplay for Function<T> {
         if self.distinct {
             f.write_str("DISTINCT ")
         }
-        f.write_node(&self.args);
+        match &self.args {
+            FunctionArgs::Args { args, order_by } if self.within_group => {
+                f.write_node(&display::comma_separated(&args));
+                f.write_str(") WITHIN GROUP (ORDER BY ");
+                f.write_node(&display::comma_separated(&order_by));
+            }
+            args => f.write_node(args),
+        }
         f.write_str(")");
         if let Some(filter) = &self.filter {
             f.write_str(" FILTER (WHERE ");
@@ -701,15 +775,35 @@ impl_display_t!(Function);
 pub enum FunctionArgs<T: AstInfo> {
     /// The special star argument, as in `count(*)`.
     Star,
-    /// A normal list of arguments.
-    Args(Vec<Expr<T>>),
+    /// A normal list of arguments, optionally followed by the `ORDER BY`
+    /// clause of an ordered aggregate, as in `array_agg(x ORDER BY y)`.
+    Args {
+        args: Vec<Expr<T>>,
+        order_by: Vec<OrderByExpr<T>>,
+    },
+}
+
+impl<T: AstInfo> FunctionArgs<T> {
+    /// Constructs a normal list of arguments, without an `ORDER BY` clause.
+    pub fn args(args: Vec<Expr<T>>) -> FunctionArgs<T> {
+        FunctionArgs::Args {
+            args,
+            order_by: vec![],
+        }
+    }
 }
 
 impl<T: AstInfo> AstDisplay for FunctionArgs<T> {
     fn fmt(&self, f: &mut AstFormatter) {
         match self {
             FunctionArgs::Star => f.write_str("*"),
-            FunctionArgs::Args(args) => f.write_node(&display::comma_separated(&args)),
+            FunctionArgs::Args { args, order_by } => {
+                f.write_node(&display::comma_separated(&args));
+                if !order_by.is_empty() {
+                    f.write_str(" ORDER BY ");
+                    f.write_node(&display::comma_separated(&order_by));
+                }
+            }
         }
     }
 }
@@ -237,7 +237,7 @@ pub struct Select<T: AstInfo> {
     /// WHERE
     pub selection: Option<Expr<T>>,
     /// GROUP BY
-    pub group_by: Vec<Expr<T>>,
+    pub group_by: Vec<GroupByExpr<T>>,
     /// HAVING
     pub having: Option<Expr<T>>,
     /// OPTION
@@ -317,6 +317,67 @@ impl<T: AstInfo> AstDisplay for Distinct<T> {
     }
 }
 
+/// An item in a `GROUP BY` clause.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GroupByExpr<T: AstInfo> {
+    /// An expression to group by.
+    Expr(Expr<T>),
+    /// A parenthesized grouping set, like `(a, b)` or `()`.
+    Set(Vec<Expr<T>>),
+    /// `ROLLUP (<element>, ...)`, where each element is a list of expressions
+    /// that are grouped by together.
+    Rollup(Vec<Vec<Expr<T>>>),
+    /// `CUBE (<element>, ...)`, where each element is a list of expressions
+    /// that are grouped by together.
+    Cube(Vec<Vec<Expr<T>>>),
+    /// `GROUPING SETS (<item>, ...)`
+    GroupingSets(Vec<GroupByExpr<T>>),
+}
+
+impl<T: AstInfo> AstDisplay for GroupByExpr<T> {
+    fn fmt(&self, f: &mut AstFormatter) {
+        fn write_elements<T: AstInfo>(f: &mut AstFormatter, elements: &[Vec<Expr<T>>]) {
+            f.write_str("(");
+            for (i, element) in elements.iter().enumerate() {
+                if i > 0 {
+                    f.write_str(", ");
+                }
+                if element.len() == 1 {
+                    f.write_node(&element[0]);
+                } else {
+                    f.write_str("(");
+                    f.write_node(&display::comma_separated(element));
+                    f.write_str(")");
+                }
+            }
+            f.write_str(")");
+        }
+
+        match self {
+            GroupByExpr::Expr(expr) => f.write_node(expr),
+            GroupByExpr::Set(exprs) => {
+                f.write_str("(");
+                f.write_node(&display::comma_separated(exprs));
+                f.write_str(")");
+            }
+            GroupByExpr::Rollup(elements) => {
+                f.write_str("ROLLUP ");
+                write_elements(f, elements);
+            }
+            GroupByExpr::Cube(elements) => {
+                f.write_str("CUBE ");
+                write_elements(f, elements);
+            }
+            GroupByExpr::GroupingSets(items) => {
+                f.write_str("GROUPING SETS (");
+                f.write_node(&display::comma_separated(items));
+                f.write_str(")");
+            }
+        }
+    }
+}
+impl_display_t!(GroupByExpr);
+
 /// A single CTE (used after `WITH`): `alias [(col1, col2, ...)] AS ( query )`
 /// The names in the column list before `AS`, when specified, replace the names
 /// of the columns returned by the query. The parser does not validate that the
@@ -403,6 +464,9 @@ pub enum TableFactor<T: AstInfo> {
         name: UnresolvedObjectName,
         args: FunctionArgs<T>,
         alias: Option<TableAlias>,
+        /// Whether the function's output is numbered by a trailing
+        /// ordinality column.
+        with_ordinality: bool,
     },
     Derived {
         lateral: bool,
@@ -429,11 +493,19 @@ impl<T: AstInfo> AstDisplay for TableFactor<T> {
                     f.write_node(alias);
                 }
             }
-            TableFactor::Function { name, args, alias } => {
+            TableFactor::Function {
+                name,
+                args,
+                alias,
+                with_ordinality,
+            } => {
                 f.write_node(name);
                 f.write_str("(");
                 f.write_node(args);
                 f.write_str(")");
+                if *with_ordinality {
+                    f.write_str(" WITH ORDINALITY");
+                }
                 if let Some(alias) = alias {
                     f.write_str(" AS ");
                     f.write_node(alias);
@@ -586,6 +658,9 @@ pub enum JoinConstraint<T: AstInfo> {
 pub struct OrderByExpr<T: AstInfo> {
     pub expr: Expr<T>,
     pub asc: Option<bool>,
+    /// Whether NULLs sort after all other values, as requested by
+    /// `NULLS LAST`, or before them, as requested by `NULLS FIRST`.
+    pub nulls_last: Option<bool>,
 }
 
 impl<T: AstInfo> AstDisplay for OrderByExpr<T> {
@@ -596,6 +671,11 @@ impl<T: AstInfo> AstDisplay for OrderByExpr<T> {
             Some(false) => f.write_str(" DESC"),
             None => {}
         }
+        match self.nulls_last {
+            Some(true) => f.write_str(" NULLS LAST"),
+            Some(false) => f.write_str(" NULLS FIRST"),
+            None => {}
+        }
     }
 }
 impl_display_t!(OrderByExpr);
@@ -20,8 +20,8 @@
 
 use crate::ast::display::{self, AstDisplay, AstFormatter};
 use crate::ast::{
-    AstInfo, ColumnDef, Connector, DataType, Envelope, Expr, Format, Ident, Query, TableConstraint,
-    UnresolvedObjectName, Value,
+    AstInfo, ColumnDef, Connector, DataType, Envelope, Expr, Format, Ident, OrderByExpr, Query,
+    TableConstraint, UnresolvedObjectName, Value,
 };
 
 /// A top-level statement (SELECT, INSERT, CREATE, etc.)
@@ -44,6 +44,7 @@ pub enum Statement<T: AstInfo> {
     CreateRole(CreateRoleStatement),
     AlterObjectRename(AlterObjectRenameStatement),
     AlterIndexOptions(AlterIndexOptionsStatement),
+    AlterSourceOptions(AlterSourceOptionsStatement),
     Discard(DiscardStatement),
     DropDatabase(DropDatabaseStatement),
     DropObjects(DropObjectsStatement),
@@ -67,6 +68,10 @@ pub enum Statement<T: AstInfo> {
     Declare(DeclareStatement<T>),
     Fetch(FetchStatement),
     Close(CloseStatement),
+    CancelQuery(CancelQueryStatement),
+    Listen(ListenStatement),
+    Unlisten(UnlistenStatement),
+    Notify(NotifyStatement),
 }
 
 impl<T: AstInfo> Statement<T> {
@@ -95,6 +100,7 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::CreateType(stmt) => f.write_node(stmt),
             Statement::AlterObjectRename(stmt) => f.write_node(stmt),
             Statement::AlterIndexOptions(stmt) => f.write_node(stmt),
+            Statement::AlterSourceOptions(stmt) => f.write_node(stmt),
             Statement::Discard(stmt) => f.write_node(stmt),
             Statement::DropDatabase(stmt) => f.write_node(stmt),
             Statement::DropObjects(stmt) => f.write_node(stmt),
@@ -118,6 +124,10 @@ impl<T: AstInfo> AstDisplay for Statement<T> {
             Statement::Declare(stmt) => f.write_node(stmt),
             Statement::Close(stmt) => f.write_node(stmt),
             Statement::Fetch(stmt) => f.write_node(stmt),
+            Statement::CancelQuery(stmt) => f.write_node(stmt),
+            Statement::Listen(stmt) => f.write_node(stmt),
+            Statement::Unlisten(stmt) => f.write_node(stmt),
+            Statement::Notify(stmt) => f.write_node(stmt),
         }
     }
 }
@@ -150,6 +160,8 @@ pub struct InsertStatement<T: AstInfo> {
     pub columns: Vec<Ident>,
     /// A SQL query that specifies what to insert.
     pub source: InsertSource<T>,
+    /// The `ON CONFLICT` clause, if any.
+    pub on_conflict: Option<OnConflict>,
 }
 
 impl<T: AstInfo> AstDisplay for InsertStatement<T> {
@@ -163,10 +175,34 @@ impl<T: AstInfo> AstDisplay for InsertStatement<T> {
         }
         f.write_str(" ");
         f.write_node(&self.source);
+        if let Some(on_conflict) = &self.on_conflict {
+            f.write_str(" ");
+            f.write_node(on_conflict);
+        }
     }
 }
 impl_display_t!(InsertStatement);
 
+/// `ON CONFLICT [ ( <columns> ) ] DO NOTHING`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct OnConflict {
+    /// The columns of the key to check for conflicts, if specified.
+    pub columns: Vec<Ident>,
+}
+
+impl AstDisplay for OnConflict {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_str("ON CONFLICT ");
+        if !self.columns.is_empty() {
+            f.write_str("(");
+            f.write_node(&display::comma_separated(&self.columns));
+            f.write_str(") ");
+        }
+        f.write_str("DO NOTHING");
+    }
+}
+impl_display!(OnConflict);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum CopyRelation<T: AstInfo> {
     Table {
@@ -742,6 +778,29 @@ impl AstDisplay for AlterIndexOptionsStatement {
 
 impl_display!(AlterIndexOptionsStatement);
 
+/// `ALTER SOURCE ... SET`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AlterSourceOptionsStatement {
+    pub source_name: UnresolvedObjectName,
+    pub if_exists: bool,
+    pub options: Vec<SqlOption>,
+}
+
+impl AstDisplay for AlterSourceOptionsStatement {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_str("ALTER SOURCE ");
+        if self.if_exists {
+            f.write_str("IF EXISTS ");
+        }
+        f.write_node(&self.source_name);
+        f.write_str(" SET (");
+        f.write_node(&display::comma_separated(&self.options));
+        f.write_str(")");
+    }
+}
+
+impl_display!(AlterSourceOptionsStatement);
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct DiscardStatement {
     pub target: DiscardTarget,
@@ -1128,6 +1187,8 @@ pub struct TailStatement<T: AstInfo> {
     pub name: UnresolvedObjectName,
     pub options: Vec<WithOption>,
     pub as_of: Option<Expr<T>>,
+    /// The order in which to emit the updates within each timestamp.
+    pub order_by: Vec<OrderByExpr<T>>,
 }
 
 impl<T: AstInfo> AstDisplay for TailStatement<T> {
@@ -1143,6 +1204,10 @@ impl<T: AstInfo> AstDisplay for TailStatement<T> {
             f.write_str(" AS OF ");
             f.write_node(as_of);
         }
+        if !self.order_by.is_empty() {
+            f.write_str(" ORDER BY ");
+            f.write_node(&display::comma_separated(&self.order_by));
+        }
     }
 }
 impl_display_t!(TailStatement);
@@ -1158,6 +1223,10 @@ pub struct ExplainStatement<T: AstInfo> {
 impl<T: AstInfo> AstDisplay for ExplainStatement<T> {
     fn fmt(&self, f: &mut AstFormatter) {
         f.write_str("EXPLAIN ");
+        if let Explainee::Drop(_) = &self.explainee {
+            f.write_node(&self.explainee);
+            return;
+        }
         if self.options.typed {
             f.write_str("TYPED ");
         }
@@ -1252,6 +1321,10 @@ pub enum SqlOption {
         name: Ident,
         data_type: DataType,
     },
+    Columns {
+        name: Ident,
+        columns: Vec<Ident>,
+    },
 }
 
 impl SqlOption {
@@ -1260,6 +1333,7 @@ impl SqlOption {
             SqlOption::Value { name, .. } => name,
             SqlOption::ObjectName { name, .. } => name,
             SqlOption::DataType { name, .. } => name,
+            SqlOption::Columns { name, .. } => name,
         }
     }
 }
@@ -1282,6 +1356,12 @@ impl AstDisplay for SqlOption {
                 f.write_str(" = ");
                 f.write_node(data_type);
             }
+            SqlOption::Columns { name, columns } => {
+                f.write_node(name);
+                f.write_str(" = (");
+                f.write_node(&display::comma_separated(columns));
+                f.write_str(")");
+            }
         }
     }
 }
@@ -1420,6 +1500,9 @@ pub enum ExplainStage {
     DecorrelatedPlan,
     /// The expr::MirRelationExpr after optimization
     OptimizedPlan,
+    /// The expr::MirRelationExpr after optimization, annotated with estimates
+    /// of the memory its arrangements will use
+    Memory,
 }
 
 impl AstDisplay for ExplainStage {
@@ -1428,6 +1511,7 @@ impl AstDisplay for ExplainStage {
             ExplainStage::RawPlan => f.write_str("RAW PLAN"),
             ExplainStage::DecorrelatedPlan => f.write_str("DECORRELATED PLAN"),
             ExplainStage::OptimizedPlan => f.write_str("OPTIMIZED PLAN"),
+            ExplainStage::Memory => f.write_str("MEMORY"),
         }
     }
 }
@@ -1437,6 +1521,8 @@ impl_display!(ExplainStage);
 pub enum Explainee<T: AstInfo> {
     View(UnresolvedObjectName),
     Query(Query<T>),
+    /// The objects a `DROP` would remove, which ignores the stage and options.
+    Drop(DropObjectsStatement),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -1452,6 +1538,7 @@ impl<T: AstInfo> AstDisplay for Explainee<T> {
                 f.write_node(&name);
             }
             Explainee::Query(query) => f.write_node(query),
+            Explainee::Drop(stmt) => f.write_node(stmt),
         }
     }
 }
@@ -1495,6 +1582,72 @@ impl AstDisplay for CloseStatement {
 }
 impl_display!(CloseStatement);
 
+/// `CANCEL QUERY ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CancelQueryStatement {
+    pub query_id: u64,
+}
+
+impl AstDisplay for CancelQueryStatement {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_str("CANCEL QUERY ");
+        f.write_str(self.query_id);
+    }
+}
+impl_display!(CancelQueryStatement);
+
+/// `LISTEN ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ListenStatement {
+    pub channel: Ident,
+}
+
+impl AstDisplay for ListenStatement {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_str("LISTEN ");
+        f.write_node(&self.channel);
+    }
+}
+impl_display!(ListenStatement);
+
+/// `UNLISTEN ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct UnlistenStatement {
+    /// The channel to stop listening on, or `None` for `UNLISTEN *`.
+    pub channel: Option<Ident>,
+}
+
+impl AstDisplay for UnlistenStatement {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_str("UNLISTEN ");
+        match &self.channel {
+            Some(channel) => f.write_node(channel),
+            None => f.write_str("*"),
+        }
+    }
+}
+impl_display!(UnlistenStatement);
+
+/// `NOTIFY ...`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct NotifyStatement {
+    pub channel: Ident,
+    pub payload: Option<String>,
+}
+
+impl AstDisplay for NotifyStatement {
+    fn fmt(&self, f: &mut AstFormatter) {
+        f.write_str("NOTIFY ");
+        f.write_node(&self.channel);
+        if let Some(payload) = &self.payload {
+            f.write_str(", '");
+            f.write_node(&display::escape_single_quote_string(payload));
+            f.write_str("'");
+        }
+    }
+}
+impl_display!(NotifyStatement);
+
 /// `FETCH ...`
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct FetchStatement {
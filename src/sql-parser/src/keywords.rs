@@ -94,7 +94,9 @@ impl Keyword {
             // `OUTER` is not strictly ambiguous, but it prevents `a OUTER JOIN
             // b` from parsing as `a AS outer JOIN b`, instead producing a nice
             // syntax error.
-            OUTER
+            OUTER |
+            // `FOR` introduces `FOR SYSTEM_TIME AS OF`.
+            FOR
         ) || self.is_reserved()
     }
 
@@ -114,7 +116,9 @@ impl Keyword {
             // suffixes. They are not strictly ambiguous, but marking them
             // reserved prevents e.g. `SELECT pg_catalog.interval '1' year` from
             // parsing as `SELECT pg_catalog.interval '1' AS YEAR`.
-            YEAR | MONTH | DAY | HOUR | MINUTE | SECOND
+            YEAR | MONTH | DAY | HOUR | MINUTE | SECOND |
+            // `FOR` introduces `FOR SYSTEM_TIME AS OF`.
+            FOR
         ) || self.is_reserved()
     }
 
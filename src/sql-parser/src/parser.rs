@@ -223,6 +223,10 @@ impl<'a> Parser<'a> {
                 Token::Keyword(DECLARE) => Ok(self.parse_declare()?),
                 Token::Keyword(FETCH) => Ok(self.parse_fetch()?),
                 Token::Keyword(CLOSE) => Ok(self.parse_close()?),
+                Token::Keyword(CANCEL) => Ok(self.parse_cancel_query()?),
+                Token::Keyword(LISTEN) => Ok(self.parse_listen()?),
+                Token::Keyword(UNLISTEN) => Ok(self.parse_unlisten()?),
+                Token::Keyword(NOTIFY) => Ok(self.parse_notify()?),
                 Token::Keyword(kw) => parser_err!(
                     self,
                     self.peek_prev_pos(),
@@ -318,6 +322,9 @@ impl<'a> Parser<'a> {
             }
             Token::Keyword(ARRAY) => self.parse_array(),
             Token::Keyword(LIST) => self.parse_list(),
+            Token::Keyword(MAP) if self.peek_token() == Some(Token::LBracket) => {
+                self.parse_map_expr()
+            }
             Token::Keyword(CASE) => self.parse_case_expr(),
             Token::Keyword(CAST) => self.parse_cast_expr(),
             Token::Keyword(COALESCE) => self.parse_coalesce_expr(),
@@ -328,6 +335,11 @@ impl<'a> Parser<'a> {
             Token::Keyword(NOT) => Ok(Expr::Not {
                 expr: Box::new(self.parse_subexpr(Precedence::PrefixNot)?),
             }),
+            Token::Keyword(OPERATOR) if self.peek_token() == Some(Token::LParen) => Ok(Expr::Op {
+                op: self.parse_operator()?,
+                expr1: Box::new(self.parse_subexpr(Precedence::PrefixPlusMinus)?),
+                expr2: None,
+            }),
             Token::Keyword(ROW) => self.parse_row_expr(),
             Token::Keyword(TRIM) => self.parse_trim_expr(),
             Token::Keyword(kw) if kw.is_reserved() => {
@@ -339,7 +351,7 @@ impl<'a> Parser<'a> {
             Token::Keyword(id) => self.parse_qualified_identifier(id.into_ident()),
             Token::Ident(id) => self.parse_qualified_identifier(Ident::new(id)),
             Token::Op(op) if op == "+" || op == "-" => Ok(Expr::Op {
-                op,
+                op: Op::bare(op),
                 expr1: Box::new(self.parse_subexpr(Precedence::PrefixPlusMinus)?),
                 expr2: None,
             }),
@@ -491,7 +503,35 @@ impl<'a> Parser<'a> {
                 format!("Cannot specify both ALL and DISTINCT in function: {}", name)
             );
         }
-        let args = self.parse_optional_args()?;
+        let mut args = self.parse_optional_args()?;
+        let within_group_pos = self.peek_pos();
+        let within_group = self.parse_keywords(&[WITHIN, GROUP]);
+        if within_group {
+            let order_by = match &mut args {
+                _ if distinct => {
+                    return parser_err!(
+                        self,
+                        within_group_pos,
+                        "cannot use DISTINCT with WITHIN GROUP"
+                    );
+                }
+                FunctionArgs::Star => {
+                    return parser_err!(self, within_group_pos, "cannot use * with WITHIN GROUP");
+                }
+                FunctionArgs::Args { order_by, .. } if !order_by.is_empty() => {
+                    return parser_err!(
+                        self,
+                        within_group_pos,
+                        "cannot use multiple ORDER BY clauses with WITHIN GROUP"
+                    );
+                }
+                FunctionArgs::Args { order_by, .. } => order_by,
+            };
+            self.expect_token(&Token::LParen)?;
+            self.expect_keywords(&[ORDER, BY])?;
+            *order_by = self.parse_comma_separated(Parser::parse_order_by_expr)?;
+            self.expect_token(&Token::RParen)?;
+        }
         let filter = if self.parse_keyword(FILTER) {
             self.expect_token(&Token::LParen)?;
             self.expect_keyword(WHERE)?;
@@ -538,6 +578,7 @@ impl<'a> Parser<'a> {
             filter,
             over,
             distinct,
+            within_group,
         }))
     }
 
@@ -663,10 +704,11 @@ impl<'a> Parser<'a> {
         self.expect_token(&Token::RParen)?;
         Ok(Expr::Function(Function {
             name: UnresolvedObjectName::unqualified("date_part"),
-            args: FunctionArgs::Args(vec![Expr::Value(Value::String(field)), expr]),
+            args: FunctionArgs::args(vec![Expr::Value(Value::String(field)), expr]),
             filter: None,
             over: None,
             distinct: false,
+            within_group: false,
         }))
     }
 
@@ -712,10 +754,11 @@ impl<'a> Parser<'a> {
         self.expect_token(&Token::RParen)?;
         Ok(Expr::Function(Function {
             name: UnresolvedObjectName::unqualified(name),
-            args: FunctionArgs::Args(exprs),
+            args: FunctionArgs::args(exprs),
             filter: None,
             over: None,
             distinct: false,
+            within_group: false,
         }))
     }
 
@@ -807,6 +850,26 @@ impl<'a> Parser<'a> {
         })))
     }
 
+    /// Parse the parenthesized operator name that follows the `OPERATOR`
+    /// keyword, as in `OPERATOR(pg_catalog.+)`.
+    fn parse_operator(&mut self) -> Result<Op, ParserError> {
+        self.expect_token(&Token::LParen)?;
+        let mut namespace = vec![];
+        let op = loop {
+            match self.next_token() {
+                Some(Token::Keyword(kw)) => namespace.push(kw.into_ident()),
+                Some(Token::Ident(id)) => namespace.push(Ident::new(id)),
+                Some(Token::Op(op)) => break op,
+                Some(Token::Eq) => break "=".into(),
+                Some(Token::Star) => break "*".into(),
+                tok => return self.expected(self.peek_prev_pos(), "operator", tok),
+            }
+            self.expect_token(&Token::Dot)?;
+        };
+        self.expect_token(&Token::RParen)?;
+        Ok(Op { namespace, op })
+    }
+
     /// Parse an operator following an expression
     fn parse_infix(
         &mut self,
@@ -817,16 +880,17 @@ impl<'a> Parser<'a> {
         let tok = self.next_token().unwrap(); // safe as EOF's precedence is the lowest
 
         let regular_binary_operator = match &tok {
-            Token::Op(s) => Some(s.as_str()),
-            Token::Eq => Some("="),
-            Token::Star => Some("*"),
-            Token::Keyword(ILIKE) => Some("~~*"),
-            Token::Keyword(LIKE) => Some("~~"),
+            Token::Keyword(OPERATOR) => Some(self.parse_operator()?),
+            Token::Op(s) => Some(Op::bare(s.clone())),
+            Token::Eq => Some(Op::bare("=")),
+            Token::Star => Some(Op::bare("*")),
+            Token::Keyword(ILIKE) => Some(Op::bare("~~*")),
+            Token::Keyword(LIKE) => Some(Op::bare("~~")),
             Token::Keyword(NOT) => {
                 if self.parse_keyword(LIKE) {
-                    Some("!~~")
+                    Some(Op::bare("!~~"))
                 } else if self.parse_keyword(ILIKE) {
-                    Some("!~~*")
+                    Some(Op::bare("!~~*"))
                 } else {
                     None
                 }
@@ -836,6 +900,14 @@ impl<'a> Parser<'a> {
 
         if let Some(op) = regular_binary_operator {
             if let Some(kw) = self.parse_one_of_keywords(&[ANY, SOME, ALL]) {
+                if !op.namespace.is_empty() {
+                    return parser_err!(
+                        self,
+                        self.peek_prev_pos(),
+                        "qualified operators are not supported with ANY, SOME, or ALL"
+                    );
+                }
+                let op = op.op;
                 self.expect_token(&Token::LParen)?;
 
                 let expr = if self.parse_one_of_keywords(&[SELECT, VALUES]).is_some() {
@@ -845,13 +917,13 @@ impl<'a> Parser<'a> {
                     if kw == ALL {
                         Expr::AllSubquery {
                             left: Box::new(expr),
-                            op: op.into(),
+                            op,
                             right: Box::new(subquery),
                         }
                     } else {
                         Expr::AnySubquery {
                             left: Box::new(expr),
-                            op: op.into(),
+                            op,
                             right: Box::new(subquery),
                         }
                     }
@@ -861,13 +933,13 @@ impl<'a> Parser<'a> {
                     if kw == ALL {
                         Expr::AllExpr {
                             left: Box::new(expr),
-                            op: op.into(),
+                            op,
                             right: Box::new(right),
                         }
                     } else {
                         Expr::AnyExpr {
                             left: Box::new(expr),
-                            op: op.into(),
+                            op,
                             right: Box::new(right),
                         }
                     }
@@ -877,7 +949,7 @@ impl<'a> Parser<'a> {
                 Ok(expr)
             } else {
                 Ok(Expr::Op {
-                    op: op.into(),
+                    op,
                     expr1: Box::new(expr),
                     expr2: Some(Box::new(self.parse_subexpr(precedence)?)),
                 })
@@ -934,10 +1006,11 @@ impl<'a> Parser<'a> {
                     self.expect_keywords(&[TIME, ZONE])?;
                     Ok(Expr::Function(Function {
                         name: UnresolvedObjectName(vec!["timezone".into()]),
-                        args: FunctionArgs::Args(vec![self.parse_subexpr(precedence)?, expr]),
+                        args: FunctionArgs::args(vec![self.parse_subexpr(precedence)?, expr]),
                         filter: None,
                         over: None,
                         distinct: false,
+                        within_group: false,
                     }))
                 }
                 COLLATE => Ok(Expr::Collate {
@@ -1094,12 +1167,8 @@ impl<'a> Parser<'a> {
                 Token::Keyword(BETWEEN) => Precedence::Like,
                 Token::Keyword(ILIKE) => Precedence::Like,
                 Token::Keyword(LIKE) => Precedence::Like,
-                Token::Op(s) => match s.as_str() {
-                    "<" | "<=" | "<>" | "!=" | ">" | ">=" => Precedence::Cmp,
-                    "+" | "-" => Precedence::PlusMinus,
-                    "/" | "%" => Precedence::MultiplyDivide,
-                    _ => Precedence::Other,
-                },
+                Token::Keyword(OPERATOR) => self.get_qualified_operator_precedence(),
+                Token::Op(s) => Self::get_operator_precedence(s),
                 Token::Eq => Precedence::Cmp,
                 Token::Star => Precedence::MultiplyDivide,
                 Token::Keyword(COLLATE) | Token::Keyword(AT) => Precedence::PostfixCollateAt,
@@ -1113,6 +1182,41 @@ impl<'a> Parser<'a> {
         }
     }
 
+    fn get_operator_precedence(op: &str) -> Precedence {
+        match op {
+            "<" | "<=" | "<>" | "!=" | ">" | ">=" | "=" => Precedence::Cmp,
+            "+" | "-" => Precedence::PlusMinus,
+            "*" | "/" | "%" => Precedence::MultiplyDivide,
+            // Separates the keys and values of `MAP[...]` expressions. It is
+            // not an operator.
+            "=>" => Precedence::Zero,
+            _ => Precedence::Other,
+        }
+    }
+
+    /// Get the precedence of an upcoming `OPERATOR(...)` construct.
+    ///
+    /// Unlike PostgreSQL, which gives every `OPERATOR(...)` construct the same
+    /// precedence, we use the precedence of the named operator, so that
+    /// qualifying an operator never changes how an expression parses.
+    fn get_qualified_operator_precedence(&self) -> Precedence {
+        // `OPERATOR` is only an infix operator when it introduces a
+        // parenthesized operator name.
+        if self.peek_nth_token(1) != Some(Token::LParen) {
+            return Precedence::Zero;
+        }
+        let mut n = 2;
+        loop {
+            match self.peek_nth_token(n) {
+                Some(Token::Op(op)) => return Self::get_operator_precedence(&op),
+                Some(Token::Eq) => return Precedence::Cmp,
+                Some(Token::Star) => return Precedence::MultiplyDivide,
+                Some(Token::Keyword(_)) | Some(Token::Ident(_)) | Some(Token::Dot) => n += 1,
+                _ => return Precedence::Other,
+            }
+        }
+    }
+
     /// Return the first non-whitespace token that has not yet been processed
     /// (or None if reached end-of-file)
     fn peek_token(&self) -> Option<Token> {
@@ -1446,15 +1550,38 @@ impl<'a> Parser<'a> {
                 delimiter,
             }
         } else if self.parse_keyword(JSON) {
-            Format::Json
+            if self.parse_keywords(&[USING, SCHEMA, REGISTRY]) {
+                let url = self.parse_literal_string()?;
+                let seed = if self.parse_keywords(&[SEED, SCHEMA]) {
+                    Some(self.parse_literal_string()?)
+                } else {
+                    None
+                };
+                // Look ahead to avoid erroring on `WITH SNAPSHOT`; we only
+                // want to accept `WITH (...)` here.
+                let with_options = if self.peek_nth_token(1) == Some(Token::LParen) {
+                    self.parse_opt_with_sql_options()?
+                } else {
+                    vec![]
+                };
+                Format::JsonSchema {
+                    url,
+                    seed,
+                    with_options,
+                }
+            } else {
+                Format::Json
+            }
         } else if self.parse_keyword(TEXT) {
             Format::Text
         } else if self.parse_keyword(BYTES) {
             Format::Bytes
+        } else if self.parse_keyword(PARQUET) {
+            Format::Parquet
         } else {
             return self.expected(
                 self.peek_pos(),
-                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, or BYTES",
+                "AVRO, PROTOBUF, REGEX, CSV, JSON, TEXT, BYTES, or PARQUET",
                 self.peek_token(),
             );
         };
@@ -1636,7 +1763,7 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_connector(&mut self) -> Result<Connector, ParserError> {
-        match self.expect_one_of_keywords(&[FILE, KAFKA, KINESIS, AVRO, S3])? {
+        match self.expect_one_of_keywords(&[FILE, KAFKA, KINESIS, AVRO, S3, NOTIFY])? {
             FILE => {
                 let path = self.parse_literal_string()?;
                 let compression = if self.parse_keyword(COMPRESSION) {
@@ -1650,13 +1777,19 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(BROKER)?;
                 let broker = self.parse_literal_string()?;
                 self.expect_keyword(TOPIC)?;
+                let topic_pattern = self.parse_keyword(PATTERN);
                 let topic = self.parse_literal_string()?;
                 let key = if self.parse_keyword(KEY) {
                     Some(self.parse_parenthesized_column_list(Mandatory)?)
                 } else {
                     None
                 };
-                Ok(Connector::Kafka { broker, topic, key })
+                Ok(Connector::Kafka {
+                    broker,
+                    topic,
+                    topic_pattern,
+                    key,
+                })
             }
             KINESIS => {
                 self.expect_keyword(ARN)?;
@@ -1668,7 +1801,21 @@ impl<'a> Parser<'a> {
                 let path = self.parse_literal_string()?;
                 Ok(Connector::AvroOcf { path })
             }
+            NOTIFY => {
+                let channel = self.parse_identifier()?;
+                Ok(Connector::Notify { channel })
+            }
             S3 => {
+                // INTO S3 BUCKET '<bucket>' PREFIX '<prefix>'
+                if self.parse_keyword(BUCKET) {
+                    let bucket = self.parse_literal_string()?;
+                    let prefix = if self.parse_keyword(PREFIX) {
+                        Some(self.parse_literal_string()?)
+                    } else {
+                        None
+                    };
+                    return Ok(Connector::S3Bucket { bucket, prefix });
+                }
                 // FROM S3 OBJECTS FROM (SCAN '<bucket>')+ MATCHING '<pattern>'
                 self.expect_keywords(&[OBJECTS, FROM])?;
                 let mut key_sources = Vec::new();
@@ -2098,7 +2245,11 @@ impl<'a> Parser<'a> {
             SqlOption::Value { name, value }
         } else {
             self.prev_token();
-            if let Ok(object_name) = self.parse_object_name() {
+            if self.consume_token(&Token::LParen) {
+                let columns = self.parse_comma_separated(Parser::parse_identifier)?;
+                self.expect_token(&Token::RParen)?;
+                SqlOption::Columns { name, columns }
+            } else if let Ok(object_name) = self.parse_object_name() {
                 SqlOption::ObjectName { name, object_name }
             } else {
                 self.expected(self.peek_pos(), "option value", token)?
@@ -2163,7 +2314,8 @@ impl<'a> Parser<'a> {
         let if_exists = self.parse_if_exists()?;
         let name = self.parse_object_name()?;
 
-        // We support `ALTER INDEX ... {RESET, SET} and `ALTER <object type> RENAME
+        // We support `ALTER INDEX ... {RESET, SET}`, `ALTER SOURCE ... SET`, and
+        // `ALTER <object type> RENAME`
         if object_type == ObjectType::Index {
             let options = match self.parse_one_of_keywords(&[RESET, SET]) {
                 Some(RESET) => {
@@ -2191,6 +2343,15 @@ impl<'a> Parser<'a> {
             }
         }
 
+        if object_type == ObjectType::Source && self.parse_keyword(SET) {
+            let options = self.parse_options()?;
+            return Ok(Statement::AlterSourceOptions(AlterSourceOptionsStatement {
+                source_name: name,
+                if_exists,
+                options,
+            }));
+        }
+
         self.expect_keywords(&[RENAME, TO])?;
         let to_item_name = self.parse_identifier()?;
 
@@ -2302,6 +2463,29 @@ impl<'a> Parser<'a> {
         Ok(Expr::List(self.parse_sequence(Self::parse_list)?))
     }
 
+    fn parse_map_expr(&mut self) -> Result<Expr<Raw>, ParserError> {
+        self.expect_token(&Token::LBracket)?;
+        let mut entries = vec![];
+        loop {
+            if let Some(Token::RBracket) = self.peek_token() {
+                break;
+            }
+            let key = self.parse_expr()?;
+            self.expect_token(&Token::Op("=>".to_owned()))?;
+            let value = if let Some(Token::LBracket) = self.peek_token() {
+                self.parse_map_expr()?
+            } else {
+                self.parse_expr()?
+            };
+            entries.push(MapEntry { key, value });
+            if !self.consume_token(&Token::Comma) {
+                break;
+            }
+        }
+        self.expect_token(&Token::RBracket)?;
+        Ok(Expr::Map(entries))
+    }
+
     fn parse_sequence<F>(&mut self, mut f: F) -> Result<Vec<Expr<Raw>>, ParserError>
     where
         F: FnMut(&mut Self) -> Result<Expr<Raw>, ParserError>,
@@ -2958,7 +3142,7 @@ impl<'a> Parser<'a> {
         };
 
         let group_by = if self.parse_keywords(&[GROUP, BY]) {
-            self.parse_comma_separated(Parser::parse_expr)?
+            self.parse_comma_separated(Parser::parse_group_by_expr)?
         } else {
             vec![]
         };
@@ -2986,6 +3170,52 @@ impl<'a> Parser<'a> {
         })
     }
 
+    fn parse_group_by_expr(&mut self) -> Result<GroupByExpr<Raw>, ParserError> {
+        if self.peek_token() == Some(Token::LParen) && self.peek_nth_token(1) == Some(Token::RParen)
+        {
+            self.next_token();
+            self.next_token();
+            Ok(GroupByExpr::Set(vec![]))
+        } else if self.peek_keyword() == Some(ROLLUP)
+            && self.peek_nth_token(1) == Some(Token::LParen)
+        {
+            self.next_token();
+            Ok(GroupByExpr::Rollup(self.parse_grouping_elements()?))
+        } else if self.peek_keyword() == Some(CUBE) && self.peek_nth_token(1) == Some(Token::LParen)
+        {
+            self.next_token();
+            Ok(GroupByExpr::Cube(self.parse_grouping_elements()?))
+        } else if self.parse_keywords(&[GROUPING, SETS]) {
+            self.expect_token(&Token::LParen)?;
+            let items = self.parse_comma_separated(|parser| {
+                // Within `GROUPING SETS`, a parenthesized list of expressions
+                // is a grouping set rather than a row constructor.
+                match parser.parse_group_by_expr()? {
+                    GroupByExpr::Expr(Expr::Row { exprs }) if exprs.len() != 1 => {
+                        Ok(GroupByExpr::Set(exprs))
+                    }
+                    item => Ok(item),
+                }
+            })?;
+            self.expect_token(&Token::RParen)?;
+            Ok(GroupByExpr::GroupingSets(items))
+        } else {
+            Ok(GroupByExpr::Expr(self.parse_expr()?))
+        }
+    }
+
+    /// Parses the parenthesized elements of a `ROLLUP` or `CUBE`, each of which
+    /// is either an expression or a parenthesized list of expressions.
+    fn parse_grouping_elements(&mut self) -> Result<Vec<Vec<Expr<Raw>>>, ParserError> {
+        self.expect_token(&Token::LParen)?;
+        let elements = self.parse_comma_separated(|parser| match parser.parse_expr()? {
+            Expr::Row { exprs } if exprs.len() > 1 => Ok(exprs),
+            expr => Ok(vec![expr]),
+        })?;
+        self.expect_token(&Token::RParen)?;
+        Ok(elements)
+    }
+
     fn parse_set(&mut self) -> Result<Statement<Raw>, ParserError> {
         let modifier = self.parse_one_of_keywords(&[SESSION, LOCAL]);
         let mut variable = self.parse_identifier()?;
@@ -3009,8 +3239,25 @@ impl<'a> Parser<'a> {
                 value,
             }))
         } else if variable.as_str().parse() == Ok(TRANSACTION) && modifier.is_none() {
-            Ok(Statement::SetTransaction(SetTransactionStatement {
-                modes: self.parse_transaction_modes()?,
+            let modes = self.parse_transaction_modes()?;
+            if modes.is_empty() {
+                return self.expected(self.peek_pos(), "transaction mode", self.peek_token());
+            }
+            Ok(Statement::SetTransaction(SetTransactionStatement { modes }))
+        } else if variable.as_str().parse() == Ok(CHARACTERISTICS)
+            && modifier == Some(SESSION)
+            && self.parse_keywords(&[AS, TRANSACTION])
+        {
+            // Of the session's transaction characteristics, only the default
+            // isolation level is supported, which has its own variable.
+            self.expect_keywords(&[ISOLATION, LEVEL])?;
+            let iso_level = self.parse_transaction_isolation_level()?;
+            Ok(Statement::SetVariable(SetVariableStatement {
+                local: false,
+                variable: Ident::new("default_transaction_isolation"),
+                value: SetVariableValue::Literal(Value::String(
+                    iso_level.to_string().to_lowercase(),
+                )),
             }))
         } else {
             self.expected(self.peek_pos(), "equals sign or TO", self.peek_token())
@@ -3252,11 +3499,7 @@ impl<'a> Parser<'a> {
             } else {
                 let name = self.parse_object_name()?;
                 self.expect_token(&Token::LParen)?;
-                return Ok(TableFactor::Function {
-                    name,
-                    args: self.parse_optional_args()?,
-                    alias: self.parse_optional_table_alias()?,
-                });
+                return self.parse_table_function(name);
             }
         }
 
@@ -3331,11 +3574,7 @@ impl<'a> Parser<'a> {
         } else {
             let name = self.parse_object_name()?;
             if self.consume_token(&Token::LParen) {
-                Ok(TableFactor::Function {
-                    name,
-                    args: self.parse_optional_args()?,
-                    alias: self.parse_optional_table_alias()?,
-                })
+                self.parse_table_function(name)
             } else {
                 Ok(TableFactor::Table {
                     name: RawName::Name(name),
@@ -3345,6 +3584,21 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses the arguments of the table function `name`, whose opening
+    /// parenthesis has been consumed, followed by an optional
+    /// `WITH ORDINALITY` and an optional `[AS] alias`.
+    fn parse_table_function(
+        &mut self,
+        name: UnresolvedObjectName,
+    ) -> Result<TableFactor<Raw>, ParserError> {
+        Ok(TableFactor::Function {
+            name,
+            args: self.parse_optional_args()?,
+            with_ordinality: self.parse_keywords(&[WITH, ORDINALITY]),
+            alias: self.parse_optional_table_alias()?,
+        })
+    }
+
     fn parse_derived_table_factor(
         &mut self,
         lateral: IsLateral,
@@ -3390,10 +3644,18 @@ impl<'a> Parser<'a> {
         } else {
             InsertSource::Query(self.parse_query()?)
         };
+        let on_conflict = if self.parse_keywords(&[ON, CONFLICT]) {
+            let columns = self.parse_parenthesized_column_list(Optional)?;
+            self.expect_keywords(&[DO, NOTHING])?;
+            Some(OnConflict { columns })
+        } else {
+            None
+        };
         Ok(Statement::Insert(InsertStatement {
             table_name,
             columns,
             source,
+            on_conflict,
         }))
     }
 
@@ -3426,26 +3688,36 @@ impl<'a> Parser<'a> {
             self.expect_token(&Token::RParen)?;
             Ok(FunctionArgs::Star)
         } else if self.consume_token(&Token::RParen) {
-            Ok(FunctionArgs::Args(vec![]))
+            Ok(FunctionArgs::args(vec![]))
         } else {
             let args = self.parse_comma_separated(Parser::parse_expr)?;
+            let order_by = if self.parse_keywords(&[ORDER, BY]) {
+                self.parse_comma_separated(Parser::parse_order_by_expr)?
+            } else {
+                vec![]
+            };
             self.expect_token(&Token::RParen)?;
-            Ok(FunctionArgs::Args(args))
+            Ok(FunctionArgs::Args { args, order_by })
         }
     }
 
-    /// Parse `AS OF`, if present.
+    /// Parse `AS OF`, or its SQL:2011 spelling `FOR SYSTEM_TIME AS OF`, if
+    /// present.
     fn parse_optional_as_of(&mut self) -> Result<Option<Expr<Raw>>, ParserError> {
-        if self.parse_keyword(AS) {
-            self.expect_keyword(OF)?;
-            match self.parse_expr() {
-                Ok(expr) => Ok(Some(expr)),
-                Err(e) => {
-                    self.expected(e.pos, "a timestamp value after 'AS OF'", self.peek_token())
-                }
+        if self.parse_keyword(FOR) {
+            match self.next_token() {
+                Some(Token::Ident(id)) if id == "system_time" => (),
+                other => return self.expected(self.peek_prev_pos(), "SYSTEM_TIME", other),
             }
+            self.expect_keywords(&[AS, OF])?;
+        } else if self.parse_keyword(AS) {
+            self.expect_keyword(OF)?;
         } else {
-            Ok(None)
+            return Ok(None);
+        }
+        match self.parse_expr() {
+            Ok(expr) => Ok(Some(expr)),
+            Err(e) => self.expected(e.pos, "a timestamp value after 'AS OF'", self.peek_token()),
         }
     }
 
@@ -3460,7 +3732,8 @@ impl<'a> Parser<'a> {
         })
     }
 
-    /// Parse an expression, optionally followed by ASC or DESC (used in ORDER BY)
+    /// Parse an expression, optionally followed by ASC or DESC and by NULLS
+    /// FIRST or NULLS LAST (used in ORDER BY)
     fn parse_order_by_expr(&mut self) -> Result<OrderByExpr<Raw>, ParserError> {
         let expr = self.parse_expr()?;
 
@@ -3471,7 +3744,22 @@ impl<'a> Parser<'a> {
         } else {
             None
         };
-        Ok(OrderByExpr { expr, asc })
+
+        let nulls_last = if self.parse_keyword(NULLS) {
+            match self.expect_one_of_keywords(&[FIRST, LAST])? {
+                FIRST => Some(false),
+                LAST => Some(true),
+                _ => unreachable!(),
+            }
+        } else {
+            None
+        };
+
+        Ok(OrderByExpr {
+            expr,
+            asc,
+            nulls_last,
+        })
     }
 
     fn parse_values(&mut self) -> Result<Values<Raw>, ParserError> {
@@ -3498,23 +3786,28 @@ impl<'a> Parser<'a> {
         }))
     }
 
+    fn parse_transaction_isolation_level(
+        &mut self,
+    ) -> Result<TransactionIsolationLevel, ParserError> {
+        if self.parse_keywords(&[READ, UNCOMMITTED]) {
+            Ok(TransactionIsolationLevel::ReadUncommitted)
+        } else if self.parse_keywords(&[READ, COMMITTED]) {
+            Ok(TransactionIsolationLevel::ReadCommitted)
+        } else if self.parse_keywords(&[REPEATABLE, READ]) {
+            Ok(TransactionIsolationLevel::RepeatableRead)
+        } else if self.parse_keyword(SERIALIZABLE) {
+            Ok(TransactionIsolationLevel::Serializable)
+        } else {
+            self.expected(self.peek_pos(), "isolation level", self.peek_token())
+        }
+    }
+
     fn parse_transaction_modes(&mut self) -> Result<Vec<TransactionMode>, ParserError> {
         let mut modes = vec![];
         let mut required = false;
         loop {
             let mode = if self.parse_keywords(&[ISOLATION, LEVEL]) {
-                let iso_level = if self.parse_keywords(&[READ, UNCOMMITTED]) {
-                    TransactionIsolationLevel::ReadUncommitted
-                } else if self.parse_keywords(&[READ, COMMITTED]) {
-                    TransactionIsolationLevel::ReadCommitted
-                } else if self.parse_keywords(&[REPEATABLE, READ]) {
-                    TransactionIsolationLevel::RepeatableRead
-                } else if self.parse_keyword(SERIALIZABLE) {
-                    TransactionIsolationLevel::Serializable
-                } else {
-                    self.expected(self.peek_pos(), "isolation level", self.peek_token())?
-                };
-                TransactionMode::IsolationLevel(iso_level)
+                TransactionMode::IsolationLevel(self.parse_transaction_isolation_level()?)
             } else if self.parse_keywords(&[READ, ONLY]) {
                 TransactionMode::AccessMode(TransactionAccessMode::ReadOnly)
             } else if self.parse_keywords(&[READ, WRITE]) {
@@ -3561,23 +3854,43 @@ impl<'a> Parser<'a> {
         let name = self.parse_object_name()?;
         let options = self.parse_opt_with_options()?;
         let as_of = self.parse_optional_as_of()?;
+        let order_by = if self.parse_keywords(&[ORDER, BY]) {
+            self.parse_comma_separated(Parser::parse_order_by_expr)?
+        } else {
+            vec![]
+        };
         Ok(Statement::Tail(TailStatement {
             name,
             options,
             as_of,
+            order_by,
         }))
     }
 
     /// Parse an `EXPLAIN` statement, assuming that the `EXPLAIN` token
     /// has already been consumed.
     fn parse_explain(&mut self) -> Result<Statement<Raw>, ParserError> {
+        // DROP ...
+        if self.parse_keyword(DROP) {
+            let drop_pos = self.peek_pos();
+            return match self.parse_drop()? {
+                Statement::DropObjects(stmt) => Ok(Statement::Explain(ExplainStatement {
+                    stage: ExplainStage::OptimizedPlan,
+                    explainee: Explainee::Drop(stmt),
+                    options: ExplainOptions { typed: false },
+                })),
+                _ => parser_err!(self, drop_pos, "EXPLAIN DROP DATABASE is not supported"),
+            };
+        }
+
         // (TYPED)?
         let options = ExplainOptions {
             typed: self.parse_keyword(TYPED),
         };
 
-        // (RAW | DECORRELATED | OPTIMIZED)? PLAN
-        let stage = match self.parse_one_of_keywords(&[RAW, DECORRELATED, OPTIMIZED, PLAN]) {
+        // ((RAW | DECORRELATED | OPTIMIZED)? PLAN | MEMORY)
+        let stage = match self.parse_one_of_keywords(&[RAW, DECORRELATED, OPTIMIZED, PLAN, MEMORY])
+        {
             Some(RAW) => {
                 self.expect_keywords(&[PLAN, FOR])?;
                 ExplainStage::RawPlan
@@ -3594,6 +3907,10 @@ impl<'a> Parser<'a> {
                 self.expect_keyword(FOR)?;
                 ExplainStage::OptimizedPlan
             }
+            Some(MEMORY) => {
+                self.expect_keyword(FOR)?;
+                ExplainStage::Memory
+            }
             None => ExplainStage::OptimizedPlan,
             _ => unreachable!(),
         };
@@ -3634,6 +3951,44 @@ impl<'a> Parser<'a> {
         Ok(Statement::Close(CloseStatement { name }))
     }
 
+    /// Parse a `CANCEL QUERY` statement, assuming that the `CANCEL` token
+    /// has already been consumed.
+    fn parse_cancel_query(&mut self) -> Result<Statement<Raw>, ParserError> {
+        self.expect_keyword(QUERY)?;
+        let query_id = self.parse_literal_uint()?;
+        Ok(Statement::CancelQuery(CancelQueryStatement { query_id }))
+    }
+
+    /// Parse a `LISTEN` statement, assuming that the `LISTEN` token has
+    /// already been consumed.
+    fn parse_listen(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let channel = self.parse_identifier()?;
+        Ok(Statement::Listen(ListenStatement { channel }))
+    }
+
+    /// Parse an `UNLISTEN` statement, assuming that the `UNLISTEN` token has
+    /// already been consumed.
+    fn parse_unlisten(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let channel = if self.consume_token(&Token::Star) {
+            None
+        } else {
+            Some(self.parse_identifier()?)
+        };
+        Ok(Statement::Unlisten(UnlistenStatement { channel }))
+    }
+
+    /// Parse a `NOTIFY` statement, assuming that the `NOTIFY` token has
+    /// already been consumed.
+    fn parse_notify(&mut self) -> Result<Statement<Raw>, ParserError> {
+        let channel = self.parse_identifier()?;
+        let payload = if self.consume_token(&Token::Comma) {
+            Some(self.parse_literal_string()?)
+        } else {
+            None
+        };
+        Ok(Statement::Notify(NotifyStatement { channel, payload }))
+    }
+
     /// Parse a `FETCH` statement, assuming that the `FETCH` token
     /// has already been consumed.
     fn parse_fetch(&mut self) -> Result<Statement<Raw>, ParserError> {
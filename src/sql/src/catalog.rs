@@ -233,9 +233,10 @@ pub trait CatalogItem {
     /// catalog item is an index.
     fn index_details(&self) -> Option<(&[MirScalarExpr], GlobalId)>;
 
-    /// Returns the column defaults associated with the catalog item, if the
-    /// catalog item is a table.
-    fn table_details(&self) -> Option<&[Expr<Raw>]>;
+    /// Returns the column defaults, declared keys, and named `CHECK`
+    /// constraints associated with the catalog item, if the catalog item is a
+    /// table.
+    fn table_details(&self) -> Option<(&[Expr<Raw>], &[Vec<usize>], &[(String, Expr<Raw>)])>;
 }
 
 /// The type of a [`CatalogItem`].
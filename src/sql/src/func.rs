@@ -28,8 +28,8 @@ use sql_parser::ast::{Expr, Raw, UnresolvedObjectName};
 use crate::catalog::CatalogItemType;
 use crate::names::PartialName;
 use crate::plan::expr::{
-    AggregateFunc, BinaryFunc, CoercibleScalarExpr, HirScalarExpr, NullaryFunc, TableFunc,
-    UnaryFunc, VariadicFunc,
+    AggregateFunc, BinaryFunc, CoercibleScalarExpr, HirScalarExpr, LagLeadType, NullaryFunc,
+    TableFunc, UnaryFunc, VariadicFunc,
 };
 use crate::plan::query::{self, ExprContext, QueryContext, QueryLifetime};
 use crate::plan::scope::Scope;
@@ -113,6 +113,7 @@ impl TypeCategory {
         match param {
             ParamType::Any
             | ParamType::ArrayAny
+            | ParamType::ArrayElementAny
             | ParamType::ListAny
             | ParamType::ListElementAny
             | ParamType::NonVecAny
@@ -245,48 +246,57 @@ macro_rules! sql_op {
             static ref EXPR: Expr<Raw> = sql_parser::parser::parse_expr($l.into())
                 .expect("static function definition failed to parse");
         }
-        Operation::variadic(move |ecx, args| {
-            // Reconstruct an expression context where the parameter types are
-            // bound to the types of the expressions in `args`.
-            let mut scx = ecx.qcx.scx.clone();
-            scx.param_types = Rc::new(RefCell::new(
-                args.iter()
-                    .enumerate()
-                    .map(|(i, e)| (i + 1, ecx.scalar_type(e)))
-                    .collect(),
-            ));
-            let mut qcx = QueryContext::root(&scx, ecx.qcx.lifetime);
-
-            // Desugar the expression
-            let mut expr = EXPR.clone();
-            transform_ast::transform_expr(&scx, &mut expr)?;
-
-            let expr = query::resolve_names_expr(&mut qcx, expr)?;
-
-            let ecx = ExprContext {
-                qcx: &qcx,
-                name: "static function definition",
-                scope: &Scope::empty(None),
-                relation_type: &RelationType::empty(),
-                allow_aggregates: false,
-                allow_subqueries: true,
-            };
+        Operation::variadic(move |ecx, args| plan_sql_impl(ecx, &EXPR, args))
+    }};
+}
 
-            // Plan the expression.
-            let mut expr = query::plan_expr(&ecx, &expr)?.type_as_any(&ecx)?;
+/// Plans `expr`, a function implementation written in SQL, whose parameters
+/// `$1`, `$2`, etc. stand for the corresponding expressions in `args`.
+fn plan_sql_impl(
+    ecx: &ExprContext,
+    expr: &Expr<Raw>,
+    args: Vec<HirScalarExpr>,
+) -> Result<HirScalarExpr, anyhow::Error> {
+    // Reconstruct an expression context where the parameter types are
+    // bound to the types of the expressions in `args`.
+    let mut scx = ecx.qcx.scx.clone();
+    scx.param_types = Rc::new(RefCell::new(
+        args.iter()
+            .enumerate()
+            .map(|(i, e)| (i + 1, ecx.scalar_type(e)))
+            .collect(),
+    ));
+    let mut qcx = QueryContext::root(&scx, ecx.qcx.lifetime);
+
+    // Desugar the expression
+    let mut expr = expr.clone();
+    transform_ast::transform_expr(&scx, &mut expr)?;
+
+    let expr = query::resolve_names_expr(&mut qcx, expr)?;
+
+    let ecx = ExprContext {
+        qcx: &qcx,
+        name: "static function definition",
+        scope: &Scope::empty(None),
+        relation_type: &RelationType::empty(),
+        allow_aggregates: false,
+        allow_subqueries: true,
+    };
 
-            // Replace the parameters with the actual arguments.
-            expr.splice_parameters(&args, 0);
+    // Plan the expression.
+    let mut expr = query::plan_expr(&ecx, &expr)?.type_as_any(&ecx)?;
 
-            Ok(expr)
-        })
-    }};
+    // Replace the parameters with the actual arguments.
+    expr.splice_parameters(&args, 0);
+
+    Ok(expr)
 }
 
 /// Describes a single function's implementation.
 pub struct FuncImpl<R> {
     oid: u32,
     params: ParamList,
+    return_type: ReturnType,
     op: Operation<R>,
 }
 
@@ -296,6 +306,8 @@ pub struct FuncImplCatalogDetails {
     pub oid: u32,
     pub arg_oids: Vec<u32>,
     pub variadic_oid: Option<u32>,
+    pub return_oid: u32,
+    pub return_is_set: bool,
 }
 
 impl<R> FuncImpl<R> {
@@ -304,6 +316,8 @@ impl<R> FuncImpl<R> {
             oid: self.oid,
             arg_oids: self.params.arg_oids(),
             variadic_oid: self.params.variadic_oid(),
+            return_oid: self.return_type.oid(),
+            return_is_set: self.return_type.is_set_of,
         }
     }
 }
@@ -313,6 +327,7 @@ impl<R> fmt::Debug for FuncImpl<R> {
         f.debug_struct("FuncImpl")
             .field("oid", &self.oid)
             .field("params", &self.params)
+            .field("return_type", &self.return_type)
             .field("op", &"<omitted>")
             .finish()
     }
@@ -409,7 +424,8 @@ impl ParamList {
     ///
     /// Polymorphic type consistency constraints include:
     /// - All arguments passed to `ArrayAny` must be `ScalarType::Array`s with
-    ///   the same types of elements.
+    ///   the same types of elements. All arguments passed to `ArrayElementAny`
+    ///   must also be of these elements' type.
     /// - All arguments passed to `ListAny` must be `ScalarType::List`s with the
     ///   same types of elements. All arguments passed to `ListElementAny` must
     ///   also be of these elements' type.
@@ -578,6 +594,14 @@ impl ParamList {
                         return None;
                     }
                 }
+                (ParamType::ArrayElementAny, Some(t), None) => {
+                    constrained_type = Some(ScalarType::Array(Box::new(t.clone())));
+                }
+                (ParamType::ArrayElementAny, Some(t), Some(constrained_array)) => {
+                    if !complex_base_eq(t, constrained_array.unwrap_array_element_type()) {
+                        return None;
+                    }
+                }
                 (ParamType::ListAny, Some(typ), None) | (ParamType::MapAny, Some(typ), None) => {
                     constrained_type = Some(typ.clone());
                     custom_oid_lock = typ.is_custom_type();
@@ -724,6 +748,10 @@ pub enum ParamType {
     /// A polymorphic pseudotype permitting any array type.  For more details,
     /// see [`resolve_polymorphic_types`].
     ArrayAny,
+    /// A polymorphic pseudotype permitting all types, subject to the
+    /// constraint that they match the element type of the arguments passed to
+    /// `ArrayAny`. For more details, see [`resolve_polymorphic_types`].
+    ArrayElementAny,
     /// A polymorphic pseudotype permitting a `ScalarType::List` of any element
     /// type.  For more details, see [`resolve_polymorphic_types`].
     ListAny,
@@ -752,7 +780,7 @@ impl ParamType {
         match self {
             ArrayAny => matches!(t, Array(..)),
             ListAny => matches!(t, List{..}),
-            Any | ListElementAny => true,
+            Any | ArrayElementAny | ListElementAny => true,
             NonVecAny => !t.is_vec(),
             MapAny => matches!(t, Map { .. }),
             DecimalAny => {
@@ -784,7 +812,7 @@ impl ParamType {
     fn is_polymorphic(&self) -> bool {
         use ParamType::*;
         match self {
-            ArrayAny | ListAny | MapAny | ListElementAny | NonVecAny => true,
+            ArrayAny | ArrayElementAny | ListAny | MapAny | ListElementAny | NonVecAny => true,
             Any | DecimalAny | Plain(_) => false,
         }
     }
@@ -804,6 +832,7 @@ impl ParamType {
             },
             ParamType::Any => postgres_types::Type::ANY.oid(),
             ParamType::ArrayAny => postgres_types::Type::ANYARRAY.oid(),
+            ParamType::ArrayElementAny => postgres_types::Type::ANYELEMENT.oid(),
             ParamType::DecimalAny => postgres_types::Type::NUMERIC.oid(),
             ParamType::ListAny => pgrepr::LIST.oid(),
             ParamType::ListElementAny => postgres_types::Type::ANYELEMENT.oid(),
@@ -837,6 +866,63 @@ impl From<ScalarType> for ParamType {
     }
 }
 
+/// Describes the type of a function's result, for the purposes of the catalog.
+///
+/// Return types are not used during function selection, as each
+/// implementation's operation determines its result's precise type. Where the
+/// precise type depends on the arguments, the return type is the polymorphic
+/// [`ParamType`] that most closely describes it.
+#[derive(Debug, Clone, Eq, PartialEq, Hash)]
+pub struct ReturnType {
+    /// The type of each returned value, or `None` if each value is an
+    /// anonymous record, as for table functions that do not return exactly one
+    /// column.
+    typ: Option<ParamType>,
+    /// Whether the function returns a set of values rather than exactly one.
+    is_set_of: bool,
+}
+
+impl ReturnType {
+    /// Expresses that a function returns a set of values of type `typ`.
+    fn set_of(typ: ParamType) -> ReturnType {
+        ReturnType {
+            typ: Some(typ),
+            is_set_of: true,
+        }
+    }
+
+    /// Expresses that a function returns a set of anonymous records.
+    fn set_of_record() -> ReturnType {
+        ReturnType {
+            typ: None,
+            is_set_of: true,
+        }
+    }
+
+    /// Generates values for `mz_catalog.mz_functions.ret_id`.
+    fn oid(&self) -> u32 {
+        match &self.typ {
+            Some(typ) => typ.oid(),
+            None => postgres_types::Type::RECORD.oid(),
+        }
+    }
+}
+
+impl From<ParamType> for ReturnType {
+    fn from(typ: ParamType) -> ReturnType {
+        ReturnType {
+            typ: Some(typ),
+            is_set_of: false,
+        }
+    }
+}
+
+impl From<ScalarType> for ReturnType {
+    fn from(s: ScalarType) -> ReturnType {
+        ParamType::Plain(s).into()
+    }
+}
+
 #[derive(Clone, Debug)]
 /// Tracks candidate implementations.
 pub struct Candidate<'a, R> {
@@ -1157,6 +1243,10 @@ fn coerce_args_to_types(
             ParamType::ArrayAny | ParamType::ListAny | ParamType::MapAny => {
                 do_convert(arg, &get_constrained_ty())?
             }
+            ParamType::ArrayElementAny => {
+                let constrained_array = get_constrained_ty();
+                do_convert(arg, &constrained_array.unwrap_array_element_type())?
+            }
             ParamType::ListElementAny => {
                 let constrained_list = get_constrained_ty();
                 do_convert(arg, &constrained_list.unwrap_list_element_type())?
@@ -1203,7 +1293,7 @@ macro_rules! builtins {
     {
         $(
             $name:expr => $ty:ident {
-                $($params:expr => $op:expr, $oid:expr;)+
+                $($params:expr => $op:expr => $return_type:expr, $oid:expr;)+
             }
         ),+
     } => {{
@@ -1213,6 +1303,7 @@ macro_rules! builtins {
                 $(FuncImpl {
                     oid: $oid,
                     params: $params.into(),
+                    return_type: $return_type.into(),
                     op: $op.into(),
                 },)+
             ];
@@ -1234,6 +1325,9 @@ pub enum Func {
     Scalar(Vec<FuncImpl<HirScalarExpr>>),
     Aggregate(Vec<FuncImpl<(HirScalarExpr, AggregateFunc)>>),
     Table(Vec<FuncImpl<TableFuncPlan>>),
+    /// A window function, which is planned as a record of its arguments and
+    /// the kind of window function to compute over them.
+    Window(Vec<FuncImpl<(HirScalarExpr, LagLeadType)>>),
 }
 
 impl Func {
@@ -1242,6 +1336,7 @@ impl Func {
             Func::Scalar(impls) => impls.iter().map(|f| f.details()).collect::<Vec<_>>(),
             Func::Aggregate(impls) => impls.iter().map(|f| f.details()).collect::<Vec<_>>(),
             Func::Table(impls) => impls.iter().map(|f| f.details()).collect::<Vec<_>>(),
+            Func::Window(impls) => impls.iter().map(|f| f.details()).collect::<Vec<_>>(),
         }
     }
 }
@@ -1258,6 +1353,14 @@ macro_rules! catalog_name_only {
     };
 }
 
+/// Deprecated names of `mz_catalog` functions, each paired with the name of
+/// the function that it is an alias for.
+///
+/// References to a deprecated name are rewritten to the canonical name when
+/// an item is created, so that stored items do not break when the alias is
+/// eventually removed.
+pub const DEPRECATED_FUNC_ALIASES: &[(&str, &str)] = &[("mz_now", "mz_logical_timestamp")];
+
 lazy_static! {
     /// Correlates a built-in function name to its implementations.
     pub static ref PG_CATALOG_BUILTINS: HashMap<&'static str, Func> = {
@@ -1277,54 +1380,66 @@ lazy_static! {
 
             // Scalars.
             "abs" => Scalar {
-                params!(Int32) => UnaryFunc::AbsInt32, 1397;
-                params!(Int64) => UnaryFunc::AbsInt64, 1395;
-                params!(DecimalAny) => UnaryFunc::AbsDecimal, 1398;
-                params!(Float32) => UnaryFunc::AbsFloat32, 1394;
-                params!(Float64) => UnaryFunc::AbsFloat64, 1395;
+                params!(Int32) => UnaryFunc::AbsInt32 => Int32, 1397;
+                params!(Int64) => UnaryFunc::AbsInt64 => Int64, 1395;
+                params!(DecimalAny) => UnaryFunc::AbsDecimal => DecimalAny, 1398;
+                params!(Float32) => UnaryFunc::AbsFloat32 => Float32, 1394;
+                params!(Float64) => UnaryFunc::AbsFloat64 => Float64, 1395;
             },
             "array_length" => Scalar {
-                params![ArrayAny, Int64] => BinaryFunc::ArrayLength, 2176;
+                params![ArrayAny, Int64] => BinaryFunc::ArrayLength => Int64, 2176;
             },
             "array_lower" => Scalar {
-                params!(ArrayAny, Int64) => BinaryFunc::ArrayLower, 2091;
+                params!(ArrayAny, Int64) => BinaryFunc::ArrayLower => Int64, 2091;
+            },
+            "array_position" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => VariadicFunc::ArrayPosition => Int32, 3277;
+                params!(ArrayAny, ArrayElementAny, Int32) => VariadicFunc::ArrayPosition => Int32, 3278;
+            },
+            "array_positions" => Scalar {
+                params!(ArrayAny, ArrayElementAny) => VariadicFunc::ArrayPositions => Array(Box::new(Int32)), 3279;
             },
             "array_to_string" => Scalar {
-                params!(ArrayAny, String) => Operation::variadic(array_to_string), 395;
-                params!(ArrayAny, String, String) => Operation::variadic(array_to_string), 384;
+                params!(ArrayAny, String) => Operation::variadic(array_to_string) => String, 395;
+                params!(ArrayAny, String, String) => Operation::variadic(array_to_string) => String, 384;
             },
             "array_upper" => Scalar {
-                params!(ArrayAny, Int64) => BinaryFunc::ArrayUpper, 2092;
+                params!(ArrayAny, Int64) => BinaryFunc::ArrayUpper => Int64, 2092;
             },
             "ascii" => Scalar {
-                params!(String) => UnaryFunc::Ascii, 1620;
+                params!(String) => UnaryFunc::Ascii => Int32, 1620;
             },
             "avg" => Scalar {
-                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2100;
-                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2101;
-                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2103;
-                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2104;
-                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2105;
-                params!(Interval) => Operation::nullary(|_ecx| catalog_name_only!("avg")), 2105;
+                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("avg")) => DecimalAny, 2100;
+                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("avg")) => DecimalAny, 2101;
+                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("avg")) => DecimalAny, 2103;
+                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("avg")) => Float64, 2104;
+                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("avg")) => Float64, 2105;
+                params!(Interval) => Operation::nullary(|_ecx| catalog_name_only!("avg")) => Interval, 2105;
+            },
+            "bit_count" => Scalar {
+                params!(Int32) => UnaryFunc::BitCountInt32 => Int32, oid::FUNC_BIT_COUNT_I32_OID;
+                params!(Int64) => UnaryFunc::BitCountInt64 => Int32, oid::FUNC_BIT_COUNT_I64_OID;
+                params!(Bytes) => UnaryFunc::BitCountBytes => Int64, 6163;
             },
             "bit_length" => Scalar {
-                params!(Bytes) => UnaryFunc::BitLengthBytes, 1810;
-                params!(String) => UnaryFunc::BitLengthString, 1811;
+                params!(Bytes) => UnaryFunc::BitLengthBytes => Int32, 1810;
+                params!(String) => UnaryFunc::BitLengthString => Int32, 1811;
             },
             "btrim" => Scalar {
-                params!(String) => UnaryFunc::TrimWhitespace, 885;
-                params!(String, String) => BinaryFunc::Trim, 884;
+                params!(String) => UnaryFunc::TrimWhitespace => String, 885;
+                params!(String, String) => BinaryFunc::Trim => String, 884;
             },
             "ceil" => Scalar {
-                params!(Float32) => UnaryFunc::CeilFloat32, oid::FUNC_CEIL_F32_OID;
-                params!(Float64) => UnaryFunc::CeilFloat64, 2308;
+                params!(Float32) => UnaryFunc::CeilFloat32 => Float32, oid::FUNC_CEIL_F32_OID;
+                params!(Float64) => UnaryFunc::CeilFloat64 => Float64, 2308;
                 params!(DecimalAny) => Operation::unary(|ecx, e| {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::CeilDecimal(s)))
-                }), 1711;
+                }) => DecimalAny, 1711;
             },
             "char_length" => Scalar {
-                params!(String) => UnaryFunc::CharLength, 1381;
+                params!(String) => UnaryFunc::CharLength => Int32, 1381;
             },
             "concat" => Scalar {
                 params!(Any...) => Operation::variadic(|ecx, cexprs| {
@@ -1343,13 +1458,13 @@ lazy_static! {
                         }
                     }
                     Ok(HirScalarExpr::CallVariadic { func: VariadicFunc::Concat, exprs })
-                }), 3058;
+                }) => String, 3058;
             },
             "convert_from" => Scalar {
-                params!(Bytes, String) => BinaryFunc::ConvertFrom, 1714;
+                params!(Bytes, String) => BinaryFunc::ConvertFrom => String, 1714;
             },
             "current_schema" => Scalar {
-                params!() => sql_op!("current_schemas(false)[1]"), 1402;
+                params!() => sql_op!("current_schemas(false)[1]") => String, 1402;
             },
             "current_schemas" => Scalar {
                 params!(Bool) => Operation::unary(|ecx, e| {
@@ -1364,34 +1479,44 @@ lazy_static! {
                         then: Box::new(with_sys),
                         els: Box::new(without_sys),
                     })
-                }), 1403;
+                }) => Array(Box::new(String)), 1403;
             },
             "current_user" => Scalar {
                 params!() => Operation::nullary(|ecx| {
                     let datum = Datum::String(ecx.qcx.scx.catalog.user());
                     Ok(HirScalarExpr::literal(datum, ScalarType::String))
-                }), 745;
+                }) => String, 745;
+            },
+            "date_bin" => Scalar {
+                params!(Interval, Timestamp, Timestamp) => VariadicFunc::DateBinTimestamp => Timestamp, 6177;
+                params!(Interval, TimestampTz, TimestampTz) => VariadicFunc::DateBinTimestampTz => TimestampTz, 6178;
             },
             "date_part" => Scalar {
-                params!(String, Interval) => BinaryFunc::DatePartInterval, 1172;
-                params!(String, Timestamp) => BinaryFunc::DatePartTimestamp, 2021;
-                params!(String, TimestampTz) => BinaryFunc::DatePartTimestampTz, 1171;
+                params!(String, Interval) => BinaryFunc::DatePartInterval => Float64, 1172;
+                params!(String, Timestamp) => BinaryFunc::DatePartTimestamp => Float64, 2021;
+                params!(String, TimestampTz) => BinaryFunc::DatePartTimestampTz => Float64, 1171;
             },
             "date_trunc" => Scalar {
-                params!(String, Timestamp) => BinaryFunc::DateTruncTimestamp, 2020;
-                params!(String, TimestampTz) => BinaryFunc::DateTruncTimestampTz, 1217;
+                params!(String, Timestamp) => BinaryFunc::DateTruncTimestamp => Timestamp, 2020;
+                params!(String, TimestampTz) => BinaryFunc::DateTruncTimestampTz => TimestampTz, 1217;
+            },
+            "decode" => Scalar {
+                params!(String, String) => BinaryFunc::Decode => Bytes, 1947;
             },
             "digest" => Scalar {
-                params!(String, String) => BinaryFunc::DigestString, 44154;
-                params!(Bytes, String) => BinaryFunc::DigestBytes, 44155;
+                params!(String, String) => BinaryFunc::DigestString => Bytes, 44154;
+                params!(Bytes, String) => BinaryFunc::DigestBytes => Bytes, 44155;
+            },
+            "encode" => Scalar {
+                params!(Bytes, String) => BinaryFunc::Encode => String, 1946;
             },
             "floor" => Scalar {
-                params!(Float32) => UnaryFunc::FloorFloat32, oid::FUNC_FLOOR_F32_OID;
-                params!(Float64) => UnaryFunc::FloorFloat64, 2309;
+                params!(Float32) => UnaryFunc::FloorFloat32 => Float32, oid::FUNC_FLOOR_F32_OID;
+                params!(Float64) => UnaryFunc::FloorFloat64 => Float64, 2309;
                 params!(DecimalAny) => Operation::unary(|ecx, e| {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::FloorDecimal(s)))
-                }), 1712;
+                }) => DecimalAny, 1712;
             },
             "format_type" => Scalar {
                 params!(Oid, Int32) => sql_op!(
@@ -1399,24 +1524,59 @@ lazy_static! {
                         WHEN $1 IS NULL THEN NULL
                         ELSE coalesce((SELECT concat(name, mz_internal.mz_render_typemod($1, $2)) FROM mz_catalog.mz_types WHERE oid = $1), '???')
                     END"
-                ), 1081;
+                ) => String, 1081;
+            },
+            "get_bit" => Scalar {
+                params!(Bytes, Int64) => BinaryFunc::GetBitBytes => Int32, 723;
+            },
+            // Calls to `grouping` in the SELECT, HAVING, and ORDER BY clauses
+            // of a query with a GROUP BY clause are computed alongside the
+            // query's aggregates, so planning one here means it is misplaced.
+            "grouping" => Scalar {
+                params!(Any...) => Operation::variadic(|ecx, _exprs| {
+                    bail!("grouping operations are not allowed in {}", ecx.name)
+                }) => Int32, oid::FUNC_GROUPING_OID;
+            },
+            "has_database_privilege" => Scalar {
+                params!(String, String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Database, exprs)) => Bool, 2250;
+                params!(String, Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Database, exprs)) => Bool, 2251;
+                params!(Oid, String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Database, exprs)) => Bool, 2252;
+                params!(Oid, Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Database, exprs)) => Bool, 2253;
+                params!(String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Database, exprs)) => Bool, 2254;
+                params!(Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Database, exprs)) => Bool, 2255;
+            },
+            "has_schema_privilege" => Scalar {
+                params!(String, String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Schema, exprs)) => Bool, 2268;
+                params!(String, Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Schema, exprs)) => Bool, 2269;
+                params!(Oid, String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Schema, exprs)) => Bool, 2270;
+                params!(Oid, Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Schema, exprs)) => Bool, 2271;
+                params!(String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Schema, exprs)) => Bool, 2272;
+                params!(Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Schema, exprs)) => Bool, 2273;
+            },
+            "has_table_privilege" => Scalar {
+                params!(String, String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Table, exprs)) => Bool, 1922;
+                params!(String, Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Table, exprs)) => Bool, 1923;
+                params!(Oid, String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Table, exprs)) => Bool, 1924;
+                params!(Oid, Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Table, exprs)) => Bool, 1925;
+                params!(String, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Table, exprs)) => Bool, 1926;
+                params!(Oid, String) => Operation::variadic(|ecx, exprs| plan_has_privilege(ecx, PrivilegeObject::Table, exprs)) => Bool, 1927;
             },
             "hmac" => Scalar {
-                params!(String, String, String) => VariadicFunc::HmacString, 44156;
-                params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes, 44157;
+                params!(String, String, String) => VariadicFunc::HmacString => Bytes, 44156;
+                params!(Bytes, Bytes, String) => VariadicFunc::HmacBytes => Bytes, 44157;
             },
             "jsonb_array_length" => Scalar {
-                params!(Jsonb) => UnaryFunc::JsonbArrayLength, 3207;
+                params!(Jsonb) => UnaryFunc::JsonbArrayLength => Int64, 3207;
             },
             "jsonb_build_array" => Scalar {
-                params!() => VariadicFunc::JsonbBuildArray, 3272;
+                params!() => VariadicFunc::JsonbBuildArray => Jsonb, 3272;
                 params!(Any...) => Operation::variadic(|ecx, exprs| Ok(HirScalarExpr::CallVariadic {
                     func: VariadicFunc::JsonbBuildArray,
                     exprs: exprs.into_iter().map(|e| typeconv::to_jsonb(ecx, e)).collect(),
-                })), 3271;
+                })) => Jsonb, 3271;
             },
             "jsonb_build_object" => Scalar {
-                params!() => VariadicFunc::JsonbBuildObject, 3274;
+                params!() => VariadicFunc::JsonbBuildObject => Jsonb, 3274;
                 params!(Any...) => Operation::variadic(|ecx, exprs| {
                     if exprs.len() % 2 != 0 {
                         bail!("argument list must have even number of elements")
@@ -1429,47 +1589,79 @@ lazy_static! {
                             vec![key, val]
                         }).flatten().collect(),
                     })
-                }), 3273;
+                }) => Jsonb, 3273;
+            },
+            "jsonb_insert" => Scalar {
+                params!(Jsonb, Array(Box::new(String)), Jsonb) => Operation::variadic(|_ecx, mut exprs| {
+                    exprs.push(HirScalarExpr::literal(Datum::False, ScalarType::Bool));
+                    Ok(HirScalarExpr::CallVariadic { func: VariadicFunc::JsonbInsert, exprs })
+                }) => Jsonb, oid::FUNC_JSONB_INSERT_OID;
+                params!(Jsonb, Array(Box::new(String)), Jsonb, Bool) => VariadicFunc::JsonbInsert => Jsonb, 3579;
+            },
+            "jsonb_path_query_first" => Scalar {
+                params!(Jsonb, String) => BinaryFunc::JsonbPathQueryFirst => Jsonb, 4008;
             },
             "jsonb_pretty" => Scalar {
-                params!(Jsonb) => UnaryFunc::JsonbPretty, 3306;
+                params!(Jsonb) => UnaryFunc::JsonbPretty => String, 3306;
+            },
+            "jsonb_set" => Scalar {
+                params!(Jsonb, Array(Box::new(String)), Jsonb) => Operation::variadic(|_ecx, mut exprs| {
+                    exprs.push(HirScalarExpr::literal_true());
+                    Ok(HirScalarExpr::CallVariadic { func: VariadicFunc::JsonbSet, exprs })
+                }) => Jsonb, oid::FUNC_JSONB_SET_OID;
+                params!(Jsonb, Array(Box::new(String)), Jsonb, Bool) => VariadicFunc::JsonbSet => Jsonb, 3304;
             },
             "jsonb_strip_nulls" => Scalar {
-                params!(Jsonb) => UnaryFunc::JsonbStripNulls, 3262;
+                params!(Jsonb) => UnaryFunc::JsonbStripNulls => Jsonb, 3262;
             },
             "jsonb_typeof" => Scalar {
-                params!(Jsonb) => UnaryFunc::JsonbTypeof, 3210;
+                params!(Jsonb) => UnaryFunc::JsonbTypeof => String, 3210;
+            },
+            "justify_days" => Scalar {
+                params!(Interval) => UnaryFunc::JustifyDays => Interval, 1295;
+            },
+            "justify_hours" => Scalar {
+                params!(Interval) => UnaryFunc::JustifyHours => Interval, 1175;
+            },
+            "justify_interval" => Scalar {
+                params!(Interval) => UnaryFunc::JustifyInterval => Interval, 2711;
             },
             "length" => Scalar {
-                params!(Bytes) => UnaryFunc::ByteLengthBytes, 2010;
-                params!(String) => UnaryFunc::CharLength, 1317;
-                params!(Bytes, String) => BinaryFunc::EncodedBytesCharLength, 1713;
+                params!(Bytes) => UnaryFunc::ByteLengthBytes => Int32, 2010;
+                params!(String) => UnaryFunc::CharLength => Int32, 1317;
+                params!(Bytes, String) => BinaryFunc::EncodedBytesCharLength => Int32, 1713;
+            },
+            "levenshtein" => Scalar {
+                params!(String, String) => BinaryFunc::Levenshtein => Int32, oid::FUNC_LEVENSHTEIN_OID;
+            },
+            "levenshtein_less_equal" => Scalar {
+                params!(String, String, Int32) => VariadicFunc::LevenshteinLessEqual => Int32, oid::FUNC_LEVENSHTEIN_LESS_EQUAL_OID;
             },
             "lower" => Scalar {
-                params!(String) => UnaryFunc::Lower, 870;
+                params!(String) => UnaryFunc::Lower => String, 870;
             },
             "lpad" => Scalar {
-                params!(String, Int64) => VariadicFunc::PadLeading, 879;
-                params!(String, Int64, String) => VariadicFunc::PadLeading, 873;
+                params!(String, Int64) => VariadicFunc::PadLeading => String, 879;
+                params!(String, Int64, String) => VariadicFunc::PadLeading => String, 873;
             },
             "ltrim" => Scalar {
-                params!(String) => UnaryFunc::TrimLeadingWhitespace, 881;
-                params!(String, String) => BinaryFunc::TrimLeading, 875;
+                params!(String) => UnaryFunc::TrimLeadingWhitespace => String, 881;
+                params!(String, String) => BinaryFunc::TrimLeading => String, 875;
             },
             "make_timestamp" => Scalar {
-                params!(Int64, Int64, Int64, Int64, Int64, Float64) => VariadicFunc::MakeTimestamp, 3461;
+                params!(Int64, Int64, Int64, Int64, Int64, Float64) => VariadicFunc::MakeTimestamp => Timestamp, 3461;
             },
             "mod" => Scalar {
-                params!(DecimalAny, DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("mod")), 1728;
-                params!(Int32, Int32) => Operation::nullary(|_ecx| catalog_name_only!("mod")), 941;
-                params!(Int64, Int64) => Operation::nullary(|_ecx| catalog_name_only!("mod")), 947;
+                params!(DecimalAny, DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => DecimalAny, 1728;
+                params!(Int32, Int32) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => Int32, 941;
+                params!(Int64, Int64) => Operation::nullary(|_ecx| catalog_name_only!("mod")) => Int64, 947;
             },
             "now" => Scalar {
-                params!() => Operation::nullary(|ecx| plan_current_timestamp(ecx, "now")), 1299;
+                params!() => Operation::nullary(|ecx| plan_current_timestamp(ecx, "now")) => TimestampTz, 1299;
             },
             "octet_length" => Scalar {
-                params!(Bytes) => UnaryFunc::ByteLengthBytes, 720;
-                params!(String) => UnaryFunc::ByteLengthString, 1374;
+                params!(Bytes) => UnaryFunc::ByteLengthBytes => Int32, 720;
+                params!(String) => UnaryFunc::ByteLengthString => Int32, 1374;
             },
             "obj_description" => Scalar {
                 params!(Oid, String) => Operation::binary(|_ecx, _oid, _catalog| {
@@ -1477,22 +1669,22 @@ lazy_static! {
                     // database object, but we don't presently support comments,
                     // so stubbed out out to always return NULL.
                     Ok(HirScalarExpr::literal_null(ScalarType::String))
-                }), 1215;
+                }) => String, 1215;
             },
             "pg_encoding_to_char" => Scalar {
                 // Materialize only supports UT8-encoded databases. Return 'UTF8' if Postgres'
                 // encoding id for UTF8 (6) is provided, otherwise return 'NULL'.
-                params!(Int64) => sql_op!("CASE WHEN $1 = 6 THEN 'UTF8' ELSE NULL END"), 1597;
+                params!(Int64) => sql_op!("CASE WHEN $1 = 6 THEN 'UTF8' ELSE NULL END") => String, 1597;
             },
             "pg_get_userbyid" => Scalar {
-                params!(Oid) => sql_op!("'unknown (OID=' || $1 || ')'"), 1642;
+                params!(Oid) => sql_op!("'unknown (OID=' || $1 || ')'") => String, 1642;
             },
             "pg_table_is_visible" => Scalar {
                 params!(Oid) => sql_op!(
                     "(SELECT s.name = ANY(current_schemas(true))
                      FROM mz_catalog.mz_objects o JOIN mz_catalog.mz_schemas s ON o.schema_id = s.id
                      WHERE o.oid = $1)"
-                ), 2079;
+                ) => Bool, 2079;
             },
             "pg_typeof" => Scalar {
                 params!(Any) => Operation::new(|ecx, spec, exprs, params| {
@@ -1512,84 +1704,106 @@ lazy_static! {
                     // at that point. For now, it's useful enough to have this
                     // halfway version that returns a string.
                     Ok(HirScalarExpr::literal(Datum::String(&name), ScalarType::String))
-                }), 1619;
+                }) => String, 1619;
             },
             "regexp_match" => Scalar {
-                params!(String, String) => VariadicFunc::RegexpMatch, 3396;
-                params!(String, String, String) => VariadicFunc::RegexpMatch, 3397;
+                params!(String, String) => VariadicFunc::RegexpMatch => Array(Box::new(String)), 3396;
+                params!(String, String, String) => VariadicFunc::RegexpMatch => Array(Box::new(String)), 3397;
+            },
+            "regexp_split_to_array" => Scalar {
+                params!(String, String) => VariadicFunc::RegexpSplitToArray => Array(Box::new(String)), 2767;
+                params!(String, String, String) => VariadicFunc::RegexpSplitToArray => Array(Box::new(String)), 2768;
+            },
+            "regexp_split_to_table" => Table {
+                params!(String, String) => Operation::variadic(regexp_split_to_table) => ReturnType::set_of(String.into()), 2765;
+                params!(String, String, String) => Operation::variadic(regexp_split_to_table) => ReturnType::set_of(String.into()), 2766;
             },
             "replace" => Scalar {
-                params!(String, String, String) => VariadicFunc::Replace, 2087;
+                params!(String, String, String) => VariadicFunc::Replace => String, 2087;
             },
             "round" => Scalar {
-                params!(Float32) => UnaryFunc::RoundFloat32, oid::FUNC_ROUND_F32_OID;
-                params!(Float64) => UnaryFunc::RoundFloat64, 1342;
+                params!(Float32) => UnaryFunc::RoundFloat32 => Float32, oid::FUNC_ROUND_F32_OID;
+                params!(Float64) => UnaryFunc::RoundFloat64 => Float64, 1342;
                 params!(DecimalAny) => Operation::unary(|ecx, e| {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::RoundDecimal(s)))
-                }), 1708;
+                }) => DecimalAny, 1708;
                 params!(DecimalAny, Int64) => Operation::binary(|ecx, lhs, rhs| {
                     let (_, s) = ecx.scalar_type(&lhs).unwrap_decimal_parts();
                     Ok(lhs.call_binary(rhs, BinaryFunc::RoundDecimal(s)))
-                }), 1707;
+                }) => DecimalAny, 1707;
             },
             "rtrim" => Scalar {
-                params!(String) => UnaryFunc::TrimTrailingWhitespace, 882;
-                params!(String, String) => BinaryFunc::TrimTrailing, 876;
+                params!(String) => UnaryFunc::TrimTrailingWhitespace => String, 882;
+                params!(String, String) => BinaryFunc::TrimTrailing => String, 876;
+            },
+            "set_bit" => Scalar {
+                params!(Bytes, Int64, Int32) => VariadicFunc::SetBitBytes => Bytes, 724;
             },
             "split_part" => Scalar {
-                params!(String, String, Int64) => VariadicFunc::SplitPart, 2088;
+                params!(String, String, Int64) => VariadicFunc::SplitPart => String, 2088;
             },
             "stddev" => Scalar {
-                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2159;
-                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2157;
-                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2158;
-                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2155;
-                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("stddev")), 2154;
+                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev")) => DecimalAny, 2159;
+                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev")) => Float64, 2157;
+                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev")) => Float64, 2158;
+                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev")) => DecimalAny, 2155;
+                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("stddev")) => DecimalAny, 2154;
             },
             "stddev_pop" => Scalar {
-                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2729;
-                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2727;
-                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2728;
-                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2725;
-                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")), 2724;
+                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")) => DecimalAny, 2729;
+                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")) => Float64, 2727;
+                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")) => Float64, 2728;
+                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")) => DecimalAny, 2725;
+                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_pop")) => DecimalAny, 2724;
             },
             "stddev_samp" => Scalar {
-                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2717;
-                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2715;
-                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2716;
-                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2713;
-                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")), 2712;
+                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")) => DecimalAny, 2717;
+                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")) => Float64, 2715;
+                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")) => Float64, 2716;
+                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")) => DecimalAny, 2713;
+                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("stddev_samp")) => DecimalAny, 2712;
+            },
+            "string_to_array" => Scalar {
+                params!(String, String) => VariadicFunc::StringToArray => Array(Box::new(String)), 394;
+                params!(String, String, String) => VariadicFunc::StringToArray => Array(Box::new(String)), 376;
             },
             "substr" => Scalar {
-                params!(String, Int64) => VariadicFunc::Substr, 883;
-                params!(String, Int64, Int64) => VariadicFunc::Substr, 877;
+                params!(String, Int64) => VariadicFunc::Substr => String, 883;
+                params!(String, Int64, Int64) => VariadicFunc::Substr => String, 877;
             },
             "substring" => Scalar {
-                params!(String, Int64) => VariadicFunc::Substr, 937;
-                params!(String, Int64, Int64) => VariadicFunc::Substr, 936;
+                params!(String, Int64) => VariadicFunc::Substr => String, 937;
+                params!(String, Int64, Int64) => VariadicFunc::Substr => String, 936;
             },
             "sqrt" => Scalar {
-                params!(Float32) => UnaryFunc::SqrtFloat32, oid::FUNC_SQRT_F32_OID;
-                params!(Float64) => UnaryFunc::SqrtFloat64, 1344;
+                params!(Float32) => UnaryFunc::SqrtFloat32 => Float32, oid::FUNC_SQRT_F32_OID;
+                params!(Float64) => UnaryFunc::SqrtFloat64 => Float64, 1344;
                 params!(DecimalAny) => Operation::unary(|ecx, e| {
                     let (_, s) = ecx.scalar_type(&e).unwrap_decimal_parts();
                     Ok(e.call_unary(UnaryFunc::SqrtDec(s)))
-                }), 1730;
+                }) => DecimalAny, 1730;
             },
             "timezone" => Scalar {
-                params!(String, Timestamp) => BinaryFunc::TimezoneTimestamp, 2069;
-                params!(String, TimestampTz) => BinaryFunc::TimezoneTimestampTz, 1159;
+                params!(String, Timestamp) => BinaryFunc::TimezoneTimestamp => TimestampTz, 2069;
+                params!(String, TimestampTz) => BinaryFunc::TimezoneTimestampTz => Timestamp, 1159;
                 // PG defines this as `text timetz`
-                params!(String, Time) => BinaryFunc::TimezoneTime, 2037;
-                params!(Interval, Timestamp) => BinaryFunc::TimezoneIntervalTimestamp, 1026;
-                params!(Interval, TimestampTz) => BinaryFunc::TimezoneIntervalTimestampTz, 1026;
+                params!(String, Time) => BinaryFunc::TimezoneTime => Time, 2037;
+                params!(Interval, Timestamp) => BinaryFunc::TimezoneIntervalTimestamp => TimestampTz, 1026;
+                params!(Interval, TimestampTz) => BinaryFunc::TimezoneIntervalTimestampTz => Timestamp, 1026;
                 // PG defines this as `interval timetz`
-                params!(Interval, Time) => BinaryFunc::TimezoneIntervalTime, 2038;
+                params!(Interval, Time) => BinaryFunc::TimezoneIntervalTime => Time, 2038;
             },
             "to_char" => Scalar {
-                params!(Timestamp, String) => BinaryFunc::ToCharTimestamp, 2049;
-                params!(TimestampTz, String) => BinaryFunc::ToCharTimestampTz, 1770;
+                params!(Timestamp, String) => BinaryFunc::ToCharTimestamp => String, 2049;
+                params!(TimestampTz, String) => BinaryFunc::ToCharTimestampTz => String, 1770;
+                params!(Int32, String) => BinaryFunc::ToCharInt32 => String, 1773;
+                params!(Int64, String) => BinaryFunc::ToCharInt64 => String, 1774;
+                params!(Float64, String) => BinaryFunc::ToCharFloat64 => String, 1776;
+                params!(DecimalAny, String) => Operation::binary(|ecx, lhs, rhs| {
+                    let (_, s) = ecx.scalar_type(&lhs).unwrap_decimal_parts();
+                    Ok(lhs.call_binary(rhs, BinaryFunc::ToCharDecimal(s)))
+                }) => String, 1772;
             },
             // > Returns the value as json or jsonb. Arrays and composites
             // > are converted (recursively) to arrays and objects;
@@ -1602,34 +1816,37 @@ lazy_static! {
             //
             // https://www.postgresql.org/docs/current/functions-json.html
             "to_jsonb" => Scalar {
-                params!(Any) => Operation::unary(|ecx, e| Ok(typeconv::to_jsonb(ecx, e))), 3787;
+                params!(Any) => Operation::unary(|ecx, e| Ok(typeconv::to_jsonb(ecx, e))) => Jsonb, 3787;
             },
             "to_timestamp" => Scalar {
-                params!(Float64) => UnaryFunc::ToTimestamp, 1158;
+                params!(Float64) => UnaryFunc::ToTimestamp => TimestampTz, 1158;
+            },
+            "unaccent" => Scalar {
+                params!(String) => UnaryFunc::Unaccent => String, oid::FUNC_UNACCENT_OID;
             },
             "upper" => Scalar {
-                params!(String) => UnaryFunc::Upper, 871;
+                params!(String) => UnaryFunc::Upper => String, 871;
             },
             "variance" => Scalar {
-                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2153;
-                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2151;
-                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2152;
-                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2149;
-                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("variance")), 2148;
+                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("variance")) => DecimalAny, 2153;
+                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("variance")) => Float64, 2151;
+                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("variance")) => Float64, 2152;
+                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("variance")) => DecimalAny, 2149;
+                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("variance")) => DecimalAny, 2148;
             },
             "var_pop" => Scalar {
-                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2723;
-                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2721;
-                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2722;
-                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2719;
-                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")), 2718;
+                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")) => DecimalAny, 2723;
+                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")) => Float64, 2721;
+                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")) => Float64, 2722;
+                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")) => DecimalAny, 2719;
+                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("var_pop")) => DecimalAny, 2718;
             },
             "var_samp" => Scalar {
-                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2646;
-                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2644;
-                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2645;
-                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2642;
-                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")), 2641;
+                params!(DecimalAny) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")) => DecimalAny, 2646;
+                params!(Float32) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")) => Float64, 2644;
+                params!(Float64) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")) => Float64, 2645;
+                params!(Int32) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")) => DecimalAny, 2642;
+                params!(Int64) => Operation::nullary(|_ecx| catalog_name_only!("var_samp")) => DecimalAny, 2641;
             },
             "version" => Scalar {
                 params!() => Operation::nullary(|ecx| {
@@ -1639,52 +1856,86 @@ lazy_static! {
                         build_info.target_triple, build_info.version,
                     );
                     Ok(HirScalarExpr::literal(Datum::String(&version), ScalarType::String))
-                }), 89;
+                }) => String, 89;
+            },
+            "width_bucket" => Scalar {
+                params!(Float64, Float64, Float64, Int32) => VariadicFunc::WidthBucketFloat64 => Int32, 320;
+                params!(DecimalAny, DecimalAny, DecimalAny, Int32) => Operation::variadic(|ecx, mut exprs| {
+                    let count = exprs.pop().unwrap();
+                    let scales: Vec<_> = exprs
+                        .iter()
+                        .map(|e| ecx.scalar_type(e).unwrap_decimal_parts().1)
+                        .collect();
+                    let scale = scales.iter().copied().max().unwrap();
+                    let mut exprs: Vec<_> = exprs
+                        .into_iter()
+                        .zip(scales)
+                        .map(|(e, s)| rescale_decimal(e, s, scale))
+                        .collect();
+                    exprs.push(count);
+                    Ok(HirScalarExpr::CallVariadic {
+                        func: VariadicFunc::WidthBucketDecimal,
+                        exprs,
+                    })
+                }) => Int32, 2970;
+                params!(Any, ArrayAny) => Operation::binary(|ecx, operand, thresholds| {
+                    let elem_type = ecx.scalar_type(&thresholds).unwrap_array_element_type().clone();
+                    let operand = typeconv::plan_cast(
+                        "width_bucket", ecx, CastContext::Implicit, operand, &elem_type,
+                    )?;
+                    Ok(operand.call_binary(thresholds, BinaryFunc::WidthBucketArray))
+                }) => Int32, 3218;
             },
 
             // Aggregates.
             "array_agg" => Aggregate {
-                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("array_agg")), 4053;
+                params!(Any) => Operation::unary(|ecx, e| {
+                    ecx.require_experimental_mode("array_agg")?;
+                    if let ScalarType::Array(_) = ecx.scalar_type(&e) {
+                        bail!("array_agg on arrays not yet supported");
+                    }
+                    Ok((e, AggregateFunc::ArrayAgg { order_by: vec![] }))
+                }) => ArrayAny, 4053;
             },
             "bool_and" => Aggregate {
-                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("bool_and")), 2517;
+                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("bool_and")) => Bool, 2517;
             },
             "bool_or" => Aggregate {
-                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("bool_or")), 2518;
+                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("bool_or")) => Bool, 2518;
             },
             "count" => Aggregate {
                 params!() => Operation::nullary(|_ecx| {
                     // COUNT(*) is equivalent to COUNT(true).
                     Ok((HirScalarExpr::literal_true(), AggregateFunc::Count))
-                }), 2803;
-                params!(Any) => AggregateFunc::Count, 2147;
+                }) => Int64, 2803;
+                params!(Any) => AggregateFunc::Count => Int64, 2147;
             },
             "max" => Aggregate {
-                params!(Bool) => AggregateFunc::MaxBool, oid::FUNC_MAX_BOOL_OID;
-                params!(Int32) => AggregateFunc::MaxInt32, 2116;
-                params!(Int64) => AggregateFunc::MaxInt64, 2115;
-                params!(Float32) => AggregateFunc::MaxFloat32, 2119;
-                params!(Float64) => AggregateFunc::MaxFloat64, 2120;
-                params!(DecimalAny) => AggregateFunc::MaxDecimal, 2130;
-                params!(String) => AggregateFunc::MaxString, 2129;
-                params!(Date) => AggregateFunc::MaxDate, 2122;
-                params!(Timestamp) => AggregateFunc::MaxTimestamp, 2126;
-                params!(TimestampTz) => AggregateFunc::MaxTimestampTz, 2127;
+                params!(Bool) => AggregateFunc::MaxBool => Bool, oid::FUNC_MAX_BOOL_OID;
+                params!(Int32) => AggregateFunc::MaxInt32 => Int32, 2116;
+                params!(Int64) => AggregateFunc::MaxInt64 => Int64, 2115;
+                params!(Float32) => AggregateFunc::MaxFloat32 => Float32, 2119;
+                params!(Float64) => AggregateFunc::MaxFloat64 => Float64, 2120;
+                params!(DecimalAny) => AggregateFunc::MaxDecimal => DecimalAny, 2130;
+                params!(String) => AggregateFunc::MaxString => String, 2129;
+                params!(Date) => AggregateFunc::MaxDate => Date, 2122;
+                params!(Timestamp) => AggregateFunc::MaxTimestamp => Timestamp, 2126;
+                params!(TimestampTz) => AggregateFunc::MaxTimestampTz => TimestampTz, 2127;
             },
             "min" => Aggregate {
-                params!(Bool) => AggregateFunc::MinBool, oid::FUNC_MIN_BOOL_OID;
-                params!(Int32) => AggregateFunc::MinInt32, 2132;
-                params!(Int64) => AggregateFunc::MinInt64, 2131;
-                params!(Float32) => AggregateFunc::MinFloat32, 2135;
-                params!(Float64) => AggregateFunc::MinFloat64, 2136;
-                params!(DecimalAny) => AggregateFunc::MinDecimal, 2146;
-                params!(String) => AggregateFunc::MinString, 2145;
-                params!(Date) => AggregateFunc::MinDate, 2138;
-                params!(Timestamp) => AggregateFunc::MinTimestamp, 2142;
-                params!(TimestampTz) => AggregateFunc::MinTimestampTz, 2143;
+                params!(Bool) => AggregateFunc::MinBool => Bool, oid::FUNC_MIN_BOOL_OID;
+                params!(Int32) => AggregateFunc::MinInt32 => Int32, 2132;
+                params!(Int64) => AggregateFunc::MinInt64 => Int64, 2131;
+                params!(Float32) => AggregateFunc::MinFloat32 => Float32, 2135;
+                params!(Float64) => AggregateFunc::MinFloat64 => Float64, 2136;
+                params!(DecimalAny) => AggregateFunc::MinDecimal => DecimalAny, 2146;
+                params!(String) => AggregateFunc::MinString => String, 2145;
+                params!(Date) => AggregateFunc::MinDate => Date, 2138;
+                params!(Timestamp) => AggregateFunc::MinTimestamp => Timestamp, 2142;
+                params!(TimestampTz) => AggregateFunc::MinTimestampTz => TimestampTz, 2143;
             },
             "json_agg" => Aggregate {
-                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("json_agg")), 3175;
+                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("json_agg")) => Jsonb, 3175;
             },
             "jsonb_agg" => Aggregate {
                 params!(Any) => Operation::unary(|ecx, e| {
@@ -1699,24 +1950,76 @@ lazy_static! {
                         exprs: vec![typeconv::to_jsonb(ecx, e), json_null],
                     };
                     Ok((e, AggregateFunc::JsonbAgg))
-                }), 3267;
+                }) => Jsonb, 3267;
+            },
+            "percentile_cont" => Aggregate {
+                params!(Float64, Float64) => Operation::binary(|_ecx, fraction, value| {
+                    let e = percentile_record("percentile_cont", fraction, value)?;
+                    Ok((e, AggregateFunc::PercentileCont { order_by: vec![] }))
+                }) => Float64, 3974;
+            },
+            "percentile_disc" => Aggregate {
+                params!(Float64, Float64) => Operation::binary(|_ecx, fraction, value| {
+                    let e = percentile_record("percentile_disc", fraction, value)?;
+                    Ok((e, AggregateFunc::PercentileDisc { order_by: vec![] }))
+                }) => Float64, 3972;
+                params!(Float64, DecimalAny) => Operation::binary(|_ecx, fraction, value| {
+                    let e = percentile_record("percentile_disc", fraction, value)?;
+                    Ok((e, AggregateFunc::PercentileDisc { order_by: vec![] }))
+                }) => DecimalAny, oid::FUNC_PERCENTILE_DISC_NUMERIC_OID;
             },
             "string_agg" => Aggregate {
-                params!(Any, String) => Operation::binary(|_ecx, _lhs, _rhs| unsupported!("string_agg")), 3538;
+                params!(String, String) => Operation::binary(|_ecx, value, sep| {
+                    // `AggregateFunc::StringAgg` receives each value paired
+                    // with the delimiter that precedes it, as the delimiter
+                    // need not be constant.
+                    let e = HirScalarExpr::CallVariadic {
+                        func: VariadicFunc::RecordCreate {
+                            field_names: vec![ColumnName::from("value"), ColumnName::from("sep")],
+                        },
+                        exprs: vec![value, sep],
+                    };
+                    Ok((e, AggregateFunc::StringAgg { order_by: vec![] }))
+                }) => String, 3538;
+                params!(Bytes, Bytes) => Operation::binary(|_ecx, _lhs, _rhs| unsupported!("string_agg on BYTEA")) => Bytes, 3545;
             },
             "sum" => Aggregate {
-                params!(Int32) => AggregateFunc::SumInt32, 2108;
-                params!(Int64) => AggregateFunc::SumInt64, 2107;
-                params!(Float32) => AggregateFunc::SumFloat32, 2110;
-                params!(Float64) => AggregateFunc::SumFloat64, 2111;
-                params!(DecimalAny) => AggregateFunc::SumDecimal, 2114;
+                params!(Int32) => AggregateFunc::SumInt32 => Int64, 2108;
+                params!(Int64) => AggregateFunc::SumInt64 => DecimalAny, 2107;
+                params!(Float32) => AggregateFunc::SumFloat32 => Float32, 2110;
+                params!(Float64) => AggregateFunc::SumFloat64 => Float64, 2111;
+                params!(DecimalAny) => AggregateFunc::SumDecimal => DecimalAny, 2114;
                 params!(Interval) => Operation::unary(|_ecx, _e| {
                     // Explicitly providing this unsupported overload
                     // prevents `sum(NULL)` from choosing the `Float64`
                     // implementation, so that we match PostgreSQL's behavior.
                     // Plus we will one day want to support this overload.
                     unsupported!("sum(interval)");
-                }), 2113;
+                }) => Interval, 2113;
+            },
+
+            // Window functions.
+            "lag" => Window {
+                params!(ListElementAny) => Operation::variadic(|ecx, exprs| {
+                    lag_lead_record(ecx, LagLeadType::Lag, exprs)
+                }) => ListElementAny, 3106;
+                params!(ListElementAny, Int32) => Operation::variadic(|ecx, exprs| {
+                    lag_lead_record(ecx, LagLeadType::Lag, exprs)
+                }) => ListElementAny, 3107;
+                params!(ListElementAny, Int32, ListElementAny) => Operation::variadic(|ecx, exprs| {
+                    lag_lead_record(ecx, LagLeadType::Lag, exprs)
+                }) => ListElementAny, 3108;
+            },
+            "lead" => Window {
+                params!(ListElementAny) => Operation::variadic(|ecx, exprs| {
+                    lag_lead_record(ecx, LagLeadType::Lead, exprs)
+                }) => ListElementAny, 3109;
+                params!(ListElementAny, Int32) => Operation::variadic(|ecx, exprs| {
+                    lag_lead_record(ecx, LagLeadType::Lead, exprs)
+                }) => ListElementAny, 3110;
+                params!(ListElementAny, Int32, ListElementAny) => Operation::variadic(|ecx, exprs| {
+                    lag_lead_record(ecx, LagLeadType::Lead, exprs)
+                }) => ListElementAny, 3111;
             },
 
             // Table functions.
@@ -1727,14 +2030,28 @@ lazy_static! {
                         exprs: vec![start, stop],
                         column_names: vec![Some("generate_series".into())],
                     })
-                }), 1067;
+                }) => ReturnType::set_of(Int32.into()), 1067;
                 params!(Int64, Int64) => Operation::binary(move |_ecx, start, stop| {
                     Ok(TableFuncPlan {
                         func: TableFunc::GenerateSeriesInt64,
                         exprs: vec![start, stop],
                         column_names: vec![Some("generate_series".into())],
                     })
-                }), 1069;
+                }) => ReturnType::set_of(Int64.into()), 1069;
+                params!(Timestamp, Timestamp, Interval) => Operation::variadic(move |_ecx, exprs| {
+                    Ok(TableFuncPlan {
+                        func: TableFunc::GenerateSeriesTimestamp,
+                        exprs,
+                        column_names: vec![Some("generate_series".into())],
+                    })
+                }) => ReturnType::set_of(Timestamp.into()), 938;
+                params!(TimestampTz, TimestampTz, Interval) => Operation::variadic(move |_ecx, exprs| {
+                    Ok(TableFuncPlan {
+                        func: TableFunc::GenerateSeriesTimestampTz,
+                        exprs,
+                        column_names: vec![Some("generate_series".into())],
+                    })
+                }) => ReturnType::set_of(TimestampTz.into()), 939;
             },
             "jsonb_array_elements" => Table {
                 params!(Jsonb) => Operation::unary(move |_ecx, jsonb| {
@@ -1743,7 +2060,7 @@ lazy_static! {
                         exprs: vec![jsonb],
                         column_names: vec![Some("value".into())],
                     })
-                }), 3219;
+                }) => ReturnType::set_of(Jsonb.into()), 3219;
             },
             "jsonb_array_elements_text" => Table {
                 params!(Jsonb) => Operation::unary(move |_ecx, jsonb| {
@@ -1752,7 +2069,7 @@ lazy_static! {
                         exprs: vec![jsonb],
                         column_names: vec![Some("value".into())],
                     })
-                }), 3465;
+                }) => ReturnType::set_of(String.into()), 3465;
             },
             "jsonb_each" => Table {
                 params!(Jsonb) => Operation::unary(move |_ecx, jsonb| {
@@ -1761,7 +2078,7 @@ lazy_static! {
                         exprs: vec![jsonb],
                         column_names: vec![Some("key".into()), Some("value".into())],
                     })
-                }), 3208;
+                }) => ReturnType::set_of_record(), 3208;
             },
             "jsonb_each_text" => Table {
                 params!(Jsonb) => Operation::unary(move |_ecx, jsonb| {
@@ -1770,7 +2087,7 @@ lazy_static! {
                         exprs: vec![jsonb],
                         column_names: vec![Some("key".into()), Some("value".into())],
                     })
-                }), 3932;
+                }) => ReturnType::set_of_record(), 3932;
             },
             "jsonb_object_keys" => Table {
                 params!(Jsonb) => Operation::unary(move |_ecx, jsonb| {
@@ -1779,7 +2096,20 @@ lazy_static! {
                         exprs: vec![jsonb],
                         column_names: vec![Some("jsonb_object_keys".into())],
                     })
-                }), 3931;
+                }) => ReturnType::set_of(String.into()), 3931;
+            },
+            "jsonb_path_query" => Table {
+                params!(Jsonb, String) => Operation::binary(move |_ecx, jsonb, path| {
+                    let path = match path.into_literal_string() {
+                        None => bail!("jsonb_path_query requires a string literal as its second argument"),
+                        Some(path) => expr::JsonPath::parse(&path)?,
+                    };
+                    Ok(TableFuncPlan {
+                        func: TableFunc::JsonbPathQuery(path),
+                        exprs: vec![jsonb],
+                        column_names: vec![Some("jsonb_path_query".into())],
+                    })
+                }) => ReturnType::set_of(Jsonb.into()), 4006;
             }
         }
     };
@@ -1802,13 +2132,13 @@ lazy_static! {
                         exprs: vec![input],
                         column_names: (1..=ncols).map(|i| Some(format!("column{}", i).into())).collect(),
                     })
-                }), oid::FUNC_CSV_EXTRACT_OID;
+                }) => ReturnType::set_of_record(), oid::FUNC_CSV_EXTRACT_OID;
             },
             "concat_agg" => Aggregate {
-                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("concat_agg")), oid::FUNC_CONCAT_AGG_OID;
+                params!(Any) => Operation::unary(|_ecx, _e| unsupported!("concat_agg")) => String, oid::FUNC_CONCAT_AGG_OID;
             },
             "current_timestamp" => Scalar {
-                params!() => Operation::nullary(|ecx| plan_current_timestamp(ecx, "current_timestamp")), oid::FUNC_CURRENT_TIMESTAMP_OID;
+                params!() => Operation::nullary(|ecx| plan_current_timestamp(ecx, "current_timestamp")) => TimestampTz, oid::FUNC_CURRENT_TIMESTAMP_OID;
             },
             "internal_read_cached_data" => Table {
                 params!(String) => Operation::unary(move |ecx, source| {
@@ -1833,45 +2163,65 @@ lazy_static! {
                         exprs: vec![],
                         column_names: vec!["filename", "offset", "key", "value"].iter().map(|c| Some(ColumnName::from(*c))).collect(),
                     })
-                }), oid::FUNC_INTERNAL_READ_CACHED_DATA_OID;
+                }) => ReturnType::set_of_record(), oid::FUNC_INTERNAL_READ_CACHED_DATA_OID;
             },
             "list_append" => Scalar {
-                vec![ListAny, ListElementAny] => BinaryFunc::ListElementConcat, oid::FUNC_LIST_APPEND_OID;
+                vec![ListAny, ListElementAny] => BinaryFunc::ListElementConcat => ListAny, oid::FUNC_LIST_APPEND_OID;
             },
             "list_cat" => Scalar {
-                vec![ListAny, ListAny] =>  BinaryFunc::ListListConcat, oid::FUNC_LIST_CAT_OID;
+                vec![ListAny, ListAny] =>  BinaryFunc::ListListConcat => ListAny, oid::FUNC_LIST_CAT_OID;
             },
             "list_ndims" => Scalar {
                 vec![ListAny] => Operation::unary(|ecx, e| {
                     ecx.require_experimental_mode("list_ndims")?;
                     let d = ecx.scalar_type(&e).unwrap_list_n_dims();
                     Ok(HirScalarExpr::literal(Datum::Int32(d as i32), ScalarType::Int32))
-                }), oid::FUNC_LIST_NDIMS_OID;
+                }) => Int32, oid::FUNC_LIST_NDIMS_OID;
             },
             "list_length" => Scalar {
-                vec![ListAny] => UnaryFunc::ListLength, oid::FUNC_LIST_LENGTH_OID;
+                vec![ListAny] => UnaryFunc::ListLength => Int64, oid::FUNC_LIST_LENGTH_OID;
             },
             "list_length_max" => Scalar {
                 vec![ListAny, Plain(Int64)] => Operation::binary(|ecx, lhs, rhs| {
                     ecx.require_experimental_mode("list_length_max")?;
                     let max_dim = ecx.scalar_type(&lhs).unwrap_list_n_dims();
                     Ok(lhs.call_binary(rhs, BinaryFunc::ListLengthMax{ max_dim }))
-                }), oid::FUNC_LIST_LENGTH_MAX_OID;
+                }) => Int64, oid::FUNC_LIST_LENGTH_MAX_OID;
             },
             "list_prepend" => Scalar {
-                vec![ListElementAny, ListAny] => BinaryFunc::ElementListConcat, oid::FUNC_LIST_PREPEND_OID;
+                vec![ListElementAny, ListAny] => BinaryFunc::ElementListConcat => ListAny, oid::FUNC_LIST_PREPEND_OID;
+            },
+            "map_keys" => Scalar {
+                vec![MapAny] => UnaryFunc::MapKeys => Array(Box::new(String)), oid::FUNC_MAP_KEYS_OID;
+            },
+            "map_length" => Scalar {
+                vec![MapAny] => UnaryFunc::MapLength => Int64, oid::FUNC_MAP_LENGTH_OID;
+            },
+            "map_values" => Scalar {
+                vec![MapAny] => UnaryFunc::MapValues => ArrayAny, oid::FUNC_MAP_VALUES_OID;
             },
             "mz_cluster_id" => Scalar {
-                params!() => Operation::nullary(mz_cluster_id), oid::FUNC_MZ_CLUSTER_ID_OID;
+                params!() => Operation::nullary(mz_cluster_id) => Uuid, oid::FUNC_MZ_CLUSTER_ID_OID;
             },
             "mz_logical_timestamp" => Scalar {
-                params!() => NullaryFunc::MzLogicalTimestamp, oid::FUNC_MZ_LOGICAL_TIMESTAMP_OID;
+                params!() => NullaryFunc::MzLogicalTimestamp => DecimalAny, oid::FUNC_MZ_LOGICAL_TIMESTAMP_OID;
+            },
+            // Deprecated alias for `mz_logical_timestamp`. See
+            // `DEPRECATED_FUNC_ALIASES`.
+            "mz_now" => Scalar {
+                params!() => NullaryFunc::MzLogicalTimestamp => DecimalAny, oid::FUNC_MZ_NOW_OID;
             },
             "mz_version" => Scalar {
                 params!() => Operation::nullary(|ecx| {
                     let version = ecx.catalog().config().build_info.human_version();
                     Ok(HirScalarExpr::literal(Datum::String(&version), ScalarType::String))
-                }), oid::FUNC_MZ_VERSION_OID;
+                }) => String, oid::FUNC_MZ_VERSION_OID;
+            },
+            "mz_version_num" => Scalar {
+                params!() => Operation::nullary(|ecx| {
+                    let version_num = ecx.catalog().config().build_info.version_num();
+                    Ok(HirScalarExpr::literal(Datum::Int32(version_num), ScalarType::Int32))
+                }) => Int32, oid::FUNC_MZ_VERSION_NUM_OID;
             },
             "regexp_extract" => Table {
                 params!(String, String) => Operation::binary(move |_ecx, regex, haystack| {
@@ -1891,7 +2241,7 @@ lazy_static! {
                         exprs: vec![haystack],
                         column_names,
                     })
-                }), oid::FUNC_REGEXP_EXTRACT_OID;
+                }) => ReturnType::set_of_record(), oid::FUNC_REGEXP_EXTRACT_OID;
             },
             "repeat" => Table {
                 params!(Int64) => Operation::unary(move |ecx, n| {
@@ -1901,7 +2251,7 @@ lazy_static! {
                         exprs: vec![n],
                         column_names: vec![]
                     })
-                }), oid::FUNC_REPEAT_OID;
+                }) => ReturnType::set_of_record(), oid::FUNC_REPEAT_OID;
             },
             "unnest" => Table {
                 vec![ArrayAny] => Operation::unary(move |ecx, e| {
@@ -1911,7 +2261,7 @@ lazy_static! {
                         exprs: vec![e],
                         column_names: vec![Some("unnest".into())],
                     })
-                }), 2331;
+                }) => ReturnType::set_of(ListElementAny.into()), 2331;
                 vec![ListAny] => Operation::unary(move |ecx, e| {
                     let el_typ =  ecx.scalar_type(&e).unwrap_list_element_type().clone();
                     Ok(TableFuncPlan {
@@ -1919,7 +2269,7 @@ lazy_static! {
                         exprs: vec![e],
                         column_names: vec![Some("unnest".into())],
                     })
-                }), oid::FUNC_UNNEST_LIST_OID;
+                }) => ReturnType::set_of(ListElementAny.into()), oid::FUNC_UNNEST_LIST_OID;
             }
         }
     };
@@ -1930,10 +2280,10 @@ lazy_static! {
         use ScalarType::*;
         builtins! {
             "mz_all" => Aggregate {
-                params!(Any) => AggregateFunc::All, oid::FUNC_MZ_ALL_OID;
+                params!(Any) => AggregateFunc::All => Bool, oid::FUNC_MZ_ALL_OID;
             },
             "mz_any" => Aggregate {
-                params!(Any) => AggregateFunc::Any, oid::FUNC_MZ_ANY_OID;
+                params!(Any) => AggregateFunc::Any => Bool, oid::FUNC_MZ_ANY_OID;
             },
             "mz_avg_promotion" => Scalar {
                 // Promotes a numeric type to the smallest fractional type that
@@ -1941,15 +2291,15 @@ lazy_static! {
                 // aggregate function, so that the avg of an integer column does
                 // not get truncated to an integer, which would be surprising to
                 // users (#549).
-                params!(Float32) => Operation::identity(), oid::FUNC_MZ_AVG_PROMOTION_F32_OID;
-                params!(Float64) => Operation::identity(), oid::FUNC_MZ_AVG_PROMOTION_F64_OID;
-                params!(DecimalAny) => Operation::identity(), oid::FUNC_MZ_AVG_PROMOTION_DECIMAL_OID;
+                params!(Float32) => Operation::identity() => Float32, oid::FUNC_MZ_AVG_PROMOTION_F32_OID;
+                params!(Float64) => Operation::identity() => Float64, oid::FUNC_MZ_AVG_PROMOTION_F64_OID;
+                params!(DecimalAny) => Operation::identity() => DecimalAny, oid::FUNC_MZ_AVG_PROMOTION_DECIMAL_OID;
                 params!(Int32) => Operation::unary(|ecx, e| {
                       typeconv::plan_cast(
                           "internal.avg_promotion", ecx, CastContext::Explicit,
                           e, &ScalarType::Decimal(10, 0),
                       )
-                }), oid::FUNC_MZ_AVG_PROMOTION_I32_OID;
+                }) => DecimalAny, oid::FUNC_MZ_AVG_PROMOTION_I32_OID;
             },
             "mz_classify_object_id" => Scalar {
                 params!(String) => sql_op!(
@@ -1958,14 +2308,30 @@ lazy_static! {
                         WHEN $1 LIKE 's%' THEN 'system'
                         WHEN $1 like 't%' THEN 'temp'
                     END"
-                ), oid::FUNC_MZ_CLASSIFY_OBJECT_ID_OID;
+                ) => String, oid::FUNC_MZ_CLASSIFY_OBJECT_ID_OID;
             },
             "mz_is_materialized" => Scalar {
-                params!(String) => sql_op!("EXISTS (SELECT 1 FROM mz_indexes WHERE on_id = $1)"),
+                params!(String) => sql_op!("EXISTS (SELECT 1 FROM mz_indexes WHERE on_id = $1)") => Bool,
                     oid::FUNC_MZ_IS_MATERIALIZED_OID;
             },
             "mz_render_typemod" => Scalar {
-                params!(Oid, Int32) => BinaryFunc::MzRenderTypemod, oid::FUNC_MZ_RENDER_TYPEMOD_OID;
+                params!(Oid, Int32) => BinaryFunc::MzRenderTypemod => String, oid::FUNC_MZ_RENDER_TYPEMOD_OID;
+            },
+            "mz_sleep" => Scalar {
+                // Non-deterministic, as it depends on wall-clock time, so it is
+                // never constant folded. Sleeping in a view's dataflow would
+                // stall every other operator on the worker, so only one-shot
+                // queries may sleep. Each call is capped at a minute, but a
+                // query sleeps once per row that calls it.
+                params!(Float64) => Operation::unary(|ecx, e| {
+                    match ecx.qcx.lifetime {
+                        QueryLifetime::OneShot => Ok(e.call_unary(UnaryFunc::Sleep)),
+                        QueryLifetime::Static => bail!("mz_sleep cannot be used in static queries"),
+                    }
+                }) => TimestampTz, oid::FUNC_MZ_SLEEP_OID;
+            },
+            "mz_uuid_v5" => Scalar {
+                params!(Uuid, String) => BinaryFunc::MzUuidV5 => Uuid, oid::FUNC_MZ_UUID_V5_OID;
             }
         }
     };
@@ -1981,6 +2347,127 @@ fn plan_current_timestamp(ecx: &ExprContext, name: &str) -> Result<HirScalarExpr
     }
 }
 
+/// The kinds of objects whose privileges the `has_*_privilege` functions
+/// report.
+#[derive(Clone, Copy)]
+enum PrivilegeObject {
+    Table,
+    Schema,
+    Database,
+}
+
+impl PrivilegeObject {
+    /// The privileges that PostgreSQL grants on this kind of object.
+    fn privileges(self) -> &'static [&'static str] {
+        match self {
+            PrivilegeObject::Table => &[
+                "SELECT",
+                "INSERT",
+                "UPDATE",
+                "DELETE",
+                "TRUNCATE",
+                "REFERENCES",
+                "TRIGGER",
+            ],
+            PrivilegeObject::Schema => &["CREATE", "USAGE"],
+            PrivilegeObject::Database => &["CREATE", "CONNECT", "TEMPORARY", "TEMP"],
+        }
+    }
+
+    /// The `pg_catalog` relation that lists this kind of object by OID.
+    fn catalog_relation(self) -> &'static str {
+        match self {
+            PrivilegeObject::Table => "pg_catalog.pg_class",
+            PrivilegeObject::Schema => "pg_catalog.pg_namespace",
+            PrivilegeObject::Database => "pg_catalog.pg_database",
+        }
+    }
+}
+
+/// Plans one of the `has_*_privilege` functions, whose arguments are an
+/// optional user, an `object` that is specified by name or OID, and a
+/// comma-separated list of privileges.
+///
+/// Materialize does not yet have privileges, so the functions report that
+/// every user has every privilege on every object, unless any argument is
+/// null. Users and objects that are specified by constant names must exist, and
+/// constant privileges must be ones that PostgreSQL grants on the kind of
+/// object. As in PostgreSQL, objects that are specified by an OID that does not
+/// exist yield null.
+fn plan_has_privilege(
+    ecx: &ExprContext,
+    object: PrivilegeObject,
+    exprs: Vec<HirScalarExpr>,
+) -> Result<HirScalarExpr, anyhow::Error> {
+    let (user, obj, privileges) = match &exprs[..] {
+        [user, obj, privileges] => (Some(user), obj, privileges),
+        [obj, privileges] => (None, obj, privileges),
+        _ => unreachable!("has_*_privilege takes two or three arguments"),
+    };
+    let literal_name = |e: &HirScalarExpr| match ecx.scalar_type(e) {
+        ScalarType::String => e.clone().into_literal_string(),
+        _ => None,
+    };
+    if let Some(name) = user.and_then(literal_name) {
+        ecx.catalog().resolve_role(&name)?;
+    }
+    if let Some(name) = literal_name(obj) {
+        let name = match sql_parser::parser::parse_expr(&name) {
+            Ok(Expr::Identifier(names)) => UnresolvedObjectName(names),
+            _ => bail!("invalid name syntax: {}", name),
+        };
+        match object {
+            PrivilegeObject::Table => {
+                ecx.qcx.scx.resolve_item(name)?;
+            }
+            PrivilegeObject::Schema => {
+                ecx.qcx.scx.resolve_schema(name)?;
+            }
+            PrivilegeObject::Database => {
+                ecx.qcx.scx.resolve_database(name)?;
+            }
+        }
+    }
+    if let Some(privileges) = privileges.clone().into_literal_string() {
+        for privilege in privileges.split(',') {
+            let privilege = privilege.trim();
+            let upper = privilege.to_uppercase();
+            let name = match upper.strip_suffix("WITH GRANT OPTION") {
+                Some(name) => name.trim_end(),
+                None => &upper,
+            };
+            if !object.privileges().contains(&name) {
+                bail!("unrecognized privilege type: \"{}\"", privilege);
+            }
+        }
+    }
+    let granted = match ecx.scalar_type(obj) {
+        ScalarType::Oid => {
+            let exists = sql_parser::parser::parse_expr(format!(
+                "EXISTS (SELECT 1 FROM {} WHERE oid = $1)",
+                object.catalog_relation()
+            ))
+            .expect("privilege object check failed to parse");
+            HirScalarExpr::If {
+                cond: Box::new(plan_sql_impl(ecx, &exists, vec![obj.clone()])?),
+                then: Box::new(HirScalarExpr::literal_true()),
+                els: Box::new(HirScalarExpr::literal_null(ScalarType::Bool)),
+            }
+        }
+        _ => HirScalarExpr::literal_true(),
+    };
+    let any_null = exprs
+        .into_iter()
+        .map(|e| e.call_unary(UnaryFunc::IsNull))
+        .fold1(|a, b| a.call_binary(b, BinaryFunc::Or))
+        .unwrap();
+    Ok(HirScalarExpr::If {
+        cond: Box::new(any_null),
+        then: Box::new(HirScalarExpr::literal_null(ScalarType::Bool)),
+        els: Box::new(granted),
+    })
+}
+
 fn mz_cluster_id(ecx: &ExprContext) -> Result<HirScalarExpr, anyhow::Error> {
     Ok(HirScalarExpr::literal(
         Datum::from(ecx.catalog().config().cluster_id),
@@ -2002,6 +2489,88 @@ fn array_to_string(
     })
 }
 
+/// Plans the arguments of `lag` or `lead` as a record of the value, the offset,
+/// which defaults to one, and the default, which defaults to null.
+fn lag_lead_record(
+    ecx: &ExprContext,
+    func: LagLeadType,
+    mut exprs: Vec<HirScalarExpr>,
+) -> Result<(HirScalarExpr, LagLeadType), anyhow::Error> {
+    if exprs.len() < 2 {
+        exprs.push(HirScalarExpr::literal(Datum::Int32(1), ScalarType::Int32));
+    }
+    if exprs.len() < 3 {
+        let typ = ecx.scalar_type(&exprs[0]);
+        exprs.push(HirScalarExpr::literal_null(typ));
+    }
+    let e = HirScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: vec![
+                ColumnName::from("value"),
+                ColumnName::from("offset"),
+                ColumnName::from("default"),
+            ],
+        },
+        exprs,
+    };
+    Ok((e, func))
+}
+
+/// Builds the record of a fraction and a value that the percentile aggregates
+/// receive. The planner orders the records by their values as specified by the
+/// `WITHIN GROUP` clause.
+///
+/// The aggregates take the fraction from any one of the records, so it must not
+/// vary between them. Fractions that are out of range are reported here when
+/// they are constant, and otherwise produce null.
+fn percentile_record(
+    name: &str,
+    mut fraction: HirScalarExpr,
+    value: HirScalarExpr,
+) -> Result<HirScalarExpr, anyhow::Error> {
+    let mut varies = false;
+    fraction.visit_columns(0, &mut |depth, col| {
+        if col.level == depth {
+            varies = true;
+        }
+    });
+    if varies {
+        bail!(
+            "the fraction of {} must not refer to the aggregated columns",
+            name
+        );
+    }
+    if let Some(fraction) = fraction.clone().into_literal_float64() {
+        if !(0.0..=1.0).contains(&fraction) {
+            bail!("percentile value {} is not between 0 and 1", fraction);
+        }
+    }
+    Ok(HirScalarExpr::CallVariadic {
+        func: VariadicFunc::RecordCreate {
+            field_names: vec![ColumnName::from("fraction"), ColumnName::from("value")],
+        },
+        exprs: vec![fraction, value],
+    })
+}
+
+fn regexp_split_to_table(
+    _ecx: &ExprContext,
+    exprs: Vec<HirScalarExpr>,
+) -> Result<TableFuncPlan, anyhow::Error> {
+    // `regexp_split_to_table` unnests the array that `regexp_split_to_array`
+    // would produce for the same arguments.
+    Ok(TableFuncPlan {
+        func: TableFunc::UnnestArray {
+            el_typ: ScalarType::String,
+        },
+        exprs: vec![HirScalarExpr::CallVariadic {
+            func: VariadicFunc::RegexpSplitToArray,
+            exprs,
+        }],
+        column_names: vec![Some("regexp_split_to_table".into())],
+    })
+}
+
 lazy_static! {
     /// Correlates an operator with all of its implementations.
     static ref OP_IMPLS: HashMap<&'static str, Func> = {
@@ -2036,77 +2605,77 @@ lazy_static! {
                     // we accept explicitly-typed arguments of any type, but try
                     // to coerce unknown-type arguments as `Float64`.
                     typeconv::plan_coerce(ecx, exprs.into_element(), &ScalarType::Float64)
-                }), oid::OP_UNARY_PLUS_OID;
-                params!(Int32, Int32) => AddInt32, 551;
-                params!(Int64, Int64) => AddInt64, 684;
-                params!(Float32, Float32) => AddFloat32, 586;
-                params!(Float64, Float64) => AddFloat64, 591;
+                }) => Any, oid::OP_UNARY_PLUS_OID;
+                params!(Int32, Int32) => AddInt32 => Int32, 551;
+                params!(Int64, Int64) => AddInt64 => Int64, 684;
+                params!(Float32, Float32) => AddFloat32 => Float32, 586;
+                params!(Float64, Float64) => AddFloat64 => Float64, 591;
                 params!(DecimalAny, DecimalAny) => {
                     Operation::binary(|ecx, lhs, rhs| {
                         let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                         Ok(lexpr.call_binary(rexpr, AddDecimal))
                     })
-                }, 1758;
-                params!(Interval, Interval) => AddInterval, 1337;
-                params!(Timestamp, Interval) => AddTimestampInterval, 2066;
+                } => DecimalAny, 1758;
+                params!(Interval, Interval) => AddInterval => Interval, 1337;
+                params!(Timestamp, Interval) => AddTimestampInterval => Timestamp, 2066;
                 params!(Interval, Timestamp) => {
                     Operation::binary(|_ecx, lhs, rhs| Ok(rhs.call_binary(lhs, AddTimestampInterval)))
-                }, 2066;
-                params!(TimestampTz, Interval) => AddTimestampTzInterval, 1327;
+                } => Timestamp, 2066;
+                params!(TimestampTz, Interval) => AddTimestampTzInterval => TimestampTz, 1327;
                 params!(Interval, TimestampTz) => {
                     Operation::binary(|_ecx, lhs, rhs| Ok(rhs.call_binary(lhs, AddTimestampTzInterval)))
-                }, 2554;
-                params!(Date, Interval) => AddDateInterval, 1076;
+                } => TimestampTz, 2554;
+                params!(Date, Interval) => AddDateInterval => Timestamp, 1076;
                 params!(Interval, Date) => {
                     Operation::binary(|_ecx, lhs, rhs| Ok(rhs.call_binary(lhs, AddDateInterval)))
-                }, 2551;
-                params!(Date, Time) => AddDateTime, 1360;
+                } => Timestamp, 2551;
+                params!(Date, Time) => AddDateTime => Timestamp, 1360;
                 params!(Time, Date) => {
                     Operation::binary(|_ecx, lhs, rhs| Ok(rhs.call_binary(lhs, AddDateTime)))
-                }, 1363;
-                params!(Time, Interval) => AddTimeInterval, 1800;
+                } => Timestamp, 1363;
+                params!(Time, Interval) => AddTimeInterval => Time, 1800;
                 params!(Interval, Time) => {
                     Operation::binary(|_ecx, lhs, rhs| Ok(rhs.call_binary(lhs, AddTimeInterval)))
-                }, 1849;
+                } => Time, 1849;
             },
             "-" => Scalar {
-                params!(Int32) => UnaryFunc::NegInt32, 558;
-                params!(Int64) => UnaryFunc::NegInt64, 484;
-                params!(Float32) => UnaryFunc::NegFloat32, 584;
-                params!(Float64) => UnaryFunc::NegFloat64, 585;
-                params!(DecimalAny) => UnaryFunc::NegDecimal, 1751;
-                params!(Interval) => UnaryFunc::NegInterval, 1336;
-                params!(Int32, Int32) => SubInt32, 555;
-                params!(Int64, Int64) => SubInt64, 685;
-                params!(Float32, Float32) => SubFloat32, 587;
-                params!(Float64, Float64) => SubFloat64, 592;
+                params!(Int32) => UnaryFunc::NegInt32 => Int32, 558;
+                params!(Int64) => UnaryFunc::NegInt64 => Int64, 484;
+                params!(Float32) => UnaryFunc::NegFloat32 => Float32, 584;
+                params!(Float64) => UnaryFunc::NegFloat64 => Float64, 585;
+                params!(DecimalAny) => UnaryFunc::NegDecimal => DecimalAny, 1751;
+                params!(Interval) => UnaryFunc::NegInterval => Interval, 1336;
+                params!(Int32, Int32) => SubInt32 => Int32, 555;
+                params!(Int64, Int64) => SubInt64 => Int64, 685;
+                params!(Float32, Float32) => SubFloat32 => Float32, 587;
+                params!(Float64, Float64) => SubFloat64 => Float64, 592;
                 params!(DecimalAny, DecimalAny) => Operation::binary(|ecx, lhs, rhs| {
                     let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                     Ok(lexpr.call_binary(rexpr, SubDecimal))
-                }), 1759;
-                params!(Interval, Interval) => SubInterval, 1338;
-                params!(Timestamp, Timestamp) => SubTimestamp, 2067;
-                params!(TimestampTz, TimestampTz) => SubTimestampTz, 1328;
-                params!(Timestamp, Interval) => SubTimestampInterval, 2068;
-                params!(TimestampTz, Interval) => SubTimestampTzInterval, 1329;
-                params!(Date, Date) => SubDate, 1099;
-                params!(Date, Interval) => SubDateInterval, 1077;
-                params!(Time, Time) => SubTime, 1399;
-                params!(Time, Interval) => SubTimeInterval, 1801;
-                params!(Jsonb, Int64) => JsonbDeleteInt64, 3286;
-                params!(Jsonb, String) => JsonbDeleteString, 3285;
+                }) => DecimalAny, 1759;
+                params!(Interval, Interval) => SubInterval => Interval, 1338;
+                params!(Timestamp, Timestamp) => SubTimestamp => Interval, 2067;
+                params!(TimestampTz, TimestampTz) => SubTimestampTz => Interval, 1328;
+                params!(Timestamp, Interval) => SubTimestampInterval => Timestamp, 2068;
+                params!(TimestampTz, Interval) => SubTimestampTzInterval => TimestampTz, 1329;
+                params!(Date, Date) => SubDate => Interval, 1099;
+                params!(Date, Interval) => SubDateInterval => Timestamp, 1077;
+                params!(Time, Time) => SubTime => Interval, 1399;
+                params!(Time, Interval) => SubTimeInterval => Time, 1801;
+                params!(Jsonb, Int64) => JsonbDeleteInt64 => Jsonb, 3286;
+                params!(Jsonb, String) => JsonbDeleteString => Jsonb, 3285;
                 // TODO(jamii) there should be corresponding overloads for
                 // Array(Int64) and Array(String)
             },
             "*" => Scalar {
-                params!(Int32, Int32) => MulInt32, 514;
-                params!(Int64, Int64) => MulInt64, 686;
-                params!(Float32, Float32) => MulFloat32, 589;
-                params!(Float64, Float64) => MulFloat64, 594;
-                params!(Interval, Float64) => MulInterval, 1583;
+                params!(Int32, Int32) => MulInt32 => Int32, 514;
+                params!(Int64, Int64) => MulInt64 => Int64, 686;
+                params!(Float32, Float32) => MulFloat32 => Float32, 589;
+                params!(Float64, Float64) => MulFloat64 => Float64, 594;
+                params!(Interval, Float64) => MulInterval => Interval, 1583;
                 params!(Float64, Interval) => {
                     Operation::binary(|_ecx, lhs, rhs| Ok(rhs.call_binary(lhs, MulInterval)))
-                }, 1584;
+                } => Interval, 1584;
                 params!(DecimalAny, DecimalAny) => Operation::binary(|ecx, lhs, rhs| {
                     use std::cmp::*;
                     let (_, s1) = ecx.scalar_type(&lhs).unwrap_decimal_parts();
@@ -2115,14 +2684,14 @@ lazy_static! {
                     let si = s1 + s2;
                     let expr = lhs.call_binary(rhs, MulDecimal);
                     Ok(rescale_decimal(expr, si, so))
-                }), 1760;
+                }) => DecimalAny, 1760;
             },
             "/" => Scalar {
-                params!(Int32, Int32) => DivInt32, 528;
-                params!(Int64, Int64) => DivInt64, 687;
-                params!(Float32, Float32) => DivFloat32, 588;
-                params!(Float64, Float64) => DivFloat64, 593;
-                params!(Interval, Float64) => DivInterval, 1585;
+                params!(Int32, Int32) => DivInt32 => Int32, 528;
+                params!(Int64, Int64) => DivInt64 => Int64, 687;
+                params!(Float32, Float32) => DivFloat32 => Float32, 588;
+                params!(Float64, Float64) => DivFloat64 => Float64, 593;
+                params!(Interval, Float64) => DivInterval => Interval, 1585;
                 params!(DecimalAny, DecimalAny) => Operation::binary(|ecx, lhs, rhs| {
                     use std::cmp::*;
                     let (_, s1) = ecx.scalar_type(&lhs).unwrap_decimal_parts();
@@ -2135,66 +2704,66 @@ lazy_static! {
                     let lhs = rescale_decimal(lhs, s1, si);
                     let expr = lhs.call_binary(rhs, DivDecimal);
                     Ok(rescale_decimal(expr, si - s2, s))
-                }), 1761;
+                }) => DecimalAny, 1761;
             },
             "%" => Scalar {
-                params!(Int32, Int32) => ModInt32, 530;
-                params!(Int64, Int64) => ModInt64, 439;
-                params!(Float32, Float32) => ModFloat32, oid::OP_MOD_F32_OID;
-                params!(Float64, Float64) => ModFloat64, oid::OP_MOD_F64_OID;
+                params!(Int32, Int32) => ModInt32 => Int32, 530;
+                params!(Int64, Int64) => ModInt64 => Int64, 439;
+                params!(Float32, Float32) => ModFloat32 => Float32, oid::OP_MOD_F32_OID;
+                params!(Float64, Float64) => ModFloat64 => Float64, oid::OP_MOD_F64_OID;
                 params!(DecimalAny, DecimalAny) => Operation::binary(|ecx, lhs, rhs| {
                     let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                     Ok(lexpr.call_binary(rexpr, ModDecimal))
-                }), 1762;
+                }) => DecimalAny, 1762;
             },
 
             // ILIKE
             "~~*" => Scalar {
-                params!(String, String) => IsLikePatternMatch { case_insensitive: true }, 1627;
+                params!(String, String) => IsLikePatternMatch { case_insensitive: true } => Bool, 1627;
             },
             "!~~*" => Scalar {
                 params!(String, String) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(lhs
                         .call_binary(rhs, IsLikePatternMatch { case_insensitive: true })
                         .call_unary(UnaryFunc::Not))
-                }), 1628;
+                }) => Bool, 1628;
             },
 
 
             // LIKE
             "~~" => Scalar {
-                params!(String, String) => IsLikePatternMatch { case_insensitive: false }, 1209;
+                params!(String, String) => IsLikePatternMatch { case_insensitive: false } => Bool, 1209;
             },
             "!~~" => Scalar {
                 params!(String, String) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(lhs
                         .call_binary(rhs, IsLikePatternMatch { case_insensitive: false })
                         .call_unary(UnaryFunc::Not))
-                }), 1210;
+                }) => Bool, 1210;
             },
 
             // REGEX
             "~" => Scalar {
-                params!(String, String) => IsRegexpMatch { case_insensitive: false }, 641;
+                params!(String, String) => IsRegexpMatch { case_insensitive: false } => Bool, 641;
             },
             "~*" => Scalar {
                 params!(String, String) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(lhs.call_binary(rhs, IsRegexpMatch { case_insensitive: true }))
-                }), 1228;
+                }) => Bool, 1228;
             },
             "!~" => Scalar {
                 params!(String, String) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(lhs
                         .call_binary(rhs, IsRegexpMatch { case_insensitive: false })
                         .call_unary(UnaryFunc::Not))
-                }), 642;
+                }) => Bool, 642;
             },
             "!~*" => Scalar {
                 params!(String, String) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(lhs
                         .call_binary(rhs, IsRegexpMatch { case_insensitive: true })
                         .call_unary(UnaryFunc::Not))
-                }), 1229;
+                }) => Bool, 1229;
             },
 
             // CONCAT
@@ -2208,7 +2777,7 @@ lazy_static! {
                         &ScalarType::String,
                     )?;
                     Ok(lhs.call_binary(rhs, TextConcat))
-                }), 2779;
+                }) => String, 2779;
                 vec![NonVecAny, Plain(String)] =>  Operation::binary(|ecx, lhs, rhs| {
                     let lhs = typeconv::plan_cast(
                         "text_concat",
@@ -2218,38 +2787,38 @@ lazy_static! {
                         &ScalarType::String,
                     )?;
                     Ok(lhs.call_binary(rhs, TextConcat))
-                }), 2780;
-                params!(String, String) => TextConcat, 654;
-                params!(Jsonb, Jsonb) => JsonbConcat, 3284;
-                params!(ListAny, ListAny) => ListListConcat, oid::OP_CONCAT_LIST_LIST_OID;
-                params!(ListAny, ListElementAny) => ListElementConcat, oid::OP_CONCAT_LIST_ELEMENT_OID;
-                params!(ListElementAny, ListAny) => ElementListConcat, oid::OP_CONCAT_ELEMENY_LIST_OID;
+                }) => String, 2780;
+                params!(String, String) => TextConcat => String, 654;
+                params!(Jsonb, Jsonb) => JsonbConcat => Jsonb, 3284;
+                params!(ListAny, ListAny) => ListListConcat => ListAny, oid::OP_CONCAT_LIST_LIST_OID;
+                params!(ListAny, ListElementAny) => ListElementConcat => ListAny, oid::OP_CONCAT_LIST_ELEMENT_OID;
+                params!(ListElementAny, ListAny) => ElementListConcat => ListAny, oid::OP_CONCAT_ELEMENY_LIST_OID;
             },
 
             //JSON and MAP
             "->" => Scalar {
-                params!(Jsonb, Int64) => JsonbGetInt64 { stringify: false }, 3212;
-                params!(Jsonb, String) => JsonbGetString { stringify: false }, 3211;
-                params!(MapAny, String) => MapGetValue, oid::OP_GET_VALUE_MAP_OID;
-                params!(MapAny, Plain(Array(Box::new(String)))) => MapGetValues, oid::OP_GET_VALUES_MAP_OID;
+                params!(Jsonb, Int64) => JsonbGetInt64 { stringify: false } => Jsonb, 3212;
+                params!(Jsonb, String) => JsonbGetString { stringify: false } => Jsonb, 3211;
+                params!(MapAny, String) => MapGetValue => Any, oid::OP_GET_VALUE_MAP_OID;
+                params!(MapAny, Plain(Array(Box::new(String)))) => MapGetValues => ArrayAny, oid::OP_GET_VALUES_MAP_OID;
             },
             "->>" => Scalar {
-                params!(Jsonb, Int64) => JsonbGetInt64 { stringify: true }, 3481;
-                params!(Jsonb, String) => JsonbGetString { stringify: true }, 3477;
+                params!(Jsonb, Int64) => JsonbGetInt64 { stringify: true } => String, 3481;
+                params!(Jsonb, String) => JsonbGetString { stringify: true } => String, 3477;
             },
             "@>" => Scalar {
-                params!(Jsonb, Jsonb) => JsonbContainsJsonb, 3246;
+                params!(Jsonb, Jsonb) => JsonbContainsJsonb => Bool, 3246;
                 params!(Jsonb, String) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(lhs.call_binary(
                         rhs.call_unary(UnaryFunc::CastStringToJsonb),
                         JsonbContainsJsonb,
                     ))
-                }), oid::OP_CONTAINS_JSONB_STRING_OID;
+                }) => Bool, oid::OP_CONTAINS_JSONB_STRING_OID;
                 params!(String, Jsonb) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(lhs.call_unary(UnaryFunc::CastStringToJsonb)
                           .call_binary(rhs, JsonbContainsJsonb))
-                }), oid::OP_CONTAINS_STRING_JSONB_OID;
-                params!(MapAny, MapAny) => MapContainsMap, oid::OP_CONTAINS_MAP_MAP_OID;
+                }) => Bool, oid::OP_CONTAINS_STRING_JSONB_OID;
+                params!(MapAny, MapAny) => MapContainsMap => Bool, oid::OP_CONTAINS_MAP_MAP_OID;
             },
             "<@" => Scalar {
                 params!(Jsonb, Jsonb) =>  Operation::binary(|_ecx, lhs, rhs| {
@@ -2257,30 +2826,30 @@ lazy_static! {
                         lhs,
                         JsonbContainsJsonb
                     ))
-                }), 3246;
+                }) => Bool, 3246;
                 params!(Jsonb, String) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(rhs.call_unary(UnaryFunc::CastStringToJsonb)
                           .call_binary(lhs, BinaryFunc::JsonbContainsJsonb))
-                }), oid::OP_CONTAINED_JSONB_STRING_OID;
+                }) => Bool, oid::OP_CONTAINED_JSONB_STRING_OID;
                 params!(String, Jsonb) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(rhs.call_binary(
                         lhs.call_unary(UnaryFunc::CastStringToJsonb),
                         BinaryFunc::JsonbContainsJsonb,
                     ))
-                }), oid::OP_CONTAINED_STRING_JSONB_OID;
+                }) => Bool, oid::OP_CONTAINED_STRING_JSONB_OID;
                 params!(MapAny, MapAny) => Operation::binary(|_ecx, lhs, rhs| {
                     Ok(rhs.call_binary(lhs, MapContainsMap))
-                }), oid::OP_CONTAINED_MAP_MAP_OID;
+                }) => Bool, oid::OP_CONTAINED_MAP_MAP_OID;
             },
             "?" => Scalar {
-                params!(Jsonb, String) => JsonbContainsString, 3247;
-                params!(MapAny, String) => MapContainsKey, oid::OP_CONTAINS_KEY_MAP_OID;
+                params!(Jsonb, String) => JsonbContainsString => Bool, 3247;
+                params!(MapAny, String) => MapContainsKey => Bool, oid::OP_CONTAINS_KEY_MAP_OID;
             },
             "?&" => Scalar {
-                params!(MapAny, Plain(Array(Box::new(String)))) => MapContainsAllKeys, oid::OP_CONTAINS_ALL_KEYS_MAP_OID;
+                params!(MapAny, Plain(Array(Box::new(String)))) => MapContainsAllKeys => Bool, oid::OP_CONTAINS_ALL_KEYS_MAP_OID;
             },
             "?|" => Scalar {
-                params!(MapAny, Plain(Array(Box::new(String)))) => MapContainsAnyKeys, oid::OP_CONTAINS_ANY_KEYS_MAP_OID;
+                params!(MapAny, Plain(Array(Box::new(String)))) => MapContainsAnyKeys => Bool, oid::OP_CONTAINS_ANY_KEYS_MAP_OID;
             },
             // COMPARISON OPS
             // n.b. Decimal impls are separated from other types because they
@@ -2291,22 +2860,22 @@ lazy_static! {
                         let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                         Ok(lexpr.call_binary(rexpr, BinaryFunc::Lt))
                     })
-                }, 1754;
-                params!(Bool, Bool) => BinaryFunc::Lt, 58;
-                params!(Int32, Int32) => BinaryFunc::Lt, 97;
-                params!(Int64, Int64) => BinaryFunc::Lt, 412;
-                params!(Float32, Float32) => BinaryFunc::Lt, 622;
-                params!(Float64, Float64) => BinaryFunc::Lt, 672;
-                params!(Oid, Oid) => BinaryFunc::Lt, 609;
-                params!(Date, Date) => BinaryFunc::Lt, 1095;
-                params!(Time, Time) => BinaryFunc::Lt, 1110;
-                params!(Timestamp, Timestamp) => BinaryFunc::Lt, 2062;
-                params!(TimestampTz, TimestampTz) => BinaryFunc::Lt, 1322;
-                params!(Uuid, Uuid) => BinaryFunc::Lt, 2974;
-                params!(Interval, Interval) => BinaryFunc::Lt, 1332;
-                params!(Bytes, Bytes) => BinaryFunc::Lt, 1957;
-                params!(String, String) => BinaryFunc::Lt, 664;
-                params!(Jsonb, Jsonb) => BinaryFunc::Lt, 3242;
+                } => Bool, 1754;
+                params!(Bool, Bool) => BinaryFunc::Lt => Bool, 58;
+                params!(Int32, Int32) => BinaryFunc::Lt => Bool, 97;
+                params!(Int64, Int64) => BinaryFunc::Lt => Bool, 412;
+                params!(Float32, Float32) => BinaryFunc::Lt => Bool, 622;
+                params!(Float64, Float64) => BinaryFunc::Lt => Bool, 672;
+                params!(Oid, Oid) => BinaryFunc::Lt => Bool, 609;
+                params!(Date, Date) => BinaryFunc::Lt => Bool, 1095;
+                params!(Time, Time) => BinaryFunc::Lt => Bool, 1110;
+                params!(Timestamp, Timestamp) => BinaryFunc::Lt => Bool, 2062;
+                params!(TimestampTz, TimestampTz) => BinaryFunc::Lt => Bool, 1322;
+                params!(Uuid, Uuid) => BinaryFunc::Lt => Bool, 2974;
+                params!(Interval, Interval) => BinaryFunc::Lt => Bool, 1332;
+                params!(Bytes, Bytes) => BinaryFunc::Lt => Bool, 1957;
+                params!(String, String) => BinaryFunc::Lt => Bool, 664;
+                params!(Jsonb, Jsonb) => BinaryFunc::Lt => Bool, 3242;
             },
             "<=" => Scalar {
                 params!(DecimalAny, DecimalAny) => {
@@ -2314,22 +2883,22 @@ lazy_static! {
                         let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                         Ok(lexpr.call_binary(rexpr, BinaryFunc::Lte))
                     })
-                }, 1755;
-                params!(Bool, Bool) => BinaryFunc::Lte, 1694;
-                params!(Int32, Int32) => BinaryFunc::Lte, 523;
-                params!(Int64, Int64) => BinaryFunc::Lte, 414;
-                params!(Float32, Float32) => BinaryFunc::Lte, 624;
-                params!(Float64, Float64) => BinaryFunc::Lte, 673;
-                params!(Oid, Oid) => BinaryFunc::Lte, 611;
-                params!(Date, Date) => BinaryFunc::Lte, 1096;
-                params!(Time, Time) => BinaryFunc::Lte, 1111;
-                params!(Timestamp, Timestamp) => BinaryFunc::Lte, 2063;
-                params!(TimestampTz, TimestampTz) => BinaryFunc::Lte, 1323;
-                params!(Uuid, Uuid) => BinaryFunc::Lte, 2976;
-                params!(Interval, Interval) => BinaryFunc::Lte, 1333;
-                params!(Bytes, Bytes) => BinaryFunc::Lte, 1958;
-                params!(String, String) => BinaryFunc::Lte, 665;
-                params!(Jsonb, Jsonb) => BinaryFunc::Lte, 3244;
+                } => Bool, 1755;
+                params!(Bool, Bool) => BinaryFunc::Lte => Bool, 1694;
+                params!(Int32, Int32) => BinaryFunc::Lte => Bool, 523;
+                params!(Int64, Int64) => BinaryFunc::Lte => Bool, 414;
+                params!(Float32, Float32) => BinaryFunc::Lte => Bool, 624;
+                params!(Float64, Float64) => BinaryFunc::Lte => Bool, 673;
+                params!(Oid, Oid) => BinaryFunc::Lte => Bool, 611;
+                params!(Date, Date) => BinaryFunc::Lte => Bool, 1096;
+                params!(Time, Time) => BinaryFunc::Lte => Bool, 1111;
+                params!(Timestamp, Timestamp) => BinaryFunc::Lte => Bool, 2063;
+                params!(TimestampTz, TimestampTz) => BinaryFunc::Lte => Bool, 1323;
+                params!(Uuid, Uuid) => BinaryFunc::Lte => Bool, 2976;
+                params!(Interval, Interval) => BinaryFunc::Lte => Bool, 1333;
+                params!(Bytes, Bytes) => BinaryFunc::Lte => Bool, 1958;
+                params!(String, String) => BinaryFunc::Lte => Bool, 665;
+                params!(Jsonb, Jsonb) => BinaryFunc::Lte => Bool, 3244;
             },
             ">" => Scalar {
                 params!(DecimalAny, DecimalAny) => {
@@ -2337,22 +2906,22 @@ lazy_static! {
                         let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                         Ok(lexpr.call_binary(rexpr, BinaryFunc::Gt))
                     })
-                }, 1756;
-                params!(Bool, Bool) => BinaryFunc::Gt, 59;
-                params!(Int32, Int32) => BinaryFunc::Gt, 521;
-                params!(Int64, Int64) => BinaryFunc::Gt, 413;
-                params!(Float32, Float32) => BinaryFunc::Gt, 623;
-                params!(Float64, Float64) => BinaryFunc::Gt, 674;
-                params!(Oid, Oid) => BinaryFunc::Gt, 610;
-                params!(Date, Date) => BinaryFunc::Gt, 1097;
-                params!(Time, Time) => BinaryFunc::Gt, 1112;
-                params!(Timestamp, Timestamp) => BinaryFunc::Gt, 2064;
-                params!(TimestampTz, TimestampTz) => BinaryFunc::Gt, 1324;
-                params!(Uuid, Uuid) => BinaryFunc::Gt, 2975;
-                params!(Interval, Interval) => BinaryFunc::Gt, 1334;
-                params!(Bytes, Bytes) => BinaryFunc::Gt, 1959;
-                params!(String, String) => BinaryFunc::Gt, 666;
-                params!(Jsonb, Jsonb) => BinaryFunc::Gt, 3243;
+                } => Bool, 1756;
+                params!(Bool, Bool) => BinaryFunc::Gt => Bool, 59;
+                params!(Int32, Int32) => BinaryFunc::Gt => Bool, 521;
+                params!(Int64, Int64) => BinaryFunc::Gt => Bool, 413;
+                params!(Float32, Float32) => BinaryFunc::Gt => Bool, 623;
+                params!(Float64, Float64) => BinaryFunc::Gt => Bool, 674;
+                params!(Oid, Oid) => BinaryFunc::Gt => Bool, 610;
+                params!(Date, Date) => BinaryFunc::Gt => Bool, 1097;
+                params!(Time, Time) => BinaryFunc::Gt => Bool, 1112;
+                params!(Timestamp, Timestamp) => BinaryFunc::Gt => Bool, 2064;
+                params!(TimestampTz, TimestampTz) => BinaryFunc::Gt => Bool, 1324;
+                params!(Uuid, Uuid) => BinaryFunc::Gt => Bool, 2975;
+                params!(Interval, Interval) => BinaryFunc::Gt => Bool, 1334;
+                params!(Bytes, Bytes) => BinaryFunc::Gt => Bool, 1959;
+                params!(String, String) => BinaryFunc::Gt => Bool, 666;
+                params!(Jsonb, Jsonb) => BinaryFunc::Gt => Bool, 3243;
             },
             ">=" => Scalar {
                 params!(DecimalAny, DecimalAny) => {
@@ -2360,22 +2929,22 @@ lazy_static! {
                         let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                         Ok(lexpr.call_binary(rexpr, BinaryFunc::Gte))
                     })
-                }, 1757;
-                params!(Bool, Bool) => BinaryFunc::Gte, 1695;
-                params!(Int32, Int32) => BinaryFunc::Gte, 525;
-                params!(Int64, Int64) => BinaryFunc::Gte, 415;
-                params!(Float32, Float32) => BinaryFunc::Gte, 625;
-                params!(Float64, Float64) => BinaryFunc::Gte, 675;
-                params!(Oid, Oid) => BinaryFunc::Gte, 612;
-                params!(Date, Date) => BinaryFunc::Gte, 1098;
-                params!(Time, Time) => BinaryFunc::Gte, 1113;
-                params!(Timestamp, Timestamp) => BinaryFunc::Gte, 2065;
-                params!(TimestampTz, TimestampTz) => BinaryFunc::Gte, 1325;
-                params!(Uuid, Uuid) => BinaryFunc::Gte, 2977;
-                params!(Interval, Interval) => BinaryFunc::Gte, 1335;
-                params!(Bytes, Bytes) => BinaryFunc::Gte, 1960;
-                params!(String, String) => BinaryFunc::Gte, 667;
-                params!(Jsonb, Jsonb) => BinaryFunc::Gte, 3245;
+                } => Bool, 1757;
+                params!(Bool, Bool) => BinaryFunc::Gte => Bool, 1695;
+                params!(Int32, Int32) => BinaryFunc::Gte => Bool, 525;
+                params!(Int64, Int64) => BinaryFunc::Gte => Bool, 415;
+                params!(Float32, Float32) => BinaryFunc::Gte => Bool, 625;
+                params!(Float64, Float64) => BinaryFunc::Gte => Bool, 675;
+                params!(Oid, Oid) => BinaryFunc::Gte => Bool, 612;
+                params!(Date, Date) => BinaryFunc::Gte => Bool, 1098;
+                params!(Time, Time) => BinaryFunc::Gte => Bool, 1113;
+                params!(Timestamp, Timestamp) => BinaryFunc::Gte => Bool, 2065;
+                params!(TimestampTz, TimestampTz) => BinaryFunc::Gte => Bool, 1325;
+                params!(Uuid, Uuid) => BinaryFunc::Gte => Bool, 2977;
+                params!(Interval, Interval) => BinaryFunc::Gte => Bool, 1335;
+                params!(Bytes, Bytes) => BinaryFunc::Gte => Bool, 1960;
+                params!(String, String) => BinaryFunc::Gte => Bool, 667;
+                params!(Jsonb, Jsonb) => BinaryFunc::Gte => Bool, 3245;
             },
             "=" => Scalar {
                 params!(DecimalAny, DecimalAny) => {
@@ -2383,22 +2952,22 @@ lazy_static! {
                         let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                         Ok(lexpr.call_binary(rexpr, BinaryFunc::Eq))
                     })
-                }, 1752;
-                params!(Bool, Bool) => BinaryFunc::Eq, 91;
-                params!(Int32, Int32) => BinaryFunc::Eq, 96;
-                params!(Int64, Int64) => BinaryFunc::Eq, 410;
-                params!(Float32, Float32) => BinaryFunc::Eq, 620;
-                params!(Float64, Float64) => BinaryFunc::Eq, 670;
-                params!(Oid, Oid) => BinaryFunc::Eq, 607;
-                params!(Date, Date) => BinaryFunc::Eq, 1093;
-                params!(Time, Time) => BinaryFunc::Eq, 1108;
-                params!(Timestamp, Timestamp) => BinaryFunc::Eq, 2060;
-                params!(TimestampTz, TimestampTz) => BinaryFunc::Eq, 1320;
-                params!(Uuid, Uuid) => BinaryFunc::Eq, 2972;
-                params!(Interval, Interval) => BinaryFunc::Eq, 1330;
-                params!(Bytes, Bytes) => BinaryFunc::Eq, 1955;
-                params!(String, String) => BinaryFunc::Eq, 98;
-                params!(Jsonb, Jsonb) => BinaryFunc::Eq, 3240;
+                } => Bool, 1752;
+                params!(Bool, Bool) => BinaryFunc::Eq => Bool, 91;
+                params!(Int32, Int32) => BinaryFunc::Eq => Bool, 96;
+                params!(Int64, Int64) => BinaryFunc::Eq => Bool, 410;
+                params!(Float32, Float32) => BinaryFunc::Eq => Bool, 620;
+                params!(Float64, Float64) => BinaryFunc::Eq => Bool, 670;
+                params!(Oid, Oid) => BinaryFunc::Eq => Bool, 607;
+                params!(Date, Date) => BinaryFunc::Eq => Bool, 1093;
+                params!(Time, Time) => BinaryFunc::Eq => Bool, 1108;
+                params!(Timestamp, Timestamp) => BinaryFunc::Eq => Bool, 2060;
+                params!(TimestampTz, TimestampTz) => BinaryFunc::Eq => Bool, 1320;
+                params!(Uuid, Uuid) => BinaryFunc::Eq => Bool, 2972;
+                params!(Interval, Interval) => BinaryFunc::Eq => Bool, 1330;
+                params!(Bytes, Bytes) => BinaryFunc::Eq => Bool, 1955;
+                params!(String, String) => BinaryFunc::Eq => Bool, 98;
+                params!(Jsonb, Jsonb) => BinaryFunc::Eq => Bool, 3240;
             },
             "<>" => Scalar {
                 params!(DecimalAny, DecimalAny) => {
@@ -2406,22 +2975,22 @@ lazy_static! {
                         let (lexpr, rexpr) = rescale_decimals_to_same(ecx, lhs, rhs);
                         Ok(lexpr.call_binary(rexpr, BinaryFunc::NotEq))
                     })
-                }, 1753;
-                params!(Bool, Bool) => BinaryFunc::NotEq, 85;
-                params!(Int32, Int32) => BinaryFunc::NotEq, 518;
-                params!(Int64, Int64) => BinaryFunc::NotEq, 411;
-                params!(Float32, Float32) => BinaryFunc::NotEq, 621;
-                params!(Float64, Float64) => BinaryFunc::NotEq, 671;
-                params!(Oid, Oid) => BinaryFunc::NotEq, 608;
-                params!(Date, Date) => BinaryFunc::NotEq, 1094;
-                params!(Time, Time) => BinaryFunc::NotEq, 1109;
-                params!(Timestamp, Timestamp) => BinaryFunc::NotEq, 2061;
-                params!(TimestampTz, TimestampTz) => BinaryFunc::NotEq, 1321;
-                params!(Uuid, Uuid) => BinaryFunc::NotEq, 2973;
-                params!(Interval, Interval) => BinaryFunc::NotEq, 1331;
-                params!(Bytes, Bytes) => BinaryFunc::NotEq, 1956;
-                params!(String, String) => BinaryFunc::NotEq, 531;
-                params!(Jsonb, Jsonb) => BinaryFunc::NotEq, 3241;
+                } => Bool, 1753;
+                params!(Bool, Bool) => BinaryFunc::NotEq => Bool, 85;
+                params!(Int32, Int32) => BinaryFunc::NotEq => Bool, 518;
+                params!(Int64, Int64) => BinaryFunc::NotEq => Bool, 411;
+                params!(Float32, Float32) => BinaryFunc::NotEq => Bool, 621;
+                params!(Float64, Float64) => BinaryFunc::NotEq => Bool, 671;
+                params!(Oid, Oid) => BinaryFunc::NotEq => Bool, 608;
+                params!(Date, Date) => BinaryFunc::NotEq => Bool, 1094;
+                params!(Time, Time) => BinaryFunc::NotEq => Bool, 1109;
+                params!(Timestamp, Timestamp) => BinaryFunc::NotEq => Bool, 2061;
+                params!(TimestampTz, TimestampTz) => BinaryFunc::NotEq => Bool, 1321;
+                params!(Uuid, Uuid) => BinaryFunc::NotEq => Bool, 2973;
+                params!(Interval, Interval) => BinaryFunc::NotEq => Bool, 1331;
+                params!(Bytes, Bytes) => BinaryFunc::NotEq => Bool, 1956;
+                params!(String, String) => BinaryFunc::NotEq => Bool, 531;
+                params!(Jsonb, Jsonb) => BinaryFunc::NotEq => Bool, 3241;
             }
         }
     };
@@ -14,8 +14,10 @@ use std::convert;
 use std::fs::File;
 use std::io::Read;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use anyhow::bail;
+use itertools::Itertools;
 use log::{debug, error, info, warn};
 use rdkafka::consumer::{BaseConsumer, Consumer};
 use reqwest::Url;
@@ -28,6 +30,8 @@ enum ValType {
     String,
     // Number with range [lower, upper]
     Number(i32, i32),
+    // One of a fixed set of strings, compared case insensitively
+    OneOf(&'static [&'static str]),
 }
 
 // Describes Kafka cluster configurations users can suppply using `CREATE
@@ -37,6 +41,7 @@ struct Config {
     name: &'static str,
     val_type: ValType,
     transform: fn(String) -> String,
+    secret: bool,
 }
 
 impl Config {
@@ -45,6 +50,7 @@ impl Config {
             name,
             val_type,
             transform: convert::identity,
+            secret: false,
         }
     }
 
@@ -58,6 +64,15 @@ impl Config {
         Config::new(name, ValType::Path)
     }
 
+    // Shorthand for string config options whose values must not be revealed,
+    // not even in error messages.
+    fn secret(name: &'static str) -> Self {
+        Config {
+            secret: true,
+            ..Config::string(name)
+        }
+    }
+
     // Builds a new config that transforms the parameter according to `f` after
     // it is validated.
     fn transform(mut self, f: fn(String) -> String) -> Self {
@@ -83,6 +98,15 @@ impl Config {
                 Ok(parsed_n) if *lower <= parsed_n && parsed_n <= *upper => n.to_string(),
                 _ => bail!("must be a number between {} and {}", lower, upper),
             },
+            (ValType::OneOf(allowed), Value::String(v)) => {
+                if !allowed.iter().any(|a| a.eq_ignore_ascii_case(v)) {
+                    bail!(
+                        "must be one of {}",
+                        allowed.iter().map(|a| format!("'{}'", a)).join(", ")
+                    )
+                }
+                v.to_string()
+            }
             _ => bail!("unexpected value type"),
         };
         Ok((self.transform)(val))
@@ -98,6 +122,9 @@ fn extract(
         let value = match input.remove(config.name) {
             Some(v) => match config.validate_val(&v) {
                 Ok(v) => v,
+                Err(e) if config.secret => {
+                    bail!("Invalid WITH option {}=<REDACTED>: {}", config.name, e)
+                }
                 Err(e) => bail!("Invalid WITH option {}={}: {}", config.name, v, e),
             },
             None => continue,
@@ -136,10 +163,19 @@ pub fn extract_config(
                 // https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md
                 ValType::Number(0, 3_600_000),
             ),
-            Config::string("security_protocol"),
+            Config::new(
+                "reconnect_backoff_max_ms",
+                // The range of values comes from `reconnect.backoff.max.ms` in
+                // https://github.com/edenhill/librdkafka/blob/master/CONFIGURATION.md
+                ValType::Number(0, 3_600_000),
+            ),
+            Config::new(
+                "security_protocol",
+                ValType::OneOf(&["PLAINTEXT", "SSL", "SASL_PLAINTEXT", "SASL_SSL"]),
+            ),
             Config::path("sasl_kerberos_keytab"),
             Config::string("sasl_username"),
-            Config::string("sasl_password"),
+            Config::secret("sasl_password"),
             Config::string("sasl_kerberos_kinit_cmd"),
             Config::string("sasl_kerberos_min_time_before_relogin"),
             Config::string("sasl_kerberos_principal"),
@@ -147,11 +183,21 @@ pub fn extract_config(
             // For historical reasons, we allow `sasl_mechanisms` to be lowercase or
             // mixed case, while librdkafka requires all uppercase (e.g., `PLAIN`,
             // not `plain`).
-            Config::string("sasl_mechanisms").transform(|s| s.to_uppercase()),
+            Config::new(
+                "sasl_mechanisms",
+                ValType::OneOf(&[
+                    "GSSAPI",
+                    "PLAIN",
+                    "SCRAM-SHA-256",
+                    "SCRAM-SHA-512",
+                    "OAUTHBEARER",
+                ]),
+            )
+            .transform(|s| s.to_uppercase()),
             Config::path("ssl_ca_location"),
             Config::path("ssl_certificate_location"),
             Config::path("ssl_key_location"),
-            Config::string("ssl_key_password"),
+            Config::secret("ssl_key_password"),
         ],
     )
 }
@@ -247,6 +293,18 @@ impl rdkafka::client::ClientContext for RDKafkaErrCheckContext {
     }
 }
 
+/// How long to wait for a connection to a schema registry, unless the
+/// `csr_connect_timeout_ms` option says otherwise.
+const DEFAULT_CSR_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How long to wait for each response from a schema registry, unless the
+/// `csr_read_timeout_ms` option says otherwise.
+const DEFAULT_CSR_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// How many times to retry failed schema registry requests, unless the
+/// `csr_max_retries` option says otherwise.
+const DEFAULT_CSR_MAX_RETRIES: u32 = 2;
+
 // Generates a `ccsr::ClientConfig` based on the configuration extracted from
 // `extract_security_config()`. Currently only supports SSL auth.
 pub fn generate_ccsr_client_config(
@@ -286,11 +344,35 @@ pub fn generate_ccsr_client_config(
 
     let mut ccsr_options = extract(
         &mut ccsr_options,
-        &[Config::string("username"), Config::string("password")],
+        &[
+            Config::string("username"),
+            Config::secret("password"),
+            Config::new("csr_connect_timeout_ms", ValType::Number(1, 3_600_000)),
+            Config::new("csr_read_timeout_ms", ValType::Number(1, 3_600_000)),
+            Config::new("csr_max_retries", ValType::Number(0, 100)),
+        ],
     )?;
     if let Some(username) = ccsr_options.remove("username") {
         client_config = client_config.auth(username, ccsr_options.remove("password"));
     }
 
+    // The values of these options were validated as numbers above.
+    let connect_timeout = match ccsr_options.remove("csr.connect.timeout.ms") {
+        Some(ms) => Duration::from_millis(ms.parse()?),
+        None => DEFAULT_CSR_CONNECT_TIMEOUT,
+    };
+    let read_timeout = match ccsr_options.remove("csr.read.timeout.ms") {
+        Some(ms) => Duration::from_millis(ms.parse()?),
+        None => DEFAULT_CSR_READ_TIMEOUT,
+    };
+    let max_retries = match ccsr_options.remove("csr.max.retries") {
+        Some(n) => n.parse()?,
+        None => DEFAULT_CSR_MAX_RETRIES,
+    };
+    client_config = client_config
+        .connect_timeout(connect_timeout)
+        .timeout(read_timeout)
+        .max_retries(max_retries);
+
     Ok(client_config)
 }
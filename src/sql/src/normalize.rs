@@ -21,15 +21,16 @@ use aws_util::aws;
 use rusoto_core::Region;
 
 use repr::ColumnName;
-use sql_parser::ast::display::AstDisplay;
+use sql_parser::ast::display::{self, AstDisplay};
 use sql_parser::ast::visit_mut::{self, VisitMut};
 use sql_parser::ast::{
     AstInfo, CreateIndexStatement, CreateSinkStatement, CreateSourceStatement,
     CreateTableStatement, CreateTypeStatement, CreateViewStatement, DataType, Function,
-    FunctionArgs, Ident, IfExistsBehavior, Query, Raw, RawName, SqlOption, Statement, TableFactor,
-    UnresolvedObjectName, Value,
+    FunctionArgs, Ident, IfExistsBehavior, Op, Query, Raw, RawName, SqlOption, Statement,
+    TableFactor, UnresolvedObjectName, Value,
 };
 
+use crate::func;
 use crate::names::{DatabaseSpecifier, FullName, PartialName};
 use crate::plan::error::PlanError;
 use crate::plan::query;
@@ -63,6 +64,18 @@ pub fn object_name(mut name: UnresolvedObjectName) -> Result<PartialName, PlanEr
     Ok(out)
 }
 
+/// Normalizes an operator name.
+///
+/// All operators are built in and live in the `pg_catalog` schema, so the only
+/// permissible qualification is `pg_catalog`.
+pub fn op(op: &Op) -> Result<&str, anyhow::Error> {
+    match op.namespace.as_slice() {
+        [] => Ok(&op.op),
+        [schema] if schema.as_str() == "pg_catalog" => Ok(&op.op),
+        _ => bail!("operator does not exist: {}", op),
+    }
+}
+
 /// Normalizes a list of `WITH` options.
 pub fn options(options: &[SqlOption]) -> BTreeMap<String, Value> {
     options
@@ -77,6 +90,10 @@ pub fn options(options: &[SqlOption]) -> BTreeMap<String, Value> {
                 ident(name.clone()),
                 Value::String(data_type.to_ast_string()),
             ),
+            SqlOption::Columns { name, columns } => (
+                ident(name.clone()),
+                Value::String(display::comma_separated(columns).to_ast_string()),
+            ),
         })
         .collect()
 }
@@ -134,8 +151,16 @@ pub fn create_statement(
         scx: &StatementContext,
         name: &mut UnresolvedObjectName,
     ) -> Result<(), PlanError> {
-        let full_name = scx.resolve_function(name.clone())?;
-        *name = unresolve(full_name.name().clone());
+        let mut full_name = scx.resolve_function(name.clone())?.name().clone();
+        if full_name.schema == "mz_catalog" {
+            if let Some((_, canonical)) = func::DEPRECATED_FUNC_ALIASES
+                .iter()
+                .find(|(alias, _)| *alias == full_name.item)
+            {
+                full_name.item = canonical.to_string();
+            }
+        }
+        *name = unresolve(full_name);
         Ok(())
     };
 
@@ -173,10 +198,13 @@ pub fn create_statement(
 
             match &mut func.args {
                 FunctionArgs::Star => (),
-                FunctionArgs::Args(args) => {
+                FunctionArgs::Args { args, order_by } => {
                     for arg in args {
                         self.visit_expr_mut(arg);
                     }
+                    for expr in order_by {
+                        self.visit_order_by_expr_mut(expr);
+                    }
                 }
             }
             if let Some(over) = &mut func.over {
@@ -196,6 +224,7 @@ pub fn create_statement(
                     ref mut name,
                     args,
                     alias,
+                    with_ordinality: _,
                 } => {
                     if let Err(e) = normalize_function_name(self.scx, name) {
                         self.err = Some(e);
@@ -204,10 +233,13 @@ pub fn create_statement(
 
                     match args {
                         FunctionArgs::Star => (),
-                        FunctionArgs::Args(args) => {
+                        FunctionArgs::Args { args, order_by } => {
                             for expr in args {
                                 self.visit_expr_mut(expr);
                             }
+                            for expr in order_by {
+                                self.visit_order_by_expr_mut(expr);
+                            }
                         }
                     }
                     if let Some(alias) = alias {
@@ -422,6 +454,20 @@ macro_rules! with_option_type {
             ::anyhow::bail!("expected Interval");
         }
     };
+    ($name:ident, i64) => {
+        if let Some(crate::ast::WithOptionValue::Value(crate::ast::Value::Number(value))) = $name {
+            value.parse::<i64>()?
+        } else {
+            ::anyhow::bail!("expected i64");
+        }
+    };
+    ($name:ident, Value) => {
+        if let Some(crate::ast::WithOptionValue::Value(value)) = $name {
+            value
+        } else {
+            ::anyhow::bail!("expected value");
+        }
+    };
 }
 
 /// This macro accepts a struct definition and will generate it and a `try_from`
@@ -435,6 +481,8 @@ macro_rules! with_option_type {
 ///   option which will be interpreted as true: (`WITH (name)`.
 /// - `Interval`: expects either a SQL interval or string that can be parsed as
 ///   an interval.
+/// - `i64`: expects a SQL integer (`WITH (name = 42)`).
+/// - `Value`: expects any SQL literal, which is passed through unchecked.
 macro_rules! with_options {
   (struct $name:ident {
         $($field_name:ident: $field_type:ident,)*
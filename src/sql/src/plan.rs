@@ -26,14 +26,15 @@
 // `plan_root_query` and fanning out based on the contents of the `SELECT`
 // statement.
 
+use std::collections::BTreeMap;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
 use enum_kinds::EnumKind;
 use serde::{Deserialize, Serialize};
 
-use ::expr::{GlobalId, RowSetFinishing};
-use dataflow_types::{SinkConnectorBuilder, SinkEnvelope, SourceConnector};
+use ::expr::{ColumnOrder, GlobalId, RowSetFinishing};
+use dataflow_types::{SinkConnectorBuilder, SinkEnvelope, SourceConnector, TailOutput};
 use repr::{ColumnName, RelationDesc, Row, ScalarType, Timestamp};
 
 use crate::ast::{ExplainOptions, ExplainStage, Expr, FetchDirection, ObjectType, Raw, Statement};
@@ -80,6 +81,8 @@ pub enum Plan {
         source: Source,
         if_not_exists: bool,
         materialized: bool,
+        /// The column types that were inferred for the source, if any.
+        inferred_column_types: Vec<(ColumnName, ScalarType)>,
     },
     CreateSink {
         name: FullName,
@@ -127,6 +130,9 @@ pub enum Plan {
         items: Vec<GlobalId>,
         ty: ObjectType,
     },
+    ExplainDrop {
+        items: Vec<GlobalId>,
+    },
     EmptyQuery,
     ShowAllVariables,
     ShowVariable(String),
@@ -151,6 +157,8 @@ pub enum Plan {
         emit_progress: bool,
         object_columns: usize,
         desc: RelationDesc,
+        output: TailOutput,
+        order_by: Vec<ColumnOrder>,
     },
     SendRows(Vec<Row>),
     ExplainPlan {
@@ -169,6 +177,11 @@ pub enum Plan {
     Insert {
         id: GlobalId,
         values: ::expr::MirRelationExpr,
+        /// The keys on which to skip rows that conflict with existing rows, as
+        /// requested by `ON CONFLICT DO NOTHING`. Empty if no such clause.
+        conflict_keys: Vec<Vec<usize>>,
+        /// The names and expressions of the table's `CHECK` constraints.
+        checks: Vec<(String, ::expr::MirScalarExpr)>,
     },
     /// Generated by `ALTER ... IF EXISTS` if the named object did not exist.
     AlterNoop {
@@ -182,6 +195,13 @@ pub enum Plan {
         id: GlobalId,
         options: Vec<IndexOptionName>,
     },
+    AlterSourceSetOptions {
+        id: GlobalId,
+        /// The source's `CREATE SOURCE` statement, with the new options.
+        create_sql: String,
+        /// The source connector's complete set of Kafka options.
+        config_options: BTreeMap<String, String>,
+    },
     AlterItemRename {
         id: GlobalId,
         to_name: String,
@@ -199,6 +219,21 @@ pub enum Plan {
     Close {
         name: String,
     },
+    CancelQuery {
+        query_id: u64,
+    },
+    Listen {
+        channel: String,
+    },
+    Unlisten {
+        /// The channel to stop listening on, or `None` to stop listening on
+        /// all channels.
+        channel: Option<String>,
+    },
+    Notify {
+        channel: String,
+        payload: String,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -206,6 +241,13 @@ pub struct Table {
     pub create_sql: String,
     pub desc: RelationDesc,
     pub defaults: Vec<Expr<Raw>>,
+    /// The keys declared by `PRIMARY KEY` and `UNIQUE` constraints, as column
+    /// indices. Unlike the keys of `desc`, these are not enforced; they are
+    /// consulted only by `INSERT ... ON CONFLICT`.
+    pub keys: Vec<Vec<usize>>,
+    /// The names and expressions of the `CHECK` constraints, which are
+    /// enforced by `INSERT`.
+    pub checks: Vec<(String, Expr<Raw>)>,
     pub temporary: bool,
 }
 
@@ -230,6 +272,9 @@ pub struct View {
     pub expr: ::expr::MirRelationExpr,
     pub column_names: Vec<Option<ColumnName>>,
     pub temporary: bool,
+    /// The amount of history that indexes on the view retain by default, as
+    /// specified by the `retain_history` option.
+    pub retain_history: Option<Duration>,
 }
 
 #[derive(Clone, Debug)]
@@ -237,6 +282,9 @@ pub struct Index {
     pub create_sql: String,
     pub on: GlobalId,
     pub keys: Vec<::expr::MirScalarExpr>,
+    /// The maximum number of bytes that the index's arrangement may occupy,
+    /// if limited.
+    pub memory_limit_bytes: Option<usize>,
 }
 
 #[derive(Clone, Debug)]
@@ -287,7 +335,7 @@ pub enum ExecuteTimeout {
     WaitOnce,
 }
 
-#[derive(Clone, Debug, EnumKind)]
+#[derive(Clone, Debug, EnumKind, Serialize)]
 #[enum_kind(IndexOptionName)]
 pub enum IndexOption {
     /// Configures the logical compaction window for an index. `None` disables
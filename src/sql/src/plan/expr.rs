@@ -28,7 +28,9 @@ use crate::plan::typeconv::{self, CastContext};
 use crate::plan::Params;
 
 // these happen to be unchanged at the moment, but there might be additions later
-pub use expr::{BinaryFunc, ColumnOrder, NullaryFunc, TableFunc, UnaryFunc, VariadicFunc};
+pub use expr::{
+    BinaryFunc, ColumnOrder, LagLeadType, NullaryFunc, TableFunc, UnaryFunc, VariadicFunc,
+};
 use repr::adt::array::ArrayDimension;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -76,6 +78,7 @@ pub enum HirRelationExpr {
         group_key: Vec<usize>,
         aggregates: Vec<AggregateExpr>,
         expected_group_size: Option<usize>,
+        hash_aggregate: bool,
     },
     Distinct {
         input: Box<HirRelationExpr>,
@@ -350,7 +353,7 @@ pub struct AggregateExpr {
 /// here than in `expr`, as these aggregates may be applied over empty
 /// result sets and should be null in those cases, whereas `expr` variants
 /// only return null values when supplied nulls as input.
-#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
 pub enum AggregateFunc {
     MaxInt32,
     MaxInt64,
@@ -386,6 +389,36 @@ pub enum AggregateFunc {
     /// layer, this function filters out `Datum::Null`, for consistency with
     /// the other aggregate functions.
     JsonbAgg,
+    /// Accumulates the first elements of `Datum::List`s into an array, ordered
+    /// by the lists' remaining elements as specified by `order_by`.
+    ArrayAgg {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Concatenates the first elements of `Datum::List`s, which are lists of a
+    /// string value and its delimiter, ordered by the lists' remaining elements
+    /// as specified by `order_by`.
+    StringAgg {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Computes the continuous percentile of the second elements of
+    /// `Datum::List`s, which are `Float64`s, ordered as specified by
+    /// `order_by`. The first element of each list is the fraction of the
+    /// percentile.
+    PercentileCont {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Like `PercentileCont`, but chooses the first of the ordered values at or
+    /// after the fraction, rather than interpolating between them, and thus
+    /// accepts values of any type.
+    PercentileDisc {
+        order_by: Vec<ColumnOrder>,
+    },
+    /// Computes the `lag` and `lead` window functions of a window partition.
+    /// See `expr::AggregateFunc::LagLead` for details.
+    LagLead {
+        funcs: Vec<LagLeadType>,
+        order_by: Vec<ColumnOrder>,
+    },
     /// Accumulates any number of `Datum::Dummy`s into `Datum::Dummy`.
     ///
     /// Useful for removing an expensive aggregation while maintaining the shape
@@ -426,6 +459,17 @@ impl AggregateFunc {
             AggregateFunc::Any => expr::AggregateFunc::Any,
             AggregateFunc::All => expr::AggregateFunc::All,
             AggregateFunc::JsonbAgg => expr::AggregateFunc::JsonbAgg,
+            AggregateFunc::ArrayAgg { order_by } => expr::AggregateFunc::ArrayAgg { order_by },
+            AggregateFunc::StringAgg { order_by } => expr::AggregateFunc::StringAgg { order_by },
+            AggregateFunc::PercentileCont { order_by } => {
+                expr::AggregateFunc::PercentileCont { order_by }
+            }
+            AggregateFunc::PercentileDisc { order_by } => {
+                expr::AggregateFunc::PercentileDisc { order_by }
+            }
+            AggregateFunc::LagLead { funcs, order_by } => {
+                expr::AggregateFunc::LagLead { funcs, order_by }
+            }
             AggregateFunc::Dummy => expr::AggregateFunc::Dummy,
         }
     }
@@ -452,6 +496,21 @@ impl AggregateFunc {
             AggregateFunc::Any => ScalarType::Bool,
             AggregateFunc::All => ScalarType::Bool,
             AggregateFunc::JsonbAgg => ScalarType::Jsonb,
+            AggregateFunc::ArrayAgg { .. } => match input_type.scalar_type {
+                ScalarType::Record { fields, .. } => {
+                    ScalarType::Array(Box::new(fields[0].1.scalar_type.clone()))
+                }
+                _ => unreachable!("array_agg input must be a record"),
+            },
+            AggregateFunc::StringAgg { .. } => ScalarType::String,
+            AggregateFunc::PercentileCont { .. } => ScalarType::Float64,
+            AggregateFunc::PercentileDisc { .. } => match input_type.scalar_type {
+                ScalarType::Record { fields, .. } => fields[1].1.scalar_type.clone(),
+                _ => unreachable!("percentile_disc input must be a record"),
+            },
+            AggregateFunc::LagLead { .. } => {
+                self.clone().into_expr().output_type(input_type).scalar_type
+            }
             AggregateFunc::SumInt32 => ScalarType::Int64,
             AggregateFunc::SumInt64 => {
                 ScalarType::Decimal(repr::adt::decimal::MAX_DECIMAL_PRECISION, 0)
@@ -525,6 +584,7 @@ impl HirRelationExpr {
                 group_key,
                 aggregates,
                 expected_group_size: _,
+                hash_aggregate: _,
             } => {
                 let input_typ = input.typ(outers, params);
                 let mut column_types = group_key
@@ -629,12 +689,14 @@ impl HirRelationExpr {
         group_key: Vec<usize>,
         aggregates: Vec<AggregateExpr>,
         expected_group_size: Option<usize>,
+        hash_aggregate: bool,
     ) -> Self {
         HirRelationExpr::Reduce {
             input: Box::new(self),
             group_key,
             aggregates,
             expected_group_size,
+            hash_aggregate,
         }
     }
 
@@ -1271,6 +1333,25 @@ impl HirScalarExpr {
         })
     }
 
+    /// Attempts to simplify this expression to a literal 64-bit float.
+    ///
+    /// Returns `None` if this expression cannot be simplified, e.g. because it
+    /// contains non-literal values.
+    ///
+    /// # Panics
+    ///
+    /// Panics if this expression does not have type [`ScalarType::Float64`].
+    pub fn into_literal_float64(self) -> Option<f64> {
+        self.simplify_to_literal().and_then(|row| {
+            let datum = row.unpack_first();
+            if datum.is_null() {
+                None
+            } else {
+                Some(datum.unwrap_float64())
+            }
+        })
+    }
+
     /// Attempts to simplify this expression to a literal string.
     ///
     /// Returns `None` if this expression cannot be simplified, e.g. because it
@@ -413,6 +413,7 @@ impl HirRelationExpr {
                 group_key,
                 aggregates,
                 expected_group_size,
+                hash_aggregate,
             } => {
                 // Reduce may contain expressions with correlated subqueries.
                 // In addition, here an empty reduction key signifies that we need to supply default values
@@ -420,6 +421,7 @@ impl HirRelationExpr {
                 let mut input = input.applied_to(id_gen, get_outer.clone(), col_map);
                 let applied_group_key = (0..get_outer.arity())
                     .chain(group_key.iter().map(|i| get_outer.arity() + i))
+                    .map(expr::MirScalarExpr::Column)
                     .collect();
                 let applied_aggregates = aggregates
                     .into_iter()
@@ -436,8 +438,14 @@ impl HirRelationExpr {
                     })
                     .collect();
                 // NOTE we don't need to remove any extra columns from aggregate.applied_to above because the reduce will do that anyway
-                let mut reduced =
-                    input.reduce(applied_group_key, applied_aggregates, expected_group_size);
+                let mut reduced = expr::MirRelationExpr::Reduce {
+                    input: Box::new(input),
+                    group_key: applied_group_key,
+                    aggregates: applied_aggregates,
+                    monotonic: false,
+                    expected_group_size,
+                    hash_aggregate,
+                };
 
                 // Introduce default values in the case the group key is empty.
                 if group_key.is_empty() {
@@ -466,6 +474,7 @@ impl HirRelationExpr {
                     .map(|column_order| ColumnOrder {
                         column: column_order.column + get_outer.arity(),
                         desc: column_order.desc,
+                        nulls_last: column_order.nulls_last,
                     })
                     .collect();
                 input.top_k(applied_group_key, applied_order_key, limit, offset)
@@ -939,14 +948,18 @@ fn attempt_outer_join(
     // If we've gotten this far, we can do the clever thing.
     // We'll want to use left and right multiple times
     let result = left.let_in(id_gen, |id_gen, get_left| {
-        right.let_in(id_gen, |id_gen, get_right| {
-            // TODO: we know that we can re-use the arrangements of left and right
-            // needed for the inner join with each of the conditional outer joins.
-            // It is not clear whether we should hint that, or just let the planner
-            // and optimizer run and see what happens.
+        right.let_in(id_gen, |_id_gen, get_right| {
+            // The inner join, and the semijoins below that determine which
+            // rows of each input are matched, read only from `left`, `right`,
+            // and the distinct join keys of each, rather than from the output
+            // of another join. It is up to join implementation to choose a
+            // plan for each of them; inputs that are not already arranged by
+            // the join keys are joined differentially. Each outer side costs
+            // an arrangement of the distinct join keys of the other input, so
+            // a full join maintains two of them in addition to the inner join.
 
             // We'll want the inner join (minus repeated columns)
-            let join = expr::MirRelationExpr::join(
+            let mut result = expr::MirRelationExpr::join(
                 vec![get_left.clone(), get_right.clone()],
                 (0..oa).map(|i| vec![(0, i), (1, i)]).collect(),
             )
@@ -959,87 +972,107 @@ fn attempt_outer_join(
             // apply the filter constraints here, to ensure nulls are not matched.
             .filter(vec![on]);
 
-            // We'll want to re-use the results of the join multiple times.
-            join.let_in(id_gen, |id_gen, get_join| {
-                let mut result = get_join.clone();
+            // The plan is now to determine the left and right rows that have a
+            // match in the other input, subtract them from left and right
+            // respectively, pad what remains with nulls, and fold them in to
+            // `result`. A row has a match exactly when its join key is present
+            // in the other input, so retracting the last match for a key
+            // reintroduces the null-padded rows for that key, and inserting
+            // the first match retracts them.
 
-                // A collection of keys present in both left and right collections.
-                let both_keys = get_join
-                    .project((0..oa).chain(l_keys.clone()).collect::<Vec<_>>())
-                    .distinct();
-
-                // The plan is now to determine the left and right rows matched in the
-                // inner join, subtract them from left and right respectively, pad what
-                // remains with nulls, and fold them in to `result`.
-
-                both_keys.let_in(id_gen, |_id_gen, get_both| {
-                    if let JoinKind::LeftOuter { .. } | JoinKind::FullOuter = kind {
-                        // Rows in `left` that are matched in the inner equijoin.
-                        let left_present = expr::MirRelationExpr::join(
-                            vec![get_left.clone(), get_both.clone()],
-                            (0..oa)
-                                .chain(l_keys.clone())
-                                .enumerate()
-                                .map(|(i, c)| vec![(0, c), (1, i)])
-                                .collect::<Vec<_>>(),
-                        )
-                        .project((0..(oa + la)).collect());
+            if let JoinKind::LeftOuter { .. } | JoinKind::FullOuter = kind {
+                // Rows in `left` that are matched in the inner equijoin.
+                let left_present = semijoin(
+                    get_left.clone(),
+                    distinct_keys(get_right.clone(), oa, &r_keys),
+                    oa,
+                    &l_keys,
+                );
 
-                        // Determine the types of nulls to use as filler.
-                        let right_fill = rt
-                            .column_types
-                            .into_iter()
-                            .skip(oa)
-                            .map(|typ| expr::MirScalarExpr::literal_null(typ.scalar_type))
-                            .collect();
+                // Determine the types of nulls to use as filler.
+                let right_fill = rt
+                    .column_types
+                    .into_iter()
+                    .skip(oa)
+                    .map(|typ| expr::MirScalarExpr::literal_null(typ.scalar_type))
+                    .collect();
 
-                        // Add to `result` absent elements, filled with typed nulls.
-                        result = left_present
-                            .negate()
-                            .union(get_left.clone())
-                            .map(right_fill)
-                            .union(result);
-                    }
+                // Add to `result` absent elements, filled with typed nulls.
+                result = left_present
+                    .negate()
+                    .union(get_left.clone())
+                    .map(right_fill)
+                    .union(result);
+            }
 
-                    if let JoinKind::RightOuter | JoinKind::FullOuter = kind {
-                        // Rows in `right` that are matched in the inner equijoin.
-                        let right_present = expr::MirRelationExpr::join(
-                            vec![get_right.clone(), get_both],
-                            (0..oa)
-                                .chain(r_keys.clone())
-                                .enumerate()
-                                .map(|(i, c)| vec![(0, c), (1, i)])
-                                .collect::<Vec<_>>(),
-                        )
-                        .project((0..(oa + ra)).collect());
+            if let JoinKind::RightOuter | JoinKind::FullOuter = kind {
+                // Rows in `right` that are matched in the inner equijoin.
+                let right_present = semijoin(
+                    get_right.clone(),
+                    distinct_keys(get_left, oa, &l_keys),
+                    oa,
+                    &r_keys,
+                );
 
-                        // Determine the types of nulls to use as filler.
-                        let left_fill = lt
-                            .column_types
-                            .into_iter()
-                            .skip(oa)
-                            .map(|typ| expr::MirScalarExpr::literal_null(typ.scalar_type))
-                            .collect();
+                // Determine the types of nulls to use as filler.
+                let left_fill = lt
+                    .column_types
+                    .into_iter()
+                    .skip(oa)
+                    .map(|typ| expr::MirScalarExpr::literal_null(typ.scalar_type))
+                    .collect();
 
-                        // Add to `result` absent elemetns, prepended with typed nulls.
-                        result = right_present
-                            .negate()
-                            .union(get_right.clone())
-                            .map(left_fill)
-                            // Permute left fill before right values.
-                            .project(
-                                (0..oa)
-                                    .chain(oa + ra..oa + ra + la)
-                                    .chain(oa..oa + ra)
-                                    .collect(),
-                            )
-                            .union(result)
-                    }
+                // Add to `result` absent elements, prepended with typed nulls.
+                result = right_present
+                    .negate()
+                    .union(get_right)
+                    .map(left_fill)
+                    // Permute left fill before right values.
+                    .project(
+                        (0..oa)
+                            .chain(oa + ra..oa + ra + la)
+                            .chain(oa..oa + ra)
+                            .collect(),
+                    )
+                    .union(result)
+            }
 
-                    result
-                })
-            })
+            result
         })
     });
     Some(result)
 }
+
+/// Returns the distinct values of the outer columns and `keys` of `input`,
+/// whose first `oa` columns correspond to an outer context, omitting those
+/// with a null in any of `keys`, as nulls never satisfy a join equality.
+fn distinct_keys(input: expr::MirRelationExpr, oa: usize, keys: &[usize]) -> expr::MirRelationExpr {
+    input
+        .filter(keys.iter().map(|k| {
+            expr::MirScalarExpr::Column(*k)
+                .call_unary(expr::UnaryFunc::IsNull)
+                .call_unary(expr::UnaryFunc::Not)
+        }))
+        .project((0..oa).chain(keys.iter().cloned()).collect())
+        .distinct()
+}
+
+/// Returns the rows of `input` whose outer columns and `keys` are present in
+/// `present`, as produced by [`distinct_keys`].
+fn semijoin(
+    input: expr::MirRelationExpr,
+    present: expr::MirRelationExpr,
+    oa: usize,
+    keys: &[usize],
+) -> expr::MirRelationExpr {
+    let arity = input.arity();
+    expr::MirRelationExpr::join(
+        vec![input, present],
+        (0..oa)
+            .chain(keys.iter().cloned())
+            .enumerate()
+            .map(|(i, c)| vec![(0, c), (1, i)])
+            .collect(),
+    )
+    .project((0..arity).collect())
+}
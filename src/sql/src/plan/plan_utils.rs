@@ -11,9 +11,10 @@
 
 use std::fmt;
 
-use anyhow::bail;
+use anyhow::{anyhow, bail};
+use regex::Regex;
 
-use repr::RelationDesc;
+use repr::{ColumnName, RelationDesc};
 
 use crate::ast::Ident;
 use crate::normalize;
@@ -53,3 +54,59 @@ pub fn maybe_rename_columns(
 
     Ok(desc.with_names(new_names))
 }
+
+/// Renames the first `arity` columns in `desc` according to `spec`, which has
+/// the form `regex => template`.
+///
+/// Each of those columns whose name matches `regex` is renamed by replacing
+/// the first match with `template`, which may refer to capture groups using
+/// the syntax of [`Regex::replace`]. Columns that do not match, and columns
+/// after the first `arity`, keep their names.
+///
+/// Returns an error if a renamed column would have the same name as any other
+/// column in `desc`.
+pub fn rename_columns_matching(
+    desc: RelationDesc,
+    spec: &str,
+    arity: usize,
+) -> Result<RelationDesc, anyhow::Error> {
+    let mut parts = spec.splitn(2, "=>");
+    let (regex, template) = match (parts.next(), parts.next()) {
+        (Some(regex), Some(template)) => (regex.trim(), template.trim()),
+        _ => bail!("rename_columns must have the form 'regex => template'"),
+    };
+    let regex = Regex::new(regex).map_err(|e| anyhow!("invalid rename_columns regex: {}", e))?;
+
+    let mut renamed = vec![];
+    let new_names: Vec<Option<ColumnName>> = desc
+        .iter_names()
+        .enumerate()
+        .map(|(i, name)| match name {
+            Some(name) if i < arity => {
+                let new_name =
+                    ColumnName::from(regex.replace(name.as_str(), template).into_owned());
+                if &new_name != name {
+                    renamed.push(i);
+                }
+                Some(new_name)
+            }
+            name => name.cloned(),
+        })
+        .collect();
+
+    for i in renamed {
+        let name = new_names[i].as_ref().expect("renamed columns have names");
+        let occurrences = new_names
+            .iter()
+            .filter(|n| n.as_ref() == Some(name))
+            .count();
+        if occurrences > 1 {
+            bail!(
+                "rename_columns would produce duplicate column name \"{}\"",
+                name
+            );
+        }
+    }
+
+    Ok(desc.with_names(new_names))
+}
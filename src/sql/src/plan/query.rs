@@ -21,7 +21,7 @@
 
 use std::borrow::Cow;
 use std::cell::RefCell;
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::convert::TryInto;
 use std::iter;
 use std::mem;
@@ -35,10 +35,10 @@ use sql_parser::ast::display::{AstDisplay, AstFormatter};
 use sql_parser::ast::fold::Fold;
 use sql_parser::ast::visit::{self, Visit};
 use sql_parser::ast::{
-    AstInfo, Cte, DataType, Distinct, Expr, Function, FunctionArgs, Ident, InsertSource,
-    JoinConstraint, JoinOperator, Limit, OrderByExpr, Query, Raw, RawName, Select, SelectItem,
-    SetExpr, SetOperator, TableAlias, TableFactor, TableWithJoins, UnresolvedObjectName, Value,
-    Values,
+    AstInfo, Cte, DataType, Distinct, Expr, Function, FunctionArgs, GroupByExpr, Ident,
+    InsertSource, JoinConstraint, JoinOperator, Limit, MapEntry, OnConflict, Op, OrderByExpr,
+    Query, Raw, RawName, Select, SelectItem, SetExpr, SetOperator, TableAlias, TableFactor,
+    TableWithJoins, UnresolvedObjectName, Value, Values, WindowSpec,
 };
 
 use ::expr::{GlobalId, Id, RowSetFinishing};
@@ -54,8 +54,9 @@ use crate::names::PartialName;
 use crate::normalize;
 use crate::plan::error::PlanError;
 use crate::plan::expr::{
-    AbstractColumnType, AbstractExpr, AggregateExpr, BinaryFunc, CoercibleScalarExpr, ColumnOrder,
-    ColumnRef, HirRelationExpr, HirScalarExpr, JoinKind, UnaryFunc, VariadicFunc,
+    AbstractColumnType, AbstractExpr, AggregateExpr, AggregateFunc, BinaryFunc,
+    CoercibleScalarExpr, ColumnOrder, ColumnRef, HirRelationExpr, HirScalarExpr, JoinKind,
+    LagLeadType, TableFunc, UnaryFunc, VariadicFunc,
 };
 use crate::plan::plan_utils;
 use crate::plan::scope::{Scope, ScopeItem, ScopeItemName};
@@ -306,12 +307,27 @@ fn try_push_projection_order_by(
     }
 }
 
+/// Plans the source of an `INSERT` statement.
+///
+/// Returns the ID of the target table, the rows to insert, the keys on which
+/// rows that conflict with existing rows are to be skipped, as requested by
+/// `on_conflict`, and the table's `CHECK` constraints, which every inserted row
+/// must satisfy.
 pub fn plan_insert_query(
     scx: &StatementContext,
     table_name: UnresolvedObjectName,
     columns: Vec<Ident>,
     source: InsertSource<Raw>,
-) -> Result<(GlobalId, HirRelationExpr), anyhow::Error> {
+    on_conflict: Option<OnConflict>,
+) -> Result<
+    (
+        GlobalId,
+        HirRelationExpr,
+        Vec<Vec<usize>>,
+        Vec<(String, ::expr::MirScalarExpr)>,
+    ),
+    anyhow::Error,
+> {
     let mut qcx = QueryContext::root(scx, QueryLifetime::OneShot);
     let table = scx.resolve_item(table_name)?;
 
@@ -324,7 +340,7 @@ pub fn plan_insert_query(
         );
     }
     let desc = table.desc()?;
-    let defaults = table
+    let (defaults, keys, checks) = table
         .table_details()
         .expect("attempted to insert into non-table");
 
@@ -332,6 +348,40 @@ pub fn plan_insert_query(
         bail!("cannot insert into system table '{}'", table.name());
     }
 
+    let conflict_keys = match on_conflict {
+        None => vec![],
+        Some(_) if keys.is_empty() => bail!(
+            "ON CONFLICT requires a PRIMARY KEY or UNIQUE constraint on table {}",
+            table.name().to_string().quoted()
+        ),
+        // Without a conflict target, a row conflicts if it conflicts on any
+        // key.
+        Some(OnConflict { columns }) if columns.is_empty() => keys.to_vec(),
+        Some(OnConflict { columns }) => {
+            let mut target = BTreeSet::new();
+            for c in columns {
+                let c = normalize::column_name(c);
+                match desc.get_by_name(&c) {
+                    Some((i, _)) => target.insert(i),
+                    None => bail!(
+                        "column {} of relation {} does not exist",
+                        c.as_str().quoted(),
+                        table.name().to_string().quoted()
+                    ),
+                };
+            }
+            match keys
+                .iter()
+                .find(|key| key.iter().copied().collect::<BTreeSet<_>>() == target)
+            {
+                Some(key) => vec![key.clone()],
+                None => {
+                    bail!("there is no unique constraint matching the ON CONFLICT specification")
+                }
+            }
+        }
+    };
+
     let columns: Vec<_> = columns.into_iter().map(normalize::column_name).collect();
 
     // Validate target column order.
@@ -452,7 +502,17 @@ pub fn plan_insert_query(
         }
     }
 
-    Ok((table.id(), expr.map(map_exprs).project(project_key)))
+    let checks = checks
+        .iter()
+        .map(|(name, expr)| Ok((name.clone(), plan_check_expr(scx, desc, expr.clone())?)))
+        .collect::<Result<_, anyhow::Error>>()?;
+
+    Ok((
+        table.id(),
+        expr.map(map_exprs).project(project_key),
+        conflict_keys,
+        checks,
+    ))
 }
 
 struct CastRelationError {
@@ -612,6 +672,53 @@ pub fn plan_index_exprs<'a>(
     Ok(out)
 }
 
+/// Plans the `partition_by` expression of a sink over `desc`, the description
+/// of the sink's input, returning the planned expression and its type.
+pub fn plan_partition_by_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    mut expr: Expr<Raw>,
+) -> Result<(::expr::MirScalarExpr, ScalarType), anyhow::Error> {
+    let scope = Scope::from_source(None, desc.iter_names(), Some(Scope::empty(None)));
+    let mut qcx = QueryContext::root(scx, QueryLifetime::Static);
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = resolve_names_expr(&mut qcx, expr)?;
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CREATE SINK",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+    };
+    let expr = plan_expr(ecx, &expr)?.type_as_any(ecx)?;
+    let ty = ecx.scalar_type(&expr);
+    Ok((expr.lower_uncorrelated()?, ty))
+}
+
+/// Plans the expression of a `CHECK` constraint over `desc`, the description
+/// of the constrained table.
+pub fn plan_check_expr(
+    scx: &StatementContext,
+    desc: &RelationDesc,
+    mut expr: Expr<Raw>,
+) -> Result<::expr::MirScalarExpr, anyhow::Error> {
+    let scope = Scope::from_source(None, desc.iter_names(), Some(Scope::empty(None)));
+    let mut qcx = QueryContext::root(scx, QueryLifetime::Static);
+    transform_ast::transform_expr(scx, &mut expr)?;
+    let expr = resolve_names_expr(&mut qcx, expr)?;
+    let ecx = &ExprContext {
+        qcx: &qcx,
+        name: "CHECK constraint",
+        scope: &scope,
+        relation_type: desc.typ(),
+        allow_aggregates: false,
+        allow_subqueries: false,
+    };
+    let expr = plan_expr(ecx, &expr)?.type_as(ecx, &ScalarType::Bool)?;
+    Ok(expr.lower_uncorrelated()?)
+}
+
 fn plan_expr_or_col_index(
     ecx: &ExprContext,
     e: &Expr<Aug>,
@@ -997,6 +1104,16 @@ fn plan_view_select(
         None => None,
     };
 
+    let hash_aggregate = match options.remove("aggregate_strategy") {
+        None => false,
+        Some(Value::String(s)) => match s.to_lowercase().as_str() {
+            "sort" => false,
+            "hash" => true,
+            _ => bail!("aggregate_strategy must be 'sort' or 'hash'"),
+        },
+        Some(_) => bail!("aggregate_strategy must be a string"),
+    };
+
     // Step 1. Handle FROM clause, including joins.
     let (mut relation_expr, from_scope) =
         from.iter().fold(Ok(plan_join_identity(qcx)), |l, twj| {
@@ -1020,7 +1137,7 @@ fn plan_view_select(
         relation_expr = relation_expr.filter(vec![expr]);
     }
 
-    // Step 3. Gather aggregates.
+    // Step 3. Gather aggregates and window functions.
     let aggregates = {
         let mut aggregate_visitor = AggregateFuncVisitor::new(&qcx.scx);
         aggregate_visitor.visit_select(&s);
@@ -1029,6 +1146,22 @@ fn plan_view_select(
         }
         aggregate_visitor.into_result()?
     };
+    let grouping_funcs = {
+        let mut grouping_func_visitor = GroupingFuncVisitor::new(&qcx.scx);
+        grouping_func_visitor.visit_select(&s);
+        for o in order_by_exprs {
+            grouping_func_visitor.visit_order_by_expr(o);
+        }
+        grouping_func_visitor.into_result()
+    };
+    let window_funcs = {
+        let mut window_func_visitor = WindowFuncVisitor::new(&qcx.scx);
+        window_func_visitor.visit_select(&s);
+        for o in order_by_exprs {
+            window_func_visitor.visit_order_by_expr(o);
+        }
+        window_func_visitor.into_result()?
+    };
 
     // Step 4. Expand SELECT clause.
     let projection = {
@@ -1051,7 +1184,7 @@ fn plan_view_select(
     };
 
     // Step 5. Handle GROUP BY clause.
-    let (group_scope, select_all_mapping) = {
+    let (mut group_scope, select_all_mapping) = {
         // Compute GROUP BY expressions.
         let ecx = &ExprContext {
             qcx,
@@ -1061,20 +1194,20 @@ fn plan_view_select(
             allow_aggregates: false,
             allow_subqueries: true,
         };
-        let mut group_key = vec![];
         let mut group_exprs = vec![];
         let mut group_scope = Scope::empty(Some(qcx.outer_scope.clone()));
         let mut select_all_mapping = BTreeMap::new();
-        for group_expr in group_by {
+        let mut plan_group_expr = |group_expr: &Expr<Aug>| -> Result<usize, anyhow::Error> {
             let (group_expr, expr) = plan_group_by_expr(ecx, group_expr, &projection)?;
-            let new_column = group_key.len();
+            let new_column = group_exprs.len();
             // Repeated expressions in GROUP BY confuse name resolution later,
             // and dropping them doesn't change the result.
-            if group_exprs
+            if let Some(i) = group_exprs
                 .iter()
-                .find(|existing_expr| **existing_expr == expr)
-                .is_none()
+                .position(|existing_expr| *existing_expr == expr)
             {
+                Ok(i)
+            } else {
                 let scope_item = if let HirScalarExpr::Column(ColumnRef {
                     level: 0,
                     column: old_column,
@@ -1097,9 +1230,34 @@ fn plan_view_select(
                     }
                 };
 
-                group_key.push(from_scope.len() + group_exprs.len());
                 group_exprs.push(expr);
                 group_scope.items.push(scope_item);
+                Ok(new_column)
+            }
+        };
+
+        // Each item in the GROUP BY clause denotes a list of grouping sets,
+        // and the query is grouped by every union of one set from each item.
+        // A plain GROUP BY clause has a single grouping set.
+        let mut grouping_sets = vec![BTreeSet::new()];
+        for item in group_by {
+            let item_sets = plan_grouping_sets(item, &mut plan_group_expr)?;
+            if grouping_sets.len() * item_sets.len() > MAX_GROUPING_SETS {
+                bail!("too many grouping sets present");
+            }
+            grouping_sets = grouping_sets
+                .iter()
+                .cartesian_product(&item_sets)
+                .map(|(a, b)| a.union(b).copied().collect())
+                .collect();
+        }
+        // Repeated grouping sets produce repeated rows, but each distinct set
+        // only needs to be reduced once.
+        let mut distinct_grouping_sets: Vec<(BTreeSet<usize>, usize)> = vec![];
+        for set in grouping_sets {
+            match distinct_grouping_sets.iter_mut().find(|(s, _)| *s == set) {
+                Some((_, count)) => *count += 1,
+                None => distinct_grouping_sets.push((set, 1)),
             }
         }
 
@@ -1121,12 +1279,105 @@ fn plan_view_select(
                 nameable: true,
             });
         }
-        if !agg_exprs.is_empty() || !group_key.is_empty() || having.is_some() {
+
+        // Plan calls to `grouping`, which are computed alongside the
+        // aggregates. Each is represented by the GROUP BY columns of its
+        // arguments.
+        let ecx = &ecx.with_name("GROUPING");
+        let mut grouping_args = vec![];
+        for sql_function in grouping_funcs {
+            let args = match &sql_function.args {
+                FunctionArgs::Args { args, order_by }
+                    if !args.is_empty() && order_by.is_empty() =>
+                {
+                    args
+                }
+                _ => bail!("GROUPING requires a list of grouping expressions"),
+            };
+            if args.len() > 31 {
+                bail!("GROUPING must have fewer than 32 arguments");
+            }
+            let mut columns = vec![];
+            for arg in args {
+                let expr = plan_expr(ecx, arg)?.type_as_any(ecx)?;
+                match group_exprs.iter().position(|e| *e == expr) {
+                    Some(i) => columns.push(i),
+                    None => bail!(
+                        "arguments to GROUPING must be grouping expressions of the associated query level"
+                    ),
+                }
+            }
+            grouping_args.push(columns);
+            group_scope.items.push(ScopeItem {
+                names: vec![],
+                expr: Some(Expr::Function(sql_function.clone())),
+                nameable: true,
+            });
+        }
+
+        if !agg_exprs.is_empty() || !group_by.is_empty() || having.is_some() {
             // apply GROUP BY / aggregates
-            relation_expr =
-                relation_expr
-                    .map(group_exprs)
-                    .reduce(group_key, agg_exprs, expected_group_size);
+            let input = relation_expr.map(group_exprs);
+            let group_types = qcx
+                .relation_type(&input)
+                .column_types
+                .split_off(from_scope.len());
+            let mut branches = vec![];
+            for (set, count) in distinct_grouping_sets {
+                // The input columns that are not in the grouping set are
+                // replaced with nulls, so that every branch of the union
+                // has the same columns.
+                let mut outputs = vec![];
+                let mut scalars = vec![];
+                let arity = set.len() + agg_exprs.len();
+                for (i, typ) in group_types.iter().enumerate() {
+                    match set.iter().position(|c| *c == i) {
+                        Some(pos) => outputs.push(pos),
+                        None => {
+                            outputs.push(arity + scalars.len());
+                            scalars.push(HirScalarExpr::literal_null(typ.scalar_type.clone()));
+                        }
+                    }
+                }
+                outputs.extend(set.len()..arity);
+                // `grouping` sets the bit of each argument that is not in the
+                // grouping set, with the first argument in the most
+                // significant position.
+                for columns in &grouping_args {
+                    let mut bits = 0;
+                    for c in columns {
+                        bits <<= 1;
+                        if !set.contains(c) {
+                            bits |= 1;
+                        }
+                    }
+                    outputs.push(arity + scalars.len());
+                    scalars.push(HirScalarExpr::literal(
+                        Datum::Int32(bits),
+                        ScalarType::Int32,
+                    ));
+                }
+                let branch = input
+                    .clone()
+                    .reduce(
+                        set.iter().map(|c| from_scope.len() + c).collect(),
+                        agg_exprs.clone(),
+                        expected_group_size,
+                        hash_aggregate,
+                    )
+                    .map(scalars)
+                    .project(outputs);
+                branches.extend(iter::repeat(branch).take(count));
+            }
+            let base = branches.remove(0);
+            relation_expr = if branches.is_empty() {
+                base
+            } else {
+                HirRelationExpr::Union {
+                    base: Box::new(base),
+                    inputs: branches,
+                }
+            };
             (group_scope, select_all_mapping)
         } else {
             // if no GROUP BY, aggregates or having then all columns remain in scope
@@ -1151,7 +1402,12 @@ fn plan_view_select(
         relation_expr = relation_expr.filter(vec![expr]);
     }
 
-    // Step 7. Handle SELECT clause.
+    // Step 7. Handle window functions.
+    if !window_funcs.is_empty() {
+        relation_expr = plan_window_funcs(qcx, relation_expr, &mut group_scope, window_funcs)?;
+    }
+
+    // Step 8. Handle SELECT clause.
     let (mut project_key, map_scope) = {
         let mut new_exprs = vec![];
         let mut project_key = vec![];
@@ -1207,7 +1463,7 @@ fn plan_view_select(
         (project_key, map_scope)
     };
 
-    // Step 8. Handle intrusive ORDER BY and DISTINCT.
+    // Step 9. Handle intrusive ORDER BY and DISTINCT.
     let order_by = {
         let (mut order_by, mut map_exprs) = plan_projected_order_by_exprs(
             &ExprContext {
@@ -1395,6 +1651,82 @@ fn plan_group_by_expr<'a>(
     }
 }
 
+/// The maximum number of grouping sets that a `GROUP BY` clause can denote.
+const MAX_GROUPING_SETS: usize = 4096;
+
+/// Expands an item in a `GROUP BY` clause into the grouping sets that it
+/// denotes.
+///
+/// Each expression in the item is planned with `plan_expr`, which returns the
+/// index of the expression among the query's grouping expressions, and each
+/// grouping set is the set of the indices of the expressions that it groups by.
+fn plan_grouping_sets<'a, F>(
+    item: &'a GroupByExpr<Aug>,
+    plan_expr: &mut F,
+) -> Result<Vec<BTreeSet<usize>>, anyhow::Error>
+where
+    F: FnMut(&'a Expr<Aug>) -> Result<usize, anyhow::Error>,
+{
+    fn plan_set<'a, F>(
+        exprs: &'a [Expr<Aug>],
+        plan_expr: &mut F,
+    ) -> Result<BTreeSet<usize>, anyhow::Error>
+    where
+        F: FnMut(&'a Expr<Aug>) -> Result<usize, anyhow::Error>,
+    {
+        exprs.iter().map(|e| plan_expr(e)).collect()
+    }
+
+    fn plan_elements<'a, F>(
+        elements: &'a [Vec<Expr<Aug>>],
+        plan_expr: &mut F,
+    ) -> Result<Vec<BTreeSet<usize>>, anyhow::Error>
+    where
+        F: FnMut(&'a Expr<Aug>) -> Result<usize, anyhow::Error>,
+    {
+        elements.iter().map(|e| plan_set(e, plan_expr)).collect()
+    }
+
+    match item {
+        GroupByExpr::Expr(expr) => Ok(vec![iter::once(plan_expr(expr)?).collect()]),
+        GroupByExpr::Set(exprs) => Ok(vec![plan_set(exprs, plan_expr)?]),
+        GroupByExpr::Rollup(elements) => {
+            // `ROLLUP (e1, ..., en)` denotes each prefix of its elements, from
+            // `(e1, ..., en)` down to `()`.
+            let elements = plan_elements(elements, plan_expr)?;
+            Ok((0..=elements.len())
+                .rev()
+                .map(|n| elements[..n].iter().flatten().copied().collect())
+                .collect())
+        }
+        GroupByExpr::Cube(elements) => {
+            // `CUBE (e1, ..., en)` denotes every subset of its elements.
+            if elements.len() > 12 {
+                bail!("CUBE is limited to 12 elements");
+            }
+            let elements = plan_elements(elements, plan_expr)?;
+            Ok((0..1_usize << elements.len())
+                .rev()
+                .map(|mask| {
+                    elements
+                        .iter()
+                        .enumerate()
+                        .filter(|(i, _)| mask & (1 << (elements.len() - 1 - i)) != 0)
+                        .flat_map(|(_, element)| element.iter().copied())
+                        .collect()
+                })
+                .collect())
+        }
+        GroupByExpr::GroupingSets(items) => {
+            let mut sets = vec![];
+            for item in items {
+                sets.extend(plan_grouping_sets(item, plan_expr)?);
+            }
+            Ok(sets)
+        }
+    }
+}
+
 /// Plans a slice of `ORDER BY` expressions.
 fn plan_order_by_exprs(
     ecx: &ExprContext,
@@ -1424,14 +1756,25 @@ fn plan_projected_order_by_exprs(
                 ecx.relation_type.arity() + map_exprs.len() - 1
             }
         };
+        let (desc, nulls_last) = resolve_desc_and_nulls_last(obe);
         order_by.push(ColumnOrder {
             column,
-            desc: !obe.asc.unwrap_or(true),
+            desc,
+            nulls_last,
         });
     }
     Ok((order_by, map_exprs))
 }
 
+/// Returns whether `obe` sorts in descending order and whether it sorts nulls
+/// last. The placement of nulls defaults to that of PostgreSQL, which treats
+/// nulls as larger than any other value.
+fn resolve_desc_and_nulls_last(obe: &OrderByExpr<Aug>) -> (bool, bool) {
+    let desc = !obe.asc.unwrap_or(true);
+    let nulls_last = obe.nulls_last.unwrap_or(!desc);
+    (desc, nulls_last)
+}
+
 /// Plans an expression that appears in an `ORDER BY` or `DISTINCT ON` clause.
 ///
 /// This is like `plan_expr_or_col_index`, except that any column ordinal
@@ -1499,7 +1842,12 @@ fn plan_table_factor(
             (expr, scope)
         }
 
-        TableFactor::Function { name, args, alias } => {
+        TableFactor::Function {
+            name,
+            args,
+            alias,
+            with_ordinality,
+        } => {
             let ecx = &ExprContext {
                 qcx: &qcx,
                 name: "FROM table function",
@@ -1508,7 +1856,7 @@ fn plan_table_factor(
                 allow_aggregates: false,
                 allow_subqueries: true,
             };
-            plan_table_function(ecx, &name, alias.as_ref(), args)?
+            plan_table_function(ecx, &name, alias.as_ref(), args, *with_ordinality)?
         }
 
         TableFactor::Derived {
@@ -1553,6 +1901,7 @@ fn plan_table_function(
     name: &UnresolvedObjectName,
     alias: Option<&TableAlias>,
     args: &FunctionArgs<Aug>,
+    with_ordinality: bool,
 ) -> Result<(HirRelationExpr, Scope), anyhow::Error> {
     if *name == UnresolvedObjectName::unqualified("values") {
         // Produce a nice error message for the common typo
@@ -1566,10 +1915,24 @@ fn plan_table_function(
     };
     let args = match args {
         FunctionArgs::Star => bail!("{} does not accept * as an argument", name),
-        FunctionArgs::Args(args) => plan_exprs(ecx, args)?,
+        FunctionArgs::Args { order_by, .. } if !order_by.is_empty() => {
+            bail!(
+                "ORDER BY specified, but {} is not an aggregate function",
+                name
+            )
+        }
+        FunctionArgs::Args { args, .. } => plan_exprs(ecx, args)?,
     };
     let name = normalize::object_name(name.clone())?;
-    let tf = func::select_impl(ecx, FuncSpec::Func(&name), impls, args)?;
+    let mut tf = func::select_impl(ecx, FuncSpec::Func(&name), impls, args)?;
+    let single_column = tf.column_names.len() == 1;
+    if with_ordinality {
+        if let TableFunc::Repeat = tf.func {
+            bail!("WITH ORDINALITY is not supported for {}", name);
+        }
+        tf.func = TableFunc::WithOrdinality(Box::new(tf.func));
+        tf.column_names.push(Some("ordinality".into()));
+    }
     let call = HirRelationExpr::CallTable {
         func: tf.func,
         exprs: tf.exprs,
@@ -1585,7 +1948,8 @@ fn plan_table_function(
     );
     let mut scope = plan_table_alias(scope, alias)?;
     if let Some(alias) = alias {
-        if let [item] = &mut *scope.items {
+        if single_column {
+            let item = &mut scope.items[0];
             // Strange special case for table functions that ouput one column.
             // If a table alias is provided but not a column alias, the column
             // implicitly takes on the same alias in addition to its inherent
@@ -1597,7 +1961,9 @@ fn plan_table_function(
             //     CREATE TABLE t (a int)
             //     SELECT x FROM t AS x
             //
-            // would return a single column of type record(int).
+            // would return a single column of type record(int). With
+            // `WITH ORDINALITY`, the alias names the function's column, not
+            // the ordinality column.
             item.names.push(ScopeItemName {
                 table_name: None,
                 column_name: Some(normalize::column_name(alias.name.clone())),
@@ -1659,6 +2025,7 @@ fn invent_column_name(ecx: &ExprContext, expr: &Expr<Aug>) -> Option<ScopeItemNa
         Expr::NullIf { .. } => Some("nullif".into()),
         Expr::Array { .. } => Some("array".into()),
         Expr::List { .. } => Some("list".into()),
+        Expr::Map { .. } => Some("map".into()),
         Expr::Cast { expr, .. } => return invent_column_name(ecx, expr),
         Expr::FieldAccess { field, .. } => Some(normalize::column_name(field.clone())),
         Expr::Exists { .. } => Some("exists".into()),
@@ -1795,9 +2162,11 @@ fn plan_join_operator(
             JoinKind::LeftOuter { lateral },
         ),
         JoinOperator::RightOuter(constraint) => {
-            if lateral {
-                bail!("the combining JOIN type must be INNER or LEFT for a LATERAL reference");
-            }
+            let (right_qcx, right) = if lateral {
+                (left_qcx, make_non_lateral(right)?)
+            } else {
+                (right_qcx, right)
+            };
             plan_join_constraint(
                 &constraint,
                 left_qcx,
@@ -1810,9 +2179,11 @@ fn plan_join_operator(
             )
         }
         JoinOperator::FullOuter(constraint) => {
-            if lateral {
-                bail!("the combining JOIN type must be INNER or LEFT for a LATERAL reference");
-            }
+            let (right_qcx, right) = if lateral {
+                (left_qcx, make_non_lateral(right)?)
+            } else {
+                (right_qcx, right)
+            };
             plan_join_constraint(
                 &constraint,
                 left_qcx,
@@ -1848,6 +2219,28 @@ fn plan_join_operator(
     }
 }
 
+/// Converts the right side of a join, which was planned as `LATERAL`, into an
+/// expression that can be planned as the right side of a non-lateral join.
+///
+/// Table functions are always planned as `LATERAL`, but joins that do not
+/// permit `LATERAL` references, like `RIGHT` and `FULL` joins, can still
+/// accept table functions whose arguments do not reference the left side of
+/// the join.
+fn make_non_lateral(mut right: HirRelationExpr) -> Result<HirRelationExpr, anyhow::Error> {
+    let mut references_left = false;
+    right.visit_columns(0, &mut |depth, col| {
+        if col.level == depth + 1 {
+            references_left = true;
+        } else if col.level > depth + 1 {
+            col.level -= 1;
+        }
+    });
+    if references_left {
+        bail!("the combining JOIN type must be INNER or LEFT for a LATERAL reference");
+    }
+    Ok(right)
+}
+
 #[allow(clippy::too_many_arguments)]
 fn plan_join_constraint<'a>(
     constraint: &'a JoinConstraint<Aug>,
@@ -2096,6 +2489,7 @@ pub fn plan_expr<'a>(
         }
         Expr::Array(exprs) => plan_array(ecx, exprs, None)?,
         Expr::List(exprs) => plan_list(ecx, exprs, None)?,
+        Expr::Map(entries) => plan_map(ecx, entries, None)?,
         Expr::Row { exprs } => {
             let mut out = vec![];
             for e in exprs {
@@ -2109,16 +2503,17 @@ pub fn plan_expr<'a>(
         Expr::Cast { expr, data_type } => {
             let to_scalar_type = scalar_type_from_sql(ecx.qcx.scx, data_type)?;
             let expr = match &**expr {
-                // Special case a direct cast of an ARRAY or LIST expression so
-                // we can pass in the target type as a type hint. This is
-                // a limited form of the coercion that we do for string literals
-                // via CoercibleScalarExpr. We used to let CoercibleScalarExpr
-                // handle ARRAY/LIST coercion too, but doing so causes
-                // PostgreSQL compatibility trouble.
+                // Special case a direct cast of an ARRAY, LIST, or MAP
+                // expression so we can pass in the target type as a type
+                // hint. This is a limited form of the coercion that we do for
+                // string literals via CoercibleScalarExpr. We used to let
+                // CoercibleScalarExpr handle ARRAY/LIST coercion too, but
+                // doing so causes PostgreSQL compatibility trouble.
                 //
                 // See: https://github.com/postgres/postgres/blob/31f403e95/src/backend/parser/parse_expr.c#L2762-L2768
                 Expr::Array(exprs) => plan_array(ecx, exprs, Some(&to_scalar_type))?,
                 Expr::List(exprs) => plan_list(ecx, exprs, Some(&to_scalar_type))?,
+                Expr::Map(entries) => plan_map(ecx, entries, Some(&to_scalar_type))?,
                 _ => plan_expr(ecx, expr)?,
             };
             let expr = typeconv::plan_coerce(ecx, expr, &to_scalar_type)?;
@@ -2202,22 +2597,40 @@ pub fn plan_expr<'a>(
         Expr::SubscriptIndex { expr, subscript } => {
             let expr = plan_expr(ecx, expr)?.type_as_any(ecx)?;
             let ty = ecx.scalar_type(&expr);
-            let func = match &ty {
-                ScalarType::List { .. } => BinaryFunc::ListIndex,
-                ScalarType::Array(_) => BinaryFunc::ArrayIndex,
+            let (func, subscript) = match &ty {
+                ScalarType::List { .. } => (
+                    BinaryFunc::ListIndex,
+                    plan_expr(ecx, subscript)?.cast_to(
+                        "subscript (indexing)",
+                        ecx,
+                        CastContext::Explicit,
+                        &ScalarType::Int64,
+                    )?,
+                ),
+                ScalarType::Array(_) => (
+                    BinaryFunc::ArrayIndex,
+                    plan_expr(ecx, subscript)?.cast_to(
+                        "subscript (indexing)",
+                        ecx,
+                        CastContext::Explicit,
+                        &ScalarType::Int64,
+                    )?,
+                ),
+                // Looking up a key that the map does not contain produces
+                // NULL, as with the `->` operator.
+                ScalarType::Map { .. } => (
+                    BinaryFunc::MapGetValue,
+                    plan_expr(ecx, subscript)?.cast_to(
+                        "subscript (indexing)",
+                        ecx,
+                        CastContext::Implicit,
+                        &ScalarType::String,
+                    )?,
+                ),
                 ty => bail!("cannot subscript type {}", ecx.humanize_scalar_type(&ty)),
             };
 
-            expr.call_binary(
-                plan_expr(ecx, subscript)?.cast_to(
-                    "subscript (indexing)",
-                    ecx,
-                    CastContext::Explicit,
-                    &ScalarType::Int64,
-                )?,
-                func,
-            )
-            .into()
+            expr.call_binary(subscript, func).into()
         }
 
         Expr::SubscriptSlice { expr, positions } => {
@@ -2409,6 +2822,54 @@ fn plan_list(
     .into())
 }
 
+fn plan_map(
+    ecx: &ExprContext,
+    entries: &[MapEntry<Aug>],
+    type_hint: Option<&ScalarType>,
+) -> Result<CoercibleScalarExpr, anyhow::Error> {
+    let (value_type, exprs) = if entries.is_empty() {
+        if let Some(ScalarType::Map { value_type, .. }) = type_hint {
+            ((**value_type).clone(), vec![])
+        } else {
+            bail!("cannot determine type of empty map");
+        }
+    } else {
+        let type_hint = match type_hint {
+            Some(ScalarType::Map { value_type, .. }) => Some(&**value_type),
+            _ => None,
+        };
+        let mut keys = vec![];
+        let mut values = vec![];
+        for MapEntry { key, value } in entries {
+            keys.push(plan_expr(ecx, key)?.cast_to(
+                "MAP key",
+                ecx,
+                CastContext::Implicit,
+                &ScalarType::String,
+            )?);
+            values.push(match value {
+                // Special case nested MAP expressions so we can plumb
+                // the type hint through.
+                Expr::Map(entries) => plan_map(ecx, entries, type_hint)?,
+                _ => plan_expr(ecx, value)?,
+            });
+        }
+        let values = coerce_homogeneous_exprs("MAP expression", ecx, values, type_hint)?;
+        let value_type = ecx.scalar_type(&values[0]);
+        let exprs = keys
+            .into_iter()
+            .zip(values)
+            .flat_map(|(k, v)| vec![k, v])
+            .collect();
+        (value_type, exprs)
+    };
+    Ok(HirScalarExpr::CallVariadic {
+        func: VariadicFunc::MapBuild { value_type },
+        exprs,
+    }
+    .into())
+}
+
 /// Coerces a list of expressions such that all input expressions will be cast
 /// to the same type. If successful, returns a new list of expressions in the
 /// same order as the input, where each expression has the appropriate casts to
@@ -2467,6 +2928,21 @@ fn plan_aggregate(
 
     let name = normalize::object_name(sql_func.name.clone())?;
 
+    // Ordered-set aggregates take the values to aggregate from their `WITHIN
+    // GROUP` clause, and their remaining arguments directly.
+    let ordered_set = matches!(name.item.as_str(), "percentile_cont" | "percentile_disc");
+    if sql_func.within_group && !ordered_set {
+        bail!(
+            "{} is not an ordered-set aggregate, so it cannot have WITHIN GROUP",
+            name
+        );
+    } else if ordered_set && !sql_func.within_group {
+        bail!(
+            "WITHIN GROUP is required for ordered-set aggregate {}",
+            name
+        );
+    }
+
     // We follow PostgreSQL's rule here for mapping `count(*)` into the
     // generalized function selection framework. The rule is simple: the user
     // must type `count(*)`, but the function selection framework sees an empty
@@ -2475,17 +2951,87 @@ fn plan_aggregate(
     // rules to all aggregates, not just `count`, since we may one day support
     // user-defined aggregates, including user-defined aggregates that take no
     // parameters.
-    let args = match &sql_func.args {
-        FunctionArgs::Star => vec![],
-        FunctionArgs::Args(args) if args.is_empty() => {
+    let (args, order_by) = match &sql_func.args {
+        FunctionArgs::Star => (vec![], &[][..]),
+        FunctionArgs::Args { args, .. } if args.is_empty() => {
             bail!(
                 "{}(*) must be used to call a parameterless aggregate function",
                 name
             );
         }
-        FunctionArgs::Args(args) => plan_exprs(ecx, args)?,
+        FunctionArgs::Args { args, order_by } => {
+            if sql_func.distinct && order_by.iter().any(|obe| !args.contains(&obe.expr)) {
+                bail!(
+                    "in an aggregate with DISTINCT, ORDER BY expressions must appear in \
+                     argument list"
+                );
+            }
+            let args: Vec<_> = if sql_func.within_group {
+                args.iter()
+                    .chain(order_by.iter().map(|obe| &obe.expr))
+                    .collect()
+            } else {
+                args.iter().collect()
+            };
+            (plan_exprs(ecx, &args)?, &order_by[..])
+        }
     };
-    let (mut expr, func) = func::select_impl(ecx, FuncSpec::Func(&name), impls, args)?;
+    let (mut expr, mut func) = func::select_impl(ecx, FuncSpec::Func(&name), impls, args)?;
+    match &mut func {
+        AggregateFunc::ArrayAgg {
+            order_by: func_order_by,
+        }
+        | AggregateFunc::StringAgg {
+            order_by: func_order_by,
+        } => {
+            // Order-sensitive aggregates receive a record whose first field is
+            // their input and whose remaining fields are the values of the
+            // ORDER BY expressions, and sort by those fields when they are
+            // evaluated. Each aggregate thus sorts independently of any other
+            // aggregates in the same query.
+            let ecx = ecx.with_name("ORDER BY clause of an aggregate");
+            let mut exprs = vec![expr];
+            for obe in order_by {
+                exprs.push(plan_expr(&ecx, &obe.expr)?.type_as_any(&ecx)?);
+                let (desc, nulls_last) = resolve_desc_and_nulls_last(obe);
+                func_order_by.push(ColumnOrder {
+                    column: exprs.len() - 1,
+                    desc,
+                    nulls_last,
+                });
+            }
+            expr = HirScalarExpr::CallVariadic {
+                func: VariadicFunc::RecordCreate {
+                    field_names: (0..exprs.len())
+                        .map(|i| ColumnName::from(format!("f{}", i + 1)))
+                        .collect(),
+                },
+                exprs,
+            };
+        }
+        AggregateFunc::PercentileCont {
+            order_by: func_order_by,
+        }
+        | AggregateFunc::PercentileDisc {
+            order_by: func_order_by,
+        } => {
+            // Percentiles receive a record of their fraction and the value
+            // from the `WITHIN GROUP` clause, which they sort by. The function
+            // selection above guarantees that there is exactly one value.
+            let (desc, nulls_last) = resolve_desc_and_nulls_last(&order_by[0]);
+            func_order_by.push(ColumnOrder {
+                column: 1,
+                desc,
+                nulls_last,
+            });
+        }
+        AggregateFunc::JsonbAgg if !order_by.is_empty() => {
+            unsupported!("ORDER BY in jsonb_agg");
+        }
+        // The result of any other aggregate does not depend on the order of
+        // its inputs, so an ORDER BY clause can be ignored.
+        _ => (),
+    }
     if let Some(filter) = &sql_func.filter {
         // If a filter is present, as in
         //
@@ -2528,6 +3074,187 @@ fn plan_aggregate(
     })
 }
 
+/// Plans the window functions in `window_funcs` over `relation_expr`, whose
+/// columns are described by `scope`. The result of each function is appended
+/// to the columns of the relation, and `scope` is extended so that the
+/// function calls resolve to those columns.
+///
+/// The calls that share a window specification are computed together, by a
+/// reduction that groups the rows by the window's `PARTITION BY` expressions
+/// and collects each partition, ordered by the window's `ORDER BY`
+/// expressions, into a list of the rows and the results of the functions for
+/// them. Unnesting that list recovers the rows.
+fn plan_window_funcs(
+    qcx: &QueryContext,
+    mut relation_expr: HirRelationExpr,
+    scope: &mut Scope,
+    window_funcs: Vec<&Function<Aug>>,
+) -> Result<HirRelationExpr, anyhow::Error> {
+    let mut windows: Vec<(&WindowSpec<Aug>, Vec<&Function<Aug>>)> = vec![];
+    for func in window_funcs {
+        let over = func.over.as_ref().expect("window function without OVER");
+        match windows.iter_mut().find(|(window, _)| *window == over) {
+            Some((_, funcs)) => funcs.push(func),
+            None => windows.push((over, vec![func])),
+        }
+    }
+
+    for (window, funcs) in windows {
+        let arity = relation_expr.arity();
+        let ecx = &ExprContext {
+            qcx,
+            name: "window function",
+            scope,
+            relation_type: &qcx.relation_type(&relation_expr),
+            allow_aggregates: true,
+            allow_subqueries: true,
+        };
+
+        let partition_ecx = ecx.with_name("PARTITION BY clause of a window function");
+        let mut partition_exprs = vec![];
+        for expr in &window.partition_by {
+            partition_exprs.push(plan_expr(&partition_ecx, expr)?.type_as_any(&partition_ecx)?);
+        }
+
+        // The reduction receives a record of the row, the arguments of each
+        // function, and the values of the ORDER BY expressions, by which it
+        // sorts the partition.
+        let mut exprs = vec![HirScalarExpr::CallVariadic {
+            func: VariadicFunc::RecordCreate {
+                field_names: (0..arity)
+                    .map(|i| ColumnName::from(format!("f{}", i + 1)))
+                    .collect(),
+            },
+            exprs: (0..arity)
+                .map(|column| HirScalarExpr::Column(ColumnRef { level: 0, column }))
+                .collect(),
+        }];
+        let mut lag_lead_funcs = vec![];
+        for func in &funcs {
+            let (expr, lag_lead_func) = plan_window_func(ecx, func)?;
+            exprs.push(expr);
+            lag_lead_funcs.push(lag_lead_func);
+        }
+        let order_ecx = ecx.with_name("ORDER BY clause of a window function");
+        let mut order_by = vec![];
+        for obe in &window.order_by {
+            exprs.push(plan_expr(&order_ecx, &obe.expr)?.type_as_any(&order_ecx)?);
+            let (desc, nulls_last) = resolve_desc_and_nulls_last(obe);
+            order_by.push(ColumnOrder {
+                column: exprs.len() - 1,
+                desc,
+                nulls_last,
+            });
+        }
+        let aggregate = AggregateExpr {
+            func: AggregateFunc::LagLead {
+                funcs: lag_lead_funcs,
+                order_by,
+            },
+            expr: Box::new(HirScalarExpr::CallVariadic {
+                func: VariadicFunc::RecordCreate {
+                    field_names: (0..exprs.len())
+                        .map(|i| ColumnName::from(format!("f{}", i + 1)))
+                        .collect(),
+                },
+                exprs,
+            }),
+            distinct: false,
+        };
+
+        let partition_arity = partition_exprs.len();
+        let group_key = (arity..arity + partition_arity).collect();
+        let reduced =
+            relation_expr
+                .map(partition_exprs)
+                .reduce(group_key, vec![aggregate], None, false);
+        let el_typ = qcx.relation_type(&reduced).column_types[partition_arity]
+            .scalar_type
+            .unwrap_list_element_type()
+            .clone();
+        let joined = HirRelationExpr::Join {
+            left: Box::new(reduced),
+            right: Box::new(HirRelationExpr::CallTable {
+                func: TableFunc::UnnestList { el_typ },
+                exprs: vec![HirScalarExpr::Column(ColumnRef {
+                    level: 1,
+                    column: partition_arity,
+                })],
+            }),
+            on: HirScalarExpr::literal_true(),
+            kind: JoinKind::Inner { lateral: true },
+        };
+
+        // Unpack each record into the columns of the row, followed by the
+        // results of the functions.
+        let record = HirScalarExpr::Column(ColumnRef {
+            level: 0,
+            column: partition_arity + 1,
+        });
+        let mut outputs: Vec<_> = (0..arity)
+            .map(|i| {
+                record
+                    .clone()
+                    .call_unary(UnaryFunc::RecordGet(0))
+                    .call_unary(UnaryFunc::RecordGet(i))
+            })
+            .collect();
+        outputs.extend(
+            (0..funcs.len()).map(|i| record.clone().call_unary(UnaryFunc::RecordGet(1 + i))),
+        );
+        let first_output = partition_arity + 2;
+        let last_output = first_output + outputs.len();
+        relation_expr = joined
+            .map(outputs)
+            .project((first_output..last_output).collect());
+
+        for func in funcs {
+            scope.items.push(ScopeItem {
+                names: vec![],
+                expr: Some(Expr::Function(func.clone())),
+                nameable: true,
+            });
+        }
+    }
+    Ok(relation_expr)
+}
+
+/// Plans the arguments of the window function `sql_func`.
+fn plan_window_func(
+    ecx: &ExprContext,
+    sql_func: &Function<Aug>,
+) -> Result<(HirScalarExpr, LagLeadType), anyhow::Error> {
+    let impls = match resolve_func(ecx, &sql_func.name, &sql_func.args)? {
+        Func::Window(impls) => impls,
+        _ => unreachable!("plan_window_func called on non-window function"),
+    };
+    if sql_func.filter.is_some() {
+        bail!("FILTER is not implemented for non-aggregate window functions");
+    }
+    if sql_func.distinct {
+        bail!("DISTINCT is not implemented for window functions");
+    }
+    if sql_func.within_group {
+        bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            sql_func.name
+        );
+    }
+    let args = match &sql_func.args {
+        FunctionArgs::Star => bail!(
+            "* argument is invalid with non-aggregate function {}",
+            sql_func.name
+        ),
+        FunctionArgs::Args { order_by, .. } if !order_by.is_empty() => bail!(
+            "ORDER BY specified, but {} is not an aggregate function",
+            sql_func.name
+        ),
+        FunctionArgs::Args { args, .. } => plan_exprs(ecx, args)?,
+    };
+    let name = normalize::object_name(sql_func.name.clone())?;
+    func::select_impl(ecx, FuncSpec::Func(&name), impls, args)
+}
+
 fn plan_identifier(ecx: &ExprContext, names: &[Ident]) -> Result<HirScalarExpr, PlanError> {
     let mut names = names.to_vec();
     let col_name = normalize::column_name(names.pop().unwrap());
@@ -2588,16 +3315,110 @@ fn plan_identifier(ecx: &ExprContext, names: &[Ident]) -> Result<HirScalarExpr,
 
 fn plan_op(
     ecx: &ExprContext,
-    op: &str,
+    op: &Op,
     expr1: &Expr<Aug>,
     expr2: Option<&Expr<Aug>>,
 ) -> Result<HirScalarExpr, anyhow::Error> {
+    let op = normalize::op(op)?;
+    match expr2 {
+        None => {
+            let impls = func::resolve_op(op)?;
+            func::select_impl(ecx, FuncSpec::Op(op), impls, plan_exprs(ecx, &[expr1])?)
+        }
+        Some(expr2) => plan_binary_op(ecx, op, plan_expr(ecx, expr1)?, plan_expr(ecx, expr2)?),
+    }
+}
+
+fn plan_binary_op(
+    ecx: &ExprContext,
+    op: &str,
+    lhs: CoercibleScalarExpr,
+    rhs: CoercibleScalarExpr,
+) -> Result<HirScalarExpr, anyhow::Error> {
+    let is_record = |e: &CoercibleScalarExpr| {
+        matches!(e, CoercibleScalarExpr::LiteralRecord(_))
+            || matches!(ecx.scalar_type(e), Some(ScalarType::Record { .. }))
+    };
+    if matches!(op, "=" | "<>" | "<" | "<=" | ">" | ">=") && (is_record(&lhs) || is_record(&rhs)) {
+        return plan_record_comparison(ecx, op, lhs, rhs);
+    }
     let impls = func::resolve_op(op)?;
-    let args = match expr2 {
-        None => plan_exprs(ecx, &[expr1])?,
-        Some(expr2) => plan_exprs(ecx, &[expr1, expr2])?,
+    func::select_impl(ecx, FuncSpec::Op(op), impls, vec![lhs, rhs])
+}
+
+/// Plans a comparison of two records in terms of comparisons of their fields,
+/// as in PostgreSQL. Records are equal if all of their fields are equal, and
+/// are otherwise ordered by their first pair of unequal fields. The comparison
+/// is NULL if its outcome depends on a NULL field.
+fn plan_record_comparison(
+    ecx: &ExprContext,
+    op: &str,
+    lhs: CoercibleScalarExpr,
+    rhs: CoercibleScalarExpr,
+) -> Result<HirScalarExpr, anyhow::Error> {
+    let (lhs, rhs) = match (record_fields(ecx, op, lhs)?, record_fields(ecx, op, rhs)?) {
+        (Some(lhs), Some(rhs)) => (lhs, rhs),
+        // Comparing a record with NULL compares each of its fields with NULL.
+        (Some(lhs), None) => {
+            let rhs = vec![CoercibleScalarExpr::LiteralNull; lhs.len()];
+            (lhs, rhs)
+        }
+        (None, Some(rhs)) => {
+            let lhs = vec![CoercibleScalarExpr::LiteralNull; rhs.len()];
+            (lhs, rhs)
+        }
+        (None, None) => unreachable!("at least one side of a record comparison is a record"),
     };
-    func::select_impl(ecx, FuncSpec::Op(op), impls, args)
+    if lhs.len() != rhs.len() {
+        bail!("unequal number of entries in row expressions");
+    }
+    if lhs.is_empty() {
+        bail!("cannot compare rows of zero length");
+    }
+    let mut pairs = lhs.into_iter().zip(rhs).rev();
+    let (l, r) = pairs.next().expect("records are not empty");
+    let mut expr = plan_binary_op(ecx, op, l, r)?;
+    for (l, r) in pairs {
+        expr = match op {
+            "=" => plan_binary_op(ecx, "=", l, r)?.call_binary(expr, BinaryFunc::And),
+            "<>" => plan_binary_op(ecx, "<>", l, r)?.call_binary(expr, BinaryFunc::Or),
+            _ => {
+                // The fields decide the comparison if they differ, and defer
+                // to the remaining fields if they are equal.
+                let strict_op = &op[..1];
+                let decides = plan_binary_op(ecx, strict_op, l.clone(), r.clone())?;
+                let defers = plan_binary_op(ecx, "=", l, r)?.call_binary(expr, BinaryFunc::And);
+                decides.call_binary(defers, BinaryFunc::Or)
+            }
+        };
+    }
+    Ok(expr)
+}
+
+/// Returns the fields of `expr` as separate expressions, or `None` if `expr`
+/// is a NULL literal.
+fn record_fields(
+    ecx: &ExprContext,
+    op: &str,
+    expr: CoercibleScalarExpr,
+) -> Result<Option<Vec<CoercibleScalarExpr>>, anyhow::Error> {
+    let expr = match expr {
+        CoercibleScalarExpr::LiteralRecord(exprs) => return Ok(Some(exprs)),
+        CoercibleScalarExpr::LiteralNull => return Ok(None),
+        expr => expr.type_as_any(ecx)?,
+    };
+    match ecx.scalar_type(&expr) {
+        ScalarType::Record { fields, .. } => Ok(Some(
+            (0..fields.len())
+                .map(|i| expr.clone().call_unary(UnaryFunc::RecordGet(i)).into())
+                .collect(),
+        )),
+        ty => bail!(
+            "cannot compare record with type {} using {}",
+            ecx.humanize_scalar_type(&ty),
+            op
+        ),
+    }
 }
 
 fn plan_function<'a>(
@@ -2621,6 +3442,12 @@ fn plan_function<'a>(
                 format!("table function ({}) in scalar position", sql_func.name)
             );
         }
+        Func::Window(_) if sql_func.over.is_none() => {
+            bail!("window function {} requires an OVER clause", sql_func.name);
+        }
+        Func::Window(_) => {
+            bail!("window functions are not allowed in {}", ecx.name);
+        }
         Func::Scalar(impls) => impls,
     };
 
@@ -2633,13 +3460,23 @@ fn plan_function<'a>(
             sql_func.name
         );
     }
+    if sql_func.within_group {
+        bail!(
+            "WITHIN GROUP specified, but {} is not an aggregate function",
+            sql_func.name
+        );
+    }
 
     let args = match &sql_func.args {
         FunctionArgs::Star => bail!(
             "* argument is invalid with non-aggregate function {}",
             sql_func.name
         ),
-        FunctionArgs::Args(args) => plan_exprs(ecx, args)?,
+        FunctionArgs::Args { order_by, .. } if !order_by.is_empty() => bail!(
+            "ORDER BY specified, but {} is not an aggregate function",
+            sql_func.name
+        ),
+        FunctionArgs::Args { args, .. } => plan_exprs(ecx, args)?,
     };
 
     let name = normalize::object_name(sql_func.name.clone())?;
@@ -2664,7 +3501,7 @@ pub fn resolve_func(
     // message.
     let cexprs = match args {
         sql_parser::ast::FunctionArgs::Star => vec![],
-        sql_parser::ast::FunctionArgs::Args(args) => plan_exprs(ecx, &args)?,
+        sql_parser::ast::FunctionArgs::Args { args, .. } => plan_exprs(ecx, &args)?,
     };
 
     let types: Vec<_> = cexprs
@@ -3028,6 +3865,7 @@ impl<'a, 'ast> Visit<'ast, Aug> for AggregateFuncVisitor<'a, 'ast> {
                 filter,
                 over: _,
                 distinct: _,
+                within_group: _,
             } = func;
             if let Some(filter) = filter {
                 self.visit_expr(filter);
@@ -3046,6 +3884,117 @@ impl<'a, 'ast> Visit<'ast, Aug> for AggregateFuncVisitor<'a, 'ast> {
     }
 }
 
+/// Gathers the calls to window functions in a `SELECT`, in the manner of
+/// [`AggregateFuncVisitor`].
+struct WindowFuncVisitor<'a, 'ast> {
+    scx: &'a StatementContext<'a>,
+    funcs: Vec<&'ast Function<Aug>>,
+    within_window_func: bool,
+    err: Option<anyhow::Error>,
+}
+
+impl<'a, 'ast> WindowFuncVisitor<'a, 'ast> {
+    fn new(scx: &'a StatementContext<'a>) -> WindowFuncVisitor<'a, 'ast> {
+        WindowFuncVisitor {
+            scx,
+            funcs: Vec::new(),
+            within_window_func: false,
+            err: None,
+        }
+    }
+
+    fn into_result(self) -> Result<Vec<&'ast Function<Aug>>, anyhow::Error> {
+        match self.err {
+            Some(err) => Err(err),
+            None => {
+                // Dedup the calls while preserving their order, which keeps
+                // query plans reproducible.
+                let mut seen = HashSet::new();
+                Ok(self
+                    .funcs
+                    .into_iter()
+                    .filter(move |func| seen.insert(&**func))
+                    .collect())
+            }
+        }
+    }
+}
+
+impl<'a, 'ast> Visit<'ast, Aug> for WindowFuncVisitor<'a, 'ast> {
+    fn visit_function(&mut self, func: &'ast Function<Aug>) {
+        let item = match self.scx.resolve_function(func.name.clone()) {
+            Ok(i) => i,
+            // Catching missing functions later in planning improves error messages.
+            Err(_) => return,
+        };
+
+        // Window functions without an OVER clause are rejected when they are
+        // planned as scalar functions.
+        if let (Ok(Func::Window { .. }), Some(_)) = (item.func(), &func.over) {
+            if self.within_window_func {
+                self.err = Some(anyhow!("window function calls cannot be nested"));
+                return;
+            }
+            self.funcs.push(func);
+            let old_within_window_func = self.within_window_func;
+            self.within_window_func = true;
+            visit::visit_function(self, func);
+            self.within_window_func = old_within_window_func;
+            return;
+        }
+        visit::visit_function(self, func);
+    }
+
+    fn visit_query(&mut self, _query: &'ast Query<Aug>) {
+        // Don't go into subqueries.
+    }
+}
+
+/// Gathers the calls to `grouping` in a `SELECT`, in the manner of
+/// [`AggregateFuncVisitor`].
+struct GroupingFuncVisitor<'a, 'ast> {
+    scx: &'a StatementContext<'a>,
+    funcs: Vec<&'ast Function<Aug>>,
+}
+
+impl<'a, 'ast> GroupingFuncVisitor<'a, 'ast> {
+    fn new(scx: &'a StatementContext<'a>) -> GroupingFuncVisitor<'a, 'ast> {
+        GroupingFuncVisitor {
+            scx,
+            funcs: Vec::new(),
+        }
+    }
+
+    fn into_result(self) -> Vec<&'ast Function<Aug>> {
+        // Dedup the calls while preserving their order, which keeps query
+        // plans reproducible.
+        let mut seen = HashSet::new();
+        self.funcs
+            .into_iter()
+            .filter(move |func| seen.insert(&**func))
+            .collect()
+    }
+}
+
+impl<'a, 'ast> Visit<'ast, Aug> for GroupingFuncVisitor<'a, 'ast> {
+    fn visit_function(&mut self, func: &'ast Function<Aug>) {
+        if let Ok(item) = self.scx.resolve_function(func.name.clone()) {
+            let name = item.name();
+            if name.schema == "pg_catalog" && name.item == "grouping" {
+                // The arguments are not evaluated, so there is nothing to
+                // gather within them.
+                self.funcs.push(func);
+                return;
+            }
+        }
+        visit::visit_function(self, func);
+    }
+
+    fn visit_query(&mut self, _query: &'ast Query<Aug>) {
+        // Don't go into subqueries.
+    }
+}
+
 /// Specifies how long a query will live. This impacts whether the query is
 /// allowed to reason about the time at which it is running, e.g., by calling
 /// the `now()` function.
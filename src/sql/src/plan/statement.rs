@@ -117,6 +117,7 @@ pub fn describe(
         Statement::DropObjects(stmt) => ddl::describe_drop_objects(&scx, stmt)?,
         Statement::AlterObjectRename(stmt) => ddl::describe_alter_object_rename(&scx, stmt)?,
         Statement::AlterIndexOptions(stmt) => ddl::describe_alter_index_options(&scx, stmt)?,
+        Statement::AlterSourceOptions(stmt) => ddl::describe_alter_source_options(&scx, stmt)?,
 
         // `SHOW` statements.
         Statement::ShowColumns(stmt) => show::show_columns(&scx, stmt)?.describe()?,
@@ -136,6 +137,10 @@ pub fn describe(
         Statement::Declare(stmt) => scl::describe_declare(&scx, stmt)?,
         Statement::Fetch(stmt) => scl::describe_fetch(&scx, stmt)?,
         Statement::Close(stmt) => scl::describe_close(&scx, stmt)?,
+        Statement::CancelQuery(stmt) => scl::describe_cancel_query(&scx, stmt)?,
+        Statement::Listen(stmt) => scl::describe_listen(&scx, stmt)?,
+        Statement::Unlisten(stmt) => scl::describe_unlisten(&scx, stmt)?,
+        Statement::Notify(stmt) => scl::describe_notify(&scx, stmt)?,
 
         // DML statements.
         Statement::Insert(stmt) => dml::describe_insert(&scx, stmt)?,
@@ -199,6 +204,7 @@ pub fn plan(
         Statement::DropDatabase(stmt) => ddl::plan_drop_database(scx, stmt),
         Statement::DropObjects(stmt) => ddl::plan_drop_objects(scx, stmt),
         Statement::AlterIndexOptions(stmt) => ddl::plan_alter_index_options(scx, stmt),
+        Statement::AlterSourceOptions(stmt) => ddl::plan_alter_source_options(scx, stmt),
         Statement::AlterObjectRename(stmt) => ddl::plan_alter_object_rename(scx, stmt),
 
         // DML statements.
@@ -228,6 +234,10 @@ pub fn plan(
         Statement::Declare(stmt) => scl::plan_declare(scx, stmt),
         Statement::Fetch(stmt) => scl::plan_fetch(scx, stmt),
         Statement::Close(stmt) => scl::plan_close(scx, stmt),
+        Statement::CancelQuery(stmt) => scl::plan_cancel_query(scx, stmt),
+        Statement::Listen(stmt) => scl::plan_listen(scx, stmt),
+        Statement::Unlisten(stmt) => scl::plan_unlisten(scx, stmt),
+        Statement::Notify(stmt) => scl::plan_notify(scx, stmt),
 
         // TCL statements.
         Statement::StartTransaction(stmt) => tcl::plan_start_transaction(scx, stmt),
@@ -25,13 +25,16 @@ use ore::str::StrExt;
 use reqwest::Url;
 
 use dataflow_types::{
-    AvroEncoding, AvroOcfEncoding, AvroOcfSinkConnectorBuilder, Consistency, CsvEncoding,
-    DataEncoding, ExternalSourceConnector, FileSourceConnector, KafkaSinkConnectorBuilder,
-    KafkaSourceConnector, KinesisSourceConnector, ProtobufEncoding, RegexEncoding,
-    S3SourceConnector, SinkConnectorBuilder, SinkEnvelope, SourceConnector, SourceEnvelope,
+    AvroEncoding, AvroOcfEncoding, AvroOcfSinkConnectorBuilder, CompressionCodec, Consistency,
+    CsvEncoding, DataEncoding, DecodeErrorPolicy, ExternalSourceConnector, FileSourceConnector,
+    JsonEncoding, JsonSchemaEncoding, KafkaSinkConnectorBuilder, KafkaSourceConnector,
+    KinesisSourceConnector, NotifySinkConnectorBuilder, ProtobufEncoding, RegexEncoding,
+    S3SinkConnectorBuilder, S3SourceConnector, SinkConnectorBuilder, SinkEnvelope, SourceConnector,
+    SourceDedupe, SourceEnvelope,
 };
-use expr::GlobalId;
+use expr::{GlobalId, MirScalarExpr};
 use interchange::avro::{self, DebeziumDeduplicationStrategy, Encoder};
+use interchange::csv;
 use interchange::envelopes;
 use ore::collections::CollectionExt;
 use ore::iter::IteratorExt;
@@ -40,13 +43,13 @@ use repr::{strconv, RelationDesc, RelationType, ScalarType};
 
 use crate::ast::display::AstDisplay;
 use crate::ast::{
-    AlterIndexOptionsList, AlterIndexOptionsStatement, AlterObjectRenameStatement, AvroSchema,
-    ColumnOption, Compression, Connector, CreateDatabaseStatement, CreateIndexStatement,
-    CreateRoleOption, CreateRoleStatement, CreateSchemaStatement, CreateSinkStatement,
-    CreateSourceStatement, CreateTableStatement, CreateTypeAs, CreateTypeStatement,
-    CreateViewStatement, DataType, DropDatabaseStatement, DropObjectsStatement, Envelope, Expr,
-    Format, Ident, IfExistsBehavior, ObjectType, Raw, SqlOption, Statement, UnresolvedObjectName,
-    Value, WithOption,
+    AlterIndexOptionsList, AlterIndexOptionsStatement, AlterObjectRenameStatement,
+    AlterSourceOptionsStatement, AvroSchema, ColumnOption, Compression, Connector,
+    CreateDatabaseStatement, CreateIndexStatement, CreateRoleOption, CreateRoleStatement,
+    CreateSchemaStatement, CreateSinkStatement, CreateSourceStatement, CreateTableStatement,
+    CreateTypeAs, CreateTypeStatement, CreateViewStatement, DataType, DropDatabaseStatement,
+    DropObjectsStatement, Envelope, Expr, Format, Ident, IfExistsBehavior, ObjectType, Raw,
+    SqlOption, Statement, TableConstraint, UnresolvedObjectName, Value,
 };
 use crate::catalog::{CatalogItem, CatalogItemType};
 use crate::kafka_util;
@@ -136,9 +139,6 @@ pub fn plan_create_table(
     if !with_options.is_empty() {
         unsupported!("WITH options");
     }
-    if !constraints.is_empty() {
-        unsupported!("CREATE TABLE with constraints")
-    }
 
     let names: Vec<_> = columns
         .iter()
@@ -156,8 +156,12 @@ pub fn plan_create_table(
     // and NOT NULL constraints.
     let mut column_types = Vec::with_capacity(columns.len());
     let mut defaults = Vec::with_capacity(columns.len());
+    let mut keys = Vec::new();
+    let mut has_primary_key = false;
+    // CHECK constraints are planned once the table's description is known.
+    let mut check_exprs = vec![];
 
-    for c in columns {
+    for (i, c) in columns.iter().enumerate() {
         let ty = plan::scalar_type_from_sql(scx, &c.data_type)?;
         let mut nullable = true;
         let mut default = Expr::null();
@@ -170,6 +174,20 @@ pub fn plan_create_table(
                     query::plan_default_expr(scx, expr, &ty)?;
                     default = expr.clone();
                 }
+                ColumnOption::Unique { is_primary } => {
+                    if *is_primary {
+                        if has_primary_key {
+                            bail!(
+                                "multiple primary keys for table {} are not allowed",
+                                name.to_string().quoted()
+                            );
+                        }
+                        has_primary_key = true;
+                        nullable = false;
+                    }
+                    keys.push(vec![i]);
+                }
+                ColumnOption::Check(expr) => check_exprs.push((option.name.clone(), expr.clone())),
                 other => unsupported!(format!("CREATE TABLE with column constraint: {}", other)),
             }
         }
@@ -177,6 +195,52 @@ pub fn plan_create_table(
         defaults.push(default);
     }
 
+    for constraint in constraints {
+        match constraint {
+            TableConstraint::Unique {
+                columns,
+                is_primary,
+                ..
+            } => {
+                if *is_primary {
+                    if has_primary_key {
+                        bail!(
+                            "multiple primary keys for table {} are not allowed",
+                            name.to_string().quoted()
+                        );
+                    }
+                    has_primary_key = true;
+                }
+                let mut key = Vec::with_capacity(columns.len());
+                for column in columns {
+                    let column = normalize::column_name(column.clone());
+                    match names.iter().position(|n| *n == column) {
+                        Some(i) => {
+                            if *is_primary {
+                                column_types[i].nullable = false;
+                            }
+                            key.push(i);
+                        }
+                        None => bail!(
+                            "column {} named in key does not exist",
+                            column.as_str().quoted()
+                        ),
+                    }
+                }
+                keys.push(key);
+            }
+            TableConstraint::Check { name, expr } => {
+                check_exprs.push((name.clone(), (**expr).clone()))
+            }
+            other => unsupported!(format!("CREATE TABLE with constraint: {}", other)),
+        }
+    }
+    // Keys that cover the same columns are indistinguishable to `ON CONFLICT`.
+    let keys: Vec<_> = keys
+        .into_iter()
+        .unique_by(|key| key.iter().copied().sorted().dedup().collect::<Vec<_>>())
+        .collect();
+
     let typ = RelationType::new(column_types);
 
     let temporary = *temporary;
@@ -187,11 +251,52 @@ pub fn plan_create_table(
     };
     let desc = RelationDesc::new(typ, names.into_iter().map(Some));
 
+    // Unnamed constraints are named after the table and, if they refer to
+    // exactly one column, that column, as in PostgreSQL.
+    let mut checks: Vec<(String, Expr<Raw>)> = Vec::with_capacity(check_exprs.len());
+    for (constraint_name, expr) in check_exprs {
+        let planned = query::plan_check_expr(scx, &desc, expr.clone())?;
+        let constraint_name = match constraint_name {
+            Some(constraint_name) => {
+                let constraint_name = normalize::ident(constraint_name);
+                if checks.iter().any(|(n, _)| *n == constraint_name) {
+                    bail!(
+                        "constraint {} for relation {} already exists",
+                        constraint_name.quoted(),
+                        name.item.quoted()
+                    );
+                }
+                constraint_name
+            }
+            None => {
+                let support = planned.support();
+                let prefix = match support.iter().exactly_one() {
+                    Ok(i) => format!(
+                        "{}_{}_check",
+                        name.item,
+                        desc.get_name(*i).expect("table columns are named").as_str()
+                    ),
+                    Err(_) => format!("{}_check", name.item),
+                };
+                let mut constraint_name = prefix.clone();
+                let mut suffix = 0;
+                while checks.iter().any(|(n, _)| *n == constraint_name) {
+                    suffix += 1;
+                    constraint_name = format!("{}{}", prefix, suffix);
+                }
+                constraint_name
+            }
+        };
+        checks.push((constraint_name, expr));
+    }
+
     let create_sql = normalize::create_statement(&scx, Statement::CreateTable(stmt.clone()))?;
     let table = Table {
         create_sql,
         desc,
         defaults,
+        keys,
+        checks,
         temporary,
     };
     Ok(Plan::CreateTable {
@@ -320,20 +425,43 @@ pub fn plan_create_source(
                         0..=127 => *delimiter as u8,
                         _ => bail!("CSV delimiter must be an ASCII character"),
                     },
+                    column_types: None,
                 })
             }
-            Format::Json => unsupported!("JSON sources"),
+            Format::Json => unsupported!("JSON sources other than file sources"),
+            Format::JsonSchema { seed, .. } => match seed {
+                Some(value_schema) => DataEncoding::JsonSchema(JsonSchemaEncoding {
+                    value_schema: value_schema.clone(),
+                }),
+                None => unreachable!("CSR seed resolution should already have been called"),
+            },
             Format::Text => DataEncoding::Text,
+            Format::Parquet => unsupported!("Parquet sources"),
         })
     };
 
+    // The columns of `dedupe_key` are resolved once the source's columns are
+    // known.
+    let dedupe_key = match normalize::option_objects(with_options).remove("dedupe_key") {
+        None => None,
+        Some(SqlOption::Columns { columns, .. }) => Some(columns),
+        Some(_) => bail!("dedupe_key must be a parenthesized list of column names"),
+    };
+
     let mut with_options = normalize::options(with_options);
+    with_options.remove("dedupe_key");
 
     let mut consistency = Consistency::RealTime;
     let mut ts_frequency = Duration::from_secs(1);
+    let mut key_encoding = None;
 
     let (external_connector, mut encoding) = match connector {
-        Connector::Kafka { broker, topic, .. } => {
+        Connector::Kafka {
+            broker,
+            topic,
+            topic_pattern,
+            ..
+        } => {
             let config_options = kafka_util::extract_config(&mut with_options)?;
 
             consistency = match with_options.remove("consistency") {
@@ -369,6 +497,24 @@ pub fn plan_create_source(
                 bail!("`start_offset` is not yet implemented for BYO consistency sources.")
             }
 
+            let start_timestamp = match with_options.remove("start_timestamp") {
+                None => None,
+                Some(Value::String(s)) => match strconv::parse_timestamptz(&s) {
+                    Ok(ts) => Some(ts.timestamp_millis()),
+                    Err(e) => bail!("invalid start_timestamp: {}", e),
+                },
+                Some(_) => bail!("start_timestamp must be a string"),
+            };
+
+            if start_timestamp.is_some() {
+                if start_offset != 0 {
+                    bail!("cannot specify both start_offset and start_timestamp");
+                }
+                if consistency != Consistency::RealTime {
+                    unsupported!("start_timestamp with BYO consistency");
+                }
+            }
+
             let enable_caching = match with_options.remove("cache") {
                 None => false,
                 Some(Value::Boolean(b)) => b,
@@ -379,6 +525,41 @@ pub fn plan_create_source(
                 unsupported!("BYO source caching")
             }
 
+            let include_headers = match with_options.remove("include_headers") {
+                None => false,
+                Some(Value::Boolean(b)) => b,
+                Some(_) => bail!("include_headers must be a boolean"),
+            };
+
+            if include_headers && enable_caching {
+                unsupported!("caching sources that include headers")
+            }
+
+            let include_timestamp_type = match with_options.remove("include_timestamp_type") {
+                None => false,
+                Some(Value::Boolean(b)) => b,
+                Some(_) => bail!("include_timestamp_type must be a boolean"),
+            };
+
+            if include_timestamp_type && enable_caching {
+                unsupported!("caching sources that include timestamp types")
+            }
+
+            if *topic_pattern {
+                if let Err(e) = Regex::new(topic) {
+                    bail!("invalid TOPIC PATTERN: {}", e);
+                }
+                if consistency != Consistency::RealTime {
+                    unsupported!("TOPIC PATTERN with BYO consistency");
+                }
+                if start_offset != 0 {
+                    bail!("start_offset is not supported with TOPIC PATTERN");
+                }
+                if enable_caching {
+                    unsupported!("caching sources with a TOPIC PATTERN");
+                }
+            }
+
             let mut start_offsets = HashMap::new();
             start_offsets.insert(0, start_offset);
 
@@ -387,12 +568,17 @@ pub fn plan_create_source(
                 topic: topic.clone(),
                 config_options,
                 start_offsets,
+                start_timestamp,
                 group_id_prefix,
                 cluster_id: scx.catalog.config().cluster_id,
                 enable_caching,
                 cached_files: None,
+                include_headers,
+                include_timestamp_type,
+                topic_pattern: *topic_pattern,
             });
             let encoding = get_encoding(format)?;
+            key_encoding = extract_key_encoding_options(&mut with_options, &encoding)?;
             (connector, encoding)
         }
         Connector::Kinesis { arn, .. } => {
@@ -415,9 +601,18 @@ pub fn plan_create_source(
                 .ok_or_else(|| anyhow!("Provided ARN does not include an AWS region"))?;
 
             let aws_info = normalize::aws_connect_info(&mut with_options, Some(region))?;
+            let shard_refresh = match with_options.remove("shard_refresh_ms") {
+                None => Duration::from_secs(60),
+                Some(Value::Number(n)) => match n.parse::<u64>() {
+                    Ok(n) if n > 0 => Duration::from_millis(n),
+                    _ => bail!("shard_refresh_ms must be a positive u64"),
+                },
+                Some(_) => bail!("shard_refresh_ms must be a positive u64"),
+            };
             let connector = ExternalSourceConnector::Kinesis(KinesisSourceConnector {
                 stream_name,
                 aws_info,
+                shard_refresh,
             });
             let encoding = get_encoding(format)?;
             (connector, encoding)
@@ -434,6 +629,11 @@ pub fn plan_create_source(
                 Some(_) => bail!("consistency must be a string"),
             };
             ts_frequency = extract_timestamp_frequency_option(&mut with_options)?;
+            let line_delimiter = match with_options.remove("line_delimiter") {
+                None => b"\n".to_vec(),
+                Some(Value::String(s)) => parse_line_delimiter(&s)?,
+                Some(_) => bail!("line_delimiter must be a string"),
+            };
 
             let connector = ExternalSourceConnector::File(FileSourceConnector {
                 path: path.clone().into(),
@@ -442,8 +642,23 @@ pub fn plan_create_source(
                     Compression::None => dataflow_types::Compression::None,
                 },
                 tail,
+                line_delimiter,
             });
-            let encoding = get_encoding(format)?;
+            let encoding = match format {
+                Some(Format::Json) => {
+                    let on_decode_error = match with_options.remove("on_decode_error") {
+                        None => DecodeErrorPolicy::Fail,
+                        Some(Value::String(s)) => match s.to_lowercase().as_str() {
+                            "fail" => DecodeErrorPolicy::Fail,
+                            "skip" => DecodeErrorPolicy::Skip,
+                            _ => bail!("on_decode_error must be 'fail' or 'skip'"),
+                        },
+                        Some(_) => bail!("on_decode_error must be a string"),
+                    };
+                    DataEncoding::Json(JsonEncoding { on_decode_error })
+                }
+                _ => get_encoding(format)?,
+            };
             (connector, encoding)
         }
         Connector::S3 {
@@ -452,6 +667,16 @@ pub fn plan_create_source(
         } => {
             scx.require_experimental_mode("S3 Sources")?;
             let aws_info = normalize::aws_connect_info(&mut with_options, None)?;
+            let compression = match with_options.remove("compression") {
+                None => Some(dataflow_types::Compression::None),
+                Some(Value::String(s)) => match s.to_lowercase().as_str() {
+                    "none" => Some(dataflow_types::Compression::None),
+                    "gzip" => Some(dataflow_types::Compression::Gzip),
+                    "auto" => None,
+                    _ => bail!("compression must be 'none', 'gzip', or 'auto'"),
+                },
+                Some(_) => bail!("compression must be a string"),
+            };
             let mut converted_sources = Vec::new();
             for ks in key_sources {
                 let dtks = match ks {
@@ -475,6 +700,7 @@ pub fn plan_create_source(
                     })
                     .transpose()?,
                 aws_info,
+                compression,
             });
             let encoding = get_encoding(format)?;
             (connector, encoding)
@@ -497,6 +723,7 @@ pub fn plan_create_source(
                 path: path.clone().into(),
                 compression: dataflow_types::Compression::None,
                 tail,
+                line_delimiter: vec![],
             });
             if format.is_some() {
                 bail!("avro ocf sources cannot specify a format");
@@ -511,8 +738,44 @@ pub fn plan_create_source(
             let encoding = DataEncoding::AvroOcf(AvroOcfEncoding { reader_schema });
             (connector, encoding)
         }
+        Connector::S3Bucket { .. } => {
+            bail!("S3 BUCKET is only valid for sinks; use S3 OBJECTS FROM SCAN BUCKET for sources")
+        }
+        Connector::Notify { .. } => bail!("NOTIFY is only valid for sinks"),
     };
 
+    // Purification samples the rows of CSV sources that ask for type
+    // inference, and records the inferred types in the `column_types` option,
+    // so that planning the source again after a restart yields the same types.
+    let mut infer_types = false;
+    if let DataEncoding::Csv(CsvEncoding {
+        n_cols,
+        column_types,
+        ..
+    }) = &mut encoding
+    {
+        infer_types = match with_options.remove("infer_types") {
+            None => false,
+            Some(Value::Boolean(b)) => b,
+            Some(_) => bail!("infer_types must be a boolean"),
+        };
+        with_options.remove("infer_sample_rows");
+        if let Some(value) = with_options.remove("column_types") {
+            let types = match value {
+                Value::String(s) => csv::decode_column_types(&s)?,
+                _ => bail!("column_types must be a string"),
+            };
+            if types.len() != *n_cols {
+                bail!(
+                    "column_types specifies {} columns, but CSV source has {}",
+                    types.len(),
+                    n_cols
+                );
+            }
+            *column_types = Some(types);
+        }
+    }
+
     // TODO (materialize#2537): cleanup format validation
     // Avro format validation is different for the Debezium envelope
     // vs the Upsert envelope.
@@ -626,6 +889,23 @@ pub fn plan_create_source(
         }
     }
 
+    if let (DataEncoding::JsonSchema(_), SourceEnvelope::Debezium(_)) = (&encoding, &envelope) {
+        unsupported!("Debezium envelope for JSON Schema sources");
+    }
+
+    if key_encoding.is_some() {
+        match &envelope {
+            SourceEnvelope::None => (),
+            SourceEnvelope::Upsert(_) => {
+                bail!("ENVELOPE UPSERT always includes the key; include_key is not needed")
+            }
+            _ => unsupported!("include_key with this envelope"),
+        }
+        if let DataEncoding::Json(_) | DataEncoding::JsonSchema(_) = &encoding {
+            unsupported!("include_key for FORMAT JSON");
+        }
+    }
+
     let mut desc = encoding.desc(&envelope)?;
     let ignore_source_keys = match with_options.remove("ignore_source_keys") {
         None => false,
@@ -637,6 +917,12 @@ pub fn plan_create_source(
     }
 
     desc = plan_utils::maybe_rename_columns(format!("source {}", name), desc, &col_names)?;
+    let rename_columns = match with_options.remove("rename_columns") {
+        None => None,
+        Some(Value::String(spec)) => Some(spec),
+        Some(_) => bail!("rename_columns must be a string"),
+    };
+    let value_arity = desc.arity();
 
     // TODO(benesch): the available metadata columns should not depend
     // on the format.
@@ -646,7 +932,25 @@ pub fn plan_create_source(
     match (&encoding, &envelope) {
         (DataEncoding::Avro { .. }, _)
         | (DataEncoding::Protobuf { .. }, _)
-        | (_, SourceEnvelope::Debezium(_)) => (),
+        | (DataEncoding::JsonSchema { .. }, _)
+        | (_, SourceEnvelope::Debezium(_)) => {
+            if let ExternalSourceConnector::Kafka(kc) = &external_connector {
+                if kc.include_headers {
+                    bail!(
+                        "include_headers is only supported for FORMAT BYTES, TEXT, CSV, and REGEX"
+                    )
+                }
+                if kc.include_timestamp_type {
+                    bail!(
+                        "include_timestamp_type is only supported for FORMAT BYTES, TEXT, CSV, \
+                         and REGEX"
+                    )
+                }
+                if kc.topic_pattern {
+                    bail!("TOPIC PATTERN is only supported for FORMAT BYTES, TEXT, CSV, and REGEX")
+                }
+            }
+        }
         _ => {
             for (name, ty) in external_connector.metadata_columns() {
                 desc = desc.with_column(name, ty);
@@ -654,6 +958,71 @@ pub fn plan_create_source(
         }
     }
 
+    // The key columns follow all other columns, and are null for messages
+    // without a key.
+    if let Some(key_encoding) = &key_encoding {
+        for (name, ty) in key_encoding.desc(&SourceEnvelope::None)?.iter() {
+            let name = format!("key_{}", name.map(|n| n.as_str()).unwrap_or("?column?"));
+            desc = desc.with_column(name, ty.scalar_type.clone().nullable(true));
+        }
+    }
+
+    // Only the columns decoded from the value are renamed, but the metadata
+    // and key columns are appended first, so that renamed columns cannot
+    // collide with them.
+    if let Some(spec) = rename_columns {
+        desc = plan_utils::rename_columns_matching(desc, &spec, value_arity)?;
+    }
+
+    let inferred_column_types = match &encoding {
+        DataEncoding::Csv(CsvEncoding { n_cols, .. }) if infer_types => desc
+            .iter()
+            .take(*n_cols)
+            .map(|(name, ty)| (name.cloned().unwrap(), ty.scalar_type.clone()))
+            .collect(),
+        _ => vec![],
+    };
+
+    let dedupe_window = match with_options.remove("dedupe_window") {
+        None => None,
+        Some(Value::String(s)) => Some(strconv::parse_interval(&s)?),
+        Some(Value::Interval(iv)) => Some(strconv::parse_interval(&iv.value)?),
+        Some(_) => bail!("dedupe_window must be an interval"),
+    };
+    let dedupe = match (dedupe_key, dedupe_window) {
+        (None, None) => None,
+        (Some(columns), Some(window)) => {
+            if !matches!(envelope, SourceEnvelope::None) {
+                bail!("dedupe_key is only supported for sources with ENVELOPE NONE");
+            }
+            let key_indices = columns
+                .into_iter()
+                .map(|column| {
+                    let name = normalize::column_name(column);
+                    match desc.get_by_name(&name) {
+                        Some((i, _)) => Ok(i),
+                        None => bail!(
+                            "column {} in dedupe_key does not exist",
+                            name.as_str().quoted()
+                        ),
+                    }
+                })
+                .collect::<Result<Vec<_>, anyhow::Error>>()?;
+            if window.months != 0 {
+                bail!("dedupe_window must not contain months or years");
+            }
+            let window_ms = window.duration_as_chrono().num_milliseconds();
+            if window_ms <= 0 {
+                bail!("dedupe_window must be positive");
+            }
+            Some(SourceDedupe {
+                key_indices,
+                window_ms: window_ms as u64,
+            })
+        }
+        _ => bail!("dedupe_key and dedupe_window must be specified together"),
+    };
+
     let if_not_exists = *if_not_exists;
     let materialized = *materialized;
     let name = scx.allocate_name(normalize::object_name(name.clone())?);
@@ -664,9 +1033,11 @@ pub fn plan_create_source(
         connector: SourceConnector::External {
             connector: external_connector,
             encoding,
+            key_encoding,
             envelope,
             consistency,
             ts_frequency,
+            dedupe,
         },
         desc,
     };
@@ -683,6 +1054,7 @@ pub fn plan_create_source(
         source,
         if_not_exists,
         materialized,
+        inferred_column_types,
     })
 }
 
@@ -708,8 +1080,17 @@ pub fn plan_create_view(
         if_exists,
         with_options,
     } = &mut stmt;
+    let mut with_options = normalize::options(with_options);
+    let retain_history = match with_options.remove("retain_history") {
+        None => None,
+        Some(Value::String(s)) => Some(parse_duration::parse(&s)?),
+        Some(_) => bail!("retain_history must be a string"),
+    };
     if !with_options.is_empty() {
-        unsupported!("WITH options");
+        bail!(
+            "unexpected parameters for CREATE VIEW: {}",
+            with_options.keys().join(",")
+        )
     }
     let name = if *temporary {
         scx.allocate_temporary_name(normalize::object_name(name.to_owned())?)
@@ -749,6 +1130,7 @@ pub fn plan_create_view(
             expr: relation_expr,
             column_names: desc.iter_names().map(|n| n.cloned()).collect(),
             temporary,
+            retain_history,
         },
         replace,
         materialize,
@@ -765,6 +1147,7 @@ fn kafka_sink_builder(
     key_desc_and_indices: Option<(RelationDesc, Vec<usize>)>,
     value_desc: RelationDesc,
     topic_suffix: String,
+    partition_by: Option<(MirScalarExpr, ScalarType)>,
 ) -> Result<SinkConnectorBuilder, anyhow::Error> {
     let (schema_registry_url, ccsr_with_options) = match format {
         Some(Format::Avro(AvroSchema::CsrUrl {
@@ -782,12 +1165,27 @@ fn kafka_sink_builder(
 
     let broker_addrs = broker.parse()?;
 
-    let include_consistency = match with_options.remove("consistency") {
+    let mut include_consistency = match with_options.remove("consistency") {
         Some(Value::Boolean(b)) => b,
         None => false,
         Some(_) => bail!("consistency must be a boolean"),
     };
 
+    let consistency_topic = match with_options.remove("consistency_topic") {
+        Some(Value::String(topic)) => {
+            if include_consistency {
+                bail!("consistency and consistency_topic cannot be specified together");
+            }
+            if topic.is_empty() {
+                bail!("consistency_topic must not be empty");
+            }
+            include_consistency = true;
+            Some(topic)
+        }
+        None => None,
+        Some(_) => bail!("consistency_topic must be a string"),
+    };
+
     let encoder = Encoder::new(
         key_desc_and_indices
             .as_ref()
@@ -826,6 +1224,28 @@ fn kafka_sink_builder(
         );
     }
 
+    // Partitioning by an expression requires knowing the number of
+    // partitions, which the broker default would hide.
+    if partition_by.is_some() && partition_count == -1 {
+        bail!("partition_by requires an explicit partition_count");
+    }
+
+    let compression_codec = match with_options.remove("compression_codec") {
+        None => CompressionCodec::None,
+        Some(Value::String(codec)) => match codec.to_lowercase().as_str() {
+            "none" => CompressionCodec::None,
+            "gzip" => CompressionCodec::Gzip,
+            "snappy" => CompressionCodec::Snappy,
+            "lz4" => CompressionCodec::Lz4,
+            "zstd" => CompressionCodec::Zstd,
+            _ => bail!(
+                "unknown compression_codec: {}; expected one of 'none', 'gzip', 'snappy', 'lz4', or 'zstd'",
+                codec
+            ),
+        },
+        Some(_) => bail!("compression_codec must be a string"),
+    };
+
     let consistency_value_schema = if include_consistency {
         Some(avro::get_debezium_transaction_schema().canonical_form())
     } else {
@@ -846,8 +1266,11 @@ fn kafka_sink_builder(
         topic_suffix,
         partition_count,
         replication_factor,
+        partition_by,
         fuel: 10000,
         consistency_value_schema,
+        consistency_topic,
+        compression_codec,
         config_options,
         ccsr_config,
         key_schema,
@@ -856,6 +1279,46 @@ fn kafka_sink_builder(
     }))
 }
 
+/// Plans the `partition_by` option of a Kafka sink, if present, as an
+/// expression over `desc`, the description of the sink's input, and the
+/// expression's type.
+fn plan_partition_by(
+    scx: &StatementContext,
+    with_options: &mut BTreeMap<String, Value>,
+    desc: &RelationDesc,
+    envelope: SinkEnvelope,
+) -> Result<Option<(MirScalarExpr, ScalarType)>, anyhow::Error> {
+    let expr = match with_options.remove("partition_by") {
+        None => return Ok(None),
+        Some(Value::String(s)) => sql_parser::parser::parse_expr(&s)
+            .map_err(|e| anyhow!("invalid partition_by expression: {}", e))?,
+        Some(_) => bail!("partition_by must be a string containing a SQL expression"),
+    };
+    // Upsert sinks write tombstones for deleted keys, which must land in the
+    // same partition as the key's earlier messages for log compaction to
+    // remove them.
+    if envelope == SinkEnvelope::Upsert {
+        bail!("partition_by is not supported for upsert sinks");
+    }
+    let (expr, ty) = query::plan_partition_by_expr(scx, desc, expr)?;
+    match ty {
+        ScalarType::Bool
+        | ScalarType::Int32
+        | ScalarType::Int64
+        | ScalarType::Decimal(..)
+        | ScalarType::Date
+        | ScalarType::Timestamp
+        | ScalarType::TimestampTz
+        | ScalarType::Bytes
+        | ScalarType::String
+        | ScalarType::Uuid => Ok(Some((expr, ty))),
+        _ => bail!(
+            "partition_by expression must not have type {}",
+            scx.humanize_scalar_type(&ty)
+        ),
+    }
+}
+
 fn avro_ocf_sink_builder(
     format: Option<Format>,
     path: String,
@@ -879,6 +1342,90 @@ fn avro_ocf_sink_builder(
     }))
 }
 
+fn notify_sink_builder(
+    format: Option<Format>,
+    envelope: SinkEnvelope,
+    channel: Ident,
+    desc: &RelationDesc,
+    value_desc: RelationDesc,
+) -> Result<SinkConnectorBuilder, anyhow::Error> {
+    if format.is_some() {
+        bail!("NOTIFY sinks cannot specify a format");
+    }
+    if envelope != SinkEnvelope::Debezium {
+        bail!("NOTIFY sinks cannot specify an envelope");
+    }
+    // Each new row is sent as the payload of one notification.
+    let typ = desc.typ();
+    if typ.column_types.len() != 1 || typ.column_types[0].scalar_type != ScalarType::String {
+        bail!("NOTIFY sinks require a relation with exactly one column of type text");
+    }
+    Ok(SinkConnectorBuilder::Notify(NotifySinkConnectorBuilder {
+        channel: normalize::ident(channel),
+        value_desc,
+    }))
+}
+
+/// The default size at which a Parquet file of an S3 sink is completed.
+const DEFAULT_S3_MAX_FILE_BYTES: u64 = 128 << 20;
+
+/// The largest object that S3 accepts in a single upload.
+const MAX_S3_MAX_FILE_BYTES: u64 = 5 << 30;
+
+/// The default age at which a Parquet file of an S3 sink is completed.
+const DEFAULT_S3_MAX_FILE_AGE: Duration = Duration::from_secs(60);
+
+fn s3_sink_builder(
+    format: Option<Format>,
+    with_options: &mut BTreeMap<String, Value>,
+    bucket: String,
+    prefix: Option<String>,
+    value_desc: RelationDesc,
+) -> Result<SinkConnectorBuilder, anyhow::Error> {
+    match format {
+        Some(Format::Parquet) => (),
+        None => bail!("S3 sinks must specify FORMAT PARQUET"),
+        Some(_) => unsupported!("S3 sinks with formats other than PARQUET"),
+    }
+
+    let max_file_bytes = match with_options.remove("max_file_bytes") {
+        None => DEFAULT_S3_MAX_FILE_BYTES,
+        Some(Value::Number(n)) => match n.parse::<u64>() {
+            Ok(n) if n > 0 && n <= MAX_S3_MAX_FILE_BYTES => n,
+            _ => bail!(
+                "max_file_bytes must be a positive integer no larger than {}",
+                MAX_S3_MAX_FILE_BYTES
+            ),
+        },
+        Some(_) => bail!("max_file_bytes must be an integer"),
+    };
+
+    let max_file_age = match with_options.remove("max_file_age_ms") {
+        None => DEFAULT_S3_MAX_FILE_AGE,
+        Some(Value::Number(n)) => match n.parse::<u64>() {
+            Ok(n) if n > 0 => Duration::from_millis(n),
+            _ => bail!("max_file_age_ms must be a positive integer"),
+        },
+        Some(_) => bail!("max_file_age_ms must be an integer"),
+    };
+
+    let aws_info = normalize::aws_connect_info(with_options, None)?;
+
+    // Reject relations that cannot be written to Parquet now, rather than
+    // when the first file is written.
+    interchange::parquet::Encoder::new(value_desc.clone())
+        .map_err(|e| anyhow!("unable to create Parquet sink: {}", e))?;
+
+    Ok(SinkConnectorBuilder::S3(S3SinkConnectorBuilder {
+        bucket,
+        prefix: prefix.unwrap_or_default(),
+        aws_info,
+        value_desc,
+        max_file_bytes,
+        max_file_age,
+    }))
+}
+
 pub fn describe_create_sink(
     _: &StatementContext,
     _: CreateSinkStatement<Raw>,
@@ -960,8 +1507,16 @@ pub fn plan_create_sink(
         Connector::Kinesis { .. } => None,
         Connector::AvroOcf { .. } => None,
         Connector::S3 { .. } => None,
+        Connector::S3Bucket { .. } => None,
+        Connector::Notify { .. } => None,
     };
 
+    // Upsert sinks express deletions as tombstones for a key, which are
+    // meaningless without one.
+    if envelope == SinkEnvelope::Upsert && key_indices.is_none() {
+        bail!("upsert sinks must specify a KEY");
+    }
+
     let key_desc_and_indices = key_indices.map(|key_indices| {
         let cols = desc.clone().into_iter().collect::<Vec<_>>();
         let (names, types): (Vec<_>, Vec<_>) =
@@ -981,18 +1536,33 @@ pub fn plan_create_sink(
     let as_of = as_of.map(|e| query::eval_as_of(scx, e)).transpose()?;
     let connector_builder = match connector {
         Connector::File { .. } => unsupported!("file sinks"),
-        Connector::Kafka { broker, topic, .. } => kafka_sink_builder(
-            format,
-            &mut with_options,
-            broker,
-            topic,
-            key_desc_and_indices,
-            value_desc,
-            suffix,
-        )?,
+        Connector::Kafka {
+            topic_pattern: true,
+            ..
+        } => unsupported!("TOPIC PATTERN for sinks"),
+        Connector::Kafka { broker, topic, .. } => {
+            let partition_by = plan_partition_by(scx, &mut with_options, &desc, envelope)?;
+            kafka_sink_builder(
+                format,
+                &mut with_options,
+                broker,
+                topic,
+                key_desc_and_indices,
+                value_desc,
+                suffix,
+                partition_by,
+            )?
+        }
         Connector::Kinesis { .. } => unsupported!("Kinesis sinks"),
         Connector::AvroOcf { path } => avro_ocf_sink_builder(format, path, suffix, value_desc)?,
-        Connector::S3 { .. } => unsupported!("S3 sinks"),
+        Connector::S3 { .. } => bail!("S3 sinks must use INTO S3 BUCKET"),
+        Connector::S3Bucket { bucket, prefix } => {
+            scx.require_experimental_mode("S3 Sinks")?;
+            s3_sink_builder(format, &mut with_options, bucket, prefix, value_desc)?
+        }
+        Connector::Notify { channel } => {
+            notify_sink_builder(format, envelope, channel, &desc, value_desc)?
+        }
     };
 
     if !with_options.is_empty() {
@@ -1116,7 +1686,15 @@ pub fn plan_create_index(
         index_name
     };
 
-    let options = plan_index_options(with_options.clone())?;
+    let with_opts = IndexWithOptions::try_from(with_options.clone())?;
+    let memory_limit_bytes = match with_opts.memory_limit_bytes {
+        None => None,
+        Some(n) => match usize::try_from(n) {
+            Ok(n) if n > 0 => Some(n),
+            _ => bail!("memory_limit_bytes must be a positive integer"),
+        },
+    };
+    let options = plan_index_options(&with_opts)?;
 
     // Normalize `stmt`.
     *name = Some(Ident::new(index_name.item.clone()));
@@ -1130,6 +1708,7 @@ pub fn plan_create_index(
             create_sql,
             on: on.id(),
             keys,
+            memory_limit_bytes,
         },
         options,
         if_not_exists,
@@ -1262,6 +1841,140 @@ fn extract_timestamp_frequency_option(
     }
 }
 
+/// Parses the `line_delimiter` option of a file source, in which `\n`, `\r`,
+/// `\t`, `\0`, `\\`, and `\xNN` escape the corresponding bytes.
+fn parse_line_delimiter(s: &str) -> Result<Vec<u8>, anyhow::Error> {
+    let mut out = vec![];
+    let mut bytes = s.bytes();
+    while let Some(b) = bytes.next() {
+        if b != b'\\' {
+            out.push(b);
+            continue;
+        }
+        match bytes.next() {
+            Some(b'n') => out.push(b'\n'),
+            Some(b'r') => out.push(b'\r'),
+            Some(b't') => out.push(b'\t'),
+            Some(b'0') => out.push(b'\0'),
+            Some(b'\\') => out.push(b'\\'),
+            Some(b'x') => {
+                let hex: Vec<u8> = bytes.by_ref().take(2).collect();
+                match std::str::from_utf8(&hex)
+                    .ok()
+                    .filter(|hex| hex.len() == 2 && hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                {
+                    Some(b) => out.push(b),
+                    None => bail!("invalid hexadecimal escape in line_delimiter"),
+                }
+            }
+            Some(b) => bail!(
+                "invalid escape sequence \\{} in line_delimiter",
+                char::from(b)
+            ),
+            None => bail!("line_delimiter must not end with an unescaped backslash"),
+        }
+    }
+    if out.is_empty() {
+        bail!("line_delimiter must not be empty");
+    }
+    Ok(out)
+}
+
+/// Extracts the `include_key` option of a Kafka source, and the options that
+/// describe how to decode its keys. Unless `key_format` says otherwise, keys
+/// are decoded like the source's values.
+fn extract_key_encoding_options(
+    with_options: &mut BTreeMap<String, Value>,
+    value_encoding: &DataEncoding,
+) -> Result<Option<DataEncoding>, anyhow::Error> {
+    let include_key = match with_options.remove("include_key") {
+        None => false,
+        Some(Value::Boolean(b)) => b,
+        Some(_) => bail!("include_key must be a boolean"),
+    };
+    let key_format = match with_options.remove("key_format") {
+        None => None,
+        Some(Value::String(s)) => Some(s.to_lowercase()),
+        Some(_) => bail!("key_format must be a string"),
+    };
+    let key_csv_columns_err = "key_csv_columns must be a positive integer";
+    let key_csv_columns = match with_options.remove("key_csv_columns") {
+        None => None,
+        Some(Value::Number(n)) => match n.parse::<usize>() {
+            Ok(n) if n > 0 => Some(n),
+            _ => bail!(key_csv_columns_err),
+        },
+        Some(_) => bail!(key_csv_columns_err),
+    };
+    let key_schema = match with_options.remove("key_schema") {
+        None => None,
+        Some(Value::String(s)) => Some(s),
+        Some(_) => bail!("key_schema must be a string"),
+    };
+
+    if !include_key {
+        if key_format.is_some() || key_csv_columns.is_some() || key_schema.is_some() {
+            bail!("key_format, key_csv_columns, and key_schema require include_key = true");
+        }
+        return Ok(None);
+    }
+
+    let key_format = match (key_format, value_encoding) {
+        (Some(format), _) => format,
+        (None, DataEncoding::Bytes) => "bytes".into(),
+        (None, DataEncoding::Text) => "text".into(),
+        (None, DataEncoding::Csv(_)) => "csv".into(),
+        (None, DataEncoding::Avro(_)) => "avro".into(),
+        (None, _) => bail!("include_key requires a key_format for this source's format"),
+    };
+    let encoding = match key_format.as_str() {
+        "bytes" => DataEncoding::Bytes,
+        "text" => DataEncoding::Text,
+        "csv" => {
+            let (n_cols, delimiter) = match (key_csv_columns, value_encoding) {
+                (Some(n_cols), DataEncoding::Csv(enc)) => (n_cols, enc.delimiter),
+                (Some(n_cols), _) => (n_cols, b','),
+                (None, DataEncoding::Csv(enc)) => (enc.n_cols, enc.delimiter),
+                (None, _) => bail!("key_format = 'csv' requires key_csv_columns"),
+            };
+            DataEncoding::Csv(CsvEncoding {
+                header_row: false,
+                n_cols,
+                delimiter,
+                column_types: None,
+            })
+        }
+        "avro" => {
+            let (value_schema, schema_registry_config) = match (key_schema, value_encoding) {
+                (Some(schema), _) => (schema, None),
+                (
+                    None,
+                    DataEncoding::Avro(AvroEncoding {
+                        key_schema: Some(schema),
+                        schema_registry_config,
+                        ..
+                    }),
+                ) => (schema.clone(), schema_registry_config.clone()),
+                (None, _) => bail!(
+                    "key_format = 'avro' requires key_schema, unless the source's values use \
+                     a Confluent Schema Registry that has a schema for its keys"
+                ),
+            };
+            DataEncoding::Avro(AvroEncoding {
+                key_schema: None,
+                value_schema,
+                schema_registry_config,
+            })
+        }
+        other => bail!(
+            "unknown key_format: {}; expected one of 'bytes', 'text', 'csv', or 'avro'",
+            other
+        ),
+    };
+    Ok(Some(encoding))
+}
+
 pub fn describe_create_role(
     _: &StatementContext,
     _: CreateRoleStatement,
@@ -1502,6 +2215,8 @@ pub fn plan_drop_item(
 with_options! {
     struct IndexWithOptions {
         logical_compaction_window: String,
+        logical_compaction_window_ms: Value,
+        memory_limit_bytes: i64,
     }
 }
 
@@ -1512,17 +2227,34 @@ pub fn describe_alter_index_options(
     Ok(StatementDesc::new(None))
 }
 
-fn plan_index_options(with_opts: Vec<WithOption>) -> Result<Vec<IndexOption>, anyhow::Error> {
-    let with_opts = IndexWithOptions::try_from(with_opts)?;
+fn plan_index_options(with_opts: &IndexWithOptions) -> Result<Vec<IndexOption>, anyhow::Error> {
     let mut out = vec![];
 
-    match with_opts.logical_compaction_window.as_deref() {
-        None => (),
-        Some("off") => out.push(IndexOption::LogicalCompactionWindow(None)),
-        Some(s) => {
+    match (
+        with_opts.logical_compaction_window.as_deref(),
+        &with_opts.logical_compaction_window_ms,
+    ) {
+        (None, None) => (),
+        (Some(_), Some(_)) => bail!(
+            "logical_compaction_window and logical_compaction_window_ms cannot both be specified"
+        ),
+        (Some("off"), None) => out.push(IndexOption::LogicalCompactionWindow(None)),
+        (Some(s), None) => {
             let window = Some(parse_duration::parse(s)?);
             out.push(IndexOption::LogicalCompactionWindow(window))
         }
+        (None, Some(Value::String(s))) if s == "off" => {
+            out.push(IndexOption::LogicalCompactionWindow(None))
+        }
+        (None, Some(Value::Number(n))) => match n.parse::<u64>() {
+            Ok(ms) => out.push(IndexOption::LogicalCompactionWindow(Some(
+                Duration::from_millis(ms),
+            ))),
+            Err(_) => bail!("logical_compaction_window_ms must be a non-negative integer"),
+        },
+        (None, Some(_)) => {
+            bail!("logical_compaction_window_ms must be a non-negative integer or 'off'")
+        }
     };
 
     Ok(out)
@@ -1557,7 +2289,9 @@ pub fn plan_alter_index_options(
             let options = options
                 .into_iter()
                 .filter_map(|o| match normalize::ident(o).as_str() {
-                    "logical_compaction_window" => Some(IndexOptionName::LogicalCompactionWindow),
+                    "logical_compaction_window" | "logical_compaction_window_ms" => {
+                        Some(IndexOptionName::LogicalCompactionWindow)
+                    }
                     // Follow Postgres and don't complain if unknown parameters
                     // are passed into `ALTER INDEX ... RESET`.
                     _ => None,
@@ -1566,12 +2300,92 @@ pub fn plan_alter_index_options(
             Ok(Plan::AlterIndexResetOptions { id, options })
         }
         AlterIndexOptionsList::Set(options) => {
-            let options = plan_index_options(options)?;
+            let with_opts = IndexWithOptions::try_from(options)?;
+            if with_opts.memory_limit_bytes.is_some() {
+                bail!("memory_limit_bytes cannot be changed after an index is created");
+            }
+            let options = plan_index_options(&with_opts)?;
             Ok(Plan::AlterIndexSetOptions { id, options })
         }
     }
 }
 
+/// The options of Kafka sources that `ALTER SOURCE ... SET` can change. Other
+/// options are fixed, as they determine the source's schema or where it starts
+/// reading.
+const ALTERABLE_KAFKA_SOURCE_OPTIONS: &[&str] =
+    &["reconnect_backoff_max_ms", "statistics_interval_ms"];
+
+pub fn describe_alter_source_options(
+    _: &StatementContext,
+    _: AlterSourceOptionsStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_alter_source_options(
+    scx: &StatementContext,
+    AlterSourceOptionsStatement {
+        source_name,
+        if_exists,
+        options,
+    }: AlterSourceOptionsStatement,
+) -> Result<Plan, anyhow::Error> {
+    let entry = match scx.resolve_item(source_name) {
+        Ok(source) => source,
+        Err(_) if if_exists => {
+            return Ok(Plan::AlterNoop {
+                object_type: ObjectType::Source,
+            });
+        }
+        Err(e) => return Err(e.into()),
+    };
+    if entry.item_type() != CatalogItemType::Source {
+        bail!("{} is a {} not a source", entry.name(), entry.item_type())
+    }
+    let id = entry.id();
+
+    let mut stmt = match crate::parse::parse(entry.create_sql())?.into_element() {
+        Statement::CreateSource(stmt) => stmt,
+        _ => unreachable!("source {} has invalid create_sql", entry.name()),
+    };
+    if !matches!(stmt.connector, Connector::Kafka { .. }) {
+        bail!("ALTER SOURCE ... SET is only supported for Kafka sources");
+    }
+    for option in &options {
+        let name = normalize::ident(option.name().clone());
+        if !ALTERABLE_KAFKA_SOURCE_OPTIONS.contains(&name.as_str()) {
+            bail!(
+                "option {} of source {} cannot be changed; only {} can be changed",
+                name,
+                entry.name(),
+                ALTERABLE_KAFKA_SOURCE_OPTIONS.join(" and ")
+            );
+        }
+    }
+    // Validate the new values before recording them.
+    kafka_util::extract_config(&mut normalize::options(&options))?;
+
+    for option in options {
+        let name = normalize::ident(option.name().clone());
+        match stmt
+            .with_options
+            .iter_mut()
+            .find(|o| normalize::ident(o.name().clone()) == name)
+        {
+            Some(existing) => *existing = option,
+            None => stmt.with_options.push(option),
+        }
+    }
+    let config_options = kafka_util::extract_config(&mut normalize::options(&stmt.with_options))?;
+
+    Ok(Plan::AlterSourceSetOptions {
+        id,
+        create_sql: stmt.to_ast_string_stable(),
+        config_options,
+    })
+}
+
 pub fn describe_alter_object_rename(
     _: &StatementContext,
     _: AlterObjectRenameStatement,
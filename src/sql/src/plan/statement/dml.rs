@@ -16,18 +16,22 @@ use std::convert::TryFrom;
 
 use anyhow::bail;
 
-use expr::RowSetFinishing;
+use dataflow_types::TailOutput;
+use expr::{ColumnOrder, RowSetFinishing};
 use ore::collections::CollectionExt;
 use repr::{RelationDesc, ScalarType};
 
 use crate::ast::{
     CopyDirection, CopyRelation, CopyStatement, CopyTarget, CreateViewStatement, DeleteStatement,
-    ExplainStage, ExplainStatement, Explainee, InsertStatement, Query, Raw, SelectStatement,
-    Statement, TailStatement, UpdateStatement,
+    DropObjectsStatement, ExplainStage, ExplainStatement, Explainee, Expr, InsertStatement,
+    ObjectType, OrderByExpr, Query, Raw, SelectStatement, Statement, TailStatement,
+    UpdateStatement, Value,
 };
 use crate::catalog::CatalogItemType;
+use crate::normalize;
 use crate::plan::query;
 use crate::plan::query::QueryLifetime;
+use crate::plan::statement::ddl;
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::{CopyFormat, Params, PeekWhen, Plan};
 
@@ -43,10 +47,10 @@ pub fn describe_insert(
         table_name,
         columns,
         source,
-        ..
+        on_conflict,
     }: InsertStatement<Raw>,
 ) -> Result<StatementDesc, anyhow::Error> {
-    query::plan_insert_query(scx, table_name, columns, source)?;
+    query::plan_insert_query(scx, table_name, columns, source, on_conflict)?;
     Ok(StatementDesc::new(None))
 }
 
@@ -56,14 +60,21 @@ pub fn plan_insert(
         table_name,
         columns,
         source,
+        on_conflict,
     }: InsertStatement<Raw>,
     params: &Params,
 ) -> Result<Plan, anyhow::Error> {
-    let (id, mut expr) = query::plan_insert_query(scx, table_name, columns, source)?;
+    let (id, mut expr, conflict_keys, checks) =
+        query::plan_insert_query(scx, table_name, columns, source, on_conflict)?;
     expr.bind_parameters(&params)?;
     let expr = expr.lower();
 
-    Ok(Plan::Insert { id, values: expr })
+    Ok(Plan::Insert {
+        id,
+        values: expr,
+        conflict_keys,
+        checks,
+    })
 }
 
 pub fn describe_update(
@@ -131,11 +142,19 @@ pub fn describe_explain(
         stage, explainee, ..
     }: ExplainStatement<Raw>,
 ) -> Result<StatementDesc, anyhow::Error> {
+    if let Explainee::Drop(_) = explainee {
+        return Ok(StatementDesc::new(Some(
+            RelationDesc::empty()
+                .with_column("object_type", ScalarType::String.nullable(false))
+                .with_column("name", ScalarType::String.nullable(false)),
+        )));
+    }
     Ok(StatementDesc::new(Some(RelationDesc::empty().with_column(
         match stage {
             ExplainStage::RawPlan => "Raw Plan",
             ExplainStage::DecorrelatedPlan => "Decorrelated Plan",
             ExplainStage::OptimizedPlan { .. } => "Optimized Plan",
+            ExplainStage::Memory => "Memory Plan",
         },
         ScalarType::String.nullable(false),
     )))
@@ -164,6 +183,9 @@ pub fn plan_explain(
     params: &Params,
 ) -> Result<Plan, anyhow::Error> {
     let is_view = matches!(explainee, Explainee::View(_));
+    if let Explainee::Drop(stmt) = explainee {
+        return plan_explain_drop(scx, stmt);
+    }
     let (scx, query) = match explainee {
         Explainee::View(name) => {
             let view = scx.resolve_item(name.clone())?;
@@ -184,6 +206,7 @@ pub fn plan_explain(
             (scx, query)
         }
         Explainee::Query(query) => (scx.clone(), query),
+        Explainee::Drop(_) => unreachable!(),
     };
     // Previouly we would bail here for ORDER BY and LIMIT; this has been relaxed to silently
     // report the plan without the ORDER BY and LIMIT decorations (which are done in post).
@@ -209,6 +232,33 @@ pub fn plan_explain(
     })
 }
 
+/// Plans `EXPLAIN DROP`, which reports the objects that the `DROP` would
+/// remove, including those removed by `CASCADE`, without removing them.
+fn plan_explain_drop(
+    scx: &StatementContext,
+    DropObjectsStatement {
+        object_type,
+        if_exists,
+        names,
+        cascade,
+    }: DropObjectsStatement,
+) -> Result<Plan, anyhow::Error> {
+    match object_type {
+        ObjectType::Source
+        | ObjectType::Table
+        | ObjectType::View
+        | ObjectType::Index
+        | ObjectType::Sink
+        | ObjectType::Type => {
+            match ddl::plan_drop_items(scx, object_type, if_exists, names, cascade)? {
+                Plan::DropItems { items, .. } => Ok(Plan::ExplainDrop { items }),
+                _ => unreachable!("plan_drop_items always plans DropItems"),
+            }
+        }
+        _ => unsupported!(format!("EXPLAIN DROP {}", object_type)),
+    }
+}
+
 /// Plans and decorrelates a `Query`. Like `query::plan_root_query`, but returns
 /// an `::expr::MirRelationExpr`, which cannot include correlated expressions.
 pub fn plan_query(
@@ -226,15 +276,28 @@ with_options! {
     struct TailOptions {
         snapshot: bool,
         progress: bool,
+        output: String,
      }
 }
 
+fn tail_output(options: &TailOptions) -> Result<TailOutput, anyhow::Error> {
+    match options.output.as_deref().map(str::to_lowercase).as_deref() {
+        None => Ok(TailOutput::Rows),
+        Some("jsonl") => Ok(TailOutput::Jsonl),
+        Some(output) => bail!("unknown TAIL output: {}", output),
+    }
+}
+
 pub fn describe_tail(
     scx: &StatementContext,
     TailStatement { name, options, .. }: TailStatement<Raw>,
 ) -> Result<StatementDesc, anyhow::Error> {
     let sql_object = scx.resolve_item(name)?;
     let options = TailOptions::try_from(options)?;
+    if let TailOutput::Jsonl = tail_output(&options)? {
+        let desc = RelationDesc::empty().with_column("data", ScalarType::String.nullable(false));
+        return Ok(StatementDesc::new(Some(desc)));
+    }
     const MAX_U64_DIGITS: u8 = 20;
     let mut desc = RelationDesc::empty().with_column(
         "timestamp",
@@ -255,6 +318,7 @@ pub fn plan_tail(
         name,
         options,
         as_of,
+        order_by,
     }: TailStatement<Raw>,
     copy_to: Option<CopyFormat>,
 ) -> Result<Plan, anyhow::Error> {
@@ -262,6 +326,10 @@ pub fn plan_tail(
     let ts = as_of.map(|e| query::eval_as_of(scx, e)).transpose()?;
     let options = TailOptions::try_from(options)?;
     let desc = entry.desc()?.clone();
+    let order_by = order_by
+        .iter()
+        .map(|obe| plan_tail_order_by(&desc, obe))
+        .collect::<Result<_, _>>()?;
 
     match entry.item_type() {
         CatalogItemType::Table | CatalogItemType::Source | CatalogItemType::View => {
@@ -273,6 +341,8 @@ pub fn plan_tail(
                 emit_progress: options.progress.unwrap_or(false),
                 object_columns: entry.desc()?.arity(),
                 desc,
+                output: tail_output(&options)?,
+                order_by,
             })
         }
         CatalogItemType::Func
@@ -286,6 +356,43 @@ pub fn plan_tail(
     }
 }
 
+/// Resolves an expression in the `ORDER BY` clause of a `TAIL` to a column of
+/// the tailed relation, which it must name or refer to by its position.
+fn plan_tail_order_by(
+    desc: &RelationDesc,
+    obe: &OrderByExpr<Raw>,
+) -> Result<ColumnOrder, anyhow::Error> {
+    let column = match &obe.expr {
+        Expr::Identifier(names) if names.len() == 1 => {
+            let name = normalize::column_name(names[0].clone());
+            let mut columns = desc
+                .iter_names()
+                .enumerate()
+                .filter(|(_, n)| *n == Some(&name))
+                .map(|(i, _)| i);
+            match (columns.next(), columns.next()) {
+                (Some(i), None) => i,
+                (Some(_), Some(_)) => bail!("column reference \"{}\" is ambiguous", name),
+                (None, _) => bail!("column \"{}\" does not exist", name),
+            }
+        }
+        Expr::Value(Value::Number(n)) => match n.parse::<usize>() {
+            Ok(i) if i > 0 && i <= desc.arity() => i - 1,
+            _ => bail!("TAIL ORDER BY position {} is not in the relation", n),
+        },
+        expr => bail!(
+            "TAIL ORDER BY only supports column names and positions, not {}",
+            expr
+        ),
+    };
+    let desc = !obe.asc.unwrap_or(true);
+    Ok(ColumnOrder {
+        column,
+        desc,
+        nulls_last: obe.nulls_last.unwrap_or(!desc),
+    })
+}
+
 with_options! {
     struct CopyOptions {
         format: String,
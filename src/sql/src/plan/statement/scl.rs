@@ -10,19 +10,24 @@
 //! Session control language (SCL).
 //!
 //! This module houses the handlers for statements that manipulate the session,
-//! like `DISCARD` and `SET`.
+//! like `DISCARD` and `SET`, the queries of other sessions, like `CANCEL
+//! QUERY`, or the notifications exchanged between sessions, like `LISTEN` and
+//! `NOTIFY`.
 
 use std::convert::TryFrom;
 
 use anyhow::bail;
 
+use dataflow_types::MAX_NOTIFY_PAYLOAD_BYTES;
 use repr::adt::interval::Interval;
 use repr::{RelationDesc, ScalarType};
 
 use crate::ast::{
-    CloseStatement, DeclareStatement, DiscardStatement, DiscardTarget, FetchStatement, Raw,
-    SetVariableStatement, SetVariableValue, ShowVariableStatement, Value,
+    CancelQueryStatement, CloseStatement, DeclareStatement, DiscardStatement, DiscardTarget,
+    FetchStatement, ListenStatement, NotifyStatement, Raw, SetVariableStatement, SetVariableValue,
+    ShowVariableStatement, UnlistenStatement, Value,
 };
+use crate::normalize;
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::{ExecuteTimeout, Plan};
 
@@ -175,3 +180,71 @@ pub fn plan_close(
         name: name.to_string(),
     })
 }
+
+pub fn describe_cancel_query(
+    _: &StatementContext,
+    _: CancelQueryStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    let desc = RelationDesc::empty().with_column("canceled", ScalarType::Bool.nullable(false));
+    Ok(StatementDesc::new(Some(desc)))
+}
+
+pub fn plan_cancel_query(
+    _: &StatementContext,
+    CancelQueryStatement { query_id }: CancelQueryStatement,
+) -> Result<Plan, anyhow::Error> {
+    Ok(Plan::CancelQuery { query_id })
+}
+
+pub fn describe_listen(
+    _: &StatementContext,
+    _: ListenStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_listen(
+    _: &StatementContext,
+    ListenStatement { channel }: ListenStatement,
+) -> Result<Plan, anyhow::Error> {
+    Ok(Plan::Listen {
+        channel: normalize::ident(channel),
+    })
+}
+
+pub fn describe_unlisten(
+    _: &StatementContext,
+    _: UnlistenStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_unlisten(
+    _: &StatementContext,
+    UnlistenStatement { channel }: UnlistenStatement,
+) -> Result<Plan, anyhow::Error> {
+    Ok(Plan::Unlisten {
+        channel: channel.map(normalize::ident),
+    })
+}
+
+pub fn describe_notify(
+    _: &StatementContext,
+    _: NotifyStatement,
+) -> Result<StatementDesc, anyhow::Error> {
+    Ok(StatementDesc::new(None))
+}
+
+pub fn plan_notify(
+    _: &StatementContext,
+    NotifyStatement { channel, payload }: NotifyStatement,
+) -> Result<Plan, anyhow::Error> {
+    let payload = payload.unwrap_or_default();
+    if payload.len() >= MAX_NOTIFY_PAYLOAD_BYTES {
+        bail!("payload string too long");
+    }
+    Ok(Plan::Notify {
+        channel: normalize::ident(channel),
+        payload,
+    })
+}
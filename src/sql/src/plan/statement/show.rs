@@ -18,13 +18,16 @@ use anyhow::bail;
 use ore::collections::CollectionExt;
 use repr::{Datum, RelationDesc, Row, ScalarType};
 
+use crate::ast::display::AstDisplay;
 use crate::ast::{
-    ObjectType, Raw, SelectStatement, ShowColumnsStatement, ShowCreateIndexStatement,
-    ShowCreateSinkStatement, ShowCreateSourceStatement, ShowCreateTableStatement,
-    ShowCreateViewStatement, ShowDatabasesStatement, ShowIndexesStatement, ShowObjectsStatement,
-    ShowStatementFilter, Statement, UnresolvedObjectName, Value,
+    AvroSchema, CreateSinkStatement, CreateSourceStatement, Format, ObjectType, Raw,
+    SelectStatement, ShowColumnsStatement, ShowCreateIndexStatement, ShowCreateSinkStatement,
+    ShowCreateSourceStatement, ShowCreateTableStatement, ShowCreateViewStatement,
+    ShowDatabasesStatement, ShowIndexesStatement, ShowObjectsStatement, ShowStatementFilter,
+    SqlOption, Statement, UnresolvedObjectName, Value,
 };
 use crate::catalog::CatalogItemType;
+use crate::normalize;
 use crate::parse;
 use crate::plan::statement::{dml, StatementContext, StatementDesc};
 use crate::plan::{Params, Plan};
@@ -100,7 +103,7 @@ pub fn plan_show_create_source(
     if let CatalogItemType::Source = source.item_type() {
         Ok(Plan::SendRows(vec![Row::pack_slice(&[
             Datum::String(&source.name().to_string()),
-            Datum::String(source.create_sql()),
+            Datum::String(&redact_secrets(source.create_sql())),
         ])]))
     } else {
         bail!("{} is not a source", source.name());
@@ -126,13 +129,62 @@ pub fn plan_show_create_sink(
     if let CatalogItemType::Sink = sink.item_type() {
         Ok(Plan::SendRows(vec![Row::pack_slice(&[
             Datum::String(&sink.name().to_string()),
-            Datum::String(sink.create_sql()),
+            Datum::String(&redact_secrets(sink.create_sql())),
         ])]))
     } else {
         bail!("'{}' is not a sink", sink.name());
     }
 }
 
+/// The options of sources and sinks whose values are secret.
+const SECRET_OPTIONS: &[&str] = &[
+    "password",
+    "sasl_password",
+    "secret_access_key",
+    "ssl_key_password",
+    "token",
+];
+
+/// Replaces the values of any secret options in the `CREATE SOURCE` or
+/// `CREATE SINK` statement `create_sql` with `'<REDACTED>'`.
+fn redact_secrets(create_sql: &str) -> String {
+    let mut stmt = parse::parse(create_sql)
+        .expect("create_sql cannot be invalid")
+        .into_element();
+    let (with_options, format) = match &mut stmt {
+        Statement::CreateSource(CreateSourceStatement {
+            with_options,
+            format,
+            ..
+        })
+        | Statement::CreateSink(CreateSinkStatement {
+            with_options,
+            format,
+            ..
+        }) => (with_options, format),
+        _ => unreachable!("redact_secrets called on {}", create_sql),
+    };
+    redact_options(with_options);
+    match format {
+        Some(Format::Avro(AvroSchema::CsrUrl { with_options, .. }))
+        | Some(Format::JsonSchema { with_options, .. }) => redact_options(with_options),
+        _ => (),
+    }
+    stmt.to_ast_string_stable()
+}
+
+fn redact_options(options: &mut [SqlOption]) {
+    for option in options {
+        let name = option.name().clone();
+        if SECRET_OPTIONS.contains(&normalize::ident(name.clone()).as_str()) {
+            *option = SqlOption::Value {
+                name,
+                value: Value::String("<REDACTED>".into()),
+            };
+        }
+    }
+}
+
 pub fn describe_show_create_index(
     _: &StatementContext,
     _: ShowCreateIndexStatement,
@@ -476,7 +528,8 @@ pub fn show_indexes<'a>(
             idx_cols.index_position AS seq_in_index,
             obj_cols.name AS column_name,
             idx_cols.on_expression AS expression,
-            idx_cols.nullable AS nullable
+            idx_cols.nullable AS nullable,
+            idxs.memory_limit_bytes AS memory_limit_bytes
         FROM
             mz_catalog.mz_indexes AS idxs
             JOIN mz_catalog.mz_index_columns AS idx_cols ON idxs.id = idx_cols.index_id
@@ -502,19 +555,20 @@ pub fn show_columns<'a>(
     if extended {
         unsupported!("SHOW EXTENDED COLUMNS");
     }
-    if full {
-        unsupported!("SHOW FULL COLUMNS");
-    }
 
     let entry = scx.resolve_item(table_name)?;
 
+    // `SHOW FULL COLUMNS` additionally reports whether each column is part of
+    // a key of the item.
+    let key = if full { ", mz_columns.key" } else { "" };
     let query = format!(
         "SELECT
             mz_columns.name,
             mz_columns.nullable,
-            mz_columns.type
+            mz_columns.full_type AS type{}
          FROM mz_catalog.mz_columns AS mz_columns
          WHERE mz_columns.id = '{}'",
+        key,
         entry.id(),
     );
     Ok(ShowSelect::new(scx, query, filter))
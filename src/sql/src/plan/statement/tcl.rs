@@ -14,6 +14,7 @@
 
 use crate::ast::{
     CommitStatement, RollbackStatement, SetTransactionStatement, StartTransactionStatement,
+    TransactionIsolationLevel, TransactionMode,
 };
 use crate::plan::statement::{StatementContext, StatementDesc};
 use crate::plan::Plan;
@@ -36,14 +37,28 @@ pub fn describe_set_transaction(
     _: &StatementContext,
     _: SetTransactionStatement,
 ) -> Result<StatementDesc, anyhow::Error> {
-    unsupported!("SET TRANSACTION")
+    Ok(StatementDesc::new(None))
 }
 
 pub fn plan_set_transaction(
     _: &StatementContext,
-    _: SetTransactionStatement,
+    SetTransactionStatement { modes }: SetTransactionStatement,
 ) -> Result<Plan, anyhow::Error> {
-    unsupported!("SET TRANSACTION")
+    // Access modes are ignored, as they are by `START TRANSACTION`. The last
+    // isolation level wins, as in PostgreSQL; without one, the transaction
+    // keeps the only level it can have, `serializable`.
+    let iso_level = modes
+        .into_iter()
+        .filter_map(|mode| match mode {
+            TransactionMode::IsolationLevel(iso_level) => Some(iso_level),
+            TransactionMode::AccessMode(_) => None,
+        })
+        .last()
+        .unwrap_or(TransactionIsolationLevel::Serializable);
+    Ok(Plan::SetVariable {
+        name: "transaction_isolation".into(),
+        value: iso_level.to_string().to_lowercase(),
+    })
 }
 
 pub fn describe_rollback(
@@ -103,10 +103,11 @@ impl<'a> FuncRewriter<'a> {
     ) -> Expr<Raw> {
         Expr::Function(Function {
             name,
-            args: FunctionArgs::Args(vec![expr]),
+            args: FunctionArgs::args(vec![expr]),
             filter,
             over: None,
             distinct,
+            within_group: false,
         })
     }
 
@@ -191,11 +192,12 @@ impl<'a> FuncRewriter<'a> {
         match expr {
             Expr::Function(Function {
                 name,
-                args: FunctionArgs::Args(args),
+                args: FunctionArgs::Args { args, order_by },
                 filter,
                 distinct,
                 over: None,
-            }) => {
+                within_group: false,
+            }) if order_by.is_empty() => {
                 let name = normalize::object_name(name.clone()).ok()?;
                 if let Some(database) = &name.database {
                     // If a database name is provided, we need only verify that
@@ -382,12 +384,13 @@ impl Desugarer {
                                 Ident::new("mz_catalog"),
                                 Ident::new("unnest"),
                             ]),
-                            args: FunctionArgs::Args(vec![right.take()]),
+                            args: FunctionArgs::args(vec![right.take()]),
                             alias: Some(TableAlias {
                                 name: Ident::new("_"),
                                 columns: vec![binding.clone()],
                                 strict: true,
                             }),
+                            with_ordinality: false,
                         },
                         joins: vec![],
                     })
@@ -491,10 +494,10 @@ impl Desugarer {
             expr2: Some(right),
         } = expr
         {
-            if let (Expr::Row { exprs: left }, Expr::Row { exprs: right }) =
-                (&mut **left, &mut **right)
+            if let (Ok(op), Expr::Row { exprs: left }, Expr::Row { exprs: right }) =
+                (normalize::op(op), &mut **left, &mut **right)
             {
-                if matches!(op.as_str(), "=" | "<>" | "<" | "<=" | ">" | ">=") {
+                if matches!(op, "=" | "<>" | "<" | "<=" | ">" | ">=") {
                     if left.len() != right.len() {
                         bail!("unequal number of entries in row expressions");
                     }
@@ -503,7 +506,7 @@ impl Desugarer {
                         bail!("cannot compare rows of zero length");
                     }
                 }
-                match op.as_str() {
+                match op {
                     "=" | "<>" => {
                         let mut new = Expr::Value(Value::Boolean(true));
                         for (l, r) in left.iter_mut().zip(right) {
@@ -515,13 +518,13 @@ impl Desugarer {
                         *expr = new;
                     }
                     "<" | "<=" | ">" | ">=" => {
-                        let strict_op = match op.as_str() {
+                        let strict_op = match op {
                             "<" | "<=" => "<",
                             ">" | ">=" => ">",
                             _ => unreachable!(),
                         };
                         let (l, r) = (left.last_mut().unwrap(), right.last_mut().unwrap());
-                        let mut new = l.take().binop(&op, r.take());
+                        let mut new = l.take().binop(op, r.take());
                         for (l, r) in left.iter_mut().zip(right).rev().skip(1) {
                             new = l
                                 .clone()
@@ -248,8 +248,8 @@ lazy_static! {
             (String, Float32) => Explicit: CastStringToFloat32,
             (String, Float64) => Explicit: CastStringToFloat64,
             (String, Decimal) => Explicit: CastTemplate::new(|_ecx, _ccx, _from_type, to_type| {
-                let (_, s) = to_type.unwrap_decimal_parts();
-                Some(move |e: HirScalarExpr| e.call_unary(CastStringToDecimal(s)))
+                let (precision, scale) = to_type.unwrap_decimal_parts();
+                Some(move |e: HirScalarExpr| e.call_unary(CastStringToDecimal { precision, scale }))
             }),
             (String, Date) => Explicit: CastStringToDate,
             (String, Time) => Explicit: CastStringToTime,
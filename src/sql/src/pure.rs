@@ -20,7 +20,8 @@ use tokio::time::Duration;
 
 use repr::strconv;
 use sql_parser::ast::{
-    AvroSchema, Connector, CreateSourceStatement, CsrSeed, Format, Ident, Raw, Statement,
+    AvroSchema, Connector, CreateSourceStatement, CsrSeed, Format, Ident, Raw, SqlOption,
+    Statement, Value,
 };
 
 use crate::kafka_util;
@@ -84,6 +85,8 @@ pub async fn purify(mut stmt: Statement<Raw>) -> Result<Statement<Raw>, anyhow::
                 aws_util::aws::validate_credentials(aws_info.clone(), Duration::from_secs(1))
                     .await?;
             }
+            // Only valid for sinks, which planning reports.
+            Connector::S3Bucket { .. } | Connector::Notify { .. } => (),
             Connector::Kinesis { arn } => {
                 let region = arn
                     .parse::<ARN>()
@@ -96,9 +99,25 @@ pub async fn purify(mut stmt: Statement<Raw>) -> Result<Statement<Raw>, anyhow::
             }
         }
 
-        purify_format(format, connector, col_names, file, &config_options).await?;
+        purify_format(
+            format,
+            connector,
+            col_names,
+            with_options,
+            file,
+            &config_options,
+        )
+        .await?;
         if let sql_parser::ast::Envelope::Upsert(format) = envelope {
-            purify_format(format, connector, col_names, None, &config_options).await?;
+            purify_format(
+                format,
+                connector,
+                col_names,
+                with_options,
+                None,
+                &config_options,
+            )
+            .await?;
         }
     }
     Ok(stmt)
@@ -108,6 +127,7 @@ async fn purify_format(
     format: &mut Option<Format>,
     connector: &mut Connector,
     col_names: &mut Vec<Ident>,
+    with_options: &mut Vec<SqlOption>,
     file: Option<tokio::fs::File>,
     connector_options: &BTreeMap<String, String>,
 ) -> Result<(), anyhow::Error> {
@@ -118,10 +138,13 @@ async fn purify_format(
                 seed,
                 with_options: ccsr_options,
             } => {
-                let topic = if let Connector::Kafka { topic, .. } = connector {
-                    topic
-                } else {
-                    bail!("Confluent Schema Registry is only supported with Kafka sources")
+                let topic = match connector {
+                    Connector::Kafka {
+                        topic_pattern: true,
+                        ..
+                    } => unsupported!("Confluent Schema Registry with TOPIC PATTERN"),
+                    Connector::Kafka { topic, .. } => topic,
+                    _ => bail!("Confluent Schema Registry is only supported with Kafka sources"),
                 };
                 if seed.is_none() {
                     let url = url.parse()?;
@@ -149,6 +172,33 @@ async fn purify_format(
             }
             _ => {}
         },
+        Some(Format::JsonSchema {
+            url,
+            seed,
+            with_options: ccsr_options,
+        }) => {
+            let topic = match connector {
+                Connector::Kafka {
+                    topic_pattern: true,
+                    ..
+                } => unsupported!("Confluent Schema Registry with TOPIC PATTERN"),
+                Connector::Kafka { topic, .. } => topic,
+                _ => bail!("Confluent Schema Registry is only supported with Kafka sources"),
+            };
+            if seed.is_none() {
+                let url = url.parse()?;
+
+                let ccsr_config = kafka_util::generate_ccsr_client_config(
+                    url,
+                    &connector_options,
+                    normalize::options(ccsr_options),
+                )?;
+
+                let Schema { value_schema, .. } =
+                    get_remote_avro_schema(ccsr_config, topic.clone()).await?;
+                *seed = Some(value_schema);
+            }
+        }
         Some(Format::Protobuf { schema, .. }) => {
             if let sql_parser::ast::Schema::File(path) = schema {
                 let descriptors = tokio::fs::read(path).await?;
@@ -159,23 +209,69 @@ async fn purify_format(
         }
         Some(Format::Csv {
             header_row,
+            n_cols,
             delimiter,
-            ..
         }) => {
-            if *header_row && col_names.is_empty() {
-                if let Some(file) = file {
-                    let file = tokio::io::BufReader::new(file);
-                    let csv_header = file.lines().next_line().await?;
-                    match csv_header {
+            let with_options_map = normalize::options(with_options);
+            let infer_types = match with_options_map.get("infer_types") {
+                None => false,
+                Some(Value::Boolean(b)) => *b,
+                Some(_) => bail!("infer_types must be a boolean"),
+            };
+            let infer_sample_rows = match with_options_map.get("infer_sample_rows") {
+                None => DEFAULT_CSV_INFER_SAMPLE_ROWS,
+                Some(Value::Number(n)) => n.parse::<usize>()?,
+                Some(_) => bail!("infer_sample_rows must be a number"),
+            };
+            let read_header = *header_row && col_names.is_empty();
+            let infer_types = infer_types && !with_options_map.contains_key("column_types");
+            if read_header || infer_types {
+                let file = match file {
+                    Some(file) => file,
+                    None if read_header => {
+                        bail!("CSV format with headers only works with file connectors")
+                    }
+                    None => bail!("CSV type inference only works with file connectors"),
+                };
+                let mut lines = tokio::io::BufReader::new(file).lines();
+                if *header_row {
+                    match lines.next_line().await? {
                         Some(csv_header) => {
-                            csv_header
-                                .split(*delimiter as char)
-                                .for_each(|v| col_names.push(Ident::from(v)));
+                            if read_header {
+                                if let Some(header) =
+                                    read_csv_records(&csv_header, *delimiter)?.first()
+                                {
+                                    col_names.extend(header.iter().map(Ident::from));
+                                }
+                            }
                         }
                         None => bail!("CSV file expected header line, but is empty"),
                     }
-                } else {
-                    bail!("CSV format with headers only works with file connectors")
+                }
+                if infer_types {
+                    let mut sample = String::new();
+                    for _ in 0..infer_sample_rows {
+                        match lines.next_line().await? {
+                            Some(line) => {
+                                sample.push_str(&line);
+                                sample.push('\n');
+                            }
+                            None => break,
+                        }
+                    }
+                    let sample = read_csv_records(&sample, *delimiter)?;
+                    let n_cols = match n_cols {
+                        Some(n_cols) if col_names.is_empty() => *n_cols,
+                        _ => col_names.len(),
+                    };
+                    let column_types = interchange::csv::infer_column_types(
+                        sample.iter().map(|record| record.iter().collect()),
+                        n_cols,
+                    );
+                    with_options.push(SqlOption::Value {
+                        name: Ident::new("column_types"),
+                        value: Value::String(interchange::csv::encode_column_types(&column_types)),
+                    });
                 }
             }
         }
@@ -184,6 +280,26 @@ async fn purify_format(
     Ok(())
 }
 
+/// The number of rows that CSV type inference samples by default.
+const DEFAULT_CSV_INFER_SAMPLE_ROWS: usize = 100;
+
+/// Parses the records of a CSV source's text, so that quoted fields and
+/// delimiters are read as the source's decoder reads them.
+fn read_csv_records(text: &str, delimiter: char) -> Result<Vec<csv::StringRecord>, anyhow::Error> {
+    let delimiter = match delimiter as u32 {
+        0..=127 => delimiter as u8,
+        _ => bail!("CSV delimiter must be an ASCII character"),
+    };
+    let records = csv::ReaderBuilder::new()
+        .delimiter(delimiter)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(text.as_bytes())
+        .into_records()
+        .collect::<Result<_, _>>()?;
+    Ok(records)
+}
+
 #[derive(Debug)]
 pub struct Schema {
     pub key_schema: Option<String>,
@@ -208,7 +324,20 @@ async fn get_remote_avro_schema(
             )
         })?;
     let subject = format!("{}-key", topic);
-    let key_schema = ccsr_client.get_schema_by_subject(&subject).await.ok();
+    let key_schema = match ccsr_client.get_schema_by_subject(&subject).await {
+        Ok(key_schema) => Some(key_schema),
+        // A key schema that is too large is an error rather than a missing key
+        // schema, so that it is not silently ignored.
+        Err(e @ ccsr::GetBySubjectError::ResponseTooLarge) => {
+            return Err(e).with_context(|| {
+                format!(
+                    "fetching latest schema for subject '{}' from registry",
+                    subject
+                )
+            })
+        }
+        Err(_) => None,
+    };
     Ok(Schema {
         key_schema: key_schema.map(|s| s.raw),
         value_schema: value_schema.raw,
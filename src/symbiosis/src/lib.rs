@@ -210,6 +210,8 @@ END $$;
                     create_sql: stmt.to_string(),
                     desc,
                     defaults,
+                    keys: vec![],
+                    checks: vec![],
                     temporary,
                 };
                 Plan::CreateTable {
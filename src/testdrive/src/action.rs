@@ -74,6 +74,8 @@ pub struct Config {
     pub reset_materialized: bool,
     /// Emit Buildkite-specific markup.
     pub ci_output: bool,
+    /// The seed to use for the run, if not a random one.
+    pub seed: Option<u32>,
 }
 
 pub struct State {
@@ -364,8 +366,14 @@ pub fn build(cmds: Vec<PosCommand>, state: &State) -> Result<Vec<PosAction>, Err
                     "kafka-create-topic" => {
                         Box::new(kafka::build_create_topic(builtin).map_err(wrap_err)?)
                     }
+                    "kafka-delete-topic" => {
+                        Box::new(kafka::build_delete_topic(builtin).map_err(wrap_err)?)
+                    }
                     "kafka-ingest" => Box::new(kafka::build_ingest(builtin).map_err(wrap_err)?),
                     "kafka-verify" => Box::new(kafka::build_verify(builtin).map_err(wrap_err)?),
+                    "kafka-verify-compression" => {
+                        Box::new(kafka::build_verify_compression(builtin).map_err(wrap_err)?)
+                    }
                     "kinesis-create-stream" => {
                         Box::new(kinesis::build_create_stream(builtin).map_err(wrap_err)?)
                     }
@@ -378,6 +386,7 @@ pub fn build(cmds: Vec<PosCommand>, state: &State) -> Result<Vec<PosAction>, Err
                         Box::new(s3::build_create_bucket(builtin).map_err(wrap_err)?)
                     }
                     "s3-put-object" => Box::new(s3::build_put_object(builtin).map_err(wrap_err)?),
+                    "s3-verify" => Box::new(s3::build_verify(builtin).map_err(wrap_err)?),
                     "set-sql-timeout" => {
                         let duration = builtin.args.string("duration").map_err(wrap_err)?;
                         if duration.to_lowercase() == "default" {
@@ -462,7 +471,7 @@ fn substitute_vars(msg: &str, vars: &HashMap<String, String>) -> Result<String,
 pub async fn create_state(
     config: &Config,
 ) -> Result<(State, impl Future<Output = Result<(), Error>>), Error> {
-    let seed = rand::thread_rng().gen();
+    let seed = config.seed.unwrap_or_else(|| rand::thread_rng().gen());
     let temp_dir = tempfile::tempdir().err_ctx("creating temporary directory")?;
 
     let materialized_catalog_path = if let Some(path) = &config.materialized_catalog_path {
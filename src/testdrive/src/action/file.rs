@@ -25,7 +25,7 @@ pub struct AppendAction {
     compression: Compression,
 }
 
-enum Compression {
+pub(crate) enum Compression {
     Gzip,
     None,
 }
@@ -42,7 +42,7 @@ impl FromStr for Compression {
     }
 }
 
-fn build_compression(cmd: &mut BuiltinCommand) -> Result<Compression, String> {
+pub(crate) fn build_compression(cmd: &mut BuiltinCommand) -> Result<Compression, String> {
     match cmd.args.opt_string("compression") {
         Some(s) => s.parse(),
         None => Ok(Compression::None),
@@ -62,10 +62,16 @@ fn build_path(cmd: &mut BuiltinCommand) -> Result<String, String> {
 pub fn build_append(mut cmd: BuiltinCommand) -> Result<AppendAction, String> {
     let path = build_path(&mut cmd)?;
     let compression = build_compression(&mut cmd)?;
+    let trailing_newline = cmd.args.opt_parse("trailing-newline")?.unwrap_or(true);
     cmd.args.done()?;
     let mut contents = vec![];
-    for line in cmd.input {
+    for (i, line) in cmd.input.iter().enumerate() {
+        if i > 0 {
+            contents.push(b'\n');
+        }
         contents.extend(bytes::unescape(line.as_bytes())?);
+    }
+    if trailing_newline && !cmd.input.is_empty() {
         contents.push(b'\n');
     }
     Ok(AppendAction {
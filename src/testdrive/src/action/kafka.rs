@@ -9,10 +9,14 @@
 
 mod add_partitions;
 mod create_topic;
+mod delete_topic;
 mod ingest;
 mod verify;
+mod verify_compression;
 
 pub use add_partitions::build_add_partitions;
 pub use create_topic::build_create_topic;
+pub use delete_topic::build_delete_topic;
 pub use ingest::build_ingest;
 pub use verify::build_verify;
+pub use verify_compression::build_verify_compression;
@@ -22,6 +22,7 @@ pub struct CreateTopicAction {
     topic_prefix: String,
     partitions: usize,
     compression: String,
+    timestamp_type: String,
 }
 
 pub fn build_create_topic(mut cmd: BuiltinCommand) -> Result<CreateTopicAction, String> {
@@ -31,12 +32,17 @@ pub fn build_create_topic(mut cmd: BuiltinCommand) -> Result<CreateTopicAction,
         .args
         .opt_string("compression")
         .unwrap_or_else(|| "producer".into());
+    let timestamp_type = cmd
+        .args
+        .opt_string("timestamp-type")
+        .unwrap_or_else(|| "CreateTime".into());
     cmd.args.done()?;
 
     Ok(CreateTopicAction {
         topic_prefix,
         partitions,
         compression,
+        timestamp_type,
     })
 }
 
@@ -157,7 +163,8 @@ impl Action for CreateTopicAction {
             // "1" is interpreted as January 1, 1970 00:00:01, which is
             // breaches the default 7-day retention policy.
             .set("retention.ms", "-1")
-            .set("compression.type", &self.compression);
+            .set("compression.type", &self.compression)
+            .set("message.timestamp.type", &self.timestamp_type);
         kafka_util::admin::create_topic(&state.kafka_admin, &state.kafka_admin_opts, &new_topic)
             .await
             .map_err(|e| e.to_string())?;
@@ -0,0 +1,83 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::time::Duration;
+
+use async_trait::async_trait;
+use rdkafka::producer::Producer;
+
+use ore::collections::CollectionExt;
+use ore::retry;
+
+use crate::action::{Action, State};
+use crate::parser::BuiltinCommand;
+
+pub struct DeleteTopicAction {
+    topic_prefix: String,
+}
+
+pub fn build_delete_topic(mut cmd: BuiltinCommand) -> Result<DeleteTopicAction, String> {
+    let topic_prefix = format!("testdrive-{}", cmd.args.string("topic")?);
+    cmd.args.done()?;
+
+    Ok(DeleteTopicAction { topic_prefix })
+}
+
+#[async_trait]
+impl Action for DeleteTopicAction {
+    async fn undo(&self, _: &mut State) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn redo(&self, state: &mut State) -> Result<(), String> {
+        let topic_name = format!("{}-{}", self.topic_prefix, state.seed);
+        println!("Deleting Kafka topic {}", topic_name);
+
+        if state.kafka_topics.remove(&topic_name).is_none() {
+            return Err(format!(
+                "topic {} not created by kafka-create-topic",
+                topic_name
+            ));
+        }
+
+        let res = state
+            .kafka_admin
+            .delete_topics(&[topic_name.as_str()], &state.kafka_admin_opts)
+            .await
+            .map_err(|e| e.to_string())?;
+        if res.len() != 1 {
+            return Err(format!(
+                "kafka topic deletion returned {} results, but exactly one result was expected",
+                res.len()
+            ));
+        }
+        if let Err((_topic_name, e)) = res.into_element() {
+            return Err(e.to_string());
+        }
+
+        // Wait until the deletion is visible in the broker's metadata, which
+        // is how sources learn of it.
+        retry::retry_for(Duration::from_secs(8), |_| async {
+            let metadata = state
+                .kafka_producer
+                .client()
+                .fetch_metadata(None, Some(Duration::from_secs(1)))
+                .map_err(|e| e.to_string())?;
+            if metadata
+                .topics()
+                .iter()
+                .any(|topic| topic.name() == topic_name && topic.error().is_none())
+            {
+                return Err(format!("topic {} still exists", topic_name));
+            }
+            Ok(())
+        })
+        .await
+    }
+}
@@ -13,6 +13,7 @@ use std::time::Duration;
 use async_trait::async_trait;
 use byteorder::{NetworkEndian, WriteBytesExt};
 use futures::stream::{FuturesUnordered, StreamExt};
+use rdkafka::message::OwnedHeaders;
 use rdkafka::producer::FutureRecord;
 use serde::de::DeserializeOwned;
 
@@ -28,6 +29,7 @@ pub struct IngestAction {
     format: Format,
     key_format: Option<Format>,
     timestamp: Option<i64>,
+    headers: Vec<(String, String)>,
     publish: bool,
     rows: Vec<String>,
 }
@@ -151,6 +153,21 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
         None => None,
     };
     let timestamp = cmd.args.opt_parse("timestamp")?;
+    // Headers are given as `name:value` pairs separated by commas, and are
+    // attached to every ingested record.
+    let headers = match cmd.args.opt_string("headers") {
+        None => vec![],
+        Some(headers) => headers
+            .split(',')
+            .map(|header| {
+                let mut parts = header.splitn(2, ':');
+                match (parts.next(), parts.next()) {
+                    (Some(name), Some(value)) => Ok((name.into(), value.into())),
+                    _ => Err(format!("invalid header {:?}: expected name:value", header)),
+                }
+            })
+            .collect::<Result<_, String>>()?,
+    };
     let publish = cmd.args.opt_bool("publish")?;
     cmd.args.done()?;
 
@@ -160,6 +177,7 @@ pub fn build_ingest(mut cmd: BuiltinCommand) -> Result<IngestAction, String> {
         format,
         key_format,
         timestamp,
+        headers,
         publish,
         rows: cmd.input,
     })
@@ -243,6 +261,13 @@ impl Action for IngestAction {
                 if let Some(timestamp) = self.timestamp {
                     record = record.timestamp(timestamp);
                 }
+                if !self.headers.is_empty() {
+                    let mut headers = OwnedHeaders::new();
+                    for (name, value) in &self.headers {
+                        headers = headers.add(name, value);
+                    }
+                    record = record.headers(headers);
+                }
                 producer.send(record, Duration::from_secs(1)).await
             });
         }
@@ -13,6 +13,7 @@ use async_trait::async_trait;
 use byteorder::{BigEndian, ByteOrder};
 use rdkafka::consumer::{Consumer, StreamConsumer};
 use rdkafka::message::Message;
+use rdkafka::{Offset, TopicPartitionList};
 use tokio::pin;
 use tokio_stream::StreamExt;
 
@@ -27,6 +28,7 @@ pub enum SinkConsistencyFormat {
 pub struct VerifyAction {
     sink: String,
     consistency: Option<SinkConsistencyFormat>,
+    partition: Option<i32>,
     expected_messages: Vec<String>,
 }
 
@@ -38,12 +40,14 @@ pub fn build_verify(mut cmd: BuiltinCommand) -> Result<VerifyAction, String> {
         Some(s) => return Err(format!("unknown sink consistency format {}", s)),
         None => None,
     };
+    let partition = cmd.args.opt_parse("partition")?;
 
     let expected_messages = cmd.input;
     cmd.args.done()?;
     Ok(VerifyAction {
         sink,
         consistency,
+        partition,
         expected_messages,
     })
 }
@@ -81,19 +85,20 @@ impl Action for VerifyAction {
     }
 
     async fn redo(&self, state: &mut State) -> Result<(), String> {
-        let topic_prefix: String = state
+        let row = state
             .pgclient
             .query_one(
-                "SELECT topic FROM mz_catalog_names JOIN mz_kafka_sinks ON global_id = sink_id WHERE name = $1",
+                "SELECT topic, consistency_topic FROM mz_catalog_names JOIN mz_kafka_sinks ON global_id = sink_id WHERE name = $1",
                 &[&self.sink],
             )
             .await
-            .map_err(|e| format!("retrieving topic name: {}", e))?
-            .get("topic");
+            .map_err(|e| format!("retrieving topic name: {}", e))?;
 
-        let topic = match self.consistency {
-            Some(SinkConsistencyFormat::Debezium) => format!("{}-consistency", topic_prefix),
-            None => topic_prefix,
+        let topic: String = match self.consistency {
+            Some(SinkConsistencyFormat::Debezium) => row
+                .get::<_, Option<String>>("consistency_topic")
+                .ok_or_else(|| format!("sink {} does not have a consistency topic", self.sink))?,
+            None => row.get("topic"),
         };
 
         println!("Verifying results in Kafka topic {}", topic);
@@ -125,7 +130,16 @@ impl Action for VerifyAction {
         let consumer: StreamConsumer = config
             .create()
             .map_err(|e| format!("creating kafka consumer: {}", e))?;
-        consumer.subscribe(&[&topic]).map_err(|e| e.to_string())?;
+        match self.partition {
+            // Read only the requested partition, from its beginning.
+            Some(partition) => {
+                let mut tpl = TopicPartitionList::new();
+                tpl.add_partition_offset(&topic, partition, Offset::Beginning)
+                    .map_err(|e| e.to_string())?;
+                consumer.assign(&tpl).map_err(|e| e.to_string())?;
+            }
+            None => consumer.subscribe(&[&topic]).map_err(|e| e.to_string())?,
+        }
 
         // Wait up to 10 seconds for each message.
         let message_stream = consumer
@@ -144,11 +158,13 @@ impl Action for VerifyAction {
         while let Some(Ok(message)) = message_stream.next().await {
             let message = message.map_err(|e| e.to_string())?;
 
-            let bytes = match message.payload() {
+            let value_datum = match message.payload() {
+                // A message without a payload is a tombstone, which upsert
+                // sinks produce for deleted keys.
+                None if key_schema.is_some() => None,
                 None => return Err("empty message payload".into()),
-                Some(bytes) => bytes,
+                Some(bytes) => Some(avro_from_bytes(value_schema, bytes)?),
             };
-            let value_datum = avro_from_bytes(value_schema, bytes)?;
             let key_datum = key_schema
                 .as_ref()
                 .map(|key_schema| {
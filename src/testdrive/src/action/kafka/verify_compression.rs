@@ -0,0 +1,147 @@
+// Copyright Materialize, Inc. All rights reserved.
+//
+// Use of this software is governed by the Business Source License
+// included in the LICENSE file.
+//
+// As of the Change Date specified in that file, in accordance with
+// the Business Source License, use of this software will be governed
+// by the Apache License, Version 2.0.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use rdkafka::consumer::{BaseConsumer, Consumer, ConsumerContext};
+use rdkafka::{ClientContext, Offset, Statistics, TopicPartitionList};
+
+use crate::action::{Action, State};
+use crate::parser::BuiltinCommand;
+
+/// Verifies that a sink's messages reach the broker compressed, by reading
+/// both its topic and the topic of an otherwise identical uncompressed sink,
+/// and comparing how many bytes the broker sent for each.
+pub struct VerifyCompressionAction {
+    sink: String,
+    uncompressed_sink: String,
+    count: usize,
+}
+
+pub fn build_verify_compression(
+    mut cmd: BuiltinCommand,
+) -> Result<VerifyCompressionAction, String> {
+    let sink = cmd.args.string("sink")?;
+    let uncompressed_sink = cmd.args.string("uncompressed-sink")?;
+    let count = cmd.args.parse("count")?;
+    cmd.args.done()?;
+    Ok(VerifyCompressionAction {
+        sink,
+        uncompressed_sink,
+        count,
+    })
+}
+
+#[async_trait]
+impl Action for VerifyCompressionAction {
+    async fn undo(&self, _state: &mut State) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn redo(&self, state: &mut State) -> Result<(), String> {
+        let compressed = fetched_bytes(state, &self.sink, self.count).await?;
+        let uncompressed = fetched_bytes(state, &self.uncompressed_sink, self.count).await?;
+        println!(
+            "Fetched {} bytes for sink {} and {} bytes for sink {}",
+            compressed, self.sink, uncompressed, self.uncompressed_sink
+        );
+        // The messages that tests use are highly compressible, so anything
+        // short of a large reduction means that the batches were not
+        // compressed.
+        if compressed * 2 >= uncompressed {
+            return Err(format!(
+                "sink {} fetched {} bytes, which is not less than half of the {} bytes that \
+                 uncompressed sink {} fetched",
+                self.sink, compressed, uncompressed, self.uncompressed_sink
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Records the bytes that a consumer has received from the brokers, as of its
+/// latest statistics.
+struct RxBytesContext(Arc<AtomicU64>);
+
+impl ClientContext for RxBytesContext {
+    fn stats(&self, statistics: Statistics) {
+        let rxbytes = statistics.brokers.values().map(|b| b.rxbytes).sum();
+        self.0.store(rxbytes, Ordering::SeqCst);
+    }
+}
+
+impl ConsumerContext for RxBytesContext {}
+
+/// Reads the first `count` messages of `sink`'s topic with a new consumer, and
+/// returns the number of bytes that the brokers sent it.
+async fn fetched_bytes(state: &State, sink: &str, count: usize) -> Result<u64, String> {
+    let row = state
+        .pgclient
+        .query_one(
+            "SELECT topic FROM mz_catalog_names JOIN mz_kafka_sinks ON global_id = sink_id WHERE name = $1",
+            &[&sink],
+        )
+        .await
+        .map_err(|e| format!("retrieving topic name: {}", e))?;
+    let topic: String = row.get("topic");
+
+    let rxbytes = Arc::new(AtomicU64::new(0));
+    let mut config = state.kafka_config.clone();
+    config.set("statistics.interval.ms", "100");
+    config.set("enable.auto.commit", "false");
+    let consumer: BaseConsumer<RxBytesContext> = config
+        .create_with_context(RxBytesContext(Arc::clone(&rxbytes)))
+        .map_err(|e| format!("creating kafka consumer: {}", e))?;
+
+    // Read every partition from its beginning, so that the consumer fetches
+    // each batch exactly once.
+    let metadata = consumer
+        .fetch_metadata(Some(&topic), Duration::from_secs(5))
+        .map_err(|e| format!("fetching metadata for topic {}: {}", topic, e))?;
+    let mut tpl = TopicPartitionList::new();
+    for partition in metadata
+        .topics()
+        .iter()
+        .flat_map(|t| t.partitions())
+        .map(|p| p.id())
+    {
+        tpl.add_partition_offset(&topic, partition, Offset::Beginning)
+            .map_err(|e| e.to_string())?;
+    }
+    consumer.assign(&tpl).map_err(|e| e.to_string())?;
+
+    let deadline = Instant::now() + Duration::from_secs(15);
+    let mut received = 0;
+    while received < count {
+        if Instant::now() > deadline {
+            return Err(format!(
+                "timed out after reading {} of {} messages from topic {}",
+                received, count, topic
+            ));
+        }
+        match consumer.poll(Duration::from_millis(100)) {
+            Some(Ok(_)) => received += 1,
+            Some(Err(e)) => return Err(format!("reading topic {}: {}", topic, e)),
+            None => (),
+        }
+    }
+
+    // Keep serving callbacks until statistics that postdate the last message
+    // have been reported.
+    let deadline = Instant::now() + Duration::from_secs(1);
+    while Instant::now() < deadline {
+        if let Some(Err(e)) = consumer.poll(Duration::from_millis(100)) {
+            return Err(format!("reading topic {}: {}", topic, e));
+        }
+    }
+    Ok(rxbytes.load(Ordering::SeqCst))
+}
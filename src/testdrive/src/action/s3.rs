@@ -8,13 +8,23 @@
 // by the Apache License, Version 2.0.
 
 use std::default::Default;
+use std::time::Duration;
 
+use async_compression::tokio::write::GzipEncoder;
 use async_trait::async_trait;
+use itertools::Itertools;
+use parquet::file::reader::{FileReader, SerializedFileReader};
+use parquet::util::cursor::SliceableCursor;
 use rusoto_core::RusotoError;
 use rusoto_s3::{
-    CreateBucketConfiguration, CreateBucketError, CreateBucketRequest, PutObjectRequest, S3,
+    CreateBucketConfiguration, CreateBucketError, CreateBucketRequest, GetObjectRequest,
+    ListObjectsV2Request, PutObjectRequest, S3,
 };
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
 
+use ore::retry;
+
+use crate::action::file::{build_compression, Compression};
 use crate::action::{Action, State};
 use crate::parser::BuiltinCommand;
 
@@ -60,6 +70,8 @@ impl Action for CreateBucketAction {
 pub struct PutObjectAction {
     bucket: String,
     key: String,
+    compression: Compression,
+    content_encoding: Option<String>,
     contents: String,
 }
 
@@ -67,6 +79,8 @@ pub fn build_put_object(mut cmd: BuiltinCommand) -> Result<PutObjectAction, Stri
     Ok(PutObjectAction {
         bucket: cmd.args.string("bucket")?,
         key: cmd.args.string("key")?,
+        compression: build_compression(&mut cmd)?,
+        content_encoding: cmd.args.opt_string("content-encoding"),
         contents: cmd.input.join("\n"),
     })
 }
@@ -80,12 +94,29 @@ impl Action for PutObjectAction {
     async fn redo(&self, state: &mut State) -> Result<(), String> {
         println!("Creating S3 Bucket {}", self.bucket);
 
+        let body = match self.compression {
+            Compression::Gzip => {
+                let mut encoder = GzipEncoder::new(Vec::new());
+                encoder
+                    .write_all(self.contents.as_bytes())
+                    .await
+                    .map_err(|e| format!("gzipping s3 object: {}", e))?;
+                encoder
+                    .shutdown()
+                    .await
+                    .map_err(|e| format!("gzipping s3 object: {}", e))?;
+                encoder.into_inner()
+            }
+            Compression::None => self.contents.clone().into_bytes(),
+        };
+
         state
             .s3_client
             .put_object(PutObjectRequest {
                 bucket: self.bucket.clone(),
-                body: Some(self.contents.clone().into_bytes().into()),
+                body: Some(body.into()),
                 key: self.key.clone(),
+                content_encoding: self.content_encoding.clone(),
                 ..Default::default()
             })
             .await
@@ -93,3 +124,119 @@ impl Action for PutObjectAction {
             .map_err(|e| format!("putting s3 object: {}", e))
     }
 }
+
+pub struct VerifyAction {
+    bucket: String,
+    prefix: String,
+    expected: Vec<String>,
+}
+
+pub fn build_verify(mut cmd: BuiltinCommand) -> Result<VerifyAction, String> {
+    let bucket = cmd.args.string("bucket")?;
+    let prefix = cmd.args.string("prefix")?;
+    cmd.args.done()?;
+    Ok(VerifyAction {
+        bucket,
+        prefix,
+        expected: cmd.input,
+    })
+}
+
+#[async_trait]
+impl Action for VerifyAction {
+    async fn undo(&self, _state: &mut State) -> Result<(), String> {
+        Ok(())
+    }
+
+    async fn redo(&self, state: &mut State) -> Result<(), String> {
+        println!(
+            "Verifying Parquet files in s3://{}/{}",
+            self.bucket, self.prefix
+        );
+
+        // Sinks upload files as their rows arrive, and the order of rows
+        // across files is not deterministic, so compare sorted rows until all
+        // of the expected rows have been uploaded.
+        let mut expected = self.expected.clone();
+        expected.sort();
+        retry::retry_for(Duration::from_secs(8), |_| async {
+            let mut actual = read_parquet_rows(state, &self.bucket, &self.prefix).await?;
+            actual.sort();
+            if actual == expected {
+                Ok(())
+            } else {
+                Err(format!(
+                    "rows did not match\nexpected:\n{}\nactual:\n{}",
+                    expected.join("\n"),
+                    actual.join("\n")
+                ))
+            }
+        })
+        .await
+    }
+}
+
+/// Reads the rows of every Parquet file under `prefix` in `bucket`, with the
+/// fields of each row separated by spaces.
+async fn read_parquet_rows(
+    state: &State,
+    bucket: &str,
+    prefix: &str,
+) -> Result<Vec<String>, String> {
+    let mut keys = vec![];
+    let mut continuation_token = None;
+    loop {
+        let res = state
+            .s3_client
+            .list_objects_v2(ListObjectsV2Request {
+                bucket: bucket.to_string(),
+                prefix: Some(prefix.to_string()),
+                continuation_token,
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| format!("listing s3 objects: {}", e))?;
+        keys.extend(
+            res.contents
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|o| o.key),
+        );
+        continuation_token = res.next_continuation_token;
+        if continuation_token.is_none() {
+            break;
+        }
+    }
+
+    let mut rows = vec![];
+    for key in keys {
+        let object = state
+            .s3_client
+            .get_object(GetObjectRequest {
+                bucket: bucket.to_string(),
+                key: key.clone(),
+                ..Default::default()
+            })
+            .await
+            .map_err(|e| format!("getting s3 object {}: {}", key, e))?;
+        let mut data = vec![];
+        object
+            .body
+            .ok_or_else(|| format!("s3 object {} has no body", key))?
+            .into_async_read()
+            .read_to_end(&mut data)
+            .await
+            .map_err(|e| format!("reading s3 object {}: {}", key, e))?;
+        let reader = SerializedFileReader::new(SliceableCursor::new(data))
+            .map_err(|e| format!("decoding parquet file {}: {}", key, e))?;
+        let iter = reader
+            .get_row_iter(None)
+            .map_err(|e| format!("decoding parquet file {}: {}", key, e))?;
+        rows.extend(iter.map(|row| {
+            row.get_column_iter()
+                .map(|(_, field)| field.to_string())
+                .join(" ")
+        }));
+    }
+    Ok(rows)
+}
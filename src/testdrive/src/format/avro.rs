@@ -224,11 +224,17 @@ pub fn from_json(json: &JsonValue, schema: SchemaNode) -> Result<Value, String>
     }
 }
 
+/// Validates that the `actual` key-value pairs read from a sink match the
+/// `expected` lines of JSON.
+///
+/// Each expected line holds a key, if the sink has a `key_schema`, followed by
+/// a value. A line with a key but no value denotes a tombstone, i.e. a message
+/// whose value is absent.
 pub fn validate_sink<I>(
     key_schema: Option<&Schema>,
     value_schema: &Schema,
     expected: I,
-    actual: &[(Option<Value>, Value)],
+    actual: &[(Option<Value>, Option<Value>)],
 ) -> Result<(), String>
 where
     I: IntoIterator,
@@ -247,11 +253,14 @@ where
             } else {
                 None
             };
-            let value: serde_json::Value = match deserializer.next() {
-                None => Err("value missing in input line".to_string()),
-                Some(r) => r.map_err(|e| format!("parsing json: {}", e)),
-            }?;
-            let value = from_json(&value, value_schema.top_node())?;
+            let value = match deserializer.next() {
+                None if key.is_some() => None,
+                None => return Err("value missing in input line".to_string()),
+                Some(r) => {
+                    let value: serde_json::Value = r.map_err(|e| format!("parsing json: {}", e))?;
+                    Some(from_json(&value, value_schema.top_node())?)
+                }
+            };
             Ok((key, value))
         })
         .collect::<Result<Vec<_>, String>>()?;
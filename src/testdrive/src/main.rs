@@ -69,6 +69,12 @@ struct Args {
     /// Emit Buildkite-specific markup.
     #[structopt(long)]
     ci_output: bool,
+    /// Use the specified seed rather than a random one.
+    ///
+    /// Scripts that run with the same seed refer to the same Kafka topics,
+    /// which lets a script pick up where an earlier script left off.
+    #[structopt(long, value_name = "N")]
+    seed: Option<u32>,
 
     // === Positional arguments. ===
     /// Paths to testdrive scripts to run.
@@ -153,6 +159,7 @@ async fn run(args: Args) -> Result<(), Error> {
         materialized_catalog_path: args.validate_catalog,
         reset_materialized: !args.no_reset,
         ci_output: args.ci_output,
+        seed: args.seed,
     };
 
     if args.files.is_empty() {
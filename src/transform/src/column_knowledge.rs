@@ -192,6 +192,7 @@ impl ColumnKnowledge {
                 aggregates,
                 monotonic: _,
                 expected_group_size: _,
+                hash_aggregate: _,
             } => {
                 let input_knowledge = ColumnKnowledge::harvest(input, knowledge)?;
                 let mut output = group_key
@@ -203,6 +203,7 @@ impl Demand {
                 aggregates,
                 monotonic: _,
                 expected_group_size: _,
+                hash_aggregate: _,
             } => {
                 let mut new_columns = HashSet::new();
                 // Group keys determine aggregation granularity and are
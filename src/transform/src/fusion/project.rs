@@ -55,6 +55,7 @@ impl Project {
             aggregates,
             monotonic: _,
             expected_group_size: _,
+            hash_aggregate: _,
         } = relation
         {
             if let MirRelationExpr::Project {
@@ -316,6 +316,7 @@ impl LiteralLifting {
                 aggregates,
                 monotonic: _,
                 expected_group_size: _,
+                hash_aggregate: _,
             } => {
                 let literals = self.action(input, gets);
                 if !literals.is_empty() {
@@ -357,7 +358,8 @@ impl LiteralLifting {
                     let temp = repr::RowArena::new();
                     let eval = aggr
                         .func
-                        .eval(Some(aggr.expr.eval(&[], &temp).unwrap()), &temp);
+                        .eval(Some(aggr.expr.eval(&[], &temp).unwrap()), &temp)
+                        .unwrap();
                     result.push(MirScalarExpr::literal_ok(
                         eval,
                         // This type information should be available in the `a.expr` literal,
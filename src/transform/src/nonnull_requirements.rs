@@ -193,6 +193,7 @@ impl NonNullRequirements {
                 aggregates,
                 monotonic: _,
                 expected_group_size: _,
+                hash_aggregate: _,
             } => {
                 let mut new_columns = HashSet::new();
                 for column in columns {
@@ -63,6 +63,7 @@ impl NonNullable {
                 aggregates,
                 monotonic: _,
                 expected_group_size: _,
+                hash_aggregate: _,
             } => {
                 if aggregates.iter().any(|a| {
                     scalar_contains_isnull(&(a).expr) || matches!(&(a).func, AggregateFunc::Count)
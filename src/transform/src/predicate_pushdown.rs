@@ -309,6 +309,7 @@ impl PredicatePushdown {
                         aggregates,
                         monotonic: _,
                         expected_group_size: _,
+                        hash_aggregate: _,
                     } => {
                         let mut retain = Vec::new();
                         let mut push_down = Vec::new();
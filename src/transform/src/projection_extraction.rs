@@ -66,6 +66,7 @@ impl ProjectionExtraction {
             aggregates,
             monotonic: _,
             expected_group_size: _,
+            hash_aggregate: _,
         } = relation
         {
             let mut projection = Vec::new();
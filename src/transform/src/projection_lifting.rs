@@ -199,6 +199,7 @@ impl ProjectionLifting {
                 aggregates,
                 monotonic: _,
                 expected_group_size: _,
+                hash_aggregate: _,
             } => {
                 // Reduce *absorbs* projections, which is amazing!
                 self.action(input, gets);
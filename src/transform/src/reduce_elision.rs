@@ -42,6 +42,7 @@ impl ReduceElision {
             aggregates,
             monotonic: _,
             expected_group_size: _,
+            hash_aggregate: _,
         } = relation
         {
             let input_type = input.typ();
@@ -49,8 +50,8 @@ impl ReduceElision {
                 keys.iter()
                     .all(|k| group_key.contains(&expr::MirScalarExpr::Column(*k)))
             }) {
-                use expr::{AggregateFunc, UnaryFunc, VariadicFunc};
-                use repr::Datum;
+                use expr::{AggregateFunc, BinaryFunc, UnaryFunc, VariadicFunc};
+                use repr::{Datum, ScalarType};
                 let map_scalars = aggregates
                     .iter()
                     .map(|a| match a.func {
@@ -82,6 +83,114 @@ impl ReduceElision {
                             exprs: vec![a.expr.clone()],
                         },
 
+                        // ArrayAgg takes a record whose first field is the
+                        // element, and outputs a one-element array, unless the
+                        // record was filtered out.
+                        AggregateFunc::ArrayAgg { .. } => {
+                            let column_type = a.typ(&input_type);
+                            let elem_type = match &column_type.scalar_type {
+                                ScalarType::Array(elem_type) => (**elem_type).clone(),
+                                _ => unreachable!("array_agg must output an array"),
+                            };
+                            a.expr.clone().call_unary(UnaryFunc::IsNull).if_then_else(
+                                MirScalarExpr::literal_null(column_type.scalar_type),
+                                MirScalarExpr::CallVariadic {
+                                    func: VariadicFunc::ArrayCreate { elem_type },
+                                    exprs: vec![a.expr.clone().call_unary(UnaryFunc::RecordGet(0))],
+                                },
+                            )
+                        }
+
+                        // StringAgg takes a record whose first field is itself
+                        // a record of the value and its delimiter, and outputs
+                        // the value alone.
+                        AggregateFunc::StringAgg { .. } => a
+                            .expr
+                            .clone()
+                            .call_unary(UnaryFunc::RecordGet(0))
+                            .call_unary(UnaryFunc::RecordGet(0)),
+
+                        // The percentiles take a record of a fraction and a
+                        // value, and output the value alone, unless the
+                        // fraction is null or out of range.
+                        AggregateFunc::PercentileCont { .. }
+                        | AggregateFunc::PercentileDisc { .. } => {
+                            let column_type = a.typ(&input_type);
+                            let fraction = a.expr.clone().call_unary(UnaryFunc::RecordGet(0));
+                            let in_range = fraction
+                                .clone()
+                                .call_binary(
+                                    MirScalarExpr::literal_ok(
+                                        Datum::from(0.0_f64),
+                                        ScalarType::Float64,
+                                    ),
+                                    BinaryFunc::Gte,
+                                )
+                                .call_binary(
+                                    fraction.call_binary(
+                                        MirScalarExpr::literal_ok(
+                                            Datum::from(1.0_f64),
+                                            ScalarType::Float64,
+                                        ),
+                                        BinaryFunc::Lte,
+                                    ),
+                                    BinaryFunc::And,
+                                );
+                            in_range.if_then_else(
+                                a.expr.clone().call_unary(UnaryFunc::RecordGet(1)),
+                                MirScalarExpr::literal_null(column_type.scalar_type),
+                            )
+                        }
+
+                        // LagLead takes a record of the row and the arguments of
+                        // each function, and outputs a one-element list of the
+                        // row and the results, since the row is the only one in
+                        // its partition: an offset of zero produces the value,
+                        // and any other offset the default.
+                        AggregateFunc::LagLead { ref funcs, .. } => {
+                            let column_type = a.typ(&input_type);
+                            let (elem_type, field_names) = match &column_type.scalar_type {
+                                ScalarType::List { element_type, .. } => match &**element_type {
+                                    ScalarType::Record { fields, .. } => (
+                                        (**element_type).clone(),
+                                        fields.iter().map(|(name, _)| name.clone()).collect(),
+                                    ),
+                                    _ => unreachable!("lag and lead must output a list of records"),
+                                },
+                                _ => unreachable!("lag and lead must output a list of records"),
+                            };
+                            let mut exprs =
+                                vec![a.expr.clone().call_unary(UnaryFunc::RecordGet(0))];
+                            for i in 0..funcs.len() {
+                                let args = a.expr.clone().call_unary(UnaryFunc::RecordGet(1 + i));
+                                let offset = args.clone().call_unary(UnaryFunc::RecordGet(1));
+                                let value = args.clone().call_unary(UnaryFunc::RecordGet(0));
+                                let default = args.call_unary(UnaryFunc::RecordGet(2));
+                                let value_type = value.typ(&input_type).scalar_type;
+                                exprs.push(
+                                    offset.clone().call_unary(UnaryFunc::IsNull).if_then_else(
+                                        MirScalarExpr::literal_null(value_type),
+                                        offset
+                                            .call_binary(
+                                                MirScalarExpr::literal_ok(
+                                                    Datum::Int32(0),
+                                                    ScalarType::Int32,
+                                                ),
+                                                BinaryFunc::Eq,
+                                            )
+                                            .if_then_else(value, default),
+                                    ),
+                                );
+                            }
+                            MirScalarExpr::CallVariadic {
+                                func: VariadicFunc::ListCreate { elem_type },
+                                exprs: vec![MirScalarExpr::CallVariadic {
+                                    func: VariadicFunc::RecordCreate { field_names },
+                                    exprs,
+                                }],
+                            }
+                        }
+
                         // All other variants should return the argument to the aggregation.
                         _ => a.expr.clone(),
                     })
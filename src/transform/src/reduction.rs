@@ -49,6 +49,7 @@ impl FoldConstants {
                 aggregates,
                 monotonic: _,
                 expected_group_size: _,
+                hash_aggregate: _,
             } => {
                 let input_typ = input.typ();
                 // Reduce expressions to their simplest form.
@@ -59,9 +60,12 @@ impl FoldConstants {
                     aggregate.expr.reduce(&input_typ);
                 }
 
-                // Guard against evaluating expression that may contain temporal expressions.
-                if group_key.iter().any(|e| e.contains_temporal())
-                    || aggregates.iter().any(|a| a.expr.contains_temporal())
+                // Guard against evaluating expressions that may contain temporal or
+                // otherwise non-deterministic expressions.
+                if group_key.iter().any(|e| e.contains_nondeterministic())
+                    || aggregates
+                        .iter()
+                        .any(|a| a.expr.contains_nondeterministic())
                 {
                     return Ok(());
                 }
@@ -115,8 +119,9 @@ impl FoldConstants {
                     scalar.reduce(&current_type);
                 }
 
-                // Guard against evaluating expression that may contain temporal expressions.
-                if scalars.iter().any(|e| e.contains_temporal()) {
+                // Guard against evaluating expressions that may contain temporal or
+                // otherwise non-deterministic expressions.
+                if scalars.iter().any(|e| e.contains_nondeterministic()) {
                     return Ok(());
                 }
 
@@ -153,8 +158,9 @@ impl FoldConstants {
                     expr.reduce(&input.typ());
                 }
 
-                // Guard against evaluating expression that may contain temporal expressions.
-                if exprs.iter().any(|e| e.contains_temporal()) {
+                // Guard against evaluating expressions that may contain temporal or
+                // otherwise non-deterministic expressions.
+                if exprs.iter().any(|e| e.contains_nondeterministic()) {
                     return Ok(());
                 }
 
@@ -175,8 +181,9 @@ impl FoldConstants {
                 }
                 predicates.retain(|p| !p.is_literal_true());
 
-                // Guard against evaluating expression that may contain temporal expressions.
-                if predicates.iter().any(|e| e.contains_temporal()) {
+                // Guard against evaluating expressions that may contain temporal or
+                // otherwise non-deterministic expressions.
+                if predicates.iter().any(|e| e.contains_nondeterministic()) {
                     return Ok(());
                 }
 
@@ -235,10 +242,11 @@ impl FoldConstants {
                     .iter()
                     .all(|i| matches!(i, MirRelationExpr::Constant { rows: Ok(_), .. }))
                 {
-                    // Guard against evaluating expression that may contain temporal expressions.
+                    // Guard against evaluating expressions that may contain temporal or
+                    // otherwise non-deterministic expressions.
                     if equivalences
                         .iter()
-                        .any(|equiv| equiv.iter().any(|e| e.contains_temporal()))
+                        .any(|equiv| equiv.iter().any(|e| e.contains_nondeterministic()))
                     {
                         return Ok(());
                     }
@@ -410,8 +418,10 @@ impl FoldConstants {
                 let mut row_packer = repr::RowPacker::new();
                 move |(key, vals)| {
                     let temp_storage = RowArena::new();
-                    let row = row_packer.pack(key.into_iter().chain(
-                        aggregates.iter().enumerate().map(|(i, agg)| {
+                    let aggs = aggregates
+                        .iter()
+                        .enumerate()
+                        .map(|(i, agg)| {
                             if agg.distinct {
                                 agg.func.eval(
                                     vals.iter()
@@ -426,12 +436,13 @@ impl FoldConstants {
                                     &temp_storage,
                                 )
                             }
-                        }),
-                    ));
-                    (row, 1)
+                        })
+                        .collect::<Result<Vec<_>, _>>()?;
+                    let row = row_packer.pack(key.into_iter().chain(aggs));
+                    Ok((row, 1))
                 }
             })
-            .collect();
+            .collect::<Result<_, EvalError>>()?;
         Ok(new_rows)
     }
 
@@ -451,7 +462,7 @@ impl FoldConstants {
                     .map(|expr| expr.eval(&datums, &temp_storage))
                     .collect::<Result<Vec<_>, _>>()?,
                 &temp_storage,
-            );
+            )?;
             for (output_row, diff2) in output_rows {
                 let row =
                     row_packer.pack(input_row.clone().into_iter().chain(output_row.into_iter()));
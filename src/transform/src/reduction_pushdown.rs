@@ -40,6 +40,7 @@ impl ReductionPushdown {
             aggregates,
             monotonic: _,
             expected_group_size: _,
+            hash_aggregate: _,
         } = relation
         {
             // Map expressions can be absorbed into the Reduce at no cost.
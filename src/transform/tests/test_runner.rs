@@ -507,6 +507,9 @@ mod tests {
             if format.contains(&"types".to_string()) {
                 explanation.explain_types();
             }
+            if format.contains(&"memory".to_string()) {
+                explanation.explain_memory();
+            }
         }
         explanation.to_string()
     }